@@ -0,0 +1,413 @@
+// APEv1 and APEv2 tags
+//
+// An APE tag consists of an optional 32-byte header, a list of key/value items and a 32-byte
+// footer, both starting with "APETAGEX". It is usually appended to MP3, Monkey's Audio, WavPack
+// and Musepack files (in front of an ID3v1 tag, before or after a Lyrics3 tag), rarely placed
+// at the start of a file. Item values are UTF-8 text (multiple values separated by zero bytes),
+// binary data such as cover art, or external locators (URLs).
+
+use std::io::{SeekFrom, Write};
+
+use crate::{
+    color::Colorize,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    id3v1::Id3v1Tag,
+    lyrics3::Lyrics3Tag,
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Size of the APE tag header and footer
+pub const APE_FOOTER_SIZE: u64 = 32;
+
+/// Largest tag accepted, protects against corrupt size fields
+const MAX_TAG_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Longest text value shown in the text output
+const MAX_DISPLAY_LENGTH: usize = 80;
+
+/// Tag flag: the tag contains a header
+const FLAG_HAS_HEADER: u32 = 1 << 31;
+/// Tag flag: this header/footer is the header
+const FLAG_IS_HEADER: u32 = 1 << 29;
+/// Item/tag flag: read only
+const FLAG_READ_ONLY: u32 = 1;
+
+/// Value type of an APE item (bits 1-2 of the item flags)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApeItemType
+{
+    /// UTF-8 text, multiple values separated by zero bytes
+    Text,
+    /// Binary data (e.g. cover art)
+    Binary,
+    /// UTF-8 link to external data
+    Locator,
+    Reserved
+}
+
+/// Key/value item of an APE tag
+#[derive(Debug, Clone)]
+pub struct ApeItem
+{
+    /// File offset of the item
+    pub offset:    u64,
+    pub key:       String,
+    pub item_type: ApeItemType,
+    pub read_only: bool,
+    pub value:     Vec<u8>
+}
+
+impl ApeItem
+{
+    /// Text values of a text or locator item
+    pub fn text_values(&self) -> Vec<String>
+    {
+        String::from_utf8_lossy(&self.value).split('\0').map(str::to_string).collect()
+    }
+
+    /// File name and image data of a binary cover art item ("Cover Art (Front)" etc.)
+    pub fn cover_art(&self) -> Option<(String, &[u8])>
+    {
+        if self.item_type != ApeItemType::Binary || self.key.starts_with("Cover Art") == false
+        {
+            return None;
+        }
+        let separator = self.value.iter().position(|&byte| byte == 0)?;
+        Some((String::from_utf8_lossy(&self.value[..separator]).to_string(), &self.value[separator + 1..]))
+    }
+
+    /// Value type name
+    pub fn type_name(&self) -> &'static str
+    {
+        match self.item_type
+        {
+            | ApeItemType::Text => "text",
+            | ApeItemType::Binary => "binary",
+            | ApeItemType::Locator => "locator",
+            | ApeItemType::Reserved => "reserved"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let mut value = serde_json::json!({
+            "offset": self.offset,
+            "key": self.key,
+            "type": self.type_name(),
+            "read_only": self.read_only,
+            "size": self.value.len()
+        });
+        match self.item_type
+        {
+            | ApeItemType::Text | ApeItemType::Locator => value["values"] = serde_json::json!(self.text_values()),
+            | _ =>
+            {
+                if let Some((file_name, image)) = self.cover_art()
+                {
+                    value["cover_art"] = serde_json::json!({ "file_name": file_name, "format": image_format(image), "size": image.len() });
+                }
+            }
+        }
+        value
+    }
+}
+
+/// Describe the format of embedded image data from its signature
+fn image_format(data: &[u8]) -> &'static str
+{
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) == true
+    {
+        "JPEG"
+    }
+    else if data.starts_with(&[0x89, b'P', b'N', b'G']) == true
+    {
+        "PNG"
+    }
+    else if data.starts_with(b"GIF8") == true
+    {
+        "GIF"
+    }
+    else
+    {
+        "unknown"
+    }
+}
+
+/// Fields of an APE tag header or footer
+struct ApeTagFooter
+{
+    version:    u32,
+    /// Size of the items and the footer, excluding the header
+    tag_size:   u32,
+    item_count: u32,
+    flags:      u32
+}
+
+impl ApeTagFooter
+{
+    /// Parse a 32-byte header/footer, returning None if the "APETAGEX" preamble is missing
+    fn parse(data: &[u8]) -> Option<Self>
+    {
+        if data.len() < APE_FOOTER_SIZE as usize || &data[0..8] != b"APETAGEX"
+        {
+            return None;
+        }
+        let field = |position: usize| u32::from_le_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]);
+
+        Some(Self { version: field(8), tag_size: field(12), item_count: field(16), flags: field(20) })
+    }
+}
+
+/// Parsed APE tag
+#[derive(Debug, Clone)]
+pub struct ApeTag
+{
+    /// File offset of the tag (header if present, first item otherwise)
+    pub offset:     u64,
+    /// Total size including header and footer
+    pub size:       u64,
+    /// Version number (1000 = APEv1, 2000 = APEv2)
+    pub version:    u32,
+    pub has_header: bool,
+    pub read_only:  bool,
+    /// Item count declared in the footer
+    pub item_count: u32,
+    pub items:      Vec<ApeItem>
+}
+
+impl ApeTag
+{
+    /// Read an APE tag at the end of the file (in front of an ID3v1 tag and a Lyrics3 tag) or at its start
+    pub fn read(file: &mut dyn MediaSource) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+
+        // Footer directly before the end of the file, before an ID3v1 tag or before a Lyrics3 tag in front of it
+        let mut data_end = match Id3v1Tag::read(file)?
+        {
+            | Some(id3v1) => id3v1.offset,
+            | None => file_size
+        };
+        if data_end < file_size &&
+            Self::read_footer(file, data_end.saturating_sub(APE_FOOTER_SIZE))?.is_none() == true &&
+            let Some(lyrics3) = Lyrics3Tag::read_before(file, data_end)?
+        {
+            data_end = lyrics3.offset;
+        }
+
+        if data_end >= APE_FOOTER_SIZE &&
+            let Some(footer) = Self::read_footer(file, data_end - APE_FOOTER_SIZE)? &&
+            footer.flags & FLAG_IS_HEADER == 0
+        {
+            let header_size = if footer.flags & FLAG_HAS_HEADER != 0
+            {
+                APE_FOOTER_SIZE
+            }
+            else
+            {
+                0
+            };
+            let tag_size = footer.tag_size as u64;
+            if tag_size < APE_FOOTER_SIZE || tag_size + header_size > data_end || tag_size > MAX_TAG_SIZE
+            {
+                return Ok(None);
+            }
+            let offset = data_end - tag_size - header_size;
+            return Ok(Some(Self::read_items(file, offset, offset + header_size, data_end - APE_FOOTER_SIZE, header_size + tag_size, &footer)?));
+        }
+
+        // Header at the start of the file
+        if let Some(header) = Self::read_footer(file, 0)? &&
+            header.flags & FLAG_IS_HEADER != 0
+        {
+            let tag_size = header.tag_size as u64;
+            if tag_size < APE_FOOTER_SIZE || APE_FOOTER_SIZE + tag_size > file_size || tag_size > MAX_TAG_SIZE
+            {
+                return Ok(None);
+            }
+            return Ok(Some(Self::read_items(file, 0, APE_FOOTER_SIZE, tag_size, APE_FOOTER_SIZE + tag_size, &header)?));
+        }
+
+        Ok(None)
+    }
+
+    /// Read a header/footer at `offset`
+    fn read_footer(file: &mut dyn MediaSource, offset: u64) -> Result<Option<ApeTagFooter>, Box<dyn std::error::Error>>
+    {
+        let mut data = [0u8; APE_FOOTER_SIZE as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        if file.read_exact(&mut data).is_err()
+        {
+            return Ok(None);
+        }
+        Ok(ApeTagFooter::parse(&data))
+    }
+
+    /// Read the items between `items_start` and `items_end` of a tag of `size` bytes at `offset`
+    ///
+    /// Parsing stops at the first malformed item.
+    fn read_items(
+        file: &mut dyn MediaSource, offset: u64, items_start: u64, items_end: u64, size: u64, footer: &ApeTagFooter
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let mut data = vec![0u8; (items_end - items_start) as usize];
+        file.seek(SeekFrom::Start(items_start))?;
+        file.read_exact(&mut data)?;
+
+        let mut items = Vec::new();
+        let mut position = 0;
+        while items.len() < footer.item_count as usize && position + 8 < data.len()
+        {
+            let value_size = u32::from_le_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]) as usize;
+            let flags = u32::from_le_bytes([data[position + 4], data[position + 5], data[position + 6], data[position + 7]]);
+
+            // Key: printable ASCII terminated by a zero byte
+            let key_start = position + 8;
+            let Some(key_length) = data[key_start..].iter().position(|&byte| byte == 0)
+            else
+            {
+                break;
+            };
+            let key = &data[key_start..key_start + key_length];
+            let value_start = key_start + key_length + 1;
+            if key.is_empty() == true || key.iter().all(|&byte| (0x20..=0x7E).contains(&byte)) == false || value_start + value_size > data.len()
+            {
+                break;
+            }
+
+            items.push(ApeItem {
+                offset:    items_start + position as u64,
+                key:       String::from_utf8_lossy(key).to_string(),
+                item_type: match (flags >> 1) & 0x03
+                {
+                    | 0 => ApeItemType::Text,
+                    | 1 => ApeItemType::Binary,
+                    | 2 => ApeItemType::Locator,
+                    | _ => ApeItemType::Reserved
+                },
+                read_only: flags & FLAG_READ_ONLY != 0,
+                value:     data[value_start..value_start + value_size].to_vec()
+            });
+            position = value_start + value_size;
+        }
+
+        Ok(Self {
+            offset,
+            size,
+            version: footer.version,
+            has_header: items_start > offset,
+            read_only: footer.flags & FLAG_READ_ONLY != 0,
+            item_count: footer.item_count,
+            items
+        })
+    }
+
+    /// Version label (APEv1 or APEv2)
+    pub fn version_name(&self) -> &'static str
+    {
+        if self.version >= 2000
+        {
+            "APEv2"
+        }
+        else
+        {
+            "APEv1"
+        }
+    }
+
+    /// Report items the tag declares but that could not be parsed
+    pub fn check_item_count(&self, diagnostics: &mut Diagnostics)
+    {
+        if self.items.len() != self.item_count as usize
+        {
+            diagnostics.warning(Some(self.offset), tr_fmt("APE tag declares {} items, but only {} could be parsed", &[&self.item_count, &self.items.len()]));
+        }
+    }
+
+    /// Structured representation of this tag for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version_name(),
+            "offset": self.offset,
+            "size": self.size,
+            "has_header": self.has_header,
+            "read_only": self.read_only,
+            "item_count": self.item_count,
+            "items": self.items.iter().map(ApeItem::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Shorten long text values for display
+fn truncate_value(value: &str) -> String
+{
+    if value.chars().count() > MAX_DISPLAY_LENGTH
+    {
+        format!("{}...", value.chars().take(MAX_DISPLAY_LENGTH).collect::<String>())
+    }
+    else
+    {
+        value.to_string()
+    }
+}
+
+/// Display the APE tag of the file, if present
+pub fn dissect_ape_tag(
+    file: &mut dyn MediaSource, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    let Some(tag) = ApeTag::read(file)?
+    else
+    {
+        return Ok(());
+    };
+
+    tag.check_item_count(diagnostics);
+
+    if options.show_header == false && options.show_data == false
+    {
+        return Ok(());
+    }
+
+    writeln!(output, "\n{}", tr_fmt("{} Tag Found at offset {}:", &[&tag.version_name(), &format!("0x{:08X}", tag.offset)]).bright_cyan().bold())?;
+    writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&tag.size]))?;
+    if tag.has_header == false
+    {
+        writeln!(output, "  {}", tr("No tag header (footer only)"))?;
+    }
+    if tag.read_only == true
+    {
+        writeln!(output, "  {}", tr("Read-only"))?;
+    }
+    writeln!(output, "  {}", tr_fmt("Items: {}", &[&tag.items.len()]))?;
+
+    for item in &tag.items
+    {
+        let value = match item.item_type
+        {
+            | ApeItemType::Text => item.text_values().iter().map(|value| truncate_value(value)).collect::<Vec<_>>().join(" / "),
+            | ApeItemType::Locator => tr_fmt("{} (external locator)", &[&truncate_value(&item.text_values().join(" / "))]),
+            | _ => match item.cover_art()
+            {
+                | Some((file_name, image)) => tr_fmt("{} image, {} bytes ({})", &[&image_format(image), &image.len(), &file_name]),
+                | None => tr_fmt("binary data, {} bytes", &[&item.value.len()])
+            }
+        };
+        writeln!(output, "    {}: {}", item.key, value)?;
+
+        if options.show_dump == true && item.item_type == ApeItemType::Binary
+        {
+            let hexdump = crate::hexdump::format_hexdump_limited(&item.value, 0, options.dump_limit.bytes());
+            for line in hexdump.lines()
+            {
+                writeln!(output, "      {}", line)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Embedded artwork
+//
+// Pictures stored in ID3v2 APIC frames (including those embedded in CHAP frames) and in
+// iTunes `covr` boxes, collected layer by layer so they can be written to image files.
+
+use std::{
+    fs,
+    path::{Path, PathBuf}
+};
+
+/// Picture embedded in a tag or metadata box
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artwork
+{
+    /// Where the picture was found (e.g. "APIC", "CHAP ch1/APIC", "moov/udta/meta/ilst/covr")
+    pub location:    String,
+    /// MIME type from the APIC frame or derived from the iTunes data type
+    pub mime_type:   String,
+    /// Picture type and description, e.g. "Cover (front)"
+    pub description: String,
+    pub data:        Vec<u8>
+}
+
+impl Artwork
+{
+    /// File extension for the picture: from the MIME type, or sniffed from the data if the type is unknown
+    pub fn extension(&self) -> &'static str
+    {
+        picture_extension(&self.mime_type, &self.data)
+    }
+}
+
+/// File extension for a picture of `mime_type`, sniffed from `data` if the type is unknown
+pub fn picture_extension(mime_type: &str, data: &[u8]) -> &'static str
+{
+    let from_mime_type = match mime_type.to_ascii_lowercase().as_str()
+    {
+        // ID3v2.2 stores a three-character image format instead of a MIME type
+        | "image/jpeg" | "image/jpg" | "jpg" => Some("jpg"),
+        | "image/png" | "png" => Some("png"),
+        | "image/gif" | "gif" => Some("gif"),
+        | "image/bmp" | "bmp" => Some("bmp"),
+        | "image/webp" => Some("webp"),
+        | "image/tiff" => Some("tif"),
+        | _ => None
+    };
+
+    from_mime_type.unwrap_or_else(|| sniff_extension(data))
+}
+
+/// Guess the image format from the leading bytes
+fn sniff_extension(data: &[u8]) -> &'static str
+{
+    match data
+    {
+        | [0xFF, 0xD8, 0xFF, ..] => "jpg",
+        | [0x89, b'P', b'N', b'G', ..] => "png",
+        | [b'G', b'I', b'F', b'8', ..] => "gif",
+        | [b'B', b'M', ..] => "bmp",
+        | [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "webp",
+        | [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => "tif",
+        | _ => "bin"
+    }
+}
+
+/// MIME type of an iTunes `data` box type (JPEG 13, PNG 14, GIF 12, BMP 27)
+pub fn itunes_mime_type(data_type: u8) -> &'static str
+{
+    match data_type
+    {
+        | 12 => "image/gif",
+        | 13 => "image/jpeg",
+        | 14 => "image/png",
+        | 27 => "image/bmp",
+        | _ => "application/octet-stream"
+    }
+}
+
+/// Write every picture to `directory` as `<stem>_<n>.<extension>`, returning the created paths
+pub fn extract(artwork: &[Artwork], directory: &Path, stem: &str) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>>
+{
+    fs::create_dir_all(directory)?;
+
+    let mut paths = Vec::new();
+    for (index, picture) in artwork.iter().enumerate()
+    {
+        let path = directory.join(format!("{}_{:02}.{}", stem, index + 1, picture.extension()));
+        fs::write(&path, &picture.data)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
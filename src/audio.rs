@@ -0,0 +1,30 @@
+// Audio stream dissection
+//
+// This module provides support for raw audio streams that are stored without a container: WavPack
+// (blocks starting with "wvpk"), Musepack (SV7 "MP+" header and SV8 "MPCK" packets), True Audio
+// ("TTA1" header with seek table), AMR speech ("#!AMR" magic number followed by frames) and AAC
+// in ADTS frames. The APEv2 and ID3v1 tags at the end of all but AMR are reported with the audio
+// stream; an ID3v2 tag in front of an ADTS stream is a layer of its own.
+
+// Core types and dissectors
+pub mod adts;
+pub mod amr;
+pub mod dissector;
+pub mod musepack;
+pub mod tta;
+pub mod wavpack;
+
+// Re-export commonly used types for convenience
+pub use dissector::{AdtsDissector, AmrDissector, MusepackDissector, TtaDissector, WavPackDissector};
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the WavPack, Musepack, True Audio, AMR and ADTS dissectors with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<WavPackDissector>(500);
+    registry.register_dissector::<MusepackDissector>(400);
+    registry.register_dissector::<TtaDissector>(300);
+    registry.register_dissector::<AmrDissector>(200);
+    registry.register_dissector::<AdtsDissector>(100);
+}
@@ -0,0 +1,148 @@
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::boxes::esds::SAMPLING_FREQUENCIES,
+    media_dissector::Confidence
+};
+
+/// Size of an ADTS header without CRC (the CRC adds 2 bytes)
+pub const HEADER_SIZE: usize = 7;
+
+/// Size of an ADTS header with CRC
+pub const MAX_HEADER_SIZE: usize = 9;
+
+/// Samples per channel of one raw data block
+pub const SAMPLES_PER_BLOCK: u64 = 1024;
+
+/// Consecutive frames a stream must start with to be rated `Confidence::High`
+const SNIFF_FRAMES: usize = 4;
+
+/// Header of an ADTS frame (Audio Data Transport Stream, ISO/IEC 13818-7 and 14496-3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdtsHeader
+{
+    /// MPEG-2 AAC (ID bit set) rather than MPEG-4 AAC
+    pub mpeg2:                    bool,
+    /// A CRC follows the header
+    pub crc:                      bool,
+    /// Audio object type minus one (0 = Main, 1 = LC, 2 = SSR, 3 = LTP)
+    pub profile:                  u8,
+    pub sampling_frequency_index: u8,
+    /// Channel configuration (0 = defined in the program config element, 1-7 = fixed layouts)
+    pub channel_configuration:    u8,
+    /// Size of the frame including its header
+    pub frame_length:             usize,
+    /// Raw data blocks in the frame minus one
+    pub raw_data_blocks:          u8
+}
+
+impl AdtsHeader
+{
+    /// Parse the header at the start of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        // Syncword and layer 0, which MPEG audio reserves
+        if data.len() < HEADER_SIZE || data[0] != 0xFF || data[1] & 0xF6 != 0xF0
+        {
+            return Err(tr("Missing ADTS syncword").to_string());
+        }
+
+        let header = AdtsHeader {
+            mpeg2:                    data[1] & 0x08 != 0,
+            crc:                      data[1] & 0x01 == 0,
+            profile:                  data[2] >> 6,
+            sampling_frequency_index: (data[2] >> 2) & 0x0F,
+            channel_configuration:    ((data[2] & 0x01) << 2) | (data[3] >> 6),
+            frame_length:             ((data[3] as usize & 0x03) << 11) | ((data[4] as usize) << 3) | (data[5] as usize >> 5),
+            raw_data_blocks:          data[6] & 0x03
+        };
+        if header.sampling_frequency_index as usize >= SAMPLING_FREQUENCIES.len()
+        {
+            return Err(tr_fmt("Invalid ADTS sampling frequency index {}", &[&header.sampling_frequency_index]));
+        }
+        if header.frame_length < header.header_size()
+        {
+            return Err(tr_fmt("ADTS frame length {} is shorter than its header", &[&header.frame_length]));
+        }
+        Ok(header)
+    }
+
+    /// Size of the header including the CRC
+    pub fn header_size(&self) -> usize
+    {
+        match self.crc
+        {
+            | true => MAX_HEADER_SIZE,
+            | false => HEADER_SIZE
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32
+    {
+        SAMPLING_FREQUENCIES[self.sampling_frequency_index as usize]
+    }
+
+    /// Channel count of a fixed channel configuration
+    pub fn channels(&self) -> Option<u16>
+    {
+        match self.channel_configuration
+        {
+            | 1..=6 => Some(self.channel_configuration as u16),
+            | 7 => Some(8),
+            | _ => None
+        }
+    }
+
+    /// Samples per channel in the frame
+    pub fn samples(&self) -> u64
+    {
+        (self.raw_data_blocks as u64 + 1) * SAMPLES_PER_BLOCK
+    }
+
+    /// Profile name (MPEG-2 AAC has no LTP profile)
+    pub fn profile_name(&self) -> &'static str
+    {
+        match (self.profile, self.mpeg2)
+        {
+            | (0, _) => "AAC Main",
+            | (1, _) => "AAC LC",
+            | (2, _) => "AAC SSR",
+            | (_, false) => "AAC LTP",
+            | (_, true) => "reserved"
+        }
+    }
+
+    /// Whether `other` continues the stream of this frame (same version, profile, sampling frequency and channels)
+    pub fn same_stream(&self, other: &AdtsHeader) -> bool
+    {
+        (self.mpeg2, self.profile, self.sampling_frequency_index, self.channel_configuration) ==
+            (other.mpeg2, other.profile, other.sampling_frequency_index, other.channel_configuration)
+    }
+}
+
+/// Rate how likely `data` starts an ADTS stream: the more frames of the same stream follow each other, the less
+/// likely the syncword occurs by chance
+pub fn sniff(data: &[u8]) -> Confidence
+{
+    let Ok(first) = AdtsHeader::parse(data)
+    else
+    {
+        return Confidence::None;
+    };
+
+    let mut frames = 1;
+    let mut offset = first.frame_length;
+    while frames < SNIFF_FRAMES &&
+        let Some(Ok(next)) = data.get(offset..).map(AdtsHeader::parse) &&
+        next.same_stream(&first) == true
+    {
+        frames += 1;
+        offset += next.frame_length;
+    }
+
+    match frames
+    {
+        | 1 => Confidence::Low,
+        | 2 | 3 => Confidence::Medium,
+        | _ => Confidence::High
+    }
+}
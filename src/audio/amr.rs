@@ -0,0 +1,158 @@
+/// Duration of one AMR frame in seconds (160 samples at 8 kHz, 320 samples at 16 kHz)
+use crate::i18n::{tr, tr_fmt};
+
+pub const FRAME_DURATION: f64 = 0.02;
+
+/// Bytes of speech data per frame type of AMR-NB (RFC 4867 storage format, without the frame header)
+const NARROWBAND_FRAME_SIZES: [usize; 16] = [12, 13, 15, 17, 19, 20, 26, 31, 5, 6, 5, 5, 0, 0, 0, 0];
+
+/// Bytes of speech data per frame type of AMR-WB
+const WIDEBAND_FRAME_SIZES: [usize; 16] = [17, 23, 32, 36, 40, 46, 50, 58, 60, 5, 0, 0, 0, 0, 0, 0];
+
+/// Magic number of an AMR file ("#!AMR\n", "#!AMR-WB\n" or their multichannel variants)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmrHeader
+{
+    pub wideband: bool,
+    /// Channels of the multichannel variants, 1 for single channel files
+    pub channels: u8,
+    /// Size of the magic number including the channel description
+    pub size:     usize
+}
+
+impl AmrHeader
+{
+    /// Parse the magic number at the start of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let (wideband, size, multichannel) = if data.starts_with(b"#!AMR\n") == true
+        {
+            (false, 6, false)
+        }
+        else if data.starts_with(b"#!AMR-WB\n") == true
+        {
+            (true, 9, false)
+        }
+        else if data.starts_with(b"#!AMR_MC1.0\n") == true
+        {
+            (false, 12, true)
+        }
+        else if data.starts_with(b"#!AMR-WB_MC1.0\n") == true
+        {
+            (true, 15, true)
+        }
+        else
+        {
+            return Err(tr("Missing AMR magic number").to_string());
+        };
+
+        if multichannel == false
+        {
+            return Ok(AmrHeader { wideband, channels: 1, size });
+        }
+
+        // 28 reserved bits and the channel count in the low 4 bits
+        let description = data.get(size..size + 4).ok_or(tr("AMR channel description truncated"))?;
+        let channels = description[3] & 0x0F;
+        if channels == 0
+        {
+            return Err(tr("AMR channel description without channels").to_string());
+        }
+        Ok(AmrHeader { wideband, channels, size: size + 4 })
+    }
+
+    pub fn sample_rate(&self) -> u32
+    {
+        match self.wideband
+        {
+            | true => 16000,
+            | false => 8000
+        }
+    }
+
+    /// Codec name (AMR-NB or AMR-WB)
+    pub fn codec_name(&self) -> &'static str
+    {
+        match self.wideband
+        {
+            | true => "AMR-WB",
+            | false => "AMR-NB"
+        }
+    }
+}
+
+/// Frame header byte: padding bit, 4-bit frame type, quality bit and two padding bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmrFrameHeader
+{
+    pub frame_type: u8,
+    /// Cleared for frames damaged in transmission
+    pub quality:    bool
+}
+
+impl AmrFrameHeader
+{
+    /// Parse a frame header byte; set padding bits mean the data is not an AMR frame
+    pub fn parse(byte: u8) -> Result<Self, String>
+    {
+        if byte & 0x83 != 0
+        {
+            return Err(tr_fmt("Invalid AMR frame header {} (padding bits set)", &[&format!("0x{:02X}", byte)]));
+        }
+        Ok(AmrFrameHeader { frame_type: (byte >> 3) & 0x0F, quality: byte & 0x04 != 0 })
+    }
+}
+
+/// Size of a frame including its header byte
+pub fn frame_size(wideband: bool, frame_type: u8) -> usize
+{
+    let sizes = match wideband
+    {
+        | true => &WIDEBAND_FRAME_SIZES,
+        | false => &NARROWBAND_FRAME_SIZES
+    };
+    sizes[(frame_type & 0x0F) as usize] + 1
+}
+
+/// Whether a frame type is reserved for future use
+pub fn is_reserved_frame_type(wideband: bool, frame_type: u8) -> bool
+{
+    match wideband
+    {
+        | true => (10..=13).contains(&frame_type),
+        | false => (12..=14).contains(&frame_type)
+    }
+}
+
+/// Describe a frame type (codec mode bit rate, comfort noise or no data)
+pub fn frame_type_name(wideband: bool, frame_type: u8) -> &'static str
+{
+    match (wideband, frame_type)
+    {
+        | (false, 0) => "4.75 kbit/s",
+        | (false, 1) => "5.15 kbit/s",
+        | (false, 2) => "5.90 kbit/s",
+        | (false, 3) => "6.70 kbit/s",
+        | (false, 4) => "7.40 kbit/s",
+        | (false, 5) => "7.95 kbit/s",
+        | (false, 6) => "10.2 kbit/s",
+        | (false, 7) => "12.2 kbit/s",
+        | (false, 8) => "SID (comfort noise)",
+        | (false, 9) => "GSM-EFR SID",
+        | (false, 10) => "TDMA-EFR SID",
+        | (false, 11) => "PDC-EFR SID",
+        | (true, 0) => "6.60 kbit/s",
+        | (true, 1) => "8.85 kbit/s",
+        | (true, 2) => "12.65 kbit/s",
+        | (true, 3) => "14.25 kbit/s",
+        | (true, 4) => "15.85 kbit/s",
+        | (true, 5) => "18.25 kbit/s",
+        | (true, 6) => "19.85 kbit/s",
+        | (true, 7) => "23.05 kbit/s",
+        | (true, 8) => "23.85 kbit/s",
+        | (true, 9) => "SID (comfort noise)",
+        | (true, 14) => "speech lost",
+        | (_, 15) => "no data",
+        | _ => "reserved"
+    }
+}
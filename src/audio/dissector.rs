@@ -0,0 +1,1271 @@
+use std::{collections::BTreeMap, io::Write};
+
+use crate::{
+    ape_tag::{ApeTag, dissect_ape_tag},
+    audio::{
+        adts::{self, AdtsHeader},
+        amr::{AmrFrameHeader, AmrHeader, FRAME_DURATION, frame_size, frame_type_name, is_reserved_frame_type},
+        musepack::{SV7_HEADER_SIZE, Sv7Header, Sv8Packet, Sv8StreamHeader, packet_name, profile_name},
+        tta::{HEADER_SIZE, TtaHeader, parse_seek_table},
+        wavpack::{BLOCK_HEADER_SIZE, WavPackBlock}
+    },
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    id3v1::{ID3V1_SIZE, Id3v1Tag, dissect_id3v1_trailer},
+    lyrics3::{Lyrics3Tag, dissect_lyrics3_tag},
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Findings of one kind reported before only the total is reported
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Largest SV8 stream header or encoder information packet that is read
+const MAX_INFO_PACKET_SIZE: u64 = 256;
+
+/// End of the audio data: the start of the APE, Lyrics3 or ID3v1 tag at the end of the file
+fn audio_end(file: &mut dyn MediaSource, audio_start: u64) -> Result<u64, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+    Ok(match (ApeTag::read(file)?, Lyrics3Tag::read(file)?)
+    {
+        | (Some(ape), Some(lyrics3)) if ape.offset > audio_start => ape.offset.min(lyrics3.offset),
+        | (Some(ape), None) if ape.offset > audio_start => ape.offset,
+        | (_, Some(lyrics3)) if lyrics3.offset > audio_start => lyrics3.offset,
+        | _ => Id3v1Tag::read(file)?.map_or(file_size, |id3v1| id3v1.offset)
+    })
+}
+
+/// Print the APE, Lyrics3 and ID3v1 tags at the end of the file
+fn dissect_trailing_tags(
+    file: &mut dyn MediaSource, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    dissect_ape_tag(file, options, output, diagnostics)?;
+    dissect_lyrics3_tag(file, options, output, diagnostics)?;
+    dissect_id3v1_trailer(file, options, output)
+}
+
+/// Add the APE, Lyrics3 and ID3v1 tags at the end of the file to the JSON content of a layer
+fn with_trailing_tags(
+    file: &mut dyn MediaSource, mut content: serde_json::Value, diagnostics: &mut Diagnostics
+) -> Result<serde_json::Value, Box<dyn std::error::Error>>
+{
+    if let Some(ape) = ApeTag::read(file)?
+    {
+        ape.check_item_count(diagnostics);
+        content["ape"] = ape.to_json();
+    }
+    if let Some(lyrics3) = Lyrics3Tag::read(file)?
+    {
+        lyrics3.check_fields(diagnostics);
+        content["lyrics3"] = lyrics3.to_json();
+    }
+    if let Some(id3v1) = Id3v1Tag::read(file)?
+    {
+        content["id3v1"] = id3v1.to_json();
+    }
+    Ok(content)
+}
+
+/// Mark the audio data from `start_offset` to `data_end` and the tags at the end of the file as covered
+///
+/// Returns the end of the file, which the audio layer extends to.
+fn add_stream_coverage(file: &mut dyn MediaSource, start_offset: u64, data_end: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+{
+    coverage.add(start_offset, data_end);
+    if let Some(ape) = ApeTag::read(file)? &&
+        ape.offset >= data_end
+    {
+        coverage.add(ape.offset, ape.offset + ape.size);
+    }
+    if let Some(lyrics3) = Lyrics3Tag::read(file)? &&
+        lyrics3.offset >= data_end
+    {
+        coverage.add(lyrics3.offset, lyrics3.offset + lyrics3.size);
+    }
+    if let Some(id3v1) = Id3v1Tag::read(file)? &&
+        id3v1.offset >= data_end
+    {
+        coverage.add(id3v1.offset, id3v1.offset + ID3V1_SIZE);
+    }
+    Ok(file.size()?.max(start_offset))
+}
+
+/// Duration in seconds of `samples` samples per channel
+fn samples_duration(samples: u64, sample_rate: u32) -> Option<f64>
+{
+    match sample_rate
+    {
+        | 0 => None,
+        | sample_rate => Some(samples as f64 / sample_rate as f64)
+    }
+}
+
+/// Result of walking the blocks of a WavPack stream
+struct WavPackStream
+{
+    first:      WavPackBlock,
+    blocks:     u64,
+    /// Channels of the first frame (one block per mono or stereo pair)
+    channels:   u16,
+    /// Samples per channel counted from the first block of every frame
+    samples:    u64,
+    /// End of the last complete block
+    blocks_end: u64
+}
+
+impl WavPackStream
+{
+    /// Samples per channel from the first block header, or counted from the blocks if the header does not know
+    fn sample_count(&self) -> u64
+    {
+        self.first.total_samples.unwrap_or(self.samples)
+    }
+
+    fn bits_per_sample(&self) -> u32
+    {
+        match self.first.is_float()
+        {
+            | true => 32,
+            | false => self.first.bytes_per_sample() * 8
+        }
+    }
+
+    fn duration(&self) -> Option<f64>
+    {
+        samples_duration(self.sample_count(), self.first.sample_rate()?)
+    }
+}
+
+/// WavPack dissector - unit struct
+#[derive(Default)]
+pub struct WavPackDissector;
+
+impl WavPackDissector
+{
+    /// Walk all blocks from `start_offset` up to the trailing tags
+    fn read_blocks(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<WavPackStream, Box<dyn std::error::Error>>
+    {
+        let audio_end = audio_end(file, start_offset)?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut header = [0u8; BLOCK_HEADER_SIZE];
+        let mut stream: Option<WavPackStream> = None;
+        let mut offset = start_offset;
+        let mut next_index: Option<u64> = None;
+        let mut first_frame_complete = false;
+        let mut index_errors = 0;
+
+        while offset + BLOCK_HEADER_SIZE as u64 <= audio_end
+        {
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut header)?;
+            let block = match (WavPackBlock::parse(&header), &stream)
+            {
+                | (Ok(block), _) => block,
+                | (Err(e), None) => return Err(e.into()),
+                | (Err(e), Some(_)) =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("{}, {} bytes of other data before the end of the audio", &[&e, &(audio_end - offset)]));
+                    break;
+                }
+            };
+            if offset + block.size > audio_end
+            {
+                diagnostics.warning(Some(offset), tr_fmt("WavPack block truncated: {} bytes declared, {} available", &[&block.size, &(audio_end - offset)]));
+                break;
+            }
+
+            let stream = stream.get_or_insert(WavPackStream { first: block, blocks: 0, channels: 0, samples: 0, blocks_end: offset });
+            stream.blocks += 1;
+            if block.block_samples > 0
+            {
+                if first_frame_complete == false
+                {
+                    stream.channels += block.channels();
+                    first_frame_complete = block.is_final();
+                }
+                if block.is_initial() == true
+                {
+                    if let Some(expected) = next_index &&
+                        block.block_index != expected
+                    {
+                        index_errors += 1;
+                        if index_errors <= MAX_REPORTED_ERRORS
+                        {
+                            diagnostics
+                                .warning(Some(offset), tr_fmt("Block starts at sample {}, the previous block ended at sample {}", &[&block.block_index, &expected]));
+                        }
+                    }
+                    next_index = Some(block.block_index + block.block_samples as u64);
+                    stream.samples += block.block_samples as u64;
+                }
+            }
+            offset += block.size;
+            stream.blocks_end = offset;
+        }
+
+        let stream = stream.ok_or(tr("WavPack block header truncated"))?;
+        if index_errors > MAX_REPORTED_ERRORS
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("{} block index discontinuities in total", &[&index_errors]));
+        }
+        if let Some(total_samples) = stream.first.total_samples &&
+            stream.blocks_end == audio_end &&
+            total_samples != stream.samples
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("Stream declares {} samples, the blocks hold {}", &[&total_samples, &stream.samples]));
+        }
+
+        Ok(stream)
+    }
+}
+
+impl MediaDissector for WavPackDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "WavPack"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "WavPack Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_blocks(file, start_offset, diagnostics)?;
+        let first = &stream.first;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("WavPack Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Stream version: {}", &[&format!("0x{:03X}", first.version)]))?;
+            writeln!(output, "  {}", tr_fmt("Mode: {}", &[&tr(first.mode())]))?;
+            if let Some(sample_rate) = first.sample_rate()
+            {
+                writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&sample_rate]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Channels: {}", &[&stream.channels]))?;
+            writeln!(output, "  {}", tr_fmt("Bits per sample: {}", &[&stream.bits_per_sample()]))?;
+            writeln!(output, "  {}", tr_fmt("Samples: {} per channel", &[&stream.sample_count()]))?;
+            if let Some(duration) = stream.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Blocks: {}", &[&stream.blocks]))?;
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.blocks_end - start_offset)]))?;
+        }
+
+        dissect_trailing_tags(file, options, output, diagnostics)?;
+
+        Ok(file.size()?.max(stream.blocks_end))
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_blocks(file, start_offset, diagnostics)?;
+        let first = &stream.first;
+        let content = serde_json::json!({
+            "version": first.version,
+            "mode": first.mode(),
+            "sample_rate": first.sample_rate(),
+            "channels": stream.channels,
+            "bits_per_sample": stream.bits_per_sample(),
+            "sample_count": stream.sample_count(),
+            "duration": stream.duration(),
+            "blocks": stream.blocks,
+            "size": stream.blocks_end - start_offset
+        });
+
+        Ok((with_trailing_tags(file, content, diagnostics)?, file.size()?.max(stream.blocks_end)))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_blocks(file, start_offset, diagnostics)?;
+        let probe_stream = ProbeStream {
+            codec_name: Some("wavpack"),
+            codec_long_name: Some("WavPack"),
+            codec_type: "audio",
+            sample_rate: stream.first.sample_rate(),
+            channels: Some(stream.channels),
+            duration: stream.duration(),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat { format_name: "wv", format_long_name: "WavPack", duration: stream.duration(), streams: vec![probe_stream] }),
+            file.size()?.max(stream.blocks_end)
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_blocks(file, start_offset, &mut Diagnostics::new())?;
+        add_stream_coverage(file, start_offset, stream.blocks_end, coverage)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_blocks(file, start_offset, diagnostics)?;
+
+        Ok(file.size()?.max(stream.blocks_end))
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        match header.len() >= BLOCK_HEADER_SIZE && &header[0..4] == b"wvpk" && WavPackBlock::parse(header).is_ok()
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
+
+/// Result of reading the header (SV7) or the packets (SV8) of a Musepack stream
+struct MusepackStream
+{
+    /// Stream version (7 or 8)
+    version:      u8,
+    sample_rate:  u32,
+    channels:     u8,
+    sample_count: u64,
+    mid_side:     bool,
+    /// Quality profile (e.g. "Standard (q=5)")
+    profile:      Option<String>,
+    encoder:      Option<String>,
+    /// Number of packets per key (SV8)
+    packets:      BTreeMap<String, u64>,
+    /// End of the audio data (SV7) or of the stream end packet (SV8)
+    data_end:     u64
+}
+
+impl MusepackStream
+{
+    fn duration(&self) -> Option<f64>
+    {
+        samples_duration(self.sample_count, self.sample_rate)
+    }
+}
+
+/// Musepack dissector (SV7 and SV8 streams) - unit struct
+#[derive(Default)]
+pub struct MusepackDissector;
+
+impl MusepackDissector
+{
+    /// Read the stream header from `start_offset`; SV8 streams are walked packet by packet up to the stream end packet
+    fn read_stream(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<MusepackStream, Box<dyn std::error::Error>>
+    {
+        let audio_end = audio_end(file, start_offset)?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+
+        let mut signature = [0u8; 4];
+        reader.read_exact(&mut signature)?;
+        if &signature[0..3] == b"MP+"
+        {
+            let mut header = [0u8; SV7_HEADER_SIZE];
+            reader.seek_to(start_offset)?;
+            reader.read_exact(&mut header)?;
+            let sv7 = Sv7Header::parse(&header)?;
+            let encoder = match sv7.encoder_version
+            {
+                | 0 => None,
+                | version => Some(format!("{}.{:02}", version / 100, version % 100))
+            };
+            return Ok(MusepackStream {
+                version: 7,
+                sample_rate: sv7.sample_rate,
+                channels: 2,
+                sample_count: sv7.sample_count(),
+                mid_side: sv7.mid_side,
+                profile: Some(profile_name(sv7.profile).to_string()),
+                encoder,
+                packets: BTreeMap::new(),
+                data_end: audio_end
+            });
+        }
+
+        let mut stream = MusepackStream {
+            version:      8,
+            sample_rate:  0,
+            channels:     0,
+            sample_count: 0,
+            mid_side:     false,
+            profile:      None,
+            encoder:      None,
+            packets:      BTreeMap::new(),
+            data_end:     audio_end
+        };
+        let mut offset = start_offset + 4;
+        let mut has_header = false;
+        let mut stream_end = false;
+        while offset + 3 <= audio_end
+        {
+            let mut header = [0u8; 11];
+            let length = (header.len() as u64).min(audio_end - offset) as usize;
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut header[..length])?;
+            let packet = match Sv8Packet::parse(&header[..length])
+            {
+                | Ok(packet) => packet,
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), e);
+                    break;
+                }
+            };
+            if offset + packet.size > audio_end
+            {
+                diagnostics.warning(
+                    Some(offset),
+                    tr_fmt("Musepack '{}' packet truncated: {} bytes declared, {} available", &[&packet.key, &packet.size, &(audio_end - offset)])
+                );
+                break;
+            }
+            *stream.packets.entry(packet.key.clone()).or_default() += 1;
+
+            if matches!(packet.key.as_str(), "SH" | "EI") == true && packet.size <= MAX_INFO_PACKET_SIZE
+            {
+                let mut payload = vec![0u8; (packet.size - packet.header_size as u64) as usize];
+                reader.seek_to(offset + packet.header_size as u64)?;
+                reader.read_exact(&mut payload)?;
+                match packet.key.as_str()
+                {
+                    | "SH" => match Sv8StreamHeader::parse(&payload)
+                    {
+                        | Ok(header) =>
+                        {
+                            if header.crc != header.computed_crc
+                            {
+                                diagnostics.warning(
+                                    Some(offset),
+                                    tr_fmt("Stream header CRC mismatch: stored {}, computed {}", &[
+                                        &format!("0x{:08X}", header.crc),
+                                        &format!("0x{:08X}", header.computed_crc)
+                                    ])
+                                );
+                            }
+                            if header.version != 8
+                            {
+                                diagnostics.warning(Some(offset), tr_fmt("Musepack stream version {} in an SV8 stream header", &[&header.version]));
+                            }
+                            has_header = true;
+                            stream.sample_rate = header.sample_rate;
+                            stream.channels = header.channels;
+                            stream.sample_count = header.sample_count;
+                            stream.mid_side = header.mid_side;
+                        }
+                        | Err(e) => diagnostics.warning(Some(offset), e)
+                    },
+                    | _ =>
+                    {
+                        // Quality in eighths, PNS flag, then the encoder version bytes
+                        if let [profile, major, minor, build, ..] = payload[..]
+                        {
+                            stream.profile = Some(format!("q={:.1}", (profile >> 1) as f64 / 8.0));
+                            stream.encoder = Some(format!("{}.{}.{}", major, minor, build));
+                        }
+                    }
+                }
+            }
+            offset += packet.size;
+            if packet.key == "SE"
+            {
+                stream_end = true;
+                break;
+            }
+        }
+
+        if has_header == false
+        {
+            diagnostics.warning(Some(start_offset), tr("Musepack SV8 stream has no stream header packet"));
+        }
+        if stream_end == false
+        {
+            diagnostics.info(Some(offset), tr("Musepack SV8 stream has no stream end packet (truncated or cut file)"));
+        }
+        else if offset < audio_end
+        {
+            diagnostics.warning(Some(offset), tr_fmt("{} bytes of other data behind the stream end packet", &[&(audio_end - offset)]));
+        }
+        stream.data_end = offset.min(audio_end);
+
+        Ok(stream)
+    }
+}
+
+impl MediaDissector for MusepackDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "Musepack"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "Musepack Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("Musepack Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Stream version: {}", &[&format!("SV{}", stream.version)]))?;
+            if let Some(ref profile) = stream.profile
+            {
+                writeln!(output, "  {}", tr_fmt("Profile: {}", &[profile]))?;
+            }
+            if let Some(ref encoder) = stream.encoder
+            {
+                writeln!(output, "  {}", tr_fmt("Encoder: {}", &[encoder]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&stream.sample_rate]))?;
+            writeln!(output, "  {}", tr_fmt("Channels: {}", &[&stream.channels]))?;
+            writeln!(output, "  {}", tr_fmt("Samples: {} per channel", &[&stream.sample_count]))?;
+            if let Some(duration) = stream.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.data_end - start_offset)]))?;
+        }
+
+        if options.show_data == true && stream.packets.is_empty() == false
+        {
+            writeln!(output, "\n{}", tr("Packets:").bright_cyan().bold())?;
+            for (key, count) in &stream.packets
+            {
+                writeln!(output, "  {} ({}): {}", key, tr(packet_name(key)), count)?;
+            }
+        }
+
+        dissect_trailing_tags(file, options, output, diagnostics)?;
+
+        Ok(file.size()?.max(stream.data_end))
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+        let content = serde_json::json!({
+            "version": stream.version,
+            "sample_rate": stream.sample_rate,
+            "channels": stream.channels,
+            "sample_count": stream.sample_count,
+            "duration": stream.duration(),
+            "mid_side": stream.mid_side,
+            "profile": stream.profile,
+            "encoder": stream.encoder,
+            "packets": stream.packets,
+            "size": stream.data_end - start_offset
+        });
+
+        Ok((with_trailing_tags(file, content, diagnostics)?, file.size()?.max(stream.data_end)))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+        let (format_name, format_long_name, codec_name, codec_long_name) = match stream.version
+        {
+            | 7 => ("mpc", "Musepack", "musepack7", "Musepack SV7"),
+            | _ => ("mpc8", "Musepack SV8", "musepack8", "Musepack SV8")
+        };
+        let probe_stream = ProbeStream {
+            codec_name: Some(codec_name),
+            codec_long_name: Some(codec_long_name),
+            codec_type: "audio",
+            sample_rate: Some(stream.sample_rate),
+            channels: Some(stream.channels as u16),
+            duration: stream.duration(),
+            ..Default::default()
+        };
+
+        Ok((Some(ProbeFormat { format_name, format_long_name, duration: stream.duration(), streams: vec![probe_stream] }), file.size()?.max(stream.data_end)))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_stream(file, start_offset, &mut Diagnostics::new())?;
+        add_stream_coverage(file, start_offset, stream.data_end, coverage)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+
+        Ok(file.size()?.max(stream.data_end))
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // SV8 starts with "MPCK", SV7 with "MP+" and the stream version in the low nibble
+        match header.starts_with(b"MPCK") || (header.len() >= 4 && &header[0..3] == b"MP+" && header[3] & 0x0F == 7)
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
+
+/// Result of reading the header and seek table of a True Audio stream
+struct TtaStream
+{
+    header:   TtaHeader,
+    frames:   u64,
+    /// End of the last frame according to the seek table
+    data_end: u64
+}
+
+/// True Audio (TTA) dissector - unit struct
+#[derive(Default)]
+pub struct TtaDissector;
+
+impl TtaDissector
+{
+    /// Read the header and the seek table from `start_offset` and check both CRCs and the frame sizes
+    fn read_stream(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<TtaStream, Box<dyn std::error::Error>>
+    {
+        let audio_end = audio_end(file, start_offset)?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut data = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut data)?;
+        let header = TtaHeader::parse(&data)?;
+
+        if header.crc != header.computed_crc
+        {
+            diagnostics.warning(
+                Some(start_offset),
+                tr_fmt("TTA header CRC mismatch: stored {}, computed {}", &[&format!("0x{:08X}", header.crc), &format!("0x{:08X}", header.computed_crc)])
+            );
+        }
+        if header.format == 2
+        {
+            diagnostics.info(Some(start_offset), tr("TTA audio is encrypted"));
+        }
+        else if header.format != 1
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("Unknown TTA audio format {}", &[&header.format]));
+        }
+
+        let seek_table_offset = start_offset + HEADER_SIZE as u64;
+        if seek_table_offset + header.seek_table_size() > audio_end
+        {
+            diagnostics.warning(Some(seek_table_offset), tr_fmt("TTA seek table of {} frames truncated", &[&header.frame_count()]));
+            return Ok(TtaStream { header, frames: 0, data_end: audio_end });
+        }
+        let mut seek_table = vec![0u8; header.seek_table_size() as usize];
+        reader.read_exact(&mut seek_table)?;
+        let (frame_sizes, crc, computed_crc) = parse_seek_table(&seek_table);
+        if crc != computed_crc
+        {
+            diagnostics.warning(
+                Some(seek_table_offset),
+                tr_fmt("TTA seek table CRC mismatch: stored {}, computed {}", &[&format!("0x{:08X}", crc), &format!("0x{:08X}", computed_crc)])
+            );
+        }
+
+        let data_end = seek_table_offset + header.seek_table_size() + frame_sizes.iter().map(|&size| size as u64).sum::<u64>();
+        if data_end != audio_end
+        {
+            diagnostics.warning(
+                Some(seek_table_offset),
+                tr_fmt("Frames of the seek table end at {}, the audio data ends at {}", &[&format!("0x{:X}", data_end), &format!("0x{:X}", audio_end)])
+            );
+        }
+
+        Ok(TtaStream { header, frames: frame_sizes.len() as u64, data_end: data_end.min(audio_end) })
+    }
+}
+
+impl MediaDissector for TtaDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "TTA"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "True Audio Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+        let header = &stream.header;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("True Audio Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Format: {}", &[&tr(header.format_name())]))?;
+            writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&header.sample_rate]))?;
+            writeln!(output, "  {}", tr_fmt("Channels: {}", &[&header.channels]))?;
+            writeln!(output, "  {}", tr_fmt("Bits per sample: {}", &[&header.bits_per_sample]))?;
+            writeln!(output, "  {}", tr_fmt("Samples: {} per channel", &[&header.sample_count]))?;
+            if let Some(duration) = header.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Frames: {}", &[&stream.frames]))?;
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.data_end - start_offset)]))?;
+        }
+
+        dissect_trailing_tags(file, options, output, diagnostics)?;
+
+        Ok(file.size()?.max(stream.data_end))
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+        let header = &stream.header;
+        let content = serde_json::json!({
+            "format": header.format,
+            "sample_rate": header.sample_rate,
+            "channels": header.channels,
+            "bits_per_sample": header.bits_per_sample,
+            "sample_count": header.sample_count,
+            "duration": header.duration(),
+            "frames": stream.frames,
+            "size": stream.data_end - start_offset
+        });
+
+        Ok((with_trailing_tags(file, content, diagnostics)?, file.size()?.max(stream.data_end)))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+        let header = &stream.header;
+        let probe_stream = ProbeStream {
+            codec_name: Some("tta"),
+            codec_long_name: Some("TTA (True Audio)"),
+            codec_type: "audio",
+            sample_rate: Some(header.sample_rate),
+            channels: Some(header.channels),
+            duration: header.duration(),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat {
+                format_name:      "tta",
+                format_long_name: "TTA (True Audio)",
+                duration:         header.duration(),
+                streams:          vec![probe_stream]
+            }),
+            file.size()?.max(stream.data_end)
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_stream(file, start_offset, &mut Diagnostics::new())?;
+        add_stream_coverage(file, start_offset, stream.data_end, coverage)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_stream(file, start_offset, diagnostics)?;
+
+        Ok(file.size()?.max(stream.data_end))
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        match header.starts_with(b"TTA1")
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
+
+/// Result of walking the frames of an AMR file
+struct AmrStream
+{
+    header:         AmrHeader,
+    frames:         u64,
+    /// Number of frames per frame type
+    frame_types:    [u64; 16],
+    /// Frames with the quality bit cleared
+    damaged_frames: u64,
+    /// End of the last complete frame
+    data_end:       u64
+}
+
+impl AmrStream
+{
+    /// Duration in seconds: one frame per channel every 20 ms
+    fn duration(&self) -> f64
+    {
+        (self.frames / self.header.channels as u64) as f64 * FRAME_DURATION
+    }
+
+    /// Average bit rate of the frames in bits per second
+    fn bit_rate(&self, start_offset: u64) -> Option<u64>
+    {
+        let duration = self.duration();
+        (duration > 0.0).then(|| ((self.data_end - start_offset - self.header.size as u64) as f64 * 8.0 / duration) as u64)
+    }
+}
+
+/// AMR (Adaptive Multi-Rate speech, RFC 4867 storage format) dissector - unit struct
+#[derive(Default)]
+pub struct AmrDissector;
+
+impl AmrDissector
+{
+    /// Walk all frames behind the magic number up to the first byte that is no frame header
+    fn read_frames(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<AmrStream, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut magic = vec![0u8; 19.min(file_size - start_offset) as usize];
+        reader.read_exact(&mut magic)?;
+        let header = AmrHeader::parse(&magic)?;
+
+        let mut stream = AmrStream { header, frames: 0, frame_types: [0; 16], damaged_frames: 0, data_end: start_offset + header.size as u64 };
+        let mut reserved_frames = 0;
+        let mut offset = stream.data_end;
+        let mut byte = [0u8; 1];
+        while offset < file_size
+        {
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut byte)?;
+            let frame = match AmrFrameHeader::parse(byte[0])
+            {
+                | Ok(frame) => frame,
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("{}, {} bytes of other data behind the last frame", &[&e, &(file_size - offset)]));
+                    break;
+                }
+            };
+            let size = frame_size(header.wideband, frame.frame_type) as u64;
+            if offset + size > file_size
+            {
+                diagnostics.warning(Some(offset), tr_fmt("AMR frame truncated: {} bytes expected, {} available", &[&size, &(file_size - offset)]));
+                stream.data_end = file_size;
+                break;
+            }
+            if is_reserved_frame_type(header.wideband, frame.frame_type) == true
+            {
+                reserved_frames += 1;
+                if reserved_frames <= MAX_REPORTED_ERRORS
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("Reserved AMR frame type {}", &[&frame.frame_type]));
+                }
+            }
+            if frame.quality == false
+            {
+                stream.damaged_frames += 1;
+            }
+            stream.frames += 1;
+            stream.frame_types[frame.frame_type as usize] += 1;
+            offset += size;
+            stream.data_end = offset;
+        }
+
+        if reserved_frames > MAX_REPORTED_ERRORS
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("{} frames of reserved types in total", &[&reserved_frames]));
+        }
+        if stream.damaged_frames > 0
+        {
+            diagnostics.info(Some(start_offset), tr_fmt("{} frames are marked as damaged (quality bit cleared)", &[&stream.damaged_frames]));
+        }
+        if stream.frames.is_multiple_of(header.channels as u64) == false
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("{} frames do not fill complete blocks of {} channels", &[&stream.frames, &header.channels]));
+        }
+
+        Ok(stream)
+    }
+}
+
+impl MediaDissector for AmrDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "AMR"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "AMR Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let header = &stream.header;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("AMR Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Codec: {}", &[&header.codec_name()]))?;
+            writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&header.sample_rate()]))?;
+            writeln!(output, "  {}", tr_fmt("Channels: {}", &[&header.channels]))?;
+            writeln!(output, "  {}", tr_fmt("Frames: {}", &[&stream.frames]))?;
+            writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", stream.duration())]))?;
+            if let Some(bit_rate) = stream.bit_rate(start_offset)
+            {
+                writeln!(output, "  {}", tr_fmt("Average Bitrate: {} kbps", &[&format!("{:.2}", bit_rate as f64 / 1000.0)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.data_end - start_offset)]))?;
+
+            if stream.frames > 0
+            {
+                writeln!(output, "\n{}", tr("Frame Types:").bright_cyan().bold())?;
+                for (frame_type, &count) in stream.frame_types.iter().enumerate().filter(|&(_, &count)| count > 0)
+                {
+                    writeln!(
+                        output,
+                        "  {:>2}: {} - {} ({:.1}%)",
+                        frame_type,
+                        tr(frame_type_name(header.wideband, frame_type as u8)),
+                        count,
+                        count as f64 * 100.0 / stream.frames as f64
+                    )?;
+                }
+            }
+        }
+
+        Ok(stream.data_end)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let header = &stream.header;
+        let frame_types: Vec<serde_json::Value> = stream
+            .frame_types
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(frame_type, &count)| serde_json::json!({ "type": frame_type, "name": frame_type_name(header.wideband, frame_type as u8), "count": count }))
+            .collect();
+        let content = serde_json::json!({
+            "codec": header.codec_name(),
+            "sample_rate": header.sample_rate(),
+            "channels": header.channels,
+            "frames": stream.frames,
+            "duration": stream.duration(),
+            "bit_rate": stream.bit_rate(start_offset),
+            "frame_types": frame_types,
+            "damaged_frames": stream.damaged_frames,
+            "size": stream.data_end - start_offset
+        });
+
+        Ok((content, stream.data_end))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let (codec_name, codec_long_name) = match stream.header.wideband
+        {
+            | true => ("amr_wb", "AMR-WB (Adaptive Multi-Rate WideBand)"),
+            | false => ("amr_nb", "AMR-NB (Adaptive Multi-Rate NarrowBand)")
+        };
+        let probe_stream = ProbeStream {
+            codec_name: Some(codec_name),
+            codec_long_name: Some(codec_long_name),
+            codec_type: "audio",
+            sample_rate: Some(stream.header.sample_rate()),
+            channels: Some(stream.header.channels as u16),
+            duration: Some(stream.duration()),
+            bit_rate: stream.bit_rate(start_offset),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat {
+                format_name:      "amr",
+                format_long_name: "3GPP AMR",
+                duration:         Some(stream.duration()),
+                streams:          vec![probe_stream]
+            }),
+            stream.data_end
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_frames(file, start_offset, &mut Diagnostics::new())?;
+        coverage.add(start_offset, stream.data_end);
+        Ok(stream.data_end)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+
+        Ok(stream.data_end)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        match AmrHeader::parse(header).is_ok()
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
+
+/// Result of walking the frames of an ADTS stream
+struct AdtsStream
+{
+    first:      AdtsHeader,
+    frames:     u64,
+    /// Samples per channel of all frames
+    samples:    u64,
+    /// End of the last complete frame
+    frames_end: u64
+}
+
+impl AdtsStream
+{
+    fn duration(&self) -> Option<f64>
+    {
+        samples_duration(self.samples, self.first.sample_rate())
+    }
+
+    /// Average bit rate of the frames in bits per second
+    fn bit_rate(&self, start_offset: u64) -> Option<u64>
+    {
+        self.duration().filter(|&duration| duration > 0.0).map(|duration| ((self.frames_end - start_offset) as f64 * 8.0 / duration) as u64)
+    }
+
+    /// Codec name with the MPEG version of the ID bit
+    fn codec_name(&self) -> &'static str
+    {
+        match self.first.mpeg2
+        {
+            | true => "MPEG-2 AAC",
+            | false => "MPEG-4 AAC"
+        }
+    }
+}
+
+/// ADTS (raw AAC in Audio Data Transport Stream frames) dissector - unit struct
+#[derive(Default)]
+pub struct AdtsDissector;
+
+impl AdtsDissector
+{
+    /// Walk all frames from `start_offset` up to the trailing tags
+    fn read_frames(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<AdtsStream, Box<dyn std::error::Error>>
+    {
+        let audio_end = audio_end(file, start_offset)?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut header = [0u8; adts::HEADER_SIZE];
+        let mut stream: Option<AdtsStream> = None;
+        let mut offset = start_offset;
+        let mut parameter_changes = 0;
+
+        while offset + adts::HEADER_SIZE as u64 <= audio_end
+        {
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut header)?;
+            let frame = match (AdtsHeader::parse(&header), &stream)
+            {
+                | (Ok(frame), _) => frame,
+                | (Err(e), None) => return Err(e.into()),
+                | (Err(e), Some(_)) =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("{}, {} bytes of other data before the end of the audio", &[&e, &(audio_end - offset)]));
+                    break;
+                }
+            };
+            if offset + frame.frame_length as u64 > audio_end
+            {
+                diagnostics.warning(Some(offset), tr_fmt("ADTS frame truncated: {} bytes declared, {} available", &[&frame.frame_length, &(audio_end - offset)]));
+                break;
+            }
+
+            let stream = stream.get_or_insert(AdtsStream { first: frame, frames: 0, samples: 0, frames_end: offset });
+            if frame.same_stream(&stream.first) == false
+            {
+                parameter_changes += 1;
+                if parameter_changes <= MAX_REPORTED_ERRORS
+                {
+                    diagnostics.warning(
+                        Some(offset),
+                        tr_fmt("ADTS frame changes the stream to {} at {} Hz, channel configuration {}", &[
+                            &frame.profile_name(),
+                            &frame.sample_rate(),
+                            &frame.channel_configuration
+                        ])
+                    );
+                }
+            }
+            stream.frames += 1;
+            stream.samples += frame.samples();
+            offset += frame.frame_length as u64;
+            stream.frames_end = offset;
+        }
+
+        let stream = stream.ok_or(tr("ADTS frame header truncated"))?;
+        if parameter_changes > MAX_REPORTED_ERRORS
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("{} frames change the stream parameters in total", &[&parameter_changes]));
+        }
+
+        Ok(stream)
+    }
+}
+
+impl MediaDissector for AdtsDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "AAC"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "ADTS Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let first = &stream.first;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("ADTS Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Codec: {}", &[&stream.codec_name()]))?;
+            writeln!(output, "  {}", tr_fmt("Profile: {}", &[&tr(first.profile_name())]))?;
+            writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&first.sample_rate()]))?;
+            match first.channels()
+            {
+                | Some(channels) => writeln!(output, "  {}", tr_fmt("Channels: {}", &[&channels]))?,
+                | None => writeln!(output, "  {}", tr_fmt("Channels: {}", &[&tr("defined in the program config element")]))?
+            }
+            let crc = match first.crc
+            {
+                | true => tr("yes"),
+                | false => tr("no")
+            };
+            writeln!(output, "  {}", tr_fmt("CRC Protection: {}", &[&crc]))?;
+            writeln!(output, "  {}", tr_fmt("Frames: {}", &[&stream.frames]))?;
+            if let Some(duration) = stream.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            if let Some(bit_rate) = stream.bit_rate(start_offset)
+            {
+                writeln!(output, "  {}", tr_fmt("Average Bitrate: {} kbps", &[&format!("{:.2}", bit_rate as f64 / 1000.0)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.frames_end - start_offset)]))?;
+        }
+
+        dissect_trailing_tags(file, options, output, diagnostics)?;
+
+        Ok(file.size()?.max(stream.frames_end))
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let first = &stream.first;
+        let content = serde_json::json!({
+            "codec": stream.codec_name(),
+            "profile": first.profile_name(),
+            "sample_rate": first.sample_rate(),
+            "channel_configuration": first.channel_configuration,
+            "channels": first.channels(),
+            "crc": first.crc,
+            "frames": stream.frames,
+            "sample_count": stream.samples,
+            "duration": stream.duration(),
+            "bit_rate": stream.bit_rate(start_offset),
+            "size": stream.frames_end - start_offset
+        });
+
+        Ok((with_trailing_tags(file, content, diagnostics)?, file.size()?.max(stream.frames_end)))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+        let probe_stream = ProbeStream {
+            codec_name: Some("aac"),
+            codec_long_name: Some("AAC (Advanced Audio Coding)"),
+            codec_type: "audio",
+            sample_rate: Some(stream.first.sample_rate()),
+            channels: stream.first.channels(),
+            duration: stream.duration(),
+            bit_rate: stream.bit_rate(start_offset),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat {
+                format_name:      "aac",
+                format_long_name: "raw ADTS AAC (Advanced Audio Coding)",
+                duration:         stream.duration(),
+                streams:          vec![probe_stream]
+            }),
+            file.size()?.max(stream.frames_end)
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_frames(file, start_offset, &mut Diagnostics::new())?;
+        add_stream_coverage(file, start_offset, stream.frames_end, coverage)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_frames(file, start_offset, diagnostics)?;
+
+        Ok(file.size()?.max(stream.frames_end))
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        adts::sniff(header)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    bytes::read_u32_le,
+    i18n::{tr, tr_fmt},
+    id3v2::extended_header::crc32
+};
+
+/// Samples per channel in one Musepack frame
+pub const FRAME_SAMPLES: u64 = 1152;
+
+/// Size of the SV7 stream header
+pub const SV7_HEADER_SIZE: usize = 28;
+
+/// Sample rates of the 2-bit (SV7) and 3-bit (SV8) sample frequency fields
+const SAMPLE_RATES: [u32; 4] = [44100, 48000, 37800, 32000];
+
+/// Name of an SV7 encoder profile (quality setting)
+pub fn profile_name(profile: u8) -> &'static str
+{
+    match profile
+    {
+        | 1 => "Unstable/Experimental",
+        | 5 => "below Telephone (q=0)",
+        | 6 => "below Telephone (q=1)",
+        | 7 => "Telephone (q=2)",
+        | 8 => "Thumb (q=3)",
+        | 9 => "Radio (q=4)",
+        | 10 => "Standard (q=5)",
+        | 11 => "Extreme (q=6)",
+        | 12 => "Insane (q=7)",
+        | 13 => "BrainDead (q=8)",
+        | 14 => "above BrainDead (q=9)",
+        | 15 => "above BrainDead (q=10)",
+        | _ => "n.a."
+    }
+}
+
+/// Stream header of a Musepack SV7 file ("MP+")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sv7Header
+{
+    pub frames:             u32,
+    pub sample_rate:        u32,
+    pub profile:            u8,
+    pub max_band:           u8,
+    pub mid_side:           bool,
+    /// The last frame holds `last_frame_samples` samples instead of a full frame
+    pub true_gapless:       bool,
+    pub last_frame_samples: u16,
+    /// Encoder version times 100 (e.g. 116 for 1.16), 0 for old encoders
+    pub encoder_version:    u8
+}
+
+impl Sv7Header
+{
+    /// Parse the SV7 stream header at the start of `data` (including the "MP+" signature)
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < SV7_HEADER_SIZE || &data[0..3] != b"MP+"
+        {
+            return Err(tr("Missing Musepack SV7 header").to_string());
+        }
+        if data[3] & 0x0F != 7
+        {
+            return Err(tr_fmt("Unsupported Musepack stream version {}", &[&(data[3] & 0x0F)]));
+        }
+
+        // Seven little-endian words with bit fields counted from the most significant bit
+        let flags = read_u32_le(data, 8);
+        let gapless = read_u32_le(data, 20);
+        Ok(Sv7Header {
+            frames:             read_u32_le(data, 4),
+            sample_rate:        SAMPLE_RATES[((flags >> 16) & 0x03) as usize],
+            profile:            ((flags >> 20) & 0x0F) as u8,
+            max_band:           ((flags >> 24) & 0x3F) as u8,
+            mid_side:           (flags >> 30) & 0x01 != 0,
+            true_gapless:       gapless >> 31 != 0,
+            last_frame_samples: ((gapless >> 20) & 0x07FF) as u16,
+            encoder_version:    (read_u32_le(data, 24) >> 24) as u8
+        })
+    }
+
+    /// Samples per channel of the stream
+    pub fn sample_count(&self) -> u64
+    {
+        match (self.true_gapless, self.frames)
+        {
+            | (true, frames) if frames > 0 => (frames as u64 - 1) * FRAME_SAMPLES + self.last_frame_samples as u64,
+            | (_, frames) => frames as u64 * FRAME_SAMPLES
+        }
+    }
+}
+
+/// Read a variable-length SV8 number (7 bits per byte, high bit set on all but the last byte)
+///
+/// Returns the value and the number of bytes it occupies.
+pub fn read_varint(data: &[u8]) -> Option<(u64, usize)>
+{
+    let mut value = 0u64;
+    for (index, &byte) in data.iter().enumerate().take(9)
+    {
+        value = (value << 7) | (byte & 0x7F) as u64;
+        if byte & 0x80 == 0
+        {
+            return Some((value, index + 1));
+        }
+    }
+    None
+}
+
+/// Describe an SV8 packet key
+pub fn packet_name(key: &str) -> &'static str
+{
+    match key
+    {
+        | "SH" => "Stream header",
+        | "RG" => "ReplayGain",
+        | "EI" => "Encoder information",
+        | "SO" => "Seek table offset",
+        | "AP" => "Audio packet",
+        | "ST" => "Seek table",
+        | "CT" => "Chapter tag",
+        | "SE" => "Stream end",
+        | _ => "Unknown packet"
+    }
+}
+
+/// Header of an SV8 packet: two-letter key and total size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sv8Packet
+{
+    pub key:         String,
+    /// Packet size including key and size field
+    pub size:        u64,
+    /// Bytes of key and size field
+    pub header_size: usize
+}
+
+impl Sv8Packet
+{
+    /// Parse the packet header at the start of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let key = data.get(0..2).ok_or(tr("Musepack packet header truncated"))?;
+        // Keys are two upper-case letters
+        if key.iter().all(u8::is_ascii_uppercase) == false
+        {
+            return Err(tr_fmt("Invalid Musepack packet key {} {}", &[&format!("{:02X}", key[0]), &format!("{:02X}", key[1])]));
+        }
+        let (size, length) = read_varint(&data[2..]).ok_or(tr("Musepack packet size truncated"))?;
+        let header_size = 2 + length;
+        if size < header_size as u64
+        {
+            return Err(tr_fmt("Musepack packet size {} is smaller than its header", &[&size]));
+        }
+
+        Ok(Sv8Packet { key: String::from_utf8_lossy(key).to_string(), size, header_size })
+    }
+}
+
+/// Payload of the SV8 stream header packet ("SH")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sv8StreamHeader
+{
+    pub crc:               u32,
+    /// CRC-32 computed over the header behind the CRC field
+    pub computed_crc:      u32,
+    pub version:           u8,
+    /// Samples per channel including the beginning silence
+    pub sample_count:      u64,
+    pub beginning_silence: u64,
+    pub sample_rate:       u32,
+    pub max_band:          u8,
+    pub channels:          u8,
+    pub mid_side:          bool,
+    /// Frames per audio packet
+    pub block_frames:      u32
+}
+
+impl Sv8StreamHeader
+{
+    /// Parse the payload of a stream header packet
+    pub fn parse(payload: &[u8]) -> Result<Self, String>
+    {
+        if payload.len() < 5
+        {
+            return Err(tr("Musepack stream header truncated").to_string());
+        }
+        let crc = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+        let computed_crc = crc32(&payload[4..]);
+        let version = payload[4];
+        let (sample_count, length) = read_varint(&payload[5..]).ok_or(tr("Musepack sample count truncated"))?;
+        let position = 5 + length;
+        let (beginning_silence, length) = read_varint(&payload[position..]).ok_or(tr("Musepack beginning silence truncated"))?;
+        let fields = payload.get(position + length..position + length + 2).ok_or(tr("Musepack stream header truncated"))?;
+
+        Ok(Sv8StreamHeader {
+            crc,
+            computed_crc,
+            version,
+            sample_count,
+            beginning_silence,
+            sample_rate: SAMPLE_RATES.get((fields[0] >> 5) as usize).copied().unwrap_or(0),
+            max_band: (fields[0] & 0x1F) + 1,
+            channels: (fields[1] >> 4) + 1,
+            mid_side: fields[1] & 0x08 != 0,
+            block_frames: 1 << (2 * (fields[1] & 0x07))
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    bytes::{read_u16_le, read_u32_le},
+    i18n::tr,
+    id3v2::extended_header::crc32
+};
+
+/// Size of the TTA1 header including its CRC
+pub const HEADER_SIZE: usize = 22;
+
+/// Header of a True Audio file ("TTA1")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtaHeader
+{
+    /// 1 for integer PCM, 2 for encrypted audio
+    pub format:          u16,
+    pub channels:        u16,
+    pub bits_per_sample: u16,
+    pub sample_rate:     u32,
+    /// Samples per channel
+    pub sample_count:    u32,
+    pub crc:             u32,
+    pub computed_crc:    u32
+}
+
+impl TtaHeader
+{
+    /// Parse the header at the start of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < HEADER_SIZE || &data[0..4] != b"TTA1"
+        {
+            return Err(tr("Missing TTA1 header").to_string());
+        }
+
+        Ok(TtaHeader {
+            format:          read_u16_le(data, 4),
+            channels:        read_u16_le(data, 6),
+            bits_per_sample: read_u16_le(data, 8),
+            sample_rate:     read_u32_le(data, 10),
+            sample_count:    read_u32_le(data, 14),
+            crc:             read_u32_le(data, 18),
+            computed_crc:    crc32(&data[0..18])
+        })
+    }
+
+    /// Samples per channel in one frame (1.04489 s worth of samples)
+    pub fn frame_length(&self) -> u64
+    {
+        256 * self.sample_rate as u64 / 245
+    }
+
+    /// Number of frames, and of entries in the seek table
+    pub fn frame_count(&self) -> u64
+    {
+        match self.frame_length()
+        {
+            | 0 => 0,
+            | length => (self.sample_count as u64).div_ceil(length)
+        }
+    }
+
+    /// Size of the seek table including its CRC
+    pub fn seek_table_size(&self) -> u64
+    {
+        self.frame_count() * 4 + 4
+    }
+
+    /// Duration in seconds
+    pub fn duration(&self) -> Option<f64>
+    {
+        match self.sample_rate
+        {
+            | 0 => None,
+            | sample_rate => Some(self.sample_count as f64 / sample_rate as f64)
+        }
+    }
+
+    /// Describe the audio format field
+    pub fn format_name(&self) -> &'static str
+    {
+        match self.format
+        {
+            | 1 => "PCM",
+            | 2 => "encrypted",
+            | _ => "unknown format"
+        }
+    }
+}
+
+/// Frame sizes of a seek table (frame count entries followed by a CRC), with the stored and computed CRC
+pub fn parse_seek_table(data: &[u8]) -> (Vec<u32>, u32, u32)
+{
+    let entries = data.len().saturating_sub(4);
+    let sizes = data[..entries].chunks_exact(4).map(|entry| read_u32_le(entry, 0)).collect();
+    (sizes, read_u32_le(data, entries), crc32(&data[..entries]))
+}
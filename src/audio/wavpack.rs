@@ -0,0 +1,134 @@
+/// Size of a WavPack block header
+use crate::{
+    bytes::read_u32_le,
+    i18n::{tr, tr_fmt}
+};
+
+pub const BLOCK_HEADER_SIZE: usize = 32;
+
+/// Sample rates selected by bits 23-26 of the block flags (15: custom rate in a metadata sub-block)
+const SAMPLE_RATES: [u32; 15] = [6000, 8000, 9600, 11025, 12000, 16000, 22050, 24000, 32000, 44100, 48000, 64000, 88200, 96000, 192000];
+
+/// Block flag: mono block (one channel)
+const FLAG_MONO: u32 = 0x0000_0004;
+/// Block flag: hybrid (lossy) mode
+const FLAG_HYBRID: u32 = 0x0000_0008;
+/// Block flag: IEEE 32-bit floating point samples
+const FLAG_FLOAT: u32 = 0x0000_0080;
+/// Block flag: first block of a multichannel frame
+const FLAG_INITIAL_BLOCK: u32 = 0x0000_0800;
+/// Block flag: last block of a multichannel frame
+const FLAG_FINAL_BLOCK: u32 = 0x0000_1000;
+/// Block flag: DSD audio (WavPack 5)
+const FLAG_DSD: u32 = 0x8000_0000;
+
+/// Header of a WavPack block ("wvpk", WavPack 4 and 5 file format)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavPackBlock
+{
+    /// Total block size including the header
+    pub size:          u64,
+    /// Stream version (0x402 to 0x410)
+    pub version:       u16,
+    /// Samples per channel of the whole file, None if unknown (e.g. for piped encodes)
+    pub total_samples: Option<u64>,
+    /// Index of the first sample of the block
+    pub block_index:   u64,
+    /// Samples per channel in the block, 0 for metadata-only blocks
+    pub block_samples: u32,
+    pub flags:         u32,
+    pub crc:           u32
+}
+
+impl WavPackBlock
+{
+    /// Parse the block header at the start of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < BLOCK_HEADER_SIZE || &data[0..4] != b"wvpk"
+        {
+            return Err(tr("Missing WavPack block header").to_string());
+        }
+        let version = u16::from_le_bytes([data[8], data[9]]);
+        if (0x402..=0x410).contains(&version) == false
+        {
+            return Err(tr_fmt("Unsupported WavPack stream version {}", &[&format!("0x{:X}", version)]));
+        }
+
+        // WavPack 5 extends the sample counts to 40 bits with the two bytes behind the version
+        let total_low = read_u32_le(data, 12);
+        let total_samples = match total_low
+        {
+            | 0xFFFF_FFFF => None,
+            | low => Some((data[11] as u64) << 32 | low as u64)
+        };
+
+        Ok(WavPackBlock {
+            size: read_u32_le(data, 4) as u64 + 8,
+            version,
+            total_samples,
+            block_index: (data[10] as u64) << 32 | read_u32_le(data, 16) as u64,
+            block_samples: read_u32_le(data, 20),
+            flags: read_u32_le(data, 24),
+            crc: read_u32_le(data, 28)
+        })
+    }
+
+    /// Bytes per sample of integer audio
+    pub fn bytes_per_sample(&self) -> u32
+    {
+        (self.flags & 0x03) + 1
+    }
+
+    /// Channels coded in this block
+    pub fn channels(&self) -> u16
+    {
+        match self.flags & FLAG_MONO
+        {
+            | 0 => 2,
+            | _ => 1
+        }
+    }
+
+    /// Sample rate from the block flags, None for custom rates
+    pub fn sample_rate(&self) -> Option<u32>
+    {
+        SAMPLE_RATES.get(((self.flags >> 23) & 0x0F) as usize).copied()
+    }
+
+    pub fn is_hybrid(&self) -> bool
+    {
+        self.flags & FLAG_HYBRID != 0
+    }
+
+    pub fn is_float(&self) -> bool
+    {
+        self.flags & FLAG_FLOAT != 0
+    }
+
+    pub fn is_dsd(&self) -> bool
+    {
+        self.flags & FLAG_DSD != 0
+    }
+
+    pub fn is_initial(&self) -> bool
+    {
+        self.flags & FLAG_INITIAL_BLOCK != 0
+    }
+
+    pub fn is_final(&self) -> bool
+    {
+        self.flags & FLAG_FINAL_BLOCK != 0
+    }
+
+    /// Coding mode description
+    pub fn mode(&self) -> &'static str
+    {
+        match (self.is_dsd(), self.is_hybrid())
+        {
+            | (true, _) => "DSD",
+            | (false, true) => "hybrid (lossy)",
+            | (false, false) => "lossless"
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Batch dissection of directories
+//
+// Collects the regular files of a directory (optionally of all subdirectories) whose
+// paths match the include patterns and none of the exclude patterns. Patterns are
+// shell-style globs matched against the file name, or against the path relative to the
+// directory if they contain a '/': `*` and `?` stay within one path component, `**`
+// matches across components. The files are dissected on a pool of worker threads, with
+// results handed back in file order so output is never interleaved.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc
+    },
+    thread
+};
+
+use crate::report::SCHEMA_VERSION;
+
+/// Selection of the files to dissect in a directory
+#[derive(Debug, Clone, Default)]
+pub struct BatchFilter
+{
+    /// Descend into subdirectories
+    pub recursive: bool,
+    /// Glob patterns of files to dissect (all files if empty)
+    pub include:   Vec<String>,
+    /// Glob patterns of files to skip
+    pub exclude:   Vec<String>
+}
+
+impl BatchFilter
+{
+    /// Check if a file (path relative to the batch directory, '/'-separated) is selected
+    pub fn matches(&self, relative_path: &str) -> bool
+    {
+        let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        let matches_pattern = |pattern: &String| {
+            if pattern.contains('/') == true
+            {
+                glob_match(pattern.as_bytes(), relative_path.as_bytes())
+            }
+            else
+            {
+                glob_match(pattern.as_bytes(), file_name.as_bytes())
+            }
+        };
+
+        (self.include.is_empty() == true || self.include.iter().any(matches_pattern)) && self.exclude.iter().any(matches_pattern) == false
+    }
+}
+
+/// Match `text` against a glob pattern (`*`, `?`, `**`)
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool
+{
+    match pattern
+    {
+        | [] => text.is_empty(),
+        | [b'*', b'*', rest @ ..] =>
+        {
+            // "**/" also matches no directory at all
+            let rest_after_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+            (0..=text.len()).any(|skip| glob_match(rest, &text[skip..]) || glob_match(rest_after_slash, &text[skip..]))
+        }
+        | [b'*', rest @ ..] => (0..=text.len()).take_while(|&skip| skip == 0 || text[skip - 1] != b'/').any(|skip| glob_match(rest, &text[skip..])),
+        | [b'?', rest @ ..] => text.first().is_some_and(|&byte| byte != b'/') && glob_match(rest, &text[1..]),
+        | [byte, rest @ ..] => text.first() == Some(byte) && glob_match(rest, &text[1..])
+    }
+}
+
+/// Collect the selected files below `directory`, sorted by path
+pub fn collect_files(directory: &Path, filter: &BatchFilter) -> io::Result<Vec<PathBuf>>
+{
+    let mut files = Vec::new();
+    walk(directory, "", filter, &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+/// Collect the files of several inputs in the order given: the selected files of directories, files as they are
+pub fn collect_inputs(inputs: &[PathBuf], filter: &BatchFilter) -> io::Result<Vec<PathBuf>>
+{
+    let mut files = Vec::new();
+    for input in inputs
+    {
+        if input.is_dir() == true
+        {
+            files.extend(collect_files(input, filter)?);
+        }
+        else
+        {
+            files.push(input.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Number of worker threads for `--jobs`: the given number, or one per available CPU for 0
+pub fn worker_count(jobs: usize) -> usize
+{
+    match jobs
+    {
+        | 0 => thread::available_parallelism().map(|count| count.get()).unwrap_or(1),
+        | jobs => jobs
+    }
+}
+
+/// Run `work` for every index below `count` on `workers` threads, passing the results to `emit` in index order
+///
+/// Results are emitted as soon as all earlier ones are done, so progress shows while later
+/// files are still being dissected.
+pub fn run_ordered<T: Send>(count: usize, workers: usize, work: impl Fn(usize) -> T + Sync, mut emit: impl FnMut(usize, T))
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1))
+        {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || {
+                loop
+                {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= count || sender.send((index, work(index))).is_err() == true
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Hold back results that finish before their predecessors
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (index, result) in receiver
+        {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&emitted)
+            {
+                emit(emitted, result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+/// Add the selected files of `directory` (at `prefix` relative to the batch directory) to `files`
+fn walk(directory: &Path, prefix: &str, filter: &BatchFilter, files: &mut Vec<PathBuf>) -> io::Result<()>
+{
+    for entry in fs::read_dir(directory)?
+    {
+        let entry = entry?;
+        let relative_path = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+        // Symbolic links to directories are not followed to avoid cycles
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() == true
+        {
+            if filter.recursive == true
+            {
+                walk(&entry.path(), &format!("{}/", relative_path), filter, files)?;
+            }
+        }
+        else if entry.path().is_file() == true && filter.matches(&relative_path) == true
+        {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+/// Aggregate results of a batch run
+#[derive(Debug, Clone, Default)]
+pub struct BatchSummary
+{
+    /// Number of files dissected successfully
+    pub files:         usize,
+    /// Number of layers per detected format
+    pub formats:       BTreeMap<String, usize>,
+    /// Number of files with warnings
+    pub with_warnings: usize,
+    /// Files that could not be dissected, with the error message
+    pub errors:        Vec<(PathBuf, String)>
+}
+
+impl BatchSummary
+{
+    /// Record a dissected file with the formats of its layers
+    pub fn add_file(&mut self, formats: &[&str], has_warnings: bool)
+    {
+        self.files += 1;
+        for format in formats
+        {
+            *self.formats.entry(format.to_string()).or_default() += 1;
+        }
+        if has_warnings == true
+        {
+            self.with_warnings += 1;
+        }
+    }
+
+    /// Record a file that could not be dissected
+    pub fn add_error(&mut self, path: &Path, error: String)
+    {
+        self.errors.push((path.to_path_buf(), error));
+    }
+
+    /// Build the batch report document from the per-file reports (see `schema/batch.schema.json`)
+    pub fn to_json(&self, inputs: &[PathBuf], reports: Vec<serde_json::Value>) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "directory": inputs[0].display().to_string(),
+            "inputs": inputs.iter().map(|input| input.display().to_string()).collect::<Vec<_>>(),
+            "files": reports,
+            "errors": self.errors.iter().map(|(path, error)| serde_json::json!({ "file": path.display().to_string(), "error": error })).collect::<Vec<_>>(),
+            "summary": {
+                "files": self.files,
+                "formats": self.formats,
+                "with_warnings": self.with_warnings,
+                "errors": self.errors.len()
+            }
+        })
+    }
+}
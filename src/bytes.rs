@@ -0,0 +1,52 @@
+// Fixed-width integers and zero-terminated strings read from parsed byte buffers
+//
+// The integer readers expect the caller to have checked that the value lies within the
+// buffer (parsers compare the buffer length against the field layout first) and panic
+// otherwise, like slice indexing. Strings end at a terminator the parser cannot know in
+// advance, so `read_cstring` reports a missing one instead.
+
+/// Read a big-endian u16 at `position`
+pub fn read_u16_be(data: &[u8], position: usize) -> u16
+{
+    u16::from_be_bytes([data[position], data[position + 1]])
+}
+
+/// Read a big-endian u32 at `position`
+pub fn read_u32_be(data: &[u8], position: usize) -> u32
+{
+    u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]])
+}
+
+/// Read a big-endian u64 at `position`
+pub fn read_u64_be(data: &[u8], position: usize) -> u64
+{
+    ((read_u32_be(data, position) as u64) << 32) | read_u32_be(data, position + 4) as u64
+}
+
+/// Read a little-endian u16 at `position`
+pub fn read_u16_le(data: &[u8], position: usize) -> u16
+{
+    u16::from_le_bytes([data[position], data[position + 1]])
+}
+
+/// Read a little-endian u32 at `position`
+pub fn read_u32_le(data: &[u8], position: usize) -> u32
+{
+    u32::from_le_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]])
+}
+
+/// Read a little-endian u64 at `position`
+pub fn read_u64_le(data: &[u8], position: usize) -> u64
+{
+    read_u32_le(data, position) as u64 | ((read_u32_le(data, position + 4) as u64) << 32)
+}
+
+/// Read a zero-terminated UTF-8 string at `position`, returning it and the position after the terminator
+///
+/// None if `position` lies beyond the buffer or the string has no terminator.
+pub fn read_cstring(data: &[u8], position: usize) -> Option<(String, usize)>
+{
+    let remaining = data.get(position..)?;
+    let length = remaining.iter().position(|&byte| byte == 0)?;
+    Some((String::from_utf8_lossy(&remaining[..length]).to_string(), position + length + 1))
+}
@@ -0,0 +1,412 @@
+// Carving of embedded media structures from raw binary data
+//
+// Scans a dump (disk image, memory dump, damaged file) byte by byte for ID3v2 headers,
+// ISOBMFF ftyp boxes, runs of MPEG audio frames and FLAC stream markers, and estimates
+// how far each structure extends so the candidates can be extracted for recovery.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read, SeekFrom},
+    path::{Path, PathBuf}
+};
+
+use crate::{i18n::tr_fmt, id3v2::tools::decode_synchsafe_int, media_reader::MediaReader, media_source::MediaSource, mpeg_audio::MpegFrameHeader};
+
+/// Number of bytes scanned per read
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Bytes at the end of a block that are rescanned with the next block so signatures spanning the boundary are found
+const LOOKAHEAD: usize = 16;
+
+/// Largest plausible size of a leading ftyp box
+const MAX_FTYP_SIZE: u32 = 4096;
+
+/// Media structure found in the scanned data
+#[derive(Debug, Clone)]
+pub struct Candidate
+{
+    pub offset:    u64,
+    pub length:    u64,
+    /// Whether the extent is a guess (up to the next candidate or the end of the data) rather than derived from the structure
+    pub estimated: bool,
+    pub format:    &'static str,
+    /// File extension used when extracting the candidate
+    pub extension: &'static str,
+    pub details:   String
+}
+
+/// Scan position: the current block and a reader for structures that extend past it
+struct Scanner<'a>
+{
+    reader:       MediaReader<'a>,
+    file_size:    u64,
+    block:        Vec<u8>,
+    block_offset: u64
+}
+
+impl Scanner<'_>
+{
+    /// Read `buffer.len()` bytes at `offset`, served from the current block when it covers the range
+    fn read_at(&mut self, offset: u64, buffer: &mut [u8]) -> io::Result<bool>
+    {
+        if offset + buffer.len() as u64 > self.file_size
+        {
+            return Ok(false);
+        }
+
+        if offset >= self.block_offset && offset + buffer.len() as u64 <= self.block_offset + self.block.len() as u64
+        {
+            let start = (offset - self.block_offset) as usize;
+            buffer.copy_from_slice(&self.block[start..start + buffer.len()]);
+        }
+        else
+        {
+            self.reader.seek_to(offset)?;
+            self.reader.read_exact(buffer)?;
+        }
+        Ok(true)
+    }
+
+    /// Follow consecutive MPEG audio frames matching the first header, returning the frame count, end offset and first header
+    fn follow_mpeg_run(&mut self, offset: u64) -> io::Result<Option<(usize, u64, MpegFrameHeader)>>
+    {
+        let mut header_bytes = [0u8; 4];
+        if self.read_at(offset, &mut header_bytes)? == false
+        {
+            return Ok(None);
+        }
+        let Some(first) = MpegFrameHeader::parse(&header_bytes)
+        else
+        {
+            return Ok(None);
+        };
+
+        let mut frames = 0;
+        let mut position = offset;
+        while self.read_at(position, &mut header_bytes)? == true
+        {
+            match MpegFrameHeader::parse(&header_bytes)
+            {
+                | Some(header) if header.version == first.version && header.layer == first.layer && header.sample_rate == first.sample_rate =>
+                {
+                    frames += 1;
+                    position += header.frame_length() as u64;
+                }
+                | _ => break
+            }
+        }
+
+        Ok(Some((frames, position.min(self.file_size), first)))
+    }
+
+    /// Extend the end of an MPEG audio run over a directly following ID3v1 tag
+    fn include_id3v1(&mut self, end: u64) -> io::Result<u64>
+    {
+        let mut marker = [0u8; 3];
+        if end + 128 <= self.file_size && self.read_at(end, &mut marker)? == true && &marker == b"TAG"
+        {
+            return Ok(end + 128);
+        }
+        Ok(end)
+    }
+
+    /// Walk top-level ISOBMFF boxes starting at `offset`, returning the end of the last plausible box
+    fn walk_boxes(&mut self, offset: u64) -> io::Result<(u64, bool)>
+    {
+        let mut position = offset;
+        let mut header = [0u8; 16];
+
+        while self.read_at(position, &mut header[..8])? == true
+        {
+            let box_type = &header[4..8];
+            if box_type.iter().all(|&byte| byte.is_ascii_alphanumeric() || byte == b' ' || byte == 0xA9) == false
+            {
+                break;
+            }
+
+            let size = match u32::from_be_bytes([header[0], header[1], header[2], header[3]])
+            {
+                | 0 => self.file_size - position,
+                | 1 =>
+                {
+                    if self.read_at(position, &mut header)? == false
+                    {
+                        break;
+                    }
+                    u64::from_be_bytes([header[8], header[9], header[10], header[11], header[12], header[13], header[14], header[15]])
+                }
+                | size => size as u64
+            };
+            if size < 8
+            {
+                break;
+            }
+
+            if position + size > self.file_size
+            {
+                // Truncated box: the stream runs to the end of the data
+                return Ok((self.file_size, true));
+            }
+            position += size;
+        }
+
+        Ok((position, false))
+    }
+
+    /// Check for an ID3v2 tag (optionally followed by MPEG audio) at `offset`
+    fn probe_id3(&mut self, bytes: &[u8], offset: u64, min_frames: usize) -> io::Result<Option<(Candidate, u64)>>
+    {
+        if bytes.len() < 10 || &bytes[0..3] != b"ID3" || (2..=4).contains(&bytes[3]) == false || bytes[4] == 0xFF || bytes[6..10].iter().any(|&byte| byte & 0x80 != 0)
+        {
+            return Ok(None);
+        }
+
+        let version = bytes[3];
+        let footer = if version == 4 && bytes[5] & 0x10 != 0
+        {
+            10
+        }
+        else
+        {
+            0
+        };
+        let tag_end = offset + 10 + decode_synchsafe_int(&bytes[6..10]) as u64 + footer;
+
+        if tag_end > self.file_size
+        {
+            let candidate = Candidate {
+                offset,
+                length: self.file_size - offset,
+                estimated: true,
+                format: "ID3v2",
+                extension: "id3",
+                details: tr_fmt("ID3v2.{} tag, truncated", &[&version])
+            };
+            // The size may be corrupt, so keep scanning inside the claimed tag
+            return Ok(Some((candidate, offset + 10)));
+        }
+
+        let candidate = match self.follow_mpeg_run(tag_end)?
+        {
+            | Some((frames, end, header)) if frames >= min_frames => Candidate {
+                offset,
+                length: self.include_id3v1(end)? - offset,
+                estimated: false,
+                format: "MP3",
+                extension: "mp3",
+                details: tr_fmt("ID3v2.{} tag, {} MPEG frames, {} Hz", &[&version, &frames, &header.sample_rate])
+            },
+            | _ => Candidate {
+                offset,
+                length: tag_end - offset,
+                estimated: false,
+                format: "ID3v2",
+                extension: "id3",
+                details: tr_fmt("ID3v2.{} tag without following audio", &[&version])
+            }
+        };
+        let resume = candidate.offset + candidate.length;
+        Ok(Some((candidate, resume)))
+    }
+
+    /// Check for an ISOBMFF stream starting with an ftyp box at `offset`
+    fn probe_isobmff(&mut self, bytes: &[u8], offset: u64) -> io::Result<Option<(Candidate, u64)>>
+    {
+        if bytes.len() < 12 || &bytes[4..8] != b"ftyp"
+        {
+            return Ok(None);
+        }
+        let ftyp_size = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let brand = &bytes[8..12];
+        if (16..=MAX_FTYP_SIZE).contains(&ftyp_size) == false || brand.iter().all(|&byte| byte.is_ascii_graphic() || byte == b' ') == false
+        {
+            return Ok(None);
+        }
+
+        let (end, truncated) = self.walk_boxes(offset)?;
+        let brand = String::from_utf8_lossy(brand).to_string();
+        let extension = match brand.as_str()
+        {
+            | "M4A " | "M4B " | "M4P " => "m4a",
+            | "qt  " => "mov",
+            | brand if brand.starts_with("3gp") == true => "3gp",
+            | _ => "mp4"
+        };
+        let details = if truncated == true
+        {
+            tr_fmt("brand '{}', truncated", &[&brand])
+        }
+        else
+        {
+            tr_fmt("brand '{}'", &[&brand])
+        };
+
+        let candidate = Candidate { offset, length: end - offset, estimated: truncated, format: "ISOBMFF", extension, details };
+        Ok(Some((candidate, end)))
+    }
+
+    /// Check for a FLAC stream marker followed by a STREAMINFO block at `offset`
+    fn probe_flac(&mut self, bytes: &[u8], offset: u64) -> io::Result<Option<(Candidate, u64)>>
+    {
+        if bytes.len() < 8 || &bytes[0..4] != b"fLaC" || bytes[4] & 0x7F != 0 || u32::from_be_bytes([0, bytes[5], bytes[6], bytes[7]]) != 34
+        {
+            return Ok(None);
+        }
+
+        let mut stream_info = [0u8; 34];
+        if self.read_at(offset + 8, &mut stream_info)? == false
+        {
+            return Ok(None);
+        }
+        let sample_rate = (u32::from_be_bytes([0, stream_info[10], stream_info[11], stream_info[12]]) >> 4) as u64;
+        let channels = ((stream_info[12] >> 1) & 0x07) + 1;
+
+        // Skip the metadata blocks, the audio frames behind them have no length field
+        let mut position = offset + 4;
+        let mut block_header = [0u8; 4];
+        while self.read_at(position, &mut block_header)? == true
+        {
+            position += 4 + u32::from_be_bytes([0, block_header[1], block_header[2], block_header[3]]) as u64;
+            if block_header[0] & 0x80 != 0
+            {
+                break;
+            }
+        }
+        let metadata_end = position.min(self.file_size);
+
+        let candidate = Candidate {
+            offset,
+            length: self.file_size - offset,
+            estimated: true,
+            format: "FLAC",
+            extension: "flac",
+            details: tr_fmt("{} Hz, {} channels", &[&sample_rate, &channels])
+        };
+        Ok(Some((candidate, metadata_end)))
+    }
+
+    /// Check for a run of at least `min_frames` MPEG audio frames at `offset`
+    fn probe_mpeg(&mut self, bytes: &[u8], offset: u64, min_frames: usize) -> io::Result<Option<(Candidate, u64)>>
+    {
+        if MpegFrameHeader::parse(bytes).is_none() == true
+        {
+            return Ok(None);
+        }
+
+        match self.follow_mpeg_run(offset)?
+        {
+            | Some((frames, end, header)) if frames >= min_frames =>
+            {
+                let end = self.include_id3v1(end)?;
+                let candidate = Candidate {
+                    offset,
+                    length: end - offset,
+                    estimated: false,
+                    format: "MP3",
+                    extension: "mp3",
+                    details: tr_fmt("{} MPEG frames, {} Hz", &[&frames, &header.sample_rate])
+                };
+                Ok(Some((candidate, end)))
+            }
+            | _ => Ok(None)
+        }
+    }
+}
+
+/// Scan `file` for embedded media structures
+///
+/// Structures with a known extent are skipped over, so MPEG sync patterns inside an MP4
+/// stream are not reported separately. FLAC streams carry no overall length and are
+/// assumed to run up to the next candidate.
+pub fn scan(file: &mut dyn MediaSource, min_frames: usize) -> Result<Vec<Candidate>, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+    let mut scanner = Scanner { reader: MediaReader::new(file, 0)?, file_size, block: Vec::new(), block_offset: 0 };
+    let mut candidates: Vec<Candidate> = Vec::new();
+
+    let mut offset = 0;
+    while offset < file_size
+    {
+        let length = (file_size - offset).min(BLOCK_SIZE as u64) as usize;
+        scanner.block.resize(length, 0);
+        scanner.reader.seek_to(offset)?;
+        scanner.reader.read_exact(&mut scanner.block)?;
+        scanner.block_offset = offset;
+
+        let scan_end = if offset + length as u64 == file_size
+        {
+            length
+        }
+        else
+        {
+            length - LOOKAHEAD
+        };
+        let mut next_offset = offset + scan_end as u64;
+
+        for position in 0..scan_end
+        {
+            let window = &scanner.block[position..(position + LOOKAHEAD).min(length)];
+            let is_ftyp = window.len() >= 8 && &window[4..8] == b"ftyp";
+            if matches!(window[0], b'I' | b'f' | 0xFF) == false && is_ftyp == false
+            {
+                continue;
+            }
+
+            // Copy the signature bytes so the probes can read further through the scanner
+            let mut signature = [0u8; LOOKAHEAD];
+            signature[..window.len()].copy_from_slice(window);
+            let bytes = &signature[..window.len()];
+
+            let candidate_offset = offset + position as u64;
+            let found = match bytes[0]
+            {
+                | b'I' => scanner.probe_id3(bytes, candidate_offset, min_frames)?,
+                | b'f' => scanner.probe_flac(bytes, candidate_offset)?,
+                | 0xFF => scanner.probe_mpeg(bytes, candidate_offset, min_frames)?,
+                | _ if is_ftyp == true => scanner.probe_isobmff(bytes, candidate_offset)?,
+                | _ => None
+            };
+
+            if let Some((candidate, resume_offset)) = found
+            {
+                candidates.push(candidate);
+                next_offset = resume_offset.max(candidate_offset + 1);
+                break;
+            }
+        }
+
+        offset = next_offset;
+    }
+
+    // Estimated extents end where the next structure begins
+    for index in 1..candidates.len()
+    {
+        let next_offset = candidates[index].offset;
+        let previous = &mut candidates[index - 1];
+        if previous.estimated == true && previous.offset + previous.length > next_offset
+        {
+            previous.length = next_offset - previous.offset;
+        }
+    }
+
+    Ok(candidates)
+}
+
+/// Write every candidate to its own file in `directory`, returning the created paths
+pub fn extract(file: &mut dyn MediaSource, candidates: &[Candidate], directory: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>>
+{
+    fs::create_dir_all(directory)?;
+
+    let mut paths = Vec::new();
+    for (index, candidate) in candidates.iter().enumerate()
+    {
+        let path = directory.join(format!("{:03}_0x{:08X}.{}", index + 1, candidate.offset, candidate.extension));
+        let mut output = File::create(&path)?;
+
+        file.seek(SeekFrom::Start(candidate.offset))?;
+        io::copy(&mut (&mut *file).take(candidate.length), &mut output)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,202 @@
+// Chapter export
+//
+// Chapters collected from ID3v2 CHAP/CTOC frames and ISOBMFF chapter tracks, exported as
+// cue sheet, FFmpeg metadata, WebVTT or JSON (see `schema/chapters.schema.json`). Chapter
+// pictures can be written to files named after the chapters.
+
+use std::{
+    fs,
+    path::{Path, PathBuf}
+};
+
+use clap::ValueEnum;
+
+use crate::{artwork::Artwork, report::SCHEMA_VERSION};
+
+/// Chapter with start and end time in milliseconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter
+{
+    pub start: u64,
+    pub end:   u64,
+    /// Chapter title (the element ID or a numbered placeholder if the chapter has no title)
+    pub title: String,
+    /// Link of the chapter (URL sub-frame of an ID3v2 CHAP frame)
+    pub url:   Option<String>,
+    /// Picture of the chapter (APIC sub-frame of an ID3v2 CHAP frame)
+    pub image: Option<Artwork>
+}
+
+/// Export format of the chapters command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ChapterFormat
+{
+    /// Cue sheet with one track per chapter
+    Cue,
+    /// FFmpeg metadata file (`ffmpeg -i in -i chapters.txt -map_metadata 1 ...`)
+    Ffmetadata,
+    /// WebVTT chapter track
+    Webvtt,
+    /// JSON document (see schema/chapters.schema.json)
+    Json
+}
+
+/// Format milliseconds as HH:MM:SS.mmm
+pub fn format_time(ms: u64) -> String
+{
+    format!("{:02}:{:02}:{:02}.{:03}", ms / 3_600_000, (ms / 60_000) % 60, (ms / 1000) % 60, ms % 1000)
+}
+
+/// Cue sheet file type for the referenced media file
+fn cue_file_type(file_path: &Path) -> &'static str
+{
+    match file_path.extension().map(|extension| extension.to_string_lossy().to_ascii_lowercase()).as_deref()
+    {
+        | Some("mp3") => "MP3",
+        | Some("wav") => "WAVE",
+        | Some("aif" | "aiff") => "AIFF",
+        | _ => "MP4"
+    }
+}
+
+/// Escape `=`, `;`, `#`, `\` and line breaks in FFmpeg metadata values
+fn escape_ffmetadata(value: &str) -> String
+{
+    let mut escaped = String::new();
+    for character in value.chars()
+    {
+        if matches!(character, '=' | ';' | '#' | '\\' | '\n') == true
+        {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+/// Make a cue sheet string value one line without double quotes: line breaks become spaces, '"' becomes '\''
+fn escape_cue(value: &str) -> String
+{
+    value
+        .chars()
+        .map(|character| match character
+        {
+            | '\r' | '\n' => ' ',
+            | '"' => '\'',
+            | character => character
+        })
+        .collect()
+}
+
+/// Make a WebVTT cue payload one line: runs of line breaks become one space (a blank line would end the cue), "-->" becomes "->"
+fn escape_webvtt(value: &str) -> String
+{
+    let mut escaped = String::new();
+    for line in value.split(['\r', '\n']).filter(|line| line.is_empty() == false)
+    {
+        if escaped.is_empty() == false
+        {
+            escaped.push(' ');
+        }
+        escaped.push_str(line);
+    }
+    escaped.replace("-->", "->")
+}
+
+/// Make text (a chapter title, an object description) usable as file name: path separators, characters Windows rejects and control characters become '_'
+pub fn file_name_from_text(text: &str) -> String
+{
+    let name: String = text
+        .chars()
+        .map(|character| match character
+        {
+            | '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            | character if character.is_control() == true => '_',
+            | character => character
+        })
+        .collect();
+    // Windows drops trailing dots and spaces, which would make names collide
+    name.trim().trim_end_matches('.').to_string()
+}
+
+/// Write the picture of every chapter to `directory` as `<nn> - <title>.<extension>`, returning the chapter index and created path
+///
+/// The number is the position of the chapter, so chapters without a picture leave gaps in the numbering.
+pub fn extract_art(chapters: &[Chapter], directory: &Path) -> Result<Vec<(usize, PathBuf)>, Box<dyn std::error::Error>>
+{
+    fs::create_dir_all(directory)?;
+
+    let mut paths = Vec::new();
+    for (index, chapter) in chapters.iter().enumerate()
+    {
+        let Some(ref image) = chapter.image
+        else
+        {
+            continue;
+        };
+        let name = file_name_from_text(&chapter.title);
+        let file_name = match name.is_empty()
+        {
+            | true => format!("{:02}.{}", index + 1, image.extension()),
+            | false => format!("{:02} - {}.{}", index + 1, name, image.extension())
+        };
+        let path = directory.join(file_name);
+        fs::write(&path, &image.data)?;
+        paths.push((index, path));
+    }
+
+    Ok(paths)
+}
+
+/// Render `chapters` of the media file at `file_path` in the given format
+pub fn export(chapters: &[Chapter], format: ChapterFormat, file_path: &Path) -> String
+{
+    let mut output = String::new();
+    match format
+    {
+        | ChapterFormat::Cue =>
+        {
+            let file_name = file_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            output.push_str(&format!("FILE \"{}\" {}\n", escape_cue(&file_name), cue_file_type(file_path)));
+            for (index, chapter) in chapters.iter().enumerate()
+            {
+                // Cue sheet indices count frames of 1/75 second
+                let frames = chapter.start * 75 / 1000;
+                output.push_str(&format!("  TRACK {:02} AUDIO\n", index + 1));
+                output.push_str(&format!("    TITLE \"{}\"\n", escape_cue(&chapter.title)));
+                output.push_str(&format!("    INDEX 01 {:02}:{:02}:{:02}\n", frames / (75 * 60), (frames / 75) % 60, frames % 75));
+            }
+        }
+        | ChapterFormat::Ffmetadata =>
+        {
+            output.push_str(";FFMETADATA1\n");
+            for chapter in chapters
+            {
+                output.push_str(&format!("\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n", chapter.start, chapter.end, escape_ffmetadata(&chapter.title)));
+            }
+        }
+        | ChapterFormat::Webvtt =>
+        {
+            output.push_str("WEBVTT\n");
+            for (index, chapter) in chapters.iter().enumerate()
+            {
+                output.push_str(&format!("\n{}\n{} --> {}\n{}\n", index + 1, format_time(chapter.start), format_time(chapter.end), escape_webvtt(&chapter.title)));
+            }
+        }
+        | ChapterFormat::Json =>
+        {
+            let document = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "file": file_path.display().to_string(),
+                "chapters": chapters.iter().map(|chapter| serde_json::json!({
+                    "start": chapter.start as f64 / 1000.0,
+                    "end": chapter.end as f64 / 1000.0,
+                    "title": chapter.title
+                })).collect::<Vec<_>>()
+            });
+            output.push_str(&serde_json::to_string_pretty(&document).unwrap_or_default());
+            output.push('\n');
+        }
+    }
+    output
+}
@@ -1,75 +1,661 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use the_drill::{
+    batch::BatchFilter,
+    chapters::ChapterFormat,
+    color::ColorChoice,
+    i18n::{Language, tr_fmt},
+    isobmff::{boxes::sample_table::DEFAULT_MAX_TABLE_ENTRIES, samples::SampleRange},
+    media_source::STDIN_PATH,
+    options::{DissectOptions, DumpLimit, TagSizeThresholds},
+    path_filter::{PathFilter, PathPattern}
+};
 
 #[derive(Parser)]
 #[command(name = "the-drill")]
 #[command(about = "A versatile media file analysis tool")]
 #[command(version)]
+#[command(after_help = "Exit codes:\n  0  Success\n  1  Success with warnings\n  2  Unknown file format\n  3  Parse failure\n  4  I/O error\n  5  Specification \
+                        violations found (validate)\n  6  Invalid command line arguments")]
 pub struct Cli
 {
     #[command(subcommand)]
-    pub command: Commands
+    pub command: Commands,
+
+    /// Language of the text output (defaults to the locale from LC_ALL/LC_MESSAGES/LANG)
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Language>,
+
+    /// When to color the text output (auto: only on a terminal and if NO_COLOR is not set)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice
 }
 
 #[derive(Subcommand)]
 pub enum Commands
 {
     /// Dissect and analyze media files
-    Dissect
-    {
-        /// Path to the media file to analyze
-        file: PathBuf,
+    Dissect(Box<DissectArgs>),
+    /// Scan raw data (disk images, dumps) for embedded media and optionally extract it
+    Carve(CarveArgs),
+    /// Export the chapters of a media file (ID3v2 CHAP frames, MP4 chapter tracks)
+    Chapters(ChaptersArgs),
+    /// Check a media file for specification violations (ID3v2 tags, ISOBMFF box structure)
+    Validate(ValidateArgs),
+    /// Print a compact overview of a media file (one line per ISOBMFF track)
+    Summary(SummaryArgs),
+    /// Print a byte map of a media file: tags, audio data and boxes with their sizes and shares of the file
+    Layout(LayoutArgs),
+    /// Print the movie fragments of a fragmented MP4 with their timing, flagging gaps and overlaps between fragments
+    Fragments(FragmentsArgs),
+    /// Check an HLS media playlist: dissect its segments and compare their durations, discontinuities and encryption with the playlist
+    Playlist(PlaylistArgs),
+    /// Find every box or frame of a type (e.g. moof, CHAP), also by scanning the raw bytes of corrupt files
+    Find(FindArgs),
+    /// Report wasted space (tag padding, free space boxes, duplicate or oversized artwork, moov after mdat) or move moov to the front
+    Optimize(OptimizeArgs),
+    /// Compare the structure of two media files: added, removed, changed and moved boxes and frames
+    Diff(DiffArgs),
+    /// Edit the ID3v2 tag of an MP3 file (the file is rewritten through a temporary file)
+    Tag(TagArgs),
+    /// Write raw payloads of a media file to disk for codec-level debugging
+    Extract(ExtractArgs)
+}
 
-        /// Show only file header information
-        #[arg(long)]
-        header: bool,
+/// Arguments of the dissect command
+#[derive(Args)]
+pub struct DissectArgs
+{
+    /// Path to the media file to analyze ("-" reads standard input), or a directory whose files are dissected and summarized
+    #[arg(required_unless_present = "segment")]
+    pub file: Option<PathBuf>,
 
-        /// Show only data structures (ID3v2 frames, ISOBMFF boxes)
-        #[arg(long)]
-        data: bool,
+    /// Further files or directories; several inputs are dissected and summarized like a directory
+    #[arg(value_name = "MORE")]
+    pub more: Vec<PathBuf>,
 
-        /// Show both header and data (default if no options specified)
-        #[arg(long)]
-        all: bool,
+    /// Number of files dissected in parallel for directories and several inputs (0: one per CPU)
+    #[arg(long, short, value_name = "N", default_value_t = 0)]
+    pub jobs: usize,
 
-        /// Show verbose output including large technical boxes (mdat, free, stts, stsc, stsz, stco, ctts)
-        #[arg(long, short)]
-        verbose: bool,
+    /// Also dissect the files in subdirectories when FILE is a directory
+    #[arg(long, short)]
+    pub recursive: bool,
 
-        /// Show hexdump of frame/box data
-        #[arg(long, short)]
-        dump: bool
-    }
+    /// Only dissect files matching this glob pattern when FILE is a directory (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files matching this glob pattern when FILE is a directory (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Start dissection at this byte offset instead of the beginning of the file (decimal or 0x-prefixed hex)
+    #[arg(long, value_name = "BYTES", value_parser = parse_byte_count)]
+    pub offset: Option<u64>,
+
+    /// Dissect at most this many bytes, e.g. an MP4 embedded in another file (decimal or 0x-prefixed hex)
+    #[arg(long, value_name = "BYTES", value_parser = parse_byte_count)]
+    pub length: Option<u64>,
+
+    /// Initialization segment of a DASH or CMAF media segment: its movie box supplies the track IDs, timescales and trex sample defaults
+    #[arg(long, value_name = "FILE", conflicts_with = "more")]
+    pub init: Option<PathBuf>,
+
+    /// Media segment to dissect with the track context of --init (instead of FILE)
+    #[arg(long, value_name = "FILE", requires = "init", conflicts_with = "file")]
+    pub segment: Option<PathBuf>,
+
+    /// Show only file header information
+    #[arg(long)]
+    pub header: bool,
+
+    /// Show only data structures (ID3v2 frames, ISOBMFF boxes)
+    #[arg(long)]
+    pub data: bool,
+
+    /// Show both header and data (default if no options specified)
+    #[arg(long)]
+    pub all: bool,
+
+    /// Show verbose output including large technical boxes (mdat, free, stts, stsc, stsz, stco, ctts)
+    #[arg(long, short)]
+    pub verbose: bool,
+
+    /// Show hexdump of frame/box data
+    #[arg(long, short)]
+    pub dump: bool,
+
+    /// Bytes shown per hexdump, or 'full' for all data
+    #[arg(long, value_name = "BYTES|full", default_value_t = DumpLimit::default())]
+    pub dump_limit: DumpLimit,
+
+    /// Only dump the frames/boxes matching this path, e.g. APIC or moov/trak/mdia/minf/stbl/stsd (repeatable, implies --dump)
+    #[arg(long, value_name = "PATH")]
+    pub dump_path: Vec<PathPattern>,
+
+    /// Report byte ranges not covered by any recognized structure (with hexdumps of small gaps)
+    #[arg(long)]
+    pub gaps: bool,
+
+    /// Print MD5 and SHA-256 of each region (tags, audio data, mdat payloads), e.g. to verify that retagging left the audio untouched
+    #[arg(long)]
+    pub hashes: bool,
+
+    /// Gather the MusicBrainz and AcoustID identifiers of all frame and item types (TXXX, UFID, iTunes freeform items), decoding AcoustID fingerprints
+    #[arg(long)]
+    pub music_ids: bool,
+
+    /// Gather the loudness normalization data of all layers: ReplayGain TXXX frames and items, RVA2 frames, iTunes Sound Check (decoded to dB)
+    #[arg(long)]
+    pub loudness: bool,
+
+    /// Decode the gapless playback information (LAME encoder delay and padding, iTunes iTunSMPB) and the samples players trim
+    #[arg(long)]
+    pub gapless: bool,
+
+    /// Gather the podcast data of all layers: iTunes podcast frames and items (feed URL, episode ID, description, category, keywords) and the chapters with their
+    /// links and pictures
+    #[arg(long)]
+    pub podcast: bool,
+
+    /// Show sample table statistics per track (duration histogram, sample sizes, chunk layout)
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Annotate every ID3v2 frame and ISOBMFF box with the specification section that defines it
+    #[arg(long)]
+    pub spec: bool,
+
+    /// Analyze embedded pictures (APIC frames, iTunes cover art): format, dimensions, color, JFIF and EXIF metadata (camera, date, GPS)
+    #[arg(long)]
+    pub inspect_art: bool,
+
+    /// Dissect embedded pictures and objects (APIC, covr, GEOB, PRIV) with the dissector of their format, e.g. an MP4 file stored in a GEOB frame
+    #[arg(long)]
+    pub payloads: bool,
+
+    /// Only show boxes/frames matching this path, e.g. moov/trak/mdia/minf/stbl/stsd or CHAP/* (repeatable, * matches one element, ** any number)
+    #[arg(long, value_name = "PATH")]
+    pub filter: Vec<PathPattern>,
+
+    /// Maximum number of entries parsed per sample table (stts, stsc, stsz, stco, co64)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_TABLE_ENTRIES)]
+    pub max_table_entries: usize,
+
+    /// On corrupt boxes or frames, report a warning and resynchronize on the next plausible header instead of aborting
+    #[arg(long)]
+    pub recover: bool,
+
+    /// Write embedded artwork (APIC frames, iTunes cover art) to image files in this directory
+    #[arg(long, value_name = "DIR")]
+    pub extract_art: Option<PathBuf>,
+
+    /// Write the pictures of ID3v2 chapters (APIC frames in CHAP frames) to this directory, named "<nn> - <chapter title>"
+    #[arg(long, value_name = "DIR")]
+    pub extract_chapter_art: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DissectFormat::Text)]
+    pub format: DissectFormat,
+
+    /// With --format json, print the metadata normalized across formats (title, artist, album, ...) instead of the full report
+    #[arg(long)]
+    pub normalized: bool,
+
+    /// With --format json, also report the parsed contents of ISOBMFF boxes and ID3v2 frames as the lines of the text output
+    #[arg(long, conflicts_with = "normalized")]
+    pub details: bool,
+
+    /// With --format text, print an outline of the layers, ISOBMFF boxes, ID3v2 frames and findings instead of the full output
+    #[arg(long, conflicts_with = "normalized")]
+    pub outline: bool,
+
+    /// Print nothing, only report the result through the exit code (errors still go to standard error)
+    #[arg(long, short, conflicts_with_all = ["extract_art", "extract_chapter_art", "normalized", "outline"])]
+    pub quiet: bool,
+
+    /// Tag size in MB above which an informational note is reported (0 disables)
+    #[arg(long, value_name = "MB", default_value_t = 10)]
+    pub tag_size_info: u64,
+
+    /// Tag size in MB above which a warning is reported (0 disables)
+    #[arg(long, value_name = "MB", default_value_t = 50)]
+    pub tag_size_warning: u64,
+
+    /// Tag size in MB above which the tag is reported as implausible (0 disables)
+    #[arg(long, value_name = "MB", default_value_t = 100)]
+    pub tag_size_critical: u64
+}
+
+/// Arguments of the carve command
+#[derive(Args)]
+pub struct CarveArgs
+{
+    /// Path to the raw data to scan ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Write each candidate to its own file in this directory
+    #[arg(long, value_name = "DIR")]
+    pub extract: Option<PathBuf>,
+
+    /// Minimum number of consecutive MPEG audio frames reported as an audio stream
+    #[arg(long, value_name = "N", default_value_t = 4)]
+    pub min_frames: usize
+}
+
+/// Arguments of the chapters command
+#[derive(Args)]
+pub struct ChaptersArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Export format
+    #[arg(long, value_enum, default_value_t = ChapterFormat::Json)]
+    pub format: ChapterFormat,
+
+    /// Write the export to this file instead of standard output
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>
+}
+
+/// Arguments of the validate command
+#[derive(Args)]
+pub struct ValidateArgs
+{
+    /// Path to the media file to check ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Print nothing, only report the result through the exit code (errors still go to standard error)
+    #[arg(long, short)]
+    pub quiet: bool
+}
+
+/// Arguments of the summary command
+#[derive(Args)]
+pub struct SummaryArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the layout command
+#[derive(Args)]
+pub struct LayoutArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Nesting levels shown below the layers and top-level boxes (frames, child boxes, ...)
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub depth: usize,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the fragments command
+#[derive(Args)]
+pub struct FragmentsArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the playlist command
+#[derive(Args)]
+pub struct PlaylistArgs
+{
+    /// Path to the media playlist (.m3u8); segments are read relative to it
+    pub file: PathBuf,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the optimize command
+#[derive(Args)]
+#[command(group(ArgGroup::new("mode").required(true)))]
+pub struct OptimizeArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Report the reclaimable space and suggested actions without changing the file
+    #[arg(long, group = "mode")]
+    pub report: bool,
+
+    /// Move the movie box in front of the media data for progressive playback, updating the chunk offsets (the file is rewritten through a temporary file)
+    #[arg(long, group = "mode")]
+    pub faststart: bool,
+
+    /// Output format of the report (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the find command
+#[derive(Args)]
+pub struct FindArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Box type or frame ID to search for (four characters, e.g. moof, stsd, ©nam, APIC)
+    pub code: String,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the diff command
+#[derive(Args)]
+pub struct DiffArgs
+{
+    /// Original media file
+    pub old: PathBuf,
+
+    /// Modified media file, e.g. after running a tagging tool
+    pub new: PathBuf,
+
+    /// Output format (text or json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat
+}
+
+/// Arguments of the tag command
+#[derive(Args)]
+pub struct TagArgs
+{
+    #[command(subcommand)]
+    pub command: TagCommands
+}
+
+/// Tag editing operations
+#[derive(Subcommand)]
+pub enum TagCommands
+{
+    /// Add text frames or iTunes items or replace their values, e.g. TIT2="New title", "TXXX:CATALOG=X123" or ©nam="New title"
+    Set(TagSetArgs),
+    /// Remove frames or iTunes items, e.g. COMM, TXXX:CATALOG or covr
+    Remove(TagRemoveArgs),
+    /// Remove the ID3v2 tag (and with --id3v1 the ID3v1 tag), leaving the audio untouched
+    Strip(TagStripArgs),
+    /// Rewrite an ID3v2.3 tag as ID3v2.4, renaming or merging the frames ID3v2.4 replaced
+    Convert(TagConvertArgs),
+    /// Rewrite the ID3v2 tag with the given amount of padding, moving the audio data after it
+    Repad(TagRepadArgs)
+}
+
+/// Arguments of the tag set command
+#[derive(Args)]
+pub struct TagSetArgs
+{
+    /// MP3 file (a new ID3v2.4 tag is added if it has none) or MP4/M4A file to edit
+    pub file: PathBuf,
+
+    /// Frames to set: frame ID (or TXXX:<description>) or iTunes item key (e.g. ©ART, trkn, covr, ----:<mean>:<name>), '=' and the value
+    #[arg(required = true, allow_hyphen_values = true, value_name = "FRAME=VALUE", value_parser = parse_frame_assignment)]
+    pub frames: Vec<(String, String)>
 }
 
-/// Options for controlling dissect output
-#[derive(Debug, Clone)]
-pub struct DissectOptions
+/// Arguments of the tag remove command
+#[derive(Args)]
+pub struct TagRemoveArgs
 {
-    pub show_header:  bool,
-    pub show_data:    bool,
-    pub show_verbose: bool,
-    pub show_dump:    bool
+    /// MP3 or MP4/M4A file to edit
+    pub file: PathBuf,
+
+    /// Frames to remove: frame ID (all frames with this ID), TXXX:<description> or iTunes item key
+    #[arg(required = true, allow_hyphen_values = true, value_name = "FRAME")]
+    pub frames: Vec<String>
+}
+
+/// Arguments of the tag strip command
+#[derive(Args)]
+pub struct TagStripArgs
+{
+    /// MP3 file to edit
+    pub file: PathBuf,
+
+    /// Also remove the ID3v1 tag at the end of the file
+    #[arg(long)]
+    pub id3v1: bool
+}
+
+/// Arguments of the tag convert command
+#[derive(Args)]
+pub struct TagConvertArgs
+{
+    /// MP3 file to edit
+    pub file: PathBuf,
+
+    /// ID3v2 version to convert the tag to
+    #[arg(long, value_enum, value_name = "VERSION", default_value_t = TagVersion::V2_4)]
+    pub to: TagVersion
+}
+
+/// Arguments of the tag repad command
+#[derive(Args)]
+pub struct TagRepadArgs
+{
+    /// MP3 file to edit
+    pub file: PathBuf,
+
+    /// Padding of the rewritten tag in bytes (0 removes it; some padding lets later edits happen in place)
+    #[arg(long, value_name = "BYTES")]
+    pub padding: u32
+}
+
+/// Arguments of the extract command
+#[derive(Args)]
+pub struct ExtractArgs
+{
+    #[command(subcommand)]
+    pub command: ExtractCommands
+}
+
+/// Extraction operations
+#[derive(Subcommand)]
+pub enum ExtractCommands
+{
+    /// Write the raw sample payloads of an ISOBMFF track (e.g. AAC frames, H.264 access units), located through stsc/stsz/stco or trun
+    Samples(ExtractSamplesArgs),
+    /// Demux an audio track: AAC into an ADTS stream (.aac), FLAC into a FLAC file, ALAC into a Core Audio Format file (.caf)
+    Audio(ExtractAudioArgs),
+    /// Write every embedded binary object (APIC pictures, GEOB, PRIV and UFID payloads) with a JSON manifest
+    Objects(ExtractObjectsArgs)
+}
+
+/// Arguments of the extract samples command
+#[derive(Args)]
+pub struct ExtractSamplesArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Track ID of the track whose samples are written (see the summary command)
+    #[arg(long, value_name = "ID")]
+    pub track: u32,
+
+    /// Samples to write, counted from 1: a..b (inclusive), a.., ..b or a single sample number (default: all samples)
+    #[arg(long, value_name = "RANGE")]
+    pub range: Option<SampleRange>,
+
+    /// Directory the samples are written to, one file per sample (created if missing)
+    #[arg(long, short, value_name = "DIR")]
+    pub output: PathBuf
 }
 
-impl DissectOptions
+/// Arguments of the extract audio command
+#[derive(Args)]
+pub struct ExtractAudioArgs
 {
-    pub fn from_flags(header: bool, data: bool, all: bool, verbose: bool, dump: bool) -> Self
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Track ID of the audio track (see the summary command)
+    #[arg(long, value_name = "ID")]
+    pub track: u32,
+
+    /// Output file (default: <input name>_track<ID>.<aac|flac|caf> in the current directory)
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>
+}
+
+/// Arguments of the extract objects command
+#[derive(Args)]
+pub struct ExtractObjectsArgs
+{
+    /// Path to the media file ("-" reads standard input)
+    pub file: PathBuf,
+
+    /// Directory the objects and manifest.json are written to (created if missing)
+    #[arg(long, short, visible_alias = "out", value_name = "DIR")]
+    pub output: PathBuf
+}
+
+/// ID3v2 version a tag is converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TagVersion
+{
+    /// ID3v2.4
+    #[value(name = "2.4")]
+    V2_4
+}
+
+/// Output format of the dissect command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DissectFormat
+{
+    /// Human-readable, colored text
+    Text,
+    /// Structured JSON report (see schema/report.schema.json)
+    Json,
+    /// JSON in the layout of `ffprobe -show_format -show_streams -of json`
+    Ffprobe
+}
+
+/// Output format of the analysis commands other than dissect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat
+{
+    /// Human-readable, colored text
+    Text,
+    /// JSON report of the command
+    Json
+}
+
+impl DissectArgs
+{
+    /// Dissection options selected by these arguments
+    pub fn options(&self) -> DissectOptions
     {
-        // If no flags specified, default to showing everything
-        if header == false && data == false && all == false
+        let tag_size_thresholds = TagSizeThresholds::from_megabytes(self.tag_size_info, self.tag_size_warning, self.tag_size_critical);
+
+        // If no flags specified, or --all is specified, show everything
+        let show_everything = (self.header == false && self.data == false) || self.all == true;
+        let (show_header, show_data) = if show_everything == true
+        {
+            (true, true)
+        }
+        else
         {
-            return DissectOptions { show_header: true, show_data: true, show_verbose: verbose, show_dump: dump };
+            (self.header, self.data)
+        };
+
+        DissectOptions {
+            show_header,
+            show_data,
+            show_verbose: self.verbose,
+            show_dump: self.dump == true || self.dump_path.is_empty() == false,
+            dump_limit: self.dump_limit,
+            dump_filter: (self.dump_path.is_empty() == false).then(|| PathFilter::new(self.dump_path.clone())),
+            show_gaps: self.gaps,
+            show_hashes: self.hashes,
+            show_music_ids: self.music_ids,
+            show_loudness: self.loudness,
+            show_gapless: self.gapless,
+            show_podcast: self.podcast,
+            show_stats: self.stats,
+            show_spec: self.spec,
+            inspect_art: self.inspect_art,
+            show_payloads: self.payloads,
+            show_details: self.details,
+            filter: (self.filter.is_empty() == false).then(|| PathFilter::new(self.filter.clone())),
+            tag_size_thresholds,
+            max_table_entries: self.max_table_entries,
+            recover: self.recover,
+            init_segment: None
         }
+    }
 
-        // If --all is specified, show everything regardless of other flags
-        if all
+    /// Byte range selected by --offset and --length, None for the whole file
+    pub fn region(&self) -> Option<(u64, Option<u64>)>
+    {
+        match (self.offset, self.length)
         {
-            return DissectOptions { show_header: true, show_data: true, show_verbose: verbose, show_dump: dump };
+            | (None, None) => None,
+            | (offset, length) => Some((offset.unwrap_or(0), length))
         }
+    }
 
-        // Otherwise, use the specific flags
-        DissectOptions { show_header: header, show_data: data, show_verbose: verbose, show_dump: dump }
+    /// Media file to dissect: FILE, or the media segment given with --segment
+    pub fn path(&self) -> &Path
+    {
+        self.segment.as_deref().or(self.file.as_deref()).unwrap_or_else(|| Path::new(STDIN_PATH))
+    }
+
+    /// File selection for directory arguments
+    /// All inputs given on the command line
+    pub fn inputs(&self) -> Vec<PathBuf>
+    {
+        std::iter::once(self.path().to_path_buf()).chain(self.more.iter().cloned()).collect()
+    }
+
+    /// Whether the inputs are dissected as a batch (a directory or several inputs) instead of a single file
+    pub fn is_batch(&self) -> bool
+    {
+        self.more.is_empty() == false || self.path().is_dir() == true
     }
+
+    pub fn filter(&self) -> BatchFilter
+    {
+        BatchFilter { recursive: self.recursive, include: self.include.clone(), exclude: self.exclude.clone() }
+    }
+}
+
+/// Parse a byte offset or count given in decimal or as 0x-prefixed hex
+fn parse_byte_count(value: &str) -> Result<u64, String>
+{
+    let result = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X"))
+    {
+        | Some(hex) => u64::from_str_radix(hex, 16),
+        | None => value.parse()
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Parse a FRAME=VALUE argument of tag set
+fn parse_frame_assignment(value: &str) -> Result<(String, String), String>
+{
+    let (frame, value) = value.split_once('=').ok_or_else(|| tr_fmt("Expected FRAME=VALUE, got '{}'", &[&value]))?;
+    Ok((frame.to_string(), value.to_string()))
 }
@@ -0,0 +1,119 @@
+// Colored text output
+//
+// Drop-in replacement for the `owo_colors::OwoColorize` methods used by the text output.
+// Styling is decided once for the whole process: `--color auto` (the default) colors only
+// when standard output is a terminal and `NO_COLOR` is not set, so redirected output and
+// logs contain no escape sequences.
+
+use std::{fmt, io::IsTerminal, sync::OnceLock};
+
+use clap::ValueEnum;
+use owo_colors::{OwoColorize, Style};
+
+/// When to color the text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice
+{
+    /// Color if standard output is a terminal and NO_COLOR is not set (default)
+    #[default]
+    Auto,
+    /// Always color, even if the output is redirected
+    Always,
+    /// Never color
+    Never
+}
+
+impl ColorChoice
+{
+    /// Whether this choice colors the output in the current environment
+    pub fn resolve(self) -> bool
+    {
+        match self
+        {
+            | ColorChoice::Always => true,
+            | ColorChoice::Never => false,
+            | ColorChoice::Auto => std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && std::io::stdout().is_terminal()
+        }
+    }
+}
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Select whether text output is colored for the whole process (only the first call has an effect)
+pub fn set_color(choice: ColorChoice)
+{
+    let _ = ENABLED.set(choice.resolve());
+}
+
+/// Whether text output is colored (library users who never select get the `auto` behavior)
+pub fn enabled() -> bool
+{
+    *ENABLED.get_or_init(|| ColorChoice::Auto.resolve())
+}
+
+/// A value that is displayed with a style if colors are enabled, plainly otherwise
+pub struct Styled<T>
+{
+    value: T,
+    style: Style
+}
+
+impl<T: fmt::Display> fmt::Display for Styled<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match enabled()
+        {
+            | true => fmt::Display::fmt(&self.value.style(self.style), f),
+            | false => fmt::Display::fmt(&self.value, f)
+        }
+    }
+}
+
+/// Styling methods of the text output, honoring the process-wide color choice
+pub trait Colorize: fmt::Display + Sized
+{
+    fn styled(self, style: Style) -> Styled<Self>
+    {
+        Styled { value: self, style }
+    }
+
+    fn bold(self) -> Styled<Self>
+    {
+        self.styled(Style::new().bold())
+    }
+
+    fn red(self) -> Styled<Self>
+    {
+        self.styled(Style::new().red())
+    }
+
+    fn green(self) -> Styled<Self>
+    {
+        self.styled(Style::new().green())
+    }
+
+    fn yellow(self) -> Styled<Self>
+    {
+        self.styled(Style::new().yellow())
+    }
+
+    fn cyan(self) -> Styled<Self>
+    {
+        self.styled(Style::new().cyan())
+    }
+
+    fn bright_red(self) -> Styled<Self>
+    {
+        self.styled(Style::new().bright_red())
+    }
+
+    fn bright_cyan(self) -> Styled<Self>
+    {
+        self.styled(Style::new().bright_cyan())
+    }
+}
+
+impl<T: fmt::Display> Colorize for T
+{
+}
@@ -0,0 +1,69 @@
+/// Largest gap shown with a hexdump in the text output
+pub const SMALL_GAP: u64 = 256;
+
+/// Byte ranges claimed by recognized structures (boxes, frames, padding, audio data)
+#[derive(Debug, Clone, Default)]
+pub struct Coverage
+{
+    ranges: Vec<(u64, u64)>
+}
+
+impl Coverage
+{
+    /// Create an empty coverage map
+    pub fn new() -> Self
+    {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Mark `start..end` as covered
+    pub fn add(&mut self, start: u64, end: u64)
+    {
+        if end > start
+        {
+            self.ranges.push((start, end));
+        }
+    }
+
+    /// Mark padding at `start` as covered, except for the span between its first and last non-zero byte
+    ///
+    /// Padding is expected to be zero-filled; anything else in it is slack space worth a look.
+    pub fn add_padding(&mut self, start: u64, data: &[u8])
+    {
+        let end = start + data.len() as u64;
+        match (data.iter().position(|&byte| byte != 0), data.iter().rposition(|&byte| byte != 0))
+        {
+            | (Some(first), Some(last)) =>
+            {
+                self.add(start, start + first as u64);
+                self.add(start + last as u64 + 1, end);
+            }
+            | _ => self.add(start, end)
+        }
+    }
+
+    /// Byte ranges in `0..file_size` not covered by any recognized structure
+    pub fn gaps(&self, file_size: u64) -> Vec<(u64, u64)>
+    {
+        let mut ranges = self.ranges.clone();
+        ranges.sort_unstable();
+
+        let mut gaps = Vec::new();
+        let mut position = 0;
+        for (start, end) in ranges
+        {
+            if start > position
+            {
+                gaps.push((position, start.min(file_size)));
+            }
+            position = position.max(end);
+        }
+        if position < file_size
+        {
+            gaps.push((position, file_size));
+        }
+
+        gaps.retain(|(start, end)| end > start);
+        gaps
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+
+use crate::{
+    color::Colorize,
+    i18n::{tr, tr_fmt}
+};
+
+/// Severity of a diagnostic finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity
+{
+    /// Noteworthy but harmless observation
+    Info,
+    /// Suspicious or non-conforming structure
+    Warning,
+    /// Specification violation that breaks the structure
+    Error
+}
+
+impl Severity
+{
+    /// Stable, untranslated name used in structured output
+    pub fn key(self) -> &'static str
+    {
+        match self
+        {
+            | Severity::Info => "info",
+            | Severity::Warning => "warning",
+            | Severity::Error => "error"
+        }
+    }
+}
+
+impl fmt::Display for Severity
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | Severity::Info => write!(f, "{}", tr("INFO")),
+            | Severity::Warning => write!(f, "{}", tr("WARNING")),
+            | Severity::Error => write!(f, "{}", tr("ERROR"))
+        }
+    }
+}
+
+/// A single finding reported by a dissector
+#[derive(Debug, Clone)]
+pub struct Diagnostic
+{
+    pub severity: Severity,
+    /// File offset of the structure the finding refers to (if known)
+    pub offset:   Option<u64>,
+    pub message:  String
+}
+
+impl Diagnostic
+{
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "severity": self.severity.key(),
+            "offset": self.offset,
+            "message": self.message
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let label = match self.severity
+        {
+            | Severity::Info => format!("{}", self.severity),
+            | Severity::Warning => format!("{}", self.severity.yellow()),
+            | Severity::Error => format!("{}", self.severity.bright_red())
+        };
+
+        match self.offset
+        {
+            | Some(offset) => write!(f, "{}", tr_fmt("{} at {}: {}", &[&label, &format!("0x{:08X}", offset), &self.message])),
+            | None => write!(f, "{}: {}", label, self.message)
+        }
+    }
+}
+
+/// Collector for findings gathered while dissecting a file
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics
+{
+    entries: Vec<Diagnostic>
+}
+
+impl Diagnostics
+{
+    /// Create an empty diagnostics collector
+    pub fn new() -> Self
+    {
+        Self { entries: Vec::new() }
+    }
+
+    /// Record a finding
+    pub fn report(&mut self, severity: Severity, offset: Option<u64>, message: impl Into<String>)
+    {
+        self.entries.push(Diagnostic { severity, offset, message: message.into() });
+    }
+
+    /// Record an informational finding
+    pub fn info(&mut self, offset: Option<u64>, message: impl Into<String>)
+    {
+        self.report(Severity::Info, offset, message);
+    }
+
+    /// Record a warning
+    pub fn warning(&mut self, offset: Option<u64>, message: impl Into<String>)
+    {
+        self.report(Severity::Warning, offset, message);
+    }
+
+    /// Record a specification violation
+    pub fn error(&mut self, offset: Option<u64>, message: impl Into<String>)
+    {
+        self.report(Severity::Error, offset, message);
+    }
+
+    /// Record the findings of a region dissected on its own, moving their offsets by `shift` bytes
+    pub fn extend_shifted(&mut self, other: Diagnostics, shift: u64)
+    {
+        self.entries.extend(other.entries.into_iter().map(|entry| Diagnostic { offset: entry.offset.map(|offset| offset + shift), ..entry }));
+    }
+
+    /// Record the findings of a payload dissected on its own, naming its location and offset in the message (the payload has no offset in the file)
+    pub fn extend_located(&mut self, other: Diagnostics, location: &str)
+    {
+        self.entries.extend(other.entries.into_iter().map(|entry| {
+            let message = match entry.offset
+            {
+                | Some(offset) => format!("{} +0x{:X}: {}", location, offset, entry.message),
+                | None => format!("{}: {}", location, entry.message)
+            };
+            Diagnostic { offset: None, message, ..entry }
+        }));
+    }
+
+    /// Check if any warning was recorded
+    pub fn has_warnings(&self) -> bool
+    {
+        self.entries.iter().any(|entry| entry.severity >= Severity::Warning)
+    }
+
+    /// Number of recorded warnings
+    pub fn warning_count(&self) -> usize
+    {
+        self.entries.iter().filter(|entry| entry.severity >= Severity::Warning).count()
+    }
+
+    /// Check if any specification violation was recorded
+    pub fn has_errors(&self) -> bool
+    {
+        self.entries.iter().any(|entry| entry.severity == Severity::Error)
+    }
+
+    /// Number of recorded findings of the given severity
+    pub fn count(&self, severity: Severity) -> usize
+    {
+        self.entries.iter().filter(|entry| entry.severity == severity).count()
+    }
+
+    /// All findings in the order they were recorded
+    pub fn entries(&self) -> &[Diagnostic]
+    {
+        &self.entries
+    }
+
+    /// Structured representation of all findings for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::Value::Array(self.entries.iter().map(Diagnostic::to_json).collect())
+    }
+
+    /// Check if no findings were recorded
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Diagnostics
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        for entry in &self.entries
+        {
+            writeln!(f, "  {}", entry)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+// Structural comparison of two files
+//
+// Backs the `diff` command: the JSON reports of both files are flattened into one node per
+// layer, box, frame or other located structure, keyed by its path (e.g. "ISOBMFF/moov/trak/tkhd"
+// or "ID3v2.4/CHAP c1"). Nodes are matched by path and compared field by field, so added and
+// removed structures, changed values and structures that only moved to another offset are
+// reported separately.
+
+use std::{collections::HashMap, fmt, path::Path};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    color::Colorize,
+    i18n::{tr, tr_fmt},
+    report::SCHEMA_VERSION
+};
+
+/// Report fields that differ between any two files and are not compared
+const IGNORED_FIELDS: [&str; 3] = ["schema_version", "file", "diagnostics"];
+
+/// How a structure differs between the two files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind
+{
+    /// Only present in the new file
+    Added,
+    /// Only present in the old file
+    Removed,
+    /// Present in both files with different values
+    Changed,
+    /// Present in both files with the same values at another offset
+    Moved
+}
+
+impl ChangeKind
+{
+    fn name(self) -> &'static str
+    {
+        match self
+        {
+            | ChangeKind::Added => "added",
+            | ChangeKind::Removed => "removed",
+            | ChangeKind::Changed => "changed",
+            | ChangeKind::Moved => "moved"
+        }
+    }
+}
+
+/// One structure that differs between the two files
+#[derive(Debug, Clone)]
+pub struct Difference
+{
+    /// Location in the report, e.g. "ISOBMFF/moov/udta/meta/ilst/©nam" ("" for file-level fields)
+    pub path:          String,
+    pub kind:          ChangeKind,
+    pub old_offset:    Option<u64>,
+    pub new_offset:    Option<u64>,
+    /// Field lines only found in the old file
+    pub removed_lines: Vec<String>,
+    /// Field lines only found in the new file
+    pub added_lines:   Vec<String>
+}
+
+impl Difference
+{
+    fn to_json(&self) -> Value
+    {
+        serde_json::json!({
+            "path": self.path,
+            "change": self.kind.name(),
+            "old_offset": self.old_offset,
+            "new_offset": self.new_offset,
+            "removed_lines": self.removed_lines,
+            "added_lines": self.added_lines
+        })
+    }
+
+    /// Offsets of the text output, e.g. "at 0x00000120" or "moved from 0x0000004A to 0x00000060"
+    fn location(&self) -> Option<String>
+    {
+        let hex = |offset: u64| format!("0x{:08X}", offset);
+        match (self.old_offset, self.new_offset)
+        {
+            | (Some(old), Some(new)) if old != new => Some(tr_fmt("moved from {} to {}", &[&hex(old), &hex(new)])),
+            | (Some(offset), None) | (None, Some(offset)) => Some(tr_fmt("at {}", &[&hex(offset)])),
+            | _ => None
+        }
+    }
+}
+
+/// Result of the diff command
+#[derive(Debug, Clone, Default)]
+pub struct Comparison
+{
+    /// Differences in the order of the new file's structure, removed structures at their old position
+    pub differences: Vec<Difference>
+}
+
+impl Comparison
+{
+    /// Compare two dissection reports (as produced by `Dissector::report`)
+    pub fn new(old_report: &Value, new_report: &Value) -> Self
+    {
+        let old_nodes = flatten(old_report);
+        let new_nodes = flatten(new_report);
+        let old_index: HashMap<&str, usize> = old_nodes.iter().enumerate().map(|(index, node)| (node.path.as_str(), index)).collect();
+
+        let mut differences = Vec::new();
+        let mut matched = vec![false; old_nodes.len()];
+        let mut next_old = 0;
+        for node in &new_nodes
+        {
+            let Some(&index) = old_index.get(node.path.as_str())
+            else
+            {
+                push_collapsed(&mut differences, Difference {
+                    path:          node.path.clone(),
+                    kind:          ChangeKind::Added,
+                    old_offset:    None,
+                    new_offset:    node.offset,
+                    removed_lines: Vec::new(),
+                    added_lines:   Vec::new()
+                });
+                continue;
+            };
+
+            // Report structures removed before this one where they used to be
+            matched[index] = true;
+            if index >= next_old
+            {
+                push_removed(&mut differences, &old_nodes[next_old..index], &matched[next_old..index]);
+                next_old = index + 1;
+            }
+
+            let old = &old_nodes[index];
+            let removed_lines: Vec<String> = old.lines.iter().filter(|line| node.lines.contains(line) == false).cloned().collect();
+            let added_lines: Vec<String> = node.lines.iter().filter(|line| old.lines.contains(line) == false).cloned().collect();
+            let kind = if removed_lines.is_empty() == false || added_lines.is_empty() == false || old.lines.len() != node.lines.len()
+            {
+                ChangeKind::Changed
+            }
+            else if old.offset != node.offset
+            {
+                ChangeKind::Moved
+            }
+            else
+            {
+                continue;
+            };
+            differences.push(Difference { path: node.path.clone(), kind, old_offset: old.offset, new_offset: node.offset, removed_lines, added_lines });
+        }
+        push_removed(&mut differences, &old_nodes[next_old.min(old_nodes.len())..], &matched[next_old.min(old_nodes.len())..]);
+
+        Self { differences }
+    }
+
+    pub fn is_identical(&self) -> bool
+    {
+        self.differences.is_empty()
+    }
+
+    /// Number of differences of one kind
+    pub fn count(&self, kind: ChangeKind) -> usize
+    {
+        self.differences.iter().filter(|difference| difference.kind == kind).count()
+    }
+
+    /// JSON document of the diff command (see `schema/diff.schema.json`)
+    pub fn to_json(&self, old_path: &Path, new_path: &Path) -> Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "old_file": old_path.display().to_string(),
+            "new_file": new_path.display().to_string(),
+            "identical": self.is_identical(),
+            "differences": self.differences.iter().map(Difference::to_json).collect::<Vec<_>>(),
+            "summary": {
+                "added": self.count(ChangeKind::Added),
+                "removed": self.count(ChangeKind::Removed),
+                "changed": self.count(ChangeKind::Changed),
+                "moved": self.count(ChangeKind::Moved)
+            }
+        })
+    }
+}
+
+impl fmt::Display for Comparison
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        for difference in &self.differences
+        {
+            let path = match difference.path.as_str()
+            {
+                | "" => tr("(file)").to_string(),
+                | path => path.to_string()
+            };
+            let kind = tr(difference.kind.name());
+            let kind = match difference.kind
+            {
+                | ChangeKind::Added => kind.green().to_string(),
+                | ChangeKind::Removed => kind.red().to_string(),
+                | ChangeKind::Changed => kind.yellow().to_string(),
+                | ChangeKind::Moved => kind.cyan().to_string()
+            };
+            match difference.location()
+            {
+                // The location of a moved structure says all there is to say
+                | Some(location) if difference.kind == ChangeKind::Moved => writeln!(f, "  {}: {}", path.bold(), location.cyan())?,
+                | Some(location) => writeln!(f, "  {}: {} ({})", path.bold(), kind, location)?,
+                | None => writeln!(f, "  {}: {}", path.bold(), kind)?
+            }
+
+            for line in &difference.removed_lines
+            {
+                writeln!(f, "      {}", format!("- {}", line).red())?;
+            }
+            for line in &difference.added_lines
+            {
+                writeln!(f, "      {}", format!("+ {}", line).green())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Add a difference unless an ancestor was added or removed as a whole already
+fn push_collapsed(differences: &mut Vec<Difference>, difference: Difference)
+{
+    let covered = differences
+        .iter()
+        .rev()
+        .any(|previous| previous.kind == difference.kind && previous.path.is_empty() == false && difference.path.starts_with(&format!("{}/", previous.path)));
+    if covered == false
+    {
+        differences.push(difference);
+    }
+}
+
+/// Report the unmatched nodes of the old file as removed
+fn push_removed(differences: &mut Vec<Difference>, nodes: &[Node], matched: &[bool])
+{
+    for (node, &matched) in nodes.iter().zip(matched)
+    {
+        if matched == false
+        {
+            push_collapsed(differences, Difference {
+                path:          node.path.clone(),
+                kind:          ChangeKind::Removed,
+                old_offset:    node.offset,
+                new_offset:    None,
+                removed_lines: Vec::new(),
+                added_lines:   Vec::new()
+            });
+        }
+    }
+}
+
+/// One located structure of a report with its fields as "name: value" lines
+struct Node
+{
+    path:   String,
+    offset: Option<u64>,
+    lines:  Vec<String>
+}
+
+/// Flatten a report into its nodes, parents before their children
+fn flatten(report: &Value) -> Vec<Node>
+{
+    let mut nodes = Vec::new();
+    if let Some(object) = report.as_object()
+    {
+        let fields: Map<String, Value> =
+            object.iter().filter(|(key, _)| IGNORED_FIELDS.contains(&key.as_str()) == false).map(|(key, value)| (key.clone(), value.clone())).collect();
+        add_node(&fields, String::new(), None, &mut nodes);
+    }
+    nodes
+}
+
+/// Add `object` and the structures below it, naming repeated siblings "name[2]", "name[3]", ...
+fn add_node(object: &Map<String, Value>, path: String, offset: Option<u64>, nodes: &mut Vec<Node>)
+{
+    let mut lines = Vec::new();
+    let mut children = Vec::new();
+    gather_fields(object, "", &mut lines, &mut children);
+
+    let index = nodes.len();
+    nodes.push(Node { path, offset, lines });
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (name, child) in children
+    {
+        let count = seen.entry(name.clone()).or_default();
+        *count += 1;
+        let name = match *count
+        {
+            | 1 => name,
+            | count => format!("{}[{}]", name, count)
+        };
+        let child_path = match nodes[index].path.as_str()
+        {
+            | "" => name,
+            | parent => format!("{}/{}", parent, name)
+        };
+        add_node(child, child_path, child.get("offset").and_then(Value::as_u64), nodes);
+    }
+}
+
+/// Split the fields of an object into value lines and child structures
+///
+/// Objects with an offset are structures of their own; other objects are flattened into
+/// "outer.inner: value" lines. Arrays of objects hold structures named by their type, ID or
+/// key, falling back to "field[n]".
+fn gather_fields<'a>(object: &'a Map<String, Value>, prefix: &str, lines: &mut Vec<String>, children: &mut Vec<(String, &'a Map<String, Value>)>)
+{
+    for (key, value) in object
+    {
+        match (key.as_str(), value)
+        {
+            | ("offset" | "description", _) if prefix.is_empty() == true => (),
+            | ("details", Value::Array(details)) =>
+                lines.extend(details.iter().map(|line| line.as_str().map(str::trim).map(str::to_string).unwrap_or_else(|| line.to_string()))),
+            | ("content", Value::Object(content)) => gather_fields(content, prefix, lines, children),
+            | (_, Value::Object(child)) if child.get("offset").is_some_and(Value::is_u64) == true => children.push((key.clone(), child)),
+            | (_, Value::Object(child)) => gather_fields(child, &format!("{}{}.", prefix, key), lines, children),
+            | (_, Value::Array(items)) if items.is_empty() == false && items.iter().all(Value::is_object) == true =>
+            {
+                for (index, item) in items.iter().filter_map(Value::as_object).enumerate()
+                {
+                    children.push((structure_name(item).unwrap_or_else(|| format!("{}[{}]", key, index + 1)), item));
+                }
+            }
+            | (_, Value::String(text)) => lines.push(format!("{}{}: {}", prefix, key, text)),
+            | (_, value) => lines.push(format!("{}{}: {}", prefix, key, value))
+        }
+    }
+}
+
+/// Name of a structure in paths: layer format, item key, element name, frame ID or box type
+///
+/// Chapter and table of contents frames are told apart by their element ID (e.g. "CHAP c1").
+fn structure_name(object: &Map<String, Value>) -> Option<String>
+{
+    let name = ["format", "key", "name", "id", "type"].iter().find_map(|field| object.get(*field).and_then(Value::as_str))?;
+
+    let element_id = object.get("fields").and_then(|fields| fields.get("element_id")).and_then(Value::as_str);
+    match element_id
+    {
+        | Some(element_id) => Some(format!("{} {}", name, element_id)),
+        | None => Some(name.to_string())
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    artwork::Artwork,
+    chapters::Chapter,
+    coverage::Coverage,
+    diagnostics::{Diagnostic, Diagnostics, Severity},
+    dissector_registry::DissectorRegistry,
+    ffprobe::{self, ProbeFormat},
+    find::{self, Search},
+    gapless::Gapless,
+    hashes::{self, RegionHash},
+    isobmff::{
+        demux::AudioStream,
+        fragments::{FragmentTiming, TrackFragment},
+        samples::SampleLocation,
+        tree::InitSegment
+    },
+    layout::Layout,
+    loudness::Loudness,
+    media_dissector::MediaDissector,
+    media_source::{self, MediaSource, Region},
+    metadata::MediaMetadata,
+    mpeg_audio,
+    music_ids::{self, MusicId},
+    objects::EmbeddedObject,
+    optimize::OptimizationReport,
+    options::DissectOptions,
+    payload,
+    playlist::SegmentMedia,
+    podcast::Podcast,
+    report,
+    summary::Summary,
+    visitor::{DissectionVisitor, TextEvents, TextPrinter}
+};
+
+/// Dissection of a media file, layer by layer (e.g. an ID3v2 tag followed by an MP4 stream)
+pub struct Dissector
+{
+    path:     PathBuf,
+    source:   Box<dyn MediaSource>,
+    /// Dissectors probed for the first and each following layer
+    registry: DissectorRegistry,
+    /// Dissector of the first layer, chosen from the file header
+    first:    Box<dyn MediaDissector>,
+    /// Offset and length of the dissected byte range when only part of the file is dissected
+    region:   Option<(u64, u64)>
+}
+
+impl Dissector
+{
+    /// Open a file ("-" reads standard input) and select the dissector for its first layer
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let path = path.as_ref();
+        Self::from_source(path, media_source::open(path)?)
+    }
+
+    /// Open a file and dissect only `length` bytes from `offset` (to the end of the file if `length` is None);
+    /// all offsets are reported relative to the start of the region
+    pub fn open_region(path: impl AsRef<Path>, offset: u64, length: Option<u64>) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let path = path.as_ref();
+        let region = Region::new(media_source::open(path)?, offset, length)?;
+        let bounds = (region.offset(), region.length());
+
+        let mut dissector = Self::from_source(path, Box::new(region))?;
+        dissector.region = Some(bounds);
+        Ok(dissector)
+    }
+
+    /// Select the dissector for the first layer of an already opened source, reported under `path`
+    pub fn from_source(path: impl AsRef<Path>, source: Box<dyn MediaSource>) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        Self::with_registry(path, source, DissectorRegistry::new())
+    }
+
+    /// Select the dissector for the first layer of an already opened source from the dissectors of `registry`,
+    /// e.g. the built-in ones extended by custom dissectors
+    pub fn with_registry(path: impl AsRef<Path>, mut source: Box<dyn MediaSource>, registry: DissectorRegistry) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let first = registry.build_for_file(source.as_mut())?;
+
+        Ok(Self { path: path.as_ref().to_path_buf(), source, registry, first, region: None })
+    }
+
+    /// Media type of the first layer ("Unknown" if no dissector recognized the file)
+    pub fn media_type(&self) -> &'static str
+    {
+        self.first.media_type()
+    }
+
+    /// Name of the dissector of the first layer
+    pub fn name(&self) -> &'static str
+    {
+        self.first.name()
+    }
+
+    pub fn path(&self) -> &Path
+    {
+        &self.path
+    }
+
+    /// Offset and length of the dissected byte range, None if the whole file is dissected
+    pub fn region(&self) -> Option<(u64, u64)>
+    {
+        self.region
+    }
+
+    /// The opened source, e.g. for reading the data of reported byte ranges
+    pub fn source(&mut self) -> &mut dyn MediaSource
+    {
+        self.source.as_mut()
+    }
+
+    /// Run `dissect` for the first dissector, then re-probe the remaining bytes for further formats
+    /// until no dissector matches or the file ends
+    pub fn for_each_layer<F>(&mut self, mut dissect: F) -> Result<(), Box<dyn std::error::Error>>
+    where F: FnMut(&dyn MediaDissector, &mut dyn MediaSource, u64) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let file_size = self.source.size()?;
+        let mut offset = dissect(self.first.as_ref(), self.source.as_mut(), 0)?;
+
+        while offset > 0 && offset < file_size
+        {
+            let Some(next_dissector) = self.registry.build_for_offset(self.source.as_mut(), offset)?
+            else
+            {
+                break;
+            };
+
+            let end_offset = dissect(next_dissector.as_ref(), self.source.as_mut(), offset)?;
+            if end_offset <= offset
+            {
+                break;
+            }
+            offset = end_offset;
+        }
+
+        Ok(())
+    }
+
+    /// Write the text output of all layers to `output`, reporting findings to `diagnostics`
+    pub fn print(&mut self, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics) -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut printer = TextPrinter::new(output);
+        self.visit_text(options, &mut printer, diagnostics)?;
+        printer.finish()?;
+
+        Ok(())
+    }
+
+    /// Walk the structures of all layers in file order, calling `visitor` for each of them and for the findings of each layer
+    pub fn visit(&mut self, visitor: &mut dyn DissectionVisitor) -> Result<(), Box<dyn std::error::Error>>
+    {
+        self.for_each_layer(|layer_dissector, file, offset| {
+            visitor.on_layer_start(layer_dissector, offset);
+            let mut diagnostics = Diagnostics::new();
+            let end_offset = layer_dissector.visit(file, offset, visitor, &mut diagnostics)?;
+            send_findings(visitor, diagnostics.entries());
+            visitor.on_layer_end(layer_dissector, end_offset);
+            Ok(end_offset)
+        })
+    }
+
+    /// Dissect all layers like `print`, sending their text output to `visitor` as `on_text` events between the layer events
+    ///
+    /// The findings of each layer are sent to `visitor` once the layer is dissected and added to `diagnostics`.
+    pub fn visit_text(
+        &mut self, options: &DissectOptions, visitor: &mut dyn DissectionVisitor, diagnostics: &mut Diagnostics
+    ) -> Result<(), Box<dyn std::error::Error>>
+    {
+        self.for_each_layer(|layer_dissector, file, offset| {
+            visitor.on_layer_start(layer_dissector, offset);
+            let reported = diagnostics.entries().len();
+            let end_offset = layer_dissector.dissect_with_options(file, offset, options, &mut TextEvents::new(&mut *visitor), diagnostics)?;
+            send_findings(visitor, &diagnostics.entries()[reported..]);
+            visitor.on_layer_end(layer_dissector, end_offset);
+            Ok(end_offset)
+        })
+    }
+
+    /// Build the JSON report of all layers (see `schema/report.schema.json`)
+    pub fn report(&mut self, options: &DissectOptions, diagnostics: &mut Diagnostics) -> Result<serde_json::Value, Box<dyn std::error::Error>>
+    {
+        let mut layers = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| {
+            let (content, end_offset) = layer_dissector.dissect_to_json(file, offset, options, diagnostics)?;
+            layers.push(report::layer(layer_dissector, offset, end_offset, content));
+            Ok(end_offset)
+        })?;
+
+        let gaps = if options.show_gaps == true
+        {
+            Some(self.gaps()?)
+        }
+        else
+        {
+            None
+        };
+
+        let hashes = if options.show_hashes == true
+        {
+            Some(self.hashes()?)
+        }
+        else
+        {
+            None
+        };
+
+        // Payload findings belong to the diagnostics of the document
+        let payloads = if options.show_payloads == true
+        {
+            Some(self.payloads(options, diagnostics)?)
+        }
+        else
+        {
+            None
+        };
+
+        let file_size = self.source.size()?;
+        let mut document = report::document(&self.path, file_size, self.region, layers, gaps.as_deref(), hashes.as_deref(), diagnostics);
+        if let Some(payloads) = payloads
+        {
+            document["payloads"] = payloads.into();
+        }
+        if options.show_music_ids == true
+        {
+            document["music_ids"] = self.music_ids()?.iter().map(MusicId::to_json).collect();
+        }
+        if options.show_loudness == true
+        {
+            document["loudness"] = self.loudness()?.iter().map(Loudness::to_json).collect();
+        }
+        if options.show_gapless == true
+        {
+            document["gapless"] = self.gapless()?.iter().map(Gapless::to_json).collect();
+        }
+        if options.show_podcast == true
+        {
+            document["podcast"] = self.podcast()?.to_json();
+        }
+
+        Ok(document)
+    }
+
+    /// Write the embedded objects of all layers (pictures, GEOB, PRIV and UFID payloads) that a dissector recognizes to `output`, each as a file of its own
+    ///
+    /// Returns the number of payloads printed.
+    pub fn print_payloads(&mut self, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics) -> Result<usize, Box<dyn std::error::Error>>
+    {
+        let mut count = 0;
+        for object in self.objects()?
+        {
+            if payload::print_payload(&self.registry, &object, options, output, diagnostics)? == true
+            {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Structured reports of the embedded objects of all layers that a dissector recognizes
+    pub fn payloads(&mut self, options: &DissectOptions, diagnostics: &mut Diagnostics) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>>
+    {
+        let objects = self.objects()?;
+        Ok(objects.iter().filter_map(|object| payload::payload_to_json(&self.registry, object, options, diagnostics)).collect())
+    }
+
+    /// Describe all layers in ffprobe terms
+    pub fn probe(&mut self, diagnostics: &mut Diagnostics) -> Result<Vec<ProbeFormat>, Box<dyn std::error::Error>>
+    {
+        let mut probes = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| {
+            let (probe, end_offset) = layer_dissector.probe(file, offset, diagnostics)?;
+            probes.extend(probe);
+            Ok(end_offset)
+        })?;
+
+        Ok(probes)
+    }
+
+    /// Build the ffprobe-compatible JSON document of all layers
+    pub fn probe_document(&mut self, diagnostics: &mut Diagnostics) -> Result<serde_json::Value, Box<dyn std::error::Error>>
+    {
+        let probes = self.probe(diagnostics)?;
+        let file_size = self.source.size()?;
+
+        Ok(ffprobe::document(&self.path, file_size, &probes))
+    }
+
+    /// Collect the pictures embedded in all layers (APIC frames, iTunes cover art)
+    pub fn artwork(&mut self) -> Result<Vec<Artwork>, Box<dyn std::error::Error>>
+    {
+        let mut artwork = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_artwork(file, offset, &mut artwork))?;
+
+        Ok(artwork)
+    }
+
+    /// Collect the binary objects of all layers: pictures first, then GEOB, PRIV and UFID payloads
+    pub fn objects(&mut self) -> Result<Vec<EmbeddedObject>, Box<dyn std::error::Error>>
+    {
+        let mut objects: Vec<EmbeddedObject> = self.artwork()?.iter().map(EmbeddedObject::from_artwork).collect();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_objects(file, offset, &mut objects))?;
+
+        Ok(objects)
+    }
+
+    /// Collect the chapters of all layers (ID3v2 CHAP frames, ISOBMFF chapter tracks)
+    pub fn chapters(&mut self) -> Result<Vec<Chapter>, Box<dyn std::error::Error>>
+    {
+        let mut chapters = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_chapters(file, offset, &mut chapters))?;
+
+        Ok(chapters)
+    }
+
+    /// Locate the samples of track `track_id` in all layers (ISOBMFF sample tables and track fragment runs)
+    pub fn samples(&mut self, track_id: u32) -> Result<Vec<SampleLocation>, Box<dyn std::error::Error>>
+    {
+        let mut samples = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_samples(file, offset, track_id, &mut samples))?;
+
+        Ok(samples)
+    }
+
+    /// Prepare track `track_id` for audio extraction, None if no layer has such a track
+    pub fn audio_stream(&mut self, track_id: u32) -> Result<Option<AudioStream>, Box<dyn std::error::Error>>
+    {
+        let mut streams = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_audio_streams(file, offset, track_id, &mut streams))?;
+
+        Ok(streams.into_iter().next())
+    }
+
+    /// Collect the track fragments of all layers with the timing discontinuities between them
+    pub fn fragments(&mut self) -> Result<FragmentTiming, Box<dyn std::error::Error>>
+    {
+        let mut fragments: Vec<TrackFragment> = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_fragments(file, offset, &mut fragments))?;
+
+        Ok(FragmentTiming::new(fragments))
+    }
+
+    /// Timing and encryption of the first layer that is an HLS media segment (MPEG-TS or fragmented MP4)
+    pub fn segment_media(&mut self, init_segment: Option<&InitSegment>) -> Result<Option<SegmentMedia>, Box<dyn std::error::Error>>
+    {
+        let mut media = Vec::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_segment_media(file, offset, init_segment, &mut media))?;
+
+        Ok(media.into_iter().next())
+    }
+
+    /// Collect the normalized metadata of all layers (ID3v2 frames, iTunes metadata items); earlier layers take precedence
+    pub fn metadata(&mut self) -> Result<MediaMetadata, Box<dyn std::error::Error>>
+    {
+        let mut metadata = MediaMetadata::default();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_metadata(file, offset, &mut metadata))?;
+
+        Ok(metadata)
+    }
+
+    /// Gather the MusicBrainz and AcoustID identifiers of the metadata of all layers
+    pub fn music_ids(&mut self) -> Result<Vec<MusicId>, Box<dyn std::error::Error>>
+    {
+        Ok(music_ids::collect(&self.metadata()?))
+    }
+
+    /// Gather the ReplayGain, RVA2 and Sound Check values of the metadata of all layers
+    pub fn loudness(&mut self) -> Result<Vec<Loudness>, Box<dyn std::error::Error>>
+    {
+        Ok(self.metadata()?.loudness)
+    }
+
+    /// Gather the gapless playback information: LAME headers of the MPEG audio streams, then iTunSMPB strings of the metadata
+    pub fn gapless(&mut self) -> Result<Vec<Gapless>, Box<dyn std::error::Error>>
+    {
+        let mut gapless = Vec::new();
+        let layout = self.layout()?;
+        for region in layout.regions.iter().filter(|region| region.depth == 0 && region.name == "MPEG audio")
+        {
+            if let Some(info) = mpeg_audio::analyze(self.source.as_mut(), region.offset)?
+            {
+                gapless.extend(Gapless::from_lame(&info));
+            }
+        }
+
+        // iTunSMPB does not store the sample rate, it is taken from the audio track or the MPEG audio stream
+        let sample_rate = self
+            .summary()?
+            .tracks
+            .iter()
+            .find(|track| track.stream.codec_type == "audio")
+            .and_then(|track| track.stream.sample_rate)
+            .or_else(|| gapless.first().and_then(|lame| lame.sample_rate));
+        let metadata = self.metadata()?;
+        gapless.extend(metadata.custom.iter().filter_map(|(field, value)| Gapless::from_itunsmpb(field, value, sample_rate)));
+
+        Ok(gapless)
+    }
+
+    /// Gather the podcast fields and the chapters of the metadata of all layers
+    pub fn podcast(&mut self) -> Result<Podcast, Box<dyn std::error::Error>>
+    {
+        Ok(Podcast::collect(&self.metadata()?))
+    }
+
+    /// Collect the summary of all layers (one line per ISOBMFF track)
+    pub fn summary(&mut self) -> Result<Summary, Box<dyn std::error::Error>>
+    {
+        let mut summary = Summary::default();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_summary(file, offset, &mut summary))?;
+
+        Ok(summary)
+    }
+
+    /// Collect the byte map of all layers; bytes no layer claims are added as unrecognized data
+    pub fn layout(&mut self) -> Result<Layout, Box<dyn std::error::Error>>
+    {
+        let mut layout = Layout::new(self.source.size()?);
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_layout(file, offset, &mut layout))?;
+        layout.fill_gaps();
+
+        Ok(layout)
+    }
+
+    /// Compute MD5 and SHA-256 of the top-level regions of the layout (tags, audio data, mdat payloads, unrecognized data)
+    pub fn hashes(&mut self) -> Result<Vec<RegionHash>, Box<dyn std::error::Error>>
+    {
+        let layout = self.layout()?;
+        hashes::hash_regions(self.source.as_mut(), &layout)
+    }
+
+    /// Collect the wasted space of all layers (padding, free space boxes, duplicate and oversized artwork, moov after mdat)
+    pub fn optimization_report(&mut self) -> Result<OptimizationReport, Box<dyn std::error::Error>>
+    {
+        let layout = self.layout()?;
+        let artwork = self.artwork()?;
+
+        Ok(OptimizationReport::new(&layout, &artwork))
+    }
+
+    /// Find the boxes or frames of type `code`: in the parsed structures of all layers, then by scanning all bytes
+    ///
+    /// An error of the structure walk (e.g. a corrupt box size) is recorded in the result; the byte scan still covers the whole file.
+    pub fn find(&mut self, code: &str) -> Result<Search, Box<dyn std::error::Error>>
+    {
+        let code_bytes = find::parse_code(code)?;
+
+        let mut parsed = Vec::new();
+        let walk_error = self.for_each_layer(|layer_dissector, file, offset| layer_dissector.collect_occurrences(file, offset, code, &mut parsed)).err();
+
+        let synchsafe = self.media_type() == "ID3v2.4";
+        let scanned = find::scan(self.source.as_mut(), &code_bytes, synchsafe)?;
+
+        Ok(Search::merge(parsed, scanned, walk_error.map(|e| e.to_string())))
+    }
+
+    /// Run the specification conformance checks of all layers, reporting findings to `diagnostics`
+    pub fn validate(&mut self, diagnostics: &mut Diagnostics) -> Result<(), Box<dyn std::error::Error>>
+    {
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.validate(file, offset, diagnostics))
+    }
+
+    /// Collect the byte ranges of the file that no dissector recognized
+    pub fn gaps(&mut self) -> Result<Vec<(u64, u64)>, Box<dyn std::error::Error>>
+    {
+        let mut coverage = Coverage::new();
+        self.for_each_layer(|layer_dissector, file, offset| layer_dissector.add_coverage(file, offset, &mut coverage))?;
+
+        Ok(coverage.gaps(self.source.size()?))
+    }
+}
+
+/// Send `findings` to `visitor`, errors to `on_error` and notes and warnings to `on_warning`
+fn send_findings(visitor: &mut dyn DissectionVisitor, findings: &[Diagnostic])
+{
+    for finding in findings
+    {
+        match finding.severity
+        {
+            | Severity::Error => visitor.on_error(finding),
+            | Severity::Info | Severity::Warning => visitor.on_warning(finding)
+        }
+    }
+}
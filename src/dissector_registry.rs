@@ -0,0 +1,182 @@
+// Dissector registry
+//
+// Selects the dissector for a region from its first bytes. Each format module registers its
+// dissectors with a priority and a probe function rating the sniff buffer read from the start of
+// the region; the registration with the highest confidence dissects the region, ties going to the
+// higher priority (then to the earlier registration).
+// Applications embedding the library register their own dissectors, e.g. for proprietary
+// container formats, on a registry passed to `Dissector::with_registry`.
+
+use std::io::{Read, SeekFrom};
+
+use crate::{
+    media_dissector::{Confidence, MediaDissector},
+    media_source::MediaSource,
+    unknown_dissector::UnknownDissector
+};
+
+/// Bytes read for format detection: enough to follow a run of MPEG audio frames or transport stream packets
+pub const SNIFF_SIZE: usize = 64 * 1024;
+
+/// Rate how likely a region is in the format of a dissector from its first bytes and the number of bytes to the end of the file;
+/// `chained` is set for regions following a dissected one
+pub type Probe = fn(header: &[u8], remaining: u64, chained: bool) -> Confidence;
+
+/// Create the dissector of a registration
+pub type Create = fn() -> Box<dyn MediaDissector>;
+
+/// A dissector with the priority and probe function that select it
+#[derive(Debug, Clone, Copy)]
+pub struct Registration
+{
+    /// Higher priorities win ties between equal confidences; the built-in dissectors use 100 to 1400 in steps of 100
+    pub priority: i32,
+    pub probe:    Probe,
+    pub create:   Create
+}
+
+/// Dissectors available for format detection, in probe order
+#[derive(Debug, Clone)]
+pub struct DissectorRegistry
+{
+    registrations: Vec<Registration>
+}
+
+impl DissectorRegistry
+{
+    /// Create a registry with the built-in dissectors of all supported formats
+    pub fn new() -> Self
+    {
+        let mut registry = Self::empty();
+        crate::id3v2::register(&mut registry);
+        crate::isobmff::register(&mut registry);
+        crate::ogg::register(&mut registry);
+        crate::mkv::register(&mut registry);
+        crate::mpegts::register(&mut registry);
+        crate::mpegps::register(&mut registry);
+        crate::dsd::register(&mut registry);
+        crate::audio::register(&mut registry);
+        crate::image::register(&mut registry);
+        registry
+    }
+
+    /// Create a registry without any dissectors
+    pub fn empty() -> Self
+    {
+        Self { registrations: Vec::new() }
+    }
+
+    /// Add a dissector selected by its own probe function
+    pub fn register(&mut self, registration: Registration) -> &mut Self
+    {
+        // Keep the registrations sorted; the insertion point after all equal priorities preserves registration order
+        let index = self.registrations.partition_point(|existing| existing.priority >= registration.priority);
+        self.registrations.insert(index, registration);
+        self
+    }
+
+    /// Add a dissector rated by its `sniff` and `sniff_chained` methods
+    pub fn register_dissector<D: MediaDissector + Default + 'static>(&mut self, priority: i32) -> &mut Self
+    {
+        self.register(Registration { priority, probe: probe_dissector::<D>, create: create_dissector::<D> })
+    }
+
+    /// All registrations in order of descending priority
+    pub fn registrations(&self) -> &[Registration]
+    {
+        &self.registrations
+    }
+
+    /// Analyze file header and return the appropriate dissector
+    pub fn build_for_file(&self, file: &mut dyn MediaSource) -> Result<Box<dyn MediaDissector>, Box<dyn std::error::Error>>
+    {
+        // If no specific dissector found, return an unknown format dissector
+        Ok(self.build(file, 0, false)?.unwrap_or_else(|| Box::new(UnknownDissector)))
+    }
+
+    /// Re-probe the bytes following a dissected region, returning a dissector if a known format starts at `offset`
+    pub fn build_for_offset(&self, file: &mut dyn MediaSource, offset: u64) -> Result<Option<Box<dyn MediaDissector>>, Box<dyn std::error::Error>>
+    {
+        self.build(file, offset, true)
+    }
+
+    /// Dissector recognizing a payload held in memory (e.g. an embedded picture or object), None if none does
+    pub fn build_for_payload(&self, data: &[u8]) -> Option<Box<dyn MediaDissector>>
+    {
+        self.best(&data[..data.len().min(SNIFF_SIZE)], data.len() as u64, false)
+    }
+
+    /// Confidence of every registration for the region at `offset`, in order of descending priority
+    pub fn rate(&self, file: &mut dyn MediaSource, offset: u64, chained: bool) -> Result<Vec<(Registration, Confidence)>, Box<dyn std::error::Error>>
+    {
+        let header = Self::read_header(file, offset)?;
+        let remaining = file.size()?.saturating_sub(offset);
+
+        Ok(self.rate_header(&header, remaining, chained))
+    }
+
+    /// Confidence of every registration for a region starting with `header`
+    fn rate_header(&self, header: &[u8], remaining: u64, chained: bool) -> Vec<(Registration, Confidence)>
+    {
+        self.registrations.iter().map(|&registration| (registration, (registration.probe)(header, remaining, chained))).collect()
+    }
+
+    /// Dissector of the registration rating the region at `offset` highest, None if none recognizes it
+    fn build(&self, file: &mut dyn MediaSource, offset: u64, chained: bool) -> Result<Option<Box<dyn MediaDissector>>, Box<dyn std::error::Error>>
+    {
+        let header = Self::read_header(file, offset)?;
+        let remaining = file.size()?.saturating_sub(offset);
+
+        Ok(self.best(&header, remaining, chained))
+    }
+
+    /// Dissector of the registration rating `header` highest, None if none recognizes it
+    fn best(&self, header: &[u8], remaining: u64, chained: bool) -> Option<Box<dyn MediaDissector>>
+    {
+        // The first of several registrations with the same confidence wins, so the scan keeps the earlier one on ties
+        let best =
+            self.rate_header(header, remaining, chained).into_iter().fold(None, |best: Option<(Registration, Confidence)>, (registration, confidence)| match best
+            {
+                | Some((_, best_confidence)) if best_confidence >= confidence => best,
+                | _ if confidence == Confidence::None => best,
+                | _ => Some((registration, confidence))
+            });
+
+        best.map(|(registration, _)| (registration.create)())
+    }
+
+    /// Read up to `SNIFF_SIZE` bytes at `offset` for format detection
+    fn read_header(file: &mut dyn MediaSource, offset: u64) -> Result<Vec<u8>, Box<dyn std::error::Error>>
+    {
+        let mut header = Vec::with_capacity(SNIFF_SIZE);
+        file.seek(SeekFrom::Start(offset))?;
+        (&mut *file).take(SNIFF_SIZE as u64).read_to_end(&mut header)?;
+        file.seek(SeekFrom::Start(offset))?; // Reset position
+
+        Ok(header)
+    }
+}
+
+impl Default for DissectorRegistry
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+/// Probe function of `register_dissector`
+fn probe_dissector<D: MediaDissector + Default>(header: &[u8], remaining: u64, chained: bool) -> Confidence
+{
+    match chained
+    {
+        | true => D::default().sniff_chained(header, remaining),
+        | false => D::default().sniff(header, remaining)
+    }
+}
+
+/// Create function of `register_dissector`
+fn create_dissector<D: MediaDissector + Default + 'static>() -> Box<dyn MediaDissector>
+{
+    Box::new(D::default())
+}
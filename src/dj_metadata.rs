@@ -0,0 +1,420 @@
+// DJ software metadata
+//
+// Cue points, loops and beat grids DJ software stores in ID3v2 tags. Serato writes GEOB frames
+// described "Serato Markers2" (base64 text of tagged entries: CUE, LOOP, COLOR, BPMLOCK) and
+// "Serato BeatGrid" (binary beat markers). Traktor writes a PRIV frame owned by "TRAKTOR4" that
+// holds a tree of chunks with reversed IDs; the cue points sit in its CUEP chunk. Both formats
+// are undocumented, the layouts follow the community reverse engineering. Decoding is best
+// effort: entries that do not fit the expected layout end the list instead of failing the frame.
+
+use std::fmt;
+
+use crate::{
+    bytes::{read_u32_be, read_u32_le, read_u64_le},
+    i18n::{tr, tr_fmt},
+    id3v2::frames::chapter::format_timestamp,
+    metadata::decode_base64
+};
+
+/// Owner identifier of the Traktor PRIV frame
+pub const TRAKTOR_OWNER: &str = "TRAKTOR4";
+
+/// Kind of cue point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CueKind
+{
+    Cue,
+    FadeIn,
+    FadeOut,
+    Load,
+    Grid,
+    Loop
+}
+
+impl CueKind
+{
+    /// Kind of a Traktor cue type, None for unknown types
+    fn from_traktor(cue_type: u32) -> Option<Self>
+    {
+        match cue_type
+        {
+            | 0 => Some(CueKind::Cue),
+            | 1 => Some(CueKind::FadeIn),
+            | 2 => Some(CueKind::FadeOut),
+            | 3 => Some(CueKind::Load),
+            | 4 => Some(CueKind::Grid),
+            | 5 => Some(CueKind::Loop),
+            | _ => None
+        }
+    }
+
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            | CueKind::Cue => "Cue",
+            | CueKind::FadeIn => "Fade-in",
+            | CueKind::FadeOut => "Fade-out",
+            | CueKind::Load => "Load",
+            | CueKind::Grid => "Grid",
+            | CueKind::Loop => "Loop"
+        }
+    }
+}
+
+/// Cue point or loop
+#[derive(Debug, Clone)]
+pub struct CuePoint
+{
+    pub kind:     CueKind,
+    /// Hot cue slot (0-based), None for cues not assigned to a slot
+    pub index:    Option<u32>,
+    /// Position in milliseconds
+    pub position: f64,
+    /// Loop length in milliseconds
+    pub length:   Option<f64>,
+    /// Color as RGB
+    pub color:    Option<u32>,
+    pub name:     String,
+    pub locked:   bool
+}
+
+impl fmt::Display for CuePoint
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.kind.name())?;
+        if let Some(index) = self.index
+        {
+            write!(f, " {}", index + 1)?;
+        }
+        write!(f, " {}", tr_fmt("at {}", &[&format_timestamp(self.position.max(0.0).round().min(u32::MAX as f64) as u32)]))?;
+        if let Some(length) = self.length.filter(|&length| length > 0.0)
+        {
+            write!(f, " {}", tr_fmt("to {}", &[&format_timestamp((self.position + length).max(0.0).round().min(u32::MAX as f64) as u32)]))?;
+        }
+        if let Some(color) = self.color
+        {
+            write!(f, "{}", tr_fmt(", color #{}", &[&format!("{:06X}", color)]))?;
+        }
+        if self.name.is_empty() == false
+        {
+            write!(f, ", \"{}\"", self.name)?;
+        }
+        if self.locked == true
+        {
+            write!(f, " {}", tr("(locked)"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Contents of a "Serato Markers2" GEOB frame
+#[derive(Debug, Clone, Default)]
+pub struct SeratoMarkers
+{
+    /// Track color as RGB
+    pub track_color: Option<u32>,
+    pub bpm_lock:    Option<bool>,
+    pub cues:        Vec<CuePoint>,
+    /// Entry types that are not decoded (e.g. FLIP)
+    pub other:       Vec<String>
+}
+
+impl SeratoMarkers
+{
+    /// Decode the object data of a "Serato Markers2" frame: a 2-byte version and base64 text of the entries
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let text = data.get(2..).ok_or(tr("Serato Markers2 data too short"))?;
+        let text = String::from_utf8_lossy(text.split(|&byte| byte == 0).next().unwrap_or_default()).to_string();
+        let entries = decode_base64(&text).ok_or(tr("Serato Markers2 data is not base64"))?;
+        // The decoded data starts with its own 2-byte version
+        let mut data = entries.get(2..).ok_or(tr("Serato Markers2 entries missing"))?;
+
+        let mut markers = Self::default();
+        while let Some(terminator) = data.iter().position(|&byte| byte == 0) &&
+            terminator > 0
+        {
+            let entry_type = String::from_utf8_lossy(&data[..terminator]).to_string();
+            if data.len() < terminator + 5
+            {
+                break;
+            }
+            let length = read_u32_be(data, terminator + 1);
+            let Some(entry) = data.get(terminator + 5..terminator + 5 + length as usize)
+            else
+            {
+                break;
+            };
+            data = &data[terminator + 5 + length as usize..];
+
+            match entry_type.as_str()
+            {
+                | "COLOR" => markers.track_color = read_rgb(entry, 1),
+                | "BPMLOCK" => markers.bpm_lock = entry.first().map(|&lock| lock != 0),
+                | "CUE" => markers.cues.extend(parse_serato_cue(entry)),
+                | "LOOP" => markers.cues.extend(parse_serato_loop(entry)),
+                | _ => markers.other.push(entry_type)
+            }
+        }
+        Ok(markers)
+    }
+}
+
+/// CUE entry: 0, index, position (ms), 0, RGB color, 2 bytes, null-terminated UTF-8 name
+fn parse_serato_cue(entry: &[u8]) -> Option<CuePoint>
+{
+    if entry.len() < 6
+    {
+        return None;
+    }
+    Some(CuePoint {
+        kind:     CueKind::Cue,
+        index:    Some(entry[1] as u32),
+        position: read_u32_be(entry, 2) as f64,
+        length:   None,
+        color:    read_rgb(entry, 7),
+        name:     read_terminated_utf8(entry.get(12..).unwrap_or_default()),
+        locked:   false
+    })
+}
+
+/// LOOP entry: 0, index, start and end (ms), 4 bytes 0xFF, ARGB color, 1 byte, locked flag, null-terminated UTF-8 name
+fn parse_serato_loop(entry: &[u8]) -> Option<CuePoint>
+{
+    if entry.len() < 10
+    {
+        return None;
+    }
+    let start = read_u32_be(entry, 2);
+    let end = read_u32_be(entry, 6);
+    Some(CuePoint {
+        kind:     CueKind::Loop,
+        index:    Some(entry[1] as u32),
+        position: start as f64,
+        length:   Some(end.saturating_sub(start) as f64),
+        color:    read_rgb(entry, 15),
+        name:     read_terminated_utf8(entry.get(20..).unwrap_or_default()),
+        locked:   entry.get(19).is_some_and(|&locked| locked != 0)
+    })
+}
+
+impl fmt::Display for SeratoMarkers
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if let Some(color) = self.track_color
+        {
+            writeln!(f, "{}", tr_fmt("Track color: #{}", &[&format!("{:06X}", color)]))?;
+        }
+        match self.bpm_lock
+        {
+            | Some(true) => writeln!(f, "{}", tr("BPM lock: yes"))?,
+            | Some(false) => writeln!(f, "{}", tr("BPM lock: no"))?,
+            | None => ()
+        }
+        writeln!(f, "{}", tr_fmt("Cue points: {}", &[&self.cues.len()]))?;
+        for cue in &self.cues
+        {
+            writeln!(f, "  {}", cue)?;
+        }
+        if self.other.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Other entries: {}", &[&self.other.join(", ")]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Beat marker of a Serato beat grid
+#[derive(Debug, Clone, Copy)]
+pub struct BeatMarker
+{
+    /// Position in seconds
+    pub position: f32,
+    /// Beats until the next marker, or the tempo of the last marker
+    pub beats:    BeatSpan
+}
+
+/// What follows a beat marker
+#[derive(Debug, Clone, Copy)]
+pub enum BeatSpan
+{
+    /// Beats until the next marker
+    Beats(u32),
+    /// Tempo from the last marker to the end of the track
+    Bpm(f32)
+}
+
+/// Contents of a "Serato BeatGrid" GEOB frame: 2-byte version, marker count, 8-byte markers, 1 footer byte
+#[derive(Debug, Clone)]
+pub struct SeratoBeatGrid
+{
+    pub markers: Vec<BeatMarker>
+}
+
+impl SeratoBeatGrid
+{
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 6
+        {
+            return Err(tr("Serato BeatGrid data too short").to_string());
+        }
+        let count = read_u32_be(data, 2) as usize;
+        let mut markers = Vec::new();
+        for index in 0..count
+        {
+            let position = 6 + index * 8;
+            if data.len() < position + 8
+            {
+                return Err(tr_fmt("Serato BeatGrid truncated after {} of {} markers", &[&index, &count]));
+            }
+            let seconds = read_u32_be(data, position);
+            let value = read_u32_be(data, position + 4);
+            // Only the last marker stores a tempo
+            let beats = match index + 1 == count
+            {
+                | true => BeatSpan::Bpm(f32::from_bits(value)),
+                | false => BeatSpan::Beats(value)
+            };
+            markers.push(BeatMarker { position: f32::from_bits(seconds), beats });
+        }
+        Ok(Self { markers })
+    }
+}
+
+impl fmt::Display for SeratoBeatGrid
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Beat markers: {}", &[&self.markers.len()]))?;
+        for marker in &self.markers
+        {
+            let position = format_timestamp((marker.position.max(0.0) * 1000.0).round() as u32);
+            match marker.beats
+            {
+                | BeatSpan::Beats(beats) => writeln!(f, "  {}", tr_fmt("Marker at {}, {} beats to the next marker", &[&position, &beats]))?,
+                | BeatSpan::Bpm(bpm) => writeln!(f, "  {}", tr_fmt("Marker at {}, {} BPM", &[&position, &format!("{:.2}", bpm)]))?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cue points of a Traktor PRIV frame
+#[derive(Debug, Clone)]
+pub struct TraktorCues
+{
+    pub cues: Vec<CuePoint>
+}
+
+impl TraktorCues
+{
+    /// Decode the private data of a "TRAKTOR4" PRIV frame
+    ///
+    /// Chunks consist of a reversed 4-character ID, a little-endian size of the rest of the chunk and a child
+    /// count, followed by the children or the chunk data.
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let cue_data = find_traktor_chunk(data, b"PEUC").ok_or(tr("Traktor data holds no cue point chunk"))?;
+        if cue_data.len() < 4
+        {
+            return Err(tr("Traktor cue point chunk too short").to_string());
+        }
+        let count = read_u32_le(cue_data, 0);
+
+        // Entry: version, name length in UTF-16 units, name, display order, type, start and length (f64 ms), repeats, hot cue
+        let mut cues = Vec::new();
+        let mut position = 4;
+        for _ in 0..count
+        {
+            if cue_data.len() < position + 8
+            {
+                break;
+            }
+            let name_length = read_u32_le(cue_data, position + 4) as usize * 2;
+            let name_start = position + 8;
+            let Some(name) = cue_data.get(name_start..name_start + name_length)
+            else
+            {
+                break;
+            };
+            let fields = name_start + name_length;
+            if cue_data.len() < fields + 32
+            {
+                break;
+            }
+            let cue_type = read_u32_le(cue_data, fields + 4);
+            let start = f64::from_bits(read_u64_le(cue_data, fields + 8));
+            let length = f64::from_bits(read_u64_le(cue_data, fields + 16));
+            let hot_cue = read_u32_le(cue_data, fields + 28);
+            position = fields + 32;
+
+            let name = char::decode_utf16(name.chunks_exact(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]])))
+                .map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            cues.push(CuePoint {
+                kind: CueKind::from_traktor(cue_type).unwrap_or(CueKind::Cue),
+                // -1 marks cues without a hot cue slot
+                index: (hot_cue != u32::MAX).then_some(hot_cue),
+                position: start,
+                length: (length > 0.0).then_some(length),
+                color: None,
+                name,
+                locked: false
+            });
+        }
+        Ok(Self { cues })
+    }
+}
+
+impl fmt::Display for TraktorCues
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Traktor cue points: {}", &[&self.cues.len()]))?;
+        for cue in &self.cues
+        {
+            writeln!(f, "  {}", cue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the data of the chunk with the (reversed) ID `id` in a Traktor chunk tree
+fn find_traktor_chunk<'a>(data: &'a [u8], id: &[u8; 4]) -> Option<&'a [u8]>
+{
+    let mut position = 0;
+    while let Some(header) = data.get(position..position + 12)
+    {
+        let size = read_u32_le(header, 4) as usize;
+        let children = read_u32_le(header, 8);
+        // The size counts the child count field and the contents
+        let contents = data.get(position + 12..(position + 8).checked_add(size)?)?;
+        if &header[..4] == id
+        {
+            return Some(contents);
+        }
+        if children > 0 &&
+            let Some(found) = find_traktor_chunk(contents, id)
+        {
+            return Some(found);
+        }
+        position += 8 + size;
+    }
+    None
+}
+
+/// Read a 3-byte RGB color
+fn read_rgb(data: &[u8], position: usize) -> Option<u32>
+{
+    data.get(position..position + 3).map(|rgb| u32::from_be_bytes([0, rgb[0], rgb[1], rgb[2]]))
+}
+
+/// Decode a null-terminated UTF-8 string (the whole slice without terminator)
+fn read_terminated_utf8(data: &[u8]) -> String
+{
+    String::from_utf8_lossy(data.split(|&byte| byte == 0).next().unwrap_or_default()).to_string()
+}
@@ -0,0 +1,23 @@
+// DSD audio dissection
+//
+// This module provides support for the two container formats of Direct Stream Digital audio:
+// DSF (DSD Stream File) with its DSD, fmt and data chunks followed by an ID3v2 tag, and DSDIFF
+// (DSD Interchange File Format) with its FRM8 form of property, sound data and comment chunks.
+// The ID3v2 tags of both formats are dissected by the ID3v2 dissectors.
+
+// Core types and dissectors
+pub mod dff;
+pub mod dissector;
+pub mod dsf;
+
+// Re-export commonly used types for convenience
+pub use dissector::{DsdiffDissector, DsfDissector};
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the DSF and DSDIFF dissectors with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<DsfDissector>(700);
+    registry.register_dissector::<DsdiffDissector>(600);
+}
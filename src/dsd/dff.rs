@@ -0,0 +1,167 @@
+/// Size of a chunk header (ID and 64-bit size)
+use crate::i18n::tr_fmt;
+
+pub const CHUNK_HEADER_SIZE: u64 = 12;
+
+/// Chunk of a DSDIFF file (DSDIFF 1.5 specification)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DffChunk
+{
+    /// Offset of the chunk header in the file
+    pub offset:   u64,
+    pub id:       String,
+    /// Size of the chunk data, without header and pad byte
+    pub size:     u64,
+    /// Decoded content of property chunks
+    pub details:  Option<String>,
+    pub children: Vec<DffChunk>
+}
+
+impl DffChunk
+{
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "id": self.id,
+            "offset": self.offset,
+            "size": self.size,
+            "description": chunk_description(&self.id),
+            "details": self.details,
+            "children": self.children.iter().map(DffChunk::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Describe a chunk ID
+pub fn chunk_description(id: &str) -> &'static str
+{
+    match id
+    {
+        | "FRM8" => "Form",
+        | "FVER" => "Format version",
+        | "PROP" => "Property",
+        | "FS  " => "Sample rate",
+        | "CHNL" => "Channels",
+        | "CMPR" => "Compression type",
+        | "ABSS" => "Absolute start time",
+        | "LSCO" => "Loudspeaker configuration",
+        | "DSD " => "DSD sound data",
+        | "DST " => "DST sound data",
+        | "FRTE" => "DST frame information",
+        | "DSTI" => "DST sound index",
+        | "COMT" => "Comments",
+        | "DIIN" => "Edited master information",
+        | "EMID" => "Edited master ID",
+        | "MARK" => "Marker",
+        | "DIAR" => "Artist",
+        | "DITI" => "Title",
+        | "MANF" => "Manufacturer specific",
+        | "ID3 " => "ID3v2 tag",
+        | _ => "Unknown chunk"
+    }
+}
+
+/// Format version of an FVER chunk (e.g. "1.5.0.0")
+pub fn parse_version(body: &[u8]) -> Option<String>
+{
+    let bytes = body.get(0..4)?;
+    Some(format!("{}.{}.{}.{}", bytes[0], bytes[1], bytes[2], bytes[3]))
+}
+
+/// Sample rate of an FS chunk in Hz
+pub fn parse_sample_rate(body: &[u8]) -> Option<u32>
+{
+    Some(u32::from_be_bytes(body.get(0..4)?.try_into().ok()?))
+}
+
+/// Channel IDs of a CHNL chunk (e.g. "SLFT", "SRGT")
+pub fn parse_channels(body: &[u8]) -> Option<Vec<String>>
+{
+    let count = u16::from_be_bytes(body.get(0..2)?.try_into().ok()?) as usize;
+    let ids = body.get(2..2 + count * 4)?;
+    Some(ids.chunks_exact(4).map(|id| String::from_utf8_lossy(id).trim_end().to_string()).collect())
+}
+
+/// Compression type and its name from a CMPR chunk
+pub fn parse_compression(body: &[u8]) -> Option<(String, String)>
+{
+    let id = String::from_utf8_lossy(body.get(0..4)?).to_string();
+    let length = *body.get(4)? as usize;
+    let name = String::from_utf8_lossy(body.get(5..5 + length)?).to_string();
+    Some((id, name))
+}
+
+/// Absolute start time of an ABSS chunk as "hh:mm:ss + samples"
+pub fn parse_start_time(body: &[u8]) -> Option<String>
+{
+    let bytes = body.get(0..8)?;
+    let samples = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    Some(tr_fmt("{}:{}:{} + {} samples", &[
+        &format!("{:02}", u16::from_be_bytes([bytes[0], bytes[1]])),
+        &format!("{:02}", bytes[2]),
+        &format!("{:02}", bytes[3]),
+        &samples
+    ]))
+}
+
+/// Describe the loudspeaker configuration of an LSCO chunk
+pub fn loudspeaker_name(configuration: u16) -> &'static str
+{
+    match configuration
+    {
+        | 0 => "2-channel stereo",
+        | 3 => "5-channel (ITU-R BS.775-1)",
+        | 4 => "6-channel (5.1, ITU-R BS.775-1)",
+        | 65535 => "undefined",
+        | _ => "reserved"
+    }
+}
+
+/// Text of a DIAR or DITI chunk (32-bit length followed by the text)
+pub fn parse_counted_text(body: &[u8]) -> Option<String>
+{
+    let length = u32::from_be_bytes(body.get(0..4)?.try_into().ok()?) as usize;
+    Some(String::from_utf8_lossy(body.get(4..4 + length)?).to_string())
+}
+
+/// Comments of a COMT chunk as "YYYY-MM-DD hh:mm: text"
+pub fn parse_comments(body: &[u8]) -> Vec<String>
+{
+    let count = body.get(0..2).map_or(0, |bytes| u16::from_be_bytes([bytes[0], bytes[1]]));
+    let mut comments = Vec::new();
+    let mut position = 2;
+    for _ in 0..count
+    {
+        // Time stamp, comment type and reference, text length, text padded to an even size
+        let Some(header) = body.get(position..position + 14)
+        else
+        {
+            break;
+        };
+        let length = u32::from_be_bytes([header[10], header[11], header[12], header[13]]) as usize;
+        let Some(text) = body.get(position + 14..position + 14 + length)
+        else
+        {
+            break;
+        };
+        comments.push(format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}: {}",
+            u16::from_be_bytes([header[0], header[1]]),
+            header[2],
+            header[3],
+            header[4],
+            header[5],
+            String::from_utf8_lossy(text)
+        ));
+        position += 14 + length + (length & 1);
+    }
+    comments
+}
+
+/// Frame count and frame rate of a DST FRTE chunk
+pub fn parse_frame_information(body: &[u8]) -> Option<(u32, u16)>
+{
+    let bytes = body.get(0..6)?;
+    Some((u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]), u16::from_be_bytes([bytes[4], bytes[5]])))
+}
@@ -0,0 +1,892 @@
+use std::io::{Cursor, Write};
+
+use crate::{
+    artwork::Artwork,
+    chapters::Chapter,
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    dissector_registry::DissectorRegistry,
+    dsd::{
+        dff::{
+            CHUNK_HEADER_SIZE, DffChunk, chunk_description, loudspeaker_name, parse_channels, parse_comments, parse_compression, parse_counted_text,
+            parse_frame_information, parse_sample_rate, parse_start_time, parse_version
+        },
+        dsf::{
+            BLOCK_SIZE, DATA_HEADER_SIZE, DSD_CHUNK_SIZE, DsdChunk, FMT_CHUNK_SIZE, FmtChunk, channel_type_channels, channel_type_name, data_chunk_size, dsd_rate_name
+        }
+    },
+    ffprobe::{ProbeFormat, ProbeStream},
+    find::Occurrence,
+    i18n::{tr, tr_fmt},
+    id3v2::tools::detect_id3v2_version,
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    metadata::MediaMetadata,
+    objects::EmbeddedObject,
+    options::DissectOptions,
+    summary::Summary
+};
+
+/// Property and comment chunks larger than this are listed but not decoded
+const MAX_PROPERTY_SIZE: u64 = 1024 * 1024;
+
+/// Sample rate with its DSD rate name (e.g. "2822400 Hz (DSD64)")
+fn format_sample_rate(sample_rate: u32) -> String
+{
+    match dsd_rate_name(sample_rate)
+    {
+        | Some(name) => format!("{} ({})", tr_fmt("Sample Rate: {} Hz", &[&sample_rate]), name),
+        | None => tr_fmt("Sample Rate: {} Hz", &[&sample_rate])
+    }
+}
+
+/// Result of reading the chunks of a DSF file
+struct DsfFile
+{
+    dsd:        DsdChunk,
+    format:     FmtChunk,
+    /// Size of the sample data in the data chunk
+    data_size:  u64,
+    /// Offset, ID and size (including the header) of the DSD, fmt and data chunks
+    chunks:     Vec<(u64, &'static str, u64)>,
+    /// Offset of the ID3v2 tag in the file
+    metadata:   Option<u64>,
+    end_offset: u64
+}
+
+/// DSF (DSD Stream File) dissector - unit struct
+#[derive(Default)]
+pub struct DsfDissector;
+
+impl DsfDissector
+{
+    /// Read the DSD, fmt and data chunks from `start_offset` and check them against each other
+    fn read_chunks(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<DsfFile, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut header = vec![0u8; (DSD_CHUNK_SIZE + FMT_CHUNK_SIZE).min(file_size - start_offset) as usize];
+        reader.read_exact(&mut header)?;
+        let dsd = DsdChunk::parse(&header)?;
+        let format = FmtChunk::parse(&header[DSD_CHUNK_SIZE as usize..])?;
+
+        if format.format_version != 1
+        {
+            diagnostics.warning(Some(start_offset + DSD_CHUNK_SIZE), tr_fmt("DSF format version {} (only version 1 is defined)", &[&format.format_version]));
+        }
+        if format.format_id != 0
+        {
+            diagnostics.warning(Some(start_offset + DSD_CHUNK_SIZE), tr_fmt("DSF format ID {} is not DSD raw", &[&format.format_id]));
+        }
+        if channel_type_channels(format.channel_type).is_some_and(|channels| channels != format.channel_count) == true
+        {
+            diagnostics.warning(
+                Some(start_offset + DSD_CHUNK_SIZE),
+                tr_fmt("Channel type {} ({}) does not match the channel count {}", &[
+                    &format.channel_type,
+                    &channel_type_name(format.channel_type),
+                    &format.channel_count
+                ])
+            );
+        }
+        if matches!(format.bits_per_sample, 1 | 8) == false
+        {
+            diagnostics.warning(Some(start_offset + DSD_CHUNK_SIZE), tr_fmt("{} bits per sample (1 or 8 expected)", &[&format.bits_per_sample]));
+        }
+        if format.block_size != BLOCK_SIZE
+        {
+            diagnostics.warning(
+                Some(start_offset + DSD_CHUNK_SIZE),
+                tr_fmt("Block size of {} bytes per channel (the specification requires {})", &[&format.block_size, &BLOCK_SIZE])
+            );
+        }
+
+        let mut chunks = vec![(start_offset, "DSD ", DSD_CHUNK_SIZE), (start_offset + DSD_CHUNK_SIZE, "fmt ", format.size)];
+        let data_offset = start_offset + DSD_CHUNK_SIZE + format.size;
+        let mut data_header = [0u8; DATA_HEADER_SIZE as usize];
+        let data_chunk = match data_offset.checked_add(DATA_HEADER_SIZE)
+        {
+            | Some(end) if end <= file_size =>
+            {
+                reader.seek_to(data_offset)?;
+                reader.read_exact(&mut data_header)?;
+                data_chunk_size(&data_header)
+            }
+            | _ => None
+        };
+        let Some(data_chunk_size) = data_chunk
+        else
+        {
+            diagnostics.warning(Some(data_offset), tr("DSF data chunk missing"));
+            return Ok(DsfFile { dsd, format, data_size: 0, chunks, metadata: None, end_offset: data_offset.min(file_size) });
+        };
+        chunks.push((data_offset, "data", data_chunk_size));
+
+        let data_size = data_chunk_size.saturating_sub(DATA_HEADER_SIZE);
+        let data_end = data_offset.saturating_add(data_chunk_size);
+        let channels = format.channel_count.max(1) as u64;
+        if data_size % (format.block_size.max(1) as u64 * channels) != 0
+        {
+            diagnostics.warning(Some(data_offset), tr_fmt("Data chunk of {} bytes does not hold whole blocks of all {} channels", &[&data_size, &channels]));
+        }
+        if data_size < format.expected_data_size()
+        {
+            diagnostics.warning(
+                Some(data_offset),
+                tr_fmt("Data chunk holds {} bytes, {} samples per channel need {}", &[&data_size, &format.sample_count, &format.expected_data_size()])
+            );
+        }
+
+        // The DSD chunk points to the tag behind the data chunk; the tag is left to the ID3v2 dissectors
+        let metadata = match dsd.metadata_offset
+        {
+            | 0 => None,
+            | offset => Some(start_offset.saturating_add(offset))
+        };
+        if let Some(metadata_offset) = metadata
+        {
+            let mut signature = [0u8; 3];
+            if metadata_offset.saturating_add(3) > file_size
+            {
+                diagnostics.warning(Some(start_offset), tr_fmt("Metadata pointer {} lies beyond the end of the file", &[&format!("0x{:X}", metadata_offset)]));
+            }
+            else
+            {
+                reader.seek_to(metadata_offset)?;
+                reader.read_exact(&mut signature)?;
+                if &signature != b"ID3"
+                {
+                    diagnostics.warning(Some(start_offset), tr_fmt("Metadata pointer {} does not point to an ID3v2 tag", &[&format!("0x{:X}", metadata_offset)]));
+                }
+                else if metadata_offset != data_end
+                {
+                    diagnostics.warning(
+                        Some(start_offset),
+                        tr_fmt("ID3v2 tag at {} does not follow the data chunk ending at {}", &[&format!("0x{:X}", metadata_offset), &format!("0x{:X}", data_end)])
+                    );
+                }
+            }
+        }
+
+        if data_end > file_size
+        {
+            diagnostics.warning(Some(data_offset), tr_fmt("Data chunk truncated: {} bytes declared, {} available", &[&data_chunk_size, &(file_size - data_offset)]));
+        }
+        else if dsd.file_size != file_size - start_offset
+        {
+            diagnostics
+                .warning(Some(start_offset), tr_fmt("DSD chunk declares a file size of {} bytes, {} bytes present", &[&dsd.file_size, &(file_size - start_offset)]));
+        }
+
+        Ok(DsfFile { dsd, format, data_size, chunks, metadata, end_offset: data_end.min(file_size) })
+    }
+}
+
+impl MediaDissector for DsfDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "DSF"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "DSF Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let dsf = Self::read_chunks(file, start_offset, diagnostics)?;
+        let format = &dsf.format;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("DSF File:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Format version: {}", &[&format.format_version]))?;
+            writeln!(output, "  {}", tr_fmt("Channels: {} ({})", &[&format.channel_count, &tr(channel_type_name(format.channel_type))]))?;
+            writeln!(output, "  {}", format_sample_rate(format.sample_rate))?;
+            writeln!(output, "  {}", tr_fmt("Bits per sample: {}", &[&format.bits_per_sample]))?;
+            writeln!(output, "  {}", tr_fmt("Samples: {} per channel", &[&format.sample_count]))?;
+            if let Some(duration) = format.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Block size: {} bytes per channel", &[&format.block_size]))?;
+            if let Some(metadata) = dsf.metadata
+            {
+                writeln!(output, "  {}", tr_fmt("Metadata: ID3v2 tag at offset 0x{}", &[&format!("{:08X}", metadata)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(dsf.end_offset - start_offset)]))?;
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "\n{}", tr("DSF Chunks:").bright_cyan().bold())?;
+            for &(offset, id, size) in &dsf.chunks
+            {
+                writeln!(output, "  0x{:08X}  {}  {}", offset, id, tr_fmt("{} bytes", &[&size]))?;
+            }
+        }
+
+        Ok(dsf.end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let dsf = Self::read_chunks(file, start_offset, diagnostics)?;
+        let format = &dsf.format;
+
+        Ok((
+            serde_json::json!({
+                "format_version": format.format_version,
+                "format_id": format.format_id,
+                "channel_type": format.channel_type,
+                "channels": format.channel_count,
+                "sample_rate": format.sample_rate,
+                "bits_per_sample": format.bits_per_sample,
+                "sample_count": format.sample_count,
+                "block_size": format.block_size,
+                "duration": format.duration(),
+                "file_size": dsf.dsd.file_size,
+                "data_size": dsf.data_size,
+                "metadata_offset": dsf.metadata,
+                "chunks": dsf.chunks.iter().map(|&(offset, id, size)| serde_json::json!({ "id": id, "offset": offset, "size": size })).collect::<Vec<_>>()
+            }),
+            dsf.end_offset
+        ))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let dsf = Self::read_chunks(file, start_offset, diagnostics)?;
+        let format = &dsf.format;
+        let (codec_name, codec_long_name) = match format.bits_per_sample
+        {
+            | 8 => ("dsd_msbf_planar", "DSD (Direct Stream Digital), most significant bit first, planar"),
+            | _ => ("dsd_lsbf_planar", "DSD (Direct Stream Digital), least significant bit first, planar")
+        };
+        let stream = ProbeStream {
+            codec_name: Some(codec_name),
+            codec_long_name: Some(codec_long_name),
+            codec_type: "audio",
+            // ffmpeg decodes DSD to bytes of eight samples and reports that rate
+            sample_rate: Some(format.sample_rate / 8),
+            channels: Some(format.channel_count as u16),
+            duration: format.duration(),
+            bit_rate: Some(format.sample_rate as u64 * format.channel_count as u64),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat {
+                format_name:      "dsf",
+                format_long_name: "DSD Stream File (DSF)",
+                duration:         format.duration(),
+                streams:          vec![stream]
+            }),
+            dsf.end_offset
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let dsf = Self::read_chunks(file, start_offset, &mut Diagnostics::new())?;
+        coverage.add(start_offset, dsf.end_offset);
+
+        Ok(dsf.end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let dsf = Self::read_chunks(file, start_offset, diagnostics)?;
+
+        Ok(dsf.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // DSD chunk ID followed by its fixed little-endian size of 28 bytes
+        match header.len() >= 12 && &header[0..4] == b"DSD " && header[4..12] == DSD_CHUNK_SIZE.to_le_bytes()
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
+
+/// Result of reading the FRM8 form of a DSDIFF file
+#[derive(Default)]
+struct DsdiffFile
+{
+    /// The FRM8 chunk with all chunks below it
+    form:         Option<DffChunk>,
+    version:      Option<String>,
+    has_property: bool,
+    sample_rate:  Option<u32>,
+    channels:     Vec<String>,
+    /// Compression type ID and name
+    compression:  Option<(String, String)>,
+    /// Offset, ID and data size of the sound data chunk
+    sound_data:   Option<(u64, String, u64)>,
+    /// Frame count and frame rate of DST compressed sound data
+    dst_frames:   Option<(u32, u16)>,
+    artist:       Option<String>,
+    title:        Option<String>,
+    comments:     Vec<String>,
+    /// Offset and size of the ID3v2 tags in ID3 chunks
+    id3_tags:     Vec<(u64, u64)>,
+    end_offset:   u64
+}
+
+impl DsdiffFile
+{
+    /// Duration from the DST frame count, or from the size of the uncompressed sound data
+    fn duration(&self) -> Option<f64>
+    {
+        match (&self.sound_data, self.dst_frames, self.sample_rate)
+        {
+            | (Some((_, id, _)), Some((frames, frame_rate)), _) if id == "DST " && frame_rate > 0 => Some(frames as f64 / frame_rate as f64),
+            | (Some((_, id, size)), _, Some(sample_rate)) if id == "DSD " && sample_rate > 0 && self.channels.is_empty() == false =>
+                Some((size * 8 / self.channels.len() as u64) as f64 / sample_rate as f64),
+            | _ => None
+        }
+    }
+
+    /// Descriptive metadata of the edited master information chunk
+    fn metadata(&self) -> MediaMetadata
+    {
+        MediaMetadata { title: self.title.clone(), artist: self.artist.clone(), comment: self.comments.first().cloned(), ..Default::default() }
+    }
+}
+
+/// DSDIFF (DSD Interchange File Format) dissector - unit struct
+#[derive(Default)]
+pub struct DsdiffDissector;
+
+impl DsdiffDissector
+{
+    /// Read the FRM8 form from `start_offset` with all its chunks and check the required chunks
+    fn read_form(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<DsdiffFile, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        if start_offset + CHUNK_HEADER_SIZE + 4 > file_size
+        {
+            return Err("DSDIFF form header truncated".into());
+        }
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header)?;
+        let form_size = u64::from_be_bytes(header[4..12].try_into()?);
+        let form_end = match (start_offset + CHUNK_HEADER_SIZE).checked_add(form_size)
+        {
+            | Some(end) if end <= file_size => end,
+            | _ =>
+            {
+                diagnostics.warning(
+                    Some(start_offset),
+                    tr_fmt("FRM8 form truncated: {} bytes declared, {} available", &[&form_size, &(file_size - start_offset - CHUNK_HEADER_SIZE)])
+                );
+                file_size
+            }
+        };
+
+        let mut dsdiff = DsdiffFile::default();
+        let children = Self::read_chunks(&mut reader, start_offset + CHUNK_HEADER_SIZE + 4, form_end, &mut dsdiff, diagnostics)?;
+
+        if children.first().is_some_and(|chunk| chunk.id == "FVER") == false
+        {
+            diagnostics.warning(Some(start_offset), tr("Format version chunk is not the first chunk of the form"));
+        }
+        if let Some(ref version) = dsdiff.version &&
+            version.starts_with("1.") == false
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("DSDIFF format version {} (only version 1.x is defined)", &[&version]));
+        }
+        match dsdiff.has_property
+        {
+            | true =>
+            {
+                let required = [
+                    (dsdiff.sample_rate.is_none(), "Sample rate chunk missing in the property chunk"),
+                    (dsdiff.channels.is_empty(), "Channels chunk missing in the property chunk"),
+                    (dsdiff.compression.is_none(), "Compression type chunk missing in the property chunk")
+                ];
+                for (missing, message) in required
+                {
+                    if missing == true
+                    {
+                        diagnostics.warning(Some(start_offset), tr(message));
+                    }
+                }
+            }
+            | false => diagnostics.warning(Some(start_offset), tr("Property chunk missing"))
+        }
+        match (&dsdiff.sound_data, &dsdiff.compression)
+        {
+            | (None, _) => diagnostics.warning(Some(start_offset), tr("Sound data chunk missing")),
+            | (Some((offset, id, _)), Some((compression, _))) if id != compression =>
+            {
+                diagnostics.warning(Some(*offset), tr_fmt("Compression type '{}' does not match the '{}' sound data chunk", &[&compression, &id]));
+            }
+            | (Some((offset, id, size)), _) if id == "DSD " && dsdiff.channels.is_empty() == false && size % dsdiff.channels.len() as u64 != 0 =>
+            {
+                diagnostics
+                    .warning(Some(*offset), tr_fmt("DSD sound data of {} bytes does not hold the same samples for all {} channels", &[&size, &dsdiff.channels.len()]));
+            }
+            | _ => ()
+        }
+
+        dsdiff.form = Some(DffChunk { offset: start_offset, id: "FRM8".to_string(), size: form_size, details: Some("DSD".to_string()), children });
+        // Chunks are padded to an even size, the form included
+        dsdiff.end_offset = (form_end + (form_size & 1)).min(file_size);
+
+        Ok(dsdiff)
+    }
+
+    /// Read the chunks from `start` to `end`, descending into property and edited master information chunks
+    fn read_chunks(
+        reader: &mut MediaReader, start: u64, end: u64, dsdiff: &mut DsdiffFile, diagnostics: &mut Diagnostics
+    ) -> Result<Vec<DffChunk>, Box<dyn std::error::Error>>
+    {
+        let mut chunks = Vec::new();
+        let mut offset = start;
+
+        while offset + CHUNK_HEADER_SIZE <= end
+        {
+            let mut header = [0u8; CHUNK_HEADER_SIZE as usize];
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut header)?;
+            let id = String::from_utf8_lossy(&header[0..4]).to_string();
+            let size = u64::from_be_bytes(header[4..12].try_into()?);
+            let data_start = offset + CHUNK_HEADER_SIZE;
+            let data_end = match data_start.checked_add(size)
+            {
+                | Some(data_end) if data_end <= end => data_end,
+                | _ =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("'{}' chunk of {} bytes exceeds its container by {} bytes", &[&id, &size, &(size - (end - data_start))]));
+                    end
+                }
+            };
+            let mut chunk = DffChunk { offset, id: id.clone(), size, details: None, children: Vec::new() };
+
+            match id.as_str()
+            {
+                | "PROP" =>
+                {
+                    let mut property_type = [0u8; 4];
+                    if data_end - data_start >= 4
+                    {
+                        reader.read_exact(&mut property_type)?;
+                    }
+                    if &property_type != b"SND "
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("Property chunk of type '{}' (expected 'SND ')", &[&String::from_utf8_lossy(&property_type)]));
+                    }
+                    if dsdiff.sound_data.is_some() == true
+                    {
+                        diagnostics.warning(Some(offset), tr("Property chunk follows the sound data chunk"));
+                    }
+                    dsdiff.has_property = true;
+                    chunk.details = Some(String::from_utf8_lossy(&property_type).trim_end().to_string());
+                    chunk.children = Self::read_chunks(reader, (data_start + 4).min(data_end), data_end, dsdiff, diagnostics)?;
+                }
+                | "DIIN" => chunk.children = Self::read_chunks(reader, data_start, data_end, dsdiff, diagnostics)?,
+                | "DSD " | "DST " =>
+                {
+                    if dsdiff.sound_data.is_some() == true
+                    {
+                        diagnostics.warning(Some(offset), tr("Form contains more than one sound data chunk"));
+                    }
+                    dsdiff.sound_data.get_or_insert((offset, id.clone(), data_end - data_start));
+                    // DST sound data starts with the frame information chunk
+                    let mut frame_information = [0u8; CHUNK_HEADER_SIZE as usize + 6];
+                    if id == "DST " && data_end - data_start >= frame_information.len() as u64
+                    {
+                        reader.read_exact(&mut frame_information)?;
+                        if &frame_information[0..4] == b"FRTE" &&
+                            let Some((frames, frame_rate)) = parse_frame_information(&frame_information[CHUNK_HEADER_SIZE as usize..])
+                        {
+                            dsdiff.dst_frames = Some((frames, frame_rate));
+                            chunk.details = Some(tr_fmt("{} frames at {} frames/s", &[&frames, &frame_rate]));
+                        }
+                    }
+                }
+                | "ID3 " =>
+                {
+                    let mut signature = [0u8; 3];
+                    if data_end - data_start >= 3
+                    {
+                        reader.read_exact(&mut signature)?;
+                    }
+                    match &signature
+                    {
+                        | b"ID3" => dsdiff.id3_tags.push((data_start, data_end - data_start)),
+                        | _ => diagnostics.warning(Some(offset), tr("ID3 chunk does not contain an ID3v2 tag"))
+                    }
+                }
+                | _ if data_end - data_start > MAX_PROPERTY_SIZE => (),
+                | _ =>
+                {
+                    let mut body = vec![0u8; (data_end - data_start) as usize];
+                    reader.read_exact(&mut body)?;
+                    chunk.details = Self::read_property(dsdiff, &id, &body);
+                }
+            }
+
+            chunks.push(chunk);
+            offset = data_end + (size & 1);
+        }
+
+        if offset < end
+        {
+            diagnostics.warning(Some(offset), tr_fmt("{} bytes after the last chunk are too short for a chunk header", &[&(end - offset)]));
+        }
+
+        Ok(chunks)
+    }
+
+    /// Decode a property or comment chunk, returning its content for the chunk listing
+    fn read_property(dsdiff: &mut DsdiffFile, id: &str, body: &[u8]) -> Option<String>
+    {
+        match id
+        {
+            | "FVER" =>
+            {
+                dsdiff.version = parse_version(body);
+                dsdiff.version.clone()
+            }
+            | "FS  " =>
+            {
+                dsdiff.sample_rate = parse_sample_rate(body);
+                dsdiff.sample_rate.map(|sample_rate| format!("{} Hz", sample_rate))
+            }
+            | "CHNL" =>
+            {
+                dsdiff.channels = parse_channels(body).unwrap_or_default();
+                Some(dsdiff.channels.join(", "))
+            }
+            | "CMPR" =>
+            {
+                dsdiff.compression = parse_compression(body);
+                dsdiff.compression.as_ref().map(|(id, name)| format!("{} ({})", id.trim_end(), name))
+            }
+            | "ABSS" => parse_start_time(body),
+            | "LSCO" => body.get(0..2).map(|bytes| {
+                let configuration = u16::from_be_bytes([bytes[0], bytes[1]]);
+                format!("{} ({})", configuration, loudspeaker_name(configuration))
+            }),
+            | "COMT" =>
+            {
+                let comments = parse_comments(body);
+                let count = comments.len();
+                dsdiff.comments.extend(comments);
+                Some(tr_fmt("{} comments", &[&count]))
+            }
+            | "DIAR" =>
+            {
+                dsdiff.artist = parse_counted_text(body);
+                dsdiff.artist.clone()
+            }
+            | "DITI" =>
+            {
+                dsdiff.title = parse_counted_text(body);
+                dsdiff.title.clone()
+            }
+            | "EMID" => Some(String::from_utf8_lossy(body).to_string()),
+            | _ => None
+        }
+    }
+
+    /// Run `action` with the dissector of every ID3v2 tag in an ID3 chunk
+    ///
+    /// The dissector works on a copy of the tag, so it stops at the end of the chunk instead of running
+    /// into the following chunks. Its findings are moved to the offsets of the tag in the file.
+    fn for_each_tag(
+        file: &mut dyn MediaSource, dsdiff: &DsdiffFile, diagnostics: &mut Diagnostics,
+        mut action: impl FnMut(&dyn MediaDissector, &mut Cursor<Vec<u8>>, u64, &mut Diagnostics) -> Result<(), Box<dyn std::error::Error>>
+    ) -> Result<(), Box<dyn std::error::Error>>
+    {
+        for &(offset, size) in &dsdiff.id3_tags
+        {
+            let data = file.map(offset, size)?.to_vec();
+            if detect_id3v2_version(&data).is_none() == true
+            {
+                continue;
+            }
+            let Some(dissector) = DissectorRegistry::new().build_for_payload(&data).filter(|dissector| dissector.media_type().starts_with("ID3v2") == true)
+            else
+            {
+                diagnostics.info(Some(offset), tr("ID3v2 tag version not supported, tag skipped"));
+                continue;
+            };
+            let mut window = Cursor::new(data);
+            let mut tag_diagnostics = Diagnostics::new();
+            action(dissector.as_ref(), &mut window, offset, &mut tag_diagnostics)?;
+            diagnostics.extend_shifted(tag_diagnostics, offset);
+        }
+        Ok(())
+    }
+
+    /// Print a chunk and the chunks below it
+    fn print_chunk(chunk: &DffChunk, indent: usize, output: &mut dyn Write) -> std::io::Result<()>
+    {
+        let mut line =
+            tr_fmt("Chunk at offset {}: '{}' ({}) - Size: {} bytes", &[&format!("0x{:08X}", chunk.offset), &chunk.id, &tr(chunk_description(&chunk.id)), &chunk.size]);
+        if let Some(ref details) = chunk.details
+        {
+            line.push_str(&format!(" = {}", details));
+        }
+        writeln!(output, "{}{}", "    ".repeat(indent), line)?;
+        for child in &chunk.children
+        {
+            Self::print_chunk(child, indent + 1, output)?;
+        }
+        Ok(())
+    }
+}
+
+impl MediaDissector for DsdiffDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "DSDIFF"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "DSDIFF Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let dsdiff = Self::read_form(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("DSDIFF File:").bright_cyan().bold())?;
+            if let Some(ref version) = dsdiff.version
+            {
+                writeln!(output, "  {}", tr_fmt("Format version: {}", &[version]))?;
+            }
+            if let Some((ref id, ref name)) = dsdiff.compression
+            {
+                writeln!(output, "  {}", tr_fmt("Compression: {} ({})", &[&id.trim_end(), name]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Channels: {} ({})", &[&dsdiff.channels.len(), &dsdiff.channels.join(", ")]))?;
+            if let Some(sample_rate) = dsdiff.sample_rate
+            {
+                writeln!(output, "  {}", format_sample_rate(sample_rate))?;
+            }
+            if let Some(duration) = dsdiff.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            if let Some(ref title) = dsdiff.title
+            {
+                writeln!(output, "  {}", tr_fmt("Title: {}", &[title]))?;
+            }
+            if let Some(ref artist) = dsdiff.artist
+            {
+                writeln!(output, "  {}", tr_fmt("Artist: {}", &[artist]))?;
+            }
+            for comment in &dsdiff.comments
+            {
+                writeln!(output, "  {}", tr_fmt("Comment: {}", &[comment]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(dsdiff.end_offset - start_offset)]))?;
+        }
+
+        if options.show_data == true &&
+            let Some(ref form) = dsdiff.form
+        {
+            writeln!(output, "\n{}\n", tr("DSDIFF Chunks:").bright_cyan().bold())?;
+            Self::print_chunk(form, 0, output)?;
+        }
+
+        Self::for_each_tag(file, &dsdiff, diagnostics, |dissector, window, offset, tag_diagnostics| {
+            writeln!(output, "\n{}", tr_fmt("ID3 chunk at offset 0x{}:", &[&format!("{:08X}", offset)]).bright_cyan().bold())?;
+            dissector.dissect_with_options(window, 0, options, output, tag_diagnostics)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let dsdiff = Self::read_form(file, start_offset, diagnostics)?;
+        let mut tags = Vec::new();
+        Self::for_each_tag(file, &dsdiff, diagnostics, |dissector, window, offset, tag_diagnostics| {
+            let (tag, _) = dissector.dissect_to_json(window, 0, options, tag_diagnostics)?;
+            tags.push(serde_json::json!({ "offset": offset, "media_type": dissector.media_type(), "content": tag }));
+            Ok(())
+        })?;
+
+        Ok((
+            serde_json::json!({
+                "version": dsdiff.version,
+                "compression": dsdiff.compression.as_ref().map(|(id, _)| id.trim_end()),
+                "compression_name": dsdiff.compression.as_ref().map(|(_, name)| name),
+                "channels": dsdiff.channels,
+                "sample_rate": dsdiff.sample_rate,
+                "duration": dsdiff.duration(),
+                "dst_frames": dsdiff.dst_frames.map(|(frames, _)| frames),
+                "title": dsdiff.title,
+                "artist": dsdiff.artist,
+                "comments": dsdiff.comments,
+                "chunks": dsdiff.form.as_ref().map(DffChunk::to_json),
+                "id3v2": tags
+            }),
+            dsdiff.end_offset
+        ))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let dsdiff = Self::read_form(file, start_offset, diagnostics)?;
+        let (codec_name, codec_long_name) = match dsdiff.sound_data
+        {
+            | Some((_, ref id, _)) if id == "DST " => ("dst", "DST (Direct Stream Transfer)"),
+            | _ => ("dsd_msbf", "DSD (Direct Stream Digital), most significant bit first")
+        };
+        let stream = ProbeStream {
+            codec_name: Some(codec_name),
+            codec_long_name: Some(codec_long_name),
+            codec_type: "audio",
+            // ffmpeg decodes DSD to bytes of eight samples and reports that rate
+            sample_rate: dsdiff.sample_rate.map(|sample_rate| sample_rate / 8),
+            channels: Some(dsdiff.channels.len() as u16),
+            duration: dsdiff.duration(),
+            ..Default::default()
+        };
+
+        Ok((
+            Some(ProbeFormat {
+                format_name:      "iff",
+                format_long_name: "IFF (Interchange File Format)",
+                duration:         dsdiff.duration(),
+                streams:          vec![stream]
+            }),
+            dsdiff.end_offset
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let dsdiff = Self::read_form(file, start_offset, &mut Diagnostics::new())?;
+        coverage.add(start_offset, dsdiff.end_offset);
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_artwork(&self, file: &mut dyn MediaSource, start_offset: u64, artwork: &mut Vec<Artwork>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, _offset, _tag_diagnostics| {
+            dissector.collect_artwork(window, 0, artwork)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_objects(&self, file: &mut dyn MediaSource, start_offset: u64, objects: &mut Vec<EmbeddedObject>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, _offset, _tag_diagnostics| {
+            dissector.collect_objects(window, 0, objects)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_chapters(&self, file: &mut dyn MediaSource, start_offset: u64, chapters: &mut Vec<Chapter>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, _offset, _tag_diagnostics| {
+            dissector.collect_chapters(window, 0, chapters)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_metadata(&self, file: &mut dyn MediaSource, start_offset: u64, metadata: &mut MediaMetadata) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, _offset, _tag_diagnostics| {
+            dissector.collect_metadata(window, 0, metadata)?;
+            Ok(())
+        })?;
+        metadata.merge(dsdiff.metadata());
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_summary(&self, file: &mut dyn MediaSource, start_offset: u64, summary: &mut Summary) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, _offset, _tag_diagnostics| {
+            dissector.collect_summary(window, 0, summary)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn collect_occurrences(
+        &self, file: &mut dyn MediaSource, start_offset: u64, code: &str, occurrences: &mut Vec<Occurrence>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let dsdiff = Self::read_form(file, start_offset, &mut diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, &mut diagnostics, |dissector, window, offset, _tag_diagnostics| {
+            let mut tag_occurrences = Vec::new();
+            dissector.collect_occurrences(window, 0, code, &mut tag_occurrences)?;
+            occurrences.extend(tag_occurrences.into_iter().map(|occurrence| Occurrence { offset: occurrence.offset + offset, ..occurrence }));
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let dsdiff = Self::read_form(file, start_offset, diagnostics)?;
+        Self::for_each_tag(file, &dsdiff, diagnostics, |dissector, window, _offset, tag_diagnostics| {
+            dissector.validate(window, 0, tag_diagnostics)?;
+            Ok(())
+        })?;
+
+        Ok(dsdiff.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // FRM8 form of type "DSD "
+        match header.len() >= 16 && &header[0..4] == b"FRM8" && &header[12..16] == b"DSD "
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
@@ -0,0 +1,162 @@
+/// Size of the DSD chunk including its header
+use crate::{
+    bytes::{read_u32_le, read_u64_le},
+    i18n::{tr, tr_fmt}
+};
+
+pub const DSD_CHUNK_SIZE: u64 = 28;
+
+/// Size of the fmt chunk of format version 1 including its header
+pub const FMT_CHUNK_SIZE: u64 = 52;
+
+/// Size of the data chunk header
+pub const DATA_HEADER_SIZE: u64 = 12;
+
+/// Block size per channel required by the specification
+pub const BLOCK_SIZE: u32 = 4096;
+
+/// Describe a channel type of the fmt chunk (DSF file format specification 1.01)
+pub fn channel_type_name(channel_type: u32) -> &'static str
+{
+    match channel_type
+    {
+        | 1 => "mono",
+        | 2 => "stereo",
+        | 3 => "3 channels",
+        | 4 => "quad",
+        | 5 => "4 channels",
+        | 6 => "5 channels",
+        | 7 => "5.1 channels",
+        | _ => "unknown channel type"
+    }
+}
+
+/// Number of channels a channel type consists of
+pub fn channel_type_channels(channel_type: u32) -> Option<u32>
+{
+    match channel_type
+    {
+        | 1..=4 => Some(channel_type),
+        | 5 => Some(4),
+        | 6 => Some(5),
+        | 7 => Some(6),
+        | _ => None
+    }
+}
+
+/// Name of a DSD sample rate as multiple of 44.1 kHz (e.g. "DSD64" for 2.8224 MHz)
+pub fn dsd_rate_name(sample_rate: u32) -> Option<String>
+{
+    match (sample_rate % 44100, sample_rate / 44100)
+    {
+        | (0, multiple) if multiple >= 32 => Some(format!("DSD{}", multiple)),
+        | _ => None
+    }
+}
+
+/// DSD chunk: total file size and position of the metadata chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DsdChunk
+{
+    /// Total file size declared by the writer
+    pub file_size:       u64,
+    /// Offset of the ID3v2 tag from the start of the file, 0 if there is none
+    pub metadata_offset: u64
+}
+
+impl DsdChunk
+{
+    /// Parse the DSD chunk at the start of `data` (including its header)
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < DSD_CHUNK_SIZE as usize || &data[0..4] != b"DSD "
+        {
+            return Err(tr("Missing DSF DSD chunk").to_string());
+        }
+        let size = read_u64_le(data, 4);
+        if size != DSD_CHUNK_SIZE
+        {
+            return Err(tr_fmt("DSD chunk size {} (the specification requires {})", &[&size, &DSD_CHUNK_SIZE]));
+        }
+
+        Ok(DsdChunk { file_size: read_u64_le(data, 12), metadata_offset: read_u64_le(data, 20) })
+    }
+}
+
+/// fmt chunk: layout of the DSD samples in the data chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FmtChunk
+{
+    /// Chunk size including the header
+    pub size:            u64,
+    pub format_version:  u32,
+    /// 0 for DSD raw
+    pub format_id:       u32,
+    pub channel_type:    u32,
+    pub channel_count:   u32,
+    /// Sampling frequency in Hz (2822400 for DSD64)
+    pub sample_rate:     u32,
+    /// 1 for LSB-first samples, 8 for MSB-first samples
+    pub bits_per_sample: u32,
+    /// Samples per channel
+    pub sample_count:    u64,
+    /// Bytes per channel in one block of the data chunk
+    pub block_size:      u32
+}
+
+impl FmtChunk
+{
+    /// Parse the fmt chunk at the start of `data` (including its header)
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12 || &data[0..4] != b"fmt "
+        {
+            return Err(tr("Missing DSF fmt chunk").to_string());
+        }
+        let size = read_u64_le(data, 4);
+        if size < FMT_CHUNK_SIZE || data.len() < FMT_CHUNK_SIZE as usize
+        {
+            return Err(tr_fmt("fmt chunk of {} bytes is too short", &[&size]));
+        }
+
+        Ok(FmtChunk {
+            size,
+            format_version: read_u32_le(data, 12),
+            format_id: read_u32_le(data, 16),
+            channel_type: read_u32_le(data, 20),
+            channel_count: read_u32_le(data, 24),
+            sample_rate: read_u32_le(data, 28),
+            bits_per_sample: read_u32_le(data, 32),
+            sample_count: read_u64_le(data, 36),
+            block_size: read_u32_le(data, 44)
+        })
+    }
+
+    /// Duration in seconds
+    pub fn duration(&self) -> Option<f64>
+    {
+        match self.sample_rate
+        {
+            | 0 => None,
+            | sample_rate => Some(self.sample_count as f64 / sample_rate as f64)
+        }
+    }
+
+    /// Size of the sample data in whole blocks, as the data chunk must hold it
+    pub fn expected_data_size(&self) -> u64
+    {
+        let bytes_per_channel = (self.sample_count * self.bits_per_sample.max(1) as u64).div_ceil(8);
+        let block_size = self.block_size.max(1) as u64;
+        bytes_per_channel.div_ceil(block_size) * block_size * self.channel_count as u64
+    }
+}
+
+/// Size of the data chunk from its header (including the header), None if `data` is no data chunk header
+pub fn data_chunk_size(data: &[u8]) -> Option<u64>
+{
+    match data.get(0..4)
+    {
+        | Some(b"data") if data.len() >= DATA_HEADER_SIZE as usize => Some(read_u64_le(data, 4)),
+        | _ => None
+    }
+}
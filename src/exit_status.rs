@@ -0,0 +1,61 @@
+use std::process::ExitCode;
+
+/// Process exit status of the CLI, ordered by severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExitStatus
+{
+    /// Dissection completed without findings
+    Success,
+    /// Dissection completed but warnings were reported
+    Warnings,
+    /// Validation found specification violations
+    Violations,
+    /// No dissector recognized the file format
+    UnknownFormat,
+    /// The file could not be parsed (malformed or truncated structures)
+    ParseFailure,
+    /// The file could not be opened or read
+    IoError,
+    /// The command line arguments are invalid
+    UsageError
+}
+
+impl ExitStatus
+{
+    /// Numeric process exit code
+    pub fn code(self) -> u8
+    {
+        match self
+        {
+            | ExitStatus::Success => 0,
+            | ExitStatus::Warnings => 1,
+            | ExitStatus::UnknownFormat => 2,
+            | ExitStatus::ParseFailure => 3,
+            | ExitStatus::IoError => 4,
+            | ExitStatus::Violations => 5,
+            | ExitStatus::UsageError => 6
+        }
+    }
+
+    /// Classify an error returned by a dissection run
+    ///
+    /// Reads past the end of the file come from structures whose declared size exceeds the data
+    /// (a truncated tag or box), so they count as parse failures like other malformed structures.
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self
+    {
+        match error.downcast_ref::<std::io::Error>()
+        {
+            | Some(io_error) if io_error.kind() == std::io::ErrorKind::UnexpectedEof => ExitStatus::ParseFailure,
+            | Some(_) => ExitStatus::IoError,
+            | None => ExitStatus::ParseFailure
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode
+{
+    fn from(status: ExitStatus) -> Self
+    {
+        ExitCode::from(status.code())
+    }
+}
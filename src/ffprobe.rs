@@ -0,0 +1,124 @@
+// ffprobe-compatible output
+//
+// Mirrors the subset of `ffprobe -show_format -show_streams -of json` that tooling
+// typically consumes. Numeric fields that ffprobe prints as strings (duration,
+// bit_rate, sample_rate, size) are emitted as strings as well.
+
+use std::path::Path;
+
+/// Stream information in ffprobe terms
+#[derive(Debug, Clone, Default)]
+pub struct ProbeStream
+{
+    /// ffmpeg codec name (e.g. "h264", "aac", "mp3"), None if there is no equivalent
+    pub codec_name:       Option<&'static str>,
+    pub codec_long_name:  Option<&'static str>,
+    /// "video", "audio", "subtitle" or "data"
+    pub codec_type:       &'static str,
+    /// Four-character code of the codec as stored in the file
+    pub codec_tag_string: Option<String>,
+    pub width:            Option<u32>,
+    pub height:           Option<u32>,
+    pub sample_rate:      Option<u32>,
+    pub channels:         Option<u16>,
+    /// Duration in seconds
+    pub duration:         Option<f64>,
+    /// Bit rate in bits per second
+    pub bit_rate:         Option<u64>
+}
+
+/// Container information in ffprobe terms for one dissected region
+#[derive(Debug, Clone)]
+pub struct ProbeFormat
+{
+    /// ffmpeg demuxer name (e.g. "mov,mp4,m4a,3gp,3g2,mj2", "mp3")
+    pub format_name:      &'static str,
+    pub format_long_name: &'static str,
+    /// Duration in seconds
+    pub duration:         Option<f64>,
+    pub streams:          Vec<ProbeStream>
+}
+
+/// Format a duration the way ffprobe does (seconds with six decimals)
+fn format_duration(seconds: f64) -> String
+{
+    format!("{:.6}", seconds)
+}
+
+/// Build the JSON object of one stream
+fn stream_to_json(index: usize, stream: &ProbeStream) -> serde_json::Value
+{
+    let mut object = serde_json::Map::new();
+    object.insert("index".to_string(), index.into());
+    if let Some(codec_name) = stream.codec_name
+    {
+        object.insert("codec_name".to_string(), codec_name.into());
+    }
+    if let Some(codec_long_name) = stream.codec_long_name
+    {
+        object.insert("codec_long_name".to_string(), codec_long_name.into());
+    }
+    object.insert("codec_type".to_string(), stream.codec_type.into());
+    if let Some(ref codec_tag_string) = stream.codec_tag_string
+    {
+        object.insert("codec_tag_string".to_string(), codec_tag_string.clone().into());
+    }
+    if let Some(width) = stream.width
+    {
+        object.insert("width".to_string(), width.into());
+    }
+    if let Some(height) = stream.height
+    {
+        object.insert("height".to_string(), height.into());
+    }
+    if let Some(sample_rate) = stream.sample_rate
+    {
+        object.insert("sample_rate".to_string(), sample_rate.to_string().into());
+    }
+    if let Some(channels) = stream.channels
+    {
+        object.insert("channels".to_string(), channels.into());
+    }
+    if let Some(duration) = stream.duration
+    {
+        object.insert("duration".to_string(), format_duration(duration).into());
+    }
+    if let Some(bit_rate) = stream.bit_rate
+    {
+        object.insert("bit_rate".to_string(), bit_rate.to_string().into());
+    }
+    serde_json::Value::Object(object)
+}
+
+/// Build the ffprobe-style document from the probes of all dissected regions
+///
+/// The container is described by the first region that carries streams (e.g. the MP4
+/// stream behind an ID3v2 tag); streams of all regions are numbered consecutively.
+pub fn document(file_path: &Path, file_size: u64, probes: &[ProbeFormat]) -> serde_json::Value
+{
+    let primary = probes.iter().find(|probe| probe.streams.is_empty() == false).or(probes.first());
+    let streams: Vec<&ProbeStream> = probes.iter().flat_map(|probe| probe.streams.iter()).collect();
+
+    let mut format = serde_json::Map::new();
+    format.insert("filename".to_string(), file_path.display().to_string().into());
+    format.insert("nb_streams".to_string(), streams.len().into());
+    format.insert("nb_programs".to_string(), 0.into());
+    format.insert("format_name".to_string(), primary.map_or("unknown", |probe| probe.format_name).into());
+    format.insert("format_long_name".to_string(), primary.map_or("unknown", |probe| probe.format_long_name).into());
+
+    let duration = primary.and_then(|probe| probe.duration).filter(|&duration| duration > 0.0);
+    if let Some(duration) = duration
+    {
+        format.insert("duration".to_string(), format_duration(duration).into());
+    }
+    format.insert("size".to_string(), file_size.to_string().into());
+    if let Some(duration) = duration
+    {
+        format.insert("bit_rate".to_string(), ((file_size as f64 * 8.0 / duration) as u64).to_string().into());
+    }
+
+    serde_json::json!({
+        "streams": streams.iter().enumerate().map(|(index, stream)| stream_to_json(index, stream)).collect::<Vec<_>>(),
+        "format": format
+    })
+}
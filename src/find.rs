@@ -0,0 +1,257 @@
+// Search for box types and frame IDs
+//
+// Backs the `find` command: occurrences of a four-character code are collected from the
+// parsed box and frame trees, and a byte-wise scan of the whole file adds the occurrences
+// the tree walk cannot reach (corrupt or truncated files, unknown containers, slack space).
+// Scan hits are interpreted as an ISOBMFF box header (size before the type) or, for codes
+// that look like ID3v2 frame IDs, as a frame header (size after the ID).
+
+use std::{fmt, io::SeekFrom, path::Path};
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::tools::decode_synchsafe_int,
+    media_source::MediaSource,
+    report::SCHEMA_VERSION,
+    summary::write_table
+};
+
+/// Number of bytes scanned per read
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Structure a code names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureKind
+{
+    /// ISOBMFF box: 32-bit size, then the type
+    Box,
+    /// ID3v2 frame: the ID, then the size
+    Frame
+}
+
+impl StructureKind
+{
+    /// Kind of structure a code names: frame IDs are upper-case letters and digits
+    pub fn of_code(code: &[u8; 4]) -> Self
+    {
+        if code[0].is_ascii_uppercase() == true && code.iter().all(|&byte| byte.is_ascii_uppercase() || byte.is_ascii_digit()) == true
+        {
+            StructureKind::Frame
+        }
+        else
+        {
+            StructureKind::Box
+        }
+    }
+
+    fn name(self) -> &'static str
+    {
+        match self
+        {
+            | StructureKind::Box => "box",
+            | StructureKind::Frame => "frame"
+        }
+    }
+}
+
+/// One occurrence of the searched code
+#[derive(Debug, Clone)]
+pub struct Occurrence
+{
+    /// Offset of the box or frame header
+    pub offset: u64,
+    /// Total size including the header, None if the size field is implausible
+    pub size:   Option<u64>,
+    pub kind:   StructureKind,
+    /// Location in the parsed structure (e.g. "moov/trak/mdia"), None for occurrences only found by the byte scan
+    pub path:   Option<String>
+}
+
+impl Occurrence
+{
+    /// Offset of the code itself
+    fn code_offset(&self) -> u64
+    {
+        match self.kind
+        {
+            | StructureKind::Box => self.offset + 4,
+            | StructureKind::Frame => self.offset
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "offset": self.offset,
+            "size": self.size,
+            "kind": self.kind.name(),
+            "path": self.path,
+            "parsed": self.path.is_some()
+        })
+    }
+}
+
+/// Result of the find command
+#[derive(Debug, Clone, Default)]
+pub struct Search
+{
+    /// Occurrences in file order
+    pub occurrences: Vec<Occurrence>,
+    /// Error that stopped the tree walk; the byte scan still covers the whole file
+    pub walk_error:  Option<String>
+}
+
+impl Search
+{
+    /// Combine the occurrences of the tree walk with those of the byte scan, dropping scan hits the walk already found
+    pub fn merge(parsed: Vec<Occurrence>, scanned: Vec<Occurrence>, walk_error: Option<String>) -> Self
+    {
+        let mut occurrences = parsed;
+        let known: Vec<u64> = occurrences.iter().map(Occurrence::code_offset).collect();
+        occurrences.extend(scanned.into_iter().filter(|occurrence| known.contains(&occurrence.code_offset()) == false));
+        occurrences.sort_by_key(|occurrence| occurrence.offset);
+
+        Self { occurrences, walk_error }
+    }
+
+    /// Number of occurrences only found by the byte scan
+    pub fn scan_only(&self) -> usize
+    {
+        self.occurrences.iter().filter(|occurrence| occurrence.path.is_none()).count()
+    }
+
+    /// JSON document of the find command (see `schema/find.schema.json`)
+    pub fn to_json(&self, file_path: &Path, code: &str) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "code": code,
+            "walk_error": self.walk_error,
+            "occurrences": self.occurrences.iter().map(Occurrence::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for Search
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut rows = vec![[tr("Offset"), tr("Size"), tr("Kind"), tr("Location")].map(str::to_string)];
+        for occurrence in &self.occurrences
+        {
+            rows.push([
+                format!("0x{:08X}", occurrence.offset),
+                occurrence.size.map(|size| size.to_string()).unwrap_or_else(|| "?".to_string()),
+                tr(occurrence.kind.name()).to_string(),
+                occurrence.path.clone().unwrap_or_else(|| tr("(byte scan only)").to_string())
+            ]);
+        }
+        write_table(f, &rows)
+    }
+}
+
+/// Convert a code given on the command line to its four bytes ('©' stands for the MacRoman byte 0xA9 of iTunes item types)
+pub fn parse_code(code: &str) -> Result<[u8; 4], String>
+{
+    let bytes: Vec<u8> = code
+        .chars()
+        .map(|character| match character
+        {
+            | '©' => Ok(0xA9),
+            | character if character.is_ascii() == true => Ok(character as u8),
+            | _ => Err(tr_fmt("Invalid code '{}': only ASCII characters and '©' are allowed", &[&code]))
+        })
+        .collect::<Result<_, _>>()?;
+
+    bytes.try_into().map_err(|_| tr_fmt("Invalid code '{}': expected four characters", &[&code]))
+}
+
+/// Scan all bytes of `file` for `code`, interpreting each hit as a box or frame header
+///
+/// Frame sizes are read as synchsafe integers if `synchsafe` is set (ID3v2.4), as plain
+/// 32-bit integers otherwise (ID3v2.3).
+pub fn scan(file: &mut dyn MediaSource, code: &[u8; 4], synchsafe: bool) -> Result<Vec<Occurrence>, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+
+    // Find the code offsets block by block; blocks overlap by three bytes so codes spanning a boundary are found
+    let mut positions = Vec::new();
+    let mut block = vec![0u8; BLOCK_SIZE];
+    let mut offset = 0;
+    while offset < file_size
+    {
+        let length = (file_size - offset).min(BLOCK_SIZE as u64) as usize;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut block[..length])?;
+
+        positions.extend(block[..length].windows(4).enumerate().filter(|(_, window)| *window == code).map(|(position, _)| offset + position as u64));
+        if offset + length as u64 >= file_size
+        {
+            break;
+        }
+        offset += length as u64 - 3;
+    }
+
+    let kind = StructureKind::of_code(code);
+    let mut occurrences = Vec::new();
+    for position in positions
+    {
+        let occurrence = match kind
+        {
+            | StructureKind::Box if position >= 4 => interpret_box(file, position - 4, file_size)?,
+            | StructureKind::Box => continue,
+            | StructureKind::Frame => interpret_frame(file, position, file_size, synchsafe)?
+        };
+        occurrences.push(occurrence);
+    }
+
+    Ok(occurrences)
+}
+
+/// Read the box header at `offset`, keeping the size only if the box fits into the file
+fn interpret_box(file: &mut dyn MediaSource, offset: u64, file_size: u64) -> Result<Occurrence, Box<dyn std::error::Error>>
+{
+    let mut header = [0u8; 16];
+    let available = (file_size - offset).min(16) as usize;
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut header[..available])?;
+
+    let size = match u32::from_be_bytes([header[0], header[1], header[2], header[3]])
+    {
+        | 0 => Some(file_size - offset),
+        | 1 if available == 16 =>
+            Some(u64::from_be_bytes([header[8], header[9], header[10], header[11], header[12], header[13], header[14], header[15]])).filter(|&size| size >= 16),
+        | 1 => None,
+        | size => Some(size as u64).filter(|&size| size >= 8)
+    };
+
+    Ok(Occurrence { offset, size: size.filter(|&size| offset + size <= file_size), kind: StructureKind::Box, path: None })
+}
+
+/// Read the frame header at `offset`, keeping the size only if the frame fits into the file
+fn interpret_frame(file: &mut dyn MediaSource, offset: u64, file_size: u64, synchsafe: bool) -> Result<Occurrence, Box<dyn std::error::Error>>
+{
+    let mut header = [0u8; 10];
+    let size = if offset + 10 <= file_size
+    {
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut header)?;
+
+        let data_size = if synchsafe == true
+        {
+            decode_synchsafe_int(&header[4..8])
+        }
+        else
+        {
+            u32::from_be_bytes([header[4], header[5], header[6], header[7]])
+        };
+        Some(10 + data_size as u64).filter(|&size| size > 10 && offset + size <= file_size)
+    }
+    else
+    {
+        None
+    };
+
+    Ok(Occurrence { offset, size, kind: StructureKind::Frame, path: None })
+}
@@ -0,0 +1,149 @@
+// Gapless playback information
+//
+// Backs `--gapless`: how many samples a player has to drop at the start and the end of a stream
+// so consecutive tracks play without a gap. MP3 encoders record their delay and padding in the
+// LAME extension of the Xing/Info header; the decoder adds another 529 samples of delay on top.
+// iTunes stores the same information for AAC (and MP3) as the `iTunSMPB` string, either in a
+// `----:com.apple.iTunes:iTunSMPB` item or an ID3v2 COMM frame, already including the decoder delay.
+
+use crate::mpeg_audio::MpegAudioInfo;
+
+/// Delay of an MP3 decoder (a 528-sample synthesis filter delay plus one sample), not included in the LAME delay
+pub const MP3_DECODER_DELAY: u32 = 529;
+
+/// Where the gapless information is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GaplessSource
+{
+    /// LAME extension of an MP3 Xing/Info header
+    Lame,
+    /// iTunes `iTunSMPB` string
+    ITunSmpb
+}
+
+impl GaplessSource
+{
+    /// Identifier used in JSON reports
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | GaplessSource::Lame => "lame",
+            | GaplessSource::ITunSmpb => "itunsmpb"
+        }
+    }
+}
+
+/// Encoder delay and padding of a stream
+#[derive(Debug, Clone)]
+pub struct Gapless
+{
+    pub source:        GaplessSource,
+    /// Where the values were read from: the Xing/Info header name or the metadata field, e.g. "com.apple.iTunes:iTunSMPB"
+    pub location:      String,
+    /// File offset of the Xing/Info header
+    pub offset:        Option<u64>,
+    /// Samples the encoder added in front of the audio
+    pub encoder_delay: u32,
+    /// Samples the encoder added behind the audio
+    pub padding:       u32,
+    /// Delay the decoder adds on top of the encoder delay
+    pub decoder_delay: u32,
+    /// Samples of the original audio, without delay and padding
+    pub samples:       Option<u64>,
+    pub sample_rate:   Option<u32>
+}
+
+impl Gapless
+{
+    /// Gapless information of the LAME extension of an MP3 stream, None without one
+    pub fn from_lame(info: &MpegAudioInfo) -> Option<Self>
+    {
+        let vbr = info.vbr_header.as_ref()?;
+        let (delay, padding) = vbr.gapless?;
+        let (encoder_delay, padding) = (delay as u32, padding as u32);
+
+        // The frame count excludes the frame holding the header, which decodes to silence and is skipped by players
+        let samples =
+            vbr.frames.map(|frames| frames as u64 * info.header.samples_per_frame() as u64).map(|total| total.saturating_sub((encoder_delay + padding) as u64));
+        Some(Self {
+            source: GaplessSource::Lame,
+            location: vbr.name().to_string(),
+            offset: Some(vbr.offset),
+            encoder_delay,
+            padding,
+            decoder_delay: MP3_DECODER_DELAY,
+            samples,
+            sample_rate: Some(info.header.sample_rate)
+        })
+    }
+
+    /// Gapless information of an `iTunSMPB` string, None for other fields or unreadable values
+    ///
+    /// `field` is the key of the field in the custom metadata: "COMM:<description>" or "<mean>:<name>". The string
+    /// holds hexadecimal fields, the second to fourth are encoder delay, padding and the original sample count.
+    pub fn from_itunsmpb(field: &str, value: &str, sample_rate: Option<u32>) -> Option<Self>
+    {
+        if field.rsplit_once(':').is_some_and(|(_, name)| name.eq_ignore_ascii_case("iTunSMPB")) == false
+        {
+            return None;
+        }
+
+        let fields: Vec<u64> = value.split_whitespace().map(|field| u64::from_str_radix(field, 16).ok()).collect::<Option<_>>()?;
+        let (encoder_delay, padding, samples) = match fields[..]
+        {
+            | [_, delay, padding, samples, ..] => (u32::try_from(delay).ok()?, u32::try_from(padding).ok()?, samples),
+            | _ => return None
+        };
+        Some(Self {
+            source: GaplessSource::ITunSmpb,
+            location: field.to_string(),
+            offset: None,
+            encoder_delay,
+            padding,
+            decoder_delay: 0,
+            samples: Some(samples),
+            sample_rate
+        })
+    }
+
+    /// Samples a player drops at the start
+    pub fn trim_start(&self) -> u32
+    {
+        self.encoder_delay + self.decoder_delay
+    }
+
+    /// Samples a player drops at the end (the decoder delay shifts part of the padding into the stream)
+    pub fn trim_end(&self) -> u32
+    {
+        self.padding.saturating_sub(self.decoder_delay)
+    }
+
+    /// Playing time of the original audio in seconds
+    pub fn duration(&self) -> Option<f64>
+    {
+        match (self.samples, self.sample_rate)
+        {
+            | (Some(samples), Some(sample_rate)) if sample_rate > 0 => Some(samples as f64 / sample_rate as f64),
+            | _ => None
+        }
+    }
+
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "source": self.source.id(),
+            "location": self.location,
+            "offset": self.offset,
+            "encoder_delay": self.encoder_delay,
+            "padding": self.padding,
+            "decoder_delay": self.decoder_delay,
+            "trim_start": self.trim_start(),
+            "trim_end": self.trim_end(),
+            "samples": self.samples,
+            "sample_rate": self.sample_rate,
+            "duration": self.duration()
+        })
+    }
+}
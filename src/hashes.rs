@@ -0,0 +1,297 @@
+// Region hashes
+//
+// Backs `--hashes`: MD5 and SHA-256 digests of the logical regions of a file (the tags, the audio
+// stream without them, the payload of each `mdat` box), so it can be verified that a retagging
+// tool left the audio data untouched. Both digests are implemented here (RFC 1321, FIPS 180-4)
+// to keep the dependencies minimal, like the DEFLATE decoder.
+
+use std::io::SeekFrom;
+
+use crate::{layout::Layout, media_source::MediaSource};
+
+/// Bytes read from the file per step while hashing
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Per-round shift amounts of MD5
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    4, 11, 16, 23, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21
+];
+
+/// Round constants of MD5 (integer parts of |sin(i + 1)| * 2^32)
+const MD5_CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122,
+    0xfd987193, 0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6,
+    0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60,
+    0xbebfbc70, 0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1, 0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391
+];
+
+/// Round constants of SHA-256 (fractional parts of the cube roots of the first 64 primes)
+const SHA256_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74,
+    0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d,
+    0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e,
+    0x92722c85, 0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5,
+    0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2
+];
+
+/// Block-buffering shared by both digests: both process 64-byte blocks and pad the message the same way
+struct BlockBuffer
+{
+    block:  [u8; 64],
+    filled: usize,
+    length: u64
+}
+
+impl BlockBuffer
+{
+    fn new() -> Self
+    {
+        Self { block: [0; 64], filled: 0, length: 0 }
+    }
+
+    /// Feed `data`, calling `compress` for every complete block
+    fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; 64]))
+    {
+        self.length += data.len() as u64;
+        while data.is_empty() == false
+        {
+            let count = (64 - self.filled).min(data.len());
+            self.block[self.filled..self.filled + count].copy_from_slice(&data[..count]);
+            self.filled += count;
+            data = &data[count..];
+            if self.filled == 64
+            {
+                compress(&self.block);
+                self.filled = 0;
+            }
+        }
+    }
+
+    /// Append the 0x80 marker, zero bytes and the message length in bits (`length_bytes` encodes it)
+    fn finish(&mut self, length_bytes: [u8; 8], mut compress: impl FnMut(&[u8; 64]))
+    {
+        let length = self.length;
+        let padding = match self.filled < 56
+        {
+            | true => 56 - self.filled,
+            | false => 120 - self.filled
+        };
+        let mut tail = vec![0u8; padding];
+        tail[0] = 0x80;
+        tail.extend(length_bytes);
+        self.update(&tail, &mut compress);
+        self.length = length;
+    }
+}
+
+/// MD5 digest (RFC 1321)
+pub struct Md5
+{
+    state:  [u32; 4],
+    buffer: BlockBuffer
+}
+
+impl Default for Md5
+{
+    fn default() -> Self
+    {
+        Self { state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476], buffer: BlockBuffer::new() }
+    }
+}
+
+impl Md5
+{
+    pub fn update(&mut self, data: &[u8])
+    {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| md5_compress(state, block));
+    }
+
+    pub fn finalize(mut self) -> [u8; 16]
+    {
+        let bits = self.buffer.length.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.finish(bits.to_le_bytes(), |block| md5_compress(state, block));
+
+        let mut digest = [0u8; 16];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state)
+        {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+fn md5_compress(state: &mut [u32; 4], block: &[u8; 64])
+{
+    let words: Vec<u32> = block.chunks_exact(4).map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect();
+    let [mut a, mut b, mut c, mut d] = *state;
+    for round in 0..64
+    {
+        let (f, index) = match round / 16
+        {
+            | 0 => ((b & c) | (!b & d), round),
+            | 1 => ((d & b) | (!d & c), (5 * round + 1) % 16),
+            | 2 => (b ^ c ^ d, (3 * round + 5) % 16),
+            | _ => (c ^ (b | !d), (7 * round) % 16)
+        };
+        let rotated = a.wrapping_add(f).wrapping_add(MD5_CONSTANTS[round]).wrapping_add(words[index]).rotate_left(MD5_SHIFTS[round]);
+        (a, d, c) = (d, c, b);
+        b = b.wrapping_add(rotated);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d])
+    {
+        *word = word.wrapping_add(value);
+    }
+}
+
+/// SHA-256 digest (FIPS 180-4)
+pub struct Sha256
+{
+    state:  [u32; 8],
+    buffer: BlockBuffer
+}
+
+impl Default for Sha256
+{
+    fn default() -> Self
+    {
+        Self { state: [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19], buffer: BlockBuffer::new() }
+    }
+}
+
+impl Sha256
+{
+    pub fn update(&mut self, data: &[u8])
+    {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| sha256_compress(state, block));
+    }
+
+    pub fn finalize(mut self) -> [u8; 32]
+    {
+        let bits = self.buffer.length.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.finish(bits.to_be_bytes(), |block| sha256_compress(state, block));
+
+        let mut digest = [0u8; 32];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state)
+        {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+fn sha256_compress(state: &mut [u32; 8], block: &[u8; 64])
+{
+    let mut schedule = [0u32; 64];
+    for (word, chunk) in schedule.iter_mut().zip(block.chunks_exact(4))
+    {
+        *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for index in 16..64
+    {
+        let s0 = schedule[index - 15].rotate_right(7) ^ schedule[index - 15].rotate_right(18) ^ (schedule[index - 15] >> 3);
+        let s1 = schedule[index - 2].rotate_right(17) ^ schedule[index - 2].rotate_right(19) ^ (schedule[index - 2] >> 10);
+        schedule[index] = schedule[index - 16].wrapping_add(s0).wrapping_add(schedule[index - 7]).wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (constant, word) in SHA256_CONSTANTS.iter().zip(schedule)
+    {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let temp1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(*constant).wrapping_add(word);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(majority);
+        (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(temp1), c, b, a, temp1.wrapping_add(temp2));
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h])
+    {
+        *word = word.wrapping_add(value);
+    }
+}
+
+/// Lowercase hexadecimal form of a digest
+pub fn to_hex(digest: &[u8]) -> String
+{
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Digests of one region of a file
+#[derive(Debug, Clone)]
+pub struct RegionHash
+{
+    pub offset: u64,
+    pub size:   u64,
+    /// Layout name of the region (e.g. "ID3v2.3", "MPEG audio", "mdat")
+    pub name:   String,
+    pub md5:    String,
+    pub sha256: String
+}
+
+impl RegionHash
+{
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "offset": self.offset,
+            "size": self.size,
+            "name": self.name,
+            "md5": self.md5,
+            "sha256": self.sha256
+        })
+    }
+}
+
+/// Hash `size` bytes of `file` starting at `offset`
+pub fn hash_range(file: &mut dyn MediaSource, offset: u64, size: u64) -> Result<(String, String), Box<dyn std::error::Error>>
+{
+    let mut md5 = Md5::default();
+    let mut sha256 = Sha256::default();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut remaining = size;
+    file.seek(SeekFrom::Start(offset))?;
+    while remaining > 0
+    {
+        let count = (remaining as usize).min(CHUNK_SIZE);
+        file.read_exact(&mut buffer[..count])?;
+        md5.update(&buffer[..count]);
+        sha256.update(&buffer[..count]);
+        remaining -= count as u64;
+    }
+    Ok((to_hex(&md5.finalize()), to_hex(&sha256.finalize())))
+}
+
+/// Hash the top-level regions of `layout`; `mdat` boxes are hashed without their header, so only the media data counts
+pub fn hash_regions(file: &mut dyn MediaSource, layout: &Layout) -> Result<Vec<RegionHash>, Box<dyn std::error::Error>>
+{
+    let mut hashes = Vec::new();
+    for region in layout.regions.iter().filter(|region| region.depth == 0)
+    {
+        let (mut offset, mut size) = (region.offset, region.size);
+        if region.name == "mdat" && size >= 8
+        {
+            let mut header = [0u8; 4];
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut header)?;
+            let header_size = match u32::from_be_bytes(header)
+            {
+                | 1 => 16,
+                | _ => 8
+            }
+            .min(size);
+            offset += header_size;
+            size -= header_size;
+        }
+
+        let (md5, sha256) = hash_range(file, offset, size)?;
+        hashes.push(RegionHash { offset, size, name: region.name.clone(), md5, sha256 });
+    }
+    Ok(hashes)
+}
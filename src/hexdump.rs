@@ -1,3 +1,90 @@
+use owo_colors::Style;
+
+use crate::{
+    color::Colorize,
+    i18n::{tr, tr_fmt}
+};
+
+/// Labeled field of dumped data, highlighted in the hexdump and listed in its legend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexSpan
+{
+    /// First byte of the field (relative to the dumped data)
+    pub start: usize,
+    /// Byte after the last byte of the field
+    pub end:   usize,
+    /// Field name, translated when displayed
+    pub label: &'static str
+}
+
+/// Collects the spans of consecutive fields while walking through the dumped data
+pub struct SpanBuilder<'a>
+{
+    data:     &'a [u8],
+    position: usize,
+    spans:    Vec<HexSpan>
+}
+
+impl<'a> SpanBuilder<'a>
+{
+    pub fn new(data: &'a [u8]) -> Self
+    {
+        Self { data, position: 0, spans: Vec::new() }
+    }
+
+    /// Byte at the current position
+    pub fn peek(&self) -> Option<u8>
+    {
+        self.data.get(self.position).copied()
+    }
+
+    /// Field of `length` bytes, cut at the end of the data
+    pub fn field(&mut self, length: usize, label: &'static str)
+    {
+        let end = self.position.saturating_add(length).min(self.data.len());
+        if end > self.position
+        {
+            self.spans.push(HexSpan { start: self.position, end, label });
+            self.position = end;
+        }
+    }
+
+    /// String field followed by a null terminator (two null bytes if `wide`), labeled separately
+    pub fn terminated(&mut self, label: &'static str, wide: bool)
+    {
+        let width = if wide == true
+        {
+            2
+        }
+        else
+        {
+            1
+        };
+        let remaining = &self.data[self.position.min(self.data.len())..];
+        let terminator = remaining.chunks(width).position(|unit| unit.len() == width && unit.iter().all(|byte| *byte == 0));
+        match terminator
+        {
+            | Some(index) =>
+            {
+                self.field(index * width, label);
+                self.field(width, "Terminator");
+            }
+            | None => self.rest(label)
+        }
+    }
+
+    /// Field covering all remaining bytes
+    pub fn rest(&mut self, label: &'static str)
+    {
+        self.field(self.data.len().saturating_sub(self.position), label);
+    }
+
+    pub fn finish(self) -> Vec<HexSpan>
+    {
+        self.spans
+    }
+}
+
 /// Format data as a hexdump
 pub fn format_hexdump(data: &[u8], base_offset: usize) -> String
 {
@@ -5,8 +92,14 @@ pub fn format_hexdump(data: &[u8], base_offset: usize) -> String
 }
 
 /// Format data as a hexdump with optional byte limit
-/// If max_bytes is Some(n), only format first n bytes and append a truncation notice
+/// If max_bytes is Some(n), only format first n bytes and append a notice on the bytes left out
 pub fn format_hexdump_limited(data: &[u8], base_offset: usize, max_bytes: Option<usize>) -> String
+{
+    format_hexdump_annotated(data, base_offset, max_bytes, &[])
+}
+
+/// Format data as a hexdump with the bytes of each span colored and a legend of the spans below
+pub fn format_hexdump_annotated(data: &[u8], base_offset: usize, max_bytes: Option<usize>, spans: &[HexSpan]) -> String
 {
     let mut output = String::new();
 
@@ -30,7 +123,8 @@ pub fn format_hexdump_limited(data: &[u8], base_offset: usize, max_bytes: Option
             {
                 output.push(' ');
             }
-            output.push_str(&format!("{:02X} ", byte));
+            output.push_str(&highlight(format!("{:02X}", byte), spans, i * 16 + j));
+            output.push(' ');
         }
 
         // Pad if less than 16 bytes
@@ -49,16 +143,17 @@ pub fn format_hexdump_limited(data: &[u8], base_offset: usize, max_bytes: Option
 
         // ASCII representation
         output.push_str(" |");
-        for byte in chunk
+        for (j, byte) in chunk.iter().enumerate()
         {
-            if *byte >= 0x20 && *byte <= 0x7E
+            let character = if *byte >= 0x20 && *byte <= 0x7E
             {
-                output.push(*byte as char);
+                *byte as char
             }
             else
             {
-                output.push('.');
-            }
+                '.'
+            };
+            output.push_str(&highlight(character.to_string(), spans, i * 16 + j));
         }
         // Pad ASCII section to 16 characters if less than 16 bytes
         for _ in chunk.len()..16
@@ -71,8 +166,55 @@ pub fn format_hexdump_limited(data: &[u8], base_offset: usize, max_bytes: Option
 
     if is_truncated
     {
-        output.push_str("<truncated>\n");
+        output.push_str(&tr_fmt("... {} more bytes (--dump-limit full shows all)", &[&(data.len() - data_to_dump.len())]));
+        output.push('\n');
+    }
+
+    // Legend, also listing fields beyond the dumped bytes
+    if spans.is_empty() == false
+    {
+        output.push_str(tr("Fields:"));
+        output.push('\n');
+        for (index, span) in spans.iter().enumerate()
+        {
+            let label = tr(span.label).styled(palette(index));
+            output.push_str(&format!("  {:08X}-{:08X}  {} ({})\n", base_offset + span.start, base_offset + span.end - 1, label, byte_count(span.end - span.start)));
+        }
     }
 
     output
 }
+
+/// Color of the n-th span, repeating after six spans
+fn palette(index: usize) -> Style
+{
+    match index % 6
+    {
+        | 0 => Style::new().cyan(),
+        | 1 => Style::new().yellow(),
+        | 2 => Style::new().green(),
+        | 3 => Style::new().magenta(),
+        | 4 => Style::new().bright_blue(),
+        | _ => Style::new().bright_red()
+    }
+}
+
+/// Byte text colored like the span containing `position`, unchanged outside of all spans
+fn highlight(text: String, spans: &[HexSpan], position: usize) -> String
+{
+    match spans.iter().position(|span| span.start <= position && position < span.end)
+    {
+        | Some(index) => text.styled(palette(index)).to_string(),
+        | None => text
+    }
+}
+
+/// "1 byte" or "n bytes"
+fn byte_count(count: usize) -> String
+{
+    match count
+    {
+        | 1 => tr("1 byte").to_string(),
+        | count => tr_fmt("{} bytes", &[&count])
+    }
+}
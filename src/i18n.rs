@@ -0,0 +1,85 @@
+// Localization of user-facing text
+//
+// Messages are looked up by their English text (gettext style), so untranslated
+// strings fall back to English automatically. Templates use `{}` placeholders
+// that are filled in order by `tr_fmt`.
+
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use clap::ValueEnum;
+
+// Translation catalogs
+pub mod de;
+
+/// Language of the text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Language
+{
+    /// English (default)
+    #[value(name = "en")]
+    English,
+    /// German
+    #[value(name = "de")]
+    German
+}
+
+impl Language
+{
+    /// Derive the language from the usual locale environment variables (LC_ALL, LC_MESSAGES, LANG)
+    pub fn from_environment() -> Self
+    {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter().filter_map(|name| std::env::var(name).ok()).find(|value| value.is_empty() == false);
+
+        match locale
+        {
+            | Some(value) if value.starts_with("de") == true => Language::German,
+            | _ => Language::English
+        }
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Select the output language for the whole process (only the first call has an effect)
+pub fn set_language(language: Language)
+{
+    let _ = LANGUAGE.set(language);
+}
+
+/// Translation table for the selected language (None for English)
+fn catalog() -> Option<&'static HashMap<&'static str, &'static str>>
+{
+    static GERMAN: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+    match LANGUAGE.get().copied().unwrap_or(Language::English)
+    {
+        | Language::English => None,
+        | Language::German => Some(GERMAN.get_or_init(|| de::CATALOG.iter().copied().collect()))
+    }
+}
+
+/// Translate a message, falling back to the English text
+pub fn tr(text: &'static str) -> &'static str
+{
+    catalog().and_then(|catalog| catalog.get(text).copied()).unwrap_or(text)
+}
+
+/// Translate a template and fill its `{}` placeholders in order
+pub fn tr_fmt(template: &'static str, args: &[&dyn fmt::Display]) -> String
+{
+    let mut output = String::new();
+    let mut args = args.iter();
+
+    let mut parts = tr(template).split("{}").peekable();
+    while let Some(part) = parts.next()
+    {
+        output.push_str(part);
+        if parts.peek().is_some() == true &&
+            let Some(arg) = args.next()
+        {
+            output.push_str(&arg.to_string());
+        }
+    }
+
+    output
+}
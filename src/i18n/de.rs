@@ -0,0 +1,2055 @@
+/// German catalog: English message -> German translation
+pub const CATALOG: &[(&str, &str)] = &[
+    // General output
+    ("Error:", "Fehler:"),
+    ("Analyzing file: {}", "Analysiere Datei: {}"),
+    ("Detected format: {} ({})", "Erkanntes Format: {} ({})"),
+    ("Detected format at offset {}: {} ({})", "Erkanntes Format an Offset {}: {} ({})"),
+    ("Diagnostics:", "Diagnose:"),
+    ("INFO", "HINWEIS"),
+    ("WARNING", "WARNUNG"),
+    ("ERROR", "FEHLER"),
+    ("{} at {}: {}", "{} an {}: {}"),
+    ("Raw data:", "Rohdaten:"),
+    ("Specification: {}", "Spezifikation: {}"),
+    ("Size: {} bytes", "Größe: {} Bytes"),
+    // Batch output
+    ("Analyzing directory: {} ({} files)", "Analysiere Verzeichnis: {} ({} Dateien)"),
+    ("Analyzing {} inputs ({} files)", "Analysiere {} Eingaben ({} Dateien)"),
+    ("{} warnings", "{} Warnungen"),
+    ("Summary:", "Zusammenfassung:"),
+    ("Files dissected: {}", "Untersuchte Dateien: {}"),
+    ("Formats:", "Formate:"),
+    ("Files with warnings: {}", "Dateien mit Warnungen: {}"),
+    ("Errors: {}", "Fehler: {}"),
+    ("ffprobe output is only available for single files", "Die ffprobe-Ausgabe ist nur für einzelne Dateien verfügbar"),
+    // ID3v1 output
+    ("{} Tag Found at offset {}:", "{}-Tag gefunden an Offset {}:"),
+    ("Title: {}", "Titel: {}"),
+    ("Artist: {}", "Interpret: {}"),
+    ("Album: {}", "Album: {}"),
+    ("Year: {}", "Jahr: {}"),
+    ("Comment: {}", "Kommentar: {}"),
+    ("Track: {}", "Titelnummer: {}"),
+    ("Genre: {} ({})", "Genre: {} ({})"),
+    ("unset", "nicht gesetzt"),
+    // Gap reporting
+    ("Region Hashes:", "Prüfsummen der Bereiche:"),
+    ("mdat payload", "mdat-Nutzdaten"),
+    ("{} at offset {} ({} bytes)", "{} an Offset {} ({} Bytes)"),
+    ("Uncovered Byte Ranges:", "Nicht abgedeckte Bytebereiche:"),
+    ("{} - {} ({} bytes)", "{} - {} ({} Bytes)"),
+    ("Total: {} bytes in {} ranges", "Gesamt: {} Bytes in {} Bereichen"),
+    // Music identifiers
+    ("Music Identifiers:", "Musik-Kennungen:"),
+    ("from {}", "aus {}"),
+    ("Chromaprint algorithm {}, {} values ({} s of audio)", "Chromaprint-Algorithmus {}, {} Werte ({} s Audio)"),
+    ("MusicBrainz recording ID", "MusicBrainz-Aufnahme-ID"),
+    ("MusicBrainz track ID", "MusicBrainz-Titel-ID"),
+    ("MusicBrainz release ID", "MusicBrainz-Veröffentlichungs-ID"),
+    ("MusicBrainz release group ID", "MusicBrainz-Veröffentlichungsgruppen-ID"),
+    ("MusicBrainz artist ID", "MusicBrainz-Künstler-ID"),
+    ("MusicBrainz release artist ID", "MusicBrainz-Veröffentlichungskünstler-ID"),
+    ("MusicBrainz work ID", "MusicBrainz-Werk-ID"),
+    ("MusicBrainz disc ID", "MusicBrainz-Disc-ID"),
+    ("MusicBrainz original release ID", "MusicBrainz-Originalveröffentlichungs-ID"),
+    ("MusicBrainz original artist ID", "MusicBrainz-Originalkünstler-ID"),
+    ("MusicBrainz TRM ID", "MusicBrainz-TRM-ID"),
+    ("MusicIP PUID", "MusicIP-PUID"),
+    ("AcoustID", "AcoustID"),
+    ("AcoustID fingerprint", "AcoustID-Fingerabdruck"),
+    // Loudness
+    ("Loudness:", "Lautheit:"),
+    ("peak {}", "Spitze {}"),
+    ("invalid value", "ungültiger Wert"),
+    ("ReplayGain track gain", "ReplayGain-Titelverstärkung"),
+    ("ReplayGain track peak", "ReplayGain-Titelspitze"),
+    ("ReplayGain album gain", "ReplayGain-Albumverstärkung"),
+    ("ReplayGain album peak", "ReplayGain-Albumspitze"),
+    ("ReplayGain reference loudness", "ReplayGain-Referenzlautheit"),
+    ("Sound Check", "Sound Check"),
+    ("Other", "Sonstige"),
+    ("Master volume", "Gesamtlautstärke"),
+    ("Front right", "Vorne rechts"),
+    ("Front left", "Vorne links"),
+    ("Back right", "Hinten rechts"),
+    ("Back left", "Hinten links"),
+    ("Front centre", "Vorne Mitte"),
+    ("Back centre", "Hinten Mitte"),
+    ("Subwoofer", "Subwoofer"),
+    // Gapless playback
+    ("Gapless Playback:", "Lückenlose Wiedergabe:"),
+    ("Encoder delay: {} samples, padding: {} samples", "Encoder-Verzögerung: {} Samples, Auffüllung: {} Samples"),
+    ("Decoder delay: {} samples", "Decoder-Verzögerung: {} Samples"),
+    ("Trim: {} samples at the start, {} samples at the end", "Abschneiden: {} Samples am Anfang, {} Samples am Ende"),
+    ("Playable samples: {} ({} s)", "Wiedergegebene Samples: {} ({} s)"),
+    ("Playable samples: {}", "Wiedergegebene Samples: {}"),
+    // Podcast
+    ("Podcast:", "Podcast:"),
+    ("Feed URL", "Feed-URL"),
+    ("Episode ID", "Episoden-ID"),
+    ("Long description", "Lange Beschreibung"),
+    ("Chapters ({}):", "Kapitel ({}):"),
+    ("URL: {}", "URL: {}"),
+    ("Image: {}, {} bytes", "Bild: {}, {} Bytes"),
+    // Carving
+    ("Carving file: {}", "Durchsuche Datei: {}"),
+    ("No embedded media structures found", "Keine eingebetteten Medienstrukturen gefunden"),
+    ("Candidates ({}):", "Kandidaten ({}):"),
+    ("{} bytes", "{} Bytes"),
+    ("Extracted files:", "Extrahierte Dateien:"),
+    ("ID3v2.{} tag, truncated", "ID3v2.{}-Tag, abgeschnitten"),
+    ("ID3v2.{} tag, {} MPEG frames, {} Hz", "ID3v2.{}-Tag, {} MPEG-Frames, {} Hz"),
+    ("ID3v2.{} tag without following audio", "ID3v2.{}-Tag ohne nachfolgendes Audio"),
+    ("brand '{}', truncated", "Marke '{}', abgeschnitten"),
+    ("brand '{}'", "Marke '{}'"),
+    ("{} Hz, {} channels", "{} Hz, {} Kanäle"),
+    ("{} MPEG frames, {} Hz", "{} MPEG-Frames, {} Hz"),
+    // Chapter export
+    ("No chapters found in {}", "Keine Kapitel gefunden in {}"),
+    // Artwork extraction
+    ("Extracted Artwork:", "Extrahierte Bilder:"),
+    ("Extracted Chapter Artwork:", "Extrahierte Kapitelbilder:"),
+    ("{} ({}, {}, {} bytes)", "{} ({}, {}, {} Bytes)"),
+    ("Artwork extraction is only available for single files", "Bildextraktion ist nur für einzelne Dateien verfügbar"),
+    // Embedded payloads
+    ("Embedded Payloads:", "Eingebettete Nutzdaten:"),
+    ("{} ({} bytes): {} ({})", "{} ({} Bytes): {} ({})"),
+    ("Payload not dissected: {}", "Nutzdaten nicht zerlegt: {}"),
+    // Outline
+    ("The outline is only available with --format text", "Die Gliederung ist nur mit --format text verfügbar"),
+    ("The outline is only available for single files", "Die Gliederung ist nur für einzelne Dateien verfügbar"),
+    ("{} ({}) at {}", "{} ({}) an {}"),
+    ("{} at {} ({} bytes)", "{} an {} ({} Bytes)"),
+    // Normalized metadata
+    ("Normalized metadata is only available with --format json", "Normalisierte Metadaten sind nur mit --format json verfügbar"),
+    ("Normalized metadata is only available for single files", "Normalisierte Metadaten sind nur für einzelne Dateien verfügbar"),
+    // Byte regions
+    ("Region: {} - {} ({} bytes, offsets below are relative to the region)", "Bereich: {} - {} ({} Bytes, folgende Offsets beziehen sich auf den Bereichsanfang)"),
+    ("Offset {} is beyond the end of the input ({} bytes)", "Offset {} liegt hinter dem Ende der Eingabe ({} Bytes)"),
+    ("Region of {} bytes at offset {} exceeds the input ({} bytes)", "Bereich von {} Bytes ab Offset {} überschreitet die Eingabe ({} Bytes)"),
+    ("--offset and --length are only available for single files", "--offset und --length sind nur für einzelne Dateien verfügbar"),
+    // Media segments
+    ("--init is only available for single files", "--init ist nur für einzelne Dateien verfügbar"),
+    ("Initialization segment: {}", "Initialisierungssegment: {}"),
+    ("No moov box found in the initialization segment {}", "Keine moov-Box im Initialisierungssegment {} gefunden"),
+    (
+        "Media segment without movie box: timescales and trex sample defaults are unknown (pass the initialization segment with --init)",
+        "Mediensegment ohne Movie-Box: Zeitskalen und trex-Standardwerte der Samples sind unbekannt (Initialisierungssegment mit --init angeben)"
+    ),
+    ("Track fragment of track {} has no matching track in the movie box", "Das Spurfragment von Spur {} hat keine passende Spur in der Movie-Box"),
+    // Path filter
+    ("Invalid path expression '{}': empty path element", "Ungültiger Pfadausdruck '{}': leeres Pfadelement"),
+    // Validation
+    ("Validating file: {}", "Validiere Datei: {}"),
+    ("Findings:", "Befunde:"),
+    ("{} errors, {} warnings, {} notes", "{} Fehler, {} Warnungen, {} Hinweise"),
+    ("Tag size is not a valid synchsafe integer", "Die Tag-Größe ist keine gültige synchsichere Ganzzahl"),
+    ("Declared tag size ({} bytes) exceeds the end of the file", "Die angegebene Tag-Größe ({} Bytes) reicht über das Dateiende hinaus"),
+    ("Undefined tag header flags are set: {}", "Undefinierte Tag-Header-Flags sind gesetzt: {}"),
+    ("Extended header size is not a valid synchsafe integer", "Die Größe des erweiterten Headers ist keine gültige synchsichere Ganzzahl"),
+    ("Frame {} size is not a valid synchsafe integer", "Die Größe von Frame {} ist keine gültige synchsichere Ganzzahl"),
+    ("Frame {} uses text encoding {}, which is not valid for ID3v2.{}", "Frame {} verwendet die Textkodierung {}, die für ID3v2.{} nicht gültig ist"),
+    ("Frame {} has an invalid text encoding byte {}", "Frame {} hat ein ungültiges Textkodierungs-Byte {}"),
+    ("Frame {} appears more than once, but must be unique", "Frame {} kommt mehrfach vor, muss aber eindeutig sein"),
+    ("Chapter \"{}\" ends before it starts", "Kapitel \"{}\" endet vor seinem Beginn"),
+    ("Chapter \"{}\" ends where it starts", "Kapitel \"{}\" endet an seinem Beginn"),
+    (
+        "Chapter \"{}\" starts before the preceding chapter \"{}\" of ordered CTOC \"{}\"",
+        "Kapitel \"{}\" beginnt vor dem vorhergehenden Kapitel \"{}\" des geordneten CTOC \"{}\""
+    ),
+    ("Chapter \"{}\" overlaps the preceding chapter \"{}\" by {} ms", "Kapitel \"{}\" überlappt das vorhergehende Kapitel \"{}\" um {} ms"),
+    ("Gap of {} ms between chapter \"{}\" and the preceding chapter \"{}\"", "Lücke von {} ms zwischen Kapitel \"{}\" und dem vorhergehenden Kapitel \"{}\""),
+    ("Element ID \"{}\" is used by more than one CHAP/CTOC frame", "Element-ID \"{}\" wird von mehr als einem CHAP/CTOC-Frame verwendet"),
+    ("Tag has {} top-level CTOC frames, expected exactly one", "Das Tag hat {} CTOC-Frames der obersten Ebene, erwartet wird genau einer"),
+    ("CTOC references element ID \"{}\", but no CHAP/CTOC frame has it", "CTOC verweist auf Element-ID \"{}\", aber kein CHAP/CTOC-Frame hat sie"),
+    ("Chapter \"{}\" is not referenced by any CTOC frame", "Kapitel \"{}\" wird von keinem CTOC-Frame referenziert"),
+    ("Extended header declares {} bytes of padding, but the tag has {}", "Der erweiterte Header gibt {} Bytes Auffüllung an, das Tag hat aber {}"),
+    ("Tag has both a footer and padding, which ID3v2.4 does not allow", "Das Tag hat sowohl einen Footer als auch Auffüllung, was ID3v2.4 nicht erlaubt"),
+    ("Frame {} follows the tag, the declared tag size is too small", "Frame {} folgt auf das Tag, die angegebene Tag-Größe ist zu klein"),
+    ("No {} frame ({}) in the tag", "Kein {}-Frame ({}) im Tag"),
+    ("No ftyp box (file type compatibility is unknown)", "Keine ftyp-Box (Dateityp-Kompatibilität ist unbekannt)"),
+    ("ftyp box is not the first significant box", "Die ftyp-Box ist nicht die erste maßgebliche Box"),
+    ("Unknown major brand '{}' (dissected as generic ISOBMFF)", "Unbekannte Hauptmarke '{}' (als generisches ISOBMFF zerlegt)"),
+    ("{} {} boxes at top level, at most one is allowed", "{} {}-Boxen auf oberster Ebene, höchstens eine ist erlaubt"),
+    ("No moov box (movie header and track setup are missing)", "Keine moov-Box (Film-Header und Spurkonfiguration fehlen)"),
+    ("moov box has no tracks", "Die moov-Box hat keine Spuren"),
+    ("{} box is missing its mandatory {} box", "Der {}-Box fehlt die vorgeschriebene {}-Box"),
+    ("Track with handler '{}' has no {} media header box", "Spur mit Handler '{}' hat keine {}-Medien-Header-Box"),
+    ("sidx reference {} points to offset {}, where no top-level box starts", "sidx-Referenz {} verweist auf Offset {}, an dem keine Box der obersten Ebene beginnt"),
+    (
+        "sidx reference {} declares {} bytes, but the subsegment at offset {} spans {} bytes (up to the end of its first mdat box)",
+        "sidx-Referenz {} gibt {} Bytes an, aber das Subsegment bei Offset {} umfasst {} Bytes (bis zum Ende seiner ersten mdat-Box)"
+    ),
+    ("sidx reference {} points to a {} box instead of a sidx box", "sidx-Referenz {} verweist auf eine {}-Box statt auf eine sidx-Box"),
+    (
+        "sidx reference {} declares a duration of {}, but the track runs of track {} add up to {} (sidx timescale {})",
+        "sidx-Referenz {} gibt eine Dauer von {} an, aber die Spurfragment-Läufe von Spur {} ergeben {} (sidx-Zeitskala {})"
+    ),
+    // Layout
+    ("Layout of file: {} ({} bytes)", "Aufbau der Datei: {} ({} Bytes)"),
+    ("Range", "Bereich"),
+    ("Share", "Anteil"),
+    ("Structure", "Struktur"),
+    ("MPEG audio", "MPEG-Audio"),
+    ("Padding", "Auffüllung"),
+    ("Unrecognized data", "Nicht erkannte Daten"),
+    // Fragments
+    ("Fragments of file: {}", "Fragmente der Datei: {}"),
+    ("No movie fragments found", "Keine Filmfragmente gefunden"),
+    ("Fragments:", "Fragmente:"),
+    ("Timing:", "Zeitverlauf:"),
+    ("Seq", "Nr."),
+    ("Decode Time", "Dekodierzeit"),
+    ("Samples", "Samples"),
+    ("Byte Range", "Byte-Bereich"),
+    ("0x{}-0x{} ({} bytes)", "0x{}-0x{} ({} Bytes)"),
+    ("No gaps or overlaps between consecutive fragments of a track", "Keine Lücken oder Überlappungen zwischen aufeinanderfolgenden Fragmenten einer Spur"),
+    (
+        "Track {}: gap of {} between fragments {} and {} (expected decode time {}, found {})",
+        "Spur {}: Lücke von {} zwischen den Fragmenten {} und {} (erwartete Dekodierzeit {}, gefunden {})"
+    ),
+    (
+        "Track {}: overlap of {} between fragments {} and {} (expected decode time {}, found {})",
+        "Spur {}: Überlappung von {} zwischen den Fragmenten {} und {} (erwartete Dekodierzeit {}, gefunden {})"
+    ),
+    // Playlist
+    ("Not an HLS playlist: the first line is not #EXTM3U", "Keine HLS-Playlist: Die erste Zeile ist nicht #EXTM3U"),
+    ("Segment {} has no EXTINF tag", "Segment {} hat kein EXTINF-Tag"),
+    ("Invalid EXTINF duration '{}'", "Ungültige EXTINF-Dauer '{}'"),
+    ("Invalid EXT-X-BYTERANGE '{}'", "Ungültiger EXT-X-BYTERANGE '{}'"),
+    (
+        "Master playlist with {} variant streams ({}); pass one of the media playlists",
+        "Master-Playlist mit {} Varianten ({}); bitte eine der Medien-Playlists angeben"
+    ),
+    ("Initialization segment {}: {}", "Initialisierungssegment {}: {}"),
+    ("Initialization segment {}: remote, not fetched", "Initialisierungssegment {}: entfernt, nicht abgerufen"),
+    ("Playlist: {}", "Playlist: {}"),
+    ("Target duration: {} s, media sequence {}", "Zieldauer: {} s, Mediensequenz {}"),
+    ("{} segments, complete (EXT-X-ENDLIST)", "{} Segmente, vollständig (EXT-X-ENDLIST)"),
+    ("{} segments, open for more (no EXT-X-ENDLIST)", "{} Segmente, weitere können folgen (kein EXT-X-ENDLIST)"),
+    ("No media segments found", "Keine Mediensegmente gefunden"),
+    ("Segments:", "Segmente:"),
+    ("Segment", "Segment"),
+    ("Start", "Start"),
+    ("Key", "Schlüssel"),
+    ("Encryption", "Verschlüsselung"),
+    ("(discontinuity)", "(Diskontinuität)"),
+    ("remote", "entfernt"),
+    ("unreadable", "nicht lesbar"),
+    ("The segments match the playlist", "Die Segmente stimmen mit der Playlist überein"),
+    ("Segment {}: {}", "Segment {}: {}"),
+    ("Segment {}: EXTINF of {} s exceeds EXT-X-TARGETDURATION of {} s", "Segment {}: EXTINF von {} s überschreitet EXT-X-TARGETDURATION von {} s"),
+    ("Segment {}: remote segment, not fetched", "Segment {}: entferntes Segment, nicht abgerufen"),
+    ("Segment {}: encrypted as a whole with AES-128, not inspected", "Segment {}: vollständig mit AES-128 verschlüsselt, nicht untersucht"),
+    ("Segment {}: neither an MPEG transport stream nor a fragmented MP4 segment", "Segment {}: weder ein MPEG-Transportstrom noch ein fragmentiertes MP4-Segment"),
+    (
+        "Segment {}: EXT-X-KEY declares AES-128, but the segment is readable in the clear",
+        "Segment {}: EXT-X-KEY deklariert AES-128, aber das Segment ist unverschlüsselt lesbar"
+    ),
+    (
+        "Segment {}: {} segments carry no timing the playlist can be checked against",
+        "Segment {}: {}-Segmente enthalten keine Zeitangaben, mit denen die Playlist geprüft werden kann"
+    ),
+    ("Segment {}: EXTINF declares {} s, the media lasts {} s", "Segment {}: EXTINF deklariert {} s, die Medien dauern {} s"),
+    (
+        "Segment {}: timestamps jump by {} s from the previous segment without EXT-X-DISCONTINUITY",
+        "Segment {}: Zeitstempel springen gegenüber dem vorigen Segment um {} s ohne EXT-X-DISCONTINUITY"
+    ),
+    (
+        "Segment {}: EXT-X-DISCONTINUITY, but the timestamps continue the previous segment",
+        "Segment {}: EXT-X-DISCONTINUITY, aber die Zeitstempel setzen das vorige Segment fort"
+    ),
+    ("Segment {}: encrypted ({}), but no EXT-X-KEY is in effect", "Segment {}: verschlüsselt ({}), aber kein EXT-X-KEY ist wirksam"),
+    (
+        "Segment {}: EXT-X-KEY declares {}, but the segment carries no encryption signaling",
+        "Segment {}: EXT-X-KEY deklariert {}, aber das Segment signalisiert keine Verschlüsselung"
+    ),
+    (
+        "Segment {}: EXT-X-KEY declares {}, but the segment is encrypted with '{}' (expected '{}')",
+        "Segment {}: EXT-X-KEY deklariert {}, aber das Segment ist mit '{}' verschlüsselt (erwartet '{}')"
+    ),
+    // Find
+    ("Searching '{}' in file: {}", "Suche '{}' in Datei: {}"),
+    ("Structure walk stopped:", "Strukturdurchlauf abgebrochen:"),
+    ("No occurrences found", "Keine Vorkommen gefunden"),
+    ("{} occurrences, {} found only by the byte scan", "{} Vorkommen, davon {} nur durch die Byte-Suche gefunden"),
+    ("Offset", "Offset"),
+    ("Kind", "Art"),
+    ("Location", "Fundstelle"),
+    ("box", "Box"),
+    ("frame", "Frame"),
+    ("(byte scan only)", "(nur Byte-Suche)"),
+    ("Invalid code '{}': only ASCII characters and '©' are allowed", "Ungültiger Code '{}': nur ASCII-Zeichen und '©' sind erlaubt"),
+    ("Invalid code '{}': expected four characters", "Ungültiger Code '{}': vier Zeichen erwartet"),
+    // Optimize
+    ("Optimization report of file: {} ({} bytes)", "Optimierungsbericht der Datei: {} ({} Bytes)"),
+    ("No wasted space found", "Kein verschwendeter Platz gefunden"),
+    ("Reclaimable: {} bytes ({}% of the file)", "Einsparbar: {} Bytes ({}% der Datei)"),
+    ("Finding", "Befund"),
+    ("Reclaimable", "Einsparbar"),
+    ("Details", "Details"),
+    ("Suggestions:", "Vorschläge:"),
+    ("same picture as {}", "gleiches Bild wie {}"),
+    ("Tag padding", "Tag-Auffüllung"),
+    ("Free space box", "Freiraum-Box"),
+    ("Duplicate artwork", "Doppeltes Bild"),
+    ("Oversized artwork", "Übergroßes Bild"),
+    ("Movie box after media data", "Movie-Box nach den Mediendaten"),
+    (
+        "Rewrite the tag with less padding with tag repad (keep some if the tag is edited often, it avoids rewriting the file)",
+        "Tag mit tag repad mit weniger Auffüllung neu schreiben (bei häufig bearbeiteten Tags etwas behalten, es erspart das Neuschreiben der Datei)"
+    ),
+    (
+        "Drop the box when the file is rewritten (keep it if the metadata is edited often, it absorbs size changes)",
+        "Box beim Neuschreiben der Datei entfernen (bei häufig bearbeiteten Metadaten behalten, sie fängt Größenänderungen auf)"
+    ),
+    (
+        "Store the picture once, e.g. leave it out of the chapters that repeat the cover",
+        "Bild nur einmal speichern, z. B. in Kapiteln weglassen, die das Cover wiederholen"
+    ),
+    ("Scale the picture down to at most 3000x3000 pixels and store it as JPEG", "Bild auf höchstens 3000x3000 Pixel verkleinern und als JPEG speichern"),
+    (
+        "Move the movie box in front of the media data with optimize --faststart, so playback can start before the file is fully downloaded",
+        "Movie-Box mit optimize --faststart vor die Mediendaten verschieben, damit die Wiedergabe vor dem vollständigen Download beginnen kann"
+    ),
+    ("--faststart only supports text output", "--faststart unterstützt nur die Textausgabe"),
+    ("{} is not an ISOBMFF file (MP4, M4A, MOV, ...)", "{} ist keine ISOBMFF-Datei (MP4, M4A, MOV, ...)"),
+    ("Moving the movie box to the front of file: {}", "Verschiebe die Movie-Box an den Anfang der Datei: {}"),
+    (
+        "Movie box of {} bytes moved in front of the media data, {} chunk offsets updated",
+        "Movie-Box mit {} Bytes vor die Mediendaten verschoben, {} Chunk-Offsets aktualisiert"
+    ),
+    ("The movie box already precedes the media data, the file was left untouched", "Die Movie-Box steht bereits vor den Mediendaten, die Datei wurde nicht verändert"),
+    ("The movie box of a fragmented file cannot be moved", "Die Movie-Box einer fragmentierten Datei kann nicht verschoben werden"),
+    // Diff
+    ("Comparing {} with {}", "Vergleiche {} mit {}"),
+    ("No structural differences", "Keine strukturellen Unterschiede"),
+    ("{} added, {} removed, {} changed, {} moved", "{} hinzugefügt, {} entfernt, {} geändert, {} verschoben"),
+    ("(file)", "(Datei)"),
+    ("added", "hinzugefügt"),
+    ("removed", "entfernt"),
+    ("changed", "geändert"),
+    ("moved from {} to {}", "verschoben von {} nach {}"),
+    ("at {}", "bei {}"),
+    // Tag editing
+    ("Editing ID3v2.{} tag of file: {}", "Bearbeite ID3v2.{}-Tag der Datei: {}"),
+    ("Added {}: \"{}\"", "{} hinzugefügt: \"{}\""),
+    ("Replaced {}: \"{}\"", "{} ersetzt: \"{}\""),
+    ("Removed {}: 1 frame", "{} entfernt: 1 Frame"),
+    ("Removed {}: {} frames", "{} entfernt: {} Frames"),
+    ("Removed {}: 1 box", "{} entfernt: 1 Box"),
+    ("Removed {}: {} boxes", "{} entfernt: {} Boxen"),
+    ("No {} frame to remove", "Kein {}-Frame zum Entfernen vorhanden"),
+    ("No {} box to remove", "Keine {}-Box zum Entfernen vorhanden"),
+    ("Tag written: {} bytes of frames and padding ({} bytes padding)", "Tag geschrieben: {} Bytes Frames und Padding ({} Bytes Padding)"),
+    (
+        "The extended header was dropped, its CRC would no longer match the frames",
+        "Der erweiterte Header wurde entfernt, seine CRC würde nicht mehr zu den Frames passen"
+    ),
+    ("Nothing changed, the file was left untouched", "Keine Änderung, die Datei bleibt unverändert"),
+    ("Expected FRAME=VALUE, got '{}'", "FRAME=WERT erwartet, '{}' erhalten"),
+    (
+        "Expected a sample range such as 1..10, 5.. or 7 (samples are counted from 1), got '{}'",
+        "Sample-Bereich wie 1..10, 5.. oder 7 erwartet (Samples werden ab 1 gezählt), '{}' erhalten"
+    ),
+    ("No samples found for track {} in {}", "Keine Samples für Spur {} in {} gefunden"),
+    ("Track {} has {} samples, none in the requested range", "Spur {} hat {} Samples, keines im angeforderten Bereich"),
+    ("Extracting {} of {} samples of track {} from file: {}", "Extrahiere {} von {} Samples der Spur {} aus Datei: {}"),
+    ("Sample {}: offset {}, {} bytes -> {}", "Sample {}: Offset {}, {} Bytes -> {}"),
+    ("Sample {} at offset {} ({} bytes) lies beyond the end of the file", "Sample {} an Offset {} ({} Bytes) liegt hinter dem Dateiende"),
+    ("No track {} found in {}", "Keine Spur {} in {} gefunden"),
+    ("Track {} is not an audio track (handler '{}')", "Spur {} ist keine Audiospur (Handler '{}')"),
+    ("Track {} has no sample description", "Spur {} hat keine Sample-Beschreibung"),
+    ("The '{}' sample entry of track {} has no decoder configuration", "Der '{}'-Sample-Eintrag der Spur {} hat keine Decoder-Konfiguration"),
+    (
+        "Track {} has codec '{}', only AAC (mp4a), ALAC and FLAC tracks can be extracted",
+        "Spur {} hat den Codec '{}', nur AAC- (mp4a), ALAC- und FLAC-Spuren können extrahiert werden"
+    ),
+    ("{} at {} Hz cannot be written with ADTS headers", "{} mit {} Hz kann nicht mit ADTS-Headern geschrieben werden"),
+    ("Sample {} of track {} is too large for an ADTS frame ({} bytes)", "Sample {} der Spur {} ist zu groß für einen ADTS-Frame ({} Bytes)"),
+    ("Extracted {} samples of track {} ({}) to {} ({} bytes)", "{} Samples der Spur {} ({}) nach {} extrahiert ({} Bytes)"),
+    ("No embedded objects found in {}", "Keine eingebetteten Objekte in {} gefunden"),
+    ("Extracting {} objects from file: {}", "Extrahiere {} Objekte aus Datei: {}"),
+    ("{} ({}, {} bytes)", "{} ({}, {} Bytes)"),
+    ("Manifest: {}", "Manifest: {}"),
+    ("Invalid frame ID '{}': expected four upper-case letters or digits", "Ungültige Frame-ID '{}': vier Großbuchstaben oder Ziffern erwartet"),
+    ("Only TXXX frames are selected by description, not '{}'", "Nur TXXX-Frames werden über die Beschreibung ausgewählt, nicht '{}'"),
+    ("The file has no ID3v2 tag and does not start with MPEG audio", "Die Datei hat keinen ID3v2-Tag und beginnt nicht mit MPEG-Audio"),
+    ("ID3v2.{} tags cannot be edited", "ID3v2.{}-Tags können nicht bearbeitet werden"),
+    ("Corrupt frame data at position {} of the tag, the tag is not rewritten", "Beschädigte Frame-Daten an Position {} des Tags, der Tag wird nicht neu geschrieben"),
+    ("Only text frames (T...) can be set, not '{}'", "Nur Text-Frames (T...) können gesetzt werden, nicht '{}'"),
+    ("TXXX frames are set with a description: TXXX:<description>=<value>", "TXXX-Frames werden mit Beschreibung gesetzt: TXXX:<Beschreibung>=<Wert>"),
+    ("Frame {} is not defined in ID3v2.{}", "Frame {} ist in ID3v2.{} nicht definiert"),
+    ("The tag would exceed the maximum ID3v2 size of {} bytes", "Der Tag würde die maximale ID3v2-Größe von {} Bytes überschreiten"),
+    ("Stripping tags of file: {}", "Entferne Tags der Datei: {}"),
+    ("No ID3v2 tag found", "Kein ID3v2-Tag gefunden"),
+    ("No ID3v1 tag found", "Kein ID3v1-Tag gefunden"),
+    ("Removed ID3v2 tag: {} bytes", "ID3v2-Tag entfernt: {} Bytes"),
+    ("Removed ID3v1 tag: {} bytes", "ID3v1-Tag entfernt: {} Bytes"),
+    ("Converting ID3v2.3 tag to ID3v2.4 of file: {}", "Konvertiere ID3v2.3-Tag nach ID3v2.4 in der Datei: {}"),
+    ("Renamed {} to {}", "{} in {} umbenannt"),
+    ("Merged {} into TDRC: \"{}\"", "{} in TDRC zusammengeführt: \"{}\""),
+    ("Dropped {}: not defined in ID3v2.4", "{} entfernt: in ID3v2.4 nicht definiert"),
+    ("Only ID3v2.3 tags can be converted, this tag is ID3v2.{}", "Nur ID3v2.3-Tags können konvertiert werden, dieser Tag ist ID3v2.{}"),
+    ("The file has no ID3v2 tag to convert", "Die Datei hat keinen ID3v2-Tag zum Konvertieren"),
+    ("Repadding ID3v2.{} tag of file: {}", "Ändere das Padding des ID3v2.{}-Tags der Datei: {}"),
+    ("Padding: {} bytes before, {} bytes requested", "Padding: vorher {} Bytes, angefordert {} Bytes"),
+    ("The audio data moved by {} bytes", "Die Audiodaten wurden um {} Bytes verschoben"),
+    ("The file has no ID3v2 tag to repad", "Die Datei hat keinen ID3v2-Tag, dessen Padding geändert werden kann"),
+    ("ID3v2.4 tags with a footer must not have padding", "ID3v2.4-Tags mit Footer dürfen kein Padding haben"),
+    ("Frame {} is too short for the data announced by its flags", "Frame {} ist zu kurz für die von seinen Flags angekündigten Daten"),
+    ("Editing iTunes metadata of file: {}", "Bearbeite iTunes-Metadaten der Datei: {}"),
+    ("Movie box written: {} bytes", "Movie-Box geschrieben: {} Bytes"),
+    (
+        "Free space next to the metadata absorbed the size change, the media data did not move",
+        "Freier Platz neben den Metadaten hat die Größenänderung aufgenommen, die Mediendaten wurden nicht verschoben"
+    ),
+    (
+        "The media data after the movie box moved by {} bytes, {} chunk offsets updated",
+        "Die Mediendaten nach der Movie-Box wurden um {} Bytes verschoben, {} Chunk-Offsets angepasst"
+    ),
+    ("Chunk offset out of range after moving the media data", "Chunk-Offset nach dem Verschieben der Mediendaten außerhalb des gültigen Bereichs"),
+    (
+        "Chunk offsets would exceed 32 bits, the 'stco' table would have to become 'co64'",
+        "Chunk-Offsets würden 32 Bit überschreiten, die 'stco'-Tabelle müsste zu 'co64' werden"
+    ),
+    ("The 'ilst' box cannot be parsed", "Die 'ilst'-Box kann nicht gelesen werden"),
+    ("The file has no movie box ('moov')", "Die Datei hat keine Movie-Box ('moov')"),
+    ("The movie box has no room for an 'ilst' box", "Die Movie-Box bietet keinen Platz für eine 'ilst'-Box"),
+    (
+        "The movie box of a fragmented file cannot change its size without free space next to it",
+        "Die Movie-Box einer fragmentierten Datei kann ihre Größe ohne freien Platz daneben nicht ändern"
+    ),
+    ("'{}' is not a number for item {}", "'{}' ist keine Zahl für Eintrag {}"),
+    (
+        "Box '{}' at offset {} has an invalid size of {} bytes, the file is not rewritten",
+        "Box '{}' bei Offset {} hat eine ungültige Größe von {} Bytes, die Datei wird nicht neu geschrieben"
+    ),
+    ("Box '{}' at position {} has an invalid size of {} bytes", "Box '{}' an Position {} hat eine ungültige Größe von {} Bytes"),
+    ("Cannot read cover image '{}': {}", "Cover-Bild '{}' kann nicht gelesen werden: {}"),
+    ("Cover image '{}' is neither JPEG nor PNG", "Cover-Bild '{}' ist weder JPEG noch PNG"),
+    ("Invalid free-form key '{}': expected ----:<mean>:<name>", "Ungültiger Freiform-Schlüssel '{}': ----:<mean>:<name> erwartet"),
+    (
+        "Invalid iTunes item key '{}': expected four characters (e.g. ©nam) or ----:<mean>:<name>",
+        "Ungültiger iTunes-Schlüssel '{}': vier Zeichen (z. B. ©nam) oder ----:<mean>:<name> erwartet"
+    ),
+    ("Unexpected data at position {} after the last box", "Unerwartete Daten an Position {} nach der letzten Box"),
+    ("Standard input cannot be edited", "Die Standardeingabe kann nicht bearbeitet werden"),
+    ("The file ended while it was being copied", "Die Datei endete während des Kopierens"),
+    // Recovery
+    ("{}; {} trailing bytes ignored", "{}; {} nachfolgende Bytes ignoriert"),
+    ("{}; skipped {} bytes to the next box", "{}; {} Bytes bis zur nächsten Box übersprungen"),
+    ("{}; no further box in the remaining {} bytes", "{}; keine weitere Box in den verbleibenden {} Bytes"),
+    ("{}; skipped {} bytes to the next frame", "{}; {} Bytes bis zum nächsten Frame übersprungen"),
+    ("{}; no further frame in the remaining {} bytes", "{}; kein weiterer Frame in den verbleibenden {} Bytes"),
+    ("{} bytes of unrecognized data before the file type box at {} skipped", "{} Bytes nicht erkannter Daten vor der Dateityp-Box bei {} übersprungen"),
+    ("Unexpected data in the padding", "Unerwartete Daten im Padding"),
+    ("Corrupt frame '{}' (size {} bytes)", "Beschädigter Frame '{}' (Größe {} Bytes)"),
+    ("Skipped corrupt data at position {}, resuming at position {}", "Beschädigte Daten an Position {} übersprungen, weiter ab Position {}"),
+    // Large files
+    ("Raw data (first {} of {} bytes):", "Rohdaten (erste {} von {} Bytes):"),
+    ("... {} more bytes (--dump-limit full shows all)", "... {} weitere Bytes (--dump-limit full zeigt alle)"),
+    ("Expected a byte count or 'full', got '{}'", "Byte-Anzahl oder 'full' erwartet, '{}' erhalten"),
+    // Hexdump fields
+    ("Fields:", "Felder:"),
+    ("1 byte", "1 Byte"),
+    ("Terminator", "Terminator"),
+    ("Text encoding", "Textkodierung"),
+    ("Value", "Wert"),
+    ("URL", "URL"),
+    ("Text", "Text"),
+    ("Content descriptor", "Inhaltsbeschreibung"),
+    ("MIME type", "MIME-Typ"),
+    ("Picture type", "Bildtyp"),
+    ("Picture data", "Bilddaten"),
+    ("Owner identifier", "Eigentümerkennung"),
+    ("Identifier", "Kennung"),
+    ("Private data", "Private Daten"),
+    ("Filename", "Dateiname"),
+    ("Encapsulated object", "Eingebettetes Objekt"),
+    ("Element ID", "Element-ID"),
+    ("Start time", "Startzeit"),
+    ("End time", "Endzeit"),
+    ("Start offset", "Start-Offset"),
+    ("End offset", "End-Offset"),
+    ("Embedded frames", "Eingebettete Frames"),
+    ("Flags", "Flags"),
+    ("Entry count", "Anzahl der Einträge"),
+    ("Child element ID", "Kind-Element-ID"),
+    ("Email to user", "E-Mail des Benutzers"),
+    ("Rating", "Bewertung"),
+    ("Version", "Version"),
+    ("Major brand", "Hauptmarke"),
+    ("Minor version", "Unterversion"),
+    ("Compatible brands", "Kompatible Marken"),
+    ("Pre-defined", "Vordefiniert"),
+    ("Handler type", "Handler-Typ"),
+    ("Reserved", "Reserviert"),
+    ("Creation time", "Erstellungszeit"),
+    ("Modification time", "Änderungszeit"),
+    ("Timescale", "Zeitskala"),
+    ("Rate", "Rate"),
+    ("Volume", "Lautstärke"),
+    ("Matrix", "Matrix"),
+    ("Next track ID", "Nächste Track-ID"),
+    ("Track ID", "Track-ID"),
+    ("Layer", "Ebene"),
+    ("Alternate group", "Alternativgruppe"),
+    ("Width", "Breite"),
+    ("Height", "Höhe"),
+    ("Entries", "Einträge"),
+    ("Sample size", "Sample-Größe"),
+    ("Sample count", "Anzahl der Samples"),
+    ("Sequence number", "Sequenznummer"),
+    ("Base media decode time", "Basis-Dekodierzeit"),
+    ("Type indicator", "Typkennung"),
+    ("Locale", "Gebietsschema"),
+    ("Sample flags", "Sample-Flags"),
+    // Truncation
+    ("Incomplete box header at {}: the file ends after {} bytes", "Unvollständiger Box-Header bei {}: die Datei endet nach {} Bytes"),
+    ("Incomplete box header at {}: the parent box ends after {} bytes", "Unvollständiger Box-Header bei {}: die übergeordnete Box endet nach {} Bytes"),
+    ("{} bytes of unrecognized data after the last box", "{} Bytes nicht erkannter Daten nach der letzten Box"),
+    ("Truncated: declared size {} bytes, {} bytes missing", "Abgeschnitten: angegebene Größe {} Bytes, {} Bytes fehlen"),
+    (
+        "Box '{}' is truncated: declared size {} bytes, {} bytes present, {} bytes missing",
+        "Box '{}' ist abgeschnitten: angegebene Größe {} Bytes, {} Bytes vorhanden, {} Bytes fehlen"
+    ),
+    ("{} (cut off inside {})", "{} (abgeschnitten innerhalb von {})"),
+    ("Top-level boxes not reached before the end of the file: {}", "Vor dem Dateiende nicht erreichte Boxen der obersten Ebene: {}"),
+    ("Track {}: {} of {} chunks start beyond the end of the file", "Spur {}: {} von {} Chunks beginnen hinter dem Dateiende"),
+    // Summary
+    ("Summary of file: {}", "Übersicht der Datei: {}"),
+    ("Nothing to summarize", "Nichts zusammenzufassen"),
+    ("Tracks:", "Spuren:"),
+    ("ID", "ID"),
+    ("Type", "Typ"),
+    ("Codec", "Codec"),
+    ("Duration", "Dauer"),
+    ("Language", "Sprache"),
+    ("Format", "Format"),
+    ("video", "Video"),
+    ("audio", "Audio"),
+    ("subtitle", "Untertitel"),
+    ("data", "Daten"),
+    ("{} tag:", "{}-Tag:"),
+    ("Title", "Titel"),
+    ("Artist", "Interpret"),
+    ("Album", "Album"),
+    ("Genre", "Genre"),
+    ("Track", "Titelnummer"),
+    ("Pictures", "Bilder"),
+    ("{} ({} bytes)", "{} ({} Bytes)"),
+    ("Chapters", "Kapitel"),
+    // Dissector names
+    ("ID3v2.3 Dissector", "ID3v2.3-Dissector"),
+    ("ID3v2.4 Dissector", "ID3v2.4-Dissector"),
+    ("ISO Base Media File Format Dissector", "ISO-Base-Media-File-Format-Dissector"),
+    ("Matroska/WebM Dissector", "Matroska/WebM-Dissector"),
+    ("Ogg Container Dissector", "Ogg-Container-Dissector"),
+    ("Image Dissector", "Bild-Dissector"),
+    ("MPEG Transport Stream Dissector", "MPEG-Transportstrom-Dissector"),
+    ("MPEG Program Stream Dissector", "MPEG-Programmstrom-Dissector"),
+    ("DSF Dissector", "DSF-Dissector"),
+    ("DSDIFF Dissector", "DSDIFF-Dissector"),
+    ("WavPack Dissector", "WavPack-Dissector"),
+    ("Musepack Dissector", "Musepack-Dissector"),
+    ("True Audio Dissector", "True-Audio-Dissector"),
+    ("AMR Dissector", "AMR-Dissector"),
+    ("ADTS Dissector", "ADTS-Dissector"),
+    ("Unknown Format Dissector", "Dissector für unbekannte Formate"),
+    // Timestamp plausibility checks
+    ("creation time", "Erstellungszeit"),
+    ("modification time", "Änderungszeit"),
+    ("{} {} is not set (0)", "{}: {} ist nicht gesetzt (0)"),
+    ("{} {} {} lies before 1970", "{}: {} {} liegt vor 1970"),
+    ("{} {} {} lies in the future", "{}: {} {} liegt in der Zukunft"),
+    ("{} modification time ({}) is earlier than its creation time ({})", "{}: Änderungszeit ({}) liegt vor der Erstellungszeit ({})"),
+    (
+        "{} creation time differs from {} at {} by {} days (possible re-mux or edited file)",
+        "{}: Erstellungszeit weicht von {} an {} um {} Tage ab (möglicherweise neu gemuxt oder bearbeitet)"
+    ),
+    // ISOBMFF output
+    ("ISO Base Media File Format Header:", "ISO-Base-Media-File-Format-Header:"),
+    ("Box Structure:", "Box-Struktur:"),
+    ("Box at offset {}: {} - Size: {} bytes", "Box an Offset {}: {} - Größe: {} Bytes"),
+    ("Metadata Key {}", "Metadatenschlüssel {}"),
+    // Sample table statistics
+    ("Sample Table Statistics:", "Sample-Tabellen-Statistik:"),
+    ("Chapter Track:", "Kapitelspur:"),
+    ("Track {} (referenced by track {}), {} chapters:", "Spur {} (referenziert von Spur {}), {} Kapitel:"),
+    ("Track {} ('{}'), timescale {}:", "Spur {} ('{}'), Zeitbasis {}:"),
+    ("Samples: {}, duration: {} s", "Samples: {}, Dauer: {} s"),
+    ("Sample durations (units x samples): {}", "Sample-Dauern (Einheiten x Samples): {}"),
+    ("{} more", "{} weitere"),
+    ("Sample sizes: min {}, max {}, average {} bytes ({} bytes total)", "Sample-Größen: min. {}, max. {}, Durchschnitt {} Bytes ({} Bytes gesamt)"),
+    ("Chunks: {}, {}-{} samples per chunk (average {})", "Chunks: {}, {}-{} Samples pro Chunk (Durchschnitt {})"),
+    ("not in file order", "nicht in Dateireihenfolge"),
+    // APE tag output
+    ("No tag header (footer only)", "Kein Tag-Header (nur Footer)"),
+    ("Read-only", "Schreibgeschützt"),
+    ("Items: {}", "Einträge: {}"),
+    ("{} (external locator)", "{} (externer Verweis)"),
+    ("{} image, {} bytes ({})", "{}-Bild, {} Bytes ({})"),
+    ("binary data, {} bytes", "Binärdaten, {} Bytes"),
+    ("APE tag declares {} items, but only {} could be parsed", "APE-Tag gibt {} Einträge an, aber nur {} konnten gelesen werden"),
+    // Lyrics3 tag output
+    ("Fields: {}", "Felder: {}"),
+    ("{} lines", "{} Zeilen"),
+    ("Indications", "Kennzeichen"),
+    ("Lyrics", "Liedtext"),
+    ("Additional Information", "Zusätzliche Informationen"),
+    ("Lyrics Author", "Textautor"),
+    ("Extended Album", "Erweitertes Album"),
+    ("Extended Artist", "Erweiterter Künstler"),
+    ("Extended Title", "Erweiterter Titel"),
+    ("Image Links", "Bildverweise"),
+    ("Unknown field", "Unbekanntes Feld"),
+    ("Lyrics3v2 field is malformed, {} bytes of the tag could not be parsed", "Lyrics3v2-Feld ist fehlerhaft, {} Bytes des Tags konnten nicht gelesen werden"),
+    (
+        "{} tag of {} bytes between the audio data and the ID3v1 tag (tools unaware of it count it as audio)",
+        "{}-Tag mit {} Bytes zwischen den Audiodaten und dem ID3v1-Tag (Werkzeuge, die ihn nicht kennen, zählen ihn zu den Audiodaten)"
+    ),
+    // Matroska output
+    ("EBML Header:", "EBML-Header:"),
+    ("Element Structure:", "Element-Struktur:"),
+    ("Element at offset {}: {} - Size: {} bytes", "Element an Offset {}: {} - Größe: {} Bytes"),
+    ("unknown size", "unbekannte Größe"),
+    // Image output
+    ("Image:", "Bild:"),
+    ("Format: {}", "Format: {}"),
+    ("Dimensions: {} x {}", "Abmessungen: {} x {}"),
+    ("Color: {}", "Farbe: {}"),
+    ("Image Structure:", "Bildstruktur:"),
+    ("Image: {}", "Bild: {}"),
+    ("Cover Art:", "Cover-Art:"),
+    ("Picture not analyzed: {}", "Bild nicht analysiert: {}"),
+    ("EXIF:", "EXIF:"),
+    // MPEG transport stream output
+    ("MPEG Transport Stream:", "MPEG-Transportstrom:"),
+    ("Packet format: {}", "Paketformat: {}"),
+    ("Packets: {}", "Pakete: {}"),
+    ("Transport stream ID: {}", "Transportstrom-ID: {}"),
+    ("Programs: {}", "Programme: {}"),
+    ("Programs:", "Programme:"),
+    ("Program {}: PMT PID {}, PCR PID {}", "Programm {}: PMT-PID {}, PCR-PID {}"),
+    ("PIDs:", "PIDs:"),
+    ("{} packets", "{} Pakete"),
+    ("{} continuity errors", "{} Kontinuitätsfehler"),
+    ("PTS span {} s", "PTS-Spanne {} s"),
+    ("PCR Timing:", "PCR-Zeitbasis:"),
+    ("Timed ID3 Metadata:", "Zeitgesteuerte ID3-Metadaten:"),
+    ("ID3v2.{} tag with {} frames", "ID3v2.{}-Tag mit {} Frames"),
+    ("At 0x{}, PTS {}: {}", "Bei 0x{}, PTS {}: {}"),
+    ("At 0x{}: {}", "Bei 0x{}: {}"),
+    ("{}: {} bytes", "{}: {} Bytes"),
+    ("PID 0x{}: {} values, {} s, maximum interval {} ms", "PID 0x{}: {} Werte, {} s, maximaler Abstand {} ms"),
+    ("{} kbit/s", "{} kbit/s"),
+    // MPEG program stream output
+    ("MPEG Program Stream:", "MPEG-Programmstrom:"),
+    ("Pack headers: MPEG-{}", "Pack-Header: MPEG-{}"),
+    ("Packs: {}", "Packs: {}"),
+    ("Mux rate: {} kbit/s", "Multiplexrate: {} kbit/s"),
+    ("System Header:", "System-Header:"),
+    ("Elementary Streams:", "Elementarströme:"),
+    ("DVD navigation packets: {}", "DVD-Navigationspakete: {}"),
+    ("Padding: {} bytes", "Auffüllung: {} Bytes"),
+    // DSD output
+    ("DSF File:", "DSF-Datei:"),
+    ("Format version: {}", "Formatversion: {}"),
+    ("Channels: {} ({})", "Kanäle: {} ({})"),
+    ("mono", "Mono"),
+    ("stereo", "Stereo"),
+    ("3 channels", "3 Kanäle"),
+    ("quad", "Quadro"),
+    ("4 channels", "4 Kanäle"),
+    ("5 channels", "5 Kanäle"),
+    ("5.1 channels", "5.1 Kanäle"),
+    ("unknown channel type", "unbekannter Kanaltyp"),
+    ("Bits per sample: {}", "Bits pro Sample: {}"),
+    ("Samples: {} per channel", "Samples: {} pro Kanal"),
+    ("Block size: {} bytes per channel", "Blockgröße: {} Bytes pro Kanal"),
+    ("Metadata: ID3v2 tag at offset 0x{}", "Metadaten: ID3v2-Tag an Offset 0x{}"),
+    ("DSF Chunks:", "DSF-Chunks:"),
+    ("DSDIFF File:", "DSDIFF-Datei:"),
+    ("Compression: {} ({})", "Kompression: {} ({})"),
+    ("DSDIFF Chunks:", "DSDIFF-Chunks:"),
+    ("Chunk at offset {}: '{}' ({}) - Size: {} bytes", "Chunk an Offset {}: '{}' ({}) - Größe: {} Bytes"),
+    ("ID3 chunk at offset 0x{}:", "ID3-Chunk an Offset 0x{}:"),
+    ("Form", "Form"),
+    ("Format version", "Formatversion"),
+    ("Property", "Eigenschaften"),
+    ("Sample rate", "Abtastrate"),
+    ("Channels", "Kanäle"),
+    ("Compression type", "Kompressionstyp"),
+    ("Absolute start time", "Absolute Startzeit"),
+    ("Loudspeaker configuration", "Lautsprecherkonfiguration"),
+    ("DSD sound data", "DSD-Audiodaten"),
+    ("DST sound data", "DST-Audiodaten"),
+    ("DST frame information", "DST-Frame-Informationen"),
+    ("DST sound index", "DST-Audioindex"),
+    ("Edited master information", "Informationen zum editierten Master"),
+    ("Edited master ID", "ID des editierten Masters"),
+    ("Marker", "Marke"),
+    ("Manufacturer specific", "Herstellerspezifisch"),
+    ("ID3v2 tag", "ID3v2-Tag"),
+    ("Unknown chunk", "Unbekannter Chunk"),
+    // WavPack, Musepack, TTA, AMR and ADTS output
+    ("WavPack Stream:", "WavPack-Strom:"),
+    ("Musepack Stream:", "Musepack-Strom:"),
+    ("True Audio Stream:", "True-Audio-Strom:"),
+    ("Stream version: {}", "Stromversion: {}"),
+    ("Mode: {}", "Modus: {}"),
+    ("lossless", "verlustfrei"),
+    ("hybrid (lossy)", "hybrid (verlustbehaftet)"),
+    ("Channels: {}", "Kanäle: {}"),
+    ("Blocks: {}", "Blöcke: {}"),
+    ("Profile: {}", "Profil: {}"),
+    ("Packets:", "Pakete:"),
+    ("Stream header", "Stromheader"),
+    ("Encoder information", "Encoder-Informationen"),
+    ("Seek table offset", "Offset der Sprungtabelle"),
+    ("Audio packet", "Audiopaket"),
+    ("Seek table", "Sprungtabelle"),
+    ("Chapter tag", "Kapitel-Tag"),
+    ("Stream end", "Stromende"),
+    ("Unknown packet", "Unbekanntes Paket"),
+    ("encrypted", "verschlüsselt"),
+    ("unknown format", "unbekanntes Format"),
+    ("AMR Stream:", "AMR-Strom:"),
+    ("Codec: {}", "Codec: {}"),
+    ("Frame Types:", "Frame-Typen:"),
+    ("SID (comfort noise)", "SID (Komfortrauschen)"),
+    ("speech lost", "Sprache verloren"),
+    ("no data", "keine Daten"),
+    ("ADTS Stream:", "ADTS-Strom:"),
+    ("defined in the program config element", "im Program Config Element festgelegt"),
+    // Ogg output
+    ("Ogg Container:", "Ogg-Container:"),
+    ("Pages: {}", "Seiten: {}"),
+    ("Logical streams: {}", "Logische Datenströme: {}"),
+    ("Logical Streams:", "Logische Datenströme:"),
+    ("Stream 0x{}: {}", "Datenstrom 0x{}: {}"),
+    ("First page at offset {}", "Erste Seite an Offset {}"),
+    ("Last granule position: {}", "Letzte Granule-Position: {}"),
+    ("Duration: {} s", "Dauer: {} s"),
+    ("Pages:", "Seiten:"),
+    // MPEG audio output
+    ("MPEG Audio Stream:", "MPEG-Audio-Datenstrom:"),
+    ("First frame at offset {}", "Erster Frame an Offset {}"),
+    ("Bitrate: {} kbps", "Bitrate: {} kbit/s"),
+    ("Sample Rate: {} Hz", "Abtastrate: {} Hz"),
+    ("Channel Mode: {}", "Kanalmodus: {}"),
+    ("Stereo", "Stereo"),
+    ("Joint stereo", "Joint Stereo"),
+    ("Dual channel", "Zweikanal"),
+    ("Mono", "Mono"),
+    ("CRC Protection: {}", "CRC-Schutz: {}"),
+    ("Copyright: {}, Original: {}", "Copyright: {}, Original: {}"),
+    ("Emphasis: {}", "Emphasis: {}"),
+    ("none", "keine"),
+    ("reserved", "reserviert"),
+    ("yes", "ja"),
+    ("no", "nein"),
+    ("{} header at offset {}", "{}-Header an Offset {}"),
+    ("Frames: {}", "Frames: {}"),
+    ("Stream size: {} bytes", "Datenstromgröße: {} Bytes"),
+    ("Quality: {}", "Qualität: {}"),
+    ("Encoder: {}", "Encoder: {}"),
+    ("No VBR header (Xing/Info/VBRI)", "Kein VBR-Header (Xing/Info/VBRI)"),
+    ("Duration: {}", "Dauer: {}"),
+    ("Duration: {} (estimated from file size, assuming constant bit rate)", "Dauer: {} (aus der Dateigröße geschätzt, konstante Bitrate angenommen)"),
+    ("Average Bitrate: {} kbps", "Durchschnittliche Bitrate: {} kbit/s"),
+    ("No MPEG audio frames found after the tag", "Keine MPEG-Audio-Frames nach dem Tag gefunden"),
+    ("MPEG Frame Scan:", "MPEG-Frame-Scan:"),
+    ("Bit rate mode: {} ({} kbps)", "Bitratenmodus: {} ({} kbit/s)"),
+    ("Padded frames: {} ({}%)", "Aufgefüllte Frames: {} ({}%)"),
+    ("Frame sync lost: {} times, {} bytes skipped", "Frame-Synchronisation verloren: {} Mal, {} Bytes übersprungen"),
+    ("ICY metadata blocks: {}", "ICY-Metadatenblöcke: {}"),
+    ("Bit Rates:", "Bitraten:"),
+    ("{} kbps: {} frames", "{} kbit/s: {} Frames"),
+    ("ICY Metadata:", "ICY-Metadaten:"),
+    ("{} bytes of unrecognized data before the first MPEG audio frame", "{} Bytes nicht erkannter Daten vor dem ersten MPEG-Audio-Frame"),
+    (
+        "{} header stream size ({} bytes) differs from the audio data size ({} bytes)",
+        "Datenstromgröße im {}-Header ({} Bytes) weicht von der Größe der Audiodaten ({} Bytes) ab"
+    ),
+    // ID3v2 output
+    ("ID3v2 Header Found:", "ID3v2-Header gefunden:"),
+    ("Version: {}", "Version: {}"),
+    ("Flags: {}", "Flags: {}"),
+    ("Active: {}", "Aktiv: {}"),
+    ("Tag Size: {} bytes", "Tag-Größe: {} Bytes"),
+    ("Footer at {}: {}", "Footer bei {}: {}"),
+    ("valid", "gültig"),
+    ("invalid", "ungültig"),
+    ("Expected ID3v2.{}, found version 2.{}", "ID3v2.{} erwartet, Version 2.{} gefunden"),
+    ("No ID3v2 header found", "Kein ID3v2-Header gefunden"),
+    ("Dissecting ID3v2.{} tag (size: {} bytes, flags: {})...", "Zerlege ID3v2.{}-Tag (Größe: {} Bytes, Flags: {})..."),
+    ("Successfully read {} bytes of tag data", "{} Bytes Tag-Daten erfolgreich gelesen"),
+    ("Unsynchronization detected - removing sync bytes", "Unsynchronisation erkannt - entferne Sync-Bytes"),
+    ("Unsynchronization detected - removing sync bytes per frame", "Unsynchronisation erkannt - entferne Sync-Bytes pro Frame"),
+    ("After unsynchronization removal: {} bytes", "Nach Entfernen der Unsynchronisation: {} Bytes"),
+    ("ID3v2.{} Frames:", "ID3v2.{}-Frames:"),
+    ("Reached padding or end of frames at position {}", "Padding oder Ende der Frames an Position {} erreicht"),
+    ("Padding: {} bytes at position {}", "Padding: {} Bytes an Position {}"),
+    ("Frame: {} ({}) - Size: {} bytes", "Frame: {} ({}) - Größe: {} Bytes"),
+    ("Extremely large tag size ({} bytes > {} MB), verify file integrity", "Extrem großes Tag ({} Bytes > {} MB), Dateiintegrität prüfen"),
+    (
+        "Tag size is very large ({} bytes > {} MB), likely rich podcast with chapter images",
+        "Sehr großes Tag ({} Bytes > {} MB), vermutlich Podcast mit Kapitelbildern"
+    ),
+    (
+        "Large tag size ({} bytes > {} MB), possibly podcast with embedded chapter content",
+        "Großes Tag ({} Bytes > {} MB), möglicherweise Podcast mit eingebetteten Kapitelinhalten"
+    ),
+    ("Tag size ({} bytes) exceeds the file, only {} bytes are present", "Tag-Größe ({} Bytes) übersteigt die Datei, nur {} Bytes sind vorhanden"),
+    ("ID3v2 extended header CRC-32 mismatch: stored {}, computed {}", "CRC-32 des erweiterten ID3v2-Headers stimmt nicht: gespeichert {}, berechnet {}"),
+    (
+        "ID3v2.4 footer flag is set, but the footer is missing or does not match the tag header",
+        "ID3v2.4-Footer-Flag ist gesetzt, aber der Footer fehlt oder passt nicht zum Tag-Header"
+    ),
+    // Unknown format output
+    ("Unknown format - no suitable dissector available", "Unbekanntes Format - kein passender Dissector verfügbar"),
+    ("File Summary:", "Dateiübersicht:"),
+    ("Entropy: {} bits/byte over first {} bytes ({})", "Entropie: {} Bit/Byte über die ersten {} Bytes ({})"),
+    ("compressed or encrypted data", "komprimierte oder verschlüsselte Daten"),
+    ("mixed binary data", "gemischte Binärdaten"),
+    ("structured binary data", "strukturierte Binärdaten"),
+    ("text or sparse data", "Text oder dünn besetzte Daten"),
+    ("Magic bytes: no known signature in first {} bytes", "Magic Bytes: keine bekannte Signatur in den ersten {} Bytes"),
+    ("Magic bytes:", "Magic Bytes:"),
+    ("Printable Strings (>= {} chars, first {} bytes):", "Druckbare Zeichenketten (>= {} Zeichen, erste {} Bytes):"),
+    ("(none)", "(keine)"),
+    ("... {} more", "... {} weitere"),
+    ("Leading Bytes:", "Anfangsbytes:"),
+    ("ID3v2 tag (unsupported version)", "ID3v2-Tag (nicht unterstützte Version)"),
+    ("ISOBMFF file type box (unrecognized brand)", "ISOBMFF-Dateityp-Box (unbekannte Marke)"),
+    ("QuickTime movie box without ftyp", "QuickTime-Movie-Box ohne ftyp"),
+    ("QuickTime media data box without ftyp", "QuickTime-Mediendaten-Box ohne ftyp"),
+    ("QuickTime wide placeholder box", "QuickTime-Platzhalter-Box (wide)"),
+    ("FLAC audio", "FLAC-Audio"),
+    ("Ogg container page", "Ogg-Container-Seite"),
+    ("EBML header (Matroska/WebM)", "EBML-Header (Matroska/WebM)"),
+    ("RIFF container", "RIFF-Container"),
+    ("RIFF WAVE audio", "RIFF-WAVE-Audio"),
+    ("RIFF AVI video", "RIFF-AVI-Video"),
+    ("RIFF WebP image", "RIFF-WebP-Bild"),
+    ("IFF container (AIFF/AIFC)", "IFF-Container (AIFF/AIFC)"),
+    ("WavPack audio block", "WavPack-Audioblock"),
+    ("Musepack SV8 audio", "Musepack-SV8-Audio"),
+    ("Musepack SV7 audio", "Musepack-SV7-Audio"),
+    ("AMR audio", "AMR-Audio"),
+    ("DSF (DSD stream file)", "DSF (DSD-Stream-Datei)"),
+    ("DSDIFF audio", "DSDIFF-Audio"),
+    ("MPEG program stream pack header", "MPEG-Programmstrom-Pack-Header"),
+    ("APE tag", "APE-Tag"),
+    ("Lyrics3 tag", "Lyrics3-Tag"),
+    ("JPEG image", "JPEG-Bild"),
+    ("PNG image", "PNG-Bild"),
+    ("GIF image", "GIF-Bild"),
+    ("PDF document", "PDF-Dokument"),
+    ("ZIP archive", "ZIP-Archiv"),
+    ("gzip compressed data", "gzip-komprimierte Daten"),
+    ("ELF executable", "ELF-Programmdatei"),
+    // ID3v2 frame descriptions
+    ("Album sort order", "Sortierreihenfolge Album"),
+    ("Album/Movie/Show title", "Album-/Film-/Sendungstitel"),
+    ("Attached picture", "Angehängtes Bild"),
+    ("Audio encryption", "Audioverschlüsselung"),
+    ("Audio seek point index", "Audio-Suchpunktindex"),
+    ("BPM (beats per minute)", "BPM (Schläge pro Minute)"),
+    ("Band/orchestra/accompaniment", "Band/Orchester/Begleitung"),
+    ("Chapter frame", "Kapitel-Frame"),
+    ("Comments", "Kommentare"),
+    ("Commercial frame", "Kommerzieller Frame"),
+    ("Commercial information", "Kommerzielle Informationen"),
+    ("Composer", "Komponist"),
+    ("Conductor/performer refinement", "Dirigent/Interpret-Präzisierung"),
+    ("Content group description", "Inhaltsgruppenbeschreibung"),
+    ("Content type", "Inhaltstyp"),
+    ("Copyright message", "Copyright-Hinweis"),
+    ("Copyright/Legal information", "Copyright/Rechtliche Informationen"),
+    ("Date", "Datum"),
+    ("Encoded by", "Kodiert von"),
+    ("Encoding time", "Kodierungszeit"),
+    ("Encryption method registration", "Registrierung der Verschlüsselungsmethode"),
+    ("Equalisation (2)", "Equalizer (2)"),
+    ("Equalisation", "Equalizer"),
+    ("Event timing codes", "Ereignis-Zeitcodes"),
+    ("File owner/licensee", "Dateieigentümer/Lizenznehmer"),
+    ("File type", "Dateityp"),
+    ("General encapsulated object", "Allgemeines eingebettetes Objekt"),
+    ("Group identification registration", "Registrierung der Gruppenidentifikation"),
+    ("ISRC (international standard recording code)", "ISRC (International Standard Recording Code)"),
+    ("Initial key", "Anfangstonart"),
+    ("Internet radio station name", "Name des Internetradiosenders"),
+    ("Internet radio station owner", "Eigentümer des Internetradiosenders"),
+    ("Interpreted, remixed, or otherwise modified by", "Interpretiert, geremixt oder anderweitig bearbeitet von"),
+    ("Involved people list", "Liste beteiligter Personen"),
+    ("Language(s)", "Sprache(n)"),
+    ("Lead performer(s)/Soloist(s)", "Hauptinterpret(en)/Solist(en)"),
+    ("Length", "Länge"),
+    ("Linked information", "Verknüpfte Informationen"),
+    ("Lyricist/Text writer", "Texter/Textautor"),
+    ("MPEG location lookup table", "MPEG-Positionstabelle"),
+    ("Media type", "Medientyp"),
+    ("Mood", "Stimmung"),
+    ("Music CD identifier", "Musik-CD-Kennung"),
+    ("Musician credits list", "Liste der Musiker"),
+    ("Official artist/performer webpage", "Offizielle Webseite des Künstlers/Interpreten"),
+    ("Official audio file webpage", "Offizielle Webseite der Audiodatei"),
+    ("Official audio source webpage", "Offizielle Webseite der Audioquelle"),
+    ("Official internet radio station homepage", "Offizielle Homepage des Internetradiosenders"),
+    ("Original album/movie/show title", "Originaltitel Album/Film/Sendung"),
+    ("Original artist(s)/performer(s)", "Ursprüngliche(r) Künstler/Interpret(en)"),
+    ("Original filename", "Ursprünglicher Dateiname"),
+    ("Original lyricist(s)/text writer(s)", "Ursprüngliche(r) Texter/Textautor(en)"),
+    ("Original release time", "Ursprüngliche Veröffentlichungszeit"),
+    ("Original release year", "Ursprüngliches Veröffentlichungsjahr"),
+    ("Ownership frame", "Eigentums-Frame"),
+    ("Part of a set", "Teil einer Sammlung"),
+    ("Payment", "Bezahlung"),
+    ("Performer sort order", "Sortierreihenfolge Interpret"),
+    ("Play counter", "Wiedergabezähler"),
+    ("Playlist delay", "Wiedergabelisten-Verzögerung"),
+    ("Popularimeter", "Beliebtheitsmesser"),
+    ("Podcast category", "Podcast-Kategorie"),
+    ("Podcast description", "Podcast-Beschreibung"),
+    ("Podcast episode identifier", "Podcast-Episodenkennung"),
+    ("Podcast feed URL", "Podcast-Feed-URL"),
+    ("Podcast flag", "Podcast-Kennzeichen"),
+    ("Podcast keywords", "Podcast-Schlüsselwörter"),
+    ("Position synchronisation frame", "Positionssynchronisations-Frame"),
+    ("Private frame", "Privater Frame"),
+    ("Produced notice", "Produktionshinweis"),
+    ("Publisher", "Verlag"),
+    ("Publishers official webpage", "Offizielle Webseite des Verlags"),
+    ("Recommended buffer size", "Empfohlene Puffergröße"),
+    ("Recording dates", "Aufnahmedaten"),
+    ("Recording time", "Aufnahmezeit"),
+    ("Relative volume adjustment (2)", "Relative Lautstärkeanpassung (2)"),
+    ("Relative volume adjustment", "Relative Lautstärkeanpassung"),
+    ("Release time", "Veröffentlichungszeit"),
+    ("Reverb", "Hall"),
+    ("Seek frame", "Such-Frame"),
+    ("Set subtitle", "Untertitel der Sammlung"),
+    ("Signature frame", "Signatur-Frame"),
+    ("Size", "Größe"),
+    ("Software/Hardware and settings used for encoding", "Zur Kodierung verwendete Software/Hardware und Einstellungen"),
+    ("Subtitle/Description refinement", "Untertitel/Beschreibungspräzisierung"),
+    ("Synchronized lyric/text", "Synchronisierter Liedtext/Text"),
+    ("Synchronized tempo codes", "Synchronisierte Tempocodes"),
+    ("Table of contents frame", "Inhaltsverzeichnis-Frame"),
+    ("Tagging time", "Tagging-Zeit"),
+    ("Terms of use", "Nutzungsbedingungen"),
+    ("Time", "Uhrzeit"),
+    ("Title sort order", "Sortierreihenfolge Titel"),
+    ("Title/songname/content description", "Titel/Songname/Inhaltsbeschreibung"),
+    ("Track number/Position in set", "Titelnummer/Position in der Sammlung"),
+    ("Unique file identifier", "Eindeutige Dateikennung"),
+    ("Unknown frame type", "Unbekannter Frame-Typ"),
+    ("Unsychronized lyric/text transcription", "Nicht synchronisierter Liedtext/Transkription"),
+    ("User defined URL link frame", "Benutzerdefinierter URL-Link-Frame"),
+    ("User defined text information frame", "Benutzerdefinierter Textinformations-Frame"),
+    ("Year", "Jahr"),
+    // ISOBMFF box descriptions
+    ("3GPP Timed Text", "3GPP-Zeitgesteuerter Text"),
+    ("A-law Audio", "A-law-Audio"),
+    ("AC-3 Audio (Dolby Digital)", "AC-3-Audio (Dolby Digital)"),
+    ("AC-3 Specific Box", "AC-3-spezifische Box"),
+    ("AMR Narrow-Band Audio", "AMR-Schmalband-Audio"),
+    ("AMR Wide-Band Audio", "AMR-Breitband-Audio"),
+    ("AMR Wide-Band+ Audio", "AMR-Breitband+-Audio"),
+    ("AV1 Configuration", "AV1-Konfiguration"),
+    ("AV1 Video", "AV1-Video"),
+    ("AVC Configuration", "AVC-Konfiguration"),
+    ("AVC/H.264 Video (no parameter sets)", "AVC/H.264-Video (ohne Parametersätze)"),
+    ("AVC/H.264 Video (parameter sets in-band)", "AVC/H.264-Video (Parametersätze im Datenstrom)"),
+    ("AVC/H.264 Video (parameter sets in-band, no SPS/PPS)", "AVC/H.264-Video (Parametersätze im Datenstrom, ohne SPS/PPS)"),
+    ("AVC/H.264 Video", "AVC/H.264-Video"),
+    ("Album (iTunes)", "Album (iTunes)"),
+    ("Album Artist (iTunes)", "Albuminterpret (iTunes)"),
+    ("Apple Lossless Audio", "Apple-Lossless-Audio"),
+    ("Apple Store Account (iTunes)", "Apple-Store-Konto (iTunes)"),
+    ("Artist (iTunes)", "Interpret (iTunes)"),
+    ("Binary XML Metadata", "Binäre XML-Metadaten"),
+    ("Bit Rate", "Bitrate"),
+    ("CEA-608 Closed Captions", "CEA-608-Untertitel"),
+    ("CEA-708 Closed Captions", "CEA-708-Untertitel"),
+    ("Category", "Kategorie"),
+    ("Chapter Track Reference", "Kapitelspur-Referenz"),
+    ("Chunk Offset (32-bit)", "Chunk-Offset (32 Bit)"),
+    ("Chunk Offset (64-bit)", "Chunk-Offset (64 Bit)"),
+    ("Clean Aperture", "Saubere Apertur"),
+    ("Clipping Region", "Beschnittbereich"),
+    ("Clipping", "Beschnitt"),
+    ("Color Information", "Farbinformationen"),
+    ("Comment (iTunes)", "Kommentar (iTunes)"),
+    ("Compact Sample Sizes", "Kompakte Sample-Größen"),
+    ("Compilation (iTunes)", "Kompilation (iTunes)"),
+    ("Composer (iTunes)", "Komponist (iTunes)"),
+    ("Composition Time-to-Sample", "Kompositionszeit-zu-Sample"),
+    ("Compressed Matte", "Komprimierte Maske"),
+    ("Content Description Track Reference", "Inhaltsbeschreibungsspur-Referenz"),
+    ("Content Light Level", "Inhalts-Lichtpegel"),
+    ("Copyright (iTunes)", "Copyright (iTunes)"),
+    ("Copyright", "Copyright"),
+    ("Cover Art (iTunes)", "Cover (iTunes)"),
+    ("Custom iTunes Metadata", "Benutzerdefinierte iTunes-Metadaten"),
+    ("DTS Coherent Acoustics", "DTS Coherent Acoustics"),
+    ("DTS Express", "DTS Express"),
+    ("DTS-HD High Resolution", "DTS-HD High Resolution"),
+    ("DTS-HD Master Audio", "DTS-HD Master Audio"),
+    ("Data Entry URL", "Dateneintrag URL"),
+    ("Data Entry URN", "Dateneintrag URN"),
+    ("Data Information", "Dateninformationen"),
+    ("Data Reference", "Datenreferenz"),
+    ("Description", "Beschreibung"),
+    ("Director (iTunes)", "Regisseur (iTunes)"),
+    ("Disk Number (iTunes)", "Disc-Nummer (iTunes)"),
+    ("Dolby Vision H.265 (profile 8)", "Dolby Vision H.265 (Profil 8)"),
+    ("Dolby Vision H.265", "Dolby Vision H.265"),
+    ("Edit Date 1 (iTunes)", "Bearbeitungsdatum 1 (iTunes)"),
+    ("Edit Date 2 (iTunes)", "Bearbeitungsdatum 2 (iTunes)"),
+    ("Edit Date 3 (iTunes)", "Bearbeitungsdatum 3 (iTunes)"),
+    ("Edit List Container", "Schnittlisten-Container"),
+    ("Edit List", "Schnittliste"),
+    ("Encoding Tool (iTunes)", "Kodierwerkzeug (iTunes)"),
+    ("Encrypted Audio Sample Entry", "Verschlüsselter Audio-Sample-Eintrag"),
+    ("Encrypted Text Sample Entry", "Verschlüsselter Text-Sample-Eintrag"),
+    ("Encrypted Video Sample Entry", "Verschlüsselter Video-Sample-Eintrag"),
+    ("Enhanced AC-3 Audio (Dolby Digital Plus)", "Enhanced-AC-3-Audio (Dolby Digital Plus)"),
+    ("Enhanced AC-3 Specific Box", "Enhanced-AC-3-spezifische Box"),
+    ("Episode Global Unique ID", "Global eindeutige Episoden-ID"),
+    ("Event Message", "Ereignisnachricht"),
+    ("FLAC Audio", "FLAC-Audio"),
+    ("Field/Frame Information", "Halbbild-/Vollbildinformationen"),
+    ("File Type and Compatibility", "Dateityp und Kompatibilität"),
+    ("Format (iTunes)", "Format (iTunes)"),
+    ("Free Space", "Freier Speicher"),
+    ("Gapless Playback (iTunes)", "Lückenlose Wiedergabe (iTunes)"),
+    ("Genre (iTunes old)", "Genre (iTunes, alt)"),
+    ("Genre (iTunes)", "Genre (iTunes)"),
+    ("Group List", "Gruppenliste"),
+    ("Grouping (iTunes)", "Gruppierung (iTunes)"),
+    ("H.263 Video", "H.263-Video"),
+    ("HD Video (iTunes)", "HD-Video (iTunes)"),
+    ("HEVC Configuration", "HEVC-Konfiguration"),
+    ("HEVC/H.265 Video (parameter sets in-band)", "HEVC/H.265-Video (Parametersätze im Datenstrom)"),
+    ("HEVC/H.265 Video", "HEVC/H.265-Video"),
+    ("Handler Reference", "Handler-Referenz"),
+    ("Hint Media Header", "Hint-Medien-Header"),
+    ("Image Spatial Extents", "Räumliche Bildausdehnung"),
+    ("Information (iTunes)", "Informationen (iTunes)"),
+    ("Initial Object Descriptor", "Initialer Objektdeskriptor"),
+    ("Item Data", "Elementdaten"),
+    ("Item Information", "Elementinformationen"),
+    ("Item Information Entry", "Elementinformationseintrag"),
+    ("Item Location", "Elementposition"),
+    ("Item Properties", "Elementeigenschaften"),
+    ("Item Property Association", "Zuordnung der Elementeigenschaften"),
+    ("Item Property Container", "Container der Elementeigenschaften"),
+    ("Item Protection", "Elementschutz"),
+    ("Item Reference", "Elementreferenz"),
+    ("Keywords", "Schlüsselwörter"),
+    ("Level Assignment", "Ebenenzuordnung"),
+    ("Linear PCM", "Lineares PCM"),
+    ("Long Description", "Lange Beschreibung"),
+    ("Lyrics (iTunes)", "Liedtext (iTunes)"),
+    ("MPEG-1/2 Audio Layer III", "MPEG-1/2 Audio Layer III"),
+    ("MPEG-4 Audio (AAC)", "MPEG-4-Audio (AAC)"),
+    ("MPEG-4 Elementary Stream Descriptor", "MPEG-4-Elementarstrom-Deskriptor"),
+    ("MPEG-4 Visual", "MPEG-4 Visual"),
+    ("Mastering Display Color Volume", "Farbvolumen des Mastering-Displays"),
+    ("Matte", "Maske"),
+    ("Media Container", "Medien-Container"),
+    ("Media Data", "Mediendaten"),
+    ("Media Header", "Medien-Header"),
+    ("Media Information", "Medieninformationen"),
+    ("Media Type (iTunes)", "Medientyp (iTunes)"),
+    ("Metadata Container", "Metadaten-Container"),
+    ("Metadata Item Keys", "Schlüssel der Metadatenelemente"),
+    ("Metadata Text", "Metadaten-Text"),
+    ("Metadata XML", "Metadaten-XML"),
+    ("Motion JPEG 2000", "Motion JPEG 2000"),
+    ("Movie Extends Header", "Movie-Extends-Header"),
+    ("Movie Extends", "Movie-Erweiterungen"),
+    ("Movie Fragment Header", "Movie-Fragment-Header"),
+    ("Movie Fragment Random Access Offset", "Offset für wahlfreien Zugriff auf Movie-Fragmente"),
+    ("Movie Fragment Random Access", "Wahlfreier Zugriff auf Movie-Fragmente"),
+    ("Movie Fragment", "Movie-Fragment"),
+    ("Movie Header", "Movie-Header"),
+    ("Movie Metadata Container", "Movie-Metadaten-Container"),
+    ("Name (iTunes)", "Name (iTunes)"),
+    ("Name", "Name"),
+    ("Non-Primary Source", "Nicht-primäre Quelle"),
+    ("Null Media Header", "Null-Medien-Header"),
+    ("Opus Audio", "Opus-Audio"),
+    ("Original Format", "Ursprüngliches Format"),
+    ("PCM Signed Big-Endian", "PCM vorzeichenbehaftet Big-Endian"),
+    ("PCM Signed Little-Endian", "PCM vorzeichenbehaftet Little-Endian"),
+    ("PCM Uncompressed", "PCM unkomprimiert"),
+    ("Padding Bits", "Füllbits"),
+    ("Performers (iTunes)", "Mitwirkende (iTunes)"),
+    ("Pixel Aspect Ratio", "Pixel-Seitenverhältnis"),
+    ("Pixel Information", "Pixelinformationen"),
+    ("Podcast (iTunes)", "Podcast (iTunes)"),
+    ("Podcast URL", "Podcast-URL"),
+    ("Preview", "Vorschau"),
+    ("Primary Item", "Primäres Element"),
+    ("Producer (iTunes)", "Produzent (iTunes)"),
+    ("Producer Reference Time", "Referenzzeit des Erzeugers"),
+    ("Progressive Download Information", "Informationen zum progressiven Download"),
+    ("Protection Scheme Information", "Informationen zum Schutzschema"),
+    ("QuickTime Text", "QuickTime-Text"),
+    ("QuickTime Wide Atom (deprecated)", "QuickTime-Wide-Atom (veraltet)"),
+    ("Rating (iTunes)", "Altersfreigabe (iTunes)"),
+    ("Requirements (iTunes)", "Anforderungen (iTunes)"),
+    ("Restricted Scheme Information", "Informationen zum eingeschränkten Schema"),
+    ("Sample Degradation Priority", "Sample-Degradationspriorität"),
+    ("Sample Dependency", "Sample-Abhängigkeit"),
+    ("Sample Description", "Sample-Beschreibung"),
+    ("Sample Group Description", "Sample-Gruppenbeschreibung"),
+    ("Sample Sizes", "Sample-Größen"),
+    ("Sample Table", "Sample-Tabelle"),
+    ("Sample-to-Chunk", "Sample-zu-Chunk"),
+    ("Sample-to-Group", "Sample-zu-Gruppe"),
+    ("Scheme Information", "Schema-Informationen"),
+    ("Scheme Type", "Schema-Typ"),
+    ("Script Track Reference", "Skriptspur-Referenz"),
+    ("Segment Index", "Segmentindex"),
+    ("Segment Type", "Segmenttyp"),
+    ("Shadow Sync Sample", "Schatten-Sync-Sample"),
+    ("Short Description", "Kurzbeschreibung"),
+    ("Software (iTunes)", "Software (iTunes)"),
+    ("Sound Media Header", "Audio-Medien-Header"),
+    ("Source (iTunes)", "Quelle (iTunes)"),
+    ("Sub-Sample Information", "Sub-Sample-Informationen"),
+    ("Sync Sample Table", "Sync-Sample-Tabelle"),
+    ("Sync Track Reference", "Sync-Spur-Referenz"),
+    ("TV Episode (iTunes)", "TV-Episode (iTunes)"),
+    ("TV Episode Number (iTunes)", "TV-Episodennummer (iTunes)"),
+    ("TV Network Name (iTunes)", "TV-Sendername (iTunes)"),
+    ("TV Season (iTunes)", "TV-Staffel (iTunes)"),
+    ("TV Show Name (iTunes)", "TV-Sendungsname (iTunes)"),
+    ("Tempo (iTunes)", "Tempo (iTunes)"),
+    ("Time-to-Sample", "Zeit-zu-Sample"),
+    ("Timecode Track Reference", "Timecode-Spur-Referenz"),
+    ("Track Container", "Spur-Container"),
+    ("Track Extends Defaults", "Standardwerte für Spurerweiterungen"),
+    ("Track Fragment Decode Time", "Dekodierzeit des Spurfragments"),
+    ("Track Fragment Header", "Spurfragment-Header"),
+    ("Track Fragment Random Access", "Wahlfreier Zugriff auf Spurfragmente"),
+    ("Track Fragment Run", "Spurfragment-Lauf"),
+    ("Track Fragment", "Spurfragment"),
+    ("Track Grouping", "Spurgruppierung"),
+    ("Track Header", "Spur-Header"),
+    ("Track Input Map", "Spur-Eingabezuordnung"),
+    ("Track Load Settings", "Spur-Ladeeinstellungen"),
+    ("Track Number (iTunes)", "Titelnummer (iTunes)"),
+    ("Track Reference", "Spurreferenz"),
+    ("URI Metadata", "URI-Metadaten"),
+    ("User Data", "Benutzerdaten"),
+    ("User Extension (UUID)", "Benutzererweiterung (UUID)"),
+    ("VC-1 Configuration", "VC-1-Konfiguration"),
+    ("VP Codec Configuration", "VP-Codec-Konfiguration"),
+    ("VP8 Video", "VP8-Video"),
+    ("VP9 Video", "VP9-Video"),
+    ("Video Media Header", "Video-Medien-Header"),
+    ("WebVTT Subtitle", "WebVTT-Untertitel"),
+    ("XML Metadata", "XML-Metadaten"),
+    ("XML Subtitle", "XML-Untertitel"),
+    ("Year (iTunes)", "Jahr (iTunes)"),
+    ("iTunes Metadata Data", "iTunes-Metadaten-Daten"),
+    ("iTunes Metadata List", "iTunes-Metadatenliste"),
+    ("iTunes Metadata Mean", "iTunes-Metadaten-Namensraum"),
+    ("μ-law Audio", "μ-law-Audio"),
+    // ID3v2 frame fields
+    ("Extended header flag set, parsing...", "Flag für erweiterten Header gesetzt, wird ausgewertet..."),
+    ("Frame data starts at offset: {}", "Frame-Daten beginnen bei Offset: {}"),
+    ("Frame '{}' has zero size, skipping", "Frame '{}' hat die Größe null, wird übersprungen"),
+    ("Frame '{}' size ({} bytes) exceeds remaining buffer, stopping", "Größe von Frame '{}' ({} Bytes) überschreitet den verbleibenden Puffer, Abbruch"),
+    ("Flags: Top-level TOC", "Flags: Oberstes Inhaltsverzeichnis"),
+    ("Flags: Ordered", "Flags: Geordnet"),
+    ("WARNING: Failed to parse frame, showing raw info", "WARNUNG: Frame konnte nicht ausgewertet werden, Rohdaten werden angezeigt"),
+    ("Raw data preview: ", "Rohdatenvorschau: "),
+    ("Extended header size: {} bytes", "Größe des erweiterten Headers: {} Bytes"),
+    ("Padding size: {} bytes", "Größe des Paddings: {} Bytes"),
+    ("CRC-32: {} (computed {}, {})", "CRC-32: {} (berechnet {}, {})"),
+    ("Tag restrictions: {}", "Tag-Einschränkungen: {}"),
+    ("Tag size: {}", "Tag-Größe: {}"),
+    ("Text encoding: {}", "Textkodierung: {}"),
+    ("Text fields size: {}", "Größe der Textfelder: {}"),
+    ("Image encoding: {}", "Bildkodierung: {}"),
+    ("Image size: {}", "Bildgröße: {}"),
+    ("- Text: \"{}\"", "- Text: \"{}\""),
+    ("- URL: \"{}\"", "- URL: \"{}\""),
+    ("{} embedded sub-frame(s)", "{} eingebettete(r) Unter-Frame(s)"),
+    ("MIME type: {}", "MIME-Typ: {}"),
+    ("Picture type: {} ({})", "Bildtyp: {} ({})"),
+    ("Data size: {} bytes", "Datengröße: {} Bytes"),
+    ("Element ID: \"{}\"", "Element-ID: \"{}\""),
+    ("Time: {} - {} (duration: {})", "Zeit: {} - {} (Dauer: {})"),
+    ("Byte offsets: {} - {}", "Byte-Offsets: {} - {}"),
+    ("Sub-frames: {} embedded frame(s)", "Unter-Frames: {} eingebettete(r) Frame(s)"),
+    ("Frame: {} - Size: {} bytes", "Frame: {} - Größe: {} Bytes"),
+    ("Text: \"{}\"", "Text: \"{}\""),
+    ("Language: \"{}\"", "Sprache: \"{}\""),
+    ("Price: {}", "Preis: {}"),
+    ("Valid until: {}", "Gültig bis: {}"),
+    ("Contact URL: \"{}\"", "Kontakt-URL: \"{}\""),
+    ("Received as: {} ({})", "Erhalten als: {} ({})"),
+    ("Seller: \"{}\"", "Verkäufer: \"{}\""),
+    ("Seller logo: {}, {} bytes", "Verkäuferlogo: {}, {} Bytes"),
+    ("MIME type: \"{}\"", "MIME-Typ: \"{}\""),
+    ("Filename: \"{}\"", "Dateiname: \"{}\""),
+    ("Object: {} bytes", "Objekt: {} Bytes"),
+    ("Undecodable Serato data: {}", "Nicht dekodierbare Serato-Daten: {}"),
+    ("Interpolation: {} ({})", "Interpolation: {} ({})"),
+    ("{} Hz: {} dB", "{} Hz: {} dB"),
+    ("... {} more points", "... {} weitere Punkte"),
+    ("Tracks: {}-{}", "Titel: {}-{}"),
+    ("Track {}: offset {} frames, length {}{}", "Titel {}: Offset {} Frames, Länge {}{}"),
+    ("Lead-out: offset {} frames", "Lead-out: Offset {} Frames"),
+    ("Disc Length: {}", "Disc-Länge: {}"),
+    ("FreeDB Disc ID: {}", "FreeDB-Disc-ID: {}"),
+    ("Price paid: {}", "Bezahlter Preis: {}"),
+    ("Date of purchase: {}", "Kaufdatum: {}"),
+    ("Private data: {} bytes", "Private Daten: {} Bytes"),
+    ("Undecodable Traktor data: {}", "Nicht dekodierbare Traktor-Daten: {}"),
+    ("MPEG-TS Timestamp: {}", "MPEG-TS-Zeitstempel: {}"),
+    ("Identification: \"{}\"", "Kennung: \"{}\""),
+    ("{}: {} dB", "{}: {} dB"),
+    (", peak {} ({} bits)", ", Spitze {} ({} Bit)"),
+    ("Flags: Top-level: {}, Ordered: {}", "Flags: Oberste Ebene: {}, Geordnet: {}"),
+    ("Child elements ({}): ", "Untergeordnete Elemente ({}): "),
+    ("Values ({} strings):", "Werte ({} Zeichenketten):"),
+    ("Owner: \"{}\"", "Besitzer: \"{}\""),
+    ("Identifier: \"{}\" ({})", "Kennung: \"{}\" ({})"),
+    ("Identifier: {} bytes", "Kennung: {} Bytes"),
+    ("Identifier: {}", "Kennung: {}"),
+    ("Chromaprint: algorithm {}, {} values ({} s of audio)", "Chromaprint: Algorithmus {}, {} Werte ({} s Audio)"),
+    ("Encoding: {}", "Kodierung: {}"),
+    ("Description: \"{}\"", "Beschreibung: \"{}\""),
+    ("URL: \"{}\"", "URL: \"{}\""),
+    ("Raw header bytes: {}", "Rohe Header-Bytes: {}"),
+    ("Size bytes: {}", "Größen-Bytes: {}"),
+    ("WARNING: Size byte {} ({}) violates synchsafe format (MSB set)!", "WARNUNG: Größen-Byte {} ({}) verletzt das Synchsafe-Format (MSB gesetzt)!"),
+    ("ERROR: Invalid synchsafe format detected in size field", "FEHLER: Ungültiges Synchsafe-Format im Größenfeld erkannt"),
+    ("Frame offset {}, ID: {} = \"{}\", Size: {} = {}, Flags: {}", "Frame-Offset {}, ID: {} = \"{}\", Größe: {} = {}, Flags: {}"),
+    ("ID: {} = \"{}\", Size: {} = {}, Flags: {}", "ID: {} = \"{}\", Größe: {} = {}, Flags: {}"),
+    // DJ software metadata
+    ("to {}", "bis {}"),
+    (", color #{}", ", Farbe #{}"),
+    ("(locked)", "(gesperrt)"),
+    ("Track color: #{}", "Titelfarbe: #{}"),
+    ("BPM lock: yes", "BPM-Sperre: ja"),
+    ("BPM lock: no", "BPM-Sperre: nein"),
+    ("Cue points: {}", "Cue-Punkte: {}"),
+    ("Other entries: {}", "Weitere Einträge: {}"),
+    ("Beat markers: {}", "Beat-Marker: {}"),
+    ("Marker at {}, {} beats to the next marker", "Marker bei {}, {} Beats bis zum nächsten Marker"),
+    ("Marker at {}, {} BPM", "Marker bei {}, {} BPM"),
+    ("Traktor cue points: {}", "Traktor-Cue-Punkte: {}"),
+    // ISOBMFF box fields
+    ("Vendor: '{}'", "Hersteller: '{}'"),
+    ("Decoder Version: {}", "Decoder-Version: {}"),
+    ("Mode Set: {} ({})", "Modus-Satz: {} ({})"),
+    ("Mode Change Period: {}", "Modus-Wechselperiode: {}"),
+    ("Frames per Sample: {}", "Frames pro Sample: {}"),
+    ("Level: {} ({} tier)", "Level: {} (Tier {})"),
+    ("Chroma Format: {}, sample position {}", "Chroma-Format: {}, Sample-Position {}"),
+    ("Bit Depth: {}", "Bittiefe: {}"),
+    ("Initial Presentation Delay: {} frames", "Anfängliche Präsentationsverzögerung: {} Frames"),
+    ("Config OBUs: {} bytes", "Konfigurations-OBUs: {} Bytes"),
+    ("Configuration Version: {}", "Konfigurationsversion: {}"),
+    ("Profile: {} ({})", "Profil: {} ({})"),
+    ("Profile Compatibility: {}", "Profilkompatibilität: {}"),
+    ("Level: {}.{}", "Level: {}.{}"),
+    ("Chroma Format: {}", "Chroma-Format: {}"),
+    ("Bit Depth: {} (luma), {} (chroma)", "Bittiefe: {} (Luma), {} (Chroma)"),
+    ("NAL Length Size: {} bytes", "NAL-Längenfeld: {} Bytes"),
+    ("SPS {}: {} bytes", "SPS {}: {} Bytes"),
+    ("PPS {}: {} bytes", "PPS {}: {} Bytes"),
+    ("Chapter Track IDs: {}", "Kapitelspur-IDs: {}"),
+    ("Location: {}", "Ort: {}"),
+    ("(data in same file)", "(Daten in derselben Datei)"),
+    ("Entry Count: {} edit list entries", "Anzahl Einträge: {} Edit-List-Einträge"),
+    ("Entry {}: Segment Duration: {}, Media Time: {}, Media Rate: {}{}", "Eintrag {}: Segmentdauer: {}, Medienzeit: {}, Medienrate: {}{}"),
+    ("Parsed Entries: {} (box truncated)", "Ausgewertete Einträge: {} (Box abgeschnitten)"),
+    ("ES ID: {}", "ES-ID: {}"),
+    ("Object Type: {} ({})", "Objekttyp: {} ({})"),
+    ("Stream Type: {} ({})", "Stream-Typ: {} ({})"),
+    ("Buffer Size: {} bytes", "Puffergröße: {} Bytes"),
+    ("Max Bitrate: {} bit/s", "Maximale Bitrate: {} bit/s"),
+    ("Average Bitrate: {} bit/s", "Durchschnittliche Bitrate: {} bit/s"),
+    ("Audio Object Type: {} ({})", "Audio-Objekttyp: {} ({})"),
+    ("Output Sample Rate: {} Hz", "Ausgabe-Abtastrate: {} Hz"),
+    ("Channel Configuration: {}", "Kanalkonfiguration: {}"),
+    ("Decoder Specific Info: {} bytes", "Decoder-spezifische Informationen: {} Bytes"),
+    ("Scheme ID URI: {}", "Schema-ID-URI: {}"),
+    ("Presentation Time Delta: {}", "Präsentationszeit-Delta: {}"),
+    ("Presentation Time: {}", "Präsentationszeit: {}"),
+    ("Event Duration: unknown", "Ereignisdauer: unbekannt"),
+    ("Event Duration: {}", "Ereignisdauer: {}"),
+    ("ID: {}", "ID: {}"),
+    ("Message Data: {} bytes", "Nachrichtendaten: {} Bytes"),
+    ("ID3v2.{} Tag: {} frames", "ID3v2.{}-Tag: {} Frames"),
+    ("Message: \"{}\"", "Nachricht: \"{}\""),
+    ("Flags: {} ({})", "Flags: {} ({})"),
+    ("Reference Track ID: {}", "Referenzspur-ID: {}"),
+    ("NTP Timestamp: {} ({})", "NTP-Zeitstempel: {} ({})"),
+    ("NTP Timestamp: {}", "NTP-Zeitstempel: {}"),
+    ("Media Time: {}", "Medienzeit: {}"),
+    ("Major Brand: '{}'", "Hauptmarke: '{}'"),
+    ("Minor Version: {}", "Nebenversion: {}"),
+    ("Compatible Brands: ", "Kompatible Marken: "),
+    ("Handler Type: '{}' ({})", "Handler-Typ: '{}' ({})"),
+    ("Manufacturer: '{}'", "Hersteller: '{}'"),
+    ("Name: \"{}\"", "Name: \"{}\""),
+    ("Profile: {} ({}), Profile Space: {}", "Profil: {} ({}), Profilraum: {}"),
+    ("Average Frame Rate: {} fps", "Durchschnittliche Bildrate: {} fps"),
+    ("{} {}: {} bytes", "{} {}: {} Bytes"),
+    ("Item Count: {}", "Anzahl Elemente: {}"),
+    ("Item {}: {} bytes in {} extent(s) ({}): {}", "Element {}: {} Bytes in {} Bereich(en) ({}): {}"),
+    ("... {} more items", "... {} weitere Elemente"),
+    ("Item ID: {}", "Element-ID: {}"),
+    ("Item Type: '{}'", "Elementtyp: '{}'"),
+    ("Item Type: '{}' ({})", "Elementtyp: '{}' ({})"),
+    ("Item Name: \"{}\"", "Elementname: \"{}\""),
+    ("Content Type: {}", "Inhaltstyp: {}"),
+    ("URI Type: {}", "URI-Typ: {}"),
+    ("Protection Index: {}", "Schutzindex: {}"),
+    ("Hidden: true", "Versteckt: ja"),
+    ("Primary Item ID: {}", "Primäre Element-ID: {}"),
+    ("'{}'{}: item {} -> {}", "'{}'{}: Element {} -> {}"),
+    ("... {} more references", "... {} weitere Referenzen"),
+    ("Item {}: properties {} (! = essential)", "Element {}: Eigenschaften {} (! = wesentlich)"),
+    ("... {} more entries", "... {} weitere Einträge"),
+    ("Image Size: {}x{}", "Bildgröße: {}x{}"),
+    ("Channels: {} ({} bits per channel)", "Kanäle: {} ({} Bit pro Kanal)"),
+    ("Creation Time: {}", "Erstellungszeit: {}"),
+    ("Modification Time: {}", "Änderungszeit: {}"),
+    ("Timescale: {} units/second", "Zeitskala: {} Einheiten/Sekunde"),
+    ("Duration: {} units ({} seconds)", "Dauer: {} Einheiten ({} Sekunden)"),
+    ("Language: {} ({})", "Sprache: {} ({})"),
+    ("Language: {}", "Sprache: {}"),
+    ("Graphics Mode: {}", "Grafikmodus: {}"),
+    ("OpColor: R={}, G={}, B={}", "OpColor: R={}, G={}, B={}"),
+    ("Balance: {} (0=center, -1=full left, 1=full right)", "Balance: {} (0=Mitte, -1=ganz links, 1=ganz rechts)"),
+    ("Namespace: {}", "Namensraum: {}"),
+    ("Name: {}", "Name: {}"),
+    ("Key {}: '{}' {}", "Schlüssel {}: '{}' {}"),
+    ("Sequence Number: {}", "Sequenznummer: {}"),
+    ("Default Sample Description Index: {}", "Standard-Sample-Beschreibungsindex: {}"),
+    ("Default Sample Duration: {}", "Standard-Sample-Dauer: {}"),
+    ("Default Sample Size: {} bytes", "Standard-Sample-Größe: {} Bytes"),
+    ("Default Sample Flags: {}", "Standard-Sample-Flags: {}"),
+    ("Base Data Offset: {}", "Basis-Datenoffset: {}"),
+    ("Base Data Offset: start of moof (default-base-is-moof)", "Basis-Datenoffset: Beginn von moof (default-base-is-moof)"),
+    ("Sample Description Index: {}", "Sample-Beschreibungsindex: {}"),
+    ("Duration Is Empty: true", "Dauer ist leer: ja"),
+    ("Base Media Decode Time: {}", "Basis-Mediendekodierzeit: {}"),
+    ("Data Offset: {}", "Datenoffset: {}"),
+    ("First Sample Flags: {}", "Flags des ersten Samples: {}"),
+    ("Total Duration: {}", "Gesamtdauer: {}"),
+    ("Total Duration: {} from the default sample duration {}", "Gesamtdauer: {} aus der Standard-Sample-Dauer {}"),
+    ("Total Size: {} bytes", "Gesamtgröße: {} Bytes"),
+    ("Total Size: {} bytes from the default sample size {}", "Gesamtgröße: {} Bytes aus der Standard-Sample-Größe {}"),
+    ("Sample {}: {}", "Sample {}: {}"),
+    ("Parsed Samples: {} (box truncated)", "Ausgewertete Samples: {} (Box abgeschnitten)"),
+    ("Preferred Rate: {}", "Bevorzugte Rate: {}"),
+    ("Preferred Volume: {}", "Bevorzugte Lautstärke: {}"),
+    ("System ID: {} ({})", "System-ID: {} ({})"),
+    ("KID: {}", "KID: {}"),
+    ("Data Size: {} bytes", "Datengröße: {} Bytes"),
+    ("Protected: {}", "Geschützt: {}"),
+    ("Default KID: {}", "Standard-KID: {}"),
+    ("Per-Sample IV Size: 0 (constant IV {})", "IV-Größe pro Sample: 0 (konstanter IV {})"),
+    ("Per-Sample IV Size: {} bytes", "IV-Größe pro Sample: {} Bytes"),
+    ("Pattern: {} encrypted, {} skipped blocks", "Muster: {} verschlüsselte, {} übersprungene Blöcke"),
+    ("Flags: {} (Subsample encryption: {})", "Flags: {} (Subsample-Verschlüsselung: {})"),
+    ("Sample Count: {}", "Anzahl Samples: {}"),
+    (
+        "Per-Sample IV Size: unknown (entries do not fit an IV size of 0, 8 or 16 bytes)",
+        "IV-Größe pro Sample: unbekannt (die Einträge passen zu keiner IV-Größe von 0, 8 oder 16 Bytes)"
+    ),
+    ("Subsamples per Sample: {} to {}", "Subsamples pro Sample: {} bis {}"),
+    ("Clear Bytes: {}, Protected Bytes: {}", "Unverschlüsselte Bytes: {}, geschützte Bytes: {}"),
+    ("Sample {}: IV {}", "Sample {}: IV {}"),
+    ("Sample {}: IV {}, {} subsamples", "Sample {}: IV {}, {} Subsamples"),
+    ("... {} more samples", "... {} weitere Samples"),
+    ("Auxiliary Information Type: '{}'", "Typ der Zusatzinformationen: '{}'"),
+    ("Sample Info Sizes: {} to {} bytes", "Größen der Sample-Informationen: {} bis {} Bytes"),
+    ("Sample Info Size: {} bytes (constant)", "Größe der Sample-Informationen: {} Bytes (konstant)"),
+    ("Offsets ({}): {}{}", "Offsets ({}): {}{}"),
+    ("Scheme: '{}' ({})", "Schema: '{}' ({})"),
+    ("Scheme Version: {}", "Schemaversion: {}"),
+    ("Scheme URI: {}", "Schema-URI: {}"),
+    ("Original Format: '{}'", "Ursprüngliches Format: '{}'"),
+    ("Track Encryption (tenc):", "Spurverschlüsselung (tenc):"),
+    ("Pixel Aspect Ratio: {}:{}", "Pixel-Seitenverhältnis: {}:{}"),
+    ("Colour Type: {}", "Farbtyp: {}"),
+    ("Colour Primaries: {} ({})", "Farbprimärvalenzen: {} ({})"),
+    ("Transfer Characteristics: {} ({})", "Übertragungskennlinie: {} ({})"),
+    ("Matrix Coefficients: {} ({})", "Matrixkoeffizienten: {} ({})"),
+    ("Full Range: {}", "Voller Wertebereich: {}"),
+    ("ICC Profile: {} bytes", "ICC-Profil: {} Bytes"),
+    ("Data Reference Index: {}", "Datenreferenzindex: {}"),
+    ("Dimensions: {}x{}", "Abmessungen: {}x{}"),
+    ("Resolution: {} x {} dpi", "Auflösung: {} x {} dpi"),
+    ("Frame Count: {}", "Anzahl Frames: {}"),
+    ("Compressor Name: {}", "Kompressorname: {}"),
+    ("Depth: {}", "Tiefe: {}"),
+    ("Sound Description Version: {}", "Version der Tonbeschreibung: {}"),
+    ("Sample Size: {} bits", "Sample-Größe: {} Bit"),
+    ("'{}' ({} bytes)", "'{}' ({} Bytes)"),
+    ("roll distance {} samples", "Roll-Distanz {} Samples"),
+    ("{} leading samples", "{} führende Samples"),
+    ("leading samples unknown", "führende Samples unbekannt"),
+    ("protected, KID {}", "geschützt, KID {}"),
+    ("not protected", "nicht geschützt"),
+    (", per-sample IV size {}", ", IV-Größe pro Sample {}"),
+    (", constant IV {}", ", konstanter IV {}"),
+    (", pattern {}:{}", ", Muster {}:{}"),
+    ("{} bytes: {}", "{} Bytes: {}"),
+    ("Grouping Type: '{}' ({})", "Gruppierungstyp: '{}' ({})"),
+    ("Default Group Description Index: {}", "Standard-Gruppenbeschreibungsindex: {}"),
+    ("Group {}: {}", "Gruppe {}: {}"),
+    ("... {} more groups", "... {} weitere Gruppen"),
+    ("Parsed Entries: {} (entry size unknown or table truncated)", "Ausgewertete Einträge: {} (Eintragsgröße unbekannt oder Tabelle abgeschnitten)"),
+    ("Grouping Type Parameter: {}", "Parameter des Gruppierungstyps: {}"),
+    ("Samples: {} ({} in a group)", "Samples: {} ({} in einer Gruppe)"),
+    ("Samples {}-{}: {}", "Samples {}-{}: {}"),
+    ("... {} more runs", "... {} weitere Abschnitte"),
+    ("Entry Count: {}", "Anzahl Einträge: {}"),
+    ("Sample Entries: ", "Sample-Einträge: "),
+    ("Sample Entry '{}':", "Sample-Eintrag '{}':"),
+    ("Parsed Entries: {} (table truncated or limited)", "Ausgewertete Einträge: {} (Tabelle abgeschnitten oder begrenzt)"),
+    ("Entry Count: {} time-to-sample entries", "Anzahl Einträge: {} Time-to-Sample-Einträge"),
+    ("Samples: {}, total duration: {} units", "Samples: {}, Gesamtdauer: {} Einheiten"),
+    ("Entry Count: {} sample-to-chunk entries", "Anzahl Einträge: {} Sample-to-Chunk-Einträge"),
+    ("Sample Size: Variable", "Sample-Größe: variabel"),
+    ("Sample Count: {} (with individual sizes)", "Anzahl Samples: {} (mit einzelnen Größen)"),
+    ("Sample Size: {} bytes (constant)", "Sample-Größe: {} Bytes (konstant)"),
+    ("Entry Count: {} chunk offsets (32-bit)", "Anzahl Einträge: {} Chunk-Offsets (32 Bit)"),
+    ("Entry Count: {} chunk offsets (64-bit)", "Anzahl Einträge: {} Chunk-Offsets (64 Bit)"),
+    ("Entry Count: {} composition offset entries", "Anzahl Einträge: {} Kompositions-Offset-Einträge"),
+    ("Samples: {}, composition offsets: {} to {} units", "Samples: {}, Kompositions-Offsets: {} bis {} Einheiten"),
+    ("Frame Reordering: yes ({} distinct offsets)", "Frame-Umordnung: ja ({} verschiedene Offsets)"),
+    ("Entry Count: {} sync samples (keyframes)", "Anzahl Einträge: {} Sync-Samples (Keyframes)"),
+    ("Keyframes: {}{}", "Keyframes: {}{}"),
+    ("Keyframe Interval: {} to {} samples, average {} ({})", "Keyframe-Intervall: {} bis {} Samples, Durchschnitt {} ({})"),
+    ("Samples: {}", "Samples: {}"),
+    ("Independent (I): {}", "Unabhängig (I): {}"),
+    ("Dependent: {}", "Abhängig: {}"),
+    ("Disposable (not referenced): {}", "Verwerfbar (nicht referenziert): {}"),
+    ("Leading Samples: {}", "Führende Samples: {}"),
+    ("Redundant Coding: {} samples", "Redundante Kodierung: {} Samples"),
+    ("Pattern: {}{} (I independent, P referenced, b disposable)", "Muster: {}{} (I unabhängig, P referenziert, b verwerfbar)"),
+    ("Reference ID: {}", "Referenz-ID: {}"),
+    ("Earliest Presentation Time: {}", "Früheste Präsentationszeit: {}"),
+    ("First Offset: {}", "Erster Offset: {}"),
+    ("Reference Count: {}", "Anzahl Referenzen: {}"),
+    ("Total Referenced Size: {} bytes", "Referenzierte Gesamtgröße: {} Bytes"),
+    ("Reference {}: {} {} bytes, duration {}{}", "Referenz {}: {} {} Bytes, Dauer {}{}"),
+    ("Flags: {} (Track enabled: {}, In movie: {}, In preview: {})", "Flags: {} (Spur aktiviert: {}, Im Film: {}, In der Vorschau: {})"),
+    ("Track ID: {}", "Spur-ID: {}"),
+    ("Duration: {} units", "Dauer: {} Einheiten"),
+    ("Layer: {}", "Ebene: {}"),
+    ("Alternate Group: {}", "Alternativgruppe: {}"),
+    ("Volume: {}", "Lautstärke: {}"),
+    ("Width: {} pixels", "Breite: {} Pixel"),
+    ("Height: {} pixels", "Höhe: {} Pixel"),
+    ("Matrix: identity", "Matrix: Identität"),
+    ("Matrix: {}", "Matrix: {}"),
+    ("Rotation: {}° clockwise (displayed as {}x{})", "Drehung: {}° im Uhrzeigersinn (angezeigt als {}x{})"),
+    ("Rotation: {}° clockwise", "Drehung: {}° im Uhrzeigersinn"),
+    ("Mirrored: yes", "Gespiegelt: ja"),
+    ("Scale: {} x {}", "Skalierung: {} x {}"),
+    ("Translation: {}, {} pixels", "Verschiebung: {}, {} Pixel"),
+    ("UUID: {} ({})", "UUID: {} ({})"),
+    ("UUID: {}", "UUID: {}"),
+    ("... {} more lines", "... {} weitere Zeilen"),
+    ("Fragment Absolute Time: {}", "Absolute Fragmentzeit: {}"),
+    ("Fragment Duration: {}", "Fragmentdauer: {}"),
+    ("Fragment Count: {}", "Anzahl Fragmente: {}"),
+    ("Fragment {}: time {}, duration {}", "Fragment {}: Zeit {}, Dauer {}"),
+    ("{} (type {}): \"{}\"", "{} (Typ {}): \"{}\""),
+    ("Payload: {} bytes", "Nutzdaten: {} Bytes"),
+    ("Implicit", "Implizit"),
+    ("UTF-8", "UTF-8"),
+    ("UTF-16 BE", "UTF-16 BE"),
+    ("JPEG Image", "JPEG-Bild"),
+    ("PNG Image", "PNG-Bild"),
+    ("Signed Integer", "Vorzeichenbehaftete Ganzzahl"),
+    ("Unsigned Integer", "Vorzeichenlose Ganzzahl"),
+    ("Binary ({})", "Binär ({})"),
+    ("Data Type: {}", "Datentyp: {}"),
+    ("Value: \"{}\"", "Wert: \"{}\""),
+    ("Value: {}", "Wert: {}"),
+    ("Value: {} image, {} bytes", "Wert: {}-Bild, {} Bytes"),
+    ("Value: Binary data, {} bytes", "Wert: Binärdaten, {} Bytes"),
+    ("Value: Track {} of {}", "Wert: Titel {} von {}"),
+    ("Value: Track {}", "Wert: Titel {}"),
+    ("Value: Disk {} of {}", "Wert: Disc {} von {}"),
+    ("Value: Disk {}", "Wert: Disc {}"),
+    // WavPack, Musepack, TTA, AMR and ADTS diagnostics
+    ("Missing Musepack SV7 header", "Musepack-SV7-Header fehlt"),
+    ("Unsupported Musepack stream version {}", "Nicht unterstützte Musepack-Stream-Version {}"),
+    ("Musepack packet header truncated", "Musepack-Paket-Header abgeschnitten"),
+    ("Invalid Musepack packet key {} {}", "Ungültiger Musepack-Paketschlüssel {} {}"),
+    ("Musepack packet size truncated", "Musepack-Paketgröße abgeschnitten"),
+    ("Musepack packet size {} is smaller than its header", "Musepack-Paketgröße {} ist kleiner als sein Header"),
+    ("Musepack stream header truncated", "Musepack-Stream-Header abgeschnitten"),
+    ("Musepack sample count truncated", "Musepack-Sample-Anzahl abgeschnitten"),
+    ("Musepack beginning silence truncated", "Musepack-Anfangsstille abgeschnitten"),
+    ("{}, {} bytes of other data before the end of the audio", "{}, {} Bytes anderer Daten vor dem Ende des Audios"),
+    ("WavPack block truncated: {} bytes declared, {} available", "WavPack-Block abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("Block starts at sample {}, the previous block ended at sample {}", "Block beginnt bei Sample {}, der vorherige Block endete bei Sample {}"),
+    ("{} block index discontinuities in total", "Insgesamt {} Unterbrechungen des Blockindex"),
+    ("Stream declares {} samples, the blocks hold {}", "Stream gibt {} Samples an, die Blöcke enthalten {}"),
+    ("Musepack '{}' packet truncated: {} bytes declared, {} available", "Musepack-Paket '{}' abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("Stream header CRC mismatch: stored {}, computed {}", "CRC des Stream-Headers stimmt nicht: gespeichert {}, berechnet {}"),
+    ("Musepack stream version {} in an SV8 stream header", "Musepack-Stream-Version {} in einem SV8-Stream-Header"),
+    ("Musepack SV8 stream has no stream header packet", "Musepack-SV8-Stream hat kein Stream-Header-Paket"),
+    ("Musepack SV8 stream has no stream end packet (truncated or cut file)", "Musepack-SV8-Stream hat kein Stream-Ende-Paket (abgeschnittene oder gekürzte Datei)"),
+    ("{} bytes of other data behind the stream end packet", "{} Bytes anderer Daten hinter dem Stream-Ende-Paket"),
+    ("TTA header CRC mismatch: stored {}, computed {}", "CRC des TTA-Headers stimmt nicht: gespeichert {}, berechnet {}"),
+    ("TTA audio is encrypted", "TTA-Audio ist verschlüsselt"),
+    ("Unknown TTA audio format {}", "Unbekanntes TTA-Audioformat {}"),
+    ("TTA seek table of {} frames truncated", "TTA-Sprungtabelle mit {} Frames abgeschnitten"),
+    ("TTA seek table CRC mismatch: stored {}, computed {}", "CRC der TTA-Sprungtabelle stimmt nicht: gespeichert {}, berechnet {}"),
+    ("Frames of the seek table end at {}, the audio data ends at {}", "Die Frames der Sprungtabelle enden bei {}, die Audiodaten enden bei {}"),
+    ("{}, {} bytes of other data behind the last frame", "{}, {} Bytes anderer Daten hinter dem letzten Frame"),
+    ("AMR frame truncated: {} bytes expected, {} available", "AMR-Frame abgeschnitten: {} Bytes erwartet, {} vorhanden"),
+    ("Reserved AMR frame type {}", "Reservierter AMR-Frame-Typ {}"),
+    ("{} frames of reserved types in total", "Insgesamt {} Frames reservierter Typen"),
+    ("{} frames are marked as damaged (quality bit cleared)", "{} Frames sind als beschädigt markiert (Qualitätsbit gelöscht)"),
+    ("{} frames do not fill complete blocks of {} channels", "{} Frames füllen keine vollständigen Blöcke von {} Kanälen"),
+    ("ADTS frame truncated: {} bytes declared, {} available", "ADTS-Frame abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("ADTS frame changes the stream to {} at {} Hz, channel configuration {}", "ADTS-Frame ändert den Stream auf {} mit {} Hz, Kanalkonfiguration {}"),
+    ("{} frames change the stream parameters in total", "Insgesamt {} Frames ändern die Stream-Parameter"),
+    // DSD diagnostics
+    ("Sample rate chunk missing in the property chunk", "Abtastraten-Chunk fehlt im Eigenschafts-Chunk"),
+    ("Channels chunk missing in the property chunk", "Kanal-Chunk fehlt im Eigenschafts-Chunk"),
+    ("Compression type chunk missing in the property chunk", "Kompressionstyp-Chunk fehlt im Eigenschafts-Chunk"),
+    ("DSF format version {} (only version 1 is defined)", "DSF-Formatversion {} (nur Version 1 ist definiert)"),
+    ("DSF format ID {} is not DSD raw", "DSF-Format-ID {} ist nicht DSD raw"),
+    ("Channel type {} ({}) does not match the channel count {}", "Kanaltyp {} ({}) passt nicht zur Kanalanzahl {}"),
+    ("{} bits per sample (1 or 8 expected)", "{} Bit pro Sample (1 oder 8 erwartet)"),
+    ("Block size of {} bytes per channel (the specification requires {})", "Blockgröße von {} Bytes pro Kanal (die Spezifikation verlangt {})"),
+    ("DSF data chunk missing", "DSF-Daten-Chunk fehlt"),
+    ("Data chunk of {} bytes does not hold whole blocks of all {} channels", "Daten-Chunk mit {} Bytes enthält keine ganzen Blöcke aller {} Kanäle"),
+    ("Data chunk holds {} bytes, {} samples per channel need {}", "Daten-Chunk enthält {} Bytes, {} Samples pro Kanal benötigen {}"),
+    ("Metadata pointer {} lies beyond the end of the file", "Metadatenzeiger {} liegt hinter dem Dateiende"),
+    ("Metadata pointer {} does not point to an ID3v2 tag", "Metadatenzeiger {} zeigt nicht auf ein ID3v2-Tag"),
+    ("ID3v2 tag at {} does not follow the data chunk ending at {}", "ID3v2-Tag bei {} folgt nicht auf den bei {} endenden Daten-Chunk"),
+    ("Data chunk truncated: {} bytes declared, {} available", "Daten-Chunk abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("DSD chunk declares a file size of {} bytes, {} bytes present", "DSD-Chunk gibt eine Dateigröße von {} Bytes an, {} Bytes vorhanden"),
+    ("FRM8 form truncated: {} bytes declared, {} available", "FRM8-Form abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("Format version chunk is not the first chunk of the form", "Formatversions-Chunk ist nicht der erste Chunk der Form"),
+    ("DSDIFF format version {} (only version 1.x is defined)", "DSDIFF-Formatversion {} (nur Version 1.x ist definiert)"),
+    ("Property chunk missing", "Eigenschafts-Chunk fehlt"),
+    ("Sound data chunk missing", "Tondaten-Chunk fehlt"),
+    ("Compression type '{}' does not match the '{}' sound data chunk", "Kompressionstyp '{}' passt nicht zum Tondaten-Chunk '{}'"),
+    (
+        "DSD sound data of {} bytes does not hold the same samples for all {} channels",
+        "DSD-Tondaten mit {} Bytes enthalten nicht dieselben Samples für alle {} Kanäle"
+    ),
+    ("'{}' chunk of {} bytes exceeds its container by {} bytes", "Chunk '{}' mit {} Bytes überschreitet seinen Container um {} Bytes"),
+    ("Property chunk of type '{}' (expected 'SND ')", "Eigenschafts-Chunk vom Typ '{}' ('SND ' erwartet)"),
+    ("Property chunk follows the sound data chunk", "Eigenschafts-Chunk folgt auf den Tondaten-Chunk"),
+    ("Form contains more than one sound data chunk", "Form enthält mehr als einen Tondaten-Chunk"),
+    ("ID3 chunk does not contain an ID3v2 tag", "ID3-Chunk enthält kein ID3v2-Tag"),
+    ("{} bytes after the last chunk are too short for a chunk header", "{} Bytes nach dem letzten Chunk sind zu kurz für einen Chunk-Header"),
+    ("ID3v2 tag version not supported, tag skipped", "ID3v2-Tag-Version nicht unterstützt, Tag übersprungen"),
+    // Image fields and diagnostics
+    ("PNG IHDR chunk too short", "PNG-IHDR-Chunk zu kurz"),
+    ("PNG iTXt chunk too short", "PNG-iTXt-Chunk zu kurz"),
+    ("Invalid PNG signature", "Ungültige PNG-Signatur"),
+    ("Invalid TIFF header in EXIF data", "Ungültiger TIFF-Header in den EXIF-Daten"),
+    ("EXIF data too short", "EXIF-Daten zu kurz"),
+    ("JPEG frame header too short", "JPEG-Frame-Header zu kurz"),
+    ("Missing JPEG start of image marker", "JPEG-Bildanfang-Marker fehlt"),
+    ("BMP file size {} exceeds the {} bytes available", "BMP-Dateigröße {} überschreitet die {} vorhandenen Bytes"),
+    ("BMP pixel data offset {} lies beyond the end of the bitmap", "BMP-Pixeldaten-Offset {} liegt hinter dem Ende der Bitmap"),
+    ("Declared File Size: {} bytes", "Angegebene Dateigröße: {} Bytes"),
+    ("Pixel Data Offset: {}", "Pixeldaten-Offset: {}"),
+    ("DIB Header: {} ({} bytes)", "DIB-Header: {} ({} Bytes)"),
+    ("Row Order: top-down", "Zeilenreihenfolge: von oben nach unten"),
+    ("Row Order: bottom-up", "Zeilenreihenfolge: von unten nach oben"),
+    ("Compression: {}", "Kompression: {}"),
+    ("Resolution: {}x{} pixels per meter", "Auflösung: {}x{} Pixel pro Meter"),
+    ("Colors Used: {}", "Verwendete Farben: {}"),
+    ("Camera: {}", "Kamera: {}"),
+    ("Date: {}", "Datum: {}"),
+    ("GPS: {}° {}, {}° {}", "GPS: {}° {}, {}° {}"),
+    ("Tags: {}", "Tags: {}"),
+    ("Image {}x{} at {},{}, {} bytes of image data", "Bild {}x{} bei {},{}, {} Bytes Bilddaten"),
+    (", local color table with {} entries", ", lokale Farbtabelle mit {} Einträgen"),
+    (", interlaced", ", interlaced"),
+    ("Graphic Control: delay {} ms, disposal method {}", "Grafiksteuerung: Verzögerung {} ms, Entsorgungsmethode {}"),
+    (", transparent color {}", ", transparente Farbe {}"),
+    ("Application {}: loops forever", "Anwendung {}: Endlosschleife"),
+    ("Application {}: {} loops", "Anwendung {}: {} Schleifen"),
+    ("Application {}", "Anwendung {}"),
+    ("Comment: \"{}\"", "Kommentar: \"{}\""),
+    ("Plain Text Extension", "Klartext-Erweiterung"),
+    ("Extension {}", "Erweiterung {}"),
+    ("GIF image descriptor truncated", "GIF-Bildbeschreibung abgeschnitten"),
+    ("GIF image data truncated", "GIF-Bilddaten abgeschnitten"),
+    ("GIF extension {} truncated", "GIF-Erweiterung {} abgeschnitten"),
+    ("Unexpected GIF block introducer {}", "Unerwartete GIF-Blockeinleitung {}"),
+    ("GIF data stream ends without a trailer", "GIF-Datenstrom endet ohne Abschluss"),
+    ("Unknown GIF version '{}'", "Unbekannte GIF-Version '{}'"),
+    ("Version: GIF{}", "Version: GIF{}"),
+    ("Background Color Index: {}", "Hintergrundfarbindex: {}"),
+    ("Animation Duration: {} s", "Animationsdauer: {} s"),
+    ("JFIF {}.{}, density {}x{}{}", "JFIF {}.{}, Dichte {}x{}{}"),
+    (", thumbnail {}x{}", ", Vorschaubild {}x{}"),
+    ("{}  FF{}  {} {} bytes", "{}  FF{}  {} {} Bytes"),
+    ("({} bytes of scan data)", "({} Bytes Scandaten)"),
+    ("Expected JPEG marker, found byte {}", "JPEG-Marker erwartet, Byte {} gefunden"),
+    ("JPEG segment length truncated at end of data", "JPEG-Segmentlänge am Datenende abgeschnitten"),
+    ("JPEG segment FF{} declares {} bytes, {} available", "JPEG-Segment FF{} gibt {} Bytes an, {} vorhanden"),
+    ("JPEG stream ends without an end of image marker", "JPEG-Datenstrom endet ohne Bildende-Marker"),
+    ("JPEG stream has no frame header (SOFn)", "JPEG-Datenstrom hat keinen Frame-Header (SOFn)"),
+    ("Frame: {}", "Frame: {}"),
+    ("Component {}: sampling {}x{}, quantization table {}", "Komponente {}: Abtastung {}x{}, Quantisierungstabelle {}"),
+    ("Segments: {}", "Segmente: {}"),
+    ("{}  {}  {} bytes", "{}  {}  {} Bytes"),
+    ("[CRC mismatch]", "[CRC stimmt nicht]"),
+    ("Invalid PNG chunk type, chunk walk stopped", "Ungültiger PNG-Chunk-Typ, Durchlauf der Chunks abgebrochen"),
+    ("PNG chunk {} declares {} bytes, {} available", "PNG-Chunk {} gibt {} Bytes an, {} vorhanden"),
+    ("PNG chunk {} CRC mismatch: stored {}, computed {}", "CRC von PNG-Chunk {} stimmt nicht: gespeichert {}, berechnet {}"),
+    ("Unknown critical PNG chunk {}", "Unbekannter kritischer PNG-Chunk {}"),
+    ("PNG file does not start with an IHDR chunk", "PNG-Datei beginnt nicht mit einem IHDR-Chunk"),
+    ("PNG file has no image data (IDAT)", "PNG-Datei hat keine Bilddaten (IDAT)"),
+    ("PNG file ends without an IEND chunk", "PNG-Datei endet ohne IEND-Chunk"),
+    ("PNG bit depth {} is not allowed for color type {}", "PNG-Bittiefe {} ist für Farbtyp {} nicht erlaubt"),
+    ("PNG {} chunk: {}", "PNG-Chunk {}: {}"),
+    ("Chunks: {}", "Chunks: {}"),
+    ("{}  IDAT  {} chunks, {} bytes of image data", "{}  IDAT  {} Chunks, {} Bytes Bilddaten"),
+    ("Text ({}, {}): {} = \"{}\"", "Text ({}, {}): {} = \"{}\""),
+    ("Text ({}): {} = \"{}\"", "Text ({}): {} = \"{}\""),
+    // Matroska diagnostics
+    ("EBML document type '{}' is neither Matroska nor WebM", "EBML-Dokumenttyp '{}' ist weder Matroska noch WebM"),
+    ("EBML header has no DocType element", "EBML-Header hat kein DocType-Element"),
+    ("No Segment element found after the EBML header", "Kein Segment-Element nach dem EBML-Header gefunden"),
+    ("Invalid EBML element header, skipping the rest of the parent element", "Ungültiger EBML-Element-Header, der Rest des Elternelements wird übersprungen"),
+    (
+        "Element '{}' extends beyond its parent or the end of the file (size: {}, available: {})",
+        "Element '{}' reicht über sein Elternelement oder das Dateiende hinaus (Größe: {}, vorhanden: {})"
+    ),
+    // MPEG audio frame scan diagnostics
+    ("Last MPEG audio frame truncated: {} bytes declared, {} available", "Letzter MPEG-Audio-Frame abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("MPEG audio frame sync lost, {} bytes skipped before the next frame", "MPEG-Audio-Frame-Synchronisation verloren, {} Bytes bis zum nächsten Frame übersprungen"),
+    ("Frame sync lost {} times in total ({} bytes skipped)", "Frame-Synchronisation insgesamt {}-mal verloren ({} Bytes übersprungen)"),
+    ("{} bytes of other data behind the last MPEG audio frame", "{} Bytes anderer Daten hinter dem letzten MPEG-Audio-Frame"),
+    ("{} ICY metadata blocks between the frames (Shoutcast/Icecast stream dump)", "{} ICY-Metadatenblöcke zwischen den Frames (Shoutcast/Icecast-Stream-Mitschnitt)"),
+    // MPEG program stream fields and diagnostics
+    ("MPEG-2 pack header truncated", "MPEG-2-Pack-Header abgeschnitten"),
+    ("MPEG-1 pack header truncated", "MPEG-1-Pack-Header abgeschnitten"),
+    ("Unknown pack header layout", "Unbekannter Aufbau des Pack-Headers"),
+    ("System header too short", "System-Header zu kurz"),
+    ("Program stream start code missing, {} bytes skipped to the next pack", "Startcode des Programmstroms fehlt, {} Bytes bis zum nächsten Pack übersprungen"),
+    ("Program stream packs end, {} bytes of other data follow", "Packs des Programmstroms enden, {} Bytes anderer Daten folgen"),
+    ("MPEG-{} pack header in an MPEG-{} program stream", "MPEG-{}-Pack-Header in einem MPEG-{}-Programmstrom"),
+    ("SCR jumps from {} s to {} s", "SCR springt von {} s auf {} s"),
+    ("Program stream packet header truncated at end of file", "Paket-Header des Programmstroms am Dateiende abgeschnitten"),
+    ("Program stream packet truncated: {} bytes declared, {} available", "Paket des Programmstroms abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("Program stream has no end code (truncated or cut file)", "Programmstrom hat keinen Endcode (abgeschnittene oder gekürzte Datei)"),
+    ("{} SCR discontinuities in total", "Insgesamt {} SCR-Unterbrechungen"),
+    ("Program stream contains no pack header", "Programmstrom enthält keinen Pack-Header"),
+    ("PES packet of stream {} has a truncated header", "PES-Paket von Stream {} hat einen abgeschnittenen Header"),
+    ("Rate bound: {} kbit/s", "Ratengrenze: {} kbit/s"),
+    ("Audio bound: {}, video bound: {}", "Audiogrenze: {}, Videogrenze: {}"),
+    ("Stream {} ({}): buffer {} bytes", "Stream {} ({}): Puffer {} Bytes"),
+    (", {} fps", ", {} fps"),
+    // MPEG transport stream fields and diagnostics
+    ("Missing transport packet sync byte", "Sync-Byte des Transportpakets fehlt"),
+    ("Adaptation field length {} exceeds the packet", "Länge des Adaptationsfelds {} überschreitet das Paket"),
+    ("Transport packet sync lost, {} bytes skipped", "Synchronisation der Transportpakete verloren, {} Bytes übersprungen"),
+    ("Transport packets end, {} bytes of other data follow", "Transportpakete enden, {} Bytes anderer Daten folgen"),
+    ("PID {}: transport error indicator set", "PID {}: Transportfehler-Indikator gesetzt"),
+    ("PID {}: continuity counter {} follows {} ({} packets lost)", "PID {}: Kontinuitätszähler {} folgt auf {} ({} Pakete verloren)"),
+    ("PID {}: PCR jumps by {} s without discontinuity indicator", "PID {}: PCR springt um {} s ohne Unterbrechungsindikator"),
+    ("Transport packet truncated: {} of {} bytes", "Transportpaket abgeschnitten: {} von {} Bytes"),
+    ("PID {}: timed ID3 data without PES header", "PID {}: zeitgesteuerte ID3-Daten ohne PES-Header"),
+    ("PID {}: timed ID3 PES packet without ID3v2 tag", "PID {}: zeitgesteuertes ID3-PES-Paket ohne ID3v2-Tag"),
+    ("PID {}: timed ID3 tag: {}", "PID {}: zeitgesteuertes ID3-Tag: {}"),
+    ("PAT version changes from {} to {}", "PAT-Version ändert sich von {} auf {}"),
+    ("PAT: {}", "PAT: {}"),
+    ("Program {}: PMT version changes from {} to {}", "Programm {}: PMT-Version ändert sich von {} auf {}"),
+    ("PMT on PID {}: {}", "PMT auf PID {}: {}"),
+    ("Program {}: no program map table found on PID {}", "Programm {}: keine Program Map Table auf PID {} gefunden"),
+    ("No program association table (PID 0x0000) found", "Keine Program Association Table (PID 0x0000) gefunden"),
+    ("PID {}: {} continuity counter errors in total", "PID {}: insgesamt {} Fehler des Kontinuitätszählers"),
+    ("PID {}: {} packets with transport error indicator in total", "PID {}: insgesamt {} Pakete mit Transportfehler-Indikator"),
+    ("PID {}: {} scrambled packets", "PID {}: {} verschlüsselte Pakete"),
+    ("PID {}: PCR interval of up to {} ms exceeds the allowed 100 ms", "PID {}: PCR-Intervall von bis zu {} ms überschreitet die erlaubten 100 ms"),
+    ("PID {}: {} packets not referenced by any program", "PID {}: {} Pakete werden von keinem Programm referenziert"),
+    ("PID {}  ", "PID {}  "),
+    ("188-byte packets", "188-Byte-Pakete"),
+    ("192-byte packets (M2TS, 4-byte timestamp prefix)", "192-Byte-Pakete (M2TS, 4 Byte Zeitstempel-Präfix)"),
+    ("204-byte packets (16 bytes Reed-Solomon parity)", "204-Byte-Pakete (16 Bytes Reed-Solomon-Parität)"),
+    ("registration \"{}\"", "Registrierung \"{}\""),
+    ("language {}", "Sprache {}"),
+    ("descriptor {}", "Deskriptor {}"),
+    ("PID {}  type {}  {}", "PID {}  Typ {}  {}"),
+    // Ogg fields and diagnostics
+    ("Block Size: {} - {} samples", "Blockgröße: {} - {} Samples"),
+    ("Frame Size: {} - {} bytes", "Frame-Größe: {} - {} Bytes"),
+    ("Bits per Sample: {}", "Bit pro Sample: {}"),
+    ("Total Samples: {}", "Samples insgesamt: {}"),
+    ("MD5: {}", "MD5: {}"),
+    ("Mapping Version: {}.{}", "Mapping-Version: {}.{}"),
+    ("Header Packets: {}", "Header-Pakete: {}"),
+    ("Opus Version: {}", "Opus-Version: {}"),
+    ("Pre-skip: {} samples", "Vorlauf: {} Samples"),
+    ("Input Sample Rate: {} Hz", "Eingangs-Abtastrate: {} Hz"),
+    ("Output Gain: {} dB", "Ausgangsverstärkung: {} dB"),
+    ("Channel Mapping Family: {} ({})", "Kanalzuordnungsfamilie: {} ({})"),
+    ("Vorbis Version: {}", "Vorbis-Version: {}"),
+    ("Bitrate (max/nominal/min): {} / {} / {}", "Bitrate (max/nominal/min): {} / {} / {}"),
+    ("Block Sizes: {} / {}", "Blockgrößen: {} / {}"),
+    ("Ogg stream {}: {}", "Ogg-Stream {}: {}"),
+    ("Ogg pages end, {} bytes of other data follow", "Ogg-Seiten enden, {} Bytes anderer Daten folgen"),
+    ("Ogg page header truncated at end of file", "Ogg-Seiten-Header am Dateiende abgeschnitten"),
+    ("Ogg page truncated: {} bytes declared, {} available", "Ogg-Seite abgeschnitten: {} Bytes angegeben, {} vorhanden"),
+    ("Ogg page checksum mismatch: stored {}, computed {}", "Prüfsumme der Ogg-Seite stimmt nicht: gespeichert {}, berechnet {}"),
+    ("Unsupported Ogg page version {}", "Nicht unterstützte Ogg-Seitenversion {}"),
+    ("Ogg stream {}: page sequence {} follows {} (pages lost or reordered)", "Ogg-Stream {}: Seitensequenz {} folgt auf {} (Seiten verloren oder umsortiert)"),
+    ("Ogg stream {}: page after end of stream", "Ogg-Stream {}: Seite nach dem Stream-Ende"),
+    ("Ogg stream {}: first page lacks the beginning-of-stream flag", "Ogg-Stream {}: der ersten Seite fehlt das Stream-Anfang-Flag"),
+    ("Ogg stream {} has no end-of-stream page (truncated or live capture)", "Ogg-Stream {} hat keine Stream-Ende-Seite (abgeschnitten oder Live-Mitschnitt)"),
+    ("Page at offset {}: serial {}, sequence {}, ", "Seite bei Offset {}: Seriennummer {}, Sequenz {}, "),
+    ("granule -", "Granule -"),
+    ("granule {}", "Granule {}"),
+    (", {} segments, {} bytes", ", {} Segmente, {} Bytes"),
+    ("Vendor: {}", "Hersteller: {}"),
+    ("Comments: {}", "Kommentare: {}"),
+    ("{}={}... ({} chars)", "{}={}... ({} Zeichen)"),
+    // ID3v2 tag structure
+    (
+        "ERROR: '{}' is not a valid ID3v2.3 frame ID (may be from ID3v2.4 or other version)",
+        "FEHLER: '{}' ist keine gültige ID3v2.3-Frame-ID (evtl. aus ID3v2.4 oder einer anderen Version)"
+    ),
+    (
+        "ERROR: '{}' is not a valid ID3v2.4 frame ID (may be from ID3v2.3 or other version)",
+        "FEHLER: '{}' ist keine gültige ID3v2.4-Frame-ID (evtl. aus ID3v2.3 oder einer anderen Version)"
+    ),
+    ("ERROR: Failed to skip tag data: {}", "FEHLER: Tag-Daten konnten nicht übersprungen werden: {}"),
+    ("ERROR: Failed to read tag data: {}", "FEHLER: Tag-Daten konnten nicht gelesen werden: {}"),
+    ("ERROR: Invalid frame size {}, falling back to 1-byte skip", "FEHLER: Ungültige Frame-Größe {}, es wird auf 1-Byte-Sprünge zurückgegriffen"),
+    ("Extended flags: {}", "Erweiterte Flags: {}"),
+    ("Extended flags: {} ({})", "Erweiterte Flags: {} ({})"),
+    ("CRC data present", "CRC-Daten vorhanden"),
+    ("tag is an update", "Tag ist ein Update"),
+    ("tag restrictions", "Tag-Einschränkungen"),
+    ("OK", "OK"),
+    ("MISMATCH", "ABWEICHUNG"),
+    ("No restrictions", "Keine Einschränkungen"),
+    ("No more than 128 frames and 1 MB total tag size", "Höchstens 128 Frames und 1 MB Tag-Größe insgesamt"),
+    ("No more than 64 frames and 128 KB total tag size", "Höchstens 64 Frames und 128 KB Tag-Größe insgesamt"),
+    ("No more than 32 frames and 40 KB total tag size", "Höchstens 32 Frames und 40 KB Tag-Größe insgesamt"),
+    ("No more than 32 frames and 4 KB total tag size", "Höchstens 32 Frames und 4 KB Tag-Größe insgesamt"),
+    ("Only ISO-8859-1 or UTF-8", "Nur ISO-8859-1 oder UTF-8"),
+    ("No string longer than 1024 characters", "Keine Zeichenkette länger als 1024 Zeichen"),
+    ("No string longer than 128 characters", "Keine Zeichenkette länger als 128 Zeichen"),
+    ("No string longer than 30 characters", "Keine Zeichenkette länger als 30 Zeichen"),
+    ("Only PNG or JPEG", "Nur PNG oder JPEG"),
+    ("256x256 pixels or smaller", "256x256 Pixel oder kleiner"),
+    ("64x64 pixels or smaller", "64x64 Pixel oder kleiner"),
+    ("Exactly 64x64 pixels, unless required otherwise", "Genau 64x64 Pixel, sofern nicht anders verlangt"),
+    ("UTF-16 with BOM", "UTF-16 mit BOM"),
+    ("unsynchronised", "unsynchronisiert"),
+    ("compressed", "komprimiert"),
+    ("encrypted (method {})", "verschlüsselt (Methode {})"),
+    ("data length {} bytes", "Datenlänge {} Bytes"),
+    // ID3v2 parse errors
+    ("Expected ID3v2.3, found version 2.{}", "ID3v2.3 erwartet, Version 2.{} gefunden"),
+    ("Expected ID3v2.4, found version 2.{}", "ID3v2.4 erwartet, Version 2.{} gefunden"),
+    ("Unsupported ID3v2 version 2.{}", "Nicht unterstützte ID3v2-Version 2.{}"),
+    ("Buffer too small for extended header", "Puffer zu klein für den erweiterten Header"),
+    ("Invalid extended header size {}", "Ungültige Größe des erweiterten Headers {}"),
+    ("Invalid number of extended flag bytes {}", "Ungültige Anzahl erweiterter Flag-Bytes {}"),
+    ("Extended header flag data exceeds the extended header", "Flag-Daten überschreiten den erweiterten Header"),
+    ("Invalid extended header flag data length {}", "Ungültige Länge der Flag-Daten des erweiterten Headers {}"),
+    ("Frame too short for the {} bytes announced by its format flags", "Frame zu kurz für die {} Bytes, die seine Format-Flags ankündigen"),
+    ("Failed to decompress frame: {}", "Frame konnte nicht dekomprimiert werden: {}"),
+    ("Text encoding {} is not valid for ID3v2.{}", "Textkodierung {} ist für ID3v2.{} nicht gültig"),
+    ("Unknown text encoding: {}", "Unbekannte Textkodierung: {}"),
+    ("Invalid UTF-16 encoding", "Ungültige UTF-16-Kodierung"),
+    ("UTF-16 data length must be even", "Die Länge von UTF-16-Daten muss gerade sein"),
+    ("Picture frame data too short", "Daten des Bild-Frames zu kurz"),
+    ("Picture frame MIME type not null-terminated", "MIME-Typ des Bild-Frames nicht nullterminiert"),
+    ("Picture frame missing picture type", "Dem Bild-Frame fehlt der Bildtyp"),
+    ("Picture frame description not properly terminated", "Beschreibung des Bild-Frames nicht korrekt terminiert"),
+    ("Chapter frame data is empty", "Daten des Kapitel-Frames sind leer"),
+    ("Chapter frame element ID not null-terminated", "Element-ID des Kapitel-Frames nicht nullterminiert"),
+    ("Chapter frame missing start time", "Dem Kapitel-Frame fehlt die Startzeit"),
+    ("Chapter frame missing end time", "Dem Kapitel-Frame fehlt die Endzeit"),
+    ("Chapter frame missing start offset", "Dem Kapitel-Frame fehlt der Start-Offset"),
+    ("Chapter frame missing end offset", "Dem Kapitel-Frame fehlt der End-Offset"),
+    ("Comment frame data too short", "Daten des Kommentar-Frames zu kurz"),
+    ("Commercial frame data too short", "Daten des Werbe-Frames zu kurz"),
+    ("Commercial frame price not null-terminated", "Preis des Werbe-Frames nicht nullterminiert"),
+    ("Commercial frame missing valid-until date", "Dem Werbe-Frame fehlt das Gültig-bis-Datum"),
+    ("Commercial frame contact URL not null-terminated", "Kontakt-URL des Werbe-Frames nicht nullterminiert"),
+    ("Commercial frame missing received-as type", "Dem Werbe-Frame fehlt die Erhalten-als-Art"),
+    ("Encapsulated object frame data too short", "Daten des Frames mit gekapseltem Objekt zu kurz"),
+    ("Encapsulated object MIME type not null-terminated", "MIME-Typ des gekapselten Objekts nicht nullterminiert"),
+    ("EQU2 frame data is empty", "Daten des EQU2-Frames sind leer"),
+    ("EQU2 identification not null-terminated", "EQU2-Kennung nicht nullterminiert"),
+    ("MCDI frame too short for a table of contents", "MCDI-Frame zu kurz für ein Inhaltsverzeichnis"),
+    ("MCDI table of contents has no lead-out", "MCDI-Inhaltsverzeichnis hat keinen Lead-out"),
+    ("MCDI table of contents has invalid track addresses", "MCDI-Inhaltsverzeichnis hat ungültige Titeladressen"),
+    ("Ownership frame data too short", "Daten des Besitz-Frames zu kurz"),
+    ("Ownership frame price not null-terminated", "Preis des Besitz-Frames nicht nullterminiert"),
+    ("Ownership frame missing date of purchase", "Dem Besitz-Frame fehlt das Kaufdatum"),
+    ("PRIV owner identifier not null-terminated", "PRIV-Besitzerkennung nicht nullterminiert"),
+    ("RVA2 identification not null-terminated", "RVA2-Kennung nicht nullterminiert"),
+    ("RVA2 peak volume truncated", "RVA2-Spitzenpegel abgeschnitten"),
+    ("Table of contents frame data is empty", "Daten des Inhaltsverzeichnis-Frames sind leer"),
+    ("TOC frame element ID not null-terminated", "Element-ID des TOC-Frames nicht nullterminiert"),
+    ("TOC frame missing flags", "Dem TOC-Frame fehlen die Flags"),
+    ("TOC frame missing entry count", "Dem TOC-Frame fehlt die Anzahl der Einträge"),
+    ("TOC frame child element ID not null-terminated", "ID eines untergeordneten Elements des TOC-Frames nicht nullterminiert"),
+    ("Terms of use frame data too short", "Daten des Nutzungsbedingungen-Frames zu kurz"),
+    ("Text frame data is empty", "Daten des Text-Frames sind leer"),
+    ("Text frame data too short", "Daten des Text-Frames zu kurz"),
+    ("UFID frame data is empty", "Daten des UFID-Frames sind leer"),
+    ("UFID owner identifier not null-terminated", "UFID-Besitzerkennung nicht nullterminiert"),
+    ("UFID identifier too long (max 64 bytes)", "UFID-Kennung zu lang (max. 64 Bytes)"),
+    ("User text frame data is empty", "Daten des Benutzertext-Frames sind leer"),
+    ("User text frame data too short", "Daten des Benutzertext-Frames zu kurz"),
+    ("User URL frame data is empty", "Daten des Benutzer-URL-Frames sind leer"),
+    ("User URL frame data too short", "Daten des Benutzer-URL-Frames zu kurz"),
+    ("Serato Markers2 data too short", "Serato-Markers2-Daten zu kurz"),
+    ("Serato Markers2 data is not base64", "Serato-Markers2-Daten sind kein Base64"),
+    ("Serato Markers2 entries missing", "Serato-Markers2-Einträge fehlen"),
+    ("Serato BeatGrid data too short", "Serato-BeatGrid-Daten zu kurz"),
+    ("Serato BeatGrid truncated after {} of {} markers", "Serato-BeatGrid nach {} von {} Markern abgeschnitten"),
+    ("Traktor data holds no cue point chunk", "Traktor-Daten enthalten keinen Cue-Punkt-Chunk"),
+    ("Traktor cue point chunk too short", "Traktor-Cue-Punkt-Chunk zu kurz"),
+    ("Unexpected end of compressed data", "Unerwartetes Ende der komprimierten Daten"),
+    ("Invalid Huffman code in compressed data", "Ungültiger Huffman-Code in den komprimierten Daten"),
+    ("Invalid DEFLATE block type", "Ungültiger DEFLATE-Blocktyp"),
+    ("zlib stream too short", "zlib-Datenstrom zu kurz"),
+    ("Invalid zlib header", "Ungültiger zlib-Header"),
+    ("zlib preset dictionaries are not supported", "Voreingestellte zlib-Wörterbücher werden nicht unterstützt"),
+    ("zlib Adler-32 checksum mismatch", "zlib-Adler-32-Prüfsumme stimmt nicht"),
+    ("Invalid stored block length", "Ungültige Länge eines gespeicherten Blocks"),
+    ("Invalid dynamic block code counts", "Ungültige Code-Anzahlen im dynamischen Block"),
+    ("Repeated code length without a previous length", "Wiederholte Codelänge ohne vorherige Länge"),
+    ("Too many code lengths in dynamic block", "Zu viele Codelängen im dynamischen Block"),
+    ("Dynamic block has no end-of-block code", "Dynamischer Block hat keinen Blockende-Code"),
+    ("Invalid distance code in compressed data", "Ungültiger Distanzcode in den komprimierten Daten"),
+    ("Distance too far back in compressed data", "Distanz reicht in den komprimierten Daten zu weit zurück"),
+    ("Invalid literal/length code in compressed data", "Ungültiger Literal-/Längencode in den komprimierten Daten"),
+    ("Decompressed data exceeds the size limit", "Dekomprimierte Daten überschreiten die Größengrenze"),
+    // ISOBMFF box values
+    ("0 (not set)", "0 (nicht gesetzt)"),
+    ("Video Track", "Videospur"),
+    ("Audio Track", "Audiospur"),
+    ("Hint Track", "Hint-Spur"),
+    ("Metadata Track", "Metadatenspur"),
+    ("Metadata Directory", "Metadatenverzeichnis"),
+    ("QuickTime Metadata", "QuickTime-Metadaten"),
+    ("Auxiliary Video Track", "Hilfsvideospur"),
+    ("Text/Subtitle Track", "Text-/Untertitelspur"),
+    ("Subtitle Track", "Untertitelspur"),
+    ("Closed Caption Track", "Spur für Untertitel für Hörgeschädigte"),
+    ("Timecode Track", "Timecode-Spur"),
+    ("Image Items", "Bildelemente"),
+    ("Unknown Handler", "Unbekannter Handler"),
+    ("QuickTime language code {}", "QuickTime-Sprachcode {}"),
+    ("unknown QuickTime language code {}", "unbekannter QuickTime-Sprachcode {}"),
+    ("duration {}", "Dauer {}"),
+    ("size {}", "Größe {}"),
+    ("flags {}", "Flags {}"),
+    ("composition offset {}", "Kompositions-Offset {}"),
+    ("group {} of this fragment", "Gruppe {} dieses Fragments"),
+    ("group {}", "Gruppe {}"),
+    (", starts with SAP type {}", ", beginnt mit SAP-Typ {}"),
+    // ISOBMFF parse errors
+    ("damr box too short", "damr-Box zu kurz"),
+    ("av1C box too short", "av1C-Box zu kurz"),
+    ("avcC box too short", "avcC-Box zu kurz"),
+    ("chap box too short", "chap-Box zu kurz"),
+    ("dref box too short", "dref-Box zu kurz"),
+    ("url box too short", "url-Box zu kurz"),
+    ("urn box too short", "urn-Box zu kurz"),
+    ("elst box too short", "elst-Box zu kurz"),
+    ("esds box too short", "esds-Box zu kurz"),
+    ("emsg box too short", "emsg-Box zu kurz"),
+    ("emsg version 1 box too short", "emsg-Box der Version 1 zu kurz"),
+    ("emsg version 0 box too short", "emsg-Box der Version 0 zu kurz"),
+    ("prft box too short", "prft-Box zu kurz"),
+    ("prft version 1 box too short", "prft-Box der Version 1 zu kurz"),
+    ("ftyp box too short", "ftyp-Box zu kurz"),
+    ("hdlr box too short", "hdlr-Box zu kurz"),
+    ("hvcC box too short", "hvcC-Box zu kurz"),
+    ("iloc box too short", "iloc-Box zu kurz"),
+    ("infe box too short", "infe-Box zu kurz"),
+    ("pitm box too short", "pitm-Box zu kurz"),
+    ("iref box too short", "iref-Box zu kurz"),
+    ("ipma box too short", "ipma-Box zu kurz"),
+    ("ispe box too short", "ispe-Box zu kurz"),
+    ("pixi box too short", "pixi-Box zu kurz"),
+    ("pixi box truncated", "pixi-Box abgeschnitten"),
+    ("mdhd box too short", "mdhd-Box zu kurz"),
+    ("mdhd version 1 box too short", "mdhd-Box der Version 1 zu kurz"),
+    ("mdhd version 0 box too short", "mdhd-Box der Version 0 zu kurz"),
+    ("vmhd box too short", "vmhd-Box zu kurz"),
+    ("smhd box too short", "smhd-Box zu kurz"),
+    ("nmhd box too short", "nmhd-Box zu kurz"),
+    ("mean box too short", "mean-Box zu kurz"),
+    ("name box too short", "name-Box zu kurz"),
+    ("keys box too short", "keys-Box zu kurz"),
+    ("mfhd box too short", "mfhd-Box zu kurz"),
+    ("trex box too short", "trex-Box zu kurz"),
+    ("tfhd box too short", "tfhd-Box zu kurz"),
+    ("tfhd box truncated", "tfhd-Box abgeschnitten"),
+    ("tfdt box too short", "tfdt-Box zu kurz"),
+    ("tfdt version 1 box too short", "tfdt-Box der Version 1 zu kurz"),
+    ("trun box too short", "trun-Box zu kurz"),
+    ("mvhd box too short", "mvhd-Box zu kurz"),
+    ("mvhd version 1 box too short", "mvhd-Box der Version 1 zu kurz"),
+    ("mvhd version 0 box too short", "mvhd-Box der Version 0 zu kurz"),
+    ("pssh box too short", "pssh-Box zu kurz"),
+    ("tenc box too short", "tenc-Box zu kurz"),
+    ("senc box too short", "senc-Box zu kurz"),
+    ("saiz box too short", "saiz-Box zu kurz"),
+    ("saio box too short", "saio-Box zu kurz"),
+    ("{} box too short", "{}-Box zu kurz"),
+    ("schm box too short", "schm-Box zu kurz"),
+    ("frma box too short", "frma-Box zu kurz"),
+    ("btrt box too short", "btrt-Box zu kurz"),
+    ("pasp box too short", "pasp-Box zu kurz"),
+    ("colr box too short", "colr-Box zu kurz"),
+    ("sgpd box too short", "sgpd-Box zu kurz"),
+    ("sbgp box too short", "sbgp-Box zu kurz"),
+    ("stsd box too short", "stsd-Box zu kurz"),
+    ("stts box too short", "stts-Box zu kurz"),
+    ("stsc box too short", "stsc-Box zu kurz"),
+    ("stsz box too short", "stsz-Box zu kurz"),
+    ("stco box too short", "stco-Box zu kurz"),
+    ("co64 box too short", "co64-Box zu kurz"),
+    ("ctts box too short", "ctts-Box zu kurz"),
+    ("stss box too short", "stss-Box zu kurz"),
+    ("sdtp box too short", "sdtp-Box zu kurz"),
+    ("sidx box too short", "sidx-Box zu kurz"),
+    ("sidx version 1 box too short", "sidx-Box der Version 1 zu kurz"),
+    ("sidx version 0 box too short", "sidx-Box der Version 0 zu kurz"),
+    ("tkhd box too short", "tkhd-Box zu kurz"),
+    ("tkhd version 1 box too short", "tkhd-Box der Version 1 zu kurz"),
+    ("tkhd version 0 box too short", "tkhd-Box der Version 0 zu kurz"),
+    ("uuid box too short", "uuid-Box zu kurz"),
+    ("tfxd uuid box too short", "tfxd-uuid-Box zu kurz"),
+    ("tfrf uuid box too short", "tfrf-uuid-Box zu kurz"),
+    ("Maximum box nesting depth exceeded", "Maximale Verschachtelungstiefe der Boxen überschritten"),
+    ("Failed to read box header at {}: {}", "Box-Header bei {} konnte nicht gelesen werden: {}"),
+    ("Failed to read extended size: {}", "Erweiterte Größe konnte nicht gelesen werden: {}"),
+    ("Invalid box size {} at offset {} (smaller than header)", "Ungültige Box-Größe {} bei Offset {} (kleiner als der Header)"),
+    ("Read error while resynchronizing: {}", "Lesefehler bei der Resynchronisierung: {}"),
+    ("Box at offset {} extends beyond parent (size: {}, available: {})", "Box bei Offset {} reicht über das Elternelement hinaus (Größe: {}, vorhanden: {})"),
+    ("Failed to read iinf version: {}", "iinf-Version konnte nicht gelesen werden: {}"),
+    ("Failed to read box data: {}", "Box-Daten konnten nicht gelesen werden: {}"),
+    ("Failed to map box data: {}", "Box-Daten konnten nicht eingeblendet werden: {}"),
+    ("Failed to parse ISOBMFF boxes: {}", "ISOBMFF-Boxen konnten nicht ausgewertet werden: {}"),
+    ("{} box too short for its information type", "{}-Box zu kurz für ihren Informationstyp"),
+    ("mdhd box too short for language", "mdhd-Box zu kurz für die Sprache"),
+    ("mvhd box too short for rate/volume", "mvhd-Box zu kurz für Rate/Lautstärke"),
+    ("tkhd box too short for additional fields", "tkhd-Box zu kurz für weitere Felder"),
+    ("sgpd box too short for entry count", "sgpd-Box zu kurz für die Anzahl der Einträge"),
+    ("sbgp box too short for entry count", "sbgp-Box zu kurz für die Anzahl der Einträge"),
+    ("pssh box too short for its KIDs", "pssh-Box zu kurz für ihre KIDs"),
+    ("senc box too short for its track encryption override", "senc-Box zu kurz für ihre Überschreibung der Spurverschlüsselung"),
+    ("pssh data exceeds the box", "pssh-Daten überschreiten die Box"),
+    ("tenc constant IV exceeds the box", "Konstanter IV von tenc überschreitet die Box"),
+    ("av1C box without marker bit", "av1C-Box ohne Marker-Bit"),
+    ("esds box without ES_Descriptor", "esds-Box ohne ES_Descriptor"),
+    ("esds box: expected ES_Descriptor, found tag {}", "esds-Box: ES_Descriptor erwartet, Tag {} gefunden"),
+    ("emsg box: string missing or unterminated", "emsg-Box: Zeichenkette fehlt oder ist nicht terminiert"),
+    ("infe box: item name missing or unterminated", "infe-Box: Elementname fehlt oder ist nicht terminiert"),
+    ("iTunes data box too short", "iTunes-Daten-Box zu kurz"),
+    ("Invalid signed integer size: {} bytes", "Ungültige Größe einer vorzeichenbehafteten Ganzzahl: {} Bytes"),
+    ("Invalid unsigned integer size: {} bytes", "Ungültige Größe einer vorzeichenlosen Ganzzahl: {} Bytes"),
+    // Audio and container parse errors
+    ("Missing ADTS syncword", "ADTS-Syncwort fehlt"),
+    ("Invalid ADTS sampling frequency index {}", "Ungültiger ADTS-Abtastratenindex {}"),
+    ("ADTS frame length {} is shorter than its header", "ADTS-Frame-Länge {} ist kürzer als sein Header"),
+    ("ADTS frame header truncated", "ADTS-Frame-Header abgeschnitten"),
+    ("Missing AMR magic number", "AMR-Kennung fehlt"),
+    ("AMR channel description truncated", "AMR-Kanalbeschreibung abgeschnitten"),
+    ("AMR channel description without channels", "AMR-Kanalbeschreibung ohne Kanäle"),
+    ("Invalid AMR frame header {} (padding bits set)", "Ungültiger AMR-Frame-Header {} (Füllbits gesetzt)"),
+    ("Missing WavPack block header", "WavPack-Block-Header fehlt"),
+    ("WavPack block header truncated", "WavPack-Block-Header abgeschnitten"),
+    ("Unsupported WavPack stream version {}", "Nicht unterstützte WavPack-Stream-Version {}"),
+    ("Missing TTA1 header", "TTA1-Header fehlt"),
+    ("Missing DSF DSD chunk", "DSF-DSD-Chunk fehlt"),
+    ("DSD chunk size {} (the specification requires {})", "DSD-Chunk-Größe {} (die Spezifikation verlangt {})"),
+    ("Missing DSF fmt chunk", "DSF-fmt-Chunk fehlt"),
+    ("fmt chunk of {} bytes is too short", "fmt-Chunk mit {} Bytes ist zu kurz"),
+    ("{}:{}:{} + {} samples", "{}:{}:{} + {} Samples"),
+    ("{} frames at {} frames/s", "{} Frames mit {} Frames/s"),
+    ("{} comments", "{} Kommentare"),
+    ("Failed to parse EBML elements: {}", "EBML-Elemente konnten nicht ausgewertet werden: {}"),
+    ("Maximum element nesting depth exceeded", "Maximale Verschachtelungstiefe der Elemente überschritten"),
+    ("Seek error at offset {}: {}", "Positionierungsfehler bei Offset {}: {}"),
+    ("Seek error: {}", "Positionierungsfehler: {}"),
+    ("Failed to read element header at {}: {}", "Element-Header bei {} konnte nicht gelesen werden: {}"),
+    ("Failed to read element data: {}", "Elementdaten konnten nicht gelesen werden: {}"),
+    ("Element '{}' at offset {} has unknown size but is not a master element", "Element '{}' bei Offset {} hat eine unbekannte Größe, ist aber kein Master-Element"),
+    ("invalid float of {} bytes", "ungültige Gleitkommazahl mit {} Bytes"),
+    ("invalid date of {} bytes", "ungültiges Datum mit {} Bytes"),
+    ("track {}, timestamp {}", "Spur {}, Zeitstempel {}"),
+    ("No MPEG transport stream packets found", "Keine MPEG-Transportstrom-Pakete gefunden"),
+    ("Section of {} bytes is too short", "Abschnitt mit {} Bytes ist zu kurz"),
+    ("Table {} lacks the section syntax indicator", "Tabelle {} fehlt der Abschnittssyntax-Indikator"),
+    ("Table {} section CRC mismatch (stored {})", "CRC eines Abschnitts von Tabelle {} stimmt nicht (gespeichert {})"),
+    ("Unexpected table {} on the PAT PID", "Unerwartete Tabelle {} auf der PAT-PID"),
+    ("Unexpected table {} on a PMT PID", "Unerwartete Tabelle {} auf einer PMT-PID"),
+    ("Program map table too short", "Program Map Table zu kurz"),
+    ("STREAMINFO block too short", "STREAMINFO-Block zu kurz"),
+    ("FLAC mapping header too short", "FLAC-Mapping-Header zu kurz"),
+    ("OpusHead packet too short", "OpusHead-Paket zu kurz"),
+    ("Vorbis identification header too short", "Vorbis-Identifikations-Header zu kurz"),
+    ("{} bps", "{} bps"),
+    ("Vorbis comment vendor string truncated", "Herstellerzeichenkette des Vorbis-Kommentars abgeschnitten"),
+    ("Vorbis comment entry truncated", "Eintrag des Vorbis-Kommentars abgeschnitten"),
+    // Image values and parse errors
+    ("Unknown image format", "Unbekanntes Bildformat"),
+    ("BMP header too short", "BMP-Header zu kurz"),
+    ("Unsupported BMP DIB header size {}", "Nicht unterstützte BMP-DIB-Header-Größe {}"),
+    ("Indexed, {} bits per pixel", "Indiziert, {} Bit pro Pixel"),
+    ("RGB, {} bits per pixel", "RGB, {} Bit pro Pixel"),
+    ("GIF header too short", "GIF-Header zu kurz"),
+    ("Indexed, {} global colors, {} bits per primary color", "Indiziert, {} globale Farben, {} Bit pro Grundfarbe"),
+    ("Indexed, local color tables, {} bits per primary color", "Indiziert, lokale Farbtabellen, {} Bit pro Grundfarbe"),
+    ("{}x{}, {} components, {} bits", "{}x{}, {} Komponenten, {} Bit"),
+    ("ICC profile chunk {} of {}", "ICC-Profil-Teil {} von {}"),
+    ("Adobe, color transform {}", "Adobe, Farbtransformation {}"),
+    ("{} (16-bit)", "{} (16 Bit)"),
+    ("tables {}", "Tabellen {}"),
+    ("interval {} MCUs", "Intervall {} MCUs"),
+    ("{} components", "{} Komponenten"),
+    ("YCbCr {}", "YCbCr {}"),
+    ("{}, {} bits per sample", "{}, {} Bit pro Sample"),
+    ("{}, {} bits per sample{}", "{}, {} Bit pro Sample{}"),
+    ("PNG {} chunk without keyword terminator", "PNG-Chunk {} ohne Abschluss des Schlüsselworts"),
+    ("{} entries", "{} Einträge"),
+    ("gamma {}", "Gamma {}"),
+    ("rendering intent {}", "Wiedergabeabsicht {}"),
+    ("profile \"{}\"", "Profil \"{}\""),
+    ("{}x{} pixels per meter ({} dpi)", "{}x{} Pixel pro Meter ({} dpi)"),
+    ("aspect ratio {}:{}", "Seitenverhältnis {}:{}"),
+    ("animated, {} frames, looping forever", "animiert, {} Frames, Endlosschleife"),
+    ("animated, {} frames, {} plays", "animiert, {} Frames, {} Durchläufe"),
+    ("EXIF ({}, {} tags)", "EXIF ({}, {} Tags)")
+];
@@ -0,0 +1,182 @@
+// ID3v1 and ID3v1.1 tags
+//
+// The legacy tag occupies the last 128 bytes of a file: "TAG", 30-byte title, artist and
+// album fields, a 4-byte year, a 30-byte comment and a genre byte. ID3v1.1 shortens the
+// comment to 28 bytes and stores the track number in the last byte behind a zero byte.
+
+use std::io::{SeekFrom, Write};
+
+use crate::{
+    color::Colorize,
+    i18n::{tr, tr_fmt},
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Size of an ID3v1 tag
+pub const ID3V1_SIZE: u64 = 128;
+
+/// Genre names indexed by the genre byte (0-79 from the ID3v1 specification, 80-191 Winamp extensions)
+const GENRES: &[&str] = &[
+    "Blues", "Classic Rock", "Country", "Dance", "Disco", "Funk", "Grunge", "Hip-Hop", "Jazz", "Metal", "New Age", "Oldies", "Other", "Pop", "R&B", "Rap", "Reggae",
+    "Rock", "Techno", "Industrial", "Alternative", "Ska", "Death Metal", "Pranks", "Soundtrack", "Euro-Techno", "Ambient", "Trip-Hop", "Vocal", "Jazz+Funk", "Fusion",
+    "Trance", "Classical", "Instrumental", "Acid", "House", "Game", "Sound Clip", "Gospel", "Noise", "Alternative Rock", "Bass", "Soul", "Punk", "Space",
+    "Meditative", "Instrumental Pop", "Instrumental Rock", "Ethnic", "Gothic", "Darkwave", "Techno-Industrial", "Electronic", "Pop-Folk", "Eurodance", "Dream",
+    "Southern Rock", "Comedy", "Cult", "Gangsta", "Top 40", "Christian Rap", "Pop/Funk", "Jungle", "Native American", "Cabaret", "New Wave", "Psychedelic", "Rave",
+    "Showtunes", "Trailer", "Lo-Fi", "Tribal", "Acid Punk", "Acid Jazz", "Polka", "Retro", "Musical", "Rock & Roll", "Hard Rock", "Folk", "Folk-Rock",
+    "National Folk", "Swing", "Fast Fusion", "Bebop", "Latin", "Revival", "Celtic", "Bluegrass", "Avantgarde", "Gothic Rock", "Progressive Rock", "Psychedelic Rock",
+    "Symphonic Rock", "Slow Rock", "Big Band", "Chorus", "Easy Listening", "Acoustic", "Humour", "Speech", "Chanson", "Opera", "Chamber Music", "Sonata", "Symphony",
+    "Booty Bass", "Primus", "Porn Groove", "Satire", "Slow Jam", "Club", "Tango", "Samba", "Folklore", "Ballad", "Power Ballad", "Rhythmic Soul", "Freestyle", "Duet",
+    "Punk Rock", "Drum Solo", "A Cappella", "Euro-House", "Dance Hall", "Goa", "Drum & Bass", "Club-House", "Hardcore Techno", "Terror", "Indie", "BritPop",
+    "Negerpunk", "Polsk Punk", "Beat", "Christian Gangsta Rap", "Heavy Metal", "Black Metal", "Crossover", "Contemporary Christian", "Christian Rock", "Merengue",
+    "Salsa", "Thrash Metal", "Anime", "Jpop", "Synthpop", "Abstract", "Art Rock", "Baroque", "Bhangra", "Big Beat", "Breakbeat", "Chillout", "Downtempo", "Dub",
+    "EBM", "Eclectic", "Electro", "Electroclash", "Emo", "Experimental", "Garage", "Global", "IDM", "Illbient", "Industro-Goth", "Jam Band", "Krautrock", "Leftfield",
+    "Lounge", "Math Rock", "New Romantic", "Nu-Breakz", "Post-Punk", "Post-Rock", "Psytrance", "Shoegaze", "Space Rock", "Trop Rock", "World Music", "Neoclassical",
+    "Audiobook", "Audio Theatre", "Neue Deutsche Welle", "Podcast", "Indie Rock", "G-Funk", "Dubstep", "Garage Rock", "Psybient"
+];
+
+/// Genre name for an ID3v1 genre index (also used by ID3v2 TCON references such as "(13)")
+pub fn genre_by_index(index: usize) -> Option<&'static str>
+{
+    GENRES.get(index).copied()
+}
+
+/// Parsed ID3v1/ID3v1.1 tag
+#[derive(Debug, Clone)]
+pub struct Id3v1Tag
+{
+    /// File offset of the "TAG" marker
+    pub offset:  u64,
+    pub title:   String,
+    pub artist:  String,
+    pub album:   String,
+    pub year:    String,
+    pub comment: String,
+    /// Track number (ID3v1.1 only)
+    pub track:   Option<u8>,
+    pub genre:   u8
+}
+
+/// Decode a fixed-size ISO-8859-1 field, dropping the zero/space padding
+fn decode_field(data: &[u8]) -> String
+{
+    let end = data.iter().position(|&byte| byte == 0).unwrap_or(data.len());
+    data[..end].iter().map(|&byte| byte as char).collect::<String>().trim_end().to_string()
+}
+
+impl Id3v1Tag
+{
+    /// Parse a 128-byte ID3v1 tag, returning None if the "TAG" marker is missing
+    pub fn parse(data: &[u8], offset: u64) -> Option<Self>
+    {
+        if data.len() < ID3V1_SIZE as usize || &data[0..3] != b"TAG"
+        {
+            return None;
+        }
+
+        // ID3v1.1: zero byte before a non-zero track number at the end of the comment
+        let (comment, track) = if data[125] == 0 && data[126] != 0
+        {
+            (decode_field(&data[97..125]), Some(data[126]))
+        }
+        else
+        {
+            (decode_field(&data[97..127]), None)
+        };
+
+        Some(Self {
+            offset,
+            title: decode_field(&data[3..33]),
+            artist: decode_field(&data[33..63]),
+            album: decode_field(&data[63..93]),
+            year: decode_field(&data[93..97]),
+            comment,
+            track,
+            genre: data[127]
+        })
+    }
+
+    /// Read the ID3v1 tag from the last 128 bytes of the file, if present
+    pub fn read(file: &mut dyn MediaSource) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        if file_size < ID3V1_SIZE
+        {
+            return Ok(None);
+        }
+
+        let offset = file_size - ID3V1_SIZE;
+        let mut data = [0u8; ID3V1_SIZE as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut data)?;
+
+        Ok(Self::parse(&data, offset))
+    }
+
+    /// Version label (ID3v1 or ID3v1.1)
+    pub fn version(&self) -> &'static str
+    {
+        if self.track.is_some() == true
+        {
+            "ID3v1.1"
+        }
+        else
+        {
+            "ID3v1"
+        }
+    }
+
+    /// Genre name, None for 255 (unset) and unknown values
+    pub fn genre_name(&self) -> Option<&'static str>
+    {
+        genre_by_index(self.genre as usize)
+    }
+
+    /// Structured representation of this tag for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version(),
+            "offset": self.offset,
+            "title": self.title,
+            "artist": self.artist,
+            "album": self.album,
+            "year": self.year,
+            "comment": self.comment,
+            "track": self.track,
+            "genre": self.genre,
+            "genre_name": self.genre_name()
+        })
+    }
+}
+
+/// Display the ID3v1 trailer of the file, if present
+pub fn dissect_id3v1_trailer(file: &mut dyn MediaSource, options: &DissectOptions, output: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>>
+{
+    let Some(tag) = Id3v1Tag::read(file)?
+    else
+    {
+        return Ok(());
+    };
+
+    if options.show_header == true || options.show_data == true
+    {
+        writeln!(output, "\n{}", tr_fmt("{} Tag Found at offset {}:", &[&tag.version(), &format!("0x{:08X}", tag.offset)]).bright_cyan().bold())?;
+        writeln!(output, "  {}", tr_fmt("Title: {}", &[&tag.title]))?;
+        writeln!(output, "  {}", tr_fmt("Artist: {}", &[&tag.artist]))?;
+        writeln!(output, "  {}", tr_fmt("Album: {}", &[&tag.album]))?;
+        writeln!(output, "  {}", tr_fmt("Year: {}", &[&tag.year]))?;
+        writeln!(output, "  {}", tr_fmt("Comment: {}", &[&tag.comment]))?;
+        if let Some(track) = tag.track
+        {
+            writeln!(output, "  {}", tr_fmt("Track: {}", &[&track]))?;
+        }
+        match tag.genre_name()
+        {
+            | Some(name) => writeln!(output, "  {}", tr_fmt("Genre: {} ({})", &[&tag.genre, &name]))?,
+            | None => writeln!(output, "  {}", tr_fmt("Genre: {} ({})", &[&tag.genre, &tr("unset")]))?
+        }
+    }
+
+    Ok(())
+}
@@ -5,9 +5,13 @@
 // unsynchronization, and embedded frames in chapter structures.
 
 // Core types and utilities
+pub mod extended_header;
 pub mod frame;
+pub mod tag;
 pub mod text_encoding;
 pub mod tools;
+pub mod validation;
+pub mod writer;
 
 // Version-specific dissectors
 pub mod dissectors
@@ -22,7 +26,15 @@ pub mod frames
     pub mod attached_picture;
     pub mod chapter;
     pub mod comment;
+    pub mod commercial;
+    pub mod encapsulated_object;
+    pub mod equalisation;
+    pub mod music_cd_id;
+    pub mod ownership;
+    pub mod private;
+    pub mod relative_volume;
     pub mod table_of_contents;
+    pub mod terms_of_use;
     pub mod text;
     pub mod unique_file_id;
     pub mod url;
@@ -32,3 +44,14 @@ pub mod frames
 
 // Re-export commonly used types for convenience
 pub use dissectors::{v3::Id3v23Dissector, v4::Id3v24Dissector};
+pub use frame::Id3v2Frame;
+pub use tag::Id3v2Tag;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the ID3v2.3 and ID3v2.4 dissectors with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<Id3v23Dissector>(1400);
+    registry.register_dissector::<Id3v24Dissector>(1300);
+}
@@ -1,14 +1,28 @@
-use std::{fs::File, io::Read};
-
-use owo_colors::OwoColorize;
+use std::io::{self, Write};
 
 use crate::{
-    cli::DissectOptions,
-    id3v2::{frame::Id3v2Frame, tools::*},
-    media_dissector::MediaDissector
+    artwork::Artwork,
+    chapters::Chapter,
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::ProbeFormat,
+    find::Occurrence,
+    i18n::{tr, tr_fmt},
+    id3v2::{extended_header::ExtendedHeader, frame::Id3v2Frame, tag::Id3v2Tag, tools::*, validation::validate_tag},
+    layout::Layout,
+    media_dissector::{Confidence, MediaDissector},
+    media_source::{ByteWindow, MediaSource},
+    metadata::MediaMetadata,
+    mpeg_audio,
+    objects::EmbeddedObject,
+    options::DissectOptions,
+    summary::Summary,
+    visitor::DissectionVisitor
 };
 
 /// ID3v2.3 dissector for MP3 files
+#[derive(Default)]
 pub struct Id3v23Dissector;
 
 /// Parse an ID3v2.3 frame from raw buffer data
@@ -59,21 +73,150 @@ fn media_type(&self) -> &'static str
         "ID3v2.3"
     }
 
-    fn dissect_with_options(&self, file: &mut File, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        dissect_id3v2_3_file_with_options(file, start_offset, options, output, diagnostics)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let tag = if options.recover == true
+        {
+            Id3v2Tag::read_recovering(file, start_offset, diagnostics)?
+        }
+        else
+        {
+            Id3v2Tag::read(file, start_offset)?
+        };
+        match tag
+        {
+            | Some(mut tag) if tag.major_version == 3 =>
+            {
+                check_tag_size(tag.size, start_offset, &options.tag_size_thresholds, diagnostics);
+                if let Some(ref filter) = options.filter
+                {
+                    tag.frames.retain_mut(|frame| select_frame(filter, frame));
+                }
+                if let Some(ref extended_header) = tag.extended_header
+                {
+                    check_extended_header_crc(extended_header, start_offset + 10, diagnostics);
+                }
+                Ok((with_tagged_audio(file, tag.end_offset(), tag.to_json(options.show_details), diagnostics)?, tag.end_offset()))
+            }
+            | Some(tag) => Err(tr_fmt("Expected ID3v2.3, found version 2.{}", &[&tag.major_version]).into()),
+            | None => Ok((with_tagged_audio(file, start_offset, serde_json::Value::Null, diagnostics)?, start_offset))
+        }
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, _diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        probe_tagged_audio(file, start_offset)
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        add_tagged_audio_coverage(file, start_offset, coverage)
+    }
+
+    fn collect_artwork(&self, file: &mut dyn MediaSource, start_offset: u64, artwork: &mut Vec<Artwork>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            artwork.extend(tag.artwork());
+        }
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    fn collect_objects(&self, file: &mut dyn MediaSource, start_offset: u64, objects: &mut Vec<EmbeddedObject>) -> Result<u64, Box<dyn std::error::Error>>
     {
-        dissect_id3v2_3_file_with_options(file, options)
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            objects.extend(tag.objects());
+        }
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
     }
 
-    fn can_handle(&self, header: &[u8]) -> bool
+    fn collect_chapters(&self, file: &mut dyn MediaSource, start_offset: u64, chapters: &mut Vec<Chapter>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            chapters.extend(tag.chapters());
+        }
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    fn collect_metadata(&self, file: &mut dyn MediaSource, start_offset: u64, metadata: &mut MediaMetadata) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            metadata.merge(tag.metadata());
+        }
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    fn collect_occurrences(
+        &self, file: &mut dyn MediaSource, start_offset: u64, code: &str, occurrences: &mut Vec<Occurrence>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            occurrences.extend(tag.occurrences(code));
+        }
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    fn collect_summary(&self, file: &mut dyn MediaSource, start_offset: u64, summary: &mut Summary) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        collect_tag_summary(file, start_offset, summary)
+    }
+
+    fn collect_layout(&self, file: &mut dyn MediaSource, start_offset: u64, layout: &mut Layout) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        collect_tagged_audio_layout(file, start_offset, layout)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        validate_tag(file, start_offset, diagnostics)?;
+        add_tagged_audio_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    fn visit(
+        &self, file: &mut dyn MediaSource, start_offset: u64, visitor: &mut dyn DissectionVisitor, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        if let Some(tag) = Id3v2Tag::read(file, start_offset)?
+        {
+            tag.visit(visitor);
+        }
+        // The findings are those of the dissection, which also covers the audio following the tag
+        self.dissect_with_options(file, start_offset, &DissectOptions::default(), &mut io::sink(), diagnostics)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
     {
         // Check for ID3v2.3 specifically
-        if let Some((major, _minor)) = detect_id3v2_version(header)
+        match detect_id3v2_version(header)
         {
-            return major == 3;
+            | Some((3, _minor)) => Confidence::High,
+            | Some(_) => Confidence::None,
+            // Also check for MPEG audio frames (a raw stream without a tag)
+            | None => mpeg_audio::sniff(header)
         }
+    }
 
-        // Also check for MPEG sync (might contain ID3v2.3)
-        detect_mpeg_sync(header)
+    fn sniff_chained(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // MPEG audio following a tag is not an ID3v2.3 tag, only accept a real header
+        match detect_id3v2_version(header)
+        {
+            | Some((3, _minor)) => Confidence::High,
+            | _ => Confidence::None
+        }
     }
 
     fn name(&self) -> &'static str
@@ -82,24 +225,30 @@ fn name(&self) -> &'static str
     }
 }
 
-/// Dissect an ID3v2.3 file from the beginning with specific options
-pub fn dissect_id3v2_3_file_with_options(file: &mut File, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+/// Dissect an ID3v2.3 tag starting at `start_offset` with specific options, returning the offset past the tag
+pub fn dissect_id3v2_3_file_with_options(
+    file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<u64, Box<dyn std::error::Error>>
 {
+    let mut end_offset = start_offset;
+
     // Read and parse ID3v2 header
-    if let Some((major, minor, flags, size)) = read_id3v2_header(file)?
+    if let Some((major, minor, flags, size)) = read_id3v2_header(file, start_offset, output)?
     {
         if major == 3
         {
+            check_tag_size(size, start_offset, &options.tag_size_thresholds, diagnostics);
+
             if options.show_header == true
             {
-                println!("\nID3v2 Header Found:");
-                println!("  Version: 2.{}.{}", major, minor);
-                println!("  Flags: 0x{:02X}", flags);
+                writeln!(output, "\n{}", tr("ID3v2 Header Found:"))?;
+                writeln!(output, "  {}", tr_fmt("Version: {}", &[&format!("2.{}.{}", major, minor)]))?;
+                writeln!(output, "  {}", tr_fmt("Flags: {}", &[&format!("0x{:02X}", flags)]))?;
 
                 // Interpret header flags
                 if flags != 0
                 {
-                    print!("    ");
+                    write!(output, "    ")?;
                     let mut flag_parts = Vec::new();
                     if flags & 0x80 != 0
                     {
@@ -115,60 +264,73 @@ pub fn dissect_id3v2_3_file_with_options(file: &mut File, options: &DissectOptio
                     }
                     if flag_parts.is_empty() == false
                     {
-                        println!("Active: {}", flag_parts.join(", "));
+                        writeln!(output, "{}", tr_fmt("Active: {}", &[&flag_parts.join(", ")]))?;
                     }
                 }
 
-                println!("  Tag Size: {} bytes", size);
+                writeln!(output, "  {}", tr_fmt("Tag Size: {} bytes", &[&size]))?;
+            }
 
-                if size > 100_000_000
-                {
-                    println!("  WARNING: Extremely large tag size (> 100MB), verify file integrity");
-                }
-                else if size > 50_000_000
-                {
-                    println!("  WARNING: Tag size is very large (> 50MB), likely rich podcast with chapter images");
-                }
-                else if size > 10_000_000
-                {
-                    println!("  INFO: Large tag size (> 10MB), possibly podcast with embedded chapter content");
-                }
+            // A truncated tag keeps the frames that are present with --recover
+            let size = if options.recover == true
+            {
+                clamp_tag_size(size, start_offset, file.size()?, diagnostics)
             }
+            else
+            {
+                size
+            };
 
             if size > 0
             {
                 // Allow very large tags for podcast content with chapter images
-                dissect_id3v2_3_with_options(file, size, flags, options)?;
+                dissect_id3v2_3_with_options(file, start_offset, size, flags, options, output, diagnostics)?;
             }
+
+            end_offset = start_offset + 10 + size as u64;
         }
         else if options.show_header == true
         {
-            println!("  Expected ID3v2.3, found version 2.{}", major);
+            writeln!(output, "  {}", tr_fmt("Expected ID3v2.{}, found version 2.{}", &[&3, &major]))?;
         }
     }
     else if options.show_header
     {
-        println!("No ID3v2 header found");
+        writeln!(output, "{}", tr("No ID3v2 header found"))?;
     }
 
-    Ok(())
+    dissect_tagged_audio(file, end_offset, end_offset == start_offset, options, output, diagnostics)?;
+
+    Ok(end_offset)
 }
 
-pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+pub fn dissect_id3v2_3_with_options(
+    file: &mut dyn MediaSource, start_offset: u64, tag_size: u32, flags: u8, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
 {
     if options.show_data == false
     {
-        // If not showing data, skip the tag data entirely
-        let mut buffer = vec![0u8; tag_size as usize];
-        match file.read_exact(&mut buffer)
+        // If not showing data, skip the tag data entirely (mapped, so only the extended header is read)
+        match file.map(start_offset + 10, tag_size as u64)
         {
-            | Ok(_) =>
+            | Ok(mut buffer) =>
             {
-                // Successfully skipped tag data
+                // Successfully skipped tag data, still verify the extended header CRC
+                if flags & 0x40 != 0
+                {
+                    if flags & 0x80 != 0
+                    {
+                        buffer = ByteWindow::Owned(remove_unsynchronization(&buffer));
+                    }
+                    if let Ok((extended_header, _)) = ExtendedHeader::parse_v3(&buffer)
+                    {
+                        check_extended_header_crc(&extended_header, start_offset + 10, diagnostics);
+                    }
+                }
             }
             | Err(e) =>
             {
-                println!("{}", format!("ERROR: Failed to skip tag data: {}", e).bright_red());
+                writeln!(output, "{}", tr_fmt("ERROR: Failed to skip tag data: {}", &[&e]).bright_red())?;
                 return Err(Box::new(e));
             }
         }
@@ -176,59 +338,58 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
     }
 
     // Diagnostic output
-    println!("\nDissecting ID3v2.3 tag (size: {} bytes, flags: 0x{:02X})...", tag_size, flags);
+    writeln!(output, "\n{}", tr_fmt("Dissecting ID3v2.{} tag (size: {} bytes, flags: {})...", &[&3, &tag_size, &format!("0x{:02X}", flags)]))?;
 
-    let mut buffer = vec![0u8; tag_size as usize];
-    match file.read_exact(&mut buffer)
+    // The tag is mapped rather than copied, so frames are only loaded as they are dissected
+    let mut buffer = match file.map(start_offset + 10, tag_size as u64)
     {
-        | Ok(_) =>
+        | Ok(buffer) =>
         {
-            println!("Successfully read {} bytes of tag data", tag_size);
+            writeln!(output, "{}", tr_fmt("Successfully read {} bytes of tag data", &[&tag_size]))?;
+            buffer
         }
         | Err(e) =>
         {
-            println!("{}", format!("ERROR: Failed to read tag data: {}", e).bright_red());
+            writeln!(output, "{}", tr_fmt("ERROR: Failed to read tag data: {}", &[&e]).bright_red())?;
             return Err(Box::new(e));
         }
-    }
+    };
 
     // Handle unsynchronization if flag is set
     let unsync_flag = flags & 0x80 != 0; // Bit 7
     if unsync_flag
     {
-        println!("  Unsynchronization detected - removing sync bytes");
-        buffer = remove_unsynchronization(&buffer);
-        println!("  After unsynchronization removal: {} bytes", buffer.len());
+        writeln!(output, "  {}", tr("Unsynchronization detected - removing sync bytes"))?;
+        buffer = ByteWindow::Owned(remove_unsynchronization(&buffer));
+        writeln!(output, "  {}", tr_fmt("After unsynchronization removal: {} bytes", &[&buffer.len()]))?;
     }
 
-    println!("\nID3v2.3 Frames:");
+    writeln!(output, "\n{}", tr_fmt("ID3v2.{} Frames:", &[&3]))?;
 
     // Check for extended header
     let mut frame_start = 0;
     if flags & 0x40 != 0
     {
         // Extended header flag
-        println!("Extended header flag set, parsing...");
+        writeln!(output, "{}", tr("Extended header flag set, parsing..."))?;
 
-        if buffer.len() >= 4
+        match ExtendedHeader::parse_v3(&buffer)
         {
-            // ID3v2.3 uses regular big-endian integer for extended header size
-            let extended_size = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
-            frame_start = 4 + extended_size as usize;
-
-            println!("  Extended header size: {} bytes", extended_size);
-            println!("  Frame data starts at offset: {}", frame_start);
-
-            if frame_start > buffer.len()
+            | Ok((extended_header, start)) =>
             {
-                println!("  {}", "ERROR: Extended header size exceeds buffer length".bright_red());
-                return Err("Invalid extended header size".into());
+                frame_start = start;
+                for line in extended_header.to_string().lines()
+                {
+                    writeln!(output, "  {}", line)?;
+                }
+                writeln!(output, "  {}", tr_fmt("Frame data starts at offset: {}", &[&frame_start]))?;
+                check_extended_header_crc(&extended_header, start_offset + 10, diagnostics);
+            }
+            | Err(e) =>
+            {
+                writeln!(output, "  {}", format!("ERROR: {}", e).bright_red())?;
+                return Err(e.into());
             }
-        }
-        else
-        {
-            println!("  {}", "ERROR: Buffer too small to read extended header size".bright_red());
-            return Err("Buffer too small for extended header".into());
         }
     }
 
@@ -243,7 +404,18 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
         // Stop if we hit padding (null bytes)
         if frame_id.starts_with('\0') || !frame_id.chars().all(|c| c.is_ascii_alphanumeric())
         {
-            println!("  Reached padding or end of frames at position 0x{:08X}", pos);
+            if options.recover == true &&
+                let Some(next) = recover_frames(&buffer, pos, 3, start_offset, tr("Unexpected data in the padding"), diagnostics)
+            {
+                writeln!(
+                    output,
+                    "  {}",
+                    tr_fmt("Skipped corrupt data at position {}, resuming at position {}", &[&format!("0x{:08X}", pos), &format!("0x{:08X}", next)])
+                )?;
+                pos = next;
+                continue;
+            }
+            writeln!(output, "  {}", tr_fmt("Reached padding or end of frames at position {}", &[&format!("0x{:08X}", pos)]))?;
             break;
         }
 
@@ -251,6 +423,15 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
         let frame_size = u32::from_be_bytes([buffer[pos + 4], buffer[pos + 5], buffer[pos + 6], buffer[pos + 7]]);
         let frame_flags = u16::from_be_bytes([buffer[pos + 8], buffer[pos + 9]]);
 
+        // Frames outside the --filter selection are skipped
+        if let Some(ref filter) = options.filter &&
+            frame_size as usize <= buffer.len() - pos - 10 &&
+            is_frame_selected(filter, frame_id, || parse_id3v2_3_frame(&buffer, pos)) == false
+        {
+            pos += 10 + frame_size as usize;
+            continue;
+        }
+
         // Check if this is a valid ID3v2.3 frame ID
         if is_valid_frame_for_version(frame_id, 3) == false
         {
@@ -258,35 +439,66 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
             let temp_frame = crate::id3v2::frame::Id3v2Frame::new_with_offset(frame_id.to_string(), frame_size, frame_flags, pos, Vec::new());
 
             // Use the unified frame header display function
-            crate::id3v2::tools::display_frame_header(&mut std::io::stdout(), &temp_frame, "    ")?;
+            crate::id3v2::tools::display_frame_header(output, &temp_frame, "    ")?;
 
-            println!("    {}", format!("ERROR: '{}' is not a valid ID3v2.3 frame ID (may be from ID3v2.4 or other version)", frame_id).red());
-            println!();
+            writeln!(output, "    {}", tr_fmt("ERROR: '{}' is not a valid ID3v2.3 frame ID (may be from ID3v2.4 or other version)", &[&frame_id]).red())?;
+            writeln!(output)?;
 
             // Skip the entire frame (header + data) instead of just 1 byte
             if frame_size > 0 && frame_size <= (buffer.len() - pos - 10) as u32
             {
                 pos += 10 + frame_size as usize;
             }
-            else
+            else if options.recover == false
             {
-                println!("    {}", format!("ERROR: Invalid frame size {}, falling back to 1-byte skip", frame_size).bright_red());
+                writeln!(output, "    {}", tr_fmt("ERROR: Invalid frame size {}, falling back to 1-byte skip", &[&frame_size]).bright_red())?;
                 pos += 1;
             }
+            else
+            {
+                let problem = tr_fmt("Corrupt frame '{}' (size {} bytes)", &[&frame_id, &frame_size]);
+                match recover_frames(&buffer, pos, 3, start_offset, &problem, diagnostics)
+                {
+                    | Some(next) =>
+                    {
+                        writeln!(
+                            output,
+                            "    {}",
+                            tr_fmt("Skipped corrupt data at position {}, resuming at position {}", &[&format!("0x{:08X}", pos), &format!("0x{:08X}", next)])
+                        )?;
+                        pos = next;
+                    }
+                    | None => break
+                }
+            }
             continue;
         }
 
         // Sanity check frame size
         if frame_size == 0
         {
-            println!("  Frame '{}' has zero size, skipping", frame_id);
+            writeln!(output, "  {}", tr_fmt("Frame '{}' has zero size, skipping", &[&frame_id]))?;
             pos += 10;
             continue;
         }
 
         if frame_size > (buffer.len() - pos - 10) as u32
         {
-            println!("  Frame '{}' size ({} bytes) exceeds remaining buffer, stopping", frame_id, frame_size);
+            if options.recover == true
+            {
+                let problem = tr_fmt("Corrupt frame '{}' (size {} bytes)", &[&frame_id, &frame_size]);
+                if let Some(next) = recover_frames(&buffer, pos, 3, start_offset, &problem, diagnostics)
+                {
+                    writeln!(
+                        output,
+                        "  {}",
+                        tr_fmt("Skipped corrupt data at position {}, resuming at position {}", &[&format!("0x{:08X}", pos), &format!("0x{:08X}", next)])
+                    )?;
+                    pos = next;
+                    continue;
+                }
+            }
+            writeln!(output, "  {}", tr_fmt("Frame '{}' size ({} bytes) exceeds remaining buffer, stopping", &[&frame_id, &frame_size]))?;
             break;
         }
 
@@ -300,15 +512,26 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
         );
 
         // Use the unified frame header display function
-        crate::id3v2::tools::display_frame_header(&mut std::io::stdout(), &temp_frame, "    ")?;
+        crate::id3v2::tools::display_frame_header(output, &temp_frame, "    ")?;
+        if options.show_spec == true &&
+            let Some(reference) = get_frame_spec_reference(frame_id, 3)
+        {
+            writeln!(output, "    {}", tr_fmt("Specification: {}", &[&reference]))?;
+        }
 
         // Parse the frame using the new typed system
         match parse_id3v2_3_frame(&buffer, pos)
         {
-            | Some(frame) =>
+            | Some(mut frame) =>
             {
+                // Selected chapter frames only show their selected sub-frames
+                if let Some(ref filter) = options.filter
+                {
+                    select_frame(filter, &mut frame);
+                }
+
                 // Display frame content differently based on dump flag
-                if options.show_dump == true
+                if options.dump_layout(&[&frame.id]) == true
                 {
                     // For frames with embedded content, handle display manually
                     if let Some(content) = &frame.content
@@ -318,148 +541,148 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
                             | crate::id3v2::frame::Id3v2FrameContent::Chapter(chapter_frame) =>
                             {
                                 // Display chapter info
-                                println!("    Element ID: \"{}\"", chapter_frame.element_id);
+                                writeln!(output, "    {}", tr_fmt("Element ID: \"{}\"", &[&chapter_frame.element_id]))?;
                                 let start_formatted = crate::id3v2::frames::chapter::format_timestamp(chapter_frame.start_time);
                                 let end_formatted = crate::id3v2::frames::chapter::format_timestamp(chapter_frame.end_time);
                                 let duration_formatted = crate::id3v2::frames::chapter::format_timestamp(chapter_frame.duration());
-                                println!("    Time: {} - {} (duration: {})", start_formatted, end_formatted, duration_formatted);
+                                writeln!(output, "    {}", tr_fmt("Time: {} - {} (duration: {})", &[&start_formatted, &end_formatted, &duration_formatted]))?;
                                 if chapter_frame.has_byte_offsets() == true
                                 {
-                                    println!("    Byte offsets: {} - {}", chapter_frame.start_offset, chapter_frame.end_offset);
+                                    writeln!(output, "    {}", tr_fmt("Byte offsets: {} - {}", &[&chapter_frame.start_offset, &chapter_frame.end_offset]))?;
                                 }
 
                                 // Show main frame raw data first
-                                println!("    Raw data:");
-                                let hexdump = crate::hexdump::format_hexdump(&frame.data, 0);
-                                for line in hexdump.lines()
+                                if options.dumps(&[&frame.id]) == true
                                 {
-                                    println!("    {}", line);
+                                    writeln!(output, "    {}", tr("Raw data:"))?;
+                                    for line in crate::hexdump::format_hexdump_annotated(&frame.data, 0, options.dump_limit.bytes(), &frame.field_spans()).lines()
+                                    {
+                                        writeln!(output, "    {}", line)?;
+                                    }
+                                    writeln!(output)?;
                                 }
-                                println!();
 
                                 if chapter_frame.sub_frames.is_empty() == false
                                 {
-                                    println!("    Sub-frames: {} embedded frame(s)", chapter_frame.sub_frames.len());
-                                    println!();
+                                    writeln!(output, "    {}", tr_fmt("Sub-frames: {} embedded frame(s)", &[&chapter_frame.sub_frames.len()]))?;
+                                    writeln!(output)?;
 
                                     for sub_frame in &chapter_frame.sub_frames
                                     {
                                         // Display embedded frame with hexdump (includes trailing newline)
-                                        print!("{}", crate::id3v2::frames::chapter::display_embedded_frame_with_dump(sub_frame, "        "));
+                                        let dump = options.dumps(&[&frame.id, &sub_frame.id]).then_some(options.dump_limit);
+                                        write!(output, "{}", crate::id3v2::frames::chapter::display_embedded_frame_with_dump(sub_frame, "        ", dump))?;
                                     }
                                 }
                             }
                             | crate::id3v2::frame::Id3v2FrameContent::TableOfContents(toc_frame) =>
                             {
                                 // Display TOC info
-                                println!("    Element ID: \"{}\"", toc_frame.element_id);
+                                writeln!(output, "    {}", tr_fmt("Element ID: \"{}\"", &[&toc_frame.element_id]))?;
                                 if toc_frame.top_level == true
                                 {
-                                    println!("    Flags: Top-level TOC");
+                                    writeln!(output, "    {}", tr("Flags: Top-level TOC"))?;
                                 }
                                 if toc_frame.ordered == true
                                 {
-                                    println!("    Flags: Ordered");
+                                    writeln!(output, "    {}", tr("Flags: Ordered"))?;
                                 }
 
                                 if toc_frame.child_element_ids.is_empty() == false
                                 {
-                                    print!("    Child elements ({}): ", toc_frame.child_element_ids.len());
+                                    write!(output, "    {}", tr_fmt("Child elements ({}): ", &[&toc_frame.child_element_ids.len()]))?;
                                     for (i, child_id) in toc_frame.child_element_ids.iter().enumerate()
                                     {
-                                        print!("[{}] \"{}\"", i + 1, child_id);
+                                        write!(output, "[{}] \"{}\"", i + 1, child_id)?;
                                         if i < toc_frame.child_element_ids.len() - 1
                                         {
-                                            print!(", ");
+                                            write!(output, ", ")?;
                                         }
                                     }
-                                    println!();
+                                    writeln!(output)?;
                                 }
 
                                 // Show main frame raw data first
-                                println!("    Raw data:");
-                                let hexdump = crate::hexdump::format_hexdump(&frame.data, 0);
-                                for line in hexdump.lines()
+                                if options.dumps(&[&frame.id]) == true
                                 {
-                                    println!("    {}", line);
+                                    writeln!(output, "    {}", tr("Raw data:"))?;
+                                    for line in crate::hexdump::format_hexdump_annotated(&frame.data, 0, options.dump_limit.bytes(), &frame.field_spans()).lines()
+                                    {
+                                        writeln!(output, "    {}", line)?;
+                                    }
+                                    writeln!(output)?;
                                 }
-                                println!();
 
                                 if toc_frame.sub_frames.is_empty() == false
                                 {
-                                    println!("    Sub-frames: {} embedded frame(s)", toc_frame.sub_frames.len());
-                                    println!();
+                                    writeln!(output, "    {}", tr_fmt("Sub-frames: {} embedded frame(s)", &[&toc_frame.sub_frames.len()]))?;
+                                    writeln!(output)?;
 
                                     for sub_frame in &toc_frame.sub_frames
                                     {
                                         // Display embedded frame with hexdump (includes trailing newline)
-                                        print!("{}", crate::id3v2::frames::chapter::display_embedded_frame_with_dump(sub_frame, "        "));
+                                        let dump = options.dumps(&[&frame.id, &sub_frame.id]).then_some(options.dump_limit);
+                                        write!(output, "{}", crate::id3v2::frames::chapter::display_embedded_frame_with_dump(sub_frame, "        ", dump))?;
                                     }
                                 }
                             }
                             | _ =>
                             {
                                 // Standard frame display
-                                print!("    {}", frame);
+                                write!(output, "    {}", frame)?;
 
-                                println!("    Raw data:");
-                                // Limit hexdump for APIC frames (cover art) to 128 bytes
-                                let hexdump = if frame.id == "APIC"
-                                {
-                                    crate::hexdump::format_hexdump_limited(&frame.data, 0, Some(128))
-                                }
-                                else
-                                {
-                                    crate::hexdump::format_hexdump(&frame.data, 0)
-                                };
+                                writeln!(output, "    {}", tr("Raw data:"))?;
+                                let hexdump = crate::hexdump::format_hexdump_annotated(&frame.data, 0, options.dump_limit.bytes(), &frame.field_spans());
                                 for line in hexdump.lines()
                                 {
-                                    println!("    {}", line);
+                                    writeln!(output, "    {}", line)?;
                                 }
-                                println!();
+                                writeln!(output)?;
                             }
                         }
                     }
                     else
                     {
                         // No parsed content, show standard display
-                        print!("    {}", frame);
+                        write!(output, "    {}", frame)?;
 
-                        println!("    Raw data:");
-                        // Limit hexdump for APIC frames (cover art) to 128 bytes
-                        let hexdump = if frame.id == "APIC"
-                        {
-                            crate::hexdump::format_hexdump_limited(&frame.data, 0, Some(128))
-                        }
-                        else
-                        {
-                            crate::hexdump::format_hexdump(&frame.data, 0)
-                        };
+                        writeln!(output, "    {}", tr("Raw data:"))?;
+                        let hexdump = crate::hexdump::format_hexdump_annotated(&frame.data, 0, options.dump_limit.bytes(), &frame.field_spans());
                         for line in hexdump.lines()
                         {
-                            println!("    {}", line);
+                            writeln!(output, "    {}", line)?;
                         }
-                        println!();
+                        writeln!(output)?;
                     }
                 }
                 else
                 {
                     // No dump flag, use standard Display
-                    print!("    {}", frame);
+                    write!(output, "    {}", frame)?;
+                }
+
+                if options.inspect_art == true &&
+                    let Some(crate::id3v2::frame::Id3v2FrameContent::Picture(ref picture_frame)) = frame.content
+                {
+                    for line in crate::image::inspect(&picture_frame.picture_data)
+                    {
+                        writeln!(output, "    {}", line)?;
+                    }
+                    writeln!(output)?;
                 }
             }
             | None =>
             {
-                println!("        WARNING: Failed to parse frame, showing raw info");
+                writeln!(output, "        {}", tr("WARNING: Failed to parse frame, showing raw info"))?;
 
                 let preview_len = std::cmp::min(20, frame_size as usize);
                 let preview_data = &buffer[pos + 10..pos + 10 + preview_len];
-                print!("          Raw data preview: ");
+                write!(output, "          {}", tr("Raw data preview: "))?;
                 for byte in preview_data
                 {
-                    print!("{:02X} ", byte);
+                    write!(output, "{:02X} ", byte)?;
                 }
-                println!();
+                writeln!(output)?;
             }
         }
 
@@ -467,5 +690,11 @@ pub fn dissect_id3v2_3_with_options(file: &mut File, tag_size: u32, flags: u8, o
         pos += 10 + frame_size as usize;
     }
 
+    // Zero bytes behind the last frame are padding (anything else was reported above)
+    if buffer.get(pos..).is_some_and(|rest| rest.iter().all(|&byte| byte == 0)) == true
+    {
+        writeln!(output, "  {}", tr_fmt("Padding: {} bytes at position {}", &[&(buffer.len() - pos), &format!("0x{:08X}", pos)]))?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,339 @@
+use std::fmt;
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::tools::decode_synchsafe_int
+};
+
+/// Lookup table for the ID3v2 CRC-32 (ISO 3309, reflected polynomial 0xEDB88320)
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256
+    {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8
+        {
+            crc = if crc & 1 != 0
+            {
+                (crc >> 1) ^ 0xEDB8_8320
+            }
+            else
+            {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// CRC-32 as used by the ID3v2 extended header (same as zlib/PNG)
+pub fn crc32(data: &[u8]) -> u32
+{
+    !data.iter().fold(0xFFFF_FFFFu32, |crc, &byte| (crc >> 8) ^ CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize])
+}
+
+/// ID3v2.4 tag restrictions (extended header flag d)
+#[derive(Debug, Clone, Copy)]
+pub struct TagRestrictions(pub u8);
+
+impl TagRestrictions
+{
+    /// Tag size restriction (bits 7-6)
+    pub fn tag_size(&self) -> &'static str
+    {
+        match self.0 >> 6
+        {
+            | 0 => "No more than 128 frames and 1 MB total tag size",
+            | 1 => "No more than 64 frames and 128 KB total tag size",
+            | 2 => "No more than 32 frames and 40 KB total tag size",
+            | _ => "No more than 32 frames and 4 KB total tag size"
+        }
+    }
+
+    /// Text encoding restriction (bit 5)
+    pub fn text_encoding(&self) -> &'static str
+    {
+        if self.0 & 0x20 != 0
+        {
+            "Only ISO-8859-1 or UTF-8"
+        }
+        else
+        {
+            "No restrictions"
+        }
+    }
+
+    /// Text fields size restriction (bits 4-3)
+    pub fn text_fields_size(&self) -> &'static str
+    {
+        match (self.0 >> 3) & 0x03
+        {
+            | 0 => "No restrictions",
+            | 1 => "No string longer than 1024 characters",
+            | 2 => "No string longer than 128 characters",
+            | _ => "No string longer than 30 characters"
+        }
+    }
+
+    /// Image encoding restriction (bit 2)
+    pub fn image_encoding(&self) -> &'static str
+    {
+        if self.0 & 0x04 != 0
+        {
+            "Only PNG or JPEG"
+        }
+        else
+        {
+            "No restrictions"
+        }
+    }
+
+    /// Image size restriction (bits 1-0)
+    pub fn image_size(&self) -> &'static str
+    {
+        match self.0 & 0x03
+        {
+            | 0 => "No restrictions",
+            | 1 => "256x256 pixels or smaller",
+            | 2 => "64x64 pixels or smaller",
+            | _ => "Exactly 64x64 pixels, unless required otherwise"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "value": self.0,
+            "tag_size": self.tag_size(),
+            "text_encoding": self.text_encoding(),
+            "text_fields_size": self.text_fields_size(),
+            "image_encoding": self.image_encoding(),
+            "image_size": self.image_size()
+        })
+    }
+}
+
+/// ID3v2 extended header (between the tag header and the first frame)
+#[derive(Debug, Clone)]
+pub struct ExtendedHeader
+{
+    /// Major version of the tag (3 or 4)
+    pub major_version: u8,
+    /// Size of the extended header as stored in the tag
+    pub size:          u32,
+    /// Extended header flags (ID3v2.3: bit 15 = CRC data present, ID3v2.4: flag byte)
+    pub flags:         u16,
+    /// Size of the padding after the frames (ID3v2.3)
+    pub padding_size:  Option<u32>,
+    /// Tag is an update of an earlier tag (ID3v2.4)
+    pub update:        bool,
+    /// Stored CRC-32 of the frame data
+    pub crc:           Option<u32>,
+    /// CRC-32 computed over the frame data between extended header and padding
+    pub computed_crc:  Option<u32>,
+    /// Tag restrictions (ID3v2.4)
+    pub restrictions:  Option<TagRestrictions>
+}
+
+impl ExtendedHeader
+{
+    /// Parse an ID3v2.3 extended header at the start of the (unsynchronised) tag body
+    ///
+    /// Returns the header and the offset of the first frame.
+    pub fn parse_v3(buffer: &[u8]) -> Result<(Self, usize), String>
+    {
+        if buffer.len() < 10
+        {
+            return Err(tr("Buffer too small for extended header").to_string());
+        }
+
+        // The size excludes the size field itself (6 bytes, or 10 with CRC)
+        let size = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
+        let frame_start = 4 + size as usize;
+        if size < 6 || frame_start > buffer.len()
+        {
+            return Err(tr_fmt("Invalid extended header size {}", &[&size]));
+        }
+
+        let flags = u16::from_be_bytes([buffer[4], buffer[5]]);
+        let padding_size = u32::from_be_bytes([buffer[6], buffer[7], buffer[8], buffer[9]]);
+
+        let mut header =
+            ExtendedHeader { major_version: 3, size, flags, padding_size: Some(padding_size), update: false, crc: None, computed_crc: None, restrictions: None };
+        if flags & 0x8000 != 0 && size >= 10
+        {
+            header.crc = Some(u32::from_be_bytes([buffer[10], buffer[11], buffer[12], buffer[13]]));
+
+            // The CRC covers the frame data between the extended header and the padding
+            let frames_end = buffer.len().saturating_sub(padding_size as usize).max(frame_start);
+            header.computed_crc = Some(crc32(&buffer[frame_start..frames_end]));
+        }
+
+        Ok((header, frame_start))
+    }
+
+    /// Parse an ID3v2.4 extended header at the start of the tag body
+    ///
+    /// Returns the header and the offset of the first frame.
+    pub fn parse_v4(buffer: &[u8]) -> Result<(Self, usize), String>
+    {
+        if buffer.len() < 6
+        {
+            return Err(tr("Buffer too small for extended header").to_string());
+        }
+
+        // The synchsafe size covers the whole extended header including the size field
+        let size = decode_synchsafe_int(&buffer[0..4]);
+        let frame_start = size as usize;
+        if size < 6 || frame_start > buffer.len()
+        {
+            return Err(tr_fmt("Invalid extended header size {}", &[&size]));
+        }
+
+        let flag_bytes = buffer[4] as usize;
+        if flag_bytes == 0 || 5 + flag_bytes > frame_start
+        {
+            return Err(tr_fmt("Invalid number of extended flag bytes {}", &[&flag_bytes]));
+        }
+        let flags = buffer[5] as u16;
+
+        let mut header = ExtendedHeader { major_version: 4, size, flags, padding_size: None, update: false, crc: None, computed_crc: None, restrictions: None };
+
+        // Flag data follows in flag order, each item prefixed with its length
+        let mut pos = 5 + flag_bytes;
+        for flag in [0x40u16, 0x20, 0x10]
+        {
+            if flags & flag == 0
+            {
+                continue;
+            }
+            if pos >= frame_start || pos + 1 + buffer[pos] as usize > frame_start
+            {
+                return Err(tr("Extended header flag data exceeds the extended header").to_string());
+            }
+            let length = buffer[pos] as usize;
+            let data = &buffer[pos + 1..pos + 1 + length];
+            match flag
+            {
+                | 0x40 => header.update = true,
+                | 0x20 if length == 5 =>
+                {
+                    // 35-bit synchsafe CRC over the frames and padding
+                    header.crc = Some(data.iter().fold(0u64, |crc, &byte| (crc << 7) | (byte & 0x7F) as u64) as u32);
+                    header.computed_crc = Some(crc32(&buffer[frame_start..]));
+                }
+                | 0x10 if length == 1 => header.restrictions = Some(TagRestrictions(data[0])),
+                | _ => return Err(tr_fmt("Invalid extended header flag data length {}", &[&length]))
+            }
+            pos += 1 + length;
+        }
+
+        Ok((header, frame_start))
+    }
+
+    /// Whether the stored CRC matches the frame data (None if there is no CRC)
+    pub fn crc_matches(&self) -> Option<bool>
+    {
+        Some(self.crc? == self.computed_crc?)
+    }
+
+    /// Names of the set extended header flags
+    fn flag_names(&self) -> Vec<&'static str>
+    {
+        let mut names = Vec::new();
+        if self.major_version == 3
+        {
+            if self.flags & 0x8000 != 0
+            {
+                names.push("CRC data present");
+            }
+            return names;
+        }
+
+        if self.flags & 0x40 != 0
+        {
+            names.push("tag is an update");
+        }
+        if self.flags & 0x20 != 0
+        {
+            names.push("CRC data present");
+        }
+        if self.flags & 0x10 != 0
+        {
+            names.push("tag restrictions");
+        }
+        names
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "size": self.size,
+            "flags": self.flags,
+            "padding_size": self.padding_size,
+            "update": self.update,
+            "crc": self.crc,
+            "crc_valid": self.crc_matches(),
+            "restrictions": self.restrictions.map(|restrictions| restrictions.to_json())
+        })
+    }
+}
+
+impl fmt::Display for ExtendedHeader
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Extended header size: {} bytes", &[&self.size]))?;
+        let flag_names = self.flag_names();
+        let width = if self.major_version == 3
+        {
+            4
+        }
+        else
+        {
+            2
+        };
+        if flag_names.is_empty() == true
+        {
+            writeln!(f, "{}", tr_fmt("Extended flags: {}", &[&format!("0x{:0width$X}", self.flags, width = width)]))?;
+        }
+        else
+        {
+            let flag_names: Vec<&str> = flag_names.into_iter().map(tr).collect();
+            writeln!(f, "{}", tr_fmt("Extended flags: {} ({})", &[&format!("0x{:0width$X}", self.flags, width = width), &flag_names.join(", ")]))?;
+        }
+        if let Some(padding_size) = self.padding_size
+        {
+            writeln!(f, "{}", tr_fmt("Padding size: {} bytes", &[&padding_size]))?;
+        }
+        if let (Some(crc), Some(computed_crc)) = (self.crc, self.computed_crc)
+        {
+            let status = if crc == computed_crc
+            {
+                "OK"
+            }
+            else
+            {
+                "MISMATCH"
+            };
+            writeln!(f, "{}", tr_fmt("CRC-32: {} (computed {}, {})", &[&format!("0x{:08X}", crc), &format!("0x{:08X}", computed_crc), &tr(status)]))?;
+        }
+        if let Some(restrictions) = self.restrictions
+        {
+            writeln!(f, "{}", tr_fmt("Tag restrictions: {}", &[&format!("0x{:02X}", restrictions.0)]))?;
+            writeln!(f, "  {}", tr_fmt("Tag size: {}", &[&tr(restrictions.tag_size())]))?;
+            writeln!(f, "  {}", tr_fmt("Text encoding: {}", &[&tr(restrictions.text_encoding())]))?;
+            writeln!(f, "  {}", tr_fmt("Text fields size: {}", &[&tr(restrictions.text_fields_size())]))?;
+            writeln!(f, "  {}", tr_fmt("Image encoding: {}", &[&tr(restrictions.image_encoding())]))?;
+            writeln!(f, "  {}", tr_fmt("Image size: {}", &[&tr(restrictions.image_size())]))?;
+        }
+        Ok(())
+    }
+}
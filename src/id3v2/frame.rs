@@ -1,11 +1,18 @@
 use std::fmt;
 
-use crate::id3v2::{
-    frames::{
-        attached_picture::AttachedPictureFrame, chapter::ChapterFrame, comment::CommentFrame, table_of_contents::TableOfContentsFrame, text::TextFrame,
-        unique_file_id::UniqueFileIdFrame, url::UrlFrame, user_text::UserTextFrame, user_url::UserUrlFrame
+use crate::{
+    hexdump::{HexSpan, SpanBuilder},
+    i18n::{tr, tr_fmt},
+    id3v2::{
+        frames::{
+            attached_picture::AttachedPictureFrame, chapter::ChapterFrame, comment::CommentFrame, commercial::CommercialFrame,
+            encapsulated_object::EncapsulatedObjectFrame, equalisation::EqualisationFrame, music_cd_id::MusicCdIdFrame, ownership::OwnershipFrame,
+            private::PrivateFrame, relative_volume::RelativeVolumeFrame, table_of_contents::TableOfContentsFrame, terms_of_use::TermsOfUseFrame, text::TextFrame,
+            unique_file_id::UniqueFileIdFrame, url::UrlFrame, user_text::UserTextFrame, user_url::UserUrlFrame
+        },
+        tools::{decode_synchsafe_int, get_frame_description, remove_unsynchronization}
     },
-    tools::get_frame_description
+    inflate::zlib_decompress
 };
 
 /// Parsed content of an ID3v2 frame
@@ -26,14 +33,60 @@ pub enum Id3v2FrameContent
     Picture(AttachedPictureFrame),
     /// Unique file identifier (UFID)
     UniqueFileId(UniqueFileIdFrame),
+    /// Music CD identifier (MCDI)
+    MusicCdId(MusicCdIdFrame),
+    /// Relative volume adjustment (RVA2)
+    RelativeVolume(RelativeVolumeFrame),
+    /// Equalisation (EQU2)
+    Equalisation(EqualisationFrame),
+    /// Commercial frame (COMR)
+    Commercial(CommercialFrame),
+    /// Ownership frame (OWNE)
+    Ownership(OwnershipFrame),
+    /// Terms of use frame (USER)
+    TermsOfUse(TermsOfUseFrame),
     /// Chapter frame (CHAP)
     Chapter(ChapterFrame),
     /// Table of contents frame (CTOC)
     TableOfContents(TableOfContentsFrame),
+    /// General encapsulated object (GEOB)
+    EncapsulatedObject(EncapsulatedObjectFrame),
+    /// Private frame (PRIV)
+    Private(PrivateFrame),
     /// Raw binary data for unsupported/unknown frames
     Binary
 }
 
+impl Id3v2FrameContent
+{
+    /// Structured representation of the parsed fields for JSON reports, `data_offset` is the offset of the frame data (sub-frames of chapter frames
+    /// carry the text output lines of their contents if `details` is set)
+    pub fn to_json(&self, data_offset: u64, details: bool) -> serde_json::Value
+    {
+        match self
+        {
+            | Id3v2FrameContent::Text(text_frame) => text_frame.to_json(),
+            | Id3v2FrameContent::Url(url_frame) => url_frame.to_json(),
+            | Id3v2FrameContent::UserText(user_text_frame) => user_text_frame.to_json(),
+            | Id3v2FrameContent::UserUrl(user_url_frame) => user_url_frame.to_json(),
+            | Id3v2FrameContent::Comment(comment_frame) => comment_frame.to_json(),
+            | Id3v2FrameContent::Picture(picture_frame) => picture_frame.to_json(),
+            | Id3v2FrameContent::UniqueFileId(ufid_frame) => ufid_frame.to_json(),
+            | Id3v2FrameContent::MusicCdId(mcdi_frame) => mcdi_frame.to_json(),
+            | Id3v2FrameContent::RelativeVolume(rva2_frame) => rva2_frame.to_json(),
+            | Id3v2FrameContent::Equalisation(equ2_frame) => equ2_frame.to_json(),
+            | Id3v2FrameContent::Commercial(comr_frame) => comr_frame.to_json(),
+            | Id3v2FrameContent::Ownership(owne_frame) => owne_frame.to_json(),
+            | Id3v2FrameContent::TermsOfUse(user_frame) => user_frame.to_json(),
+            | Id3v2FrameContent::Chapter(chapter_frame) => chapter_frame.to_json(data_offset, details),
+            | Id3v2FrameContent::TableOfContents(toc_frame) => toc_frame.to_json(data_offset, details),
+            | Id3v2FrameContent::EncapsulatedObject(geob_frame) => geob_frame.to_json(),
+            | Id3v2FrameContent::Private(priv_frame) => priv_frame.to_json(),
+            | Id3v2FrameContent::Binary => serde_json::Value::Null
+        }
+    }
+}
+
 impl fmt::Display for Id3v2FrameContent
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
@@ -47,13 +100,75 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
             | Id3v2FrameContent::Comment(comment_frame) => write!(f, "{}", comment_frame),
             | Id3v2FrameContent::Picture(picture_frame) => write!(f, "{}", picture_frame),
             | Id3v2FrameContent::UniqueFileId(ufid_frame) => write!(f, "{}", ufid_frame),
+            | Id3v2FrameContent::MusicCdId(mcdi_frame) => write!(f, "{}", mcdi_frame),
+            | Id3v2FrameContent::RelativeVolume(rva2_frame) => write!(f, "{}", rva2_frame),
+            | Id3v2FrameContent::Equalisation(equ2_frame) => write!(f, "{}", equ2_frame),
+            | Id3v2FrameContent::Commercial(comr_frame) => write!(f, "{}", comr_frame),
+            | Id3v2FrameContent::Ownership(owne_frame) => write!(f, "{}", owne_frame),
+            | Id3v2FrameContent::TermsOfUse(user_frame) => write!(f, "{}", user_frame),
             | Id3v2FrameContent::Chapter(chapter_frame) => write!(f, "{}", chapter_frame),
             | Id3v2FrameContent::TableOfContents(toc_frame) => write!(f, "{}", toc_frame),
+            | Id3v2FrameContent::EncapsulatedObject(geob_frame) => write!(f, "{}", geob_frame),
+            | Id3v2FrameContent::Private(priv_frame) => write!(f, "{}", priv_frame),
             | Id3v2FrameContent::Binary => Ok(())
         }
     }
 }
 
+/// Payload layout announced by the frame format flags
+#[derive(Debug, Clone, Default)]
+pub struct FrameFormat
+{
+    /// Payload is zlib compressed
+    pub compressed:        bool,
+    /// Encryption method symbol (see ENCR frames) of an encrypted payload
+    pub encryption_method: Option<u8>,
+    /// Group identifier (see GRID frames) of a grouped frame
+    pub group_id:          Option<u8>,
+    /// Decompressed size (ID3v2.3) or data length indicator (ID3v2.4)
+    pub data_length:       Option<u32>,
+    /// Frame data is unsynchronised (ID3v2.4)
+    pub unsynchronised:    bool
+}
+
+impl FrameFormat
+{
+    /// Whether any format flag changes the payload layout
+    pub fn is_plain(&self) -> bool
+    {
+        self.compressed == false && self.encryption_method.is_none() && self.group_id.is_none() && self.data_length.is_none() && self.unsynchronised == false
+    }
+}
+
+impl fmt::Display for FrameFormat
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut parts = Vec::new();
+        if self.unsynchronised == true
+        {
+            parts.push(tr("unsynchronised").to_string());
+        }
+        if self.compressed == true
+        {
+            parts.push(tr("compressed").to_string());
+        }
+        if let Some(method) = self.encryption_method
+        {
+            parts.push(tr_fmt("encrypted (method {})", &[&format!("0x{:02X}", method)]));
+        }
+        if let Some(group_id) = self.group_id
+        {
+            parts.push(tr_fmt("group {}", &[&format!("0x{:02X}", group_id)]));
+        }
+        if let Some(data_length) = self.data_length
+        {
+            parts.push(tr_fmt("data length {} bytes", &[&data_length]));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
 /// ID3v2 frame representation for all versions
 #[derive(Debug, Clone)]
 pub struct Id3v2Frame
@@ -71,7 +186,9 @@ pub struct Id3v2Frame
     /// Parsed frame content (if successfully parsed)
     pub content:         Option<Id3v2FrameContent>,
     /// Embedded sub-frames (for CHAP and CTOC frames)
-    pub embedded_frames: Option<Vec<Id3v2Frame>>
+    pub embedded_frames: Option<Vec<Id3v2Frame>>,
+    /// Data added in front of the payload by the format flags
+    pub format:          FrameFormat
 }
 
 impl Id3v2Frame
@@ -79,7 +196,100 @@ impl Id3v2Frame
     /// Create a new ID3v2 frame with offset information
     pub fn new_with_offset(id: String, size: u32, flags: u16, offset: usize, data: Vec<u8>) -> Self
     {
-        Self { id, size, flags, offset: Some(offset), data, content: None, embedded_frames: None }
+        Self { id, size, flags, offset: Some(offset), data, content: None, embedded_frames: None, format: FrameFormat::default() }
+    }
+
+    /// Strip the data the format flags add in front of the payload and decompress compressed payloads
+    ///
+    /// Afterwards `data` holds the payload the frame content is parsed from (still encrypted for encrypted frames).
+    /// `format.unsynchronised` may be set in advance for ID3v2.4 tags with the unsynchronisation header flag.
+    fn decode_format(&mut self, version_major: u8) -> Result<(), String>
+    {
+        // ID3v2.4 unsynchronises each frame on its own, covering all data after the frame header
+        let unsynchronised = version_major == 4 && (self.flags & 0x0002 != 0 || self.format.unsynchronised == true);
+        if unsynchronised == true
+        {
+            self.data = remove_unsynchronization(&self.data);
+        }
+
+        // ID3v2.3: %ijk00000 (compression, encryption, grouping), added data in that order
+        // ID3v2.4: %0h00kmnp (grouping, compression, encryption, unsynchronisation, data length indicator),
+        // added data in the order grouping, encryption, data length indicator
+        let (compressed, encrypted, grouped) = if version_major == 4
+        {
+            (self.flags & 0x0008 != 0, self.flags & 0x0004 != 0, self.flags & 0x0040 != 0)
+        }
+        else
+        {
+            (self.flags & 0x0080 != 0, self.flags & 0x0040 != 0, self.flags & 0x0020 != 0)
+        };
+        let has_data_length = if version_major == 4
+        {
+            self.flags & 0x0001 != 0
+        }
+        else
+        {
+            compressed
+        };
+
+        let added_size = has_data_length as usize * 4 + encrypted as usize + grouped as usize;
+        if self.data.len() < added_size
+        {
+            return Err(tr_fmt("Frame too short for the {} bytes announced by its format flags", &[&added_size]));
+        }
+
+        let mut position = 0;
+        let mut format = FrameFormat { compressed, unsynchronised, ..FrameFormat::default() };
+        if version_major == 4
+        {
+            if grouped == true
+            {
+                format.group_id = Some(self.data[position]);
+                position += 1;
+            }
+            if encrypted == true
+            {
+                format.encryption_method = Some(self.data[position]);
+                position += 1;
+            }
+            if has_data_length == true
+            {
+                format.data_length = Some(decode_synchsafe_int(&self.data[position..position + 4]));
+                position += 4;
+            }
+        }
+        else
+        {
+            if has_data_length == true
+            {
+                format.data_length = Some(u32::from_be_bytes([self.data[position], self.data[position + 1], self.data[position + 2], self.data[position + 3]]));
+                position += 4;
+            }
+            if encrypted == true
+            {
+                format.encryption_method = Some(self.data[position]);
+                position += 1;
+            }
+            if grouped == true
+            {
+                format.group_id = Some(self.data[position]);
+                position += 1;
+            }
+        }
+
+        if position > 0
+        {
+            self.data.drain(..position);
+        }
+        self.format = format;
+
+        // Encrypted payloads cannot be decompressed without decrypting them first
+        if compressed == true && encrypted == false
+        {
+            self.data = zlib_decompress(&self.data).map_err(|e| tr_fmt("Failed to decompress frame: {}", &[&e]))?;
+        }
+
+        Ok(())
     }
 
     /// Parse frame content based on frame ID
@@ -93,16 +303,24 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
             return Ok(());
         }
 
+        self.decode_format(version_major)?;
+        if self.format.encryption_method.is_some()
+        {
+            // Encrypted payload, the content cannot be parsed
+            self.content = Some(Id3v2FrameContent::Binary);
+            return Ok(());
+        }
+
         let content = match self.id.as_str()
         {
-            // Text information frames
-            | id if id.starts_with('T') && id != "TXXX" =>
+            // Text information frames; the iTunes podcast feed URL is stored as text too
+            | id if (id.starts_with('T') && id != "TXXX") || id == "WFED" =>
             {
                 let text_frame = TextFrame::parse(&self.data)?;
                 // Validate text encoding for this ID3v2 version
                 if text_frame.encoding.is_valid_for_version(version_major) == false
                 {
-                    return Err(format!("Text encoding {:?} is not valid for ID3v2.{}", text_frame.encoding, version_major));
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", text_frame.encoding), &version_major]));
                 }
                 Id3v2FrameContent::Text(text_frame)
             }
@@ -115,7 +333,7 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
                 // Validate text encoding for this ID3v2 version
                 if user_text_frame.encoding.is_valid_for_version(version_major) == false
                 {
-                    return Err(format!("Text encoding {:?} is not valid for ID3v2.{}", user_text_frame.encoding, version_major));
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", user_text_frame.encoding), &version_major]));
                 }
                 Id3v2FrameContent::UserText(user_text_frame)
             }
@@ -125,7 +343,7 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
                 // Validate text encoding for this ID3v2 version
                 if user_url_frame.encoding.is_valid_for_version(version_major) == false
                 {
-                    return Err(format!("Text encoding {:?} is not valid for ID3v2.{}", user_url_frame.encoding, version_major));
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", user_url_frame.encoding), &version_major]));
                 }
                 Id3v2FrameContent::UserUrl(user_url_frame)
             }
@@ -136,7 +354,7 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
                 // Validate text encoding for this ID3v2 version
                 if comment_frame.encoding.is_valid_for_version(version_major) == false
                 {
-                    return Err(format!("Text encoding {:?} is not valid for ID3v2.{}", comment_frame.encoding, version_major));
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", comment_frame.encoding), &version_major]));
                 }
                 Id3v2FrameContent::Comment(comment_frame)
             }
@@ -147,12 +365,60 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
                 // Validate text encoding for this ID3v2 version
                 if picture_frame.encoding.is_valid_for_version(version_major) == false
                 {
-                    return Err(format!("Text encoding {:?} is not valid for ID3v2.{}", picture_frame.encoding, version_major));
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", picture_frame.encoding), &version_major]));
                 }
                 Id3v2FrameContent::Picture(picture_frame)
             }
             // Unique file identifier (no encoding)
             | "UFID" => Id3v2FrameContent::UniqueFileId(UniqueFileIdFrame::parse(&self.data)?),
+            // Music CD identifier (binary table of contents)
+            | "MCDI" => Id3v2FrameContent::MusicCdId(MusicCdIdFrame::parse(&self.data)?),
+            // Relative volume and equalisation (ID3v2.4)
+            | "RVA2" => Id3v2FrameContent::RelativeVolume(RelativeVolumeFrame::parse(&self.data)?),
+            | "EQU2" => Id3v2FrameContent::Equalisation(EqualisationFrame::parse(&self.data)?),
+            // Private and encapsulated data (DJ software stores cue points here)
+            | "PRIV" => Id3v2FrameContent::Private(PrivateFrame::parse(&self.data)?),
+            | "GEOB" =>
+            {
+                let object_frame = EncapsulatedObjectFrame::parse(&self.data)?;
+                // Validate text encoding for this ID3v2 version
+                if object_frame.encoding.is_valid_for_version(version_major) == false
+                {
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", object_frame.encoding), &version_major]));
+                }
+                Id3v2FrameContent::EncapsulatedObject(object_frame)
+            }
+            // Commercial and legal frames
+            | "COMR" =>
+            {
+                let commercial_frame = CommercialFrame::parse(&self.data)?;
+                // Validate text encoding for this ID3v2 version
+                if commercial_frame.encoding.is_valid_for_version(version_major) == false
+                {
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", commercial_frame.encoding), &version_major]));
+                }
+                Id3v2FrameContent::Commercial(commercial_frame)
+            }
+            | "OWNE" =>
+            {
+                let ownership_frame = OwnershipFrame::parse(&self.data)?;
+                // Validate text encoding for this ID3v2 version
+                if ownership_frame.encoding.is_valid_for_version(version_major) == false
+                {
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", ownership_frame.encoding), &version_major]));
+                }
+                Id3v2FrameContent::Ownership(ownership_frame)
+            }
+            | "USER" =>
+            {
+                let terms_frame = TermsOfUseFrame::parse(&self.data)?;
+                // Validate text encoding for this ID3v2 version
+                if terms_frame.encoding.is_valid_for_version(version_major) == false
+                {
+                    return Err(tr_fmt("Text encoding {} is not valid for ID3v2.{}", &[&format!("{:?}", terms_frame.encoding), &version_major]));
+                }
+                Id3v2FrameContent::TermsOfUse(terms_frame)
+            }
             // Chapter frames (may contain sub-frames with their own validation)
             | "CHAP" => Id3v2FrameContent::Chapter(ChapterFrame::parse(&self.data, version_major)?),
             | "CTOC" => Id3v2FrameContent::TableOfContents(TableOfContentsFrame::parse(&self.data, version_major)?),
@@ -164,6 +430,38 @@ pub fn parse_content(&mut self, version_major: u8) -> Result<(), String>
         Ok(())
     }
 
+    /// Structured representation of this frame for JSON reports, `base_offset` is added to the frame offset; the text output lines of the parsed content
+    /// are added if `details` is set
+    pub fn to_json(&self, base_offset: u64, details: bool) -> serde_json::Value
+    {
+        let data_offset = base_offset + self.offset.unwrap_or(0) as u64 + 10;
+
+        let mut json = serde_json::json!({
+            "id": self.id,
+            "description": get_frame_description(&self.id),
+            "offset": self.offset.map(|offset| base_offset + offset as u64),
+            "size": self.size,
+            "flags": self.flags,
+            "fields": self.content.as_ref().map(|content| content.to_json(data_offset, details))
+        });
+        if details == true
+        {
+            let lines: Vec<String> = self.content.as_ref().map(|content| content.to_string().lines().map(str::to_string).collect()).unwrap_or_default();
+            json["details"] = serde_json::json!(lines);
+        }
+        if self.format.is_plain() == false
+        {
+            json["format"] = serde_json::json!({
+                "compressed": self.format.compressed,
+                "encryption_method": self.format.encryption_method,
+                "group_id": self.format.group_id,
+                "data_length": self.format.data_length,
+                "unsynchronised": self.format.unsynchronised
+            });
+        }
+        json
+    }
+
     /// Get text content if this is a text frame
     pub fn get_text(&self) -> Option<&str>
     {
@@ -176,6 +474,119 @@ pub fn get_text(&self) -> Option<&str>
         }
     }
 
+    /// Embedded sub-frames of CHAP and CTOC frames
+    pub fn sub_frames_mut(&mut self) -> Option<&mut Vec<Id3v2Frame>>
+    {
+        match &mut self.content
+        {
+            | Some(Id3v2FrameContent::Chapter(chapter_frame)) => Some(&mut chapter_frame.sub_frames),
+            | Some(Id3v2FrameContent::TableOfContents(toc_frame)) => Some(&mut toc_frame.sub_frames),
+            | _ => None
+        }
+    }
+
+    /// Field layout of the frame data, highlighted in hexdumps (empty for unparsed or encrypted frames)
+    pub fn field_spans(&self) -> Vec<HexSpan>
+    {
+        if matches!(self.content, None | Some(Id3v2FrameContent::Binary)) == true
+        {
+            return Vec::new();
+        }
+
+        let mut spans = SpanBuilder::new(&self.data);
+        match self.id.as_str()
+        {
+            | "TXXX" | "WXXX" =>
+            {
+                let wide = encoding_field(&mut spans);
+                spans.terminated("Description", wide);
+                spans.rest(
+                    if self.id == "TXXX"
+                    {
+                        "Value"
+                    }
+                    else
+                    {
+                        "URL"
+                    }
+                );
+            }
+            | id if id.starts_with('T') || id == "WFED" =>
+            {
+                encoding_field(&mut spans);
+                spans.rest("Text");
+            }
+            | id if id.starts_with('W') => spans.rest("URL"),
+            | "COMM" | "USLT" =>
+            {
+                let wide = encoding_field(&mut spans);
+                spans.field(3, "Language");
+                spans.terminated("Content descriptor", wide);
+                spans.rest("Text");
+            }
+            | "APIC" =>
+            {
+                let wide = encoding_field(&mut spans);
+                spans.terminated("MIME type", false);
+                spans.field(1, "Picture type");
+                spans.terminated("Description", wide);
+                spans.rest("Picture data");
+            }
+            | "GEOB" =>
+            {
+                let wide = encoding_field(&mut spans);
+                spans.terminated("MIME type", false);
+                spans.terminated("Filename", wide);
+                spans.terminated("Content descriptor", wide);
+                spans.rest("Encapsulated object");
+            }
+            | "UFID" | "PRIV" =>
+            {
+                spans.terminated("Owner identifier", false);
+                spans.rest(
+                    if self.id == "UFID"
+                    {
+                        "Identifier"
+                    }
+                    else
+                    {
+                        "Private data"
+                    }
+                );
+            }
+            | "CHAP" =>
+            {
+                spans.terminated("Element ID", false);
+                spans.field(4, "Start time");
+                spans.field(4, "End time");
+                spans.field(4, "Start offset");
+                spans.field(4, "End offset");
+                spans.rest("Embedded frames");
+            }
+            | "CTOC" =>
+            {
+                spans.terminated("Element ID", false);
+                spans.field(1, "Flags");
+                let entry_count = spans.peek().unwrap_or(0);
+                spans.field(1, "Entry count");
+                for _ in 0..entry_count
+                {
+                    spans.terminated("Child element ID", false);
+                }
+                spans.rest("Embedded frames");
+            }
+            | "POPM" =>
+            {
+                spans.terminated("Email to user", false);
+                spans.field(1, "Rating");
+                spans.rest("Play counter");
+            }
+            | "PCNT" => spans.rest("Play counter"),
+            | _ => ()
+        }
+        spans.finish()
+    }
+
     /// Get URL if this is a URL frame
     pub fn get_url(&self) -> Option<&str>
     {
@@ -192,12 +603,15 @@ impl fmt::Display for Id3v2Frame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        write!(f, "Frame: {} ({})", self.id, get_frame_description(&self.id))?;
-        write!(f, " - Size: {} bytes", self.size)?;
+        write!(f, "{}", tr_fmt("Frame: {} ({}) - Size: {} bytes", &[&self.id, &tr(get_frame_description(&self.id)), &self.size]))?;
 
         if self.flags != 0
         {
-            write!(f, " - Flags: 0x{:04X}", self.flags)?;
+            write!(f, " - {}", tr_fmt("Flags: {}", &[&format!("0x{:04X}", self.flags)]))?;
+            if self.format.is_plain() == false
+            {
+                write!(f, " ({})", self.format)?;
+            }
         }
 
         // Show detailed parsed content using the frame's own Display implementation
@@ -225,23 +639,31 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
             {
                 if text.is_empty() == false
                 {
-                    write!(f, " - Text: \"{}\"", text)?;
+                    write!(f, " {}", tr_fmt("- Text: \"{}\"", &[&text]))?;
                 }
             }
             else if let Some(url) = self.get_url() &&
                 !url.is_empty()
             {
-                write!(f, " - URL: \"{}\"", url)?;
+                write!(f, " {}", tr_fmt("- URL: \"{}\"", &[&url]))?;
             }
         }
 
         if let Some(embedded) = &self.embedded_frames &&
             !embedded.is_empty()
         {
-            writeln!(f, "    {} embedded sub-frame(s)", embedded.len())?;
+            writeln!(f, "    {}", tr_fmt("{} embedded sub-frame(s)", &[&embedded.len()]))?;
         }
 
         writeln!(f)?; // Add newline at the end of frame display
         Ok(())
     }
 }
+
+/// Text encoding byte of a frame, returns whether its strings end with two null bytes
+fn encoding_field(spans: &mut SpanBuilder) -> bool
+{
+    let wide = matches!(spans.peek(), Some(1 | 2));
+    spans.field(1, "Text encoding");
+    wide
+}
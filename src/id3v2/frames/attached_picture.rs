@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
 /// Attached Picture Frame (APIC)
 ///
 /// Structure: Text encoding + MIME type + Picture type + Description + Picture data
@@ -22,7 +23,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 2
         {
-            return Err("Picture frame data too short".to_string());
+            return Err(tr("Picture frame data too short").to_string());
         }
 
         let encoding = TextEncoding::from_byte(data[0])?;
@@ -36,7 +37,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         }
         if pos >= data.len()
         {
-            return Err("Picture frame MIME type not null-terminated".to_string());
+            return Err(tr("Picture frame MIME type not null-terminated").to_string());
         }
         let mime_type = decode_iso88591_string(&data[mime_start..pos]);
         pos += 1; // Skip null terminator
@@ -44,7 +45,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         // Picture type (1 byte)
         if pos >= data.len()
         {
-            return Err("Picture frame missing picture type".to_string());
+            return Err(tr("Picture frame missing picture type").to_string());
         }
         let picture_type = data[pos];
         pos += 1;
@@ -64,7 +65,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         }
         if pos + terminator_len > data.len()
         {
-            return Err("Picture frame description not properly terminated".to_string());
+            return Err(tr("Picture frame description not properly terminated").to_string());
         }
 
         let description = decode_text_with_encoding_simple(&data[desc_start..pos], encoding)?;
@@ -105,20 +106,32 @@ pub fn picture_type_description(&self) -> &'static str
             | _ => "Unknown"
         }
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "encoding": self.encoding as u8,
+            "mime_type": self.mime_type,
+            "picture_type": self.picture_type,
+            "description": self.description,
+            "picture_size": self.picture_data.len()
+        })
+    }
 }
 
 impl fmt::Display for AttachedPictureFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Encoding: {}", self.encoding)?;
-        writeln!(f, "MIME type: {}", self.mime_type)?;
-        writeln!(f, "Picture type: {} ({})", self.picture_type, self.picture_type_description())?;
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("MIME type: {}", &[&self.mime_type]))?;
+        writeln!(f, "{}", tr_fmt("Picture type: {} ({})", &[&self.picture_type, &self.picture_type_description()]))?;
         if self.description.is_empty() == false
         {
-            writeln!(f, "Description: \"{}\"", self.description)?;
+            writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
         }
-        writeln!(f, "Data size: {} bytes", self.picture_data.len())?;
+        writeln!(f, "{}", tr_fmt("Data size: {} bytes", &[&self.picture_data.len()]))?;
         Ok(())
     }
 }
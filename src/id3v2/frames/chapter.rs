@@ -5,7 +5,11 @@
 /// Structure: Element ID + Start time + End time + Start offset + End offset + Sub-frames
 /// Part of ID3v2 Chapter Frame Addendum specification
 use crate::id3v2::text_encoding::decode_iso88591_string;
-use crate::id3v2::{frame::Id3v2Frame, tools::get_frame_description};
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::{frame::Id3v2Frame, tools::get_frame_description},
+    options::DumpLimit
+};
 
 /// Format a timestamp from milliseconds to "hh:mm:ss.ms" format
 pub fn format_timestamp(ms: u32) -> String
@@ -43,7 +47,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("Chapter frame data is empty".to_string());
+            return Err(tr("Chapter frame data is empty").to_string());
         }
 
         let mut pos = 0;
@@ -56,7 +60,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         }
         if pos >= data.len()
         {
-            return Err("Chapter frame element ID not null-terminated".to_string());
+            return Err(tr("Chapter frame element ID not null-terminated").to_string());
         }
         let element_id = decode_iso88591_string(&data[element_id_start..pos]);
         pos += 1; // Skip null terminator
@@ -64,7 +68,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // Start time (4 bytes)
         if pos + 4 > data.len()
         {
-            return Err("Chapter frame missing start time".to_string());
+            return Err(tr("Chapter frame missing start time").to_string());
         }
         let start_time = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
         pos += 4;
@@ -72,7 +76,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // End time (4 bytes)
         if pos + 4 > data.len()
         {
-            return Err("Chapter frame missing end time".to_string());
+            return Err(tr("Chapter frame missing end time").to_string());
         }
         let end_time = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
         pos += 4;
@@ -80,7 +84,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // Start offset (4 bytes)
         if pos + 4 > data.len()
         {
-            return Err("Chapter frame missing start offset".to_string());
+            return Err(tr("Chapter frame missing start offset").to_string());
         }
         let start_offset = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
         pos += 4;
@@ -88,7 +92,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // End offset (4 bytes)
         if pos + 4 > data.len()
         {
-            return Err("Chapter frame missing end offset".to_string());
+            return Err(tr("Chapter frame missing end offset").to_string());
         }
         let end_offset = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
         pos += 4;
@@ -106,6 +110,12 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         Ok(ChapterFrame { element_id, start_time, end_time, start_offset, end_offset, sub_frames })
     }
 
+    /// Position of the first sub-frame in the frame data (after element ID, times and offsets)
+    pub fn sub_frames_start(&self) -> usize
+    {
+        self.element_id.chars().count() + 1 + 16
+    }
+
     /// Check if byte offsets are used (not 0xFFFFFFFF)
     pub fn has_byte_offsets(&self) -> bool
     {
@@ -117,24 +127,39 @@ pub fn duration(&self) -> u32
     {
         self.end_time.saturating_sub(self.start_time)
     }
+
+    /// Structured representation for JSON reports, `data_offset` is the offset of the frame data; sub-frames carry the text output lines of their
+    /// contents if `details` is set
+    pub fn to_json(&self, data_offset: u64, details: bool) -> serde_json::Value
+    {
+        let sub_frames_offset = data_offset + self.sub_frames_start() as u64;
+        serde_json::json!({
+            "element_id": self.element_id,
+            "start_time": self.start_time,
+            "end_time": self.end_time,
+            "start_offset": self.start_offset,
+            "end_offset": self.end_offset,
+            "sub_frames": self.sub_frames.iter().map(|sub_frame| sub_frame.to_json(sub_frames_offset, details)).collect::<Vec<_>>()
+        })
+    }
 }
 
 impl fmt::Display for ChapterFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Element ID: \"{}\"", self.element_id)?;
+        writeln!(f, "{}", tr_fmt("Element ID: \"{}\"", &[&self.element_id]))?;
         let start_formatted = format_timestamp(self.start_time);
         let end_formatted = format_timestamp(self.end_time);
         let duration_formatted = format_timestamp(self.duration());
-        writeln!(f, "Time: {} - {} (duration: {})", start_formatted, end_formatted, duration_formatted)?;
+        writeln!(f, "{}", tr_fmt("Time: {} - {} (duration: {})", &[&start_formatted, &end_formatted, &duration_formatted]))?;
         if self.has_byte_offsets() == true
         {
-            writeln!(f, "Byte offsets: {} - {}", self.start_offset, self.end_offset)?;
+            writeln!(f, "{}", tr_fmt("Byte offsets: {} - {}", &[&self.start_offset, &self.end_offset]))?;
         }
         if self.sub_frames.is_empty() == false
         {
-            writeln!(f, "Sub-frames: {} embedded frame(s)", self.sub_frames.len())?;
+            writeln!(f, "{}", tr_fmt("Sub-frames: {} embedded frame(s)", &[&self.sub_frames.len()]))?;
             writeln!(f)?; // Add newline before first embedded frame
             for (i, sub_frame) in self.sub_frames.iter().enumerate()
             {
@@ -159,7 +184,7 @@ pub fn display_embedded_frame_content(f: &mut fmt::Formatter<'_>, frame: &Id3v2F
     if crate::id3v2::tools::display_frame_header(&mut buffer, frame, "        ").is_err()
     {
         // Fallback to basic display if header function fails
-        writeln!(f, "        Frame: {} - Size: {} bytes", frame.id, frame.size)?;
+        writeln!(f, "        {}", tr_fmt("Frame: {} - Size: {} bytes", &[&frame.id, &frame.size]))?;
     }
     else
     {
@@ -171,7 +196,7 @@ pub fn display_embedded_frame_content(f: &mut fmt::Formatter<'_>, frame: &Id3v2F
     }
 
     // Format embedded frames like top-level frames but with embedded indentation
-    writeln!(f, "        Frame: {} ({}) - Size: {} bytes", frame.id, get_frame_description(&frame.id), frame.size)?;
+    writeln!(f, "        {}", tr_fmt("Frame: {} ({}) - Size: {} bytes", &[&frame.id, &tr(get_frame_description(&frame.id)), &frame.size]))?;
 
     if let Some(content) = &frame.content
     {
@@ -196,19 +221,21 @@ pub fn display_embedded_frame_content(f: &mut fmt::Formatter<'_>, frame: &Id3v2F
         {
             if text.is_empty() == false
             {
-                writeln!(f, "            Text: \"{}\"", text)?;
+                writeln!(f, "            {}", tr_fmt("Text: \"{}\"", &[&text]))?;
             }
         }
         else if let Some(url) = frame.get_url()
         {
-            writeln!(f, "            URL: \"{}\"", url)?;
+            writeln!(f, "            {}", tr_fmt("URL: \"{}\"", &[&url]))?;
         }
     }
     Ok(())
 }
 
 /// Helper function to display embedded frame content with hexdump
-pub fn display_embedded_frame_with_dump(frame: &Id3v2Frame, indent: &str) -> String
+///
+/// `dump` is the limit of the hexdump, None shows the frame without it (sub-frames not selected by `--dump-path`).
+pub fn display_embedded_frame_with_dump(frame: &Id3v2Frame, indent: &str, dump: Option<DumpLimit>) -> String
 {
     let mut output = String::new();
 
@@ -221,7 +248,7 @@ pub fn display_embedded_frame_with_dump(frame: &Id3v2Frame, indent: &str) -> Str
     }
 
     // Display frame info
-    output.push_str(&format!("{}Frame: {} ({}) - Size: {} bytes\n", indent, frame.id, get_frame_description(&frame.id), frame.size));
+    output.push_str(&format!("{}{}\n", indent, tr_fmt("Frame: {} ({}) - Size: {} bytes", &[&frame.id, &tr(get_frame_description(&frame.id)), &frame.size])));
 
     // Display content
     if let Some(content) = &frame.content
@@ -246,31 +273,23 @@ pub fn display_embedded_frame_with_dump(frame: &Id3v2Frame, indent: &str) -> Str
         {
             if text.is_empty() == false
             {
-                output.push_str(&format!("{}    Text: \"{}\"\n", indent, text));
+                output.push_str(&format!("{}    {}\n", indent, tr_fmt("Text: \"{}\"", &[&text])));
             }
         }
         else if let Some(url) = frame.get_url()
         {
-            output.push_str(&format!("{}    URL: \"{}\"\n", indent, url));
+            output.push_str(&format!("{}    {}\n", indent, tr_fmt("URL: \"{}\"", &[&url])));
         }
     }
 
     // Display hexdump
-    output.push_str(&format!("{}    Raw data:\n", indent));
-
-    // Limit hexdump for APIC frames (cover art) to 128 bytes
-    let hexdump = if frame.id == "APIC"
+    if let Some(limit) = dump
     {
-        crate::hexdump::format_hexdump_limited(&frame.data, 0, Some(128))
-    }
-    else
-    {
-        crate::hexdump::format_hexdump(&frame.data, 0)
-    };
-
-    for line in hexdump.lines()
-    {
-        output.push_str(&format!("{}    {}\n", indent, line));
+        output.push_str(&format!("{}    {}\n", indent, tr("Raw data:")));
+        for line in crate::hexdump::format_hexdump_annotated(&frame.data, 0, limit.bytes(), &frame.field_spans()).lines()
+        {
+            output.push_str(&format!("{}    {}\n", indent, line));
+        }
     }
 
     // Add newline for separation between embedded frames
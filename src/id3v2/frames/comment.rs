@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
 /// Comment Frame (COMM, USLT)
 ///
 /// Structure: Text encoding + Language + Short description + Full text
@@ -21,7 +22,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 5
         {
-            return Err("Comment frame data too short".to_string());
+            return Err(tr("Comment frame data too short").to_string());
         }
 
         let encoding = TextEncoding::from_byte(data[0])?;
@@ -35,19 +36,25 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(CommentFrame { encoding, language, description, text })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "language": self.language, "description": self.description, "text": self.text })
+    }
 }
 
 impl fmt::Display for CommentFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Encoding: {}", self.encoding)?;
-        writeln!(f, "Language: \"{}\"", self.language)?;
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Language: \"{}\"", &[&self.language]))?;
         if self.description.is_empty() == false
         {
-            writeln!(f, "Description: \"{}\"", self.description)?;
+            writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
         }
-        writeln!(f, "Text: \"{}\"", self.text)?;
+        writeln!(f, "{}", tr_fmt("Text: \"{}\"", &[&self.text]))?;
         Ok(())
     }
 }
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+/// Commercial Frame (COMR)
+///
+/// Structure: Text encoding + Price string + Valid until + Contact URL + Received as + Name of seller + Description + Picture MIME type + Seller logo
+use crate::id3v2::text_encoding::{TextEncoding, decode_text_with_encoding_simple, find_text_terminator, split_iso88591_terminated};
+
+#[derive(Debug, Clone)]
+pub struct CommercialFrame
+{
+    pub encoding:    TextEncoding,
+    /// Prices as currency code (ISO 4217) followed by the amount, separated by '/' (e.g. "USD9.99/EUR8.99")
+    pub price:       String,
+    /// Date as YYYYMMDD
+    pub valid_until: String,
+    pub contact_url: String,
+    pub received_as: u8,
+    pub seller:      String,
+    pub description: String,
+    pub mime_type:   String,
+    pub seller_logo: Vec<u8>
+}
+
+impl CommercialFrame
+{
+    /// Parse a COMR frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 2
+        {
+            return Err(tr("Commercial frame data too short").to_string());
+        }
+
+        let encoding = TextEncoding::from_byte(data[0])?;
+        let (price, rest) = split_iso88591_terminated(&data[1..]).ok_or(tr("Commercial frame price not null-terminated"))?;
+        if rest.len() < 9
+        {
+            return Err(tr("Commercial frame missing valid-until date").to_string());
+        }
+        let valid_until = String::from_utf8_lossy(&rest[..8]).to_string();
+        let (contact_url, rest) = split_iso88591_terminated(&rest[8..]).ok_or(tr("Commercial frame contact URL not null-terminated"))?;
+        let received_as = *rest.first().ok_or(tr("Commercial frame missing received-as type"))?;
+
+        let (seller_bytes, rest) = find_text_terminator(&rest[1..], encoding)?;
+        let (description_bytes, rest) = find_text_terminator(rest, encoding)?;
+        let seller = decode_text_with_encoding_simple(seller_bytes, encoding)?;
+        let description = decode_text_with_encoding_simple(description_bytes, encoding)?;
+
+        // Picture MIME type and seller logo are optional
+        let (mime_type, seller_logo) = match split_iso88591_terminated(rest)
+        {
+            | Some((mime_type, logo)) => (mime_type, logo.to_vec()),
+            | None => (String::new(), Vec::new())
+        };
+
+        Ok(CommercialFrame { encoding, price, valid_until, contact_url, received_as, seller, description, mime_type, seller_logo })
+    }
+
+    /// Get received-as description
+    pub fn received_as_description(&self) -> &'static str
+    {
+        match self.received_as
+        {
+            | 0x00 => "Other",
+            | 0x01 => "Standard CD album with other songs",
+            | 0x02 => "Compressed audio on CD",
+            | 0x03 => "File over the Internet",
+            | 0x04 => "Stream over the Internet",
+            | 0x05 => "As note sheets",
+            | 0x06 => "As note sheets in a book with other sheets",
+            | 0x07 => "Music on other media",
+            | 0x08 => "Non-musical merchandise",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "encoding": self.encoding as u8,
+            "price": self.price,
+            "valid_until": self.valid_until,
+            "contact_url": self.contact_url,
+            "received_as": self.received_as,
+            "seller": self.seller,
+            "description": self.description,
+            "mime_type": self.mime_type,
+            "seller_logo_size": self.seller_logo.len()
+        })
+    }
+}
+
+/// Format an ID3v2 price string ("USD9.99/EUR8.99") as "USD 9.99, EUR 8.99"
+pub fn format_price(price: &str) -> String
+{
+    price
+        .split('/')
+        .map(|amount| match amount.char_indices().nth(3)
+        {
+            | Some((split, _)) if amount[..split].chars().all(|c| c.is_ascii_alphabetic()) => format!("{} {}", &amount[..split], &amount[split..]),
+            | _ => amount.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Format a YYYYMMDD date as YYYY-MM-DD
+pub fn format_date(date: &str) -> String
+{
+    if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit())
+    {
+        format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8])
+    }
+    else
+    {
+        date.to_string()
+    }
+}
+
+impl fmt::Display for CommercialFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Price: {}", &[&format_price(&self.price)]))?;
+        writeln!(f, "{}", tr_fmt("Valid until: {}", &[&format_date(&self.valid_until)]))?;
+        if self.contact_url.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Contact URL: \"{}\"", &[&self.contact_url]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Received as: {} ({})", &[&self.received_as, &self.received_as_description()]))?;
+        writeln!(f, "{}", tr_fmt("Seller: \"{}\"", &[&self.seller]))?;
+        if self.description.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
+        }
+        if self.seller_logo.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Seller logo: {}, {} bytes", &[&self.mime_type, &self.seller_logo.len()]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// General Encapsulated Object Frame (GEOB)
+///
+/// Structure: Text encoding + MIME type + Filename + Content description + Encapsulated object
+use crate::{
+    dj_metadata::{SeratoBeatGrid, SeratoMarkers},
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::{TextEncoding, decode_text_with_encoding_simple, find_text_terminator, split_iso88591_terminated}
+};
+
+#[derive(Debug, Clone)]
+pub struct EncapsulatedObjectFrame
+{
+    pub encoding:    TextEncoding,
+    pub mime_type:   String,
+    pub filename:    String,
+    pub description: String,
+    pub object:      Vec<u8>
+}
+
+impl EncapsulatedObjectFrame
+{
+    /// Parse a GEOB frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 2
+        {
+            return Err(tr("Encapsulated object frame data too short").to_string());
+        }
+
+        let encoding = TextEncoding::from_byte(data[0])?;
+        let (mime_type, rest) = split_iso88591_terminated(&data[1..]).ok_or(tr("Encapsulated object MIME type not null-terminated"))?;
+
+        // Filename and description are both terminated, the object takes the rest
+        let (filename_bytes, rest) = find_text_terminator(rest, encoding)?;
+        let (description_bytes, object) = find_text_terminator(rest, encoding)?;
+        let filename = decode_text_with_encoding_simple(filename_bytes, encoding)?;
+        let description = decode_text_with_encoding_simple(description_bytes, encoding)?;
+
+        Ok(EncapsulatedObjectFrame { encoding, mime_type, filename, description, object: object.to_vec() })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "encoding": self.encoding as u8,
+            "mime_type": self.mime_type,
+            "filename": self.filename,
+            "description": self.description,
+            "object_size": self.object.len()
+        })
+    }
+}
+
+impl fmt::Display for EncapsulatedObjectFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("MIME type: \"{}\"", &[&self.mime_type]))?;
+        writeln!(f, "{}", tr_fmt("Filename: \"{}\"", &[&self.filename]))?;
+        writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
+        writeln!(f, "{}", tr_fmt("Object: {} bytes", &[&self.object.len()]))?;
+
+        // Serato stores its cue points and beat grid as objects named after their content
+        let decoded = match self.description.as_str()
+        {
+            | "Serato Markers2" => Some(SeratoMarkers::parse(&self.object).map(|markers| markers.to_string())),
+            | "Serato BeatGrid" => Some(SeratoBeatGrid::parse(&self.object).map(|grid| grid.to_string())),
+            | _ => None
+        };
+        match decoded
+        {
+            | Some(Ok(text)) => write!(f, "{}", text)?,
+            | Some(Err(message)) => writeln!(f, "{}", tr_fmt("Undecodable Serato data: {}", &[&message]))?,
+            | None => ()
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+/// Equalisation Frame (EQU2)
+///
+/// Structure: Interpolation method + Identification + repeated (Frequency + Volume adjustment)
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::decode_iso88591_string
+};
+
+/// Number of adjustment points listed in the text output
+const MAX_DISPLAYED_POINTS: usize = 20;
+
+/// Volume adjustment at one frequency
+#[derive(Debug, Clone)]
+pub struct EqualisationPoint
+{
+    /// Frequency in Hz (stored in units of 1/2 Hz)
+    pub frequency:  f64,
+    /// Volume adjustment in dB (stored as a fixed-point value in 1/512 dB)
+    pub adjustment: f64
+}
+
+#[derive(Debug, Clone)]
+pub struct EqualisationFrame
+{
+    /// 0 = band (no interpolation), 1 = linear
+    pub interpolation:  u8,
+    pub identification: String,
+    pub points:         Vec<EqualisationPoint>
+}
+
+impl EqualisationFrame
+{
+    /// Parse an EQU2 frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.is_empty()
+        {
+            return Err(tr("EQU2 frame data is empty").to_string());
+        }
+
+        let interpolation = data[0];
+        let terminator = data[1..].iter().position(|&byte| byte == 0).ok_or(tr("EQU2 identification not null-terminated"))? + 1;
+        let identification = decode_iso88591_string(&data[1..terminator]);
+
+        let points = data[terminator + 1..]
+            .chunks_exact(4)
+            .map(|point| EqualisationPoint {
+                frequency:  u16::from_be_bytes([point[0], point[1]]) as f64 / 2.0,
+                adjustment: i16::from_be_bytes([point[2], point[3]]) as f64 / 512.0
+            })
+            .collect();
+
+        Ok(EqualisationFrame { interpolation, identification, points })
+    }
+
+    /// Describe the interpolation method
+    pub fn interpolation_name(&self) -> &'static str
+    {
+        match self.interpolation
+        {
+            | 0 => "Band",
+            | 1 => "Linear",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "interpolation": self.interpolation,
+            "identification": self.identification,
+            "points": self.points.iter().map(|point| serde_json::json!({ "frequency": point.frequency, "adjustment": point.adjustment })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for EqualisationFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Interpolation: {} ({})", &[&self.interpolation, &self.interpolation_name()]))?;
+        writeln!(f, "{}", tr_fmt("Identification: \"{}\"", &[&self.identification]))?;
+        for point in self.points.iter().take(MAX_DISPLAYED_POINTS)
+        {
+            writeln!(f, "{}", tr_fmt("{} Hz: {} dB", &[&format!("{:.1}", point.frequency), &format!("{:+.2}", point.adjustment)]))?;
+        }
+        if self.points.len() > MAX_DISPLAYED_POINTS
+        {
+            writeln!(f, "{}", tr_fmt("... {} more points", &[&(self.points.len() - MAX_DISPLAYED_POINTS)]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// CD frames (sectors) per second
+const FRAMES_PER_SECOND: u32 = 75;
+
+/// Frames of the two-second pregap before logical block address 0
+const PREGAP_FRAMES: u32 = 150;
+
+/// Track number of the lead-out descriptor
+const LEAD_OUT_TRACK: u8 = 0xAA;
+
+/// Track descriptor of the CD table of contents
+#[derive(Debug, Clone)]
+pub struct CdTrack
+{
+    pub number:  u8,
+    /// ADR/control nibbles (control bit 2 marks data tracks)
+    pub control: u8,
+    /// Start as logical block address (frames after the pregap)
+    pub address: u32
+}
+
+impl CdTrack
+{
+    /// Whether this is a data track instead of audio
+    pub fn is_data(&self) -> bool
+    {
+        self.control & 0x04 != 0
+    }
+}
+
+/// Music CD Identifier Frame (MCDI)
+///
+/// Structure: CD table of contents as returned by the READ TOC command
+/// (data length, first track, last track, 8-byte track descriptors ending with the lead-out)
+#[derive(Debug, Clone)]
+pub struct MusicCdIdFrame
+{
+    pub first_track: u8,
+    pub last_track:  u8,
+    pub tracks:      Vec<CdTrack>,
+    /// Lead-out address (end of the last track)
+    pub lead_out:    u32
+}
+
+impl MusicCdIdFrame
+{
+    /// Parse an MCDI frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12
+        {
+            return Err(tr("MCDI frame too short for a table of contents").to_string());
+        }
+
+        let first_track = data[2];
+        let last_track = data[3];
+
+        let mut tracks = Vec::new();
+        let mut lead_out = None;
+        for descriptor in data[4..].chunks_exact(8)
+        {
+            // Descriptors use logical block addresses (MSF addressing is not used by taggers)
+            let track = CdTrack {
+                number:  descriptor[2],
+                control: descriptor[1] & 0x0F,
+                address: u32::from_be_bytes([descriptor[4], descriptor[5], descriptor[6], descriptor[7]])
+            };
+            if track.number == LEAD_OUT_TRACK
+            {
+                lead_out = Some(track.address);
+                break;
+            }
+            tracks.push(track);
+        }
+
+        let lead_out = lead_out.ok_or(tr("MCDI table of contents has no lead-out"))?;
+        if tracks.is_empty() == true || tracks.windows(2).any(|pair| pair[1].address < pair[0].address) || tracks.last().is_some_and(|track| track.address > lead_out)
+        {
+            return Err(tr("MCDI table of contents has invalid track addresses").to_string());
+        }
+
+        Ok(MusicCdIdFrame { first_track, last_track, tracks, lead_out })
+    }
+
+    /// Length of a track in frames
+    pub fn track_frames(&self, index: usize) -> u32
+    {
+        let end = self.tracks.get(index + 1).map_or(self.lead_out, |next| next.address);
+        end.saturating_sub(self.tracks[index].address)
+    }
+
+    /// Playing time of the disc in frames
+    pub fn disc_frames(&self) -> u32
+    {
+        self.lead_out.saturating_sub(self.tracks[0].address)
+    }
+
+    /// FreeDB/CDDB disc ID
+    pub fn freedb_disc_id(&self) -> u32
+    {
+        let digit_sum = |mut value: u32| {
+            let mut sum = 0;
+            while value > 0
+            {
+                sum += value % 10;
+                value /= 10;
+            }
+            sum
+        };
+        let seconds = |address: u32| (address + PREGAP_FRAMES) / FRAMES_PER_SECOND;
+
+        let checksum: u32 = self.tracks.iter().map(|track| digit_sum(seconds(track.address))).sum();
+        let length = seconds(self.lead_out) - seconds(self.tracks[0].address);
+
+        ((checksum % 0xFF) << 24) | (length << 8) | self.tracks.len() as u32
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "first_track": self.first_track,
+            "last_track": self.last_track,
+            "tracks": self.tracks.iter().map(|track| serde_json::json!({
+                "number": track.number,
+                "control": track.control,
+                "address": track.address
+            })).collect::<Vec<_>>(),
+            "lead_out": self.lead_out
+        })
+    }
+}
+
+/// Format frames as mm:ss.ff
+fn format_frames(frames: u32) -> String
+{
+    let seconds = frames / FRAMES_PER_SECOND;
+    format!("{:02}:{:02}.{:02}", seconds / 60, seconds % 60, frames % FRAMES_PER_SECOND)
+}
+
+impl fmt::Display for MusicCdIdFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Tracks: {}-{}", &[&self.first_track, &self.last_track]))?;
+        for (index, track) in self.tracks.iter().enumerate()
+        {
+            let kind = if track.is_data() == true
+            {
+                " (data)"
+            }
+            else
+            {
+                ""
+            };
+            writeln!(
+                f,
+                "{}",
+                tr_fmt("Track {}: offset {} frames, length {}{}", &[
+                    &format!("{:2}", track.number),
+                    &(track.address + PREGAP_FRAMES),
+                    &format_frames(self.track_frames(index)),
+                    &kind
+                ])
+            )?;
+        }
+        writeln!(f, "{}", tr_fmt("Lead-out: offset {} frames", &[&(self.lead_out + PREGAP_FRAMES)]))?;
+        writeln!(f, "{}", tr_fmt("Disc Length: {}", &[&format_frames(self.disc_frames())]))?;
+        writeln!(f, "{}", tr_fmt("FreeDB Disc ID: {}", &[&format!("{:08x}", self.freedb_disc_id())]))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+/// Ownership Frame (OWNE)
+///
+/// Structure: Text encoding + Price paid + Date of purchase + Seller
+use crate::id3v2::{
+    frames::commercial::{format_date, format_price},
+    text_encoding::{TextEncoding, decode_text_with_encoding_simple, split_iso88591_terminated}
+};
+
+#[derive(Debug, Clone)]
+pub struct OwnershipFrame
+{
+    pub encoding:      TextEncoding,
+    /// Currency code (ISO 4217) followed by the amount (e.g. "USD9.99")
+    pub price_paid:    String,
+    /// Date as YYYYMMDD
+    pub purchase_date: String,
+    pub seller:        String
+}
+
+impl OwnershipFrame
+{
+    /// Parse an OWNE frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 2
+        {
+            return Err(tr("Ownership frame data too short").to_string());
+        }
+
+        let encoding = TextEncoding::from_byte(data[0])?;
+        let (price_paid, rest) = split_iso88591_terminated(&data[1..]).ok_or(tr("Ownership frame price not null-terminated"))?;
+        if rest.len() < 8
+        {
+            return Err(tr("Ownership frame missing date of purchase").to_string());
+        }
+        let purchase_date = String::from_utf8_lossy(&rest[..8]).to_string();
+        let seller = decode_text_with_encoding_simple(&rest[8..], encoding)?;
+
+        Ok(OwnershipFrame { encoding, price_paid, purchase_date, seller })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "price_paid": self.price_paid, "purchase_date": self.purchase_date, "seller": self.seller })
+    }
+}
+
+impl fmt::Display for OwnershipFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Price paid: {}", &[&format_price(&self.price_paid)]))?;
+        writeln!(f, "{}", tr_fmt("Date of purchase: {}", &[&format_date(&self.purchase_date)]))?;
+        writeln!(f, "{}", tr_fmt("Seller: \"{}\"", &[&self.seller]))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+
+/// Private Frame (PRIV)
+///
+/// Structure: Owner identifier + Private data
+use crate::{
+    dj_metadata::{TRAKTOR_OWNER, TraktorCues},
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::split_iso88591_terminated,
+    timed_id3::{TRANSPORT_STREAM_TIMESTAMP_OWNER, format_timestamp, transport_stream_timestamp}
+};
+
+#[derive(Debug, Clone)]
+pub struct PrivateFrame
+{
+    pub owner_identifier: String,
+    pub data:             Vec<u8>
+}
+
+impl PrivateFrame
+{
+    /// Parse a PRIV frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let (owner_identifier, private_data) = split_iso88591_terminated(data).ok_or(tr("PRIV owner identifier not null-terminated"))?;
+        Ok(PrivateFrame { owner_identifier, data: private_data.to_vec() })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "owner_identifier": self.owner_identifier, "data_size": self.data.len() })
+    }
+}
+
+impl fmt::Display for PrivateFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Owner: \"{}\"", &[&self.owner_identifier]))?;
+        writeln!(f, "{}", tr_fmt("Private data: {} bytes", &[&self.data.len()]))?;
+        if self.owner_identifier == TRAKTOR_OWNER
+        {
+            match TraktorCues::parse(&self.data)
+            {
+                | Ok(cues) => write!(f, "{}", cues)?,
+                | Err(message) => writeln!(f, "{}", tr_fmt("Undecodable Traktor data: {}", &[&message]))?
+            }
+        }
+        if self.owner_identifier == TRANSPORT_STREAM_TIMESTAMP_OWNER &&
+            let Some(ticks) = transport_stream_timestamp(&self.data)
+        {
+            writeln!(f, "{}", tr_fmt("MPEG-TS Timestamp: {}", &[&format_timestamp(ticks)]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// Relative Volume Adjustment Frame (RVA2)
+///
+/// Structure: Identification + repeated (Channel type + Volume adjustment + Bits representing peak + Peak volume)
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::decode_iso88591_string
+};
+
+/// Volume adjustment of one channel
+#[derive(Debug, Clone)]
+pub struct ChannelAdjustment
+{
+    pub channel_type: u8,
+    /// Volume adjustment in dB (stored as a fixed-point value in 1/512 dB)
+    pub adjustment:   f64,
+    pub peak_bits:    u8,
+    pub peak:         u64
+}
+
+impl ChannelAdjustment
+{
+    /// Describe the channel type
+    pub fn channel_name(&self) -> &'static str
+    {
+        match self.channel_type
+        {
+            | 0x00 => "Other",
+            | 0x01 => "Master volume",
+            | 0x02 => "Front right",
+            | 0x03 => "Front left",
+            | 0x04 => "Back right",
+            | 0x05 => "Back left",
+            | 0x06 => "Front centre",
+            | 0x07 => "Back centre",
+            | 0x08 => "Subwoofer",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Peak relative to full scale (1.0 = 0 dBFS)
+    pub fn peak_ratio(&self) -> Option<f64>
+    {
+        (self.peak_bits > 0 && self.peak_bits <= 64).then(|| self.peak as f64 / 2f64.powi(self.peak_bits as i32 - 1))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RelativeVolumeFrame
+{
+    /// Situation the adjustment applies to (e.g. "track", "album")
+    pub identification: String,
+    pub channels:       Vec<ChannelAdjustment>
+}
+
+impl RelativeVolumeFrame
+{
+    /// Parse an RVA2 frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let terminator = data.iter().position(|&byte| byte == 0).ok_or(tr("RVA2 identification not null-terminated"))?;
+        let identification = decode_iso88591_string(&data[..terminator]);
+
+        let mut channels = Vec::new();
+        let mut pos = terminator + 1;
+        while pos + 4 <= data.len()
+        {
+            let channel_type = data[pos];
+            let adjustment = i16::from_be_bytes([data[pos + 1], data[pos + 2]]) as f64 / 512.0;
+            let peak_bits = data[pos + 3];
+            let peak_bytes = (peak_bits as usize).div_ceil(8);
+            pos += 4;
+            if pos + peak_bytes > data.len()
+            {
+                return Err(tr("RVA2 peak volume truncated").to_string());
+            }
+            // Peaks wider than 64 bits keep their most significant bytes
+            let peak = data[pos..pos + peak_bytes].iter().take(8).fold(0u64, |value, &byte| (value << 8) | byte as u64);
+            pos += peak_bytes;
+
+            channels.push(ChannelAdjustment { channel_type, adjustment, peak_bits, peak });
+        }
+
+        Ok(RelativeVolumeFrame { identification, channels })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "identification": self.identification,
+            "channels": self.channels.iter().map(|channel| serde_json::json!({
+                "channel_type": channel.channel_type,
+                "adjustment": channel.adjustment,
+                "peak_bits": channel.peak_bits,
+                "peak": channel.peak
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for RelativeVolumeFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Identification: \"{}\"", &[&self.identification]))?;
+        for channel in &self.channels
+        {
+            write!(f, "{}", tr_fmt("{}: {} dB", &[&channel.channel_name(), &format!("{:+.2}", channel.adjustment)]))?;
+            match channel.peak_ratio()
+            {
+                | Some(ratio) => writeln!(f, "{}", tr_fmt(", peak {} ({} bits)", &[&format!("{:.6}", ratio), &channel.peak_bits]))?,
+                | None => writeln!(f)?
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 use std::fmt;
 
-use crate::id3v2::frame::Id3v2Frame;
 /// Table of Contents Frame (CTOC)
 ///
 /// Structure: Element ID + TOC flags + Entry count + Child element IDs + Sub-frames
 /// Part of ID3v2 Chapter Frame Addendum specification
 use crate::id3v2::text_encoding::decode_iso88591_string;
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::frame::Id3v2Frame
+};
 
 #[derive(Debug, Clone)]
 pub struct TableOfContentsFrame
@@ -29,7 +32,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("Table of contents frame data is empty".to_string());
+            return Err(tr("Table of contents frame data is empty").to_string());
         }
 
         let mut pos = 0;
@@ -42,7 +45,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         }
         if pos >= data.len()
         {
-            return Err("TOC frame element ID not null-terminated".to_string());
+            return Err(tr("TOC frame element ID not null-terminated").to_string());
         }
         let element_id = decode_iso88591_string(&data[element_id_start..pos]);
         pos += 1; // Skip null terminator
@@ -50,7 +53,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // TOC flags (1 byte)
         if pos >= data.len()
         {
-            return Err("TOC frame missing flags".to_string());
+            return Err(tr("TOC frame missing flags").to_string());
         }
         let flags = data[pos];
         pos += 1;
@@ -61,7 +64,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         // Entry count (1 byte)
         if pos >= data.len()
         {
-            return Err("TOC frame missing entry count".to_string());
+            return Err(tr("TOC frame missing entry count").to_string());
         }
         let entry_count = data[pos];
         pos += 1;
@@ -77,7 +80,7 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
             }
             if pos >= data.len()
             {
-                return Err("TOC frame child element ID not null-terminated".to_string());
+                return Err(tr("TOC frame child element ID not null-terminated").to_string());
             }
             let child_id = decode_iso88591_string(&data[id_start..pos]);
             child_element_ids.push(child_id);
@@ -97,6 +100,12 @@ pub fn parse(data: &[u8], version_major: u8) -> Result<Self, String>
         Ok(TableOfContentsFrame { element_id, top_level, ordered, child_element_ids, sub_frames })
     }
 
+    /// Position of the first sub-frame in the frame data (after element ID, flags and child element IDs)
+    pub fn sub_frames_start(&self) -> usize
+    {
+        self.element_id.chars().count() + 3 + self.child_element_ids.iter().map(|child_id| child_id.chars().count() + 1).sum::<usize>()
+    }
+
     /// Get number of child elements
     pub fn child_count(&self) -> usize
     {
@@ -108,17 +117,31 @@ pub fn has_sub_frames(&self) -> bool
     {
         !self.sub_frames.is_empty()
     }
+
+    /// Structured representation for JSON reports, `data_offset` is the offset of the frame data; sub-frames carry the text output lines of their
+    /// contents if `details` is set
+    pub fn to_json(&self, data_offset: u64, details: bool) -> serde_json::Value
+    {
+        let sub_frames_offset = data_offset + self.sub_frames_start() as u64;
+        serde_json::json!({
+            "element_id": self.element_id,
+            "top_level": self.top_level,
+            "ordered": self.ordered,
+            "child_element_ids": self.child_element_ids,
+            "sub_frames": self.sub_frames.iter().map(|sub_frame| sub_frame.to_json(sub_frames_offset, details)).collect::<Vec<_>>()
+        })
+    }
 }
 
 impl fmt::Display for TableOfContentsFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Element ID: \"{}\"", self.element_id)?;
-        writeln!(f, "Flags: Top-level: {}, Ordered: {}", self.top_level, self.ordered)?;
+        writeln!(f, "{}", tr_fmt("Element ID: \"{}\"", &[&self.element_id]))?;
+        writeln!(f, "{}", tr_fmt("Flags: Top-level: {}, Ordered: {}", &[&self.top_level, &self.ordered]))?;
 
         // Display child elements on a single line
-        write!(f, "Child elements ({}): ", self.child_count())?;
+        write!(f, "{}", tr_fmt("Child elements ({}): ", &[&self.child_count()]))?;
         for (i, child_id) in self.child_element_ids.iter().enumerate()
         {
             if i > 0
@@ -131,7 +154,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
 
         if self.has_sub_frames() == true
         {
-            writeln!(f, "Sub-frames: {} embedded frame(s)", self.sub_frames.len())?;
+            writeln!(f, "{}", tr_fmt("Sub-frames: {} embedded frame(s)", &[&self.sub_frames.len()]))?;
             writeln!(f)?; // Add newline before first embedded frame
             for (i, sub_frame) in self.sub_frames.iter().enumerate()
             {
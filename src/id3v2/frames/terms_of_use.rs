@@ -0,0 +1,52 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+/// Terms of Use Frame (USER)
+///
+/// Structure: Text encoding + Language + Text
+use crate::id3v2::text_encoding::{TextEncoding, decode_text_with_encoding_simple};
+
+#[derive(Debug, Clone)]
+pub struct TermsOfUseFrame
+{
+    pub encoding: TextEncoding,
+    pub language: String,
+    pub text:     String
+}
+
+impl TermsOfUseFrame
+{
+    /// Parse a USER frame from raw data
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("Terms of use frame data too short").to_string());
+        }
+
+        let encoding = TextEncoding::from_byte(data[0])?;
+
+        // Language is always 3 bytes (ISO-639-2)
+        let language = String::from_utf8_lossy(&data[1..4]).to_string();
+        let text = decode_text_with_encoding_simple(&data[4..], encoding)?;
+
+        Ok(TermsOfUseFrame { encoding, language, text })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "language": self.language, "text": self.text })
+    }
+}
+
+impl fmt::Display for TermsOfUseFrame
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Language: \"{}\"", &[&self.language]))?;
+        writeln!(f, "{}", tr_fmt("Text: \"{}\"", &[&self.text]))?;
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
 /// Text Information Frame (T*** frames except TXXX)
 ///
 /// Structure: Text encoding + Information
@@ -22,13 +23,13 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("Text frame data is empty".to_string());
+            return Err(tr("Text frame data is empty").to_string());
         }
 
         let encoding = TextEncoding::from_byte(data[0])?;
         if data.len() < 2
         {
-            return Err("Text frame data too short".to_string());
+            return Err(tr("Text frame data too short").to_string());
         }
 
         let text_data = &data[1..];
@@ -42,16 +43,22 @@ pub fn primary_text(&self) -> &str
     {
         &self.text
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "text": self.text, "strings": self.strings })
+    }
 }
 
 impl fmt::Display for TextFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Encoding: {}", self.encoding)?;
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
         if self.strings.len() > 1
         {
-            writeln!(f, "Values ({} strings):", self.strings.len())?;
+            writeln!(f, "{}", tr_fmt("Values ({} strings):", &[&self.strings.len()]))?;
             for (i, string) in self.strings.iter().enumerate()
             {
                 writeln!(f, "  [{}] \"{}\"", i + 1, string)?;
@@ -59,7 +66,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
         }
         else if self.text.is_empty() == false
         {
-            writeln!(f, "Value: \"{}\"", self.text)?;
+            writeln!(f, "{}", tr_fmt("Value: \"{}\"", &[&self.text]))?;
         }
         Ok(())
     }
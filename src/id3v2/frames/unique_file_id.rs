@@ -3,7 +3,12 @@
 /// Unique File Identifier Frame (UFID)
 ///
 /// Structure: Owner identifier + Identifier
-use crate::id3v2::text_encoding::decode_iso88591_string;
+use crate::{
+    hashes::to_hex,
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::decode_iso88591_string,
+    music_ids::{MUSICBRAINZ_UFID_OWNER, MusicIdKind}
+};
 
 #[derive(Debug, Clone)]
 pub struct UniqueFileIdFrame
@@ -19,7 +24,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("UFID frame data is empty".to_string());
+            return Err(tr("UFID frame data is empty").to_string());
         }
 
         // Find null terminator for owner identifier
@@ -30,7 +35,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         }
         if pos >= data.len()
         {
-            return Err("UFID owner identifier not null-terminated".to_string());
+            return Err(tr("UFID owner identifier not null-terminated").to_string());
         }
 
         let owner_identifier = decode_iso88591_string(&data[0..pos]);
@@ -40,19 +45,29 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         let identifier = data[pos..].to_vec();
         if identifier.len() > 64
         {
-            return Err("UFID identifier too long (max 64 bytes)".to_string());
+            return Err(tr("UFID identifier too long (max 64 bytes)").to_string());
         }
 
         Ok(UniqueFileIdFrame { owner_identifier, identifier })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "owner_identifier": self.owner_identifier, "identifier": to_hex(&self.identifier) })
+    }
 }
 
 impl fmt::Display for UniqueFileIdFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Owner: \"{}\"", self.owner_identifier)?;
-        writeln!(f, "Identifier: {} bytes", self.identifier.len())?;
+        writeln!(f, "{}", tr_fmt("Owner: \"{}\"", &[&self.owner_identifier]))?;
+        match (self.owner_identifier == MUSICBRAINZ_UFID_OWNER, std::str::from_utf8(&self.identifier))
+        {
+            | (true, Ok(identifier)) => writeln!(f, "{}", tr_fmt("Identifier: \"{}\" ({})", &[&identifier, &MusicIdKind::Recording.name()]))?,
+            | _ => writeln!(f, "{}", tr_fmt("Identifier: {} bytes", &[&self.identifier.len()]))?
+        }
         Ok(())
     }
 }
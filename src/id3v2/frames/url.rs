@@ -4,7 +4,7 @@
 ///
 /// Structure: URL (text string)
 /// Examples: WCOM, WCOP, WOAF, WOAR, WOAS, WORS, WPAY, WPUB
-use crate::id3v2::text_encoding::decode_iso88591_string;
+use crate::{i18n::tr_fmt, id3v2::text_encoding::decode_iso88591_string};
 
 #[derive(Debug, Clone)]
 pub struct UrlFrame
@@ -21,13 +21,19 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         let url = decode_iso88591_string(data);
         Ok(UrlFrame { url })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "url": self.url })
+    }
 }
 
 impl fmt::Display for UrlFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "URL: \"{}\"", self.url)?;
+        writeln!(f, "{}", tr_fmt("URL: \"{}\"", &[&self.url]))?;
         Ok(())
     }
 }
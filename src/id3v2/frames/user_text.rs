@@ -3,7 +3,11 @@
 /// User-Defined Text Information Frame (TXXX)
 ///
 /// Structure: Text encoding + Description + Value
-use crate::id3v2::text_encoding::{TextEncoding, split_terminated_text};
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::text_encoding::{TextEncoding, split_terminated_text},
+    music_ids::{Fingerprint, MusicIdKind}
+};
 
 #[derive(Debug, Clone)]
 pub struct UserTextFrame
@@ -20,13 +24,13 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("User text frame data is empty".to_string());
+            return Err(tr("User text frame data is empty").to_string());
         }
 
         let encoding = TextEncoding::from_byte(data[0])?;
         if data.len() < 2
         {
-            return Err("User text frame data too short".to_string());
+            return Err(tr("User text frame data too short").to_string());
         }
 
         let text_data = &data[1..];
@@ -34,15 +38,38 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(UserTextFrame { encoding, description, value })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "description": self.description, "value": self.value })
+    }
 }
 
 impl fmt::Display for UserTextFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Encoding: {}", self.encoding)?;
-        writeln!(f, "Description: \"{}\"", self.description)?;
-        writeln!(f, "Value: \"{}\"", self.value)?;
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
+        writeln!(f, "{}", tr_fmt("Value: \"{}\"", &[&self.value]))?;
+        if let Some(kind) = MusicIdKind::from_field_name(&self.description)
+        {
+            writeln!(f, "{}", tr_fmt("Identifier: {}", &[&kind.name()]))?;
+            if kind == MusicIdKind::AcoustIdFingerprint &&
+                let Some(fingerprint) = Fingerprint::parse(&self.value)
+            {
+                writeln!(
+                    f,
+                    "{}",
+                    tr_fmt("Chromaprint: algorithm {}, {} values ({} s of audio)", &[
+                        &fingerprint.algorithm,
+                        &fingerprint.values,
+                        &format!("{:.1}", fingerprint.duration())
+                    ])
+                )?;
+            }
+        }
         Ok(())
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
 /// User-Defined URL Link Frame (WXXX)
 ///
 /// Structure: Text encoding + Description + URL
@@ -20,13 +21,13 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.is_empty()
         {
-            return Err("User URL frame data is empty".to_string());
+            return Err(tr("User URL frame data is empty").to_string());
         }
 
         let encoding = TextEncoding::from_byte(data[0])?;
         if data.len() < 2
         {
-            return Err("User URL frame data too short".to_string());
+            return Err(tr("User URL frame data too short").to_string());
         }
 
         let text_data = &data[1..];
@@ -40,15 +41,21 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(UserUrlFrame { encoding, description, url })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "encoding": self.encoding as u8, "description": self.description, "url": self.url })
+    }
 }
 
 impl fmt::Display for UserUrlFrame
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Encoding: {}", self.encoding)?;
-        writeln!(f, "Description: \"{}\"", self.description)?;
-        writeln!(f, "URL: \"{}\"", self.url)?;
+        writeln!(f, "{}", tr_fmt("Encoding: {}", &[&self.encoding]))?;
+        writeln!(f, "{}", tr_fmt("Description: \"{}\"", &[&self.description]))?;
+        writeln!(f, "{}", tr_fmt("URL: \"{}\"", &[&self.url]))?;
         Ok(())
     }
 }
@@ -0,0 +1,642 @@
+use std::io::SeekFrom;
+
+use crate::{
+    artwork::Artwork,
+    chapters::Chapter,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    find::{Occurrence, StructureKind},
+    i18n::{tr, tr_fmt},
+    id3v1::genre_by_index,
+    id3v2::{
+        dissectors::{v3::parse_id3v2_3_frame, v4::parse_id3v2_4_frame},
+        extended_header::ExtendedHeader,
+        frame::{Id3v2Frame, Id3v2FrameContent},
+        frames::chapter::ChapterFrame,
+        tools::{clamp_tag_size, decode_synchsafe_int, id3v2_footer_matches, recover_frames, remove_unsynchronization}
+    },
+    layout::Layout,
+    loudness::Loudness,
+    media_source::{ByteWindow, MediaSource},
+    metadata::{MediaMetadata, SetPosition},
+    objects::{EmbeddedObject, ObjectKind},
+    summary::TagSummary,
+    visitor::DissectionVisitor
+};
+
+/// Text frames mapped to a field of the normalized metadata
+const NORMALIZED_FRAMES: [&str; 9] = ["TIT2", "TPE1", "TALB", "TPE2", "TDRC", "TYER", "TCON", "TRCK", "TPOS"];
+
+/// Complete ID3v2 tag parsed without producing any output
+#[derive(Debug, Clone)]
+pub struct Id3v2Tag
+{
+    /// File offset of the tag header
+    pub offset:          u64,
+    pub major_version:   u8,
+    pub minor_version:   u8,
+    pub flags:           u8,
+    /// Tag size from the header (excluding header and footer)
+    pub size:            u32,
+    /// Frames in tag order (offsets are relative to the tag body)
+    pub frames:          Vec<Id3v2Frame>,
+    /// Offset in the tag body where the frames end and padding begins
+    pub frames_end:      usize,
+    /// Parsed extended header
+    pub extended_header: Option<ExtendedHeader>,
+    /// Whether the ID3v2.4 footer matches the tag header (None if the footer flag is not set)
+    pub footer_valid:    Option<bool>
+}
+
+impl Id3v2Tag
+{
+    /// Read the ID3v2.3/2.4 tag starting at `offset`, returning `None` if there is no tag header
+    pub fn read(file: &mut dyn MediaSource, offset: u64) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        Self::read_frames(file, offset, None)
+    }
+
+    /// Read the tag like `read`, but report corrupt frame data as warnings and resume at the next plausible frame (`--recover`)
+    pub fn read_recovering(file: &mut dyn MediaSource, offset: u64, diagnostics: &mut Diagnostics) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        Self::read_frames(file, offset, Some(diagnostics))
+    }
+
+    fn read_frames(file: &mut dyn MediaSource, offset: u64, mut recovery: Option<&mut Diagnostics>) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut header = [0u8; 10];
+        if file.read_exact(&mut header).is_err() || &header[0..3] != b"ID3"
+        {
+            return Ok(None);
+        }
+
+        let major_version = header[3];
+        let minor_version = header[4];
+        let flags = header[5];
+        let mut size = decode_synchsafe_int(&header[6..10]);
+
+        if major_version != 3 && major_version != 4
+        {
+            return Err(tr_fmt("Unsupported ID3v2 version 2.{}", &[&major_version]).into());
+        }
+
+        if let Some(diagnostics) = recovery.as_deref_mut()
+        {
+            size = clamp_tag_size(size, offset, file.size()?, diagnostics);
+        }
+
+        let mut buffer = file.map(offset + 10, size as u64)?;
+
+        // Tag-level unsynchronisation (ID3v2.4 unsynchronises each frame on its own)
+        if flags & 0x80 != 0 && major_version == 3
+        {
+            buffer = ByteWindow::Owned(remove_unsynchronization(&buffer));
+        }
+
+        // Parse the extended header
+        let mut pos = 0;
+        let mut extended_header = None;
+        if flags & 0x40 != 0
+        {
+            let (header, frame_start) = if major_version == 4
+            {
+                ExtendedHeader::parse_v4(&buffer)?
+            }
+            else
+            {
+                ExtendedHeader::parse_v3(&buffer)?
+            };
+            extended_header = Some(header);
+            pos = frame_start;
+        }
+
+        let mut frames = Vec::new();
+        while pos + 10 <= buffer.len()
+        {
+            // Stop at padding
+            if buffer[pos] == 0
+            {
+                if let Some(diagnostics) = recovery.as_deref_mut() &&
+                    let Some(next) = recover_frames(&buffer, pos, major_version, offset, tr("Unexpected data in the padding"), diagnostics)
+                {
+                    pos = next;
+                    continue;
+                }
+                break;
+            }
+
+            let frame = if major_version == 4
+            {
+                parse_id3v2_4_frame(&buffer, pos, flags & 0x80 != 0)
+            }
+            else
+            {
+                parse_id3v2_3_frame(&buffer, pos)
+            };
+            match frame
+            {
+                | Some(frame) =>
+                {
+                    pos += 10 + frame.size as usize;
+                    frames.push(frame);
+                }
+                | None =>
+                {
+                    // Skip frames that are invalid for this version if their size is sane
+                    let frame_size = if major_version == 4
+                    {
+                        decode_synchsafe_int(&buffer[pos + 4..pos + 8])
+                    }
+                    else
+                    {
+                        u32::from_be_bytes([buffer[pos + 4], buffer[pos + 5], buffer[pos + 6], buffer[pos + 7]])
+                    } as usize;
+
+                    if frame_size == 0 || frame_size > buffer.len() - pos - 10
+                    {
+                        if let Some(diagnostics) = recovery.as_deref_mut()
+                        {
+                            let frame_id = String::from_utf8_lossy(&buffer[pos..pos + 4]).to_string();
+                            let problem = tr_fmt("Corrupt frame '{}' (size {} bytes)", &[&frame_id, &frame_size]);
+                            if let Some(next) = recover_frames(&buffer, pos, major_version, offset, &problem, diagnostics)
+                            {
+                                pos = next;
+                                continue;
+                            }
+                        }
+                        break;
+                    }
+                    pos += 10 + frame_size;
+                }
+            }
+        }
+
+        let footer_valid = if major_version == 4 && flags & 0x10 != 0
+        {
+            Some(id3v2_footer_matches(file, offset, (major_version, minor_version, flags, size))?)
+        }
+        else
+        {
+            None
+        };
+
+        Ok(Some(Self { offset, major_version, minor_version, flags, size, frames, frames_end: pos.min(buffer.len()), extended_header, footer_valid }))
+    }
+
+    /// Structured representation of this tag for JSON reports, with the text output lines of parsed frame contents if `details` is set
+    pub fn to_json(&self, details: bool) -> serde_json::Value
+    {
+        // Frame offsets are relative to the tag body (after unsynchronisation removal)
+        let body_offset = self.offset + 10;
+
+        let mut json = serde_json::json!({
+            "version": format!("2.{}.{}", self.major_version, self.minor_version),
+            "flags": self.flags,
+            "size": self.size,
+            "padding": (self.size as usize).saturating_sub(self.frames_end),
+            "frames": self.frames.iter().map(|frame| frame.to_json(body_offset, details)).collect::<Vec<_>>()
+        });
+        if let Some(ref extended_header) = self.extended_header
+        {
+            json["extended_header"] = extended_header.to_json();
+        }
+        if let Some(footer_valid) = self.footer_valid
+        {
+            json["footer_valid"] = serde_json::Value::Bool(footer_valid);
+        }
+        json
+    }
+
+    /// Pictures of all APIC frames, including those embedded in chapter frames
+    pub fn artwork(&self) -> Vec<Artwork>
+    {
+        let mut artwork = Vec::new();
+        collect_pictures(&self.frames, "", &mut artwork);
+        artwork
+    }
+
+    /// Payloads of all GEOB, PRIV and UFID frames, including those embedded in chapter frames
+    pub fn objects(&self) -> Vec<EmbeddedObject>
+    {
+        let mut objects = Vec::new();
+        collect_objects(&self.frames, "", &mut objects);
+        objects
+    }
+
+    /// Chapters of all CHAP frames, in the order of the top-level table of contents if it is ordered, by start time otherwise
+    pub fn chapters(&self) -> Vec<Chapter>
+    {
+        let chapter_frames: Vec<&ChapterFrame> = self
+            .frames
+            .iter()
+            .filter_map(|frame| match frame.content
+            {
+                | Some(Id3v2FrameContent::Chapter(ref chapter)) => Some(chapter),
+                | _ => None
+            })
+            .collect();
+        let to_chapter = |chapter: &ChapterFrame| {
+            let title = chapter
+                .sub_frames
+                .iter()
+                .find_map(|frame| match frame.content
+                {
+                    | Some(Id3v2FrameContent::Text(ref text)) if frame.id == "TIT2" => Some(text.text.clone()),
+                    | _ => None
+                })
+                .unwrap_or_else(|| chapter.element_id.clone());
+            let url = chapter.sub_frames.iter().find_map(|frame| frame.get_url()).map(str::to_string);
+            let mut pictures = Vec::new();
+            collect_pictures(&chapter.sub_frames, &format!("CHAP {}/", chapter.element_id), &mut pictures);
+            Chapter { start: chapter.start_time as u64, end: chapter.end_time as u64, title, url, image: pictures.into_iter().next() }
+        };
+
+        let top_level_toc = self.frames.iter().find_map(|frame| match frame.content
+        {
+            | Some(Id3v2FrameContent::TableOfContents(ref toc)) if toc.top_level == true && toc.ordered == true => Some(toc),
+            | _ => None
+        });
+        match top_level_toc
+        {
+            | Some(toc) => toc
+                .child_element_ids
+                .iter()
+                .filter_map(|element_id| chapter_frames.iter().find(|chapter| &chapter.element_id == element_id))
+                .map(|chapter| to_chapter(chapter))
+                .collect(),
+            | None =>
+            {
+                let mut chapters: Vec<Chapter> = chapter_frames.into_iter().map(to_chapter).collect();
+                chapters.sort_by_key(|chapter| chapter.start);
+                chapters
+            }
+        }
+    }
+
+    /// Key fields aggregated from the frames; the duration is only taken from TLEN here
+    pub fn summary(&self) -> TagSummary
+    {
+        let text = |frame_ids: &[&str]| {
+            frame_ids.iter().find_map(|frame_id| self.frames.iter().find(|frame| frame.id == *frame_id).and_then(|frame| frame.get_text())).map(str::to_string)
+        };
+        let artwork = self.artwork();
+
+        let duration = text(&["TLEN"]).and_then(|length| length.trim().parse::<u64>().ok()).filter(|&length| length > 0).map(|length| length as f64 / 1000.0);
+
+        TagSummary {
+            version: format!("ID3v2.{}", self.major_version),
+            title: text(&["TIT2"]),
+            artist: text(&["TPE1", "TPE2"]),
+            album: text(&["TALB"]),
+            year: text(&["TDRC", "TYER"]),
+            genre: text(&["TCON"]).map(|genre| resolve_genre(&genre)),
+            track: text(&["TRCK"]),
+            duration,
+            duration_source: duration.map(|_| "TLEN"),
+            pictures: artwork.len(),
+            picture_bytes: artwork.iter().map(|picture| picture.data.len() as u64).sum(),
+            chapters: self.chapters().len()
+        }
+    }
+
+    /// Normalized metadata: the common text frames mapped to their fields, TXXX, described COMM, textual UFID, PCST and the remaining text and URL frames as custom
+    /// fields
+    pub fn metadata(&self) -> MediaMetadata
+    {
+        let text = |frame_ids: &[&str]| {
+            frame_ids.iter().find_map(|frame_id| self.frames.iter().find(|frame| frame.id == *frame_id).and_then(|frame| frame.get_text())).map(str::to_string)
+        };
+
+        let mut metadata = MediaMetadata {
+            title: text(&["TIT2"]),
+            artist: text(&["TPE1"]),
+            album: text(&["TALB"]),
+            album_artist: text(&["TPE2"]),
+            date: text(&["TDRC", "TYER"]),
+            genre: text(&["TCON"]).map(|genre| resolve_genre(&genre)),
+            comment: text(&["COMM"]),
+            track: text(&["TRCK"]).and_then(|track| SetPosition::parse(&track)),
+            disc: text(&["TPOS"]).and_then(|disc| SetPosition::parse(&disc)),
+            artwork: self.artwork(),
+            chapters: self.chapters(),
+            ..Default::default()
+        };
+
+        for frame in &self.frames
+        {
+            match frame.content
+            {
+                | Some(Id3v2FrameContent::UserText(ref user_text)) =>
+                {
+                    let key = format!("TXXX:{}", user_text.description);
+                    metadata.loudness.extend(Loudness::from_field(&key, &user_text.value));
+                    metadata.add_custom(key, user_text.value.clone());
+                }
+                // iTunes stores Sound Check and gapless data as COMM frames described "iTunNORM" and "iTunSMPB"
+                | Some(Id3v2FrameContent::Comment(ref comment)) if frame.id == "COMM" && comment.description.is_empty() == false =>
+                {
+                    let key = format!("COMM:{}", comment.description);
+                    metadata.loudness.extend(Loudness::from_field(&key, &comment.text));
+                    metadata.add_custom(key, comment.text.clone());
+                }
+                | Some(Id3v2FrameContent::RelativeVolume(ref relative_volume)) => metadata.loudness.extend(Loudness::from_relative_volume(relative_volume)),
+                | Some(Id3v2FrameContent::Text(ref text_frame)) if NORMALIZED_FRAMES.contains(&frame.id.as_str()) == false =>
+                    metadata.add_custom(frame.id.clone(), text_frame.primary_text()),
+                // Identifiers are kept when they are text, as the MusicBrainz recording ID is
+                | Some(Id3v2FrameContent::UniqueFileId(ref ufid)) =>
+                {
+                    if let Ok(identifier) = std::str::from_utf8(&ufid.identifier) &&
+                        identifier.chars().any(char::is_control) == false
+                    {
+                        metadata.add_custom(format!("UFID:{}", ufid.owner_identifier), identifier);
+                    }
+                }
+                // iTunes marks podcast episodes with a four-byte flag
+                | Some(Id3v2FrameContent::Binary) if frame.id == "PCST" && frame.data.len() == 4 =>
+                    metadata.add_custom("PCST", u32::from_be_bytes([frame.data[0], frame.data[1], frame.data[2], frame.data[3]]).to_string()),
+                | Some(Id3v2FrameContent::Url(_) | Id3v2FrameContent::UserUrl(_)) =>
+                {
+                    if let Some(url) = frame.get_url()
+                    {
+                        metadata.add_custom(frame.id.clone(), url);
+                    }
+                }
+                | _ => ()
+            }
+        }
+        metadata
+    }
+
+    /// Frames and chapter sub-frames with the ID `code`, located by their file offsets
+    pub fn occurrences(&self, code: &str) -> Vec<Occurrence>
+    {
+        // Body offsets do not map to file offsets once tag-level unsynchronisation was removed (ID3v2.3)
+        if self.flags & 0x80 != 0 && self.major_version == 3
+        {
+            return Vec::new();
+        }
+
+        let body_offset = self.offset + 10;
+        let mut occurrences = Vec::new();
+        for frame in &self.frames
+        {
+            let Some(frame_offset) = frame.offset.map(|offset| body_offset + offset as u64)
+            else
+            {
+                continue;
+            };
+            if frame.id == code
+            {
+                occurrences.push(Occurrence {
+                    offset: frame_offset,
+                    size:   Some(10 + frame.size as u64),
+                    kind:   StructureKind::Frame,
+                    path:   Some(frame.id.clone())
+                });
+            }
+
+            // Sub-frame offsets are relative to the sub-frame list, which only maps to the file for plain frames
+            let (element_id, sub_frames, sub_frames_start) = match frame.content
+            {
+                | Some(Id3v2FrameContent::Chapter(ref chapter)) => (&chapter.element_id, &chapter.sub_frames, chapter.sub_frames_start()),
+                | Some(Id3v2FrameContent::TableOfContents(ref toc)) => (&toc.element_id, &toc.sub_frames, toc.sub_frames_start()),
+                | _ => continue
+            };
+            if frame.format.is_plain() == false
+            {
+                continue;
+            }
+            for sub_frame in sub_frames.iter().filter(|sub_frame| sub_frame.id == code)
+            {
+                let offset = frame_offset + 10 + (sub_frames_start + sub_frame.offset.unwrap_or(0)) as u64;
+                let path = format!("{} {}/{}", frame.id, element_id, sub_frame.id);
+                occurrences.push(Occurrence { offset, size: Some(10 + sub_frame.size as u64), kind: StructureKind::Frame, path: Some(path) });
+            }
+        }
+        occurrences
+    }
+
+    /// Call `visitor` for all frames and chapter sub-frames in tag order, with their data where it maps to the file
+    pub fn visit(&self, visitor: &mut dyn DissectionVisitor)
+    {
+        // Body offsets do not map to file offsets once tag-level unsynchronisation was removed (ID3v2.3)
+        let body_offset = match self.flags & 0x80 != 0 && self.major_version == 3
+        {
+            | true => None,
+            | false => Some(self.offset + 10)
+        };
+
+        for frame in &self.frames
+        {
+            let frame_offset = body_offset.zip(frame.offset).map(|(body_offset, offset)| body_offset + offset as u64);
+            visitor.on_frame(frame, frame_offset, 0);
+            if let Some(frame_offset) = frame_offset &&
+                frame.format.is_plain() == true
+            {
+                visitor.on_data(frame_offset + 10, &frame.data);
+            }
+
+            // Sub-frame offsets are relative to the sub-frame list, which only maps to the file for plain frames
+            let (sub_frames, sub_frames_start) = match frame.content
+            {
+                | Some(Id3v2FrameContent::Chapter(ref chapter)) => (&chapter.sub_frames, chapter.sub_frames_start()),
+                | Some(Id3v2FrameContent::TableOfContents(ref toc)) => (&toc.sub_frames, toc.sub_frames_start()),
+                | _ => continue
+            };
+            for sub_frame in sub_frames
+            {
+                let sub_frame_offset = frame_offset
+                    .filter(|_| frame.format.is_plain() == true)
+                    .map(|frame_offset| frame_offset + 10 + (sub_frames_start + sub_frame.offset.unwrap_or(0)) as u64);
+                visitor.on_frame(sub_frame, sub_frame_offset, 1);
+            }
+        }
+    }
+
+    /// Mark the tag header, frames, zero padding and footer as covered
+    pub fn add_coverage(&self, file: &mut dyn MediaSource, coverage: &mut Coverage) -> Result<(), Box<dyn std::error::Error>>
+    {
+        let body_offset = self.offset + 10;
+
+        // Body offsets do not map to file offsets once tag-level unsynchronisation was removed (ID3v2.3)
+        if self.flags & 0x80 != 0 && self.major_version == 3
+        {
+            coverage.add(self.offset, self.end_offset());
+            return Ok(());
+        }
+
+        let padding_offset = body_offset + self.frames_end as u64;
+        let body_end = body_offset + self.size as u64;
+        coverage.add(self.offset, padding_offset);
+
+        let mut padding = vec![0u8; (body_end - padding_offset) as usize];
+        file.seek(SeekFrom::Start(padding_offset))?;
+        file.read_exact(&mut padding)?;
+        coverage.add_padding(padding_offset, &padding);
+
+        coverage.add(body_end, self.end_offset());
+        Ok(())
+    }
+
+    /// Add the tag, its frames, chapter sub-frames and padding to `layout`
+    pub fn add_layout(&self, layout: &mut Layout)
+    {
+        layout.add(self.offset, self.end_offset() - self.offset, 0, format!("ID3v2.{}", self.major_version));
+
+        // Body offsets do not map to file offsets once tag-level unsynchronisation was removed (ID3v2.3)
+        if self.flags & 0x80 != 0 && self.major_version == 3
+        {
+            return;
+        }
+
+        let body_offset = self.offset + 10;
+        for frame in &self.frames
+        {
+            let Some(frame_offset) = frame.offset.map(|offset| body_offset + offset as u64)
+            else
+            {
+                continue;
+            };
+
+            let (element_id, sub_frames, sub_frames_start) = match frame.content
+            {
+                | Some(Id3v2FrameContent::Chapter(ref chapter)) => (&chapter.element_id, &chapter.sub_frames, chapter.sub_frames_start()),
+                | Some(Id3v2FrameContent::TableOfContents(ref toc)) => (&toc.element_id, &toc.sub_frames, toc.sub_frames_start()),
+                | _ =>
+                {
+                    layout.add(frame_offset, 10 + frame.size as u64, 1, frame.id.clone());
+                    continue;
+                }
+            };
+            layout.add(frame_offset, 10 + frame.size as u64, 1, format!("{} {}", frame.id, element_id));
+
+            // Sub-frame offsets are relative to the sub-frame list, which only maps to the file for plain frames
+            if frame.format.is_plain() == true
+            {
+                for sub_frame in sub_frames
+                {
+                    let offset = frame_offset + 10 + (sub_frames_start + sub_frame.offset.unwrap_or(0)) as u64;
+                    layout.add(offset, 10 + sub_frame.size as u64, 2, sub_frame.id.clone());
+                }
+            }
+        }
+        layout.add(body_offset + self.frames_end as u64, (self.size as usize).saturating_sub(self.frames_end) as u64, 1, "Padding");
+    }
+
+    /// Offset just past the tag (header, body and optional v2.4 footer)
+    pub fn end_offset(&self) -> u64
+    {
+        let footer = if self.major_version == 4 && self.flags & 0x10 != 0
+        {
+            10
+        }
+        else
+        {
+            0
+        };
+        self.offset + 10 + self.size as u64 + footer
+    }
+}
+
+/// Add the pictures of APIC frames to `artwork`, descending into CHAP/CTOC sub-frames
+fn collect_pictures(frames: &[Id3v2Frame], prefix: &str, artwork: &mut Vec<Artwork>)
+{
+    for frame in frames
+    {
+        match frame.content
+        {
+            | Some(Id3v2FrameContent::Picture(ref picture)) =>
+            {
+                let mut description = picture.picture_type_description().to_string();
+                if picture.description.is_empty() == false
+                {
+                    description.push_str(&format!(": {}", picture.description));
+                }
+                artwork.push(Artwork {
+                    location: format!("{}{}", prefix, frame.id),
+                    mime_type: picture.mime_type.clone(),
+                    description,
+                    data: picture.picture_data.clone()
+                });
+            }
+            | Some(Id3v2FrameContent::Chapter(ref chapter)) =>
+            {
+                collect_pictures(&chapter.sub_frames, &format!("{}{} {}/", prefix, frame.id, chapter.element_id), artwork);
+            }
+            | Some(Id3v2FrameContent::TableOfContents(ref toc)) =>
+            {
+                collect_pictures(&toc.sub_frames, &format!("{}{} {}/", prefix, frame.id, toc.element_id), artwork);
+            }
+            | _ =>
+            {}
+        }
+    }
+}
+
+/// Add the payloads of GEOB, PRIV and UFID frames in `frames` and their CHAP/CTOC sub-frames to `objects`
+fn collect_objects(frames: &[Id3v2Frame], prefix: &str, objects: &mut Vec<EmbeddedObject>)
+{
+    for frame in frames
+    {
+        let location = format!("{}{}", prefix, frame.id);
+        let object = match frame.content
+        {
+            | Some(Id3v2FrameContent::EncapsulatedObject(ref geob)) => EmbeddedObject {
+                kind: ObjectKind::EncapsulatedObject,
+                location,
+                mime_type: geob.mime_type.clone(),
+                name: match geob.filename.is_empty()
+                {
+                    | true => geob.description.clone(),
+                    | false => geob.filename.clone()
+                },
+                data: geob.object.clone()
+            },
+            | Some(Id3v2FrameContent::Private(ref private)) =>
+                EmbeddedObject { kind: ObjectKind::Private, location, mime_type: String::new(), name: private.owner_identifier.clone(), data: private.data.clone() },
+            | Some(Id3v2FrameContent::UniqueFileId(ref ufid)) => EmbeddedObject {
+                kind: ObjectKind::UniqueFileId,
+                location,
+                mime_type: String::new(),
+                name: ufid.owner_identifier.clone(),
+                data: ufid.identifier.clone()
+            },
+            | Some(Id3v2FrameContent::Chapter(ref chapter)) =>
+            {
+                collect_objects(&chapter.sub_frames, &format!("{}{} {}/", prefix, frame.id, chapter.element_id), objects);
+                continue;
+            }
+            | Some(Id3v2FrameContent::TableOfContents(ref toc)) =>
+            {
+                collect_objects(&toc.sub_frames, &format!("{}{} {}/", prefix, frame.id, toc.element_id), objects);
+                continue;
+            }
+            | _ => continue
+        };
+        objects.push(object);
+    }
+}
+
+/// Replace numeric ID3v1 genre references in a TCON value ("(13)", "13", "(13)Britpop") with the genre name
+fn resolve_genre(genre: &str) -> String
+{
+    let name = |reference: &str| match reference
+    {
+        | "RX" => Some("Remix"),
+        | "CR" => Some("Cover"),
+        | _ => reference.parse().ok().and_then(genre_by_index)
+    };
+
+    if let Some(reference) = genre.strip_prefix('(') &&
+        let Some((reference, refinement)) = reference.split_once(')')
+    {
+        // A refinement after the reference is more specific than the referenced genre
+        if refinement.is_empty() == false
+        {
+            return refinement.to_string();
+        }
+        return name(reference).map_or_else(|| genre.to_string(), str::to_string);
+    }
+    name(genre.trim()).map_or_else(|| genre.to_string(), str::to_string)
+}
@@ -4,6 +4,8 @@
 /// text in various encodings used by ID3v2 frames.
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// Text encoding types used in ID3v2 frames
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TextEncoding
@@ -29,7 +31,7 @@ pub fn from_byte(byte: u8) -> Result<Self, String>
             | 1 => Ok(TextEncoding::Utf16Bom),
             | 2 => Ok(TextEncoding::Utf16Be),
             | 3 => Ok(TextEncoding::Utf8),
-            | _ => Err(format!("Unknown text encoding: {}", byte))
+            | _ => Err(tr_fmt("Unknown text encoding: {}", &[&byte]))
         }
     }
 
@@ -55,7 +57,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
             | TextEncoding::Utf16Be => "UTF-16BE",
             | TextEncoding::Utf8 => "UTF-8"
         };
-        write!(f, "{}", name)
+        write!(f, "{}", tr(name))
     }
 }
 
@@ -189,6 +191,13 @@ pub fn is_null_terminator(bytes: &[u8], encoding: TextEncoding) -> bool
     }
 }
 
+/// Split a null-terminated ISO-8859-1 string off the front of `data`, returning it and the remaining bytes
+pub fn split_iso88591_terminated(data: &[u8]) -> Option<(String, &[u8])>
+{
+    let terminator = data.iter().position(|&b| b == 0)?;
+    Some((decode_iso88591_string(&data[..terminator]), &data[terminator + 1..]))
+}
+
 /// Decode ISO-8859-1 string
 pub fn decode_iso88591_string(data: &[u8]) -> String
 {
@@ -228,13 +237,13 @@ pub fn decode_utf16_string(data: &[u8], encoding: TextEncoding) -> Result<String
             }
         }
         | TextEncoding::Utf16Be => (0, false), // Always big endian
-        | _ => return Err("Invalid UTF-16 encoding".to_string())
+        | _ => return Err(tr("Invalid UTF-16 encoding").to_string())
     };
 
     let utf16_data = &data[start_pos..];
     if utf16_data.len().is_multiple_of(2) == false
     {
-        return Err("UTF-16 data length must be even".to_string());
+        return Err(tr("UTF-16 data length must be even").to_string());
     }
 
     let mut utf16_chars = Vec::new();
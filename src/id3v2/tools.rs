@@ -1,6 +1,21 @@
-use std::{
-    fs::File,
-    io::{Read, Seek, SeekFrom, Write}
+use std::io::{SeekFrom, Write};
+
+use crate::{
+    ape_tag::{ApeTag, dissect_ape_tag},
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    dissector_registry::DissectorRegistry,
+    ffprobe::ProbeFormat,
+    i18n::{tr, tr_fmt},
+    id3v1::{Id3v1Tag, dissect_id3v1_trailer},
+    id3v2::{extended_header::ExtendedHeader, frame::Id3v2Frame, tag::Id3v2Tag},
+    layout::Layout,
+    lyrics3::{Lyrics3Tag, dissect_lyrics3_tag},
+    media_source::MediaSource,
+    mpeg_audio,
+    options::{DissectOptions, TagSizeThresholds},
+    path_filter::{PathFilter, PathMatch},
+    summary::Summary
 };
 
 /// ID3v2 header information: (major_version, minor_version, flags, size)
@@ -116,10 +131,142 @@ pub fn get_frame_description(frame_id: &str) -> &'static str
         | "CHAP" => "Chapter frame",
         | "CTOC" => "Table of contents frame",
 
+        // iTunes podcast frames
+        | "PCST" => "Podcast flag",
+        | "TCAT" => "Podcast category",
+        | "TDES" => "Podcast description",
+        | "TGID" => "Podcast episode identifier",
+        | "TKWD" => "Podcast keywords",
+        | "WFED" => "Podcast feed URL",
+
         | _ => "Unknown frame type"
     }
 }
 
+/// Get the specification section that defines an ID3v2 frame (e.g. "id3v2.4.0-frames §4.2.1")
+pub fn get_frame_spec_reference(frame_id: &str, major_version: u8) -> Option<String>
+{
+    // The podcast frames are an iTunes extension without a specification
+    if ITUNES_PODCAST_FRAME_IDS.contains(&frame_id) == true
+    {
+        return None;
+    }
+
+    // CHAP and CTOC are defined by the addendum, not by the version's frame document
+    let chapter_section = match frame_id
+    {
+        | "CHAP" => Some("3.1"),
+        | "CTOC" => Some("3.2"),
+        | _ => None
+    };
+    if let Some(section) = chapter_section
+    {
+        return Some(format!("id3v2-chapters-1.0 §{}", section));
+    }
+
+    let section = match major_version
+    {
+        | 3 => get_frame_section_v3(frame_id)?,
+        | 4 => get_frame_section_v4(frame_id)?,
+        | _ => return None
+    };
+    let document = if major_version == 3
+    {
+        "id3v2.3.0"
+    }
+    else
+    {
+        "id3v2.4.0-frames"
+    };
+    Some(format!("{} §{}", document, section))
+}
+
+/// Section of the ID3v2.3.0 specification that defines a frame
+fn get_frame_section_v3(frame_id: &str) -> Option<&'static str>
+{
+    let section = match frame_id
+    {
+        | "UFID" => "4.1",
+        | "TXXX" => "4.2.2",
+        | id if id.starts_with('T') == true && is_valid_id3v2_3_frame(id) == true => "4.2.1",
+        | "WXXX" => "4.3.2",
+        | id if id.starts_with('W') == true && is_valid_id3v2_3_frame(id) == true => "4.3.1",
+        | "IPLS" => "4.4",
+        | "MCDI" => "4.5",
+        | "ETCO" => "4.6",
+        | "MLLT" => "4.7",
+        | "SYTC" => "4.8",
+        | "USLT" => "4.9",
+        | "SYLT" => "4.10",
+        | "COMM" => "4.11",
+        | "RVAD" => "4.12",
+        | "EQUA" => "4.13",
+        | "RVRB" => "4.14",
+        | "APIC" => "4.15",
+        | "GEOB" => "4.16",
+        | "PCNT" => "4.17",
+        | "POPM" => "4.18",
+        | "RBUF" => "4.19",
+        | "AENC" => "4.20",
+        | "LINK" => "4.21",
+        | "POSS" => "4.22",
+        | "USER" => "4.23",
+        | "OWNE" => "4.24",
+        | "COMR" => "4.25",
+        | "ENCR" => "4.26",
+        | "GRID" => "4.27",
+        | "PRIV" => "4.28",
+        | _ => return None
+    };
+    Some(section)
+}
+
+/// Section of the ID3v2.4.0 frames specification that defines a frame
+fn get_frame_section_v4(frame_id: &str) -> Option<&'static str>
+{
+    let section = match frame_id
+    {
+        | "UFID" => "4.1",
+        | "TIT1" | "TIT2" | "TIT3" | "TALB" | "TOAL" | "TRCK" | "TPOS" | "TSST" | "TSRC" => "4.2.1",
+        | "TPE1" | "TPE2" | "TPE3" | "TPE4" | "TOPE" | "TEXT" | "TOLY" | "TCOM" | "TMCL" | "TIPL" | "TENC" => "4.2.2",
+        | "TBPM" | "TLEN" | "TKEY" | "TLAN" | "TCON" | "TFLT" | "TMED" | "TMOO" => "4.2.3",
+        | "TCOP" | "TPRO" | "TPUB" | "TOWN" | "TRSN" | "TRSO" => "4.2.4",
+        | "TOFN" | "TDLY" | "TDEN" | "TDOR" | "TDRC" | "TDRL" | "TDTG" | "TSSE" | "TSOA" | "TSOP" | "TSOT" => "4.2.5",
+        | "TXXX" => "4.2.6",
+        | "WXXX" => "4.3.2",
+        | id if id.starts_with('W') == true && is_valid_id3v2_4_frame(id) == true => "4.3.1",
+        | "MCDI" => "4.4",
+        | "ETCO" => "4.5",
+        | "MLLT" => "4.6",
+        | "SYTC" => "4.7",
+        | "USLT" => "4.8",
+        | "SYLT" => "4.9",
+        | "COMM" => "4.10",
+        | "RVA2" => "4.11",
+        | "EQU2" => "4.12",
+        | "RVRB" => "4.13",
+        | "APIC" => "4.14",
+        | "GEOB" => "4.15",
+        | "PCNT" => "4.16",
+        | "POPM" => "4.17",
+        | "RBUF" => "4.18",
+        | "AENC" => "4.19",
+        | "LINK" => "4.20",
+        | "POSS" => "4.21",
+        | "USER" => "4.22",
+        | "OWNE" => "4.23",
+        | "COMR" => "4.24",
+        | "ENCR" => "4.25",
+        | "GRID" => "4.26",
+        | "PRIV" => "4.27",
+        | "SIGN" => "4.28",
+        | "SEEK" => "4.29",
+        | "ASPI" => "4.30",
+        | _ => return None
+    };
+    Some(section)
+}
+
 /// Check if the given header indicates an ID3v2 file and return the version
 pub fn detect_id3v2_version(header: &[u8]) -> Option<(u8, u8)>
 {
@@ -144,11 +291,11 @@ pub fn detect_mpeg_sync(header: &[u8]) -> bool
     false
 }
 
-/// Read and parse ID3v2 header, returning version info and tag size
-pub fn read_id3v2_header(file: &mut File) -> Result<Option<Id3v2Header>, Box<dyn std::error::Error>>
+/// Read and parse the ID3v2 header at `offset`, returning version info and tag size
+pub fn read_id3v2_header(file: &mut dyn MediaSource, offset: u64, output: &mut dyn Write) -> Result<Option<Id3v2Header>, Box<dyn std::error::Error>>
 {
-    // Seek to beginning and read ID3v2 header
-    file.seek(SeekFrom::Start(0))?;
+    // Seek to the start of the tag and read ID3v2 header
+    file.seek(SeekFrom::Start(offset))?;
     let mut id3_header = [0u8; 10];
 
     if file.read_exact(&mut id3_header).is_err()
@@ -166,16 +313,13 @@ pub fn read_id3v2_header(file: &mut File) -> Result<Option<Id3v2Header>, Box<dyn
     let flags = id3_header[5];
 
     // Add diagnostic output for raw header bytes
-    println!(
-        "  Raw header bytes: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}]",
-        id3_header[0], id3_header[1], id3_header[2], id3_header[3], id3_header[4], id3_header[5], id3_header[6], id3_header[7], id3_header[8], id3_header[9]
-    );
+    writeln!(output, "  {}", tr_fmt("Raw header bytes: {}", &[&hex_list(&id3_header)]))?;
 
     // Calculate tag size (synchsafe integer)
     let size = decode_synchsafe_int(&id3_header[6..10]);
 
     // Add diagnostic for size bytes
-    println!("  Size bytes: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}]", id3_header[6], id3_header[7], id3_header[8], id3_header[9]);
+    writeln!(output, "  {}", tr_fmt("Size bytes: {}", &[&hex_list(&id3_header[6..10])]))?;
 
     // Validate synchsafe format (each byte should have MSB = 0)
     let mut synchsafe_violation = false;
@@ -183,14 +327,14 @@ pub fn read_id3v2_header(file: &mut File) -> Result<Option<Id3v2Header>, Box<dyn
     {
         if byte & 0x80 != 0
         {
-            println!("  WARNING: Size byte {} (0x{:02X}) violates synchsafe format (MSB set)!", i, byte);
+            writeln!(output, "  {}", tr_fmt("WARNING: Size byte {} ({}) violates synchsafe format (MSB set)!", &[&i, &format!("0x{:02X}", byte)]))?;
             synchsafe_violation = true;
         }
     }
 
     if synchsafe_violation
     {
-        println!("  ERROR: Invalid synchsafe format detected in size field");
+        writeln!(output, "  {}", tr("ERROR: Invalid synchsafe format detected in size field"))?;
     }
 
     Ok(Some((version_major, version_minor, flags, size)))
@@ -209,6 +353,12 @@ pub fn decode_synchsafe_int(bytes: &[u8]) -> u32
     }
 }
 
+/// Encode a synchsafe integer (7 bits per byte, values below 2^28)
+pub fn encode_synchsafe_int(value: u32) -> [u8; 4]
+{
+    [(value >> 21) as u8 & 0x7F, (value >> 14) as u8 & 0x7F, (value >> 7) as u8 & 0x7F, value as u8 & 0x7F]
+}
+
 /// Remove unsynchronization bytes (0xFF 0x00 -> 0xFF) from ID3v2 data
 pub fn remove_unsynchronization(data: &[u8]) -> Vec<u8>
 {
@@ -233,6 +383,9 @@ pub fn remove_unsynchronization(data: &[u8]) -> Vec<u8>
     result
 }
 
+/// Frames iTunes writes to podcast episodes in ID3v2.3 and ID3v2.4 tags (WFED and TGID hold text with an encoding byte)
+pub const ITUNES_PODCAST_FRAME_IDS: [&str; 6] = ["PCST", "TCAT", "TDES", "TGID", "TKWD", "WFED"];
+
 /// Check if a frame ID is valid for ID3v2.3
 pub fn is_valid_id3v2_3_frame(frame_id: &str) -> bool
 {
@@ -247,7 +400,7 @@ pub fn is_valid_id3v2_3_frame(frame_id: &str) -> bool
         "CHAP", "CTOC"
     ];
 
-    VALID_ID3V2_3_FRAME_IDS.contains(&frame_id)
+    VALID_ID3V2_3_FRAME_IDS.contains(&frame_id) || ITUNES_PODCAST_FRAME_IDS.contains(&frame_id)
 }
 
 /// Check if a frame ID is valid for ID3v2.4
@@ -264,7 +417,7 @@ pub fn is_valid_id3v2_4_frame(frame_id: &str) -> bool
         "CHAP", "CTOC"
     ];
 
-    VALID_ID3V2_4_FRAME_IDS.contains(&frame_id)
+    VALID_ID3V2_4_FRAME_IDS.contains(&frame_id) || ITUNES_PODCAST_FRAME_IDS.contains(&frame_id)
 }
 
 /// Check if a frame ID is valid for a specific ID3v2 version
@@ -343,6 +496,88 @@ pub fn parse_embedded_frames(frame_data: &[u8], version_major: u8) -> Vec<crate:
     embedded_frames
 }
 
+/// Find the next plausible frame header in `buffer` at or after `from`: a frame ID valid for the version with a non-zero size that fits
+pub fn find_next_frame(buffer: &[u8], from: usize, version_major: u8) -> Option<usize>
+{
+    (from..buffer.len().saturating_sub(9)).find(|&pos| {
+        let frame_id = std::str::from_utf8(&buffer[pos..pos + 4]).unwrap_or("");
+        let frame_size = if version_major == 4
+        {
+            decode_synchsafe_int(&buffer[pos + 4..pos + 8])
+        }
+        else
+        {
+            u32::from_be_bytes([buffer[pos + 4], buffer[pos + 5], buffer[pos + 6], buffer[pos + 7]])
+        } as usize;
+        is_valid_frame_for_version(frame_id, version_major) == true && frame_size > 0 && frame_size <= buffer.len() - pos - 10
+    })
+}
+
+/// Resynchronize after corrupt frame data at `pos` of the tag body (`--recover`), reporting `problem` as a warning
+///
+/// Returns the position of the next plausible frame, None if the rest of the tag holds none.
+/// Trailing data that is all zero is regular padding and not reported.
+pub fn recover_frames(buffer: &[u8], pos: usize, version_major: u8, tag_offset: u64, problem: &str, diagnostics: &mut Diagnostics) -> Option<usize>
+{
+    if buffer[pos..].iter().all(|&byte| byte == 0) == true
+    {
+        return None;
+    }
+
+    let offset = Some(tag_offset + 10 + pos as u64);
+    match find_next_frame(buffer, pos + 1, version_major)
+    {
+        | Some(next) =>
+        {
+            diagnostics.warning(offset, tr_fmt("{}; skipped {} bytes to the next frame", &[&problem, &(next - pos)]));
+            Some(next)
+        }
+        | None =>
+        {
+            diagnostics.warning(offset, tr_fmt("{}; no further frame in the remaining {} bytes", &[&problem, &(buffer.len() - pos)]));
+            None
+        }
+    }
+}
+
+/// Check whether a `--filter` selects a frame, either itself or one of its sub-frames
+///
+/// `parse` is only called for frames whose sub-frames have to be inspected.
+pub fn is_frame_selected(filter: &PathFilter, frame_id: &str, parse: impl FnOnce() -> Option<Id3v2Frame>) -> bool
+{
+    match filter.check(&[frame_id])
+    {
+        | PathMatch::Selected => true,
+        | PathMatch::Ancestor => parse().is_some_and(|mut frame| select_frame(filter, &mut frame)),
+        | PathMatch::None => false
+    }
+}
+
+/// Apply a `--filter` to a parsed frame, keeping only its selected sub-frames unless the frame itself is selected
+///
+/// Returns false if neither the frame nor one of its sub-frames is selected.
+pub fn select_frame(filter: &PathFilter, frame: &mut Id3v2Frame) -> bool
+{
+    match filter.check(&[&frame.id])
+    {
+        | PathMatch::Selected => true,
+        | PathMatch::Ancestor =>
+        {
+            let id = frame.id.clone();
+            match frame.sub_frames_mut()
+            {
+                | Some(sub_frames) =>
+                {
+                    sub_frames.retain(|sub_frame| filter.check(&[&id, &sub_frame.id]) == PathMatch::Selected);
+                    sub_frames.is_empty() == false
+                }
+                | None => false
+            }
+        }
+        | PathMatch::None => false
+    }
+}
+
 /// Display frame header information with customizable indentation
 /// This function provides unified frame header display for both top-level and embedded frames
 pub fn display_frame_header(output: &mut dyn Write, frame: &crate::id3v2::frame::Id3v2Frame, indentation: &str) -> std::io::Result<()>
@@ -356,20 +591,16 @@ pub fn display_frame_header(output: &mut dyn Write, frame: &crate::id3v2::frame:
     {
         writeln!(
             output,
-            "{}Frame offset 0x{:08X}, ID: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}] = \"{}\", Size: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}] = {}, Flags: 0x{:04X}",
+            "{}{}",
             indentation,
-            offset,
-            id_bytes[0],
-            id_bytes[1],
-            id_bytes[2],
-            id_bytes[3],
-            frame.id,
-            size_bytes[0],
-            size_bytes[1],
-            size_bytes[2],
-            size_bytes[3],
-            frame.size,
-            frame.flags
+            tr_fmt("Frame offset {}, ID: {} = \"{}\", Size: {} = {}, Flags: {}", &[
+                &format!("0x{:08X}", offset),
+                &hex_list(id_bytes),
+                &frame.id,
+                &hex_list(&size_bytes),
+                &frame.size,
+                &format!("0x{:04X}", frame.flags)
+            ])
         )?;
     }
     else
@@ -377,21 +608,260 @@ pub fn display_frame_header(output: &mut dyn Write, frame: &crate::id3v2::frame:
         // Fallback for frames without offset information
         writeln!(
             output,
-            "{}ID: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}] = \"{}\", Size: [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}] = {}, Flags: 0x{:04X}",
+            "{}{}",
             indentation,
-            id_bytes[0],
-            id_bytes[1],
-            id_bytes[2],
-            id_bytes[3],
-            frame.id,
-            size_bytes[0],
-            size_bytes[1],
-            size_bytes[2],
-            size_bytes[3],
-            frame.size,
-            frame.flags
+            tr_fmt("ID: {} = \"{}\", Size: {} = {}, Flags: {}", &[
+                &hex_list(id_bytes),
+                &frame.id,
+                &hex_list(&size_bytes),
+                &frame.size,
+                &format!("0x{:04X}", frame.flags)
+            ])
         )?;
     }
 
     Ok(())
 }
+
+/// Bytes as a bracketed list of hex values, e.g. "[0x49, 0x44, 0x33]"
+fn hex_list(bytes: &[u8]) -> String
+{
+    format!("[{}]", bytes.iter().map(|byte| format!("0x{:02X}", byte)).collect::<Vec<_>>().join(", "))
+}
+
+/// Report large tag sizes according to the configured thresholds (rich podcasts embed chapter images, but huge tags may indicate corruption)
+pub fn check_tag_size(size: u32, offset: u64, thresholds: &TagSizeThresholds, diagnostics: &mut Diagnostics)
+{
+    let size = size as u64;
+    let exceeds = |threshold: Option<u64>| threshold.is_some_and(|limit| size > limit);
+
+    if exceeds(thresholds.critical) == true
+    {
+        diagnostics.warning(
+            Some(offset),
+            tr_fmt("Extremely large tag size ({} bytes > {} MB), verify file integrity", &[&size, &(thresholds.critical.unwrap_or(0) / 1_000_000)])
+        );
+    }
+    else if exceeds(thresholds.warning) == true
+    {
+        diagnostics.warning(
+            Some(offset),
+            tr_fmt("Tag size is very large ({} bytes > {} MB), likely rich podcast with chapter images", &[&size, &(thresholds.warning.unwrap_or(0) / 1_000_000)])
+        );
+    }
+    else if exceeds(thresholds.info) == true
+    {
+        diagnostics.info(
+            Some(offset),
+            tr_fmt("Large tag size ({} bytes > {} MB), possibly podcast with embedded chapter content", &[&size, &(thresholds.info.unwrap_or(0) / 1_000_000)])
+        );
+    }
+}
+
+/// Clamp the declared `size` of the tag at `offset` to the bytes present in a file of `file_size` bytes (`--recover`)
+///
+/// A tag running past the end of the file is reported as a warning, so the frames that fit can still be dissected.
+pub fn clamp_tag_size(size: u32, offset: u64, file_size: u64, diagnostics: &mut Diagnostics) -> u32
+{
+    let available = file_size.saturating_sub(offset + 10);
+    if size as u64 <= available
+    {
+        return size;
+    }
+
+    diagnostics.warning(Some(offset), tr_fmt("Tag size ({} bytes) exceeds the file, only {} bytes are present", &[&size, &available]));
+    available as u32
+}
+
+/// Report a CRC-32 mismatch of the extended header of the tag at `offset`
+pub fn check_extended_header_crc(header: &ExtendedHeader, offset: u64, diagnostics: &mut Diagnostics)
+{
+    if let (Some(crc), Some(computed_crc)) = (header.crc, header.computed_crc) &&
+        crc != computed_crc
+    {
+        diagnostics.warning(
+            Some(offset),
+            tr_fmt("ID3v2 extended header CRC-32 mismatch: stored {}, computed {}", &[&format!("0x{:08X}", crc), &format!("0x{:08X}", computed_crc)])
+        );
+    }
+}
+
+/// Whether the ID3v2.4 footer behind the tag at `offset` repeats the tag header with the "3DI" identifier
+pub fn id3v2_footer_matches(file: &mut dyn MediaSource, offset: u64, header: Id3v2Header) -> Result<bool, Box<dyn std::error::Error>>
+{
+    let (major, minor, flags, size) = header;
+    file.seek(SeekFrom::Start(offset + 10 + size as u64))?;
+    let mut footer = [0u8; 10];
+    if file.read_exact(&mut footer).is_err()
+    {
+        return Ok(false);
+    }
+
+    Ok(&footer[0..3] == b"3DI" && footer[3] == major && footer[4] == minor && footer[5] == flags && decode_synchsafe_int(&footer[6..10]) == size)
+}
+
+/// Report a missing or inconsistent ID3v2.4 footer of the tag at `offset`
+pub fn check_id3v2_footer(footer_valid: bool, offset: u64, size: u32, diagnostics: &mut Diagnostics)
+{
+    if footer_valid == false
+    {
+        diagnostics.warning(Some(offset + 10 + size as u64), tr("ID3v2.4 footer flag is set, but the footer is missing or does not match the tag header"));
+    }
+}
+
+/// Describe the MPEG audio behind the ID3v2 tag at `offset` (or at `offset` itself for files without a tag) in ffprobe terms
+///
+/// The audio stream runs to the end of the file, so the returned end offset covers it when audio is found.
+pub fn probe_tagged_audio(file: &mut dyn MediaSource, offset: u64) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+{
+    let tag_end = Id3v2Tag::read(file, offset)?.map_or(offset, |tag| tag.end_offset());
+
+    match mpeg_audio::probe(file, tag_end)?
+    {
+        | Some(format) => Ok((Some(format), file.size()?)),
+        | None => Ok((None, tag_end))
+    }
+}
+
+/// Mark the ID3v2 tag at `offset` and the MPEG audio behind it as covered, returning the end of the covered region
+pub fn add_tagged_audio_coverage(file: &mut dyn MediaSource, offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+{
+    let tag_end = match Id3v2Tag::read(file, offset)?
+    {
+        | Some(tag) =>
+        {
+            tag.add_coverage(file, coverage)?;
+            tag.end_offset()
+        }
+        | None => offset
+    };
+
+    Ok(mpeg_audio::add_coverage(file, tag_end, coverage)?.max(tag_end))
+}
+
+/// Add the ID3v2 tag at `offset`, the MPEG audio behind it and the tags at the end of the file to `layout`,
+/// returning the end of the layer
+pub fn collect_tagged_audio_layout(file: &mut dyn MediaSource, offset: u64, layout: &mut Layout) -> Result<u64, Box<dyn std::error::Error>>
+{
+    let tag_end = match Id3v2Tag::read(file, offset)?
+    {
+        | Some(tag) =>
+        {
+            tag.add_layout(layout);
+            tag.end_offset()
+        }
+        | None => offset
+    };
+    let end_offset = add_tagged_audio_coverage(file, offset, &mut Coverage::new())?;
+
+    // Another format behind the tag reports the audio and the trailing tags in its own layer
+    if other_format_follows(file, tag_end)? == true
+    {
+        return Ok(end_offset);
+    }
+    let data_end = layout.add_trailing_tags(file, tag_end, end_offset)?;
+    if let Some(info) = mpeg_audio::analyze(file, tag_end)?
+    {
+        layout.add(info.offset, data_end.saturating_sub(info.offset), 0, "MPEG audio");
+    }
+    Ok(end_offset)
+}
+
+/// Add the summary of the tag at `offset` to `summary`, taking the duration from the MPEG audio stream if the tag has no TLEN
+pub fn collect_tag_summary(file: &mut dyn MediaSource, offset: u64, summary: &mut Summary) -> Result<u64, Box<dyn std::error::Error>>
+{
+    if let Some(tag) = Id3v2Tag::read(file, offset)?
+    {
+        let mut tag_summary = tag.summary();
+        if tag_summary.duration.is_none() == true &&
+            let Some(info) = mpeg_audio::analyze(file, tag.end_offset())?
+        {
+            tag_summary.duration = Some(info.duration);
+            tag_summary.duration_source = Some("MPEG audio");
+        }
+        summary.tags.push(tag_summary);
+    }
+    add_tagged_audio_coverage(file, offset, &mut Coverage::new())
+}
+
+/// Check whether the data at `offset` belongs to another recognized format rather than to MPEG audio
+fn other_format_follows(file: &mut dyn MediaSource, offset: u64) -> Result<bool, Box<dyn std::error::Error>>
+{
+    Ok(DissectorRegistry::new().build_for_offset(file, offset)?.is_some())
+}
+
+/// Print the MPEG audio stream that follows an ID3v2 tag ending at `offset` and the file's APE and ID3v1 tags
+///
+/// Raw streams without an ID3v2 tag (`raw_stream`) are walked frame by frame. If another format follows
+/// the tag, its layer covers the rest of the file and reports the trailing tags.
+pub fn dissect_tagged_audio(
+    file: &mut dyn MediaSource, offset: u64, raw_stream: bool, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    if other_format_follows(file, offset)? == true
+    {
+        return Ok(());
+    }
+
+    mpeg_audio::dissect_mpeg_audio(file, offset, options, output, diagnostics)?;
+    if raw_stream == true
+    {
+        mpeg_audio::frame_scan::dissect_frame_scan(file, offset, options, output, diagnostics)?;
+    }
+    dissect_ape_tag(file, options, output, diagnostics)?;
+    dissect_lyrics3_tag(file, options, output, diagnostics)?;
+    dissect_id3v1_trailer(file, options, output)
+}
+
+/// Add the MPEG audio stream behind an ID3v2 tag ending at `offset` and the file's APE and ID3v1 tags to the JSON
+/// content of an ID3v2 layer (an object holding only these and the frame scan of the raw stream if there is no ID3v2 tag)
+///
+/// If another format follows the tag, its layer reports the trailing tags instead.
+pub fn with_tagged_audio(
+    file: &mut dyn MediaSource, offset: u64, content: serde_json::Value, diagnostics: &mut Diagnostics
+) -> Result<serde_json::Value, Box<dyn std::error::Error>>
+{
+    if other_format_follows(file, offset)? == true
+    {
+        return Ok(content);
+    }
+
+    let audio = mpeg_audio::analyze(file, offset)?;
+    let ape = ApeTag::read(file)?;
+    let id3v1 = Id3v1Tag::read(file)?;
+    if audio.is_none() && ape.is_none() && id3v1.is_none()
+    {
+        return Ok(content);
+    }
+
+    let raw_stream = content.is_null();
+    let mut content = match content
+    {
+        | serde_json::Value::Null => serde_json::json!({}),
+        | content => content
+    };
+    if let Some(audio) = audio
+    {
+        content["mpeg_audio"] = audio.to_json();
+    }
+    if raw_stream == true &&
+        let Some(scan) = mpeg_audio::frame_scan::scan_frames(file, offset, diagnostics)?
+    {
+        content["frame_scan"] = scan.to_json();
+    }
+    if let Some(ape) = ape
+    {
+        ape.check_item_count(diagnostics);
+        content["ape"] = ape.to_json();
+    }
+    if let Some(lyrics3) = Lyrics3Tag::read(file)?
+    {
+        lyrics3.check_fields(diagnostics);
+        content["lyrics3"] = lyrics3.to_json();
+    }
+    if let Some(id3v1) = id3v1
+    {
+        content["id3v1"] = id3v1.to_json();
+    }
+    Ok(content)
+}
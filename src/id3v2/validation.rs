@@ -0,0 +1,360 @@
+use std::{collections::HashMap, io::SeekFrom};
+
+use crate::{
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    id3v2::{
+        frame::{Id3v2Frame, Id3v2FrameContent},
+        frames::{chapter::ChapterFrame, table_of_contents::TableOfContentsFrame},
+        tag::Id3v2Tag,
+        text_encoding::TextEncoding,
+        tools::{check_extended_header_crc, check_id3v2_footer, decode_synchsafe_int, get_frame_description, is_valid_frame_for_version}
+    },
+    media_source::MediaSource
+};
+
+/// Frames that may appear only once per tag
+const SINGLE_INSTANCE_FRAMES: [&str; 12] = ["MCDI", "ETCO", "MLLT", "SYTC", "RVRB", "PCNT", "RBUF", "POSS", "OWNE", "SEEK", "ASPI", "IPLS"];
+
+/// Frames starting with a text encoding byte
+const ENCODED_FRAMES: [&str; 9] = ["WXXX", "COMM", "USLT", "APIC", "SYLT", "GEOB", "COMR", "OWNE", "USER"];
+
+/// Run specification conformance checks on the ID3v2 tag at `offset`, returning the offset past the tag
+pub fn validate_tag(file: &mut dyn MediaSource, offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+{
+    file.seek(SeekFrom::Start(offset))?;
+    let mut header = [0u8; 10];
+    if file.read_exact(&mut header).is_err() || &header[0..3] != b"ID3"
+    {
+        return Ok(offset);
+    }
+
+    let major_version = header[3];
+    let flags = header[5];
+    if header[6..10].iter().any(|byte| byte & 0x80 != 0)
+    {
+        diagnostics.error(Some(offset + 6), tr("Tag size is not a valid synchsafe integer"));
+    }
+
+    // The tag body must fit into the file before it can be parsed
+    let size = decode_synchsafe_int(&header[6..10]) as u64;
+    let footer_size = if major_version == 4 && flags & 0x10 != 0
+    {
+        10
+    }
+    else
+    {
+        0
+    };
+    let file_size = file.size()?;
+    if offset + 10 + size + footer_size > file_size
+    {
+        diagnostics.error(Some(offset), tr_fmt("Declared tag size ({} bytes) exceeds the end of the file", &[&size]));
+        return Ok(file_size);
+    }
+
+    let valid_flags = if major_version == 4
+    {
+        0xF0
+    }
+    else
+    {
+        0xE0
+    };
+    if flags & !valid_flags != 0
+    {
+        diagnostics.warning(Some(offset + 5), tr_fmt("Undefined tag header flags are set: {}", &[&format!("0x{:02X}", flags & !valid_flags)]));
+    }
+
+    if major_version == 4
+    {
+        check_synchsafe_frame_sizes(file, offset, size, flags, diagnostics)?;
+    }
+
+    let Some(tag) = Id3v2Tag::read(file, offset)?
+    else
+    {
+        return Ok(offset);
+    };
+    let body_offset = offset + 10;
+
+    if let Some(ref extended_header) = tag.extended_header
+    {
+        check_extended_header_crc(extended_header, body_offset, diagnostics);
+    }
+    if let Some(footer_valid) = tag.footer_valid
+    {
+        check_id3v2_footer(footer_valid, offset, tag.size, diagnostics);
+    }
+    check_encodings(&tag.frames, tag.major_version, body_offset, diagnostics);
+    check_unique_frames(&tag.frames, body_offset, diagnostics);
+    check_chapter_structure(&tag.frames, body_offset, diagnostics);
+    check_tag_extent(file, &tag, diagnostics)?;
+
+    for frame_id in ["TIT2", "TALB"]
+    {
+        if tag.frames.iter().any(|frame| frame.id == frame_id) == false
+        {
+            diagnostics.info(Some(offset), tr_fmt("No {} frame ({}) in the tag", &[&frame_id, &tr(get_frame_description(frame_id))]));
+        }
+    }
+
+    Ok(tag.end_offset())
+}
+
+/// Check the size fields of all ID3v2.4 frame headers for bytes with the high bit set
+fn check_synchsafe_frame_sizes(file: &mut dyn MediaSource, offset: u64, size: u64, flags: u8, diagnostics: &mut Diagnostics)
+-> Result<(), Box<dyn std::error::Error>>
+{
+    let body = file.map(offset + 10, size)?;
+
+    let mut pos = 0;
+    if flags & 0x40 != 0 && body.len() >= 4
+    {
+        if body[0..4].iter().any(|byte| byte & 0x80 != 0)
+        {
+            diagnostics.error(Some(offset + 10), tr("Extended header size is not a valid synchsafe integer"));
+        }
+        pos = decode_synchsafe_int(&body[0..4]) as usize;
+    }
+
+    while pos + 10 <= body.len() && body[pos] != 0
+    {
+        let frame_id = String::from_utf8_lossy(&body[pos..pos + 4]).to_string();
+        if body[pos + 4..pos + 8].iter().any(|byte| byte & 0x80 != 0)
+        {
+            diagnostics.error(Some(offset + 10 + pos as u64), tr_fmt("Frame {} size is not a valid synchsafe integer", &[&frame_id]));
+        }
+        pos += 10 + decode_synchsafe_int(&body[pos + 4..pos + 8]) as usize;
+    }
+
+    Ok(())
+}
+
+/// Check the text encoding byte of all frames that carry one
+fn check_encodings(frames: &[Id3v2Frame], major_version: u8, body_offset: u64, diagnostics: &mut Diagnostics)
+{
+    for frame in frames
+    {
+        let has_encoding = (frame.id.starts_with('T') == true || ENCODED_FRAMES.contains(&frame.id.as_str()) == true) &&
+            is_valid_frame_for_version(&frame.id, major_version) == true &&
+            frame.format.encryption_method.is_none();
+        let Some(&encoding_byte) = frame.data.first().filter(|_| has_encoding == true)
+        else
+        {
+            continue;
+        };
+
+        let frame_offset = frame.offset.map(|offset| body_offset + offset as u64);
+        match TextEncoding::from_byte(encoding_byte)
+        {
+            | Ok(encoding) if encoding.is_valid_for_version(major_version) == false =>
+                diagnostics.error(frame_offset, tr_fmt("Frame {} uses text encoding {}, which is not valid for ID3v2.{}", &[&frame.id, &encoding, &major_version])),
+            | Ok(_) => (),
+            | Err(_) => diagnostics.error(frame_offset, tr_fmt("Frame {} has an invalid text encoding byte {}", &[&frame.id, &format!("0x{:02X}", encoding_byte)]))
+        }
+    }
+}
+
+/// Identity of a frame among the frames of the same type, None if the frame may appear any number of times
+fn uniqueness_key(frame: &Id3v2Frame) -> Option<String>
+{
+    match (frame.id.as_str(), &frame.content)
+    {
+        | ("TXXX", Some(Id3v2FrameContent::UserText(user_text))) => Some(format!("TXXX \"{}\"", user_text.description)),
+        | ("WXXX", Some(Id3v2FrameContent::UserUrl(user_url))) => Some(format!("WXXX \"{}\"", user_url.description)),
+        | ("COMM" | "USLT", Some(Id3v2FrameContent::Comment(comment))) => Some(format!("{} {} \"{}\"", frame.id, comment.language, comment.description)),
+        | ("APIC", Some(Id3v2FrameContent::Picture(picture))) if picture.picture_type == 1 || picture.picture_type == 2 =>
+            Some(format!("APIC type {}", picture.picture_type)),
+        | ("APIC", Some(Id3v2FrameContent::Picture(picture))) => Some(format!("APIC \"{}\"", picture.description)),
+        | ("UFID", Some(Id3v2FrameContent::UniqueFileId(unique_file_id))) => Some(format!("UFID \"{}\"", unique_file_id.owner_identifier)),
+        | ("RVA2", Some(Id3v2FrameContent::RelativeVolume(relative_volume))) => Some(format!("RVA2 \"{}\"", relative_volume.identification)),
+        | ("EQU2", Some(Id3v2FrameContent::Equalisation(equalisation))) => Some(format!("EQU2 \"{}\"", equalisation.identification)),
+        | ("WCOM" | "WOAR", _) => None,
+        | (id, _) if (id.starts_with('T') == true && id != "TXXX") || (id.starts_with('W') == true && id != "WXXX") => Some(id.to_string()),
+        | (id, _) if SINGLE_INSTANCE_FRAMES.contains(&id) == true => Some(id.to_string()),
+        | _ => None
+    }
+}
+
+/// Report frames that must be unique but appear more than once
+fn check_unique_frames(frames: &[Id3v2Frame], body_offset: u64, diagnostics: &mut Diagnostics)
+{
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for frame in frames
+    {
+        let Some(key) = uniqueness_key(frame)
+        else
+        {
+            continue;
+        };
+
+        let count = seen.entry(key.clone()).or_insert(0);
+        *count += 1;
+        if *count == 2
+        {
+            diagnostics.warning(frame.offset.map(|offset| body_offset + offset as u64), tr_fmt("Frame {} appears more than once, but must be unique", &[&key]));
+        }
+    }
+}
+
+/// Check element IDs, table of contents references and chapter times of CHAP/CTOC frames
+fn check_chapter_structure(frames: &[Id3v2Frame], body_offset: u64, diagnostics: &mut Diagnostics)
+{
+    let mut element_ids: HashMap<&str, &Id3v2Frame> = HashMap::new();
+    // Child element IDs with the offset of the CTOC frame listing them
+    let mut referenced: Vec<(&str, Option<u64>)> = Vec::new();
+    let mut ordered_tocs: Vec<(&TableOfContentsFrame, Option<u64>)> = Vec::new();
+    let mut top_level_count = 0;
+    let mut has_toc = false;
+
+    for frame in frames
+    {
+        let frame_offset = frame.offset.map(|offset| body_offset + offset as u64);
+        let element_id = match frame.content
+        {
+            | Some(Id3v2FrameContent::Chapter(ref chapter)) =>
+            {
+                if chapter.end_time < chapter.start_time
+                {
+                    diagnostics.warning(frame_offset, tr_fmt("Chapter \"{}\" ends before it starts", &[&chapter.element_id]));
+                }
+                else if chapter.end_time == chapter.start_time
+                {
+                    diagnostics.warning(frame_offset, tr_fmt("Chapter \"{}\" ends where it starts", &[&chapter.element_id]));
+                }
+                chapter.element_id.as_str()
+            }
+            | Some(Id3v2FrameContent::TableOfContents(ref toc)) =>
+            {
+                has_toc = true;
+                if toc.top_level == true
+                {
+                    top_level_count += 1;
+                }
+                if toc.ordered == true
+                {
+                    ordered_tocs.push((toc, frame_offset));
+                }
+                for child in &toc.child_element_ids
+                {
+                    referenced.push((child, frame_offset));
+                }
+                toc.element_id.as_str()
+            }
+            | _ => continue
+        };
+
+        if element_ids.insert(element_id, frame).is_some()
+        {
+            diagnostics.warning(frame_offset, tr_fmt("Element ID \"{}\" is used by more than one CHAP/CTOC frame", &[&element_id]));
+        }
+    }
+
+    if has_toc == false
+    {
+        return;
+    }
+    if top_level_count != 1
+    {
+        diagnostics.warning(None, tr_fmt("Tag has {} top-level CTOC frames, expected exactly one", &[&top_level_count]));
+    }
+
+    for &(child, toc_offset) in &referenced
+    {
+        if element_ids.contains_key(child) == false
+        {
+            diagnostics.warning(toc_offset, tr_fmt("CTOC references element ID \"{}\", but no CHAP/CTOC frame has it", &[&child]));
+        }
+    }
+    for (element_id, frame) in &element_ids
+    {
+        if frame.id == "CHAP" && referenced.iter().any(|(child, _)| child == element_id) == false
+        {
+            diagnostics.info(frame.offset.map(|offset| body_offset + offset as u64), tr_fmt("Chapter \"{}\" is not referenced by any CTOC frame", &[element_id]));
+        }
+    }
+
+    for (toc, toc_offset) in ordered_tocs
+    {
+        check_chapter_order(toc, toc_offset, &element_ids, body_offset, diagnostics);
+    }
+}
+
+/// Check that the chapters listed by an ordered CTOC frame follow each other without overlapping
+///
+/// Nested CTOC entries are skipped, each ordered table of contents is checked on its own. Gaps between
+/// chapters are allowed and only reported as information.
+fn check_chapter_order(toc: &TableOfContentsFrame, toc_offset: Option<u64>, element_ids: &HashMap<&str, &Id3v2Frame>, body_offset: u64, diagnostics: &mut Diagnostics)
+{
+    let chapters: Vec<(&ChapterFrame, Option<u64>)> = toc
+        .child_element_ids
+        .iter()
+        .filter_map(|child| element_ids.get(child.as_str()))
+        .filter_map(|frame| match frame.content
+        {
+            | Some(Id3v2FrameContent::Chapter(ref chapter)) => Some((chapter, frame.offset.map(|offset| body_offset + offset as u64))),
+            | _ => None
+        })
+        .collect();
+
+    for pair in chapters.windows(2)
+    {
+        let ((previous, _), (chapter, chapter_offset)) = (pair[0], pair[1]);
+        let (previous_id, chapter_id) = (&previous.element_id, &chapter.element_id);
+        if chapter.start_time < previous.start_time
+        {
+            diagnostics.warning(
+                chapter_offset.or(toc_offset),
+                tr_fmt("Chapter \"{}\" starts before the preceding chapter \"{}\" of ordered CTOC \"{}\"", &[chapter_id, previous_id, &toc.element_id])
+            );
+        }
+        else if chapter.start_time < previous.end_time
+        {
+            diagnostics.warning(
+                chapter_offset.or(toc_offset),
+                tr_fmt("Chapter \"{}\" overlaps the preceding chapter \"{}\" by {} ms", &[chapter_id, previous_id, &(previous.end_time - chapter.start_time)])
+            );
+        }
+        else if chapter.start_time > previous.end_time
+        {
+            diagnostics.info(
+                chapter_offset.or(toc_offset),
+                tr_fmt("Gap of {} ms between chapter \"{}\" and the preceding chapter \"{}\"", &[&(chapter.start_time - previous.end_time), chapter_id, previous_id])
+            );
+        }
+    }
+}
+
+/// Compare the declared tag size with the frames and padding actually found
+fn check_tag_extent(file: &mut dyn MediaSource, tag: &Id3v2Tag, diagnostics: &mut Diagnostics) -> Result<(), Box<dyn std::error::Error>>
+{
+    let unsynchronised = tag.flags & 0x80 != 0 && tag.major_version == 3;
+    let padding = (tag.size as usize).saturating_sub(tag.frames_end);
+
+    if let Some(padding_size) = tag.extended_header.as_ref().and_then(|extended_header| extended_header.padding_size) &&
+        unsynchronised == false &&
+        padding_size as usize != padding
+    {
+        diagnostics.warning(Some(tag.offset + 10), tr_fmt("Extended header declares {} bytes of padding, but the tag has {}", &[&padding_size, &padding]));
+    }
+
+    if tag.major_version == 4 && tag.flags & 0x10 != 0 && padding > 0
+    {
+        diagnostics.warning(Some(tag.offset + 10 + tag.frames_end as u64), tr("Tag has both a footer and padding, which ID3v2.4 does not allow"));
+    }
+
+    // A frame header right behind the tag means the declared size is too small
+    let mut next = [0u8; 10];
+    file.seek(SeekFrom::Start(tag.end_offset()))?;
+    if file.read_exact(&mut next).is_ok()
+    {
+        let frame_id = String::from_utf8_lossy(&next[0..4]).to_string();
+        if frame_id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) == true && is_valid_frame_for_version(&frame_id, tag.major_version) == true
+        {
+            diagnostics.error(Some(tag.end_offset()), tr_fmt("Frame {} follows the tag, the declared tag size is too small", &[&frame_id]));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,814 @@
+use std::{fmt, io::SeekFrom, path::Path, str::FromStr};
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v1::{ID3V1_SIZE, Id3v1Tag},
+    id3v2::{
+        dissectors::{v3::parse_id3v2_3_frame, v4::parse_id3v2_4_frame},
+        extended_header::ExtendedHeader,
+        frame::Id3v2FrameContent,
+        tools::{decode_synchsafe_int, detect_mpeg_sync, encode_synchsafe_int, is_valid_frame_for_version, remove_unsynchronization}
+    },
+    media_source::{self, MediaSource},
+    rewrite
+};
+
+/// Padding added when the edited frames no longer fit into the existing tag
+pub const DEFAULT_PADDING: u32 = 1024;
+
+/// Largest tag size a synchsafe integer can declare
+const MAX_TAG_SIZE: u32 = (1 << 28) - 1;
+
+/// Frame selected by `tag set`/`tag remove`: a frame ID, or `TXXX:<description>` for one user-defined text frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameSelector
+{
+    pub id:          String,
+    /// Description of the TXXX frame (None selects all frames with the ID)
+    pub description: Option<String>
+}
+
+impl FrameSelector
+{
+    fn matches(&self, frame: &RawFrame) -> bool
+    {
+        frame.id == self.id &&
+            match (&self.description, &frame.description)
+            {
+                | (None, _) => true,
+                | (Some(wanted), Some(description)) => wanted.eq_ignore_ascii_case(description),
+                | (Some(_), None) => false
+            }
+    }
+}
+
+impl FromStr for FrameSelector
+{
+    type Err = String;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err>
+    {
+        let (id, description) = match selector.split_once(':')
+        {
+            | Some((id, description)) => (id, Some(description.to_string())),
+            | None => (selector, None)
+        };
+
+        if id.len() != 4 || id.bytes().all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit()) == false
+        {
+            return Err(tr_fmt("Invalid frame ID '{}': expected four upper-case letters or digits", &[&id]));
+        }
+        if description.is_some() == true && id != "TXXX"
+        {
+            return Err(tr_fmt("Only TXXX frames are selected by description, not '{}'", &[&selector]));
+        }
+
+        Ok(Self { id: id.to_string(), description })
+    }
+}
+
+impl fmt::Display for FrameSelector
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self.description
+        {
+            | Some(ref description) => write!(f, "{}:{}", self.id, description),
+            | None => write!(f, "{}", self.id)
+        }
+    }
+}
+
+/// One change to a tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagEdit
+{
+    /// Add the text frame or replace its value (and drop duplicates)
+    Set(FrameSelector, String),
+    /// Remove every frame the selector matches
+    Remove(FrameSelector)
+}
+
+/// What an edit did to the tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditResult
+{
+    Added,
+    Replaced,
+    /// Number of frames removed
+    Removed(usize),
+    /// Nothing matched the frame to remove
+    NotFound
+}
+
+/// What converting an ID3v2.3 tag to ID3v2.4 did to a frame that was not carried over under its ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameConversion
+{
+    /// Frame renamed to its ID3v2.4 equivalent (TORY to TDOR, IPLS to TIPL)
+    Renamed(String, &'static str),
+    /// Date frames (TYER, TDAT, TIME) combined into one TDRC timestamp
+    Merged(Vec<String>, String),
+    /// Frame ID3v2.4 does not define (e.g. TSIZ, RVAD), dropped
+    Dropped(String)
+}
+
+/// Frame of the existing tag, kept byte for byte unless an edit replaces it
+#[derive(Debug, Clone)]
+struct RawFrame
+{
+    id:          String,
+    /// Description of TXXX frames
+    description: Option<String>,
+    /// Frame header and data
+    bytes:       Vec<u8>
+}
+
+/// ID3v2.3/2.4 tag at the start of a file, rebuilt from its frames after editing
+///
+/// Frames are kept as they are in the file; only edited frames are encoded anew (UTF-8 in
+/// ID3v2.4, ISO-8859-1 or UTF-16 in ID3v2.3). Tag-level unsynchronisation is undone and the
+/// extended header is dropped, since its CRC would no longer match.
+#[derive(Debug, Clone)]
+pub struct TagWriter
+{
+    pub major_version:           u8,
+    /// Header flags of the rebuilt tag
+    flags:                       u8,
+    frames:                      Vec<RawFrame>,
+    /// Bytes the existing tag occupies at the start of the file (header, frames, padding, footer), 0 if there is none
+    pub original_length:         u64,
+    /// Size declared by the existing tag header (frames and padding)
+    original_size:               u32,
+    /// Padding requested with `set_padding`, replacing the size rules of `tag_size`
+    padding:                     Option<u32>,
+    /// Whether the existing tag had an extended header that is dropped
+    pub dropped_extended_header: bool
+}
+
+impl TagWriter
+{
+    /// Read the tag at the start of `file`, or start a new ID3v2.4 tag if the file is MPEG audio without one
+    pub fn read(file: &mut dyn MediaSource) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let mut header = [0u8; 10];
+        file.seek(SeekFrom::Start(0))?;
+        if file.size()? < 10 || file.read_exact(&mut header).is_err() == true || &header[0..3] != b"ID3"
+        {
+            if detect_mpeg_sync(&header) == false
+            {
+                return Err(tr("The file has no ID3v2 tag and does not start with MPEG audio").into());
+            }
+            return Ok(Self {
+                major_version:           4,
+                flags:                   0,
+                frames:                  Vec::new(),
+                original_length:         0,
+                original_size:           0,
+                padding:                 None,
+                dropped_extended_header: false
+            });
+        }
+
+        let major_version = header[3];
+        if major_version != 3 && major_version != 4
+        {
+            return Err(tr_fmt("ID3v2.{} tags cannot be edited", &[&major_version]).into());
+        }
+        let flags = header[5];
+        let size = decode_synchsafe_int(&header[6..10]);
+        let has_footer = major_version == 4 && flags & 0x10 != 0;
+
+        let mut body = file.map(10, size as u64)?.to_vec();
+        if major_version == 3 && flags & 0x80 != 0
+        {
+            body = remove_unsynchronization(&body);
+        }
+
+        let mut pos = 0;
+        if flags & 0x40 != 0
+        {
+            pos = match major_version
+            {
+                | 4 => ExtendedHeader::parse_v4(&body)?.1,
+                | _ => ExtendedHeader::parse_v3(&body)?.1
+            };
+        }
+
+        let mut frames = Vec::new();
+        while pos + 10 <= body.len() && body[pos] != 0
+        {
+            let frame_size = match major_version
+            {
+                | 4 => decode_synchsafe_int(&body[pos + 4..pos + 8]),
+                | _ => u32::from_be_bytes([body[pos + 4], body[pos + 5], body[pos + 6], body[pos + 7]])
+            } as usize;
+            if body[pos..pos + 4].iter().all(u8::is_ascii_alphanumeric) == false || frame_size > body.len() - pos - 10
+            {
+                return Err(tr_fmt("Corrupt frame data at position {} of the tag, the tag is not rewritten", &[&pos]).into());
+            }
+
+            let mut bytes = body[pos..pos + 10 + frame_size].to_vec();
+            // Without the tag-level flag each frame has to declare its unsynchronisation itself
+            if major_version == 4 && flags & 0x80 != 0
+            {
+                bytes[9] |= 0x02;
+            }
+            let id = String::from_utf8_lossy(&bytes[0..4]).to_string();
+            let description = match id.as_str()
+            {
+                | "TXXX" => user_text_description(&bytes, major_version),
+                | _ => None
+            };
+            frames.push(RawFrame { id, description, bytes });
+            pos += 10 + frame_size;
+        }
+        if body[pos.min(body.len())..].iter().any(|&byte| byte != 0) == true
+        {
+            return Err(tr_fmt("Corrupt frame data at position {} of the tag, the tag is not rewritten", &[&pos]).into());
+        }
+
+        Ok(Self {
+            major_version,
+            flags: flags & !(0x80 | 0x40),
+            frames,
+            original_length: 10 +
+                size as u64 +
+                if has_footer == true
+                {
+                    10
+                }
+                else
+                {
+                    0
+                },
+            original_size: size,
+            padding: None,
+            dropped_extended_header: flags & 0x40 != 0
+        })
+    }
+
+    /// Apply one edit to the frames
+    pub fn apply(&mut self, edit: &TagEdit) -> Result<EditResult, String>
+    {
+        match edit
+        {
+            | TagEdit::Set(selector, value) =>
+            {
+                if selector.id.starts_with('T') == false
+                {
+                    return Err(tr_fmt("Only text frames (T...) can be set, not '{}'", &[&selector.id]));
+                }
+                if selector.id == "TXXX" && selector.description.is_none() == true
+                {
+                    return Err(tr("TXXX frames are set with a description: TXXX:<description>=<value>").to_string());
+                }
+                if is_valid_frame_for_version(&selector.id, self.major_version) == false
+                {
+                    return Err(tr_fmt("Frame {} is not defined in ID3v2.{}", &[&selector.id, &self.major_version]));
+                }
+
+                let frame = self.encode_text_frame(selector, value);
+                match self.frames.iter().position(|existing| selector.matches(existing))
+                {
+                    | Some(index) =>
+                    {
+                        self.frames[index] = frame;
+                        let mut position = 0;
+                        self.frames.retain(|existing| {
+                            position += 1;
+                            position - 1 == index || selector.matches(existing) == false
+                        });
+                        Ok(EditResult::Replaced)
+                    }
+                    | None =>
+                    {
+                        self.frames.push(frame);
+                        Ok(EditResult::Added)
+                    }
+                }
+            }
+            | TagEdit::Remove(selector) =>
+            {
+                let count = self.frames.len();
+                self.frames.retain(|existing| selector.matches(existing) == false);
+                match count - self.frames.len()
+                {
+                    | 0 => Ok(EditResult::NotFound),
+                    | removed => Ok(EditResult::Removed(removed))
+                }
+            }
+        }
+    }
+
+    /// Turn an ID3v2.3 tag into an ID3v2.4 tag
+    ///
+    /// Frame sizes become synchsafe and the frame flags move to their ID3v2.4 positions, also for
+    /// the sub-frames of CHAP and CTOC frames; the frame data stays as it is, since ID3v2.3 text
+    /// encodings are valid in ID3v2.4. Frames
+    /// ID3v2.4 replaced are renamed or merged, frames without an equivalent are dropped.
+    pub fn convert_to_v2_4(&mut self) -> Result<Vec<FrameConversion>, String>
+    {
+        if self.major_version != 3
+        {
+            return Err(tr_fmt("Only ID3v2.3 tags can be converted, this tag is ID3v2.{}", &[&self.major_version]));
+        }
+
+        let frames = std::mem::take(&mut self.frames);
+        let date_ids: Vec<String> = frames.iter().filter(|frame| DATE_FRAMES.contains(&frame.id.as_str())).map(|frame| frame.id.clone()).collect();
+        let mut recording_time = recording_time(&frames);
+        self.major_version = 4;
+
+        let mut conversions = Vec::new();
+        for frame in &frames
+        {
+            match frame.id.as_str()
+            {
+                | id if DATE_FRAMES.contains(&id) == true => match recording_time.take()
+                {
+                    | Some(timestamp) =>
+                    {
+                        let selector = FrameSelector { id: "TDRC".to_string(), description: None };
+                        self.frames.push(self.encode_text_frame(&selector, &timestamp));
+                        conversions.push(FrameConversion::Merged(date_ids.clone(), timestamp));
+                    }
+                    // The timestamp replaces the first date frame, the others are part of it
+                    | None if conversions.iter().any(|conversion| matches!(conversion, FrameConversion::Merged(..))) == true => (),
+                    | None => conversions.push(FrameConversion::Dropped(id.to_string()))
+                },
+                | "TORY" =>
+                {
+                    self.frames.push(convert_frame_v3(frame, "TDOR")?);
+                    conversions.push(FrameConversion::Renamed(frame.id.clone(), "TDOR"));
+                }
+                | "IPLS" =>
+                {
+                    self.frames.push(convert_frame_v3(frame, "TIPL")?);
+                    conversions.push(FrameConversion::Renamed(frame.id.clone(), "TIPL"));
+                }
+                | id if is_valid_frame_for_version(id, 4) == true => self.frames.push(convert_frame_v3(frame, id)?),
+                | id => conversions.push(FrameConversion::Dropped(id.to_string()))
+            }
+        }
+
+        Ok(conversions)
+    }
+
+    /// Write the tag with exactly `padding` bytes of padding, growing or shrinking it
+    pub fn set_padding(&mut self, padding: u32) -> Result<(), String>
+    {
+        if self.has_footer() == true && padding > 0
+        {
+            return Err(tr("ID3v2.4 tags with a footer must not have padding").to_string());
+        }
+        self.padding = Some(padding);
+        Ok(())
+    }
+
+    /// Total size of the frames
+    pub fn frames_size(&self) -> u32
+    {
+        self.frames.iter().map(|frame| frame.bytes.len() as u32).sum()
+    }
+
+    /// Tag size of the rebuilt tag: the existing size if the frames still fit, otherwise the frames plus `DEFAULT_PADDING`
+    ///
+    /// Padding set with `set_padding` is used as it is.
+    pub fn tag_size(&self) -> u32
+    {
+        let frames_size = self.frames_size();
+        if let Some(padding) = self.padding
+        {
+            frames_size.saturating_add(padding)
+        }
+        else if self.has_footer() == true
+        {
+            // A tag with a footer must not have padding
+            frames_size
+        }
+        else if frames_size <= self.original_size
+        {
+            self.original_size
+        }
+        else
+        {
+            frames_size + DEFAULT_PADDING
+        }
+    }
+
+    /// Complete tag: header, frames, padding and footer
+    pub fn render(&self) -> Result<Vec<u8>, String>
+    {
+        let size = self.tag_size();
+        if size > MAX_TAG_SIZE
+        {
+            return Err(tr_fmt("The tag would exceed the maximum ID3v2 size of {} bytes", &[&MAX_TAG_SIZE]));
+        }
+
+        let mut header = vec![b'I', b'D', b'3', self.major_version, 0, self.flags];
+        header.extend(encode_synchsafe_int(size));
+
+        let mut tag = header.clone();
+        for frame in &self.frames
+        {
+            tag.extend(&frame.bytes);
+        }
+        tag.resize(10 + size as usize, 0);
+        if self.has_footer() == true
+        {
+            header[0..3].copy_from_slice(b"3DI");
+            tag.extend(header);
+        }
+        Ok(tag)
+    }
+
+    fn has_footer(&self) -> bool
+    {
+        self.major_version == 4 && self.flags & 0x10 != 0
+    }
+
+    /// Text frame holding `value` (after the description for TXXX)
+    fn encode_text_frame(&self, selector: &FrameSelector, value: &str) -> RawFrame
+    {
+        let texts: Vec<&str> = match selector.description
+        {
+            | Some(ref description) => vec![description, value],
+            | None => vec![value]
+        };
+
+        // ID3v2.4 text is UTF-8, ID3v2.3 text ISO-8859-1 if possible and UTF-16 with BOM otherwise
+        let mut data = Vec::new();
+        if self.major_version == 4
+        {
+            data.push(3);
+            data.extend(texts.join("\0").into_bytes());
+        }
+        else if texts.iter().all(|text| text.chars().all(|character| (character as u32) < 0x100)) == true
+        {
+            data.push(0);
+            data.extend(texts.join("\0").chars().map(|character| character as u8));
+        }
+        else
+        {
+            data.push(1);
+            for (index, text) in texts.iter().enumerate()
+            {
+                if index > 0
+                {
+                    data.extend([0, 0]);
+                }
+                data.extend([0xFF, 0xFE]);
+                data.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            }
+        }
+
+        let size = data.len() as u32;
+        let mut bytes = selector.id.as_bytes().to_vec();
+        match self.major_version
+        {
+            | 4 => bytes.extend(encode_synchsafe_int(size)),
+            | _ => bytes.extend(size.to_be_bytes())
+        }
+        bytes.extend([0, 0]);
+        bytes.extend(data);
+
+        RawFrame { id: selector.id.clone(), description: selector.description.clone(), bytes }
+    }
+}
+
+/// Result of editing the tag of a file
+#[derive(Debug, Clone)]
+pub struct TagEditReport
+{
+    pub major_version:           u8,
+    /// Result of each edit, in the order given
+    pub results:                 Vec<EditResult>,
+    /// Tag size of the written tag (frames and padding), None if nothing changed and the file was left alone
+    pub tag_size:                Option<u32>,
+    pub padding:                 u32,
+    pub dropped_extended_header: bool
+}
+
+/// Result of converting the tag of a file to ID3v2.4
+#[derive(Debug, Clone)]
+pub struct TagConversionReport
+{
+    /// Frames that were renamed, merged or dropped
+    pub conversions:             Vec<FrameConversion>,
+    pub tag_size:                u32,
+    pub padding:                 u32,
+    pub dropped_extended_header: bool
+}
+
+/// Result of changing the padding of the tag of a file
+#[derive(Debug, Clone)]
+pub struct TagRepadReport
+{
+    pub major_version:           u8,
+    /// Padding of the existing tag
+    pub original_padding:        u32,
+    /// Tag size of the written tag (frames and padding), None if the padding already had the requested size
+    pub tag_size:                Option<u32>,
+    pub padding:                 u32,
+    /// Bytes the audio data moved (negative towards the start of the file)
+    pub shift:                   i64,
+    pub dropped_extended_header: bool
+}
+
+/// Result of stripping the tags of a file
+#[derive(Debug, Clone, Copy)]
+pub struct StripReport
+{
+    /// Bytes of the ID3v2 tag removed from the start of the file (0 if there was none)
+    pub id3v2_length: u64,
+    /// Bytes of the ID3v1 tag removed from the end of the file (0 if there was none or it was kept)
+    pub id3v1_length: u64
+}
+
+/// Apply `edits` to the ID3v2 tag at the start of the file at `path`
+///
+/// The file is rewritten through a temporary file (see `rewrite`) if any edit changed the tag.
+pub fn edit_file(path: &Path, edits: &[TagEdit]) -> Result<TagEditReport, Box<dyn std::error::Error>>
+{
+    let mut writer = {
+        let mut file = media_source::open(path)?;
+        TagWriter::read(file.as_mut())?
+    };
+
+    let results = edits.iter().map(|edit| writer.apply(edit)).collect::<Result<Vec<_>, _>>()?;
+    let changed = results.iter().any(|&result| result != EditResult::NotFound);
+    let tag_size = if changed == true
+    {
+        let tag = writer.render()?;
+        rewrite::replace_range(path, 0, writer.original_length, &tag)?;
+        Some(writer.tag_size())
+    }
+    else
+    {
+        None
+    };
+
+    Ok(TagEditReport {
+        major_version: writer.major_version,
+        results,
+        tag_size,
+        padding: writer.tag_size() - writer.frames_size(),
+        dropped_extended_header: writer.dropped_extended_header && changed
+    })
+}
+
+/// Convert the ID3v2.3 tag at the start of the file at `path` to ID3v2.4, leaving the audio untouched
+pub fn convert_file(path: &Path) -> Result<TagConversionReport, Box<dyn std::error::Error>>
+{
+    let mut writer = {
+        let mut file = media_source::open(path)?;
+        TagWriter::read(file.as_mut())?
+    };
+    if writer.original_length == 0
+    {
+        return Err(tr("The file has no ID3v2 tag to convert").into());
+    }
+
+    let conversions = writer.convert_to_v2_4()?;
+    let tag = writer.render()?;
+    rewrite::replace_range(path, 0, writer.original_length, &tag)?;
+
+    Ok(TagConversionReport {
+        conversions,
+        tag_size: writer.tag_size(),
+        padding: writer.tag_size() - writer.frames_size(),
+        dropped_extended_header: writer.dropped_extended_header
+    })
+}
+
+/// Rewrite the ID3v2 tag at the start of the file at `path` with exactly `padding` bytes of padding
+///
+/// The frames are kept byte for byte; the audio data after the tag moves by the size change.
+/// The file is only rewritten if the padding changes.
+pub fn repad_file(path: &Path, padding: u32) -> Result<TagRepadReport, Box<dyn std::error::Error>>
+{
+    let mut writer = {
+        let mut file = media_source::open(path)?;
+        TagWriter::read(file.as_mut())?
+    };
+    if writer.original_length == 0
+    {
+        return Err(tr("The file has no ID3v2 tag to repad").into());
+    }
+
+    let original_padding = writer.original_size - writer.frames_size();
+    writer.set_padding(padding)?;
+    let changed = writer.tag_size() != writer.original_size;
+    if changed == true
+    {
+        let tag = writer.render()?;
+        rewrite::replace_range(path, 0, writer.original_length, &tag)?;
+    }
+
+    Ok(TagRepadReport {
+        major_version: writer.major_version,
+        original_padding,
+        tag_size: changed.then(|| writer.tag_size()),
+        padding,
+        shift: writer.tag_size() as i64 - writer.original_size as i64,
+        dropped_extended_header: writer.dropped_extended_header && changed
+    })
+}
+
+/// Remove the ID3v2 tag at the start of the file at `path` and, if `id3v1` is set, the ID3v1 tag at its end
+///
+/// Tags of any ID3v2 version are removed, the bytes between them are copied unchanged. The
+/// file is only rewritten if there was something to remove.
+pub fn strip_file(path: &Path, id3v1: bool) -> Result<StripReport, Box<dyn std::error::Error>>
+{
+    let (file_size, id3v2_length, id3v1_length) = {
+        let mut file = media_source::open(path)?;
+        let file_size = file.size()?;
+
+        let mut header = [0u8; 10];
+        file.seek(SeekFrom::Start(0))?;
+        let id3v2_length = match file_size >= 10 && file.read_exact(&mut header).is_ok() == true && &header[0..3] == b"ID3"
+        {
+            | true =>
+            {
+                let footer = if header[3] == 4 && header[5] & 0x10 != 0
+                {
+                    10
+                }
+                else
+                {
+                    0
+                };
+                (10 + decode_synchsafe_int(&header[6..10]) as u64 + footer).min(file_size)
+            }
+            | false => 0
+        };
+        let id3v1_length = match id3v1 == true && file_size - id3v2_length >= ID3V1_SIZE && Id3v1Tag::read(file.as_mut())?.is_some() == true
+        {
+            | true => ID3V1_SIZE,
+            | false => 0
+        };
+        (file_size, id3v2_length, id3v1_length)
+    };
+
+    if id3v2_length > 0 || id3v1_length > 0
+    {
+        rewrite::rewrite(path, |original, output| rewrite::copy_range(original, output, id3v2_length, file_size - id3v2_length - id3v1_length))?;
+    }
+    Ok(StripReport { id3v2_length, id3v1_length })
+}
+
+/// ID3v2.3 frames that make up the ID3v2.4 recording time
+const DATE_FRAMES: [&str; 3] = ["TYER", "TDAT", "TIME"];
+
+/// ID3v2.4 recording time ("yyyy", "yyyy-MM-dd" or "yyyy-MM-ddTHH:mm") from the TYER, TDAT (DDMM) and TIME (HHMM) frames
+fn recording_time(frames: &[RawFrame]) -> Option<String>
+{
+    let text = |id: &str| frames.iter().find(|frame| frame.id == id).and_then(|frame| frame_text(&frame.bytes, 3)).map(|text| text.trim().to_string());
+    let is_digits = |text: &str| text.len() == 4 && text.bytes().all(|byte| byte.is_ascii_digit());
+
+    let mut timestamp = text("TYER").filter(|year| year.is_empty() == false)?;
+    if is_digits(&timestamp) == true &&
+        let Some(date) = text("TDAT").filter(|date| is_digits(date))
+    {
+        timestamp = format!("{}-{}-{}", timestamp, &date[2..4], &date[0..2]);
+        if let Some(time) = text("TIME").filter(|time| is_digits(time))
+        {
+            timestamp = format!("{}T{}:{}", timestamp, &time[0..2], &time[2..4]);
+        }
+    }
+    Some(timestamp)
+}
+
+/// ID3v2.4 form of an ID3v2.3 frame: synchsafe size, moved flags and the added data reordered
+fn convert_frame_v3(frame: &RawFrame, id: &str) -> Result<RawFrame, String>
+{
+    // ID3v2.3 flags %abc00000 %ijk00000, added data: decompressed size, encryption method, group
+    let (status, format) = (frame.bytes[8], frame.bytes[9]);
+    let (compressed, encrypted, grouped) = (format & 0x80 != 0, format & 0x40 != 0, format & 0x20 != 0);
+    let added_size = compressed as usize * 4 + encrypted as usize + grouped as usize;
+    let data = &frame.bytes[10..];
+    if data.len() < added_size
+    {
+        return Err(tr_fmt("Frame {} is too short for the data announced by its flags", &[&frame.id]));
+    }
+    let (data_length, rest) = match compressed
+    {
+        | true => (Some(u32::from_be_bytes([data[0], data[1], data[2], data[3]])), &data[4..]),
+        | false => (None, data)
+    };
+    let (method, rest) = match encrypted
+    {
+        | true => (Some(rest[0]), &rest[1..]),
+        | false => (None, rest)
+    };
+    let (group, payload) = match grouped
+    {
+        | true => (Some(rest[0]), &rest[1..]),
+        | false => (None, rest)
+    };
+
+    // Sub-frames of chapters have ID3v2.3 headers with plain sizes that have to become synchsafe as well
+    let payload = match compressed == false && encrypted == false && matches!(id, "CHAP" | "CTOC") == true
+    {
+        | true => convert_sub_frames_v3(id, payload)?,
+        | false => payload.to_vec()
+    };
+
+    // ID3v2.4 flags %0abc0000 %0h00kmnp, added data: group, encryption method, data length indicator
+    let mut data = Vec::with_capacity(data.len());
+    data.extend(group);
+    data.extend(method);
+    data.extend(data_length.map(encode_synchsafe_int).into_iter().flatten());
+    data.extend(payload);
+    let format = (grouped as u8 * 0x40) | (compressed as u8 * 0x09) | (encrypted as u8 * 0x04);
+
+    let mut bytes = id.as_bytes().to_vec();
+    bytes.extend(encode_synchsafe_int(data.len() as u32));
+    bytes.extend([(status >> 1) & 0x70, format]);
+    bytes.extend(data);
+    Ok(RawFrame { id: id.to_string(), description: frame.description.clone(), bytes })
+}
+
+/// Offset of the embedded sub-frames in the data of a CHAP or CTOC frame, None if the data ends before them
+fn sub_frames_start(id: &str, data: &[u8]) -> Option<usize>
+{
+    // Both start with the null-terminated element ID
+    let element_end = data.iter().position(|&byte| byte == 0)? + 1;
+    match id
+    {
+        // Start and end time, start and end offset
+        | "CHAP" => Some(element_end + 16).filter(|&start| start <= data.len()),
+        // Flags, entry count and the null-terminated child element IDs
+        | _ =>
+        {
+            let entry_count = *data.get(element_end + 1)? as usize;
+            let mut position = element_end + 2;
+            for _ in 0..entry_count
+            {
+                position += data.get(position..)?.iter().position(|&byte| byte == 0)? + 1;
+            }
+            Some(position)
+        }
+    }
+}
+
+/// Data of a CHAP or CTOC frame with its embedded ID3v2.3 sub-frames converted to ID3v2.4
+///
+/// Bytes after the last complete sub-frame (padding, a truncated frame) are kept as they are.
+fn convert_sub_frames_v3(id: &str, data: &[u8]) -> Result<Vec<u8>, String>
+{
+    let Some(start) = sub_frames_start(id, data)
+    else
+    {
+        return Ok(data.to_vec());
+    };
+
+    let mut converted = data[..start].to_vec();
+    let mut position = start;
+    while position + 10 <= data.len() && data[position] != 0
+    {
+        let size = u32::from_be_bytes([data[position + 4], data[position + 5], data[position + 6], data[position + 7]]) as usize;
+        let end = position + 10 + size;
+        if end > data.len()
+        {
+            break;
+        }
+        let sub_frame = RawFrame {
+            id:          String::from_utf8_lossy(&data[position..position + 4]).to_string(),
+            description: None,
+            bytes:       data[position..end].to_vec()
+        };
+        converted.extend(convert_frame_v3(&sub_frame, &sub_frame.id)?.bytes);
+        position = end;
+    }
+    converted.extend(&data[position..]);
+    Ok(converted)
+}
+
+/// Text of a text or user-defined text frame given with its header
+fn frame_text(bytes: &[u8], major_version: u8) -> Option<String>
+{
+    let frame = match major_version
+    {
+        | 4 => parse_id3v2_4_frame(bytes, 0, false),
+        | _ => parse_id3v2_3_frame(bytes, 0)
+    }?;
+    frame.get_text().map(str::to_string)
+}
+
+/// Description of a TXXX frame given with its header
+fn user_text_description(bytes: &[u8], major_version: u8) -> Option<String>
+{
+    let frame = match major_version
+    {
+        | 4 => parse_id3v2_4_frame(bytes, 0, false),
+        | _ => parse_id3v2_3_frame(bytes, 0)
+    }?;
+
+    match frame.content
+    {
+        | Some(Id3v2FrameContent::UserText(user_text)) => Some(user_text.description),
+        | _ => None
+    }
+}
@@ -0,0 +1,216 @@
+// Image formats
+//
+// This module provides dissection of the image formats used for cover art: JPEG marker
+// segments (JFIF, EXIF, XMP and ICC profile segments, frame headers), PNG chunks with
+// checksum verification and text chunks, GIF blocks including animation extensions and
+// Windows bitmaps. Pictures extracted from tags can be passed to the dissector directly.
+
+// Core types and dissector
+pub mod bmp;
+pub mod dissector;
+pub mod exif;
+pub mod gif;
+pub mod jpeg;
+pub mod png;
+
+use std::fmt;
+
+// Re-export commonly used types for convenience
+pub use dissector::ImageDissector;
+
+use crate::{
+    diagnostics::Diagnostics,
+    dissector_registry::DissectorRegistry,
+    i18n::{tr, tr_fmt},
+    image::{bmp::BmpImage, exif::Exif, gif::GifImage, jpeg::JpegImage, png::PngImage}
+};
+
+/// Parsed image of one of the supported formats
+#[derive(Debug, Clone)]
+pub enum Image
+{
+    Jpeg(JpegImage),
+    Png(PngImage),
+    Gif(GifImage),
+    Bmp(BmpImage)
+}
+
+impl Image
+{
+    /// Check whether `header` starts with the signature of a supported image format
+    pub fn is_image(header: &[u8]) -> bool
+    {
+        match header
+        {
+            | [0xFF, 0xD8, 0xFF, ..] => true,
+            | [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => true,
+            | [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => true,
+            // The reserved fields of the bitmap file header are zero
+            | [b'B', b'M', _, _, _, _, 0, 0, 0, 0, ..] => true,
+            | _ => false
+        }
+    }
+
+    /// Parse the image at the start of `data`; offsets are reported relative to `base_offset`
+    pub fn parse(data: &[u8], base_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, String>
+    {
+        match data
+        {
+            | [0xFF, 0xD8, ..] => JpegImage::parse(data, base_offset, diagnostics).map(Image::Jpeg),
+            | [0x89, b'P', b'N', b'G', ..] => PngImage::parse(data, base_offset, diagnostics).map(Image::Png),
+            | [b'G', b'I', b'F', ..] => GifImage::parse(data, base_offset, diagnostics).map(Image::Gif),
+            | [b'B', b'M', ..] => BmpImage::parse(data, base_offset, diagnostics).map(Image::Bmp),
+            | _ => Err(tr("Unknown image format").to_string())
+        }
+    }
+
+    /// Format name, e.g. "JPEG"
+    pub fn format_name(&self) -> &'static str
+    {
+        match self
+        {
+            | Image::Jpeg(_) => "JPEG",
+            | Image::Png(_) => "PNG",
+            | Image::Gif(_) => "GIF",
+            | Image::Bmp(_) => "BMP"
+        }
+    }
+
+    /// Width and height in pixels
+    pub fn dimensions(&self) -> Option<(u32, u32)>
+    {
+        match self
+        {
+            | Image::Jpeg(jpeg) => jpeg.frame.as_ref().map(|frame| (frame.width as u32, frame.height as u32)),
+            | Image::Png(png) => png.header.as_ref().map(|header| (header.width, header.height)),
+            | Image::Gif(gif) => Some((gif.width as u32, gif.height as u32)),
+            | Image::Bmp(bmp) => Some((bmp.width.unsigned_abs(), bmp.height.unsigned_abs()))
+        }
+    }
+
+    /// Color model and depth, e.g. "YCbCr 4:2:0, 8 bits per sample"
+    pub fn color_description(&self) -> Option<String>
+    {
+        match self
+        {
+            | Image::Jpeg(jpeg) => jpeg.color_description(),
+            | Image::Png(png) => png.header.as_ref().map(|header| header.color_description()),
+            | Image::Gif(gif) => Some(gif.color_description()),
+            | Image::Bmp(bmp) => Some(bmp.color_description())
+        }
+    }
+
+    /// Number of bytes the image occupies
+    pub fn size(&self) -> u64
+    {
+        match self
+        {
+            | Image::Jpeg(jpeg) => jpeg.size,
+            | Image::Png(png) => png.size,
+            | Image::Gif(gif) => gif.size,
+            | Image::Bmp(bmp) => bmp.size
+        }
+    }
+
+    /// EXIF metadata of a JPEG APP1 segment or PNG eXIf chunk
+    pub fn exif(&self) -> Option<&Exif>
+    {
+        match self
+        {
+            | Image::Jpeg(jpeg) => jpeg.exif.as_ref(),
+            | Image::Png(png) => png.exif.as_ref(),
+            | _ => None
+        }
+    }
+
+    /// ffmpeg codec name of the image format
+    pub fn codec_name(&self) -> &'static str
+    {
+        match self
+        {
+            | Image::Jpeg(_) => "mjpeg",
+            | Image::Png(_) => "png",
+            | Image::Gif(_) => "gif",
+            | Image::Bmp(_) => "bmp"
+        }
+    }
+
+    /// One-line description, e.g. "JPEG, 600x600, YCbCr 4:2:0, 8 bits per sample"
+    pub fn summary(&self) -> String
+    {
+        let mut parts = vec![self.format_name().to_string()];
+        if let Some((width, height)) = self.dimensions()
+        {
+            parts.push(format!("{}x{}", width, height));
+        }
+        if let Some(color) = self.color_description()
+        {
+            parts.push(color);
+        }
+        parts.join(", ")
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let details = match self
+        {
+            | Image::Jpeg(jpeg) => jpeg.to_json(),
+            | Image::Png(png) => png.to_json(),
+            | Image::Gif(gif) => gif.to_json(),
+            | Image::Bmp(bmp) => bmp.to_json()
+        };
+
+        serde_json::json!({
+            "format": self.format_name(),
+            "width": self.dimensions().map(|(width, _)| width),
+            "height": self.dimensions().map(|(_, height)| height),
+            "color": self.color_description(),
+            "size": self.size(),
+            "details": details
+        })
+    }
+}
+
+impl fmt::Display for Image
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | Image::Jpeg(jpeg) => write!(f, "{}", jpeg),
+            | Image::Png(png) => write!(f, "{}", png),
+            | Image::Gif(gif) => write!(f, "{}", gif),
+            | Image::Bmp(bmp) => write!(f, "{}", bmp)
+        }
+    }
+}
+
+/// Lines describing an embedded picture for `--inspect-art`: format and dimensions, JFIF density and EXIF metadata
+pub fn inspect(data: &[u8]) -> Vec<String>
+{
+    let image = match Image::parse(data, 0, &mut Diagnostics::new())
+    {
+        | Ok(image) => image,
+        | Err(e) => return vec![tr_fmt("Picture not analyzed: {}", &[&e])]
+    };
+
+    let mut lines = vec![tr_fmt("Image: {}", &[&image.summary()])];
+    if let Image::Jpeg(ref jpeg) = image &&
+        let Some(jfif) = jpeg.jfif
+    {
+        lines.push(jfif.to_string());
+    }
+    if let Some(exif) = image.exif()
+    {
+        lines.push(tr("EXIF:").to_string());
+        lines.extend(exif.to_string().lines().map(|line| format!("  {}", line)));
+    }
+    lines
+}
+
+/// Register the image dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<ImageDissector>(100);
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::{
+    bytes::{read_u16_le, read_u32_le},
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt}
+};
+
+/// Size of the bitmap file header preceding the DIB header
+const FILE_HEADER_SIZE: usize = 14;
+
+/// Name of the DIB header structure identified by its size
+fn header_name(size: u32) -> &'static str
+{
+    match size
+    {
+        | 12 => "BITMAPCOREHEADER",
+        | 16 | 64 => "OS22XBITMAPHEADER",
+        | 40 => "BITMAPINFOHEADER",
+        | 52 => "BITMAPV2INFOHEADER",
+        | 56 => "BITMAPV3INFOHEADER",
+        | 108 => "BITMAPV4HEADER",
+        | 124 => "BITMAPV5HEADER",
+        | _ => "Unknown DIB header"
+    }
+}
+
+/// Windows bitmap (BMP)
+#[derive(Debug, Clone)]
+pub struct BmpImage
+{
+    /// File size declared in the file header
+    pub declared_size:  u32,
+    /// Offset of the pixel array
+    pub data_offset:    u32,
+    pub header_size:    u32,
+    pub width:          i32,
+    /// Negative for top-down bitmaps
+    pub height:         i32,
+    pub bits_per_pixel: u16,
+    pub compression:    u32,
+    pub image_size:     u32,
+    /// Resolution in pixels per meter
+    pub resolution:     (i32, i32),
+    pub colors_used:    u32,
+    /// Bytes of the bitmap present in the data
+    pub size:           u64
+}
+
+impl BmpImage
+{
+    /// Parse the file and DIB headers of the bitmap at the start of `data`
+    pub fn parse(data: &[u8], base_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, String>
+    {
+        if data.len() < FILE_HEADER_SIZE + 12 || data.starts_with(b"BM") == false
+        {
+            return Err(tr("BMP header too short").to_string());
+        }
+
+        let declared_size = read_u32_le(data, 2);
+        let data_offset = read_u32_le(data, 10);
+        let header_size = read_u32_le(data, 14);
+        let dib = &data[FILE_HEADER_SIZE..];
+
+        let mut image = BmpImage {
+            declared_size,
+            data_offset,
+            header_size,
+            width: 0,
+            height: 0,
+            bits_per_pixel: 0,
+            compression: 0,
+            image_size: 0,
+            resolution: (0, 0),
+            colors_used: 0,
+            size: (declared_size as u64).min(data.len() as u64)
+        };
+
+        if header_size == 12
+        {
+            image.width = read_u16_le(dib, 4) as i32;
+            image.height = read_u16_le(dib, 6) as i32;
+            image.bits_per_pixel = read_u16_le(dib, 10);
+        }
+        else if header_size >= 40 && dib.len() >= 40
+        {
+            image.width = read_u32_le(dib, 4) as i32;
+            image.height = read_u32_le(dib, 8) as i32;
+            image.bits_per_pixel = read_u16_le(dib, 14);
+            image.compression = read_u32_le(dib, 16);
+            image.image_size = read_u32_le(dib, 20);
+            image.resolution = (read_u32_le(dib, 24) as i32, read_u32_le(dib, 28) as i32);
+            image.colors_used = read_u32_le(dib, 32);
+        }
+        else
+        {
+            return Err(tr_fmt("Unsupported BMP DIB header size {}", &[&header_size]));
+        }
+
+        if (declared_size as usize) > data.len()
+        {
+            diagnostics.warning(Some(base_offset + 2), tr_fmt("BMP file size {} exceeds the {} bytes available", &[&declared_size, &data.len()]));
+        }
+        if data_offset as u64 > image.size
+        {
+            diagnostics.warning(Some(base_offset + 10), tr_fmt("BMP pixel data offset {} lies beyond the end of the bitmap", &[&data_offset]));
+        }
+
+        Ok(image)
+    }
+
+    /// Describe the compression method
+    pub fn compression_name(&self) -> &'static str
+    {
+        match self.compression
+        {
+            | 0 => "None (BI_RGB)",
+            | 1 => "RLE8",
+            | 2 => "RLE4",
+            | 3 => "Bit fields",
+            | 4 => "JPEG",
+            | 5 => "PNG",
+            | 6 => "Alpha bit fields",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Color depth, e.g. "24 bits per pixel"
+    pub fn color_description(&self) -> String
+    {
+        match self.bits_per_pixel
+        {
+            | 1 | 4 | 8 => tr_fmt("Indexed, {} bits per pixel", &[&self.bits_per_pixel]),
+            | bits => tr_fmt("RGB, {} bits per pixel", &[&bits])
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "declared_size": self.declared_size,
+            "data_offset": self.data_offset,
+            "header": header_name(self.header_size),
+            "bits_per_pixel": self.bits_per_pixel,
+            "compression": self.compression_name(),
+            "top_down": self.height < 0,
+            "image_size": self.image_size,
+            "resolution": [self.resolution.0, self.resolution.1],
+            "colors_used": self.colors_used
+        })
+    }
+}
+
+impl fmt::Display for BmpImage
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Declared File Size: {} bytes", &[&self.declared_size]))?;
+        writeln!(f, "{}", tr_fmt("Pixel Data Offset: {}", &[&self.data_offset]))?;
+        writeln!(f, "{}", tr_fmt("DIB Header: {} ({} bytes)", &[&header_name(self.header_size), &self.header_size]))?;
+        match self.height < 0
+        {
+            | true => writeln!(f, "{}", tr("Row Order: top-down"))?,
+            | false => writeln!(f, "{}", tr("Row Order: bottom-up"))?
+        }
+        writeln!(f, "{}", tr_fmt("Compression: {}", &[&self.compression_name()]))?;
+        if self.resolution != (0, 0)
+        {
+            writeln!(f, "{}", tr_fmt("Resolution: {}x{} pixels per meter", &[&self.resolution.0, &self.resolution.1]))?;
+        }
+        if self.colors_used > 0
+        {
+            writeln!(f, "{}", tr_fmt("Colors Used: {}", &[&self.colors_used]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use std::io::{Read, SeekFrom, Write};
+
+use crate::{
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    image::Image,
+    media_dissector::{Confidence, MediaDissector},
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Upper bound for the bytes read for one image
+const MAX_IMAGE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Image dissector (JPEG, PNG, GIF, BMP) - unit struct
+#[derive(Default)]
+pub struct ImageDissector;
+
+impl ImageDissector
+{
+    /// Read the region starting at `start_offset` and parse the image at its start
+    fn read_image(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Image, u64), Box<dyn std::error::Error>>
+    {
+        let mut data = Vec::new();
+        file.seek(SeekFrom::Start(start_offset))?;
+        (&mut *file).take(MAX_IMAGE_SIZE).read_to_end(&mut data)?;
+
+        let image = Image::parse(&data, start_offset, diagnostics)?;
+        let end_offset = start_offset + image.size();
+
+        Ok((image, end_offset))
+    }
+}
+
+impl MediaDissector for ImageDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "Image"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "Image Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let (image, end_offset) = Self::read_image(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("Image:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Format: {}", &[&image.format_name()]))?;
+            if let Some((width, height)) = image.dimensions()
+            {
+                writeln!(output, "  {}", tr_fmt("Dimensions: {} x {}", &[&width, &height]))?;
+            }
+            if let Some(color) = image.color_description()
+            {
+                writeln!(output, "  {}", tr_fmt("Color: {}", &[&color]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&image.size()]))?;
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "\n{}", tr("Image Structure:").bright_cyan().bold())?;
+            for line in image.to_string().lines()
+            {
+                writeln!(output, "  {}", line)?;
+            }
+        }
+
+        Ok(end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let (image, end_offset) = Self::read_image(file, start_offset, diagnostics)?;
+
+        Ok((image.to_json(), end_offset))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let (image, end_offset) = Self::read_image(file, start_offset, diagnostics)?;
+
+        let (format_name, format_long_name, codec_long_name) = match image
+        {
+            | Image::Jpeg(_) => ("jpeg_pipe", "piped jpeg sequence", "Motion JPEG"),
+            | Image::Png(_) => ("png_pipe", "piped png sequence", "PNG (Portable Network Graphics) image"),
+            | Image::Gif(_) => ("gif", "CompuServe Graphics Interchange Format (GIF)", "GIF (Graphics Interchange Format)"),
+            | Image::Bmp(_) => ("bmp_pipe", "piped bmp sequence", "BMP (Windows and OS/2 bitmap)")
+        };
+        // Only animated GIFs have a duration
+        let duration = match image
+        {
+            | Image::Gif(ref gif) if gif.frame_count() > 1 => Some(gif.duration()),
+            | _ => None
+        };
+        let stream = ProbeStream {
+            codec_name: Some(image.codec_name()),
+            codec_long_name: Some(codec_long_name),
+            codec_type: "video",
+            width: image.dimensions().map(|(width, _)| width),
+            height: image.dimensions().map(|(_, height)| height),
+            duration,
+            ..Default::default()
+        };
+
+        Ok((Some(ProbeFormat { format_name, format_long_name, duration, streams: vec![stream] }), end_offset))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let (_, end_offset) = Self::read_image(file, start_offset, &mut Diagnostics::new())?;
+        coverage.add(start_offset, end_offset);
+
+        Ok(end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let (_, end_offset) = Self::read_image(file, start_offset, diagnostics)?;
+
+        Ok(end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        match header
+        {
+            // The two-byte bitmap signature is weak even with its zero reserved fields
+            | [b'B', b'M', ..] if Image::is_image(header) == true => Confidence::Medium,
+            | _ if Image::is_image(header) == true => Confidence::High,
+            | _ => Confidence::None
+        }
+    }
+}
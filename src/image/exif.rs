@@ -0,0 +1,504 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Upper bound for the entries parsed per IFD (protects against corrupt counts)
+const MAX_IFD_ENTRIES: usize = 1000;
+
+/// Number of bytes of undefined or byte values shown before they are only counted
+const MAX_DISPLAYED_BYTES: usize = 16;
+
+/// Image file directory an EXIF entry was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExifDirectory
+{
+    /// Main image (IFD0)
+    Image,
+    /// Camera settings (Exif IFD)
+    Photo,
+    /// Location (GPS IFD)
+    Gps,
+    /// Thumbnail (IFD1)
+    Thumbnail
+}
+
+impl ExifDirectory
+{
+    fn name(self) -> &'static str
+    {
+        match self
+        {
+            | ExifDirectory::Image => "IFD0",
+            | ExifDirectory::Photo => "Exif",
+            | ExifDirectory::Gps => "GPS",
+            | ExifDirectory::Thumbnail => "IFD1"
+        }
+    }
+}
+
+/// Typed value of an EXIF entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExifValue
+{
+    Text(String),
+    Unsigned(Vec<u32>),
+    Signed(Vec<i32>),
+    Rational(Vec<(u32, u32)>),
+    SignedRational(Vec<(i32, i32)>),
+    Bytes(Vec<u8>)
+}
+
+impl ExifValue
+{
+    /// First value as a number
+    pub fn as_f64(&self) -> Option<f64>
+    {
+        match self
+        {
+            | ExifValue::Unsigned(values) => values.first().map(|&value| value as f64),
+            | ExifValue::Signed(values) => values.first().map(|&value| value as f64),
+            | ExifValue::Rational(values) =>
+                values.first().filter(|(_, denominator)| *denominator != 0).map(|&(numerator, denominator)| numerator as f64 / denominator as f64),
+            | ExifValue::SignedRational(values) =>
+                values.first().filter(|(_, denominator)| *denominator != 0).map(|&(numerator, denominator)| numerator as f64 / denominator as f64),
+            | ExifValue::Bytes(values) => values.first().map(|&value| value as f64),
+            | ExifValue::Text(_) => None
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str>
+    {
+        match self
+        {
+            | ExifValue::Text(text) => Some(text),
+            | _ => None
+        }
+    }
+}
+
+impl fmt::Display for ExifValue
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let join = |values: Vec<String>| values.join(", ");
+        match self
+        {
+            | ExifValue::Text(text) => write!(f, "\"{}\"", text),
+            | ExifValue::Unsigned(values) => write!(f, "{}", join(values.iter().map(u32::to_string).collect())),
+            | ExifValue::Signed(values) => write!(f, "{}", join(values.iter().map(i32::to_string).collect())),
+            | ExifValue::Rational(values) => write!(f, "{}", join(values.iter().map(|(numerator, denominator)| format!("{}/{}", numerator, denominator)).collect())),
+            | ExifValue::SignedRational(values) =>
+                write!(f, "{}", join(values.iter().map(|(numerator, denominator)| format!("{}/{}", numerator, denominator)).collect())),
+            | ExifValue::Bytes(bytes) if bytes.len() > MAX_DISPLAYED_BYTES => write!(f, "{}", tr_fmt("{} bytes", &[&bytes.len()])),
+            | ExifValue::Bytes(bytes) => write!(f, "{}", bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(" "))
+        }
+    }
+}
+
+/// Entry of an image file directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExifEntry
+{
+    pub directory: ExifDirectory,
+    pub tag:       u16,
+    pub value:     ExifValue
+}
+
+impl ExifEntry
+{
+    /// Tag name from the EXIF 2.3 and TIFF 6.0 specifications
+    pub fn name(&self) -> &'static str
+    {
+        match (self.directory, self.tag)
+        {
+            | (ExifDirectory::Gps, tag) => match tag
+            {
+                | 0x0000 => "GPSVersionID",
+                | 0x0001 => "GPSLatitudeRef",
+                | 0x0002 => "GPSLatitude",
+                | 0x0003 => "GPSLongitudeRef",
+                | 0x0004 => "GPSLongitude",
+                | 0x0005 => "GPSAltitudeRef",
+                | 0x0006 => "GPSAltitude",
+                | 0x0007 => "GPSTimeStamp",
+                | 0x0010 => "GPSImgDirectionRef",
+                | 0x0011 => "GPSImgDirection",
+                | 0x0012 => "GPSMapDatum",
+                | 0x001D => "GPSDateStamp",
+                | _ => "Unknown GPS tag"
+            },
+            | (_, tag) => match tag
+            {
+                | 0x0100 => "ImageWidth",
+                | 0x0101 => "ImageLength",
+                | 0x0103 => "Compression",
+                | 0x010E => "ImageDescription",
+                | 0x010F => "Make",
+                | 0x0110 => "Model",
+                | 0x0112 => "Orientation",
+                | 0x011A => "XResolution",
+                | 0x011B => "YResolution",
+                | 0x0128 => "ResolutionUnit",
+                | 0x0131 => "Software",
+                | 0x0132 => "DateTime",
+                | 0x013B => "Artist",
+                | 0x0201 => "JPEGInterchangeFormat",
+                | 0x0202 => "JPEGInterchangeFormatLength",
+                | 0x0213 => "YCbCrPositioning",
+                | 0x8298 => "Copyright",
+                | 0x829A => "ExposureTime",
+                | 0x829D => "FNumber",
+                | 0x8769 => "ExifIFDPointer",
+                | 0x8822 => "ExposureProgram",
+                | 0x8825 => "GPSInfoIFDPointer",
+                | 0x8827 => "ISOSpeedRatings",
+                | 0x9000 => "ExifVersion",
+                | 0x9003 => "DateTimeOriginal",
+                | 0x9004 => "DateTimeDigitized",
+                | 0x9010 => "OffsetTime",
+                | 0x9011 => "OffsetTimeOriginal",
+                | 0x9101 => "ComponentsConfiguration",
+                | 0x9201 => "ShutterSpeedValue",
+                | 0x9202 => "ApertureValue",
+                | 0x9204 => "ExposureBiasValue",
+                | 0x9207 => "MeteringMode",
+                | 0x9209 => "Flash",
+                | 0x920A => "FocalLength",
+                | 0x927C => "MakerNote",
+                | 0x9286 => "UserComment",
+                | 0xA000 => "FlashpixVersion",
+                | 0xA001 => "ColorSpace",
+                | 0xA002 => "PixelXDimension",
+                | 0xA003 => "PixelYDimension",
+                | 0xA005 => "InteroperabilityIFDPointer",
+                | 0xA402 => "ExposureMode",
+                | 0xA403 => "WhiteBalance",
+                | 0xA405 => "FocalLengthIn35mmFilm",
+                | 0xA420 => "ImageUniqueID",
+                | 0xA430 => "CameraOwnerName",
+                | 0xA431 => "BodySerialNumber",
+                | 0xA433 => "LensMake",
+                | 0xA434 => "LensModel",
+                | _ => "Unknown tag"
+            }
+        }
+    }
+
+    /// Value with units and enumerations resolved where the tag defines them
+    pub fn describe(&self) -> String
+    {
+        let number = self.value.as_f64();
+        match (self.directory, self.tag, number)
+        {
+            | (ExifDirectory::Gps, ..) => self.value.to_string(),
+            | (_, 0x0112, Some(orientation)) => orientation_name(orientation as u32).to_string(),
+            | (_, 0x829A, Some(seconds)) if seconds > 0.0 && seconds < 1.0 => format!("1/{:.0} s", 1.0 / seconds),
+            | (_, 0x829A, Some(seconds)) => format!("{} s", seconds),
+            | (_, 0x829D, Some(f_number)) => format!("f/{:.1}", f_number),
+            | (_, 0x920A, Some(millimeters)) => format!("{:.1} mm", millimeters),
+            | (_, 0x9000 | 0xA000, _) => match self.value
+            {
+                | ExifValue::Bytes(ref bytes) => String::from_utf8_lossy(bytes).to_string(),
+                | ref value => value.to_string()
+            },
+            | (_, 0xA001, Some(1.0)) => "sRGB".to_string(),
+            | (_, 0xA001, Some(65535.0)) => "Uncalibrated".to_string(),
+            | (_, 0x9209, Some(flash)) => match flash as u32 & 0x01
+            {
+                | 1 => "Fired".to_string(),
+                | _ => "Did not fire".to_string()
+            },
+            | _ => self.value.to_string()
+        }
+    }
+}
+
+/// Describe an orientation value (TIFF 6.0)
+fn orientation_name(value: u32) -> &'static str
+{
+    match value
+    {
+        | 1 => "Normal",
+        | 2 => "Mirrored horizontally",
+        | 3 => "Rotated 180°",
+        | 4 => "Mirrored vertically",
+        | 5 => "Mirrored horizontally, rotated 270° clockwise",
+        | 6 => "Rotated 90° clockwise",
+        | 7 => "Mirrored horizontally, rotated 90° clockwise",
+        | 8 => "Rotated 270° clockwise",
+        | _ => "Unknown orientation"
+    }
+}
+
+/// Byte-order aware reader of a TIFF structure
+struct TiffReader<'a>
+{
+    data:       &'a [u8],
+    big_endian: bool
+}
+
+impl TiffReader<'_>
+{
+    fn u16(&self, position: usize) -> Option<u16>
+    {
+        let bytes = [*self.data.get(position)?, *self.data.get(position + 1)?];
+        match self.big_endian
+        {
+            | true => Some(u16::from_be_bytes(bytes)),
+            | false => Some(u16::from_le_bytes(bytes))
+        }
+    }
+
+    fn u32(&self, position: usize) -> Option<u32>
+    {
+        let bytes: [u8; 4] = self.data.get(position..position + 4)?.try_into().ok()?;
+        match self.big_endian
+        {
+            | true => Some(u32::from_be_bytes(bytes)),
+            | false => Some(u32::from_le_bytes(bytes))
+        }
+    }
+
+    /// Read the value of an IFD entry whose 4-byte value field is at `position`
+    fn value(&self, field_type: u16, count: usize, position: usize) -> Option<ExifValue>
+    {
+        let size = match field_type
+        {
+            | 1 | 2 | 6 | 7 => 1,
+            | 3 | 8 => 2,
+            | 4 | 9 => 4,
+            | 5 | 10 => 8,
+            | _ => return None
+        };
+        let length = size * count;
+        // Values of up to four bytes are stored in the entry itself
+        let start = match length
+        {
+            | 0..=4 => position,
+            | _ => self.u32(position)? as usize
+        };
+        let bytes = self.data.get(start..start.checked_add(length)?)?;
+
+        let value = match field_type
+        {
+            | 2 => ExifValue::Text(String::from_utf8_lossy(bytes).trim_end_matches('\0').trim_end().to_string()),
+            | 3 => ExifValue::Unsigned((0..count).filter_map(|index| self.u16(start + index * 2).map(u32::from)).collect()),
+            | 4 => ExifValue::Unsigned((0..count).filter_map(|index| self.u32(start + index * 4)).collect()),
+            | 8 => ExifValue::Signed((0..count).filter_map(|index| self.u16(start + index * 2).map(|value| value as i16 as i32)).collect()),
+            | 9 => ExifValue::Signed((0..count).filter_map(|index| self.u32(start + index * 4).map(|value| value as i32)).collect()),
+            | 5 => ExifValue::Rational((0..count).filter_map(|index| Some((self.u32(start + index * 8)?, self.u32(start + index * 8 + 4)?))).collect()),
+            | 10 => ExifValue::SignedRational(
+                (0..count).filter_map(|index| Some((self.u32(start + index * 8)? as i32, self.u32(start + index * 8 + 4)? as i32))).collect()
+            ),
+            | _ => ExifValue::Bytes(bytes.to_vec())
+        };
+        Some(value)
+    }
+}
+
+/// EXIF metadata: the TIFF structure of an APP1 segment or eXIf chunk
+#[derive(Debug, Clone)]
+pub struct Exif
+{
+    pub big_endian: bool,
+    pub entries:    Vec<ExifEntry>
+}
+
+impl Exif
+{
+    /// Parse the TIFF header and the IFD0, Exif, GPS and thumbnail directories of `data`
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let big_endian = match data.get(0..4)
+        {
+            | Some(b"MM\0*") => true,
+            | Some(b"II*\0") => false,
+            | _ => return Err(tr("Invalid TIFF header in EXIF data").to_string())
+        };
+        let reader = TiffReader { data, big_endian };
+        let mut exif = Exif { big_endian, entries: Vec::new() };
+        let mut visited = Vec::new();
+
+        let first = reader.u32(4).ok_or(tr("EXIF data too short"))? as usize;
+        let next = exif.read_directory(&reader, first, ExifDirectory::Image, &mut visited);
+
+        // Sub-directories referenced by pointer tags of IFD0
+        let pointers: Vec<(u16, usize)> = exif
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry.tag, 0x8769 | 0x8825))
+            .filter_map(|entry| entry.value.as_f64().map(|offset| (entry.tag, offset as usize)))
+            .collect();
+        for (tag, offset) in pointers
+        {
+            let directory = match tag
+            {
+                | 0x8769 => ExifDirectory::Photo,
+                | _ => ExifDirectory::Gps
+            };
+            exif.read_directory(&reader, offset, directory, &mut visited);
+        }
+        if let Some(next) = next.filter(|&next| next != 0)
+        {
+            exif.read_directory(&reader, next, ExifDirectory::Thumbnail, &mut visited);
+        }
+
+        Ok(exif)
+    }
+
+    /// Add the entries of the directory at `offset`, returning the offset of the next directory
+    fn read_directory(&mut self, reader: &TiffReader, offset: usize, directory: ExifDirectory, visited: &mut Vec<usize>) -> Option<usize>
+    {
+        // Directories referenced twice would be read forever
+        if visited.contains(&offset) == true
+        {
+            return None;
+        }
+        visited.push(offset);
+
+        let count = (reader.u16(offset)? as usize).min(MAX_IFD_ENTRIES);
+        for index in 0..count
+        {
+            let position = offset + 2 + index * 12;
+            let (Some(tag), Some(field_type), Some(value_count)) = (reader.u16(position), reader.u16(position + 2), reader.u32(position + 4))
+            else
+            {
+                return None;
+            };
+            if let Some(value) = reader.value(field_type, value_count as usize, position + 8)
+            {
+                self.entries.push(ExifEntry { directory, tag, value });
+            }
+        }
+
+        reader.u32(offset + 2 + count * 12).map(|next| next as usize)
+    }
+
+    /// "big-endian" (Motorola) or "little-endian" (Intel)
+    pub fn byte_order(&self) -> &'static str
+    {
+        match self.big_endian
+        {
+            | true => "big-endian",
+            | false => "little-endian"
+        }
+    }
+
+    /// Value of the first entry with `tag` in `directory`
+    pub fn get(&self, directory: ExifDirectory, tag: u16) -> Option<&ExifValue>
+    {
+        self.entries.iter().find(|entry| entry.directory == directory && entry.tag == tag).map(|entry| &entry.value)
+    }
+
+    /// Camera make and model, e.g. "Canon EOS 5D"
+    pub fn camera(&self) -> Option<String>
+    {
+        let make = self.get(ExifDirectory::Image, 0x010F).and_then(ExifValue::as_text).unwrap_or_default();
+        let model = self.get(ExifDirectory::Image, 0x0110).and_then(ExifValue::as_text).unwrap_or_default();
+        match (make, model)
+        {
+            | ("", "") => None,
+            // Many models repeat the make
+            | (make, model) if model.starts_with(make) == true => Some(model.to_string()),
+            | (make, model) => Some(format!("{} {}", make, model).trim().to_string())
+        }
+    }
+
+    /// Date the picture was taken (DateTimeOriginal, or DateTime of the file)
+    pub fn date(&self) -> Option<&str>
+    {
+        self.get(ExifDirectory::Photo, 0x9003).or_else(|| self.get(ExifDirectory::Image, 0x0132)).and_then(ExifValue::as_text)
+    }
+
+    /// Latitude and longitude in decimal degrees (south and west negative) and altitude in meters
+    pub fn gps(&self) -> Option<(f64, f64, Option<f64>)>
+    {
+        let coordinate = |value_tag: u16, reference_tag: u16, negative: &str| -> Option<f64> {
+            let ExifValue::Rational(ref parts) = *self.get(ExifDirectory::Gps, value_tag)?
+            else
+            {
+                return None;
+            };
+            let degrees = parts.iter().take(3).zip([1.0, 60.0, 3600.0]).map(|(&(numerator, denominator), scale)| match denominator
+            {
+                | 0 => 0.0,
+                | _ => numerator as f64 / denominator as f64 / scale
+            });
+            let value: f64 = degrees.sum();
+            match self.get(ExifDirectory::Gps, reference_tag).and_then(ExifValue::as_text)
+            {
+                | Some(reference) if reference == negative => Some(-value),
+                | _ => Some(value)
+            }
+        };
+
+        let latitude = coordinate(0x0002, 0x0001, "S")?;
+        let longitude = coordinate(0x0004, 0x0003, "W")?;
+        let altitude = self.get(ExifDirectory::Gps, 0x0006).and_then(ExifValue::as_f64).map(|altitude| {
+            // Altitude reference 1: below sea level
+            match self.get(ExifDirectory::Gps, 0x0005).and_then(ExifValue::as_f64)
+            {
+                | Some(1.0) => -altitude,
+                | _ => altitude
+            }
+        });
+        Some((latitude, longitude, altitude))
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "byte_order": self.byte_order(),
+            "camera": self.camera(),
+            "date": self.date(),
+            "gps": self.gps().map(|(latitude, longitude, altitude)| serde_json::json!({ "latitude": latitude, "longitude": longitude, "altitude": altitude })),
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "directory": entry.directory.name(),
+                "tag": entry.tag,
+                "name": entry.name(),
+                "value": entry.describe()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for Exif
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if let Some(camera) = self.camera()
+        {
+            writeln!(f, "{}", tr_fmt("Camera: {}", &[&camera]))?;
+        }
+        if let Some(date) = self.date()
+        {
+            writeln!(f, "{}", tr_fmt("Date: {}", &[&date]))?;
+        }
+        if let Some((latitude, longitude, altitude)) = self.gps()
+        {
+            let north_south = match latitude < 0.0
+            {
+                | true => 'S',
+                | false => 'N'
+            };
+            let east_west = match longitude < 0.0
+            {
+                | true => 'W',
+                | false => 'E'
+            };
+            write!(f, "{}", tr_fmt("GPS: {}° {}, {}° {}", &[&format!("{:.6}", latitude.abs()), &north_south, &format!("{:.6}", longitude.abs()), &east_west]))?;
+            match altitude
+            {
+                | Some(altitude) => writeln!(f, ", {:.1} m", altitude)?,
+                | None => writeln!(f)?
+            }
+        }
+        writeln!(f, "{}", tr_fmt("Tags: {}", &[&self.entries.len()]))?;
+        for entry in &self.entries
+        {
+            writeln!(f, "  {} 0x{:04X} {}: {}", entry.directory.name(), entry.tag, entry.name(), entry.describe())?;
+        }
+        Ok(())
+    }
+}
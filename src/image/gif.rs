@@ -0,0 +1,303 @@
+use std::fmt;
+
+use crate::{
+    bytes::read_u16_le,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt}
+};
+
+/// Follow a chain of data sub-blocks starting at `position`, returning their payload and the position after the terminator
+fn read_sub_blocks(data: &[u8], mut position: usize) -> Option<(Vec<u8>, usize)>
+{
+    let mut payload = Vec::new();
+    loop
+    {
+        let size = *data.get(position)? as usize;
+        position += 1;
+        if size == 0
+        {
+            return Some((payload, position));
+        }
+        payload.extend_from_slice(data.get(position..position + size)?);
+        position += size;
+    }
+}
+
+/// Number of entries of a color table from the size bits of a packed field
+fn color_table_entries(packed: u8) -> usize
+{
+    2 << (packed & 0x07)
+}
+
+/// Block of a GIF data stream
+#[derive(Debug, Clone, PartialEq)]
+pub enum GifBlock
+{
+    /// Image descriptor followed by the LZW-compressed image data
+    Image
+    {
+        left:         u16,
+        top:          u16,
+        width:        u16,
+        height:       u16,
+        local_colors: Option<usize>,
+        interlaced:   bool,
+        data_size:    usize
+    },
+    /// Graphic control extension: delay in hundredths of a second before the next image
+    GraphicControl
+    {
+        delay:       u16,
+        disposal:    u8,
+        transparent: Option<u8>
+    },
+    /// Application extension, e.g. NETSCAPE2.0 with the loop count of an animation
+    Application
+    {
+        identifier: String,
+        loop_count: Option<u16>
+    },
+    Comment(String),
+    PlainText,
+    /// Extension with an unknown label
+    Extension(u8)
+}
+
+impl fmt::Display for GifBlock
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | GifBlock::Image { left, top, width, height, local_colors, interlaced, data_size } =>
+            {
+                write!(f, "{}", tr_fmt("Image {}x{} at {},{}, {} bytes of image data", &[&width, &height, &left, &top, &data_size]))?;
+                if let Some(colors) = local_colors
+                {
+                    write!(f, "{}", tr_fmt(", local color table with {} entries", &[&colors]))?;
+                }
+                if *interlaced == true
+                {
+                    write!(f, "{}", tr(", interlaced"))?;
+                }
+                Ok(())
+            }
+            | GifBlock::GraphicControl { delay, disposal, transparent } =>
+            {
+                write!(f, "{}", tr_fmt("Graphic Control: delay {} ms, disposal method {}", &[&(*delay as u32 * 10), &disposal]))?;
+                if let Some(index) = transparent
+                {
+                    write!(f, "{}", tr_fmt(", transparent color {}", &[&index]))?;
+                }
+                Ok(())
+            }
+            | GifBlock::Application { identifier, loop_count: Some(0) } => write!(f, "{}", tr_fmt("Application {}: loops forever", &[&identifier])),
+            | GifBlock::Application { identifier, loop_count: Some(count) } => write!(f, "{}", tr_fmt("Application {}: {} loops", &[&identifier, &count])),
+            | GifBlock::Application { identifier, loop_count: None } => write!(f, "{}", tr_fmt("Application {}", &[&identifier])),
+            | GifBlock::Comment(text) => write!(f, "{}", tr_fmt("Comment: \"{}\"", &[&text])),
+            | GifBlock::PlainText => write!(f, "{}", tr("Plain Text Extension")),
+            | GifBlock::Extension(label) => write!(f, "{}", tr_fmt("Extension {}", &[&format!("0x{:02X}", label)]))
+        }
+    }
+}
+
+/// Parsed GIF data stream
+#[derive(Debug, Clone)]
+pub struct GifImage
+{
+    /// "87a" or "89a"
+    pub version:          String,
+    /// Logical screen size
+    pub width:            u16,
+    pub height:           u16,
+    pub global_colors:    Option<usize>,
+    /// Bits per primary color of the source image
+    pub color_resolution: u8,
+    pub background_index: u8,
+    /// Blocks with their offsets
+    pub blocks:           Vec<(u64, GifBlock)>,
+    pub has_trailer:      bool,
+    /// Bytes up to and including the trailer
+    pub size:             u64
+}
+
+impl GifImage
+{
+    /// Walk the blocks of the GIF data stream at the start of `data`
+    pub fn parse(data: &[u8], base_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, String>
+    {
+        if data.len() < 13 || data.starts_with(b"GIF") == false
+        {
+            return Err(tr("GIF header too short").to_string());
+        }
+
+        let packed = data[10];
+        let mut image = GifImage {
+            version:          String::from_utf8_lossy(&data[3..6]).to_string(),
+            width:            read_u16_le(data, 6),
+            height:           read_u16_le(data, 8),
+            global_colors:    (packed & 0x80 != 0).then(|| color_table_entries(packed)),
+            color_resolution: ((packed >> 4) & 0x07) + 1,
+            background_index: data[11],
+            blocks:           Vec::new(),
+            has_trailer:      false,
+            size:             data.len() as u64
+        };
+        let mut position = 13 + image.global_colors.unwrap_or(0) * 3;
+
+        while let Some(&introducer) = data.get(position)
+        {
+            let offset = base_offset + position as u64;
+            let block = match introducer
+            {
+                | 0x3B =>
+                {
+                    image.has_trailer = true;
+                    image.size = position as u64 + 1;
+                    break;
+                }
+                | 0x2C =>
+                {
+                    let Some(descriptor) = data.get(position + 1..position + 10)
+                    else
+                    {
+                        diagnostics.warning(Some(offset), tr("GIF image descriptor truncated"));
+                        break;
+                    };
+                    let local_colors = (descriptor[8] & 0x80 != 0).then(|| color_table_entries(descriptor[8]));
+                    // Image data: LZW minimum code size, then sub-blocks
+                    let Some((lzw_data, end)) = read_sub_blocks(data, position + 11 + local_colors.unwrap_or(0) * 3)
+                    else
+                    {
+                        diagnostics.warning(Some(offset), tr("GIF image data truncated"));
+                        break;
+                    };
+                    position = end;
+                    GifBlock::Image {
+                        left: read_u16_le(descriptor, 0),
+                        top: read_u16_le(descriptor, 2),
+                        width: read_u16_le(descriptor, 4),
+                        height: read_u16_le(descriptor, 6),
+                        local_colors,
+                        interlaced: descriptor[8] & 0x40 != 0,
+                        data_size: lzw_data.len()
+                    }
+                }
+                | 0x21 =>
+                {
+                    let label = data.get(position + 1).copied().unwrap_or(0);
+                    let Some((payload, end)) = read_sub_blocks(data, position + 2)
+                    else
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("GIF extension {} truncated", &[&format!("0x{:02X}", label)]));
+                        break;
+                    };
+                    position = end;
+                    match label
+                    {
+                        | 0xF9 if payload.len() >= 4 => GifBlock::GraphicControl {
+                            delay:       read_u16_le(&payload, 1),
+                            disposal:    (payload[0] >> 2) & 0x07,
+                            transparent: (payload[0] & 0x01 != 0).then_some(payload[3])
+                        },
+                        | 0xFF =>
+                        {
+                            let identifier = String::from_utf8_lossy(&payload[..payload.len().min(11)]).to_string();
+                            // The loop count sub-block (id 1) follows the identifier of NETSCAPE2.0 and ANIMEXTS1.0
+                            let loop_count = match payload.get(11..14)
+                            {
+                                | Some(&[1, low, high]) => Some(u16::from_le_bytes([low, high])),
+                                | _ => None
+                            };
+                            GifBlock::Application { identifier, loop_count }
+                        }
+                        | 0xFE => GifBlock::Comment(String::from_utf8_lossy(&payload).to_string()),
+                        | 0x01 => GifBlock::PlainText,
+                        | label => GifBlock::Extension(label)
+                    }
+                }
+                | byte =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("Unexpected GIF block introducer {}", &[&format!("0x{:02X}", byte)]));
+                    break;
+                }
+            };
+            image.blocks.push((offset, block));
+        }
+
+        if image.has_trailer == false
+        {
+            diagnostics.warning(Some(base_offset + image.size), tr("GIF data stream ends without a trailer"));
+        }
+        if image.version != "87a" && image.version != "89a"
+        {
+            diagnostics.warning(Some(base_offset + 3), tr_fmt("Unknown GIF version '{}'", &[&image.version]));
+        }
+
+        Ok(image)
+    }
+
+    /// Number of images (animation frames)
+    pub fn frame_count(&self) -> usize
+    {
+        self.blocks.iter().filter(|(_, block)| matches!(block, GifBlock::Image { .. })).count()
+    }
+
+    /// Total delay of all graphic control extensions in seconds
+    pub fn duration(&self) -> f64
+    {
+        let centiseconds: u64 = self
+            .blocks
+            .iter()
+            .map(|(_, block)| match block
+            {
+                | GifBlock::GraphicControl { delay, .. } => *delay as u64,
+                | _ => 0
+            })
+            .sum();
+        centiseconds as f64 / 100.0
+    }
+
+    /// Color table description, e.g. "Indexed, 256 colors"
+    pub fn color_description(&self) -> String
+    {
+        match self.global_colors
+        {
+            | Some(colors) => tr_fmt("Indexed, {} global colors, {} bits per primary color", &[&colors, &self.color_resolution]),
+            | None => tr_fmt("Indexed, local color tables, {} bits per primary color", &[&self.color_resolution])
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "global_colors": self.global_colors,
+            "background_index": self.background_index,
+            "frames": self.frame_count(),
+            "duration": self.duration(),
+            "blocks": self.blocks.iter().map(|(offset, block)| serde_json::json!({ "offset": offset, "block": block.to_string() })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for GifImage
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: GIF{}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Background Color Index: {}", &[&self.background_index]))?;
+        writeln!(f, "{}", tr_fmt("Frames: {}", &[&self.frame_count()]))?;
+        if self.frame_count() > 1
+        {
+            writeln!(f, "{}", tr_fmt("Animation Duration: {} s", &[&format!("{:.2}", self.duration())]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Blocks: {}", &[&self.blocks.len()]))?;
+        for (offset, block) in &self.blocks
+        {
+            writeln!(f, "  0x{:08X}  {}", offset, block)?;
+        }
+        Ok(())
+    }
+}
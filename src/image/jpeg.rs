@@ -0,0 +1,530 @@
+use std::fmt;
+
+use crate::{
+    bytes::read_u16_be,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    image::exif::Exif
+};
+
+/// Describe a JPEG marker (the byte following 0xFF)
+pub fn marker_name(marker: u8) -> &'static str
+{
+    match marker
+    {
+        | 0xC0 => "SOF0 (Baseline DCT)",
+        | 0xC1 => "SOF1 (Extended Sequential DCT)",
+        | 0xC2 => "SOF2 (Progressive DCT)",
+        | 0xC3 => "SOF3 (Lossless)",
+        | 0xC5 => "SOF5 (Differential Sequential DCT)",
+        | 0xC6 => "SOF6 (Differential Progressive DCT)",
+        | 0xC7 => "SOF7 (Differential Lossless)",
+        | 0xC9 => "SOF9 (Extended Sequential DCT, Arithmetic)",
+        | 0xCA => "SOF10 (Progressive DCT, Arithmetic)",
+        | 0xCB => "SOF11 (Lossless, Arithmetic)",
+        | 0xCD..=0xCF => "SOF (Differential, Arithmetic)",
+        | 0xC4 => "DHT (Define Huffman Tables)",
+        | 0xC8 => "JPG (Reserved)",
+        | 0xCC => "DAC (Define Arithmetic Coding)",
+        | 0xD0..=0xD7 => "RST (Restart)",
+        | 0xD8 => "SOI (Start of Image)",
+        | 0xD9 => "EOI (End of Image)",
+        | 0xDA => "SOS (Start of Scan)",
+        | 0xDB => "DQT (Define Quantization Tables)",
+        | 0xDC => "DNL (Define Number of Lines)",
+        | 0xDD => "DRI (Define Restart Interval)",
+        | 0xE0 => "APP0",
+        | 0xE1 => "APP1",
+        | 0xE2 => "APP2",
+        | 0xED => "APP13",
+        | 0xEE => "APP14",
+        | 0xE3..=0xEF => "APPn (Application)",
+        | 0xFE => "COM (Comment)",
+        | _ => "Unknown"
+    }
+}
+
+/// Check whether a marker starts a frame (SOFn)
+fn is_frame_marker(marker: u8) -> bool
+{
+    matches!(marker, 0xC0..=0xCF) && matches!(marker, 0xC4 | 0xC8 | 0xCC) == false
+}
+
+/// Image component declared by the frame header
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JpegComponent
+{
+    pub id:                  u8,
+    pub horizontal_sampling: u8,
+    pub vertical_sampling:   u8,
+    pub quantization_table:  u8
+}
+
+/// Frame header (SOFn)
+#[derive(Debug, Clone)]
+pub struct JpegFrame
+{
+    pub marker:     u8,
+    /// Bits per sample
+    pub precision:  u8,
+    pub height:     u16,
+    pub width:      u16,
+    pub components: Vec<JpegComponent>
+}
+
+impl JpegFrame
+{
+    fn parse(marker: u8, data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 6
+        {
+            return Err(tr("JPEG frame header too short").to_string());
+        }
+
+        let components = data[6..]
+            .chunks_exact(3)
+            .take(data[5] as usize)
+            .map(|component| JpegComponent {
+                id:                  component[0],
+                horizontal_sampling: component[1] >> 4,
+                vertical_sampling:   component[1] & 0x0F,
+                quantization_table:  component[2]
+            })
+            .collect();
+
+        Ok(JpegFrame { marker, precision: data[0], height: read_u16_be(data, 1), width: read_u16_be(data, 3), components })
+    }
+
+    /// Chroma subsampling of a three-component frame, e.g. "4:2:0"
+    pub fn subsampling(&self) -> Option<String>
+    {
+        let [luma, chroma, ..] = self.components.as_slice()
+        else
+        {
+            return None;
+        };
+        if self.components.len() != 3 || chroma.horizontal_sampling == 0 || chroma.vertical_sampling == 0
+        {
+            return None;
+        }
+
+        let subsampling = match (luma.horizontal_sampling / chroma.horizontal_sampling, luma.vertical_sampling / chroma.vertical_sampling)
+        {
+            | (1, 1) => "4:4:4".to_string(),
+            | (2, 1) => "4:2:2".to_string(),
+            | (2, 2) => "4:2:0".to_string(),
+            | (4, 1) => "4:1:1".to_string(),
+            | (1, 2) => "4:4:0".to_string(),
+            | _ => self.components.iter().map(|component| format!("{}x{}", component.horizontal_sampling, component.vertical_sampling)).collect::<Vec<_>>().join(",")
+        };
+        Some(subsampling)
+    }
+}
+
+/// JFIF header of an APP0 segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JfifHeader
+{
+    pub version_major: u8,
+    pub version_minor: u8,
+    /// 0: aspect ratio only, 1: dots per inch, 2: dots per centimeter
+    pub units:         u8,
+    pub x_density:     u16,
+    pub y_density:     u16,
+    pub thumbnail:     (u8, u8)
+}
+
+impl JfifHeader
+{
+    fn parse(data: &[u8]) -> Option<Self>
+    {
+        if data.len() < 14 || data.starts_with(b"JFIF\0") == false
+        {
+            return None;
+        }
+
+        Some(JfifHeader {
+            version_major: data[5],
+            version_minor: data[6],
+            units:         data[7],
+            x_density:     read_u16_be(data, 8),
+            y_density:     read_u16_be(data, 10),
+            thumbnail:     (data[12], data[13])
+        })
+    }
+}
+
+impl fmt::Display for JfifHeader
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let units = match self.units
+        {
+            | 1 => " dpi",
+            | 2 => " dots/cm",
+            | _ => " (aspect ratio)"
+        };
+        write!(
+            f,
+            "{}",
+            tr_fmt("JFIF {}.{}, density {}x{}{}", &[&self.version_major, &format!("{:02}", self.version_minor), &self.x_density, &self.y_density, &units])
+        )?;
+        if self.thumbnail != (0, 0)
+        {
+            write!(f, "{}", tr_fmt(", thumbnail {}x{}", &[&self.thumbnail.0, &self.thumbnail.1]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Marker segment of a JPEG stream
+#[derive(Debug, Clone)]
+pub struct JpegSegment
+{
+    pub offset:     u64,
+    pub marker:     u8,
+    /// Segment length including marker and length field
+    pub length:     u64,
+    /// Entropy-coded data following a SOS segment
+    pub scan_bytes: u64,
+    pub details:    Option<String>
+}
+
+impl fmt::Display for JpegSegment
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}",
+            tr_fmt("{}  FF{}  {} {} bytes", &[
+                &format!("0x{:08X}", self.offset),
+                &format!("{:02X}", self.marker),
+                &format!("{:<34}", marker_name(self.marker)),
+                &format!("{:>8}", self.length)
+            ])
+        )?;
+        if let Some(ref details) = self.details
+        {
+            write!(f, "  {}", details)?;
+        }
+        if self.scan_bytes > 0
+        {
+            write!(f, " {}", tr_fmt("({} bytes of scan data)", &[&self.scan_bytes]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parsed JPEG stream
+#[derive(Debug, Clone)]
+pub struct JpegImage
+{
+    pub segments:        Vec<JpegSegment>,
+    pub frame:           Option<JpegFrame>,
+    pub jfif:            Option<JfifHeader>,
+    /// EXIF metadata of the first APP1 EXIF segment
+    pub exif:            Option<Exif>,
+    /// Color transform of an Adobe APP14 segment (0: none/CMYK, 1: YCbCr, 2: YCCK)
+    pub adobe_transform: Option<u8>,
+    pub comments:        Vec<String>,
+    pub has_eoi:         bool,
+    /// Bytes up to and including EOI
+    pub size:            u64
+}
+
+impl JpegImage
+{
+    /// Walk the marker segments of the JPEG stream at the start of `data`
+    pub fn parse(data: &[u8], base_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, String>
+    {
+        if data.starts_with(&[0xFF, 0xD8]) == false
+        {
+            return Err(tr("Missing JPEG start of image marker").to_string());
+        }
+
+        let mut image = JpegImage {
+            segments:        vec![JpegSegment { offset: base_offset, marker: 0xD8, length: 2, scan_bytes: 0, details: None }],
+            frame:           None,
+            jfif:            None,
+            exif:            None,
+            adobe_transform: None,
+            comments:        Vec::new(),
+            has_eoi:         false,
+            size:            data.len() as u64
+        };
+        let mut position = 2;
+
+        while position < data.len()
+        {
+            if data[position] != 0xFF
+            {
+                diagnostics.warning(Some(base_offset + position as u64), tr_fmt("Expected JPEG marker, found byte {}", &[&format!("0x{:02X}", data[position])]));
+                break;
+            }
+            // Any number of 0xFF fill bytes may precede a marker
+            let start = position;
+            while position < data.len() && data[position] == 0xFF
+            {
+                position += 1;
+            }
+            let Some(&marker) = data.get(position)
+            else
+            {
+                break;
+            };
+            position += 1;
+
+            if marker == 0xD9
+            {
+                image.segments.push(JpegSegment { offset: base_offset + start as u64, marker, length: (position - start) as u64, scan_bytes: 0, details: None });
+                image.has_eoi = true;
+                image.size = position as u64;
+                break;
+            }
+            if matches!(marker, 0x01 | 0xD0..=0xD8)
+            {
+                image.segments.push(JpegSegment { offset: base_offset + start as u64, marker, length: (position - start) as u64, scan_bytes: 0, details: None });
+                continue;
+            }
+
+            if position + 2 > data.len()
+            {
+                diagnostics.warning(Some(base_offset + start as u64), tr("JPEG segment length truncated at end of data"));
+                break;
+            }
+            let length = read_u16_be(data, position) as usize;
+            if length < 2 || position + length > data.len()
+            {
+                diagnostics.warning(
+                    Some(base_offset + start as u64),
+                    tr_fmt("JPEG segment FF{} declares {} bytes, {} available", &[&format!("{:02X}", marker), &length, &(data.len() - position)])
+                );
+                break;
+            }
+            let payload = &data[position + 2..position + length];
+            position += length;
+
+            let details = image.decode_segment(marker, payload, base_offset + (position - length + 2) as u64, diagnostics);
+            let mut segment = JpegSegment { offset: base_offset + start as u64, marker, length: (position - start) as u64, scan_bytes: 0, details };
+
+            // Entropy-coded data runs up to the next marker that is neither a stuffed 0x00 nor a restart marker
+            if marker == 0xDA
+            {
+                let scan_start = position;
+                while position + 1 < data.len()
+                {
+                    if data[position] == 0xFF && data[position + 1] != 0 && matches!(data[position + 1], 0xD0..=0xD7 | 0xFF) == false
+                    {
+                        break;
+                    }
+                    position += 1;
+                }
+                if position + 1 >= data.len()
+                {
+                    position = data.len();
+                }
+                segment.scan_bytes = (position - scan_start) as u64;
+            }
+            image.segments.push(segment);
+        }
+
+        if image.has_eoi == false
+        {
+            diagnostics.warning(Some(base_offset + image.size), tr("JPEG stream ends without an end of image marker"));
+        }
+        if image.frame.is_none() == true
+        {
+            diagnostics.warning(Some(base_offset), tr("JPEG stream has no frame header (SOFn)"));
+        }
+
+        Ok(image)
+    }
+
+    /// Decode the payload of a marker segment, returning a short description
+    fn decode_segment(&mut self, marker: u8, payload: &[u8], payload_offset: u64, diagnostics: &mut Diagnostics) -> Option<String>
+    {
+        match marker
+        {
+            | marker if is_frame_marker(marker) == true => match JpegFrame::parse(marker, payload)
+            {
+                | Ok(frame) =>
+                {
+                    let details = tr_fmt("{}x{}, {} components, {} bits", &[&frame.width, &frame.height, &frame.components.len(), &frame.precision]);
+                    if self.frame.is_none() == true
+                    {
+                        self.frame = Some(frame);
+                    }
+                    Some(details)
+                }
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(payload_offset), e);
+                    None
+                }
+            },
+            | 0xE0 =>
+            {
+                if let Some(jfif) = JfifHeader::parse(payload)
+                {
+                    self.jfif = Some(jfif);
+                    return Some(jfif.to_string());
+                }
+                payload.starts_with(b"JFXX\0").then(|| "JFIF extension (thumbnail)".to_string())
+            }
+            | 0xE1 if payload.starts_with(b"Exif\0\0") == true => match Exif::parse(&payload[6..])
+            {
+                | Ok(exif) =>
+                {
+                    let details = tr_fmt("EXIF ({}, {} tags)", &[&exif.byte_order(), &exif.entries.len()]);
+                    self.exif.get_or_insert(exif);
+                    Some(details)
+                }
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(payload_offset + 6), e);
+                    Some("EXIF".to_string())
+                }
+            },
+            | 0xE1 if payload.starts_with(b"http://ns.adobe.com/xap/1.0/\0") == true => Some("XMP packet".to_string()),
+            | 0xE2 if payload.starts_with(b"ICC_PROFILE\0") == true && payload.len() >= 14 =>
+                Some(tr_fmt("ICC profile chunk {} of {}", &[&payload[12], &payload[13]])),
+            | 0xE2 if payload.starts_with(b"MPF\0") == true => Some("Multi-Picture Format".to_string()),
+            | 0xED if payload.starts_with(b"Photoshop 3.0\0") == true => Some("Photoshop image resources".to_string()),
+            | 0xEE if payload.starts_with(b"Adobe") == true && payload.len() >= 12 =>
+            {
+                self.adobe_transform = Some(payload[11]);
+                Some(tr_fmt("Adobe, color transform {}", &[&payload[11]]))
+            }
+            | 0xFE =>
+            {
+                let comment = String::from_utf8_lossy(payload).trim_end_matches('\0').to_string();
+                self.comments.push(comment.clone());
+                Some(format!("\"{}\"", comment))
+            }
+            | 0xDB =>
+            {
+                // Each table: precision/destination byte followed by 64 values of 8 or 16 bits
+                let mut tables = Vec::new();
+                let mut position = 0;
+                while position < payload.len()
+                {
+                    let precision = payload[position] >> 4;
+                    match precision
+                    {
+                        | 1 => tables.push(tr_fmt("{} (16-bit)", &[&(payload[position] & 0x0F)])),
+                        | _ => tables.push(format!("{}", payload[position] & 0x0F))
+                    }
+                    position += 1 + 64 * (precision as usize + 1);
+                }
+                Some(tr_fmt("tables {}", &[&(tables.join(", "))]))
+            }
+            | 0xC4 =>
+            {
+                // Each table: class/destination byte, 16 code length counts, then the symbols
+                let mut tables = Vec::new();
+                let mut position = 0;
+                while position + 17 <= payload.len()
+                {
+                    let class = match payload[position] >> 4
+                    {
+                        | 0 => "DC",
+                        | _ => "AC"
+                    };
+                    tables.push(format!("{} {}", class, payload[position] & 0x0F));
+                    let symbols: usize = payload[position + 1..position + 17].iter().map(|&count| count as usize).sum();
+                    position += 17 + symbols;
+                }
+                Some(tr_fmt("tables {}", &[&(tables.join(", "))]))
+            }
+            | 0xDD if payload.len() >= 2 => Some(tr_fmt("interval {} MCUs", &[&(read_u16_be(payload, 0))])),
+            | 0xDA if payload.is_empty() == false => Some(tr_fmt("{} components", &[&payload[0]])),
+            | _ => None
+        }
+    }
+
+    /// Color model and precision of the frame, e.g. "YCbCr 4:2:0, 8 bits per sample"
+    pub fn color_description(&self) -> Option<String>
+    {
+        let frame = self.frame.as_ref()?;
+        let ids: Vec<u8> = frame.components.iter().map(|component| component.id).collect();
+        let model = match frame.components.len()
+        {
+            | 1 => "Grayscale".to_string(),
+            | 3 if self.adobe_transform == Some(0) || ids == b"RGB" => "RGB".to_string(),
+            | 3 => match frame.subsampling()
+            {
+                | Some(subsampling) => tr_fmt("YCbCr {}", &[&subsampling]),
+                | None => "YCbCr".to_string()
+            },
+            | 4 if self.adobe_transform == Some(2) => "YCCK".to_string(),
+            | 4 => "CMYK".to_string(),
+            | count => tr_fmt("{} components", &[&count])
+        };
+        Some(tr_fmt("{}, {} bits per sample", &[&model, &frame.precision]))
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "frame": self.frame.as_ref().map(|frame| serde_json::json!({
+                "type": marker_name(frame.marker),
+                "precision": frame.precision,
+                "subsampling": frame.subsampling(),
+                "components": frame.components.iter().map(|component| serde_json::json!({
+                    "id": component.id,
+                    "horizontal_sampling": component.horizontal_sampling,
+                    "vertical_sampling": component.vertical_sampling,
+                    "quantization_table": component.quantization_table
+                })).collect::<Vec<_>>()
+            })),
+            "jfif": self.jfif.map(|jfif| jfif.to_string()),
+            "exif": self.exif.as_ref().map(Exif::to_json),
+            "comments": self.comments,
+            "segments": self.segments.iter().map(|segment| serde_json::json!({
+                "offset": segment.offset,
+                "marker": format!("FF{:02X}", segment.marker),
+                "name": marker_name(segment.marker),
+                "length": segment.length,
+                "scan_bytes": segment.scan_bytes,
+                "details": segment.details
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for JpegImage
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if let Some(ref frame) = self.frame
+        {
+            writeln!(f, "{}", tr_fmt("Frame: {}", &[&marker_name(frame.marker)]))?;
+            for component in &frame.components
+            {
+                writeln!(
+                    f,
+                    "  {}",
+                    tr_fmt("Component {}: sampling {}x{}, quantization table {}", &[
+                        &component.id, &component.horizontal_sampling, &component.vertical_sampling, &component.quantization_table
+                    ])
+                )?;
+            }
+        }
+        writeln!(f, "{}", tr_fmt("Segments: {}", &[&self.segments.len()]))?;
+        for segment in &self.segments
+        {
+            writeln!(f, "  {}", segment)?;
+        }
+        if let Some(ref exif) = self.exif
+        {
+            writeln!(f, "{}", tr("EXIF:"))?;
+            for line in exif.to_string().lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
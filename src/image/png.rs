@@ -0,0 +1,464 @@
+use std::fmt;
+
+use crate::{
+    bytes::read_u32_be,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    id3v2::extended_header::crc32,
+    image::exif::Exif,
+    inflate::zlib_decompress
+};
+
+/// PNG file signature
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Image header (IHDR)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngHeader
+{
+    pub width:       u32,
+    pub height:      u32,
+    pub bit_depth:   u8,
+    pub color_type:  u8,
+    pub compression: u8,
+    pub filter:      u8,
+    pub interlace:   u8
+}
+
+impl PngHeader
+{
+    fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 13
+        {
+            return Err(tr("PNG IHDR chunk too short").to_string());
+        }
+
+        Ok(PngHeader {
+            width:       read_u32_be(data, 0),
+            height:      read_u32_be(data, 4),
+            bit_depth:   data[8],
+            color_type:  data[9],
+            compression: data[10],
+            filter:      data[11],
+            interlace:   data[12]
+        })
+    }
+
+    /// Describe the color type
+    pub fn color_type_name(&self) -> &'static str
+    {
+        match self.color_type
+        {
+            | 0 => "Grayscale",
+            | 2 => "RGB",
+            | 3 => "Indexed",
+            | 4 => "Grayscale + Alpha",
+            | 6 => "RGBA",
+            | _ => "Unknown color type"
+        }
+    }
+
+    /// Color type and depth, e.g. "RGBA, 8 bits per sample"
+    pub fn color_description(&self) -> String
+    {
+        let interlace = match self.interlace
+        {
+            | 1 => ", Adam7 interlaced",
+            | _ => ""
+        };
+        tr_fmt("{}, {} bits per sample{}", &[&self.color_type_name(), &self.bit_depth, &interlace])
+    }
+
+    /// Check the bit depth against the depths allowed for the color type
+    fn valid_bit_depth(&self) -> bool
+    {
+        match self.color_type
+        {
+            | 0 => matches!(self.bit_depth, 1 | 2 | 4 | 8 | 16),
+            | 3 => matches!(self.bit_depth, 1 | 2 | 4 | 8),
+            | 2 | 4 | 6 => matches!(self.bit_depth, 8 | 16),
+            | _ => false
+        }
+    }
+}
+
+/// Chunk of a PNG file
+#[derive(Debug, Clone)]
+pub struct PngChunk
+{
+    pub offset:       u64,
+    pub chunk_type:   String,
+    pub length:       u32,
+    pub crc:          u32,
+    pub computed_crc: u32,
+    pub details:      Option<String>
+}
+
+impl PngChunk
+{
+    /// Ancillary chunks (lowercase first letter) may be ignored by decoders
+    pub fn is_critical(&self) -> bool
+    {
+        self.chunk_type.as_bytes().first().is_some_and(|byte| byte.is_ascii_uppercase())
+    }
+}
+
+impl fmt::Display for PngChunk
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", tr_fmt("{}  {}  {} bytes", &[&format!("0x{:08X}", self.offset), &self.chunk_type, &format!("{:>8}", self.length)]))?;
+        if let Some(ref details) = self.details
+        {
+            write!(f, "  {}", details)?;
+        }
+        if self.crc != self.computed_crc
+        {
+            write!(f, "  {}", tr("[CRC mismatch]"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Textual information of a tEXt, zTXt or iTXt chunk
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngText
+{
+    pub chunk_type: String,
+    pub keyword:    String,
+    /// Language tag of an iTXt chunk
+    pub language:   Option<String>,
+    pub text:       String
+}
+
+impl PngText
+{
+    fn parse(chunk_type: &str, data: &[u8]) -> Result<Self, String>
+    {
+        let separator = data.iter().position(|&byte| byte == 0).ok_or_else(|| tr_fmt("PNG {} chunk without keyword terminator", &[&chunk_type]))?;
+        // Keywords and tEXt/zTXt text are Latin-1
+        let latin1 = |bytes: &[u8]| bytes.iter().map(|&byte| byte as char).collect::<String>();
+        let keyword = latin1(&data[..separator]);
+        let rest = &data[separator + 1..];
+
+        let (language, text) = match chunk_type
+        {
+            | "tEXt" => (None, latin1(rest)),
+            | "zTXt" =>
+            {
+                let compressed = rest.get(1..).unwrap_or_default();
+                (None, latin1(&zlib_decompress(compressed)?))
+            }
+            | _ =>
+            {
+                if rest.len() < 2
+                {
+                    return Err(tr("PNG iTXt chunk too short").to_string());
+                }
+                let compressed = rest[0] != 0;
+                let mut fields = rest[2..].splitn(3, |&byte| byte == 0);
+                let language = String::from_utf8_lossy(fields.next().unwrap_or_default()).to_string();
+                let _translated_keyword = fields.next();
+                let text = fields.next().unwrap_or_default();
+                let text = match compressed
+                {
+                    | true => zlib_decompress(text)?,
+                    | false => text.to_vec()
+                };
+                (Some(language).filter(|language| language.is_empty() == false), String::from_utf8_lossy(&text).to_string())
+            }
+        };
+
+        Ok(PngText { chunk_type: chunk_type.to_string(), keyword, language, text })
+    }
+}
+
+/// Parsed PNG file
+#[derive(Debug, Clone)]
+pub struct PngImage
+{
+    pub header:    Option<PngHeader>,
+    pub chunks:    Vec<PngChunk>,
+    pub texts:     Vec<PngText>,
+    /// Number of PLTE entries
+    pub palette:   Option<usize>,
+    /// Frame count and play count of an animated PNG (acTL)
+    pub animation: Option<(u32, u32)>,
+    /// EXIF metadata of the eXIf chunk
+    pub exif:      Option<Exif>,
+    pub has_iend:  bool,
+    /// Bytes up to and including IEND
+    pub size:      u64
+}
+
+impl PngImage
+{
+    /// Walk the chunks of the PNG file at the start of `data`, verifying their checksums
+    pub fn parse(data: &[u8], base_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, String>
+    {
+        if data.starts_with(&SIGNATURE) == false
+        {
+            return Err(tr("Invalid PNG signature").to_string());
+        }
+
+        let mut image = PngImage {
+            header:    None,
+            chunks:    Vec::new(),
+            texts:     Vec::new(),
+            palette:   None,
+            animation: None,
+            exif:      None,
+            has_iend:  false,
+            size:      data.len() as u64
+        };
+        let mut position = SIGNATURE.len();
+
+        while position + 12 <= data.len()
+        {
+            let offset = base_offset + position as u64;
+            let length = read_u32_be(data, position);
+            let chunk_type: String = data[position + 4..position + 8].iter().map(|&byte| byte as char).collect();
+            if data[position + 4..position + 8].iter().all(u8::is_ascii_alphabetic) == false
+            {
+                diagnostics.warning(Some(offset), tr("Invalid PNG chunk type, chunk walk stopped"));
+                break;
+            }
+            if position + 12 + length as usize > data.len()
+            {
+                diagnostics.warning(Some(offset), tr_fmt("PNG chunk {} declares {} bytes, {} available", &[&chunk_type, &length, &(data.len() - position - 12)]));
+                break;
+            }
+
+            let payload = &data[position + 8..position + 8 + length as usize];
+            let crc = read_u32_be(data, position + 8 + length as usize);
+            let computed_crc = crc32(&data[position + 4..position + 8 + length as usize]);
+            if crc != computed_crc
+            {
+                diagnostics.warning(
+                    Some(offset),
+                    tr_fmt("PNG chunk {} CRC mismatch: stored {}, computed {}", &[&chunk_type, &format!("0x{:08X}", crc), &format!("0x{:08X}", computed_crc)])
+                );
+            }
+
+            let details = image.decode_chunk(&chunk_type, payload, offset, diagnostics);
+            let chunk = PngChunk { offset, chunk_type, length, crc, computed_crc, details };
+            if chunk.is_critical() == true && matches!(chunk.chunk_type.as_str(), "IHDR" | "PLTE" | "IDAT" | "IEND") == false
+            {
+                diagnostics.warning(Some(offset), tr_fmt("Unknown critical PNG chunk {}", &[&chunk.chunk_type]));
+            }
+            position += 12 + length as usize;
+
+            let is_end = chunk.chunk_type == "IEND";
+            image.chunks.push(chunk);
+            if is_end == true
+            {
+                image.has_iend = true;
+                image.size = position as u64;
+                break;
+            }
+        }
+
+        if image.chunks.first().is_none_or(|chunk| chunk.chunk_type != "IHDR")
+        {
+            diagnostics.warning(Some(base_offset + SIGNATURE.len() as u64), tr("PNG file does not start with an IHDR chunk"));
+        }
+        if image.chunks.iter().any(|chunk| chunk.chunk_type == "IDAT") == false
+        {
+            diagnostics.warning(Some(base_offset), tr("PNG file has no image data (IDAT)"));
+        }
+        if image.has_iend == false
+        {
+            diagnostics.warning(Some(base_offset + image.size), tr("PNG file ends without an IEND chunk"));
+        }
+
+        Ok(image)
+    }
+
+    /// Decode the payload of a chunk, returning a short description
+    fn decode_chunk(&mut self, chunk_type: &str, payload: &[u8], offset: u64, diagnostics: &mut Diagnostics) -> Option<String>
+    {
+        match chunk_type
+        {
+            | "IHDR" => match PngHeader::parse(payload)
+            {
+                | Ok(header) =>
+                {
+                    if header.valid_bit_depth() == false
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("PNG bit depth {} is not allowed for color type {}", &[&header.bit_depth, &header.color_type]));
+                    }
+                    self.header = Some(header);
+                    Some(format!("{}x{}, {}", header.width, header.height, header.color_description()))
+                }
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), e);
+                    None
+                }
+            },
+            | "PLTE" =>
+            {
+                self.palette = Some(payload.len() / 3);
+                Some(tr_fmt("{} entries", &[&(payload.len() / 3)]))
+            }
+            | "tEXt" | "zTXt" | "iTXt" => match PngText::parse(chunk_type, payload)
+            {
+                | Ok(text) =>
+                {
+                    let details = format!("{}: \"{}\"", text.keyword, text.text.chars().take(60).collect::<String>());
+                    self.texts.push(text);
+                    Some(details)
+                }
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), tr_fmt("PNG {} chunk: {}", &[&chunk_type, &e]));
+                    None
+                }
+            },
+            | "gAMA" if payload.len() >= 4 => Some(tr_fmt("gamma {}", &[&format!("{:.5}", read_u32_be(payload, 0) as f64 / 100000.0)])),
+            | "sRGB" if payload.is_empty() == false =>
+            {
+                let intent = match payload[0]
+                {
+                    | 0 => "perceptual",
+                    | 1 => "relative colorimetric",
+                    | 2 => "saturation",
+                    | 3 => "absolute colorimetric",
+                    | _ => "unknown"
+                };
+                Some(tr_fmt("rendering intent {}", &[&intent]))
+            }
+            | "iCCP" => payload.iter().position(|&byte| byte == 0).map(|end| tr_fmt("profile \"{}\"", &[&String::from_utf8_lossy(&payload[..end])])),
+            | "pHYs" if payload.len() >= 9 =>
+            {
+                let (x, y) = (read_u32_be(payload, 0), read_u32_be(payload, 4));
+                match payload[8]
+                {
+                    | 1 => Some(tr_fmt("{}x{} pixels per meter ({} dpi)", &[&x, &y, &format!("{:.0}", x as f64 * 0.0254)])),
+                    | _ => Some(tr_fmt("aspect ratio {}:{}", &[&x, &y]))
+                }
+            }
+            | "tIME" if payload.len() >= 7 => Some(format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                u16::from_be_bytes([payload[0], payload[1]]),
+                payload[2],
+                payload[3],
+                payload[4],
+                payload[5],
+                payload[6]
+            )),
+            | "acTL" if payload.len() >= 8 =>
+            {
+                let (frames, plays) = (read_u32_be(payload, 0), read_u32_be(payload, 4));
+                self.animation = Some((frames, plays));
+                match plays
+                {
+                    | 0 => Some(tr_fmt("animated, {} frames, looping forever", &[&frames])),
+                    | plays => Some(tr_fmt("animated, {} frames, {} plays", &[&frames, &plays]))
+                }
+            }
+            | "fcTL" if payload.len() >= 26 => Some(format!(
+                "frame {}x{} at {},{}, delay {}/{} s",
+                read_u32_be(payload, 4),
+                read_u32_be(payload, 8),
+                read_u32_be(payload, 12),
+                read_u32_be(payload, 16),
+                u16::from_be_bytes([payload[20], payload[21]]),
+                u16::from_be_bytes([payload[22], payload[23]])
+            )),
+            | "eXIf" => match Exif::parse(payload)
+            {
+                | Ok(exif) =>
+                {
+                    let details = tr_fmt("EXIF ({}, {} tags)", &[&exif.byte_order(), &exif.entries.len()]);
+                    self.exif = Some(exif);
+                    Some(details)
+                }
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), e);
+                    None
+                }
+            },
+            | _ => None
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "header": self.header.map(|header| serde_json::json!({
+                "width": header.width,
+                "height": header.height,
+                "bit_depth": header.bit_depth,
+                "color_type": header.color_type,
+                "color_type_name": header.color_type_name(),
+                "compression": header.compression,
+                "filter": header.filter,
+                "interlace": header.interlace
+            })),
+            "palette_entries": self.palette,
+            "animation": self.animation.map(|(frames, plays)| serde_json::json!({ "frames": frames, "plays": plays })),
+            "exif": self.exif.as_ref().map(Exif::to_json),
+            "texts": self.texts.iter().map(|text| serde_json::json!({
+                "chunk": text.chunk_type,
+                "keyword": text.keyword,
+                "language": text.language,
+                "text": text.text
+            })).collect::<Vec<_>>(),
+            "chunks": self.chunks.iter().map(|chunk| serde_json::json!({
+                "offset": chunk.offset,
+                "type": chunk.chunk_type,
+                "length": chunk.length,
+                "crc": format!("0x{:08X}", chunk.crc),
+                "crc_valid": chunk.crc == chunk.computed_crc,
+                "details": chunk.details
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for PngImage
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Chunks: {}", &[&self.chunks.len()]))?;
+
+        // Consecutive image data chunks are listed as one run
+        let mut index = 0;
+        while index < self.chunks.len()
+        {
+            let chunk = &self.chunks[index];
+            let run = self.chunks[index..].iter().take_while(|other| other.chunk_type == "IDAT" && chunk.chunk_type == "IDAT").count();
+            if run > 1
+            {
+                let bytes: u64 = self.chunks[index..index + run].iter().map(|chunk| chunk.length as u64).sum();
+                writeln!(f, "  {}", tr_fmt("{}  IDAT  {} chunks, {} bytes of image data", &[&format!("0x{:08X}", chunk.offset), &run, &bytes]))?;
+                index += run;
+                continue;
+            }
+            writeln!(f, "  {}", chunk)?;
+            index += 1;
+        }
+
+        for text in &self.texts
+        {
+            match text.language
+            {
+                | Some(ref language) => writeln!(f, "{}", tr_fmt("Text ({}, {}): {} = \"{}\"", &[&text.chunk_type, &language, &text.keyword, &text.text]))?,
+                | None => writeln!(f, "{}", tr_fmt("Text ({}): {} = \"{}\"", &[&text.chunk_type, &text.keyword, &text.text]))?
+            }
+        }
+        if let Some(ref exif) = self.exif
+        {
+            writeln!(f, "{}", tr("EXIF:"))?;
+            for line in exif.to_string().lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+/// Base lengths of the length codes 257-285
+use crate::i18n::tr;
+
+const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+
+/// Extra bits of the length codes 257-285
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+/// Base distances of the distance codes 0-29
+const DISTANCE_BASE: [u16; 30] =
+    [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+
+/// Extra bits of the distance codes 0-29
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+/// Order in which the code length code lengths are stored in a dynamic block
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Upper bound for decompressed output, protects against decompression bombs in damaged files
+const MAX_OUTPUT_SIZE: usize = 256 * 1024 * 1024;
+
+/// LSB-first bit reader over a DEFLATE stream
+struct BitReader<'a>
+{
+    data:     &'a [u8],
+    position: usize,
+    bit:      u32
+}
+
+impl<'a> BitReader<'a>
+{
+    fn new(data: &'a [u8]) -> Self
+    {
+        BitReader { data, position: 0, bit: 0 }
+    }
+
+    /// Read `count` bits (up to 16), least significant bit first
+    fn bits(&mut self, count: u32) -> Result<u32, String>
+    {
+        let mut value = 0;
+        for index in 0..count
+        {
+            let byte = *self.data.get(self.position).ok_or(tr("Unexpected end of compressed data"))?;
+            value |= (((byte >> self.bit) & 1) as u32) << index;
+            self.bit += 1;
+            if self.bit == 8
+            {
+                self.bit = 0;
+                self.position += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Skip to the next byte boundary
+    fn align(&mut self)
+    {
+        if self.bit != 0
+        {
+            self.bit = 0;
+            self.position += 1;
+        }
+    }
+}
+
+/// Canonical Huffman code given by its code lengths
+struct Huffman
+{
+    /// Number of codes per length
+    counts:  [u16; 16],
+    /// Symbols ordered by code
+    symbols: Vec<u16>
+}
+
+impl Huffman
+{
+    fn new(lengths: &[u8]) -> Self
+    {
+        let mut counts = [0u16; 16];
+        for &length in lengths
+        {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for length in 1..16
+        {
+            offsets[length] = offsets[length - 1] + counts[length - 1];
+        }
+
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate()
+        {
+            if length != 0
+            {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    /// Decode one symbol
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String>
+    {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for length in 1..16
+        {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - first < count
+            {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(tr("Invalid Huffman code in compressed data").to_string())
+    }
+}
+
+/// Decompress a raw DEFLATE stream (RFC 1951)
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String>
+{
+    let mut reader = BitReader::new(data);
+    let mut output = Vec::new();
+
+    loop
+    {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)?
+        {
+            | 0 => inflate_stored(&mut reader, &mut output)?,
+            | 1 =>
+            {
+                let mut lengths = [0u8; 288];
+                lengths[0..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..288].fill(8);
+                inflate_codes(&mut reader, &mut output, &Huffman::new(&lengths), &Huffman::new(&[5u8; 30]))?;
+            }
+            | 2 =>
+            {
+                let (literals, distances) = read_dynamic_codes(&mut reader)?;
+                inflate_codes(&mut reader, &mut output, &literals, &distances)?;
+            }
+            | _ => return Err(tr("Invalid DEFLATE block type").to_string())
+        }
+
+        if last == true
+        {
+            break;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Decompress a zlib stream (RFC 1950) and verify its Adler-32 checksum
+pub fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, String>
+{
+    if data.len() < 6
+    {
+        return Err(tr("zlib stream too short").to_string());
+    }
+    if data[0] & 0x0F != 8 || ((data[0] as u16) << 8 | data[1] as u16).is_multiple_of(31) == false
+    {
+        return Err(tr("Invalid zlib header").to_string());
+    }
+    if data[1] & 0x20 != 0
+    {
+        return Err(tr("zlib preset dictionaries are not supported").to_string());
+    }
+
+    let output = inflate(&data[2..])?;
+
+    // The Adler-32 checksum of the decompressed data follows the compressed data
+    let trailer = &data[data.len() - 4..];
+    let stored = u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    if stored != adler32(&output)
+    {
+        return Err(tr("zlib Adler-32 checksum mismatch").to_string());
+    }
+
+    Ok(output)
+}
+
+/// Adler-32 checksum used by zlib
+fn adler32(data: &[u8]) -> u32
+{
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552)
+    {
+        for &byte in chunk
+        {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Copy a stored (uncompressed) block
+fn inflate_stored(reader: &mut BitReader, output: &mut Vec<u8>) -> Result<(), String>
+{
+    reader.align();
+    let header = reader.data.get(reader.position..reader.position + 4).ok_or(tr("Unexpected end of compressed data"))?;
+    let length = u16::from_le_bytes([header[0], header[1]]);
+    let complement = u16::from_le_bytes([header[2], header[3]]);
+    if length != !complement
+    {
+        return Err(tr("Invalid stored block length").to_string());
+    }
+
+    let start = reader.position + 4;
+    let block = reader.data.get(start..start + length as usize).ok_or(tr("Unexpected end of compressed data"))?;
+    output.extend_from_slice(block);
+    reader.position = start + length as usize;
+    Ok(())
+}
+
+/// Read the literal/length and distance codes of a dynamic block
+fn read_dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), String>
+{
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30
+    {
+        return Err(tr("Invalid dynamic block code counts").to_string());
+    }
+
+    let mut code_length_lengths = [0u8; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(code_length_count)
+    {
+        code_length_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_length_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count
+    {
+        let symbol = code_lengths.decode(reader)?;
+        let (value, repeat) = match symbol
+        {
+            | 0..=15 => (symbol as u8, 1),
+            | 16 => (*lengths.last().ok_or(tr("Repeated code length without a previous length"))?, 3 + reader.bits(2)?),
+            | 17 => (0, 3 + reader.bits(3)?),
+            | _ => (0, 11 + reader.bits(7)?)
+        };
+        if lengths.len() + repeat as usize > literal_count + distance_count
+        {
+            return Err(tr("Too many code lengths in dynamic block").to_string());
+        }
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+
+    if lengths[256] == 0
+    {
+        return Err(tr("Dynamic block has no end-of-block code").to_string());
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+/// Decode the symbols of a Huffman-coded block
+fn inflate_codes(reader: &mut BitReader, output: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> Result<(), String>
+{
+    loop
+    {
+        let symbol = literals.decode(reader)?;
+        match symbol
+        {
+            | 0..=255 => output.push(symbol as u8),
+            | 256 => return Ok(()),
+            | 257..=285 =>
+            {
+                let index = (symbol - 257) as usize;
+                let length = LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+
+                let distance_symbol = distances.decode(reader)? as usize;
+                if distance_symbol >= 30
+                {
+                    return Err(tr("Invalid distance code in compressed data").to_string());
+                }
+                let distance = DISTANCE_BASE[distance_symbol] as usize + reader.bits(DISTANCE_EXTRA[distance_symbol] as u32)? as usize;
+                if distance > output.len()
+                {
+                    return Err(tr("Distance too far back in compressed data").to_string());
+                }
+
+                let start = output.len() - distance;
+                for offset in 0..length
+                {
+                    output.push(output[start + offset]);
+                }
+            }
+            | _ => return Err(tr("Invalid literal/length code in compressed data").to_string())
+        }
+
+        if output.len() > MAX_OUTPUT_SIZE
+        {
+            return Err(tr("Decompressed data exceeds the size limit").to_string());
+        }
+    }
+}
@@ -6,25 +6,59 @@
 
 // Core types and dissector
 pub mod r#box;
+pub mod chapter_track;
 pub mod content;
+pub mod demux;
 pub mod dissector;
+pub mod fragments;
 pub mod itunes_metadata;
+pub mod probe;
+pub mod sample_stats;
+pub mod samples;
+pub mod timestamps;
+pub mod tree;
+pub mod truncation;
+pub mod validation;
+pub mod writer;
 
 // Box type implementations
 pub mod boxes
 {
+    pub mod amr_config;
+    pub mod av1_config;
+    pub mod avc_config;
     pub mod chapter;
     pub mod data_reference;
     pub mod edit_list;
+    pub mod esds;
+    pub mod event_message;
     pub mod file_type;
     pub mod handler;
+    pub mod hevc_config;
+    pub mod image_items;
     pub mod media_header;
     pub mod media_info_header;
     pub mod metadata_keys;
+    pub mod movie_fragment;
     pub mod movie_header;
+    pub mod protection;
+    pub mod sample_entry;
+    pub mod sample_group;
     pub mod sample_table;
+    pub mod segment_index;
     pub mod track_header;
+    pub mod user_extension;
 }
 
 // Re-export commonly used types for convenience
+pub use r#box::IsobmffBox;
 pub use dissector::IsobmffDissector;
+pub use tree::IsobmffTree;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the ISOBMFF dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<IsobmffDissector>(1200);
+}
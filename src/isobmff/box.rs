@@ -1,4 +1,7 @@
-use crate::isobmff::{content::IsobmffContent, itunes_metadata::ItunesMetadata};
+use crate::{
+    hexdump::{HexSpan, SpanBuilder},
+    isobmff::{content::IsobmffContent, itunes_metadata::ItunesMetadata}
+};
 
 /// Represents an ISOBMFF box (also called "atom")
 #[derive(Debug, Clone)]
@@ -12,7 +15,11 @@ pub struct IsobmffBox
     pub children:       Vec<IsobmffBox>,
     pub data:           Vec<u8>,
     pub itunes_content: Option<ItunesMetadata>,
-    pub content:        Option<IsobmffContent>
+    pub content:        Option<IsobmffContent>,
+    /// Key name of a QuickTime metadata item (resolved from the keys box)
+    pub key_name:       Option<String>,
+    /// Bytes of the declared size cut off by the end of the parent or file (`size` only covers the bytes present)
+    pub missing_bytes:  u64
 }
 
 impl IsobmffBox
@@ -22,7 +29,19 @@ pub fn new(offset: u64, box_type: String, size: u64, header_size: u64) -> Self
     {
         let is_container = is_container_type(&box_type);
 
-        Self { offset, box_type, size, header_size, is_container, children: Vec::new(), data: Vec::new(), itunes_content: None, content: None }
+        Self {
+            offset,
+            box_type,
+            size,
+            header_size,
+            is_container,
+            children: Vec::new(),
+            data: Vec::new(),
+            itunes_content: None,
+            content: None,
+            key_name: None,
+            missing_bytes: 0
+        }
     }
 
     /// Get human-readable description of box type
@@ -31,11 +50,196 @@ pub fn get_description(&self) -> &'static str
         get_box_description(&self.box_type)
     }
 
+    /// Get the specification section that defines this box type
+    pub fn spec_reference(&self) -> Option<&'static str>
+    {
+        get_box_spec_reference(&self.box_type)
+    }
+
+    /// Key index of a QuickTime metadata item (ilst child typed by a number instead of a four-character code)
+    pub fn key_index(&self) -> Option<u32>
+    {
+        self.box_type.parse().ok()
+    }
+
+    /// Find a descendant by its path of box types (e.g. "mdia/minf/stbl"), following the first match on each level
+    pub fn find(&self, path: &str) -> Option<&IsobmffBox>
+    {
+        path.split('/').try_fold(self, |current, box_type| current.children.iter().find(|child| child.box_type == box_type))
+    }
+
+    /// Field layout of the box payload, highlighted in hexdumps (empty for boxes without a known layout)
+    pub fn field_spans(&self) -> Vec<HexSpan>
+    {
+        let mut spans = SpanBuilder::new(&self.data);
+        // Times and durations of version 1 full boxes are 64-bit
+        let time_size = if self.data.first() == Some(&1)
+        {
+            8
+        }
+        else
+        {
+            4
+        };
+        let full_box_header = |spans: &mut SpanBuilder| {
+            spans.field(1, "Version");
+            spans.field(3, "Flags");
+        };
+        match self.box_type.as_str()
+        {
+            | "ftyp" | "styp" =>
+            {
+                spans.field(4, "Major brand");
+                spans.field(4, "Minor version");
+                spans.rest("Compatible brands");
+            }
+            | "hdlr" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(4, "Pre-defined");
+                spans.field(4, "Handler type");
+                spans.field(12, "Reserved");
+                spans.rest("Name");
+            }
+            | "mvhd" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(time_size, "Creation time");
+                spans.field(time_size, "Modification time");
+                spans.field(4, "Timescale");
+                spans.field(time_size, "Duration");
+                spans.field(4, "Rate");
+                spans.field(2, "Volume");
+                spans.field(10, "Reserved");
+                spans.field(36, "Matrix");
+                spans.field(24, "Pre-defined");
+                spans.field(4, "Next track ID");
+            }
+            | "tkhd" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(time_size, "Creation time");
+                spans.field(time_size, "Modification time");
+                spans.field(4, "Track ID");
+                spans.field(4, "Reserved");
+                spans.field(time_size, "Duration");
+                spans.field(8, "Reserved");
+                spans.field(2, "Layer");
+                spans.field(2, "Alternate group");
+                spans.field(2, "Volume");
+                spans.field(2, "Reserved");
+                spans.field(36, "Matrix");
+                spans.field(4, "Width");
+                spans.field(4, "Height");
+            }
+            | "mdhd" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(time_size, "Creation time");
+                spans.field(time_size, "Modification time");
+                spans.field(4, "Timescale");
+                spans.field(time_size, "Duration");
+                spans.field(2, "Language");
+                spans.field(2, "Pre-defined");
+            }
+            | "stts" | "ctts" | "stss" | "stsc" | "stco" | "co64" | "elst" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(4, "Entry count");
+                spans.rest("Entries");
+            }
+            | "stsz" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(4, "Sample size");
+                spans.field(4, "Sample count");
+                spans.rest("Entries");
+            }
+            | "sdtp" =>
+            {
+                full_box_header(&mut spans);
+                spans.rest("Sample flags");
+            }
+            | "mfhd" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(4, "Sequence number");
+            }
+            | "tfdt" =>
+            {
+                full_box_header(&mut spans);
+                spans.field(time_size, "Base media decode time");
+            }
+            | "data" =>
+            {
+                spans.field(4, "Type indicator");
+                spans.field(4, "Locale");
+                spans.rest("Value");
+            }
+            | "smhd" | "vmhd" | "nmhd" | "mehd" | "trex" | "tfhd" | "trun" | "sidx" | "emsg" | "elng" | "pitm" | "iloc" | "iinf" | "pssh" =>
+            {
+                full_box_header(&mut spans);
+            }
+            | _ => ()
+        }
+        spans.finish()
+    }
+
+    /// Size declared in the box header, including any bytes missing from a truncated box
+    pub fn declared_size(&self) -> u64
+    {
+        self.size + self.missing_bytes
+    }
+
     /// Get the data size (excluding header)
     pub fn data_size(&self) -> u64
     {
         self.size.saturating_sub(self.header_size)
     }
+
+    /// Structured representation of this box and its children for JSON reports, with the text output lines of the parsed content if `details` is set
+    pub fn to_json(&self, details: bool) -> serde_json::Value
+    {
+        let fields = match (&self.itunes_content, &self.content)
+        {
+            | (Some(itunes_content), _) => itunes_content.to_json(),
+            | (None, Some(content)) => content.to_json(),
+            | (None, None) => serde_json::Value::Null
+        };
+
+        let mut json = serde_json::json!({
+            "type": self.box_type,
+            "description": self.get_description(),
+            "offset": self.offset,
+            "size": self.size,
+            "header_size": self.header_size,
+            "container": self.is_container,
+            "fields": fields,
+            "children": self.children.iter().map(|child| child.to_json(details)).collect::<Vec<_>>()
+        });
+        if details == true
+        {
+            let mut lines: Vec<String> = Vec::new();
+            if let Some(ref itunes_content) = self.itunes_content
+            {
+                lines.extend(itunes_content.to_string().lines().map(str::to_string));
+            }
+            if let Some(ref content) = self.content
+            {
+                lines.extend(content.to_string().lines().map(str::to_string));
+            }
+            json["details"] = serde_json::json!(lines);
+        }
+        if let Some(ref key_name) = self.key_name
+        {
+            json["key"] = serde_json::json!(key_name);
+        }
+        if self.missing_bytes > 0
+        {
+            json["truncated"] = serde_json::json!({ "declared_size": self.declared_size(), "missing_bytes": self.missing_bytes });
+        }
+        json
+    }
 }
 
 /// Check if a box type is a container
@@ -65,7 +269,10 @@ pub fn is_container_type(box_type: &str) -> bool
             "ilst" |
             "trgr" |
             "grpl" |
-            "schi"
+            "schi" |
+            "iinf" |
+            "iprp" |
+            "ipco"
     )
     {
         return true;
@@ -222,6 +429,12 @@ pub fn get_box_description(box_type: &str) -> &'static str
         | "pitm" => "Primary Item",
         | "idat" => "Item Data",
         | "iref" => "Item Reference",
+        | "infe" => "Item Information Entry",
+        | "iprp" => "Item Properties",
+        | "ipco" => "Item Property Container",
+        | "ipma" => "Item Property Association",
+        | "ispe" => "Image Spatial Extents",
+        | "pixi" => "Pixel Information",
 
         // User data box children
         | "cprt" => "Copyright",
@@ -243,6 +456,7 @@ pub fn get_box_description(box_type: &str) -> &'static str
         | "aART" => "Album Artist (iTunes)",
         | "----" => "Custom iTunes Metadata",
         | "ilst" => "iTunes Metadata List",
+        | "keys" => "Metadata Item Keys",
         | "mean" => "iTunes Metadata Mean",
         | "data" => "iTunes Metadata Data",
         | "keyw" => "Keywords",
@@ -312,6 +526,11 @@ pub fn get_box_description(box_type: &str) -> &'static str
         | "encv" => "Encrypted Video Sample Entry",
         | "enca" => "Encrypted Audio Sample Entry",
         | "enct" => "Encrypted Text Sample Entry",
+        | "pssh" => "Protection System Specific Header",
+        | "tenc" => "Track Encryption",
+        | "senc" => "Sample Encryption",
+        | "saiz" => "Sample Auxiliary Information Sizes",
+        | "saio" => "Sample Auxiliary Information Offsets",
 
         // Additional container boxes
         | "rinf" => "Restricted Scheme Information",
@@ -337,6 +556,7 @@ pub fn get_box_description(box_type: &str) -> &'static str
         | "av1C" => "AV1 Configuration",
         | "dac3" => "AC-3 Specific Box",
         | "dec3" => "Enhanced AC-3 Specific Box",
+        | "damr" => "AMR Specific Box",
         | "dvc1" => "VC-1 Configuration",
         | "btrt" => "Bit Rate",
         | "colr" => "Color Information",
@@ -397,3 +617,97 @@ pub fn get_box_description(box_type: &str) -> &'static str
         | _ => "Unknown Box Type"
     }
 }
+
+/// Get the specification section that defines a box type (e.g. "ISO/IEC 14496-12 §8.2.2")
+pub fn get_box_spec_reference(box_type: &str) -> Option<&'static str>
+{
+    let reference = match box_type
+    {
+        // ISO/IEC 14496-12 (ISO base media file format)
+        | "uuid" => "ISO/IEC 14496-12 §4.2",
+        | "ftyp" => "ISO/IEC 14496-12 §4.3",
+        | "mdat" => "ISO/IEC 14496-12 §8.1.1",
+        | "free" | "skip" => "ISO/IEC 14496-12 §8.1.2",
+        | "pdin" => "ISO/IEC 14496-12 §8.1.3",
+        | "moov" => "ISO/IEC 14496-12 §8.2.1",
+        | "mvhd" => "ISO/IEC 14496-12 §8.2.2",
+        | "trak" => "ISO/IEC 14496-12 §8.3.1",
+        | "tkhd" => "ISO/IEC 14496-12 §8.3.2",
+        | "tref" => "ISO/IEC 14496-12 §8.3.3",
+        | "trgr" => "ISO/IEC 14496-12 §8.3.4",
+        | "mdia" => "ISO/IEC 14496-12 §8.4.1",
+        | "mdhd" => "ISO/IEC 14496-12 §8.4.2",
+        | "hdlr" => "ISO/IEC 14496-12 §8.4.3",
+        | "minf" => "ISO/IEC 14496-12 §8.4.4",
+        | "nmhd" => "ISO/IEC 14496-12 §8.4.5.2",
+        | "stbl" => "ISO/IEC 14496-12 §8.5.1",
+        | "stsd" | "btrt" => "ISO/IEC 14496-12 §8.5.2",
+        | "stdp" => "ISO/IEC 14496-12 §8.5.3",
+        | "stts" => "ISO/IEC 14496-12 §8.6.1.2",
+        | "ctts" => "ISO/IEC 14496-12 §8.6.1.3",
+        | "stss" => "ISO/IEC 14496-12 §8.6.2",
+        | "stsh" => "ISO/IEC 14496-12 §8.6.3",
+        | "sdtp" => "ISO/IEC 14496-12 §8.6.4",
+        | "edts" => "ISO/IEC 14496-12 §8.6.5",
+        | "elst" => "ISO/IEC 14496-12 §8.6.6",
+        | "dinf" => "ISO/IEC 14496-12 §8.7.1",
+        | "dref" | "url " | "urn " => "ISO/IEC 14496-12 §8.7.2",
+        | "stsz" | "stz2" => "ISO/IEC 14496-12 §8.7.3",
+        | "stsc" => "ISO/IEC 14496-12 §8.7.4",
+        | "stco" | "co64" => "ISO/IEC 14496-12 §8.7.5",
+        | "padb" => "ISO/IEC 14496-12 §8.7.6",
+        | "subs" => "ISO/IEC 14496-12 §8.7.7",
+        | "mvex" => "ISO/IEC 14496-12 §8.8.1",
+        | "mehd" => "ISO/IEC 14496-12 §8.8.2",
+        | "trex" => "ISO/IEC 14496-12 §8.8.3",
+        | "moof" => "ISO/IEC 14496-12 §8.8.4",
+        | "mfhd" => "ISO/IEC 14496-12 §8.8.5",
+        | "traf" => "ISO/IEC 14496-12 §8.8.6",
+        | "tfhd" => "ISO/IEC 14496-12 §8.8.7",
+        | "trun" => "ISO/IEC 14496-12 §8.8.8",
+        | "mfra" => "ISO/IEC 14496-12 §8.8.9",
+        | "tfra" => "ISO/IEC 14496-12 §8.8.10",
+        | "mfro" => "ISO/IEC 14496-12 §8.8.11",
+        | "tfdt" => "ISO/IEC 14496-12 §8.8.12",
+        | "leva" => "ISO/IEC 14496-12 §8.8.13",
+        | "sbgp" => "ISO/IEC 14496-12 §8.9.2",
+        | "sgpd" => "ISO/IEC 14496-12 §8.9.3",
+        | "udta" => "ISO/IEC 14496-12 §8.10.1",
+        | "cprt" => "ISO/IEC 14496-12 §8.10.2",
+        | "meta" => "ISO/IEC 14496-12 §8.11.1",
+        | "xml " | "bxml" => "ISO/IEC 14496-12 §8.11.2",
+        | "iloc" => "ISO/IEC 14496-12 §8.11.3",
+        | "pitm" => "ISO/IEC 14496-12 §8.11.4",
+        | "ipro" => "ISO/IEC 14496-12 §8.11.5",
+        | "iinf" | "infe" => "ISO/IEC 14496-12 §8.11.6",
+        | "idat" => "ISO/IEC 14496-12 §8.11.11",
+        | "iref" => "ISO/IEC 14496-12 §8.11.12",
+        | "sinf" => "ISO/IEC 14496-12 §8.12.1",
+        | "frma" => "ISO/IEC 14496-12 §8.12.2",
+        | "schm" => "ISO/IEC 14496-12 §8.12.5",
+        | "schi" => "ISO/IEC 14496-12 §8.12.6",
+        | "saiz" => "ISO/IEC 14496-12 §8.7.8",
+        | "saio" => "ISO/IEC 14496-12 §8.7.9",
+        | "pssh" => "ISO/IEC 23001-7 §8.1",
+        | "tenc" => "ISO/IEC 23001-7 §8.2",
+        | "senc" => "ISO/IEC 23001-7 §7.2",
+        | "styp" => "ISO/IEC 14496-12 §8.16.2",
+        | "sidx" => "ISO/IEC 14496-12 §8.16.3",
+        | "ssix" => "ISO/IEC 14496-12 §8.16.4",
+        | "prft" => "ISO/IEC 14496-12 §8.16.5",
+        | "vmhd" => "ISO/IEC 14496-12 §12.1.2",
+        | "pasp" | "clap" => "ISO/IEC 14496-12 §12.1.4",
+        | "colr" => "ISO/IEC 14496-12 §12.1.5",
+        | "smhd" => "ISO/IEC 14496-12 §12.2.2",
+        | "hmhd" => "ISO/IEC 14496-12 §12.4.2",
+        | "sthd" => "ISO/IEC 14496-12 §12.6.2",
+
+        // ISO/IEC 23008-12 (HEIF) item properties
+        | "iprp" | "ipco" | "ipma" => "ISO/IEC 23008-12 §9.3",
+        | "ispe" => "ISO/IEC 23008-12 §6.5.3",
+        | "pixi" => "ISO/IEC 23008-12 §6.5.6",
+
+        | _ => return None
+    };
+    Some(reference)
+}
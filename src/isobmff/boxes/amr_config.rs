@@ -0,0 +1,74 @@
+use std::fmt;
+
+use crate::{
+    audio::amr::frame_type_name,
+    i18n::{tr, tr_fmt}
+};
+
+/// AMR Specific Box (damr) of samr and sawb sample entries (3GPP TS 26.244)
+#[derive(Debug, Clone)]
+pub struct AmrSpecificBox
+{
+    /// Four character code of the encoder vendor
+    pub vendor:             String,
+    pub decoder_version:    u8,
+    /// One bit per codec mode (frame type) present in the stream
+    pub mode_set:           u16,
+    /// Frames between mode changes, 0 if not restricted
+    pub mode_change_period: u8,
+    pub frames_per_sample:  u8,
+    /// Set for sawb entries (AMR-WB modes)
+    pub wideband:           bool
+}
+
+impl AmrSpecificBox
+{
+    /// Parse damr (AMR Specific) box; `wideband` selects the mode names of sawb entries
+    pub fn parse(data: &[u8], wideband: bool) -> Result<Self, String>
+    {
+        if data.len() < 9
+        {
+            return Err(tr("damr box too short").to_string());
+        }
+
+        Ok(AmrSpecificBox {
+            vendor: String::from_utf8_lossy(&data[0..4]).to_string(),
+            decoder_version: data[4],
+            mode_set: u16::from_be_bytes([data[5], data[6]]),
+            mode_change_period: data[7],
+            frames_per_sample: data[8],
+            wideband
+        })
+    }
+
+    /// Names of the codec modes in the mode set
+    pub fn modes(&self) -> Vec<&'static str>
+    {
+        (0..16).filter(|mode| self.mode_set & (1 << mode) != 0).map(|mode| frame_type_name(self.wideband, mode)).collect()
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "vendor": self.vendor,
+            "decoder_version": self.decoder_version,
+            "mode_set": self.mode_set,
+            "mode_change_period": self.mode_change_period,
+            "frames_per_sample": self.frames_per_sample,
+            "wideband": self.wideband
+        })
+    }
+}
+
+impl fmt::Display for AmrSpecificBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Vendor: '{}'", &[&self.vendor]))?;
+        writeln!(f, "{}", tr_fmt("Decoder Version: {}", &[&self.decoder_version]))?;
+        writeln!(f, "{}", tr_fmt("Mode Set: {} ({})", &[&format!("0x{:04X}", self.mode_set), &self.modes().join(", ")]))?;
+        writeln!(f, "{}", tr_fmt("Mode Change Period: {}", &[&self.mode_change_period]))?;
+        writeln!(f, "{}", tr_fmt("Frames per Sample: {}", &[&self.frames_per_sample]))
+    }
+}
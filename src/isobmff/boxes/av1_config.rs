@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// AV1 Codec Configuration Record (av1C)
+#[derive(Debug, Clone)]
+pub struct Av1ConfigurationBox
+{
+    pub version:                    u8,
+    /// seq_profile (0 = Main, 1 = High, 2 = Professional)
+    pub seq_profile:                u8,
+    pub seq_level_idx:              u8,
+    /// seq_tier_0 (false = Main tier, true = High tier)
+    pub seq_tier:                   bool,
+    pub high_bitdepth:              bool,
+    pub twelve_bit:                 bool,
+    pub monochrome:                 bool,
+    pub chroma_subsampling_x:       bool,
+    pub chroma_subsampling_y:       bool,
+    pub chroma_sample_position:     u8,
+    /// Initial presentation delay in frames, if present
+    pub initial_presentation_delay: Option<u8>,
+    /// Size of the configOBUs (usually a sequence header OBU)
+    pub config_obus_size:           usize
+}
+
+impl Av1ConfigurationBox
+{
+    /// Parse av1C (AV1 Codec Configuration Record) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("av1C box too short").to_string());
+        }
+        if data[0] & 0x80 == 0
+        {
+            return Err(tr("av1C box without marker bit").to_string());
+        }
+
+        Ok(Av1ConfigurationBox {
+            version:                    data[0] & 0x7F,
+            seq_profile:                data[1] >> 5,
+            seq_level_idx:              data[1] & 0x1F,
+            seq_tier:                   data[2] & 0x80 != 0,
+            high_bitdepth:              data[2] & 0x40 != 0,
+            twelve_bit:                 data[2] & 0x20 != 0,
+            monochrome:                 data[2] & 0x10 != 0,
+            chroma_subsampling_x:       data[2] & 0x08 != 0,
+            chroma_subsampling_y:       data[2] & 0x04 != 0,
+            chroma_sample_position:     data[2] & 0x03,
+            initial_presentation_delay: (data[3] & 0x10 != 0).then_some((data[3] & 0x0F) + 1),
+            config_obus_size:           data.len() - 4
+        })
+    }
+
+    /// Profile name for the seq_profile
+    pub fn profile_name(&self) -> &'static str
+    {
+        match self.seq_profile
+        {
+            | 0 => "Main",
+            | 1 => "High",
+            | 2 => "Professional",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Bit depth from the high_bitdepth and twelve_bit flags
+    pub fn bit_depth(&self) -> u8
+    {
+        match (self.high_bitdepth, self.twelve_bit)
+        {
+            | (true, true) => 12,
+            | (true, false) => 10,
+            | _ => 8
+        }
+    }
+
+    /// Chroma format from the monochrome and subsampling flags
+    pub fn chroma_format_name(&self) -> &'static str
+    {
+        match (self.monochrome, self.chroma_subsampling_x, self.chroma_subsampling_y)
+        {
+            | (true, ..) => "4:0:0 (monochrome)",
+            | (false, true, true) => "4:2:0",
+            | (false, true, false) => "4:2:2",
+            | _ => "4:4:4"
+        }
+    }
+
+    /// Level as "major.minor" (seq_level_idx 31 means no level restriction)
+    pub fn level_name(&self) -> String
+    {
+        if self.seq_level_idx == 31
+        {
+            return "unrestricted".to_string();
+        }
+        format!("{}.{}", 2 + (self.seq_level_idx >> 2), self.seq_level_idx & 0x03)
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "seq_profile": self.seq_profile,
+            "seq_level_idx": self.seq_level_idx,
+            "seq_tier": self.seq_tier,
+            "high_bitdepth": self.high_bitdepth,
+            "twelve_bit": self.twelve_bit,
+            "monochrome": self.monochrome,
+            "chroma_subsampling_x": self.chroma_subsampling_x,
+            "chroma_subsampling_y": self.chroma_subsampling_y,
+            "chroma_sample_position": self.chroma_sample_position,
+            "initial_presentation_delay": self.initial_presentation_delay,
+            "config_obus_size": self.config_obus_size
+        })
+    }
+}
+
+impl fmt::Display for Av1ConfigurationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let tier = if self.seq_tier == true
+        {
+            "High"
+        }
+        else
+        {
+            "Main"
+        };
+
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Profile: {} ({})", &[&self.seq_profile, &self.profile_name()]))?;
+        writeln!(f, "{}", tr_fmt("Level: {} ({} tier)", &[&self.level_name(), &tier]))?;
+        writeln!(f, "{}", tr_fmt("Chroma Format: {}, sample position {}", &[&self.chroma_format_name(), &self.chroma_sample_position]))?;
+        writeln!(f, "{}", tr_fmt("Bit Depth: {}", &[&self.bit_depth()]))?;
+        if let Some(delay) = self.initial_presentation_delay
+        {
+            writeln!(f, "{}", tr_fmt("Initial Presentation Delay: {} frames", &[&delay]))?;
+        }
+        if self.config_obus_size > 0
+        {
+            writeln!(f, "{}", tr_fmt("Config OBUs: {} bytes", &[&self.config_obus_size]))?;
+        }
+        Ok(())
+    }
+}
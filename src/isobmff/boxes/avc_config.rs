@@ -0,0 +1,180 @@
+use std::fmt;
+
+use crate::{
+    hashes::to_hex,
+    i18n::{tr, tr_fmt}
+};
+
+/// Parameter set (SPS, PPS, VPS, ...) stored in a decoder configuration record
+#[derive(Debug, Clone)]
+pub struct ParameterSet
+{
+    /// NAL unit type (e.g. 7 = AVC SPS, 33 = HEVC SPS)
+    pub nal_unit_type: u8,
+    pub data:          Vec<u8>
+}
+
+impl ParameterSet
+{
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "nal_unit_type": self.nal_unit_type, "data": to_hex(&self.data) })
+    }
+}
+
+/// Read `count` parameter sets with 16-bit length prefixes at `position`, returning them and the position after the last
+pub fn read_parameter_sets(data: &[u8], mut position: usize, count: usize) -> (Vec<ParameterSet>, usize)
+{
+    let mut parameter_sets = Vec::new();
+    for _ in 0..count
+    {
+        if position + 2 > data.len()
+        {
+            break;
+        }
+        let length = u16::from_be_bytes([data[position], data[position + 1]]) as usize;
+        position += 2;
+        if position + length > data.len()
+        {
+            break;
+        }
+        let nal = &data[position..position + length];
+        parameter_sets.push(ParameterSet { nal_unit_type: nal.first().map_or(0, |header| header & 0x1F), data: nal.to_vec() });
+        position += length;
+    }
+    (parameter_sets, position)
+}
+
+/// Chroma format name for a chroma_format_idc value
+pub fn chroma_format_name(chroma_format: u8) -> &'static str
+{
+    match chroma_format
+    {
+        | 0 => "4:0:0 (monochrome)",
+        | 1 => "4:2:0",
+        | 2 => "4:2:2",
+        | _ => "4:4:4"
+    }
+}
+
+/// AVC Decoder Configuration Record (avcC)
+#[derive(Debug, Clone)]
+pub struct AvcConfigurationBox
+{
+    pub configuration_version:   u8,
+    /// profile_idc (e.g. 66 = Baseline, 77 = Main, 100 = High)
+    pub profile:                 u8,
+    pub profile_compatibility:   u8,
+    /// level_idc (e.g. 31 = level 3.1)
+    pub level:                   u8,
+    /// Size of the NAL unit length fields in the samples
+    pub nal_length_size:         u8,
+    /// Chroma format (not stored for Baseline, Main and Extended, 4:2:0 then)
+    pub chroma_format:           u8,
+    pub bit_depth_luma:          u8,
+    pub bit_depth_chroma:        u8,
+    pub sequence_parameter_sets: Vec<ParameterSet>,
+    pub picture_parameter_sets:  Vec<ParameterSet>
+}
+
+impl AvcConfigurationBox
+{
+    /// Parse avcC (AVC Decoder Configuration Record) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 7
+        {
+            return Err(tr("avcC box too short").to_string());
+        }
+
+        let nal_length_size = (data[4] & 0x03) + 1;
+        let (sequence_parameter_sets, position) = read_parameter_sets(data, 6, (data[5] & 0x1F) as usize);
+        let pps_count = data.get(position).copied().unwrap_or(0) as usize;
+        let (picture_parameter_sets, position) = read_parameter_sets(data, position + 1, pps_count);
+
+        let mut config = AvcConfigurationBox {
+            configuration_version: data[0],
+            profile: data[1],
+            profile_compatibility: data[2],
+            level: data[3],
+            nal_length_size,
+            chroma_format: 1,
+            bit_depth_luma: 8,
+            bit_depth_chroma: 8,
+            sequence_parameter_sets,
+            picture_parameter_sets
+        };
+
+        // Profiles other than Baseline, Main and Extended append chroma format and bit depths
+        if matches!(config.profile, 66 | 77 | 88) == false && position + 3 <= data.len()
+        {
+            config.chroma_format = data[position] & 0x03;
+            config.bit_depth_luma = (data[position + 1] & 0x07) + 8;
+            config.bit_depth_chroma = (data[position + 2] & 0x07) + 8;
+        }
+
+        Ok(config)
+    }
+
+    /// Profile name for the profile_idc
+    pub fn profile_name(&self) -> &'static str
+    {
+        match self.profile
+        {
+            | 66 => "Baseline",
+            | 77 => "Main",
+            | 88 => "Extended",
+            | 100 => "High",
+            | 110 => "High 10",
+            | 122 => "High 4:2:2",
+            | 244 => "High 4:4:4 Predictive",
+            | 44 => "CAVLC 4:4:4 Intra",
+            | 83 => "Scalable Baseline",
+            | 86 => "Scalable High",
+            | 118 => "Multiview High",
+            | 128 => "Stereo High",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "configuration_version": self.configuration_version,
+            "profile": self.profile,
+            "profile_compatibility": self.profile_compatibility,
+            "level": self.level,
+            "nal_length_size": self.nal_length_size,
+            "chroma_format": self.chroma_format,
+            "bit_depth_luma": self.bit_depth_luma,
+            "bit_depth_chroma": self.bit_depth_chroma,
+            "sequence_parameter_sets": self.sequence_parameter_sets.iter().map(ParameterSet::to_json).collect::<Vec<_>>(),
+            "picture_parameter_sets": self.picture_parameter_sets.iter().map(ParameterSet::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for AvcConfigurationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Configuration Version: {}", &[&self.configuration_version]))?;
+        writeln!(f, "{}", tr_fmt("Profile: {} ({})", &[&self.profile, &self.profile_name()]))?;
+        writeln!(f, "{}", tr_fmt("Profile Compatibility: {}", &[&format!("0x{:02X}", self.profile_compatibility)]))?;
+        writeln!(f, "{}", tr_fmt("Level: {}.{}", &[&(self.level / 10), &(self.level % 10)]))?;
+        writeln!(f, "{}", tr_fmt("Chroma Format: {}", &[&chroma_format_name(self.chroma_format)]))?;
+        writeln!(f, "{}", tr_fmt("Bit Depth: {} (luma), {} (chroma)", &[&self.bit_depth_luma, &self.bit_depth_chroma]))?;
+        writeln!(f, "{}", tr_fmt("NAL Length Size: {} bytes", &[&self.nal_length_size]))?;
+        for (index, sps) in self.sequence_parameter_sets.iter().enumerate()
+        {
+            writeln!(f, "{}", tr_fmt("SPS {}: {} bytes", &[&(index + 1), &sps.data.len()]))?;
+        }
+        for (index, pps) in self.picture_parameter_sets.iter().enumerate()
+        {
+            writeln!(f, "{}", tr_fmt("PPS {}: {} bytes", &[&(index + 1), &pps.data.len()]))?;
+        }
+        Ok(())
+    }
+}
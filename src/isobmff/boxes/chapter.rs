@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// Chapter Track Reference Box (chap)
 #[derive(Debug, Clone)]
 pub struct ChapterBox
@@ -14,7 +16,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("chap box too short".to_string());
+            return Err(tr("chap box too short").to_string());
         }
 
         let mut track_ids = Vec::new();
@@ -28,13 +30,19 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(ChapterBox { track_ids })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "track_ids": self.track_ids })
+    }
 }
 
 impl fmt::Display for ChapterBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Chapter Track IDs: {:?}", self.track_ids)?;
+        writeln!(f, "{}", tr_fmt("Chapter Track IDs: {}", &[&format!("{:?}", self.track_ids)]))?;
         Ok(())
     }
 }
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// Data Reference Box (dref)
 #[derive(Debug, Clone)]
 pub struct DataReferenceBox
@@ -15,7 +17,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("dref box too short".to_string());
+            return Err(tr("dref box too short").to_string());
         }
 
         let version = data[0];
@@ -23,14 +25,20 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(DataReferenceBox { version, entry_count })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "entry_count": self.entry_count })
+    }
 }
 
 impl fmt::Display for DataReferenceBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {}", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.entry_count]))?;
         Ok(())
     }
 }
@@ -51,18 +59,14 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("url box too short".to_string());
+            return Err(tr("url box too short").to_string());
         }
 
         let version = data[0];
         let flags = u32::from_be_bytes([0, data[1], data[2], data[3]]);
 
         // If flag 0x000001 is set, data is in the same file (no URL)
-        let location = if (flags & 0x000001) != 0
-        {
-            "(data in same file)".to_string()
-        }
-        else if data.len() > 4
+        let location = if (flags & 0x000001) == 0 && data.len() > 4
         {
             String::from_utf8_lossy(&data[4..]).trim_end_matches('\0').to_string()
         }
@@ -73,17 +77,27 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(UrlEntryBox { version, flags, location })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "flags": self.flags, "location": self.location })
+    }
 }
 
 impl fmt::Display for UrlEntryBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Flags: 0x{:06X}", self.flags)?;
-        if self.location.is_empty() == false
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Flags: {}", &[&format!("0x{:06X}", self.flags)]))?;
+        if self.flags & 0x000001 != 0
+        {
+            writeln!(f, "{}", tr_fmt("Location: {}", &[&tr("(data in same file)")]))?;
+        }
+        else if self.location.is_empty() == false
         {
-            writeln!(f, "Location: {}", self.location)?;
+            writeln!(f, "{}", tr_fmt("Location: {}", &[&self.location]))?;
         }
         Ok(())
     }
@@ -106,7 +120,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("urn box too short".to_string());
+            return Err(tr("urn box too short").to_string());
         }
 
         let version = data[0];
@@ -131,21 +145,27 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(UrnEntryBox { version, flags, name, location })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "flags": self.flags, "name": self.name, "location": self.location })
+    }
 }
 
 impl fmt::Display for UrnEntryBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Flags: 0x{:06X}", self.flags)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Flags: {}", &[&format!("0x{:06X}", self.flags)]))?;
         if self.name.is_empty() == false
         {
-            writeln!(f, "Name: {}", self.name)?;
+            writeln!(f, "{}", tr_fmt("Name: {}", &[&self.name]))?;
         }
         if self.location.is_empty() == false
         {
-            writeln!(f, "Location: {}", self.location)?;
+            writeln!(f, "{}", tr_fmt("Location: {}", &[&self.location]))?;
         }
         Ok(())
     }
@@ -1,11 +1,43 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
+/// Entry of an Edit List Box
+#[derive(Debug, Clone)]
+pub struct EditListEntry
+{
+    /// Duration of the edit in movie timescale units
+    pub segment_duration: u64,
+    /// Start time within the media in media timescale units, -1 for an empty edit
+    pub media_time:       i64,
+    /// Playback rate (16.16 fixed point), 0 for a dwell edit
+    pub media_rate:       f64
+}
+
+impl EditListEntry
+{
+    /// Empty edit: no media is played for the duration (e.g. an initial delay)
+    pub fn is_empty_edit(&self) -> bool
+    {
+        self.media_time == -1
+    }
+
+    /// Dwell edit: the media at `media_time` is held for the duration
+    pub fn is_dwell_edit(&self) -> bool
+    {
+        self.media_rate == 0.0
+    }
+}
+
 /// Edit List Box (elst)
 #[derive(Debug, Clone)]
 pub struct EditListBox
 {
-    pub version:     u8,
-    pub entry_count: u32
+    pub version:         u8,
+    pub entry_count:     u32,
+    pub entries:         Vec<EditListEntry>,
+    /// Timescale of the movie header, set once the whole tree is parsed (segment durations are in movie units)
+    pub movie_timescale: Option<u32>
 }
 
 impl EditListBox
@@ -15,13 +47,71 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("elst box too short".to_string());
+            return Err(tr("elst box too short").to_string());
         }
 
         let version = data[0];
         let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
 
-        Ok(EditListBox { version, entry_count })
+        // Version 1 uses 64-bit segment duration and media time
+        let entry_size = if version == 1
+        {
+            20
+        }
+        else
+        {
+            12
+        };
+
+        let mut entries = Vec::new();
+        for entry in data[8..].chunks_exact(entry_size).take(entry_count as usize)
+        {
+            let (segment_duration, media_time, rate) = if version == 1
+            {
+                (
+                    u64::from_be_bytes([entry[0], entry[1], entry[2], entry[3], entry[4], entry[5], entry[6], entry[7]]),
+                    i64::from_be_bytes([entry[8], entry[9], entry[10], entry[11], entry[12], entry[13], entry[14], entry[15]]),
+                    &entry[16..20]
+                )
+            }
+            else
+            {
+                (
+                    u32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]) as u64,
+                    i32::from_be_bytes([entry[4], entry[5], entry[6], entry[7]]) as i64,
+                    &entry[8..12]
+                )
+            };
+
+            // media_rate_integer (int16) and media_rate_fraction (int16)
+            let rate_integer = i16::from_be_bytes([rate[0], rate[1]]);
+            let rate_fraction = i16::from_be_bytes([rate[2], rate[3]]);
+            let media_rate = rate_integer as f64 + rate_fraction as f64 / 65536.0;
+
+            entries.push(EditListEntry { segment_duration, media_time, media_rate });
+        }
+
+        Ok(EditListBox { version, entry_count, entries, movie_timescale: None })
+    }
+
+    /// Segment duration in seconds, if the movie timescale is known
+    pub fn duration_seconds(&self, entry: &EditListEntry) -> Option<f64>
+    {
+        self.movie_timescale.filter(|&timescale| timescale > 0).map(|timescale| entry.segment_duration as f64 / timescale as f64)
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "segment_duration": entry.segment_duration,
+                "media_time": entry.media_time,
+                "media_rate": entry.media_rate
+            })).collect::<Vec<_>>()
+        })
     }
 }
 
@@ -29,8 +119,39 @@ impl fmt::Display for EditListBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {} edit list entries", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} edit list entries", &[&self.entry_count]))?;
+
+        for (index, entry) in self.entries.iter().enumerate()
+        {
+            let duration = match self.duration_seconds(entry)
+            {
+                | Some(seconds) => format!("{} ({:.3} s)", entry.segment_duration, seconds),
+                | None => entry.segment_duration.to_string()
+            };
+            let kind = if entry.is_empty_edit() == true
+            {
+                " [empty edit]"
+            }
+            else if entry.is_dwell_edit() == true
+            {
+                " [dwell edit]"
+            }
+            else
+            {
+                ""
+            };
+            writeln!(
+                f,
+                "{}",
+                tr_fmt("Entry {}: Segment Duration: {}, Media Time: {}, Media Rate: {}{}", &[&(index + 1), &duration, &entry.media_time, &entry.media_rate, &kind])
+            )?;
+        }
+
+        if self.entries.len() < self.entry_count as usize
+        {
+            writeln!(f, "{}", tr_fmt("Parsed Entries: {} (box truncated)", &[&self.entries.len()]))?;
+        }
         Ok(())
     }
 }
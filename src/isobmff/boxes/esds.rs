@@ -0,0 +1,412 @@
+use std::fmt;
+
+use crate::{
+    hashes::to_hex,
+    i18n::{tr, tr_fmt}
+};
+
+/// ES_Descriptor tag (ISO/IEC 14496-1)
+const ES_DESCRIPTOR_TAG: u8 = 0x03;
+/// DecoderConfigDescriptor tag
+const DECODER_CONFIG_DESCRIPTOR_TAG: u8 = 0x04;
+/// DecoderSpecificInfo tag
+const DECODER_SPECIFIC_INFO_TAG: u8 = 0x05;
+
+/// Object type indication for MPEG-4 audio (AudioSpecificConfig follows in the DecoderSpecificInfo)
+const OBJECT_TYPE_MPEG4_AUDIO: u8 = 0x40;
+
+/// Size of an ADTS header without CRC
+pub const ADTS_HEADER_SIZE: usize = 7;
+
+/// Sampling frequencies by sampling frequency index (ISO/IEC 14496-3)
+pub const SAMPLING_FREQUENCIES: [u32; 13] = [96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350];
+
+/// MSB-first bit reader for the AudioSpecificConfig
+struct BitReader<'a>
+{
+    data:     &'a [u8],
+    position: usize
+}
+
+impl<'a> BitReader<'a>
+{
+    fn new(data: &'a [u8]) -> Self
+    {
+        Self { data, position: 0 }
+    }
+
+    /// Read `count` bits (at most 32), None at the end of the data
+    fn read(&mut self, count: usize) -> Option<u32>
+    {
+        let mut value = 0u32;
+        for _ in 0..count
+        {
+            let byte = *self.data.get(self.position / 8)?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    /// Audio object type, with the escape value 31 for types above 31
+    fn read_audio_object_type(&mut self) -> Option<u8>
+    {
+        let audio_object_type = self.read(5)? as u8;
+        if audio_object_type == 31
+        {
+            return Some(32 + self.read(6)? as u8);
+        }
+        Some(audio_object_type)
+    }
+
+    /// Sampling frequency from its index, or the explicit 24-bit value for index 15
+    fn read_sampling_frequency(&mut self) -> Option<u32>
+    {
+        let index = self.read(4)? as usize;
+        if index == 15
+        {
+            return self.read(24);
+        }
+        Some(SAMPLING_FREQUENCIES.get(index).copied().unwrap_or(0))
+    }
+}
+
+/// AudioSpecificConfig of MPEG-4 audio (the DecoderSpecificInfo of AAC streams)
+#[derive(Debug, Clone)]
+pub struct AudioSpecificConfig
+{
+    /// Audio object type of the core codec (e.g. 2 = AAC LC)
+    pub audio_object_type:     u8,
+    pub sample_rate:           u32,
+    /// Channel configuration (0 = defined in the program config element, 1-7 = fixed layouts)
+    pub channel_configuration: u8,
+    /// Output sample rate when spectral band replication is signaled explicitly (HE-AAC)
+    pub extension_sample_rate: Option<u32>,
+    /// Spectral band replication (HE-AAC) signaled
+    pub sbr:                   bool,
+    /// Parametric stereo (HE-AAC v2) signaled
+    pub ps:                    bool
+}
+
+impl AudioSpecificConfig
+{
+    /// Parse the leading fields of an AudioSpecificConfig
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let mut reader = BitReader::new(data);
+        let too_short = || "AudioSpecificConfig too short".to_string();
+
+        let mut audio_object_type = reader.read_audio_object_type().ok_or_else(too_short)?;
+        let sample_rate = reader.read_sampling_frequency().ok_or_else(too_short)?;
+        let channel_configuration = reader.read(4).ok_or_else(too_short)? as u8;
+
+        // Explicit hierarchical signaling: SBR (5) or PS (29) followed by the core object type
+        let mut extension_sample_rate = None;
+        let mut sbr = false;
+        let mut ps = false;
+        if audio_object_type == 5 || audio_object_type == 29
+        {
+            sbr = true;
+            ps = audio_object_type == 29;
+            extension_sample_rate = reader.read_sampling_frequency();
+            audio_object_type = reader.read_audio_object_type().ok_or_else(too_short)?;
+        }
+
+        Ok(Self { audio_object_type, sample_rate, channel_configuration, extension_sample_rate, sbr, ps })
+    }
+
+    /// Name of the audio object type
+    pub fn audio_object_type_name(&self) -> &'static str
+    {
+        match self.audio_object_type
+        {
+            | 1 => "AAC Main",
+            | 2 => "AAC LC",
+            | 3 => "AAC SSR",
+            | 4 => "AAC LTP",
+            | 5 => "SBR",
+            | 6 => "AAC Scalable",
+            | 17 => "ER AAC LC",
+            | 19 => "ER AAC LTP",
+            | 20 => "ER AAC Scalable",
+            | 23 => "ER AAC LD",
+            | 29 => "PS",
+            | 32 => "MPEG Layer-1",
+            | 33 => "MPEG Layer-2",
+            | 34 => "MPEG Layer-3",
+            | 36 => "ALS",
+            | 39 => "ER AAC ELD",
+            | 42 => "USAC",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Profile name as used by encoders and players (e.g. "HE-AAC v2")
+    pub fn profile_name(&self) -> &'static str
+    {
+        if self.ps == true
+        {
+            "HE-AAC v2"
+        }
+        else if self.sbr == true
+        {
+            "HE-AAC"
+        }
+        else
+        {
+            self.audio_object_type_name()
+        }
+    }
+
+    /// Channel count of a fixed channel configuration
+    pub fn channels(&self) -> Option<u16>
+    {
+        match self.channel_configuration
+        {
+            | 1..=6 => Some(self.channel_configuration as u16),
+            | 7 => Some(8),
+            | _ => None
+        }
+    }
+
+    /// ADTS header (ISO/IEC 13818-7, without CRC) of a raw AAC frame of `payload_size` bytes
+    ///
+    /// ADTS only carries AAC Main, LC, SSR and LTP at a tabulated sampling frequency; HE-AAC streams
+    /// are written with their core object type and rate, leaving SBR and PS to implicit signaling.
+    pub fn adts_header(&self, payload_size: usize) -> Option<[u8; ADTS_HEADER_SIZE]>
+    {
+        let frame_length = payload_size + ADTS_HEADER_SIZE;
+        let profile = self.audio_object_type.checked_sub(1).filter(|&profile| profile < 4)?;
+        let frequency_index = SAMPLING_FREQUENCIES.iter().position(|&frequency| frequency == self.sample_rate)? as u8;
+        if frame_length >= 1 << 13 || self.channel_configuration > 7
+        {
+            return None;
+        }
+
+        // Syncword, MPEG-4, layer 0 and no CRC; buffer fullness 0x7FF signals a variable bit rate
+        Some([
+            0xFF,
+            0xF1,
+            (profile << 6) | (frequency_index << 2) | (self.channel_configuration >> 2),
+            ((self.channel_configuration & 0x03) << 6) | (frame_length >> 11) as u8,
+            (frame_length >> 3) as u8,
+            ((frame_length & 0x07) << 5) as u8 | 0x1F,
+            0xFC
+        ])
+    }
+}
+
+/// Elementary Stream Descriptor Box (esds)
+#[derive(Debug, Clone)]
+pub struct ElementaryStreamDescriptorBox
+{
+    pub version:                u8,
+    pub es_id:                  u16,
+    /// Object type indication of the DecoderConfigDescriptor (e.g. 0x40 = MPEG-4 audio)
+    pub object_type_indication: u8,
+    /// Stream type (4 = visual, 5 = audio)
+    pub stream_type:            u8,
+    /// Size of the decoding buffer in bytes
+    pub buffer_size:            u32,
+    /// Maximum bit rate in bits per second
+    pub max_bitrate:            u32,
+    /// Average bit rate in bits per second (0 for variable bit rate)
+    pub avg_bitrate:            u32,
+    /// Raw DecoderSpecificInfo
+    pub decoder_specific_info:  Vec<u8>,
+    /// Decoded DecoderSpecificInfo of MPEG-4 audio streams
+    pub audio_config:           Option<AudioSpecificConfig>
+}
+
+/// Read a descriptor header (tag and variable-length size) at `position`, returning tag, payload start and payload end
+fn read_descriptor(data: &[u8], position: usize) -> Option<(u8, usize, usize)>
+{
+    let tag = *data.get(position)?;
+    let mut size = 0usize;
+    let mut offset = position + 1;
+
+    // Up to four size bytes, seven bits each, the high bit marks another byte
+    for _ in 0..4
+    {
+        let byte = *data.get(offset)?;
+        offset += 1;
+        size = (size << 7) | (byte & 0x7F) as usize;
+        if byte & 0x80 == 0
+        {
+            break;
+        }
+    }
+
+    Some((tag, offset, (offset + size).min(data.len())))
+}
+
+impl ElementaryStreamDescriptorBox
+{
+    /// Parse esds (Elementary Stream Descriptor) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("esds box too short").to_string());
+        }
+
+        let version = data[0];
+
+        let (tag, es_start, es_end) = read_descriptor(data, 4).ok_or(tr("esds box without ES_Descriptor"))?;
+        if tag != ES_DESCRIPTOR_TAG || es_end < es_start + 3
+        {
+            return Err(tr_fmt("esds box: expected ES_Descriptor, found tag {}", &[&format!("0x{:02X}", tag)]));
+        }
+
+        let es_id = u16::from_be_bytes([data[es_start], data[es_start + 1]]);
+        let flags = data[es_start + 2];
+        let mut position = es_start + 3;
+        // streamDependenceFlag: dependsOn_ES_ID
+        if flags & 0x80 != 0
+        {
+            position += 2;
+        }
+        // URL_Flag: length-prefixed URL
+        if flags & 0x40 != 0
+        {
+            position += 1 + *data.get(position).unwrap_or(&0) as usize;
+        }
+        // OCRstreamFlag: OCR_ES_Id
+        if flags & 0x20 != 0
+        {
+            position += 2;
+        }
+
+        let mut descriptor = Self {
+            version,
+            es_id,
+            object_type_indication: 0,
+            stream_type: 0,
+            buffer_size: 0,
+            max_bitrate: 0,
+            avg_bitrate: 0,
+            decoder_specific_info: Vec::new(),
+            audio_config: None
+        };
+
+        // Sub-descriptors: DecoderConfigDescriptor, SLConfigDescriptor, ...
+        while position < es_end
+        {
+            let Some((tag, start, end)) = read_descriptor(data, position)
+            else
+            {
+                break;
+            };
+
+            if tag == DECODER_CONFIG_DESCRIPTOR_TAG && end >= start + 13
+            {
+                let config = &data[start..end];
+                descriptor.object_type_indication = config[0];
+                descriptor.stream_type = config[1] >> 2;
+                descriptor.buffer_size = u32::from_be_bytes([0, config[2], config[3], config[4]]);
+                descriptor.max_bitrate = u32::from_be_bytes([config[5], config[6], config[7], config[8]]);
+                descriptor.avg_bitrate = u32::from_be_bytes([config[9], config[10], config[11], config[12]]);
+
+                if let Some((info_tag, info_start, info_end)) = read_descriptor(data, start + 13) &&
+                    info_tag == DECODER_SPECIFIC_INFO_TAG &&
+                    info_end <= end
+                {
+                    descriptor.decoder_specific_info = data[info_start..info_end].to_vec();
+                }
+            }
+
+            position = end;
+        }
+
+        if descriptor.object_type_indication == OBJECT_TYPE_MPEG4_AUDIO && descriptor.decoder_specific_info.is_empty() == false
+        {
+            descriptor.audio_config = AudioSpecificConfig::parse(&descriptor.decoder_specific_info).ok();
+        }
+
+        Ok(descriptor)
+    }
+
+    /// Name of the object type indication
+    pub fn object_type_name(&self) -> &'static str
+    {
+        match self.object_type_indication
+        {
+            | 0x20 => "MPEG-4 Visual",
+            | 0x21 => "H.264 / AVC",
+            | 0x23 => "H.265 / HEVC",
+            | 0x40 => "MPEG-4 Audio",
+            | 0x60..=0x65 => "MPEG-2 Visual",
+            | 0x66 => "MPEG-2 AAC Main",
+            | 0x67 => "MPEG-2 AAC LC",
+            | 0x68 => "MPEG-2 AAC SSR",
+            | 0x69 => "MPEG-2 Audio (Layer 1/2/3)",
+            | 0x6A => "MPEG-1 Visual",
+            | 0x6B => "MPEG-1 Audio (Layer 1/2/3)",
+            | 0x6C => "JPEG",
+            | 0xA5 => "AC-3",
+            | 0xA6 => "E-AC-3",
+            | 0xA9 => "DTS",
+            | 0xAD => "Opus",
+            | 0xDD => "Vorbis",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "es_id": self.es_id,
+            "object_type_indication": self.object_type_indication,
+            "stream_type": self.stream_type,
+            "buffer_size": self.buffer_size,
+            "max_bitrate": self.max_bitrate,
+            "avg_bitrate": self.avg_bitrate,
+            "decoder_specific_info": to_hex(&self.decoder_specific_info),
+            "audio_config": self.audio_config.as_ref().map(|config| serde_json::json!({
+                "audio_object_type": config.audio_object_type,
+                "sample_rate": config.sample_rate,
+                "channel_configuration": config.channel_configuration,
+                "extension_sample_rate": config.extension_sample_rate,
+                "sbr": config.sbr,
+                "ps": config.ps
+            }))
+        })
+    }
+}
+
+impl fmt::Display for ElementaryStreamDescriptorBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("ES ID: {}", &[&self.es_id]))?;
+        writeln!(f, "{}", tr_fmt("Object Type: {} ({})", &[&format!("0x{:02X}", self.object_type_indication), &self.object_type_name()]))?;
+        let stream_type = match self.stream_type
+        {
+            | 4 => "visual",
+            | 5 => "audio",
+            | _ => "other"
+        };
+        writeln!(f, "{}", tr_fmt("Stream Type: {} ({})", &[&self.stream_type, &stream_type]))?;
+        writeln!(f, "{}", tr_fmt("Buffer Size: {} bytes", &[&self.buffer_size]))?;
+        writeln!(f, "{}", tr_fmt("Max Bitrate: {} bit/s", &[&self.max_bitrate]))?;
+        writeln!(f, "{}", tr_fmt("Average Bitrate: {} bit/s", &[&self.avg_bitrate]))?;
+        if let Some(ref config) = self.audio_config
+        {
+            writeln!(f, "{}", tr_fmt("Audio Object Type: {} ({})", &[&config.audio_object_type, &config.profile_name()]))?;
+            writeln!(f, "{}", tr_fmt("Sample Rate: {} Hz", &[&config.sample_rate]))?;
+            if let Some(extension_sample_rate) = config.extension_sample_rate
+            {
+                writeln!(f, "{}", tr_fmt("Output Sample Rate: {} Hz", &[&extension_sample_rate]))?;
+            }
+            writeln!(f, "{}", tr_fmt("Channel Configuration: {}", &[&config.channel_configuration]))?;
+        }
+        else if self.decoder_specific_info.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Decoder Specific Info: {} bytes", &[&self.decoder_specific_info.len()]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use crate::{
+    bytes::{read_cstring, read_u32_be, read_u64_be},
+    hexdump::format_hexdump_limited,
+    i18n::{tr, tr_fmt},
+    mkv::element::format_unix_time,
+    timed_id3::{EMSG_ID3_SCHEME, TimedId3, frame_summary}
+};
+
+/// Number of message bytes shown as hexdump
+const MAX_DISPLAYED_MESSAGE_BYTES: usize = 128;
+
+/// Seconds between the NTP epoch (1900-01-01) and the Unix epoch (1970-01-01)
+const NTP_EPOCH_OFFSET: u64 = 2_208_988_800;
+
+/// Event Message Box (emsg)
+#[derive(Debug, Clone)]
+pub struct EventMessageBox
+{
+    pub version:                 u8,
+    /// Scheme of the event (e.g. "urn:scte:scte35:2013:bin", "https://aomedia.org/emsg/ID3")
+    pub scheme_id_uri:           String,
+    pub value:                   String,
+    pub timescale:               u32,
+    /// Presentation time delta relative to the segment (version 0)
+    pub presentation_time_delta: Option<u32>,
+    /// Absolute presentation time (version 1)
+    pub presentation_time:       Option<u64>,
+    /// Event duration in timescale units (0xFFFFFFFF = unknown)
+    pub event_duration:          u32,
+    pub id:                      u32,
+    pub message_data:            Vec<u8>
+}
+
+impl EventMessageBox
+{
+    /// Parse emsg (Event Message) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("emsg box too short").to_string());
+        }
+
+        let version = data[0];
+        if version == 1
+        {
+            // Fixed fields first, then the strings
+            if data.len() < 24
+            {
+                return Err(tr("emsg version 1 box too short").to_string());
+            }
+            let (scheme_id_uri, position) = read_cstring(data, 24).ok_or(tr("emsg box: string missing or unterminated"))?;
+            let (value, position) = read_cstring(data, position).ok_or(tr("emsg box: string missing or unterminated"))?;
+
+            return Ok(EventMessageBox {
+                version,
+                scheme_id_uri,
+                value,
+                timescale: read_u32_be(data, 4),
+                presentation_time_delta: None,
+                presentation_time: Some(read_u64_be(data, 8)),
+                event_duration: read_u32_be(data, 16),
+                id: read_u32_be(data, 20),
+                message_data: data[position..].to_vec()
+            });
+        }
+
+        // Version 0: the strings come first
+        let (scheme_id_uri, position) = read_cstring(data, 4).ok_or(tr("emsg box: string missing or unterminated"))?;
+        let (value, position) = read_cstring(data, position).ok_or(tr("emsg box: string missing or unterminated"))?;
+        if position + 16 > data.len()
+        {
+            return Err(tr("emsg version 0 box too short").to_string());
+        }
+
+        Ok(EventMessageBox {
+            version,
+            scheme_id_uri,
+            value,
+            timescale: read_u32_be(data, position),
+            presentation_time_delta: Some(read_u32_be(data, position + 4)),
+            presentation_time: None,
+            event_duration: read_u32_be(data, position + 8),
+            id: read_u32_be(data, position + 12),
+            message_data: data[position + 16..].to_vec()
+        })
+    }
+
+    /// Convert timescale units to seconds
+    fn seconds(&self, value: u64) -> Option<f64>
+    {
+        (self.timescale > 0).then(|| value as f64 / self.timescale as f64)
+    }
+
+    /// ID3v2 tag of HLS timed metadata carried as the message (scheme `https://aomedia.org/emsg/ID3`)
+    pub fn timed_id3(&self) -> Option<TimedId3>
+    {
+        if self.scheme_id_uri != EMSG_ID3_SCHEME && self.message_data.starts_with(b"ID3") == false
+        {
+            return None;
+        }
+        TimedId3::parse(0, None, &self.message_data).ok().flatten()
+    }
+
+    /// Message payload as text if it is printable UTF-8
+    pub fn message_text(&self) -> Option<&str>
+    {
+        let text = std::str::from_utf8(&self.message_data).ok()?;
+        text.chars().all(|character| character.is_control() == false || character.is_whitespace() == true).then_some(text)
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "scheme_id_uri": self.scheme_id_uri,
+            "value": self.value,
+            "timescale": self.timescale,
+            "presentation_time_delta": self.presentation_time_delta,
+            "presentation_time": self.presentation_time,
+            "event_duration": self.event_duration,
+            "id": self.id,
+            "message_data_size": self.message_data.len()
+        })
+    }
+}
+
+impl fmt::Display for EventMessageBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let with_seconds = |value: u64| match self.seconds(value)
+        {
+            | Some(seconds) => format!("{} ({:.3} s)", value, seconds),
+            | None => value.to_string()
+        };
+
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Scheme ID URI: {}", &[&self.scheme_id_uri]))?;
+        writeln!(f, "{}", tr_fmt("Value: {}", &[&self.value]))?;
+        writeln!(f, "{}", tr_fmt("Timescale: {} units/second", &[&self.timescale]))?;
+        if let Some(delta) = self.presentation_time_delta
+        {
+            writeln!(f, "{}", tr_fmt("Presentation Time Delta: {}", &[&with_seconds(delta as u64)]))?;
+        }
+        if let Some(presentation_time) = self.presentation_time
+        {
+            writeln!(f, "{}", tr_fmt("Presentation Time: {}", &[&with_seconds(presentation_time)]))?;
+        }
+        if self.event_duration == u32::MAX
+        {
+            writeln!(f, "{}", tr("Event Duration: unknown"))?;
+        }
+        else
+        {
+            writeln!(f, "{}", tr_fmt("Event Duration: {}", &[&with_seconds(self.event_duration as u64)]))?;
+        }
+        writeln!(f, "{}", tr_fmt("ID: {}", &[&self.id]))?;
+        writeln!(f, "{}", tr_fmt("Message Data: {} bytes", &[&self.message_data.len()]))?;
+
+        if let Some(timed_id3) = self.timed_id3()
+        {
+            writeln!(f, "{}", tr_fmt("ID3v2.{} Tag: {} frames", &[&timed_id3.tag.major_version, &timed_id3.tag.frames.len()]))?;
+            for frame in &timed_id3.tag.frames
+            {
+                writeln!(f, "  {}", frame_summary(frame))?;
+            }
+        }
+        else if let Some(text) = self.message_text() &&
+            text.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Message: \"{}\"", &[&text]))?;
+        }
+        else if self.message_data.is_empty() == false
+        {
+            for line in format_hexdump_limited(&self.message_data, 0, Some(MAX_DISPLAYED_MESSAGE_BYTES)).lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Producer Reference Time Box (prft)
+#[derive(Debug, Clone)]
+pub struct ProducerReferenceTimeBox
+{
+    pub version:            u8,
+    /// Which moment the NTP timestamp refers to (encoder input, output, ...)
+    pub flags:              u32,
+    pub reference_track_id: u32,
+    /// 64-bit NTP timestamp (seconds since 1900 in the upper 32 bits, fraction in the lower)
+    pub ntp_timestamp:      u64,
+    /// Media time of the reference track corresponding to the NTP timestamp
+    pub media_time:         u64
+}
+
+impl ProducerReferenceTimeBox
+{
+    /// Parse prft (Producer Reference Time) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 20
+        {
+            return Err(tr("prft box too short").to_string());
+        }
+
+        let version = data[0];
+        let media_time = if version == 1
+        {
+            if data.len() < 24
+            {
+                return Err(tr("prft version 1 box too short").to_string());
+            }
+            read_u64_be(data, 16)
+        }
+        else
+        {
+            read_u32_be(data, 16) as u64
+        };
+
+        Ok(ProducerReferenceTimeBox {
+            version,
+            flags: u32::from_be_bytes([0, data[1], data[2], data[3]]),
+            reference_track_id: read_u32_be(data, 4),
+            ntp_timestamp: read_u64_be(data, 8),
+            media_time
+        })
+    }
+
+    /// Describe the flags
+    pub fn flags_name(&self) -> &'static str
+    {
+        match self.flags
+        {
+            | 0 => "unspecified",
+            | 1 => "encoder input",
+            | 2 => "encoder output",
+            | 4 => "moof finalized",
+            | 8 => "moof written",
+            | 24 => "captured",
+            | _ => "reserved"
+        }
+    }
+
+    /// NTP timestamp as ISO 8601 UTC date with milliseconds
+    pub fn wall_clock_time(&self) -> Option<String>
+    {
+        let seconds = (self.ntp_timestamp >> 32).checked_sub(NTP_EPOCH_OFFSET)?;
+        let milliseconds = ((self.ntp_timestamp & 0xFFFF_FFFF) * 1000) >> 32;
+        let date = format_unix_time(seconds as i64);
+        Some(format!("{}.{:03}Z", date.trim_end_matches('Z'), milliseconds))
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "flags": self.flags,
+            "reference_track_id": self.reference_track_id,
+            "ntp_timestamp": self.ntp_timestamp,
+            "media_time": self.media_time
+        })
+    }
+}
+
+impl fmt::Display for ProducerReferenceTimeBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Flags: {} ({})", &[&format!("0x{:06X}", self.flags), &self.flags_name()]))?;
+        writeln!(f, "{}", tr_fmt("Reference Track ID: {}", &[&self.reference_track_id]))?;
+        match self.wall_clock_time()
+        {
+            | Some(date) => writeln!(f, "{}", tr_fmt("NTP Timestamp: {} ({})", &[&format!("0x{:016X}", self.ntp_timestamp), &date]))?,
+            | None => writeln!(f, "{}", tr_fmt("NTP Timestamp: {}", &[&format!("0x{:016X}", self.ntp_timestamp)]))?
+        }
+        writeln!(f, "{}", tr_fmt("Media Time: {}", &[&self.media_time]))?;
+        Ok(())
+    }
+}
@@ -1,5 +1,22 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
+/// Registered major brands the dissector knows (files with other brands are still dissected)
+const KNOWN_BRANDS: [&str; 61] = [
+    "isom", "iso2", "iso3", "iso4", "iso5", "iso6", "iso8", "iso9", "mp41", "mp42", "mp71", "M4A ", "M4V ", "M4P ", "M4B ", "qt  ", "mqt ", "3gp4", "3gp5", "3gp6",
+    "3gp7", "3gp8", "3gp9", "3g2a", "3g2b", "3g2c", "mmp4", "avc1", "MSNV", "dash", "msdh", "msix", "cmfc", "cmf2",
+    // 3GPP profile brands (extended, general, adaptive streaming, media segment, progressive download, streaming server)
+    "3ge6", "3ge7", "3ge8", "3ge9", "3gg6", "3gg9", "3gh9", "3gm9", "3gr6", "3gr7", "3gr8", "3gr9", "3gs6", "3gs7", "3gs8", "3gs9", // HEIF/AVIF image brands
+    "heic", "heix", "heim", "heis", "hevc", "hevx", "mif1", "msf1", "avif", "avis", "avci"
+];
+
+/// Whether a brand is one of the registered brands the dissector knows
+pub fn is_known_brand(brand: &str) -> bool
+{
+    KNOWN_BRANDS.contains(&brand)
+}
+
 /// File Type Box (ftyp)
 #[derive(Debug, Clone)]
 pub struct FileTypeBox
@@ -16,7 +33,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("ftyp box too short".to_string());
+            return Err(tr("ftyp box too short").to_string());
         }
 
         let major_brand = String::from_utf8_lossy(&data[0..4]).to_string();
@@ -33,17 +50,27 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(FileTypeBox { major_brand, minor_version, compatible_brands })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "major_brand": self.major_brand,
+            "minor_version": self.minor_version,
+            "compatible_brands": self.compatible_brands
+        })
+    }
 }
 
 impl fmt::Display for FileTypeBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Major Brand: '{}'", self.major_brand)?;
-        writeln!(f, "Minor Version: {}", self.minor_version)?;
+        writeln!(f, "{}", tr_fmt("Major Brand: '{}'", &[&self.major_brand]))?;
+        writeln!(f, "{}", tr_fmt("Minor Version: {}", &[&self.minor_version]))?;
         if self.compatible_brands.is_empty() == false
         {
-            write!(f, "Compatible Brands: ")?;
+            write!(f, "{}", tr("Compatible Brands: "))?;
             let brands: Vec<String> = self.compatible_brands.iter().map(|b| format!("'{}'", b)).collect();
             writeln!(f, "{}", brands.join(", "))?;
         }
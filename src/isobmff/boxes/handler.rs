@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// Handler Reference Box (hdlr)
 #[derive(Debug, Clone)]
 pub struct HandlerBox
@@ -17,7 +19,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 24
         {
-            return Err("hdlr box too short".to_string());
+            return Err(tr("hdlr box too short").to_string());
         }
 
         let version = data[0];
@@ -52,30 +54,43 @@ fn get_handler_name(handler_type: &str) -> &'static str
             | "hint" => "Hint Track",
             | "meta" => "Metadata Track",
             | "mdir" => "Metadata Directory",
+            | "mdta" => "QuickTime Metadata",
             | "auxv" => "Auxiliary Video Track",
             | "text" => "Text/Subtitle Track",
             | "sbtl" => "Subtitle Track",
             | "subt" => "Subtitle Track",
             | "clcp" => "Closed Caption Track",
             | "tmcd" => "Timecode Track",
+            | "pict" => "Image Items",
             | _ => "Unknown Handler"
         }
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "handler_type": self.handler_type,
+            "manufacturer": self.manufacturer,
+            "name": self.name
+        })
+    }
 }
 
 impl fmt::Display for HandlerBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Handler Type: '{}' ({})", self.handler_type, Self::get_handler_name(&self.handler_type))?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Handler Type: '{}' ({})", &[&self.handler_type, &tr(Self::get_handler_name(&self.handler_type))]))?;
         if self.manufacturer.is_empty() == false && self.manufacturer.chars().any(|c| c.is_alphanumeric()) == true
         {
-            writeln!(f, "Manufacturer: '{}'", self.manufacturer)?;
+            writeln!(f, "{}", tr_fmt("Manufacturer: '{}'", &[&self.manufacturer]))?;
         }
         if self.name.is_empty() == false
         {
-            writeln!(f, "Name: \"{}\"", self.name)?;
+            writeln!(f, "{}", tr_fmt("Name: \"{}\"", &[&self.name]))?;
         }
         Ok(())
     }
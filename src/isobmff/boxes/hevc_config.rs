@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::boxes::avc_config::{ParameterSet, chroma_format_name, read_parameter_sets}
+};
+
+/// NAL unit array of an HEVC decoder configuration record
+#[derive(Debug, Clone)]
+pub struct HevcNalArray
+{
+    /// Whether all NAL units of this type are in the array (none in the samples)
+    pub array_completeness: bool,
+    /// NAL unit type (32 = VPS, 33 = SPS, 34 = PPS, 39/40 = SEI)
+    pub nal_unit_type:      u8,
+    pub nal_units:          Vec<ParameterSet>
+}
+
+impl HevcNalArray
+{
+    /// Short name of the NAL unit type
+    pub fn nal_unit_type_name(&self) -> &'static str
+    {
+        match self.nal_unit_type
+        {
+            | 32 => "VPS",
+            | 33 => "SPS",
+            | 34 => "PPS",
+            | 39 => "Prefix SEI",
+            | 40 => "Suffix SEI",
+            | _ => "NAL"
+        }
+    }
+}
+
+/// HEVC Decoder Configuration Record (hvcC)
+#[derive(Debug, Clone)]
+pub struct HevcConfigurationBox
+{
+    pub configuration_version:         u8,
+    pub general_profile_space:         u8,
+    /// Tier flag (false = Main tier, true = High tier)
+    pub general_tier_flag:             bool,
+    /// general_profile_idc (1 = Main, 2 = Main 10, 3 = Main Still Picture, 4 = Range Extensions)
+    pub general_profile_idc:           u8,
+    pub general_profile_compatibility: u32,
+    /// general_level_idc (30 times the level number)
+    pub general_level_idc:             u8,
+    pub chroma_format:                 u8,
+    pub bit_depth_luma:                u8,
+    pub bit_depth_chroma:              u8,
+    /// Average frame rate in frames per 256 seconds, 0 if unspecified
+    pub avg_frame_rate:                u16,
+    /// Size of the NAL unit length fields in the samples
+    pub nal_length_size:               u8,
+    pub arrays:                        Vec<HevcNalArray>
+}
+
+impl HevcConfigurationBox
+{
+    /// Parse hvcC (HEVC Decoder Configuration Record) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 23
+        {
+            return Err(tr("hvcC box too short").to_string());
+        }
+
+        let mut arrays = Vec::new();
+        let mut position = 23;
+        for _ in 0..data[22]
+        {
+            if position + 3 > data.len()
+            {
+                break;
+            }
+            let array_completeness = data[position] & 0x80 != 0;
+            let nal_unit_type = data[position] & 0x3F;
+            let count = u16::from_be_bytes([data[position + 1], data[position + 2]]) as usize;
+            let (mut nal_units, next_position) = read_parameter_sets(data, position + 3, count);
+            // The array header carries the HEVC NAL unit type
+            for nal_unit in &mut nal_units
+            {
+                nal_unit.nal_unit_type = nal_unit_type;
+            }
+            arrays.push(HevcNalArray { array_completeness, nal_unit_type, nal_units });
+            position = next_position;
+        }
+
+        Ok(HevcConfigurationBox {
+            configuration_version: data[0],
+            general_profile_space: data[1] >> 6,
+            general_tier_flag: data[1] & 0x20 != 0,
+            general_profile_idc: data[1] & 0x1F,
+            general_profile_compatibility: u32::from_be_bytes([data[2], data[3], data[4], data[5]]),
+            general_level_idc: data[12],
+            chroma_format: data[16] & 0x03,
+            bit_depth_luma: (data[17] & 0x07) + 8,
+            bit_depth_chroma: (data[18] & 0x07) + 8,
+            avg_frame_rate: u16::from_be_bytes([data[19], data[20]]),
+            nal_length_size: (data[21] & 0x03) + 1,
+            arrays
+        })
+    }
+
+    /// Profile name for the general_profile_idc
+    pub fn profile_name(&self) -> &'static str
+    {
+        match self.general_profile_idc
+        {
+            | 1 => "Main",
+            | 2 => "Main 10",
+            | 3 => "Main Still Picture",
+            | 4 => "Range Extensions",
+            | 5 => "High Throughput",
+            | 9 => "Screen Content Coding",
+            | _ => "Unknown"
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "configuration_version": self.configuration_version,
+            "general_profile_space": self.general_profile_space,
+            "general_tier_flag": self.general_tier_flag,
+            "general_profile_idc": self.general_profile_idc,
+            "general_profile_compatibility": self.general_profile_compatibility,
+            "general_level_idc": self.general_level_idc,
+            "chroma_format": self.chroma_format,
+            "bit_depth_luma": self.bit_depth_luma,
+            "bit_depth_chroma": self.bit_depth_chroma,
+            "avg_frame_rate": self.avg_frame_rate,
+            "nal_length_size": self.nal_length_size,
+            "arrays": self.arrays.iter().map(|array| serde_json::json!({
+                "array_completeness": array.array_completeness,
+                "nal_unit_type": array.nal_unit_type,
+                "nal_units": array.nal_units.iter().map(ParameterSet::to_json).collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for HevcConfigurationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let tier = if self.general_tier_flag == true
+        {
+            "High"
+        }
+        else
+        {
+            "Main"
+        };
+
+        writeln!(f, "{}", tr_fmt("Configuration Version: {}", &[&self.configuration_version]))?;
+        writeln!(f, "{}", tr_fmt("Profile: {} ({}), Profile Space: {}", &[&self.general_profile_idc, &self.profile_name(), &self.general_profile_space]))?;
+        writeln!(f, "{}", tr_fmt("Profile Compatibility: {}", &[&format!("0x{:08X}", self.general_profile_compatibility)]))?;
+        writeln!(f, "{}", tr_fmt("Level: {} ({} tier)", &[&(self.general_level_idc as f64 / 30.0), &tier]))?;
+        writeln!(f, "{}", tr_fmt("Chroma Format: {}", &[&chroma_format_name(self.chroma_format)]))?;
+        writeln!(f, "{}", tr_fmt("Bit Depth: {} (luma), {} (chroma)", &[&self.bit_depth_luma, &self.bit_depth_chroma]))?;
+        if self.avg_frame_rate > 0
+        {
+            writeln!(f, "{}", tr_fmt("Average Frame Rate: {} fps", &[&format!("{:.3}", self.avg_frame_rate as f64 / 256.0)]))?;
+        }
+        writeln!(f, "{}", tr_fmt("NAL Length Size: {} bytes", &[&self.nal_length_size]))?;
+        for array in &self.arrays
+        {
+            for (index, nal_unit) in array.nal_units.iter().enumerate()
+            {
+                writeln!(f, "{}", tr_fmt("{} {}: {} bytes", &[&array.nal_unit_type_name(), &(index + 1), &nal_unit.data.len()]))?;
+            }
+        }
+        Ok(())
+    }
+}
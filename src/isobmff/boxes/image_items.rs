@@ -0,0 +1,683 @@
+use std::fmt;
+
+use crate::{
+    bytes::read_cstring,
+    i18n::{tr, tr_fmt}
+};
+
+/// Number of items, extents or associations listed in the text output
+const MAX_DISPLAYED_ENTRIES: usize = 20;
+
+/// Read a big-endian unsigned integer of `size` bytes (0, 2, 4 or 8) at `position`
+fn read_sized(data: &[u8], position: usize, size: usize) -> Option<u64>
+{
+    let bytes = data.get(position..position + size)?;
+    Some(bytes.iter().fold(0u64, |value, &byte| (value << 8) | byte as u64))
+}
+
+/// Extent of an item: a byte range in the file, an idat box or another item
+#[derive(Debug, Clone)]
+pub struct ItemExtent
+{
+    pub index:  Option<u64>,
+    pub offset: u64,
+    pub length: u64
+}
+
+/// Location of one item in an Item Location Box
+#[derive(Debug, Clone)]
+pub struct ItemLocation
+{
+    pub item_id:              u32,
+    /// 0 = file offset, 1 = idat offset, 2 = item offset
+    pub construction_method:  u8,
+    pub data_reference_index: u16,
+    pub base_offset:          u64,
+    pub extents:              Vec<ItemExtent>
+}
+
+impl ItemLocation
+{
+    /// Total length of all extents
+    pub fn total_length(&self) -> u64
+    {
+        self.extents.iter().map(|extent| extent.length).sum()
+    }
+}
+
+/// Item Location Box (iloc)
+#[derive(Debug, Clone)]
+pub struct ItemLocationBox
+{
+    pub version: u8,
+    pub items:   Vec<ItemLocation>
+}
+
+impl ItemLocationBox
+{
+    /// Parse iloc (Item Location) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("iloc box too short").to_string());
+        }
+
+        let version = data[0];
+        let offset_size = (data[4] >> 4) as usize;
+        let length_size = (data[4] & 0x0F) as usize;
+        let base_offset_size = (data[5] >> 4) as usize;
+        let index_size = if version == 1 || version == 2
+        {
+            (data[5] & 0x0F) as usize
+        }
+        else
+        {
+            0
+        };
+
+        let truncated = || "iloc box truncated".to_string();
+        let (item_count, mut position) = if version < 2
+        {
+            (read_sized(data, 6, 2).ok_or_else(truncated)?, 8)
+        }
+        else
+        {
+            (read_sized(data, 6, 4).ok_or_else(truncated)?, 10)
+        };
+        let item_id_size = if version < 2
+        {
+            2
+        }
+        else
+        {
+            4
+        };
+
+        let mut items = Vec::new();
+        for _ in 0..item_count
+        {
+            let item_id = read_sized(data, position, item_id_size).ok_or_else(truncated)? as u32;
+            position += item_id_size;
+            let mut construction_method = 0;
+            if version == 1 || version == 2
+            {
+                construction_method = (read_sized(data, position, 2).ok_or_else(truncated)? & 0x0F) as u8;
+                position += 2;
+            }
+            let data_reference_index = read_sized(data, position, 2).ok_or_else(truncated)? as u16;
+            let base_offset = read_sized(data, position + 2, base_offset_size).ok_or_else(truncated)?;
+            let extent_count = read_sized(data, position + 2 + base_offset_size, 2).ok_or_else(truncated)?;
+            position += 4 + base_offset_size;
+
+            let mut extents = Vec::new();
+            for _ in 0..extent_count
+            {
+                let index = if index_size > 0
+                {
+                    position += index_size;
+                    Some(read_sized(data, position - index_size, index_size).ok_or_else(truncated)?)
+                }
+                else
+                {
+                    None
+                };
+                let offset = read_sized(data, position, offset_size).ok_or_else(truncated)?;
+                let length = read_sized(data, position + offset_size, length_size).ok_or_else(truncated)?;
+                position += offset_size + length_size;
+                extents.push(ItemExtent { index, offset, length });
+            }
+
+            items.push(ItemLocation { item_id, construction_method, data_reference_index, base_offset, extents });
+        }
+
+        Ok(ItemLocationBox { version, items })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "items": self.items.iter().map(|item| serde_json::json!({
+                "item_id": item.item_id,
+                "construction_method": item.construction_method,
+                "data_reference_index": item.data_reference_index,
+                "base_offset": item.base_offset,
+                "extents": item.extents.iter().map(|extent| serde_json::json!({
+                    "index": extent.index,
+                    "extent_offset": extent.offset,
+                    "length": extent.length
+                })).collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for ItemLocationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Item Count: {}", &[&self.items.len()]))?;
+        for item in self.items.iter().take(MAX_DISPLAYED_ENTRIES)
+        {
+            let method = match item.construction_method
+            {
+                | 0 => "file",
+                | 1 => "idat",
+                | 2 => "item",
+                | _ => "reserved"
+            };
+            let extents: Vec<String> = item.extents.iter().map(|extent| format!("0x{:08X}+{}", item.base_offset + extent.offset, extent.length)).collect();
+            writeln!(
+                f,
+                "{}",
+                tr_fmt("Item {}: {} bytes in {} extent(s) ({}): {}", &[&item.item_id, &item.total_length(), &item.extents.len(), &method, &extents.join(", ")])
+            )?;
+        }
+        if self.items.len() > MAX_DISPLAYED_ENTRIES
+        {
+            writeln!(f, "{}", tr_fmt("... {} more items", &[&(self.items.len() - MAX_DISPLAYED_ENTRIES)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Item Info Entry (infe)
+#[derive(Debug, Clone)]
+pub struct ItemInfoEntryBox
+{
+    pub version:               u8,
+    /// Hidden items (flag 1) are not meant to be displayed (e.g. thumbnails' tiles)
+    pub hidden:                bool,
+    pub item_id:               u32,
+    pub item_protection_index: u16,
+    /// Item type (e.g. "hvc1", "av01", "grid", "Exif", "mime"), empty for version 0/1 entries
+    pub item_type:             String,
+    pub item_name:             String,
+    /// MIME type of "mime" items (version 0/1: content type)
+    pub content_type:          String,
+    /// URI type of "uri " items
+    pub item_uri_type:         String
+}
+
+impl ItemInfoEntryBox
+{
+    /// Parse infe (Item Info Entry) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("infe box too short").to_string());
+        }
+
+        let version = data[0];
+        let hidden = data[3] & 0x01 != 0;
+        let mut entry = ItemInfoEntryBox {
+            version,
+            hidden,
+            item_id: 0,
+            item_protection_index: 0,
+            item_type: String::new(),
+            item_name: String::new(),
+            content_type: String::new(),
+            item_uri_type: String::new()
+        };
+
+        if version < 2
+        {
+            entry.item_id = u16::from_be_bytes([data[4], data[5]]) as u32;
+            entry.item_protection_index = u16::from_be_bytes([data[6], data[7]]);
+            let (item_name, position) = read_cstring(data, 8).ok_or(tr("infe box: item name missing or unterminated"))?;
+            entry.item_name = item_name;
+            entry.content_type = read_cstring(data, position).map(|(text, _)| text).unwrap_or_default();
+            return Ok(entry);
+        }
+
+        // Version 3 uses 32-bit item IDs
+        let id_size = if version == 3
+        {
+            4
+        }
+        else
+        {
+            2
+        };
+        if data.len() < 4 + id_size + 6
+        {
+            return Err(tr("infe box too short").to_string());
+        }
+        entry.item_id = read_sized(data, 4, id_size).unwrap_or(0) as u32;
+        let position = 4 + id_size;
+        entry.item_protection_index = u16::from_be_bytes([data[position], data[position + 1]]);
+        entry.item_type = String::from_utf8_lossy(&data[position + 2..position + 6]).to_string();
+        let (item_name, position) = read_cstring(data, position + 6).ok_or(tr("infe box: item name missing or unterminated"))?;
+        entry.item_name = item_name;
+        match entry.item_type.as_str()
+        {
+            | "mime" => entry.content_type = read_cstring(data, position).map(|(text, _)| text).unwrap_or_default(),
+            | "uri " => entry.item_uri_type = read_cstring(data, position).map(|(text, _)| text).unwrap_or_default(),
+            | _ =>
+            {}
+        }
+
+        Ok(entry)
+    }
+
+    /// Describe the item type
+    pub fn item_type_name(&self) -> &'static str
+    {
+        match self.item_type.as_str()
+        {
+            | "hvc1" => "HEVC image",
+            | "av01" => "AV1 image",
+            | "avc1" => "AVC image",
+            | "jpeg" => "JPEG image",
+            | "grid" => "image grid",
+            | "iovl" => "image overlay",
+            | "iden" => "identity derivation",
+            | "Exif" => "Exif metadata",
+            | "mime" => "MIME content",
+            | "uri " => "URI",
+            | "unci" => "uncompressed image",
+            | _ => ""
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "hidden": self.hidden,
+            "item_id": self.item_id,
+            "item_protection_index": self.item_protection_index,
+            "item_type": self.item_type,
+            "item_name": self.item_name,
+            "content_type": self.content_type,
+            "item_uri_type": self.item_uri_type
+        })
+    }
+}
+
+impl fmt::Display for ItemInfoEntryBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Item ID: {}", &[&self.item_id]))?;
+        if self.item_type.is_empty() == false
+        {
+            match self.item_type_name()
+            {
+                | "" => writeln!(f, "{}", tr_fmt("Item Type: '{}'", &[&self.item_type]))?,
+                | name => writeln!(f, "{}", tr_fmt("Item Type: '{}' ({})", &[&self.item_type, &name]))?
+            }
+        }
+        if self.item_name.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Item Name: \"{}\"", &[&self.item_name]))?;
+        }
+        if self.content_type.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("Content Type: {}", &[&self.content_type]))?;
+        }
+        if self.item_uri_type.is_empty() == false
+        {
+            writeln!(f, "{}", tr_fmt("URI Type: {}", &[&self.item_uri_type]))?;
+        }
+        if self.item_protection_index != 0
+        {
+            writeln!(f, "{}", tr_fmt("Protection Index: {}", &[&self.item_protection_index]))?;
+        }
+        if self.hidden == true
+        {
+            writeln!(f, "{}", tr("Hidden: true"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Primary Item Box (pitm)
+#[derive(Debug, Clone)]
+pub struct PrimaryItemBox
+{
+    pub item_id: u32
+}
+
+impl PrimaryItemBox
+{
+    /// Parse pitm (Primary Item) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let id_size = if data.first() == Some(&0)
+        {
+            2
+        }
+        else
+        {
+            4
+        };
+        let item_id = read_sized(data, 4, id_size).ok_or(tr("pitm box too short"))? as u32;
+
+        Ok(PrimaryItemBox { item_id })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "item_id": self.item_id })
+    }
+}
+
+impl fmt::Display for PrimaryItemBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Primary Item ID: {}", &[&self.item_id]))?;
+        Ok(())
+    }
+}
+
+/// Typed reference from one item to others (e.g. "thmb", "dimg", "cdsc", "auxl")
+#[derive(Debug, Clone)]
+pub struct ItemReference
+{
+    pub reference_type: String,
+    pub from_item_id:   u32,
+    pub to_item_ids:    Vec<u32>
+}
+
+/// Item Reference Box (iref)
+#[derive(Debug, Clone)]
+pub struct ItemReferenceBox
+{
+    pub version:    u8,
+    pub references: Vec<ItemReference>
+}
+
+impl ItemReferenceBox
+{
+    /// Parse iref (Item Reference) box and its SingleItemTypeReference children
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("iref box too short").to_string());
+        }
+
+        let version = data[0];
+        let id_size = if version == 0
+        {
+            2
+        }
+        else
+        {
+            4
+        };
+
+        let mut references = Vec::new();
+        let mut position = 4;
+        while position + 8 <= data.len()
+        {
+            let size = read_sized(data, position, 4).unwrap_or(0) as usize;
+            if size < 8 || position + size > data.len()
+            {
+                break;
+            }
+            let reference = &data[position..position + size];
+            let reference_type = String::from_utf8_lossy(&reference[4..8]).to_string();
+            if let Some(from_item_id) = read_sized(reference, 8, id_size) &&
+                let Some(count) = read_sized(reference, 8 + id_size, 2)
+            {
+                let to_item_ids = (0..count as usize).filter_map(|index| read_sized(reference, 10 + id_size + index * id_size, id_size)).map(|id| id as u32).collect();
+                references.push(ItemReference { reference_type, from_item_id: from_item_id as u32, to_item_ids });
+            }
+            position += size;
+        }
+
+        Ok(ItemReferenceBox { version, references })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "references": self.references.iter().map(|reference| serde_json::json!({
+                "reference_type": reference.reference_type,
+                "from_item_id": reference.from_item_id,
+                "to_item_ids": reference.to_item_ids
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for ItemReferenceBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        for reference in self.references.iter().take(MAX_DISPLAYED_ENTRIES)
+        {
+            let kind = match reference.reference_type.as_str()
+            {
+                | "thmb" => " (thumbnail of)",
+                | "dimg" => " (derived from)",
+                | "cdsc" => " (describes)",
+                | "auxl" => " (auxiliary image of)",
+                | "base" => " (pre-derived image based on)",
+                | _ => ""
+            };
+            let targets: Vec<String> = reference.to_item_ids.iter().map(u32::to_string).collect();
+            writeln!(f, "{}", tr_fmt("'{}'{}: item {} -> {}", &[&reference.reference_type, &kind, &reference.from_item_id, &targets.join(", ")]))?;
+        }
+        if self.references.len() > MAX_DISPLAYED_ENTRIES
+        {
+            writeln!(f, "{}", tr_fmt("... {} more references", &[&(self.references.len() - MAX_DISPLAYED_ENTRIES)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Association of an item with a property of the ipco box
+#[derive(Debug, Clone)]
+pub struct PropertyAssociation
+{
+    /// Whether the property is essential for decoding the item
+    pub essential:      bool,
+    /// 1-based index into the ipco box (0 = no property)
+    pub property_index: u16
+}
+
+/// Item Property Association Box (ipma)
+#[derive(Debug, Clone)]
+pub struct ItemPropertyAssociationBox
+{
+    pub version: u8,
+    pub flags:   u32,
+    /// Item ID and its associated properties
+    pub entries: Vec<(u32, Vec<PropertyAssociation>)>
+}
+
+impl ItemPropertyAssociationBox
+{
+    /// Parse ipma (Item Property Association) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("ipma box too short").to_string());
+        }
+
+        let version = data[0];
+        let flags = u32::from_be_bytes([0, data[1], data[2], data[3]]);
+        let entry_count = read_sized(data, 4, 4).unwrap_or(0);
+        let id_size = if version < 1
+        {
+            2
+        }
+        else
+        {
+            4
+        };
+        // Flag 1 selects 15-bit property indices
+        let index_size = if flags & 1 != 0
+        {
+            2
+        }
+        else
+        {
+            1
+        };
+
+        let mut entries = Vec::new();
+        let mut position = 8;
+        for _ in 0..entry_count
+        {
+            let (Some(item_id), Some(count)) = (read_sized(data, position, id_size), read_sized(data, position + id_size, 1))
+            else
+            {
+                break;
+            };
+            position += id_size + 1;
+
+            let mut associations = Vec::new();
+            for _ in 0..count
+            {
+                let Some(value) = read_sized(data, position, index_size)
+                else
+                {
+                    break;
+                };
+                position += index_size;
+                let high_bit = 1u64 << (index_size * 8 - 1);
+                associations.push(PropertyAssociation { essential: value & high_bit != 0, property_index: (value & (high_bit - 1)) as u16 });
+            }
+            entries.push((item_id as u32, associations));
+        }
+
+        Ok(ItemPropertyAssociationBox { version, flags, entries })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "flags": self.flags,
+            "entries": self.entries.iter().map(|(item_id, associations)| serde_json::json!({
+                "item_id": item_id,
+                "associations": associations.iter().map(|association| serde_json::json!({
+                    "essential": association.essential,
+                    "property_index": association.property_index
+                })).collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for ItemPropertyAssociationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.entries.len()]))?;
+        for (item_id, associations) in self.entries.iter().take(MAX_DISPLAYED_ENTRIES)
+        {
+            let properties: Vec<String> = associations
+                .iter()
+                .map(|association| {
+                    if association.essential == true
+                    {
+                        format!("{}!", association.property_index)
+                    }
+                    else
+                    {
+                        association.property_index.to_string()
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", tr_fmt("Item {}: properties {} (! = essential)", &[&item_id, &properties.join(", ")]))?;
+        }
+        if self.entries.len() > MAX_DISPLAYED_ENTRIES
+        {
+            writeln!(f, "{}", tr_fmt("... {} more entries", &[&(self.entries.len() - MAX_DISPLAYED_ENTRIES)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Image Spatial Extents Property (ispe)
+#[derive(Debug, Clone)]
+pub struct ImageSpatialExtentsBox
+{
+    pub width:  u32,
+    pub height: u32
+}
+
+impl ImageSpatialExtentsBox
+{
+    /// Parse ispe (Image Spatial Extents) property
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let (Some(width), Some(height)) = (read_sized(data, 4, 4), read_sized(data, 8, 4))
+        else
+        {
+            return Err(tr("ispe box too short").to_string());
+        };
+
+        Ok(ImageSpatialExtentsBox { width: width as u32, height: height as u32 })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "width": self.width, "height": self.height })
+    }
+}
+
+impl fmt::Display for ImageSpatialExtentsBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Image Size: {}x{}", &[&self.width, &self.height]))?;
+        Ok(())
+    }
+}
+
+/// Pixel Information Property (pixi)
+#[derive(Debug, Clone)]
+pub struct PixelInformationBox
+{
+    pub bits_per_channel: Vec<u8>
+}
+
+impl PixelInformationBox
+{
+    /// Parse pixi (Pixel Information) property
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let channels = *data.get(4).ok_or(tr("pixi box too short"))? as usize;
+        let bits_per_channel = data.get(5..5 + channels).ok_or(tr("pixi box truncated"))?.to_vec();
+
+        Ok(PixelInformationBox { bits_per_channel })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "bits_per_channel": self.bits_per_channel })
+    }
+}
+
+impl fmt::Display for PixelInformationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let bits: Vec<String> = self.bits_per_channel.iter().map(u8::to_string).collect();
+        writeln!(f, "{}", tr_fmt("Channels: {} ({} bits per channel)", &[&self.bits_per_channel.len(), &bits.join("/")]))?;
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::timestamps::format_mac_time
+};
+
 /// Media Header Box (mdhd)
 #[derive(Debug, Clone)]
 pub struct MediaHeaderBox
@@ -9,7 +14,10 @@ pub struct MediaHeaderBox
     pub modification_time: u64,
     pub timescale:         u32,
     pub duration:          u64,
-    pub language:          String
+    /// Three-letter ISO 639-2/T language code
+    pub language:          String,
+    /// Explanation when the language was derived from a special or legacy value
+    pub language_note:     Option<String>
 }
 
 impl MediaHeaderBox
@@ -19,7 +27,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("mdhd box too short".to_string());
+            return Err(tr("mdhd box too short").to_string());
         }
 
         let version = data[0];
@@ -29,7 +37,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 64-bit version
             if data.len() < 36
             {
-                return Err("mdhd version 1 box too short".to_string());
+                return Err(tr("mdhd version 1 box too short").to_string());
             }
 
             let creation = u64::from_be_bytes([data[4], data[5], data[6], data[7], data[8], data[9], data[10], data[11]]);
@@ -44,7 +52,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 32-bit version
             if data.len() < 24
             {
-                return Err("mdhd version 0 box too short".to_string());
+                return Err(tr("mdhd version 0 box too short").to_string());
             }
 
             let creation = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as u64;
@@ -58,28 +66,198 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         // Parse language (ISO 639-2/T language code, 3 x 5 bits)
         if data.len() < lang_offset + 2
         {
-            return Err("mdhd box too short for language".to_string());
+            return Err(tr("mdhd box too short for language").to_string());
         }
 
-        let lang_code = u16::from_be_bytes([data[lang_offset], data[lang_offset + 1]]);
-        let lang_chars: Vec<char> =
-            vec![(((lang_code >> 10) & 0x1F) as u8 + 0x60) as char, (((lang_code >> 5) & 0x1F) as u8 + 0x60) as char, ((lang_code & 0x1F) as u8 + 0x60) as char];
-        let language = lang_chars.into_iter().collect();
+        let language_code = u16::from_be_bytes([data[lang_offset], data[lang_offset + 1]]);
+        let (language, language_note) = decode_language_code(language_code);
+
+        Ok(MediaHeaderBox { version, creation_time, modification_time, timescale, duration, language, language_note })
+    }
 
-        Ok(MediaHeaderBox { version, creation_time, modification_time, timescale, duration, language })
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "creation_time": self.creation_time,
+            "modification_time": self.modification_time,
+            "timescale": self.timescale,
+            "duration": self.duration,
+            "language": self.language
+        })
     }
 }
 
+/// Decode the mdhd language field
+///
+/// Values below 0x400 are legacy QuickTime (Macintosh) language codes, 0x7FFF is the
+/// QuickTime "unspecified" value, everything else is a packed ISO 639-2/T code (3 x 5 bits).
+pub fn decode_language_code(code: u16) -> (String, Option<String>)
+{
+    if code == 0x7FFF
+    {
+        return ("und".to_string(), Some("unspecified, 0x7FFF".to_string()));
+    }
+
+    if code < 0x400
+    {
+        return match quicktime_language(code)
+        {
+            | Some(language) => (language.to_string(), Some(tr_fmt("QuickTime language code {}", &[&code]))),
+            | None => ("und".to_string(), Some(tr_fmt("unknown QuickTime language code {}", &[&code])))
+        };
+    }
+
+    let letters = [((code >> 10) & 0x1F) as u8, ((code >> 5) & 0x1F) as u8, (code & 0x1F) as u8];
+    if letters.iter().any(|&letter| letter == 0 || letter > 26) == true
+    {
+        return ("und".to_string(), Some(format!("invalid packed language code 0x{:04X}", code)));
+    }
+
+    (letters.iter().map(|&letter| (letter + 0x60) as char).collect(), None)
+}
+
+/// Map a legacy QuickTime (Macintosh) language code to ISO 639-2/T
+fn quicktime_language(code: u16) -> Option<&'static str>
+{
+    let language = match code
+    {
+        | 0 => "eng",
+        | 1 => "fra",
+        | 2 => "deu",
+        | 3 => "ita",
+        | 4 => "nld",
+        | 5 => "swe",
+        | 6 => "spa",
+        | 7 => "dan",
+        | 8 => "por",
+        | 9 => "nor",
+        | 10 => "heb",
+        | 11 => "jpn",
+        | 12 => "ara",
+        | 13 => "fin",
+        | 14 => "ell",
+        | 15 => "isl",
+        | 16 => "mlt",
+        | 17 => "tur",
+        | 18 => "hrv",
+        | 19 => "zho",
+        | 20 => "urd",
+        | 21 => "hin",
+        | 22 => "tha",
+        | 23 => "kor",
+        | 24 => "lit",
+        | 25 => "pol",
+        | 26 => "hun",
+        | 27 => "est",
+        | 28 => "lav",
+        | 29 => "sme",
+        | 30 => "fao",
+        | 31 => "fas",
+        | 32 => "rus",
+        | 33 => "zho",
+        | 34 => "nld",
+        | 35 => "gle",
+        | 36 => "sqi",
+        | 37 => "ron",
+        | 38 => "ces",
+        | 39 => "slk",
+        | 40 => "slv",
+        | 41 => "yid",
+        | 42 => "srp",
+        | 43 => "mkd",
+        | 44 => "bul",
+        | 45 => "ukr",
+        | 46 => "bel",
+        | 47 => "uzb",
+        | 48 => "kaz",
+        | 49 | 50 => "aze",
+        | 51 => "hye",
+        | 52 => "kat",
+        | 53 => "mol",
+        | 54 => "kir",
+        | 55 => "tgk",
+        | 56 => "tuk",
+        | 57 | 58 => "mon",
+        | 59 => "pus",
+        | 60 => "kur",
+        | 61 => "kas",
+        | 62 => "snd",
+        | 63 => "bod",
+        | 64 => "nep",
+        | 65 => "san",
+        | 66 => "mar",
+        | 67 => "ben",
+        | 68 => "asm",
+        | 69 => "guj",
+        | 70 => "pan",
+        | 71 => "ori",
+        | 72 => "mal",
+        | 73 => "kan",
+        | 74 => "tam",
+        | 75 => "tel",
+        | 76 => "sin",
+        | 77 => "mya",
+        | 78 => "khm",
+        | 79 => "lao",
+        | 80 => "vie",
+        | 81 => "ind",
+        | 82 => "tgl",
+        | 83 | 84 => "msa",
+        | 85 => "amh",
+        | 86 => "tir",
+        | 87 => "orm",
+        | 88 => "som",
+        | 89 => "swa",
+        | 90 => "kin",
+        | 91 => "run",
+        | 92 => "nya",
+        | 93 => "mlg",
+        | 94 => "epo",
+        | 128 => "cym",
+        | 129 => "eus",
+        | 130 => "cat",
+        | 131 => "lat",
+        | 132 => "que",
+        | 133 => "grn",
+        | 134 => "aym",
+        | 135 => "tat",
+        | 136 => "uig",
+        | 137 => "dzo",
+        | 138 => "jav",
+        | 139 => "sun",
+        | 140 => "glg",
+        | 141 => "afr",
+        | 142 => "bre",
+        | 143 => "iku",
+        | 144 => "gla",
+        | 145 => "glv",
+        | 146 => "gle",
+        | 147 => "ton",
+        | 148 => "ell",
+        | 149 => "kal",
+        | 150 => "aze",
+        | _ => return None
+    };
+
+    Some(language)
+}
+
 impl fmt::Display for MediaHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Creation Time: {} (Mac epoch)", self.creation_time)?;
-        writeln!(f, "Modification Time: {} (Mac epoch)", self.modification_time)?;
-        writeln!(f, "Timescale: {} units/second", self.timescale)?;
-        writeln!(f, "Duration: {} units ({:.2} seconds)", self.duration, (self.duration as f64) / (self.timescale as f64))?;
-        writeln!(f, "Language: {}", self.language)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Creation Time: {}", &[&format_mac_time(self.creation_time)]))?;
+        writeln!(f, "{}", tr_fmt("Modification Time: {}", &[&format_mac_time(self.modification_time)]))?;
+        writeln!(f, "{}", tr_fmt("Timescale: {} units/second", &[&self.timescale]))?;
+        writeln!(f, "{}", tr_fmt("Duration: {} units ({} seconds)", &[&self.duration, &format!("{:.2}", (self.duration as f64) / (self.timescale as f64))]))?;
+        match &self.language_note
+        {
+            | Some(note) => writeln!(f, "{}", tr_fmt("Language: {} ({})", &[&self.language, &note]))?,
+            | None => writeln!(f, "{}", tr_fmt("Language: {}", &[&self.language]))?
+        }
         Ok(())
     }
 }
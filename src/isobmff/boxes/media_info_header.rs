@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// Video Media Header Box (vmhd)
 #[derive(Debug, Clone)]
 pub struct VideoMediaHeaderBox
@@ -16,7 +18,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 12
         {
-            return Err("vmhd box too short".to_string());
+            return Err(tr("vmhd box too short").to_string());
         }
 
         let version = data[0];
@@ -25,15 +27,21 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(VideoMediaHeaderBox { version, graphics_mode, opcolor })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "graphics_mode": self.graphics_mode, "opcolor": self.opcolor })
+    }
 }
 
 impl fmt::Display for VideoMediaHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Graphics Mode: {}", self.graphics_mode)?;
-        writeln!(f, "OpColor: R={}, G={}, B={}", self.opcolor[0], self.opcolor[1], self.opcolor[2])?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Graphics Mode: {}", &[&self.graphics_mode]))?;
+        writeln!(f, "{}", tr_fmt("OpColor: R={}, G={}, B={}", &[&self.opcolor[0], &self.opcolor[1], &self.opcolor[2]]))?;
         Ok(())
     }
 }
@@ -53,7 +61,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("smhd box too short".to_string());
+            return Err(tr("smhd box too short").to_string());
         }
 
         let version = data[0];
@@ -62,14 +70,20 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(SoundMediaHeaderBox { version, balance })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "balance": self.balance })
+    }
 }
 
 impl fmt::Display for SoundMediaHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Balance: {:.2} (0=center, -1=full left, 1=full right)", self.balance)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Balance: {} (0=center, -1=full left, 1=full right)", &[&format!("{:.2}", self.balance)]))?;
         Ok(())
     }
 }
@@ -88,19 +102,25 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("nmhd box too short".to_string());
+            return Err(tr("nmhd box too short").to_string());
         }
 
         let version = data[0];
         Ok(NullMediaHeaderBox { version })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version })
+    }
 }
 
 impl fmt::Display for NullMediaHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
         Ok(())
     }
 }
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::i18n::{tr, tr_fmt};
+
 /// iTunes Metadata Mean/Namespace Box (mean)
 #[derive(Debug, Clone)]
 pub struct MetadataMeanBox
@@ -15,7 +17,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("mean box too short".to_string());
+            return Err(tr("mean box too short").to_string());
         }
 
         let version = data[0];
@@ -30,14 +32,20 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(MetadataMeanBox { version, namespace })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "namespace": self.namespace })
+    }
 }
 
 impl fmt::Display for MetadataMeanBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Namespace: {}", self.namespace)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Namespace: {}", &[&self.namespace]))?;
         Ok(())
     }
 }
@@ -57,7 +65,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("name box too short".to_string());
+            return Err(tr("name box too short").to_string());
         }
 
         let version = data[0];
@@ -72,14 +80,109 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(MetadataNameBox { version, name })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "name": self.name })
+    }
 }
 
 impl fmt::Display for MetadataNameBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Name: {}", self.name)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Name: {}", &[&self.name]))?;
+        Ok(())
+    }
+}
+
+/// Key of a QuickTime metadata item
+#[derive(Debug, Clone)]
+pub struct MetadataKey
+{
+    /// Key namespace (usually "mdta" for reverse-DNS names)
+    pub namespace: String,
+    /// Key name (e.g. "com.apple.quicktime.location.ISO6709")
+    pub name:      String
+}
+
+/// QuickTime Metadata Item Keys Box (keys)
+#[derive(Debug, Clone)]
+pub struct MetadataKeysBox
+{
+    pub version: u8,
+    /// Keys in order; ilst items refer to them by 1-based index
+    pub keys:    Vec<MetadataKey>
+}
+
+impl MetadataKeysBox
+{
+    /// Parse keys (QuickTime Metadata Item Keys) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("keys box too short").to_string());
+        }
+
+        let version = data[0];
+        let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+
+        let mut keys = Vec::new();
+        let mut position = 8;
+        for _ in 0..entry_count
+        {
+            if position + 8 > data.len()
+            {
+                break;
+            }
+
+            // Key size includes the size and namespace fields
+            let key_size = u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]) as usize;
+            if key_size < 8 || position + key_size > data.len()
+            {
+                break;
+            }
+
+            keys.push(MetadataKey {
+                namespace: String::from_utf8_lossy(&data[position + 4..position + 8]).to_string(),
+                name:      String::from_utf8_lossy(&data[position + 8..position + key_size]).to_string()
+            });
+            position += key_size;
+        }
+
+        Ok(MetadataKeysBox { version, keys })
+    }
+
+    /// Look up a key name by its 1-based ilst index
+    pub fn key_name(&self, index: u32) -> Option<&str>
+    {
+        let position = (index as usize).checked_sub(1)?;
+        self.keys.get(position).map(|key| key.name.as_str())
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "keys": self.keys.iter().map(|key| serde_json::json!({ "namespace": key.namespace, "name": key.name })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for MetadataKeysBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.keys.len()]))?;
+        for (index, key) in self.keys.iter().enumerate()
+        {
+            writeln!(f, "{}", tr_fmt("Key {}: '{}' {}", &[&(index + 1), &key.namespace, &key.name]))?;
+        }
         Ok(())
     }
 }
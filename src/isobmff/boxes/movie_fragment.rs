@@ -0,0 +1,584 @@
+use std::fmt;
+
+use crate::{
+    bytes::{read_u32_be, read_u64_be},
+    i18n::{tr, tr_fmt}
+};
+
+/// Number of trun samples listed in the text output
+const MAX_DISPLAYED_SAMPLES: usize = 10;
+
+/// tfhd flag: base-data-offset present
+const TFHD_BASE_DATA_OFFSET: u32 = 0x000001;
+/// tfhd flag: sample-description-index present
+const TFHD_SAMPLE_DESCRIPTION_INDEX: u32 = 0x000002;
+/// tfhd flag: default-sample-duration present
+const TFHD_DEFAULT_SAMPLE_DURATION: u32 = 0x000008;
+/// tfhd flag: default-sample-size present
+const TFHD_DEFAULT_SAMPLE_SIZE: u32 = 0x000010;
+/// tfhd flag: default-sample-flags present
+const TFHD_DEFAULT_SAMPLE_FLAGS: u32 = 0x000020;
+/// tfhd flag: the fragment has no samples
+const TFHD_DURATION_IS_EMPTY: u32 = 0x010000;
+/// tfhd flag: data offsets are relative to the enclosing moof
+const TFHD_DEFAULT_BASE_IS_MOOF: u32 = 0x020000;
+
+/// trun flag: data-offset present
+const TRUN_DATA_OFFSET: u32 = 0x000001;
+/// trun flag: first-sample-flags present
+const TRUN_FIRST_SAMPLE_FLAGS: u32 = 0x000004;
+/// trun flag: sample-duration present
+const TRUN_SAMPLE_DURATION: u32 = 0x000100;
+/// trun flag: sample-size present
+const TRUN_SAMPLE_SIZE: u32 = 0x000200;
+/// trun flag: sample-flags present
+const TRUN_SAMPLE_FLAGS: u32 = 0x000400;
+/// trun flag: sample-composition-time-offset present
+const TRUN_SAMPLE_COMPOSITION_TIME_OFFSET: u32 = 0x000800;
+
+/// Version and 24-bit flags of a full box
+fn version_and_flags(data: &[u8]) -> (u8, u32)
+{
+    (data[0], u32::from_be_bytes([0, data[1], data[2], data[3]]))
+}
+
+/// Describe sample flags (ISO/IEC 14496-12 8.8.3.1): sync state and dependencies
+pub fn describe_sample_flags(flags: u32) -> String
+{
+    let mut parts = Vec::new();
+    if flags & 0x0001_0000 != 0
+    {
+        parts.push("non-sync");
+    }
+    else
+    {
+        parts.push("sync");
+    }
+    match (flags >> 24) & 0x03
+    {
+        | 1 => parts.push("depends on others"),
+        | 2 => parts.push("independent"),
+        | _ =>
+        {}
+    }
+    match (flags >> 22) & 0x03
+    {
+        | 1 => parts.push("depended on"),
+        | 2 => parts.push("disposable"),
+        | _ =>
+        {}
+    }
+    match (flags >> 26) & 0x03
+    {
+        | 1 | 3 => parts.push("leading"),
+        | _ =>
+        {}
+    }
+    format!("0x{:08X} ({})", flags, parts.join(", "))
+}
+
+/// Format a value in timescale units with seconds if the timescale is known, e.g. "90000 (1.000 s)"
+fn with_seconds(value: u64, timescale: Option<u32>) -> String
+{
+    match timescale.filter(|&timescale| timescale > 0)
+    {
+        | Some(timescale) => format!("{} ({:.3} s)", value, value as f64 / timescale as f64),
+        | None => value.to_string()
+    }
+}
+
+/// Timescale and sample defaults of the track of a track fragment
+///
+/// Attached once the whole tree is parsed, from the movie box of the file or the initialization
+/// segment given for a media segment; the tfhd defaults take precedence over the trex defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FragmentContext
+{
+    /// Media timescale of the track (mdhd)
+    pub timescale:               Option<u32>,
+    pub default_sample_duration: Option<u32>,
+    pub default_sample_size:     Option<u32>
+}
+
+/// Movie Fragment Header Box (mfhd)
+#[derive(Debug, Clone)]
+pub struct MovieFragmentHeaderBox
+{
+    pub sequence_number: u32
+}
+
+impl MovieFragmentHeaderBox
+{
+    /// Parse mfhd (Movie Fragment Header) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("mfhd box too short").to_string());
+        }
+
+        Ok(MovieFragmentHeaderBox { sequence_number: read_u32_be(data, 4) })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "sequence_number": self.sequence_number })
+    }
+}
+
+impl fmt::Display for MovieFragmentHeaderBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Sequence Number: {}", &[&self.sequence_number]))?;
+        Ok(())
+    }
+}
+
+/// Track Extends Box (trex): sample defaults of a track for all movie fragments
+#[derive(Debug, Clone)]
+pub struct TrackExtendsBox
+{
+    pub track_id:                         u32,
+    pub default_sample_description_index: u32,
+    pub default_sample_duration:          u32,
+    pub default_sample_size:              u32,
+    pub default_sample_flags:             u32
+}
+
+impl TrackExtendsBox
+{
+    /// Parse trex (Track Extends) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 24
+        {
+            return Err(tr("trex box too short").to_string());
+        }
+
+        Ok(TrackExtendsBox {
+            track_id:                         read_u32_be(data, 4),
+            default_sample_description_index: read_u32_be(data, 8),
+            default_sample_duration:          read_u32_be(data, 12),
+            default_sample_size:              read_u32_be(data, 16),
+            default_sample_flags:             read_u32_be(data, 20)
+        })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "track_id": self.track_id,
+            "default_sample_description_index": self.default_sample_description_index,
+            "default_sample_duration": self.default_sample_duration,
+            "default_sample_size": self.default_sample_size,
+            "default_sample_flags": self.default_sample_flags
+        })
+    }
+}
+
+impl fmt::Display for TrackExtendsBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Track ID: {}", &[&self.track_id]))?;
+        writeln!(f, "{}", tr_fmt("Default Sample Description Index: {}", &[&self.default_sample_description_index]))?;
+        writeln!(f, "{}", tr_fmt("Default Sample Duration: {}", &[&self.default_sample_duration]))?;
+        writeln!(f, "{}", tr_fmt("Default Sample Size: {} bytes", &[&self.default_sample_size]))?;
+        writeln!(f, "{}", tr_fmt("Default Sample Flags: {}", &[&describe_sample_flags(self.default_sample_flags)]))?;
+        Ok(())
+    }
+}
+
+/// Track Fragment Header Box (tfhd)
+#[derive(Debug, Clone)]
+pub struct TrackFragmentHeaderBox
+{
+    pub flags:                    u32,
+    pub track_id:                 u32,
+    pub base_data_offset:         Option<u64>,
+    pub sample_description_index: Option<u32>,
+    pub default_sample_duration:  Option<u32>,
+    pub default_sample_size:      Option<u32>,
+    pub default_sample_flags:     Option<u32>
+}
+
+impl TrackFragmentHeaderBox
+{
+    /// Parse tfhd (Track Fragment Header) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("tfhd box too short").to_string());
+        }
+
+        let (_, flags) = version_and_flags(data);
+
+        // The 64-bit base data offset comes first, the optional 32-bit fields follow in flag order
+        let mut position = 8;
+        let base_data_offset = if flags & TFHD_BASE_DATA_OFFSET != 0
+        {
+            if data.len() < 16
+            {
+                return Err(tr("tfhd box truncated").to_string());
+            }
+            position = 16;
+            Some(read_u64_be(data, 8))
+        }
+        else
+        {
+            None
+        };
+        let mut optional_u32 = |flag: u32| -> Result<Option<u32>, String> {
+            if flags & flag == 0
+            {
+                return Ok(None);
+            }
+            if position + 4 > data.len()
+            {
+                return Err(tr("tfhd box truncated").to_string());
+            }
+            position += 4;
+            Ok(Some(read_u32_be(data, position - 4)))
+        };
+
+        Ok(TrackFragmentHeaderBox {
+            flags,
+            track_id: read_u32_be(data, 4),
+            base_data_offset,
+            sample_description_index: optional_u32(TFHD_SAMPLE_DESCRIPTION_INDEX)?,
+            default_sample_duration: optional_u32(TFHD_DEFAULT_SAMPLE_DURATION)?,
+            default_sample_size: optional_u32(TFHD_DEFAULT_SAMPLE_SIZE)?,
+            default_sample_flags: optional_u32(TFHD_DEFAULT_SAMPLE_FLAGS)?
+        })
+    }
+
+    /// Whether data offsets are relative to the enclosing moof box
+    pub fn default_base_is_moof(&self) -> bool
+    {
+        self.flags & TFHD_DEFAULT_BASE_IS_MOOF != 0
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "flags": self.flags,
+            "track_id": self.track_id,
+            "base_data_offset": self.base_data_offset,
+            "sample_description_index": self.sample_description_index,
+            "default_sample_duration": self.default_sample_duration,
+            "default_sample_size": self.default_sample_size,
+            "default_sample_flags": self.default_sample_flags
+        })
+    }
+}
+
+impl fmt::Display for TrackFragmentHeaderBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Flags: {}", &[&format!("0x{:06X}", self.flags)]))?;
+        writeln!(f, "{}", tr_fmt("Track ID: {}", &[&self.track_id]))?;
+        if let Some(base_data_offset) = self.base_data_offset
+        {
+            writeln!(f, "{}", tr_fmt("Base Data Offset: {}", &[&format!("0x{:08X}", base_data_offset)]))?;
+        }
+        if self.default_base_is_moof() == true
+        {
+            writeln!(f, "{}", tr("Base Data Offset: start of moof (default-base-is-moof)"))?;
+        }
+        if let Some(index) = self.sample_description_index
+        {
+            writeln!(f, "{}", tr_fmt("Sample Description Index: {}", &[&index]))?;
+        }
+        if let Some(duration) = self.default_sample_duration
+        {
+            writeln!(f, "{}", tr_fmt("Default Sample Duration: {}", &[&duration]))?;
+        }
+        if let Some(size) = self.default_sample_size
+        {
+            writeln!(f, "{}", tr_fmt("Default Sample Size: {} bytes", &[&size]))?;
+        }
+        if let Some(flags) = self.default_sample_flags
+        {
+            writeln!(f, "{}", tr_fmt("Default Sample Flags: {}", &[&describe_sample_flags(flags)]))?;
+        }
+        if self.flags & TFHD_DURATION_IS_EMPTY != 0
+        {
+            writeln!(f, "{}", tr("Duration Is Empty: true"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Track Fragment Base Media Decode Time Box (tfdt)
+#[derive(Debug, Clone)]
+pub struct TrackFragmentDecodeTimeBox
+{
+    pub version:                u8,
+    /// Decode time of the first sample in media timescale units
+    pub base_media_decode_time: u64,
+    /// Media timescale of the track, set once the whole tree is parsed
+    pub timescale:              Option<u32>
+}
+
+impl TrackFragmentDecodeTimeBox
+{
+    /// Parse tfdt (Track Fragment Decode Time) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("tfdt box too short").to_string());
+        }
+
+        let version = data[0];
+        let base_media_decode_time = if version == 1
+        {
+            if data.len() < 12
+            {
+                return Err(tr("tfdt version 1 box too short").to_string());
+            }
+            read_u64_be(data, 4)
+        }
+        else
+        {
+            read_u32_be(data, 4) as u64
+        };
+
+        Ok(TrackFragmentDecodeTimeBox { version, base_media_decode_time, timescale: None })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "base_media_decode_time": self.base_media_decode_time })
+    }
+}
+
+impl fmt::Display for TrackFragmentDecodeTimeBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Base Media Decode Time: {}", &[&with_seconds(self.base_media_decode_time, self.timescale)]))?;
+        Ok(())
+    }
+}
+
+/// Sample of a Track Fragment Run; fields absent from the run fall back to the tfhd/trex defaults
+#[derive(Debug, Clone, Default)]
+pub struct TrackRunSample
+{
+    pub duration:                Option<u32>,
+    pub size:                    Option<u32>,
+    pub flags:                   Option<u32>,
+    pub composition_time_offset: Option<i64>
+}
+
+/// Track Fragment Run Box (trun)
+#[derive(Debug, Clone)]
+pub struct TrackRunBox
+{
+    pub version:            u8,
+    pub flags:              u32,
+    pub sample_count:       u32,
+    /// Offset of the sample data relative to the base data offset
+    pub data_offset:        Option<i32>,
+    pub first_sample_flags: Option<u32>,
+    pub samples:            Vec<TrackRunSample>,
+    /// Timescale and sample defaults of the track, set once the whole tree is parsed
+    pub context:            Option<FragmentContext>
+}
+
+impl TrackRunBox
+{
+    /// Parse trun (Track Fragment Run) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("trun box too short").to_string());
+        }
+
+        let (version, flags) = version_and_flags(data);
+        let sample_count = read_u32_be(data, 4);
+        let mut position = 8;
+
+        let data_offset = if flags & TRUN_DATA_OFFSET != 0 && position + 4 <= data.len()
+        {
+            position += 4;
+            Some(read_u32_be(data, position - 4) as i32)
+        }
+        else
+        {
+            None
+        };
+        let first_sample_flags = if flags & TRUN_FIRST_SAMPLE_FLAGS != 0 && position + 4 <= data.len()
+        {
+            position += 4;
+            Some(read_u32_be(data, position - 4))
+        }
+        else
+        {
+            None
+        };
+
+        // Each sample record holds the fields selected by the flags
+        let fields = [TRUN_SAMPLE_DURATION, TRUN_SAMPLE_SIZE, TRUN_SAMPLE_FLAGS, TRUN_SAMPLE_COMPOSITION_TIME_OFFSET];
+        let record_size = fields.iter().filter(|&&field| flags & field != 0).count() * 4;
+
+        let mut samples = Vec::new();
+        if record_size > 0
+        {
+            for record in data[position.min(data.len())..].chunks_exact(record_size).take(sample_count as usize)
+            {
+                let mut sample = TrackRunSample::default();
+                let mut field_position = 0;
+                let mut next_field = || {
+                    field_position += 4;
+                    read_u32_be(record, field_position - 4)
+                };
+                if flags & TRUN_SAMPLE_DURATION != 0
+                {
+                    sample.duration = Some(next_field());
+                }
+                if flags & TRUN_SAMPLE_SIZE != 0
+                {
+                    sample.size = Some(next_field());
+                }
+                if flags & TRUN_SAMPLE_FLAGS != 0
+                {
+                    sample.flags = Some(next_field());
+                }
+                if flags & TRUN_SAMPLE_COMPOSITION_TIME_OFFSET != 0
+                {
+                    // Signed in version 1
+                    let offset = next_field();
+                    sample.composition_time_offset = Some(
+                        if version == 0
+                        {
+                            offset as i64
+                        }
+                        else
+                        {
+                            offset as i32 as i64
+                        }
+                    );
+                }
+                samples.push(sample);
+            }
+        }
+
+        Ok(TrackRunBox { version, flags, sample_count, data_offset, first_sample_flags, samples, context: None })
+    }
+
+    /// Sum of the sample sizes stored in the run (None if the run relies on default sizes)
+    pub fn total_size(&self) -> Option<u64>
+    {
+        (self.flags & TRUN_SAMPLE_SIZE != 0).then(|| self.samples.iter().filter_map(|sample| sample.size).map(|size| size as u64).sum())
+    }
+
+    /// Sum of the sample durations stored in the run (None if the run relies on default durations)
+    pub fn total_duration(&self) -> Option<u64>
+    {
+        (self.flags & TRUN_SAMPLE_DURATION != 0).then(|| self.samples.iter().filter_map(|sample| sample.duration).map(|duration| duration as u64).sum())
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "flags": self.flags,
+            "sample_count": self.sample_count,
+            "data_offset": self.data_offset,
+            "first_sample_flags": self.first_sample_flags,
+            "samples": self.samples.iter().map(|sample| serde_json::json!({
+                "duration": sample.duration,
+                "size": sample.size,
+                "flags": sample.flags,
+                "composition_time_offset": sample.composition_time_offset
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for TrackRunBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Flags: {}", &[&format!("0x{:06X}", self.flags)]))?;
+        writeln!(f, "{}", tr_fmt("Sample Count: {}", &[&self.sample_count]))?;
+        if let Some(data_offset) = self.data_offset
+        {
+            writeln!(f, "{}", tr_fmt("Data Offset: {}", &[&data_offset]))?;
+        }
+        if let Some(flags) = self.first_sample_flags
+        {
+            writeln!(f, "{}", tr_fmt("First Sample Flags: {}", &[&describe_sample_flags(flags)]))?;
+        }
+        // Runs without per-sample durations or sizes use the defaults of their track for every sample
+        let context = self.context.unwrap_or_default();
+        if let Some(total_duration) = self.total_duration()
+        {
+            writeln!(f, "{}", tr_fmt("Total Duration: {}", &[&with_seconds(total_duration, context.timescale)]))?;
+        }
+        else if let Some(duration) = context.default_sample_duration
+        {
+            writeln!(
+                f,
+                "{}",
+                tr_fmt("Total Duration: {} from the default sample duration {}", &[
+                    &with_seconds(self.sample_count as u64 * duration as u64, context.timescale),
+                    &duration
+                ])
+            )?;
+        }
+        if let Some(total_size) = self.total_size()
+        {
+            writeln!(f, "{}", tr_fmt("Total Size: {} bytes", &[&total_size]))?;
+        }
+        else if let Some(size) = context.default_sample_size
+        {
+            writeln!(f, "{}", tr_fmt("Total Size: {} bytes from the default sample size {}", &[&(self.sample_count as u64 * size as u64), &size]))?;
+        }
+
+        for (index, sample) in self.samples.iter().take(MAX_DISPLAYED_SAMPLES).enumerate()
+        {
+            let mut fields = Vec::new();
+            if let Some(duration) = sample.duration
+            {
+                fields.push(tr_fmt("duration {}", &[&duration]));
+            }
+            if let Some(size) = sample.size
+            {
+                fields.push(tr_fmt("size {}", &[&size]));
+            }
+            if let Some(flags) = sample.flags
+            {
+                fields.push(tr_fmt("flags {}", &[&describe_sample_flags(flags)]));
+            }
+            if let Some(offset) = sample.composition_time_offset
+            {
+                fields.push(tr_fmt("composition offset {}", &[&offset]));
+            }
+            writeln!(f, "{}", tr_fmt("Sample {}: {}", &[&(index + 1), &fields.join(", ")]))?;
+        }
+        if self.samples.len() > MAX_DISPLAYED_SAMPLES
+        {
+            writeln!(f, "{}", tr_fmt("... {} more samples", &[&(self.samples.len() - MAX_DISPLAYED_SAMPLES)]))?;
+        }
+        if self.samples.len() < self.sample_count as usize &&
+            self.flags & (TRUN_SAMPLE_DURATION | TRUN_SAMPLE_SIZE | TRUN_SAMPLE_FLAGS | TRUN_SAMPLE_COMPOSITION_TIME_OFFSET) != 0
+        {
+            writeln!(f, "{}", tr_fmt("Parsed Samples: {} (box truncated)", &[&self.samples.len()]))?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::timestamps::format_mac_time
+};
+
 /// Movie Header Box (mvhd)
 #[derive(Debug, Clone)]
 pub struct MovieHeaderBox
@@ -20,7 +25,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("mvhd box too short".to_string());
+            return Err(tr("mvhd box too short").to_string());
         }
 
         let version = data[0];
@@ -31,7 +36,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 64-bit version
             if data.len() < 36
             {
-                return Err("mvhd version 1 box too short".to_string());
+                return Err(tr("mvhd version 1 box too short").to_string());
             }
 
             let creation = u64::from_be_bytes([data[4], data[5], data[6], data[7], data[8], data[9], data[10], data[11]]);
@@ -46,7 +51,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 32-bit version
             if data.len() < 24
             {
-                return Err("mvhd version 0 box too short".to_string());
+                return Err(tr("mvhd version 0 box too short").to_string());
             }
 
             let creation = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as u64;
@@ -69,7 +74,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         if data.len() < rate_offset + 8
         {
-            return Err("mvhd box too short for rate/volume".to_string());
+            return Err(tr("mvhd box too short for rate/volume").to_string());
         }
 
         let rate_fixed = i32::from_be_bytes([data[rate_offset], data[rate_offset + 1], data[rate_offset + 2], data[rate_offset + 3]]);
@@ -80,19 +85,33 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(MovieHeaderBox { version, creation_time, modification_time, timescale, duration, rate, volume })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "creation_time": self.creation_time,
+            "modification_time": self.modification_time,
+            "timescale": self.timescale,
+            "duration": self.duration,
+            "rate": self.rate,
+            "volume": self.volume
+        })
+    }
 }
 
 impl fmt::Display for MovieHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Creation Time: {} (Mac epoch)", self.creation_time)?;
-        writeln!(f, "Modification Time: {} (Mac epoch)", self.modification_time)?;
-        writeln!(f, "Timescale: {} units/second", self.timescale)?;
-        writeln!(f, "Duration: {} units ({:.2} seconds)", self.duration, (self.duration as f64) / (self.timescale as f64))?;
-        writeln!(f, "Preferred Rate: {:.2}", self.rate)?;
-        writeln!(f, "Preferred Volume: {:.2}", self.volume)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Creation Time: {}", &[&format_mac_time(self.creation_time)]))?;
+        writeln!(f, "{}", tr_fmt("Modification Time: {}", &[&format_mac_time(self.modification_time)]))?;
+        writeln!(f, "{}", tr_fmt("Timescale: {} units/second", &[&self.timescale]))?;
+        writeln!(f, "{}", tr_fmt("Duration: {} units ({} seconds)", &[&self.duration, &format!("{:.2}", (self.duration as f64) / (self.timescale as f64))]))?;
+        writeln!(f, "{}", tr_fmt("Preferred Rate: {}", &[&format!("{:.2}", self.rate)]))?;
+        writeln!(f, "{}", tr_fmt("Preferred Volume: {}", &[&format!("{:.2}", self.volume)]))?;
         Ok(())
     }
 }
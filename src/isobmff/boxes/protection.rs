@@ -0,0 +1,702 @@
+use std::fmt;
+
+use crate::{
+    bytes::{read_u32_be, read_u64_be},
+    hashes::to_hex,
+    i18n::{tr, tr_fmt},
+    isobmff::boxes::{sample_entry::child_boxes_at, user_extension::format_uuid}
+};
+
+/// Number of per-sample entries listed in the text output
+const MAX_DISPLAYED_SAMPLES: usize = 10;
+
+/// Read a 16-byte ID (system ID or KID) at `position`
+fn read_id(data: &[u8], position: usize) -> [u8; 16]
+{
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&data[position..position + 16]);
+    id
+}
+
+/// Format bytes as uppercase hex
+fn hex(bytes: &[u8]) -> String
+{
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Name of a DRM system from its PSSH system ID
+pub fn system_id_name(system_id: &[u8; 16]) -> &'static str
+{
+    match format_uuid(system_id).as_str()
+    {
+        | "edef8ba9-79d6-4ace-a3c8-27dcd51d21ed" => "Widevine",
+        | "9a04f079-9840-4286-ab92-e65be0885f95" => "PlayReady",
+        | "94ce86fb-07ff-4f43-adb8-93d2fa968ca2" => "FairPlay",
+        | "1077efec-c0b2-4d02-ace3-3c1e52e2fb4b" => "W3C Common (ClearKey)",
+        | "e2719d58-a985-b3c9-781a-b030af78d30e" => "ClearKey (DASH-IF)",
+        | "5e629af5-38da-4063-8977-97ffbd9902d4" => "Marlin",
+        | "adb41c24-2dbf-4a6d-958b-4457c0d27b95" => "Nagra",
+        | "f239e769-efa3-4850-9c16-a903c6932efb" => "Adobe Primetime",
+        | "3d5e6d35-9b9a-41e8-b843-dd3c6e72c42c" => "ChinaDRM",
+        | "80a6be7e-1448-4c37-9e70-d5aebe04c8d2" => "Irdeto",
+        | "644fe7b5-260f-4fad-949a-0762ffb054b4" => "CMLA (OMA DRM)",
+        | _ => "Unknown DRM System"
+    }
+}
+
+/// Describe a protection scheme type
+fn scheme_name(scheme_type: &str) -> &'static str
+{
+    match scheme_type
+    {
+        | "cenc" => "AES-CTR full sample encryption",
+        | "cens" => "AES-CTR subsample pattern encryption",
+        | "cbc1" => "AES-CBC full sample encryption",
+        | "cbcs" => "AES-CBC subsample pattern encryption",
+        | "piff" => "PIFF (Smooth Streaming)",
+        | "itun" => "iTunes FairPlay",
+        | _ => "Unknown Scheme"
+    }
+}
+
+/// Protection System Specific Header Box (pssh)
+#[derive(Debug, Clone)]
+pub struct ProtectionSystemHeaderBox
+{
+    pub version:   u8,
+    pub system_id: [u8; 16],
+    /// KIDs the header applies to (version 1)
+    pub key_ids:   Vec<[u8; 16]>,
+    /// DRM system specific data (e.g. a Widevine protobuf or a PlayReady object)
+    pub data:      Vec<u8>
+}
+
+impl ProtectionSystemHeaderBox
+{
+    /// Parse pssh (Protection System Specific Header) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 24
+        {
+            return Err(tr("pssh box too short").to_string());
+        }
+
+        let version = data[0];
+        let system_id = read_id(data, 4);
+        let mut position = 20;
+        let mut key_ids = Vec::new();
+        if version > 0
+        {
+            let kid_count = read_u32_be(data, position) as usize;
+            position += 4;
+            if data.len() < position + kid_count.saturating_mul(16) + 4
+            {
+                return Err(tr("pssh box too short for its KIDs").to_string());
+            }
+            key_ids = (0..kid_count).map(|index| read_id(data, position + index * 16)).collect();
+            position += kid_count * 16;
+        }
+        let data_size = read_u32_be(data, position) as usize;
+        let system_data = data.get(position + 4..position + 4 + data_size).ok_or(tr("pssh data exceeds the box"))?.to_vec();
+
+        Ok(ProtectionSystemHeaderBox { version, system_id, key_ids, data: system_data })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "system_id": to_hex(&self.system_id),
+            "key_ids": self.key_ids.iter().map(|key_id| to_hex(key_id)).collect::<Vec<_>>(),
+            "data_size": self.data.len()
+        })
+    }
+}
+
+impl fmt::Display for ProtectionSystemHeaderBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("System ID: {} ({})", &[&format_uuid(&self.system_id), &system_id_name(&self.system_id)]))?;
+        for key_id in &self.key_ids
+        {
+            writeln!(f, "{}", tr_fmt("KID: {}", &[&format_uuid(key_id)]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Data Size: {} bytes", &[&self.data.len()]))?;
+        Ok(())
+    }
+}
+
+/// Track Encryption Box (tenc): default protection parameters of a track
+#[derive(Debug, Clone)]
+pub struct TrackEncryptionBox
+{
+    pub version:          u8,
+    /// Encrypted and skipped 16-byte blocks of the pattern (version 1, cens/cbcs)
+    pub crypt_byte_block: u8,
+    pub skip_byte_block:  u8,
+    pub is_protected:     bool,
+    /// Size of the per-sample IVs in senc (0: constant IV)
+    pub per_sample_iv:    u8,
+    pub key_id:           [u8; 16],
+    pub constant_iv:      Vec<u8>
+}
+
+impl TrackEncryptionBox
+{
+    /// Parse tenc (Track Encryption) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 24
+        {
+            return Err(tr("tenc box too short").to_string());
+        }
+
+        let version = data[0];
+        let (crypt_byte_block, skip_byte_block) = if version > 0
+        {
+            (data[5] >> 4, data[5] & 0x0F)
+        }
+        else
+        {
+            (0, 0)
+        };
+        let is_protected = data[6] != 0;
+        let per_sample_iv = data[7];
+        let key_id = read_id(data, 8);
+        let constant_iv = match data.get(24)
+        {
+            | Some(&size) if is_protected == true && per_sample_iv == 0 => data.get(25..25 + size as usize).ok_or(tr("tenc constant IV exceeds the box"))?.to_vec(),
+            | _ => Vec::new()
+        };
+
+        Ok(TrackEncryptionBox { version, crypt_byte_block, skip_byte_block, is_protected, per_sample_iv, key_id, constant_iv })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "crypt_byte_block": self.crypt_byte_block,
+            "skip_byte_block": self.skip_byte_block,
+            "is_protected": self.is_protected,
+            "per_sample_iv_size": self.per_sample_iv,
+            "key_id": to_hex(&self.key_id),
+            "constant_iv": to_hex(&self.constant_iv)
+        })
+    }
+}
+
+impl fmt::Display for TrackEncryptionBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Protected: {}", &[&self.is_protected]))?;
+        writeln!(f, "{}", tr_fmt("Default KID: {}", &[&format_uuid(&self.key_id)]))?;
+        match self.per_sample_iv
+        {
+            | 0 => writeln!(f, "{}", tr_fmt("Per-Sample IV Size: 0 (constant IV {})", &[&hex(&self.constant_iv)]))?,
+            | size => writeln!(f, "{}", tr_fmt("Per-Sample IV Size: {} bytes", &[&size]))?
+        }
+        if self.crypt_byte_block > 0 || self.skip_byte_block > 0
+        {
+            writeln!(f, "{}", tr_fmt("Pattern: {} encrypted, {} skipped blocks", &[&self.crypt_byte_block, &self.skip_byte_block]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Clear and encrypted byte counts of one subsample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Subsample
+{
+    pub clear_bytes:     u16,
+    pub protected_bytes: u32
+}
+
+/// Encryption parameters of one sample from a senc box
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleEncryptionEntry
+{
+    pub iv:         Vec<u8>,
+    pub subsamples: Vec<Subsample>
+}
+
+/// Sample Encryption Box (senc)
+#[derive(Debug, Clone)]
+pub struct SampleEncryptionBox
+{
+    pub version:      u8,
+    pub flags:        u32,
+    pub sample_count: u32,
+    /// Per-sample IV size, taken from the box (PIFF override) or inferred from its size
+    pub iv_size:      Option<u8>,
+    pub samples:      Vec<SampleEncryptionEntry>
+}
+
+/// senc flag: the box overrides the track encryption parameters (PIFF)
+const SENC_OVERRIDE_TRACK_ENCRYPTION: u32 = 0x000001;
+/// senc flag: samples are split into clear and encrypted subsamples
+const SENC_USE_SUBSAMPLE_ENCRYPTION: u32 = 0x000002;
+
+impl SampleEncryptionBox
+{
+    /// Parse senc (Sample Encryption) box
+    ///
+    /// The IV size is defined by tenc or seig; without it, the sizes 8, 16 and 0 are tried and the
+    /// first one whose samples exactly fill the box is used.
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("senc box too short").to_string());
+        }
+
+        let version = data[0];
+        let flags = u32::from_be_bytes([0, data[1], data[2], data[3]]);
+        let mut position = 4;
+        let mut declared_iv_size = None;
+        if flags & SENC_OVERRIDE_TRACK_ENCRYPTION != 0
+        {
+            // AlgorithmID (3 bytes), IV size, KID
+            if data.len() < position + 20 + 4
+            {
+                return Err(tr("senc box too short for its track encryption override").to_string());
+            }
+            declared_iv_size = Some(data[position + 3]);
+            position += 20;
+        }
+        let sample_count = read_u32_be(data, position);
+        position += 4;
+
+        let candidates = match declared_iv_size
+        {
+            | Some(size) => vec![size],
+            | None => vec![8, 16, 0]
+        };
+        let subsamples = flags & SENC_USE_SUBSAMPLE_ENCRYPTION != 0;
+        for iv_size in candidates
+        {
+            if let Some(samples) = parse_sample_entries(&data[position..], sample_count, iv_size, subsamples)
+            {
+                return Ok(SampleEncryptionBox { version, flags, sample_count, iv_size: Some(iv_size), samples });
+            }
+        }
+
+        Ok(SampleEncryptionBox { version, flags, sample_count, iv_size: None, samples: Vec::new() })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "flags": self.flags,
+            "sample_count": self.sample_count,
+            "iv_size": self.iv_size,
+            "samples": self.samples.iter().map(|sample| serde_json::json!({
+                "iv": to_hex(&sample.iv),
+                "subsamples": sample.subsamples.iter().map(|subsample| serde_json::json!({
+                    "clear_bytes": subsample.clear_bytes,
+                    "protected_bytes": subsample.protected_bytes
+                })).collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Parse the sample entries of a senc box with the given IV size, None unless they exactly fill `data`
+fn parse_sample_entries(data: &[u8], sample_count: u32, iv_size: u8, subsamples: bool) -> Option<Vec<SampleEncryptionEntry>>
+{
+    let mut samples = Vec::new();
+    let mut position = 0;
+    for _ in 0..sample_count
+    {
+        let iv = data.get(position..position + iv_size as usize)?.to_vec();
+        position += iv_size as usize;
+        let mut entry = SampleEncryptionEntry { iv, subsamples: Vec::new() };
+        if subsamples == true
+        {
+            let count = u16::from_be_bytes(data.get(position..position + 2)?.try_into().ok()?) as usize;
+            position += 2;
+            for _ in 0..count
+            {
+                let subsample = data.get(position..position + 6)?;
+                entry.subsamples.push(Subsample { clear_bytes: u16::from_be_bytes([subsample[0], subsample[1]]), protected_bytes: read_u32_be(subsample, 2) });
+                position += 6;
+            }
+        }
+        samples.push(entry);
+    }
+    (position == data.len()).then_some(samples)
+}
+
+impl fmt::Display for SampleEncryptionBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Flags: {} (Subsample encryption: {})", &[&format!("0x{:06X}", self.flags), &(self.flags & SENC_USE_SUBSAMPLE_ENCRYPTION != 0)]))?;
+        writeln!(f, "{}", tr_fmt("Sample Count: {}", &[&self.sample_count]))?;
+        let Some(iv_size) = self.iv_size
+        else
+        {
+            writeln!(f, "{}", tr("Per-Sample IV Size: unknown (entries do not fit an IV size of 0, 8 or 16 bytes)"))?;
+            return Ok(());
+        };
+        writeln!(f, "{}", tr_fmt("Per-Sample IV Size: {} bytes", &[&iv_size]))?;
+
+        if self.flags & SENC_USE_SUBSAMPLE_ENCRYPTION != 0
+        {
+            let counts = self.samples.iter().map(|sample| sample.subsamples.len());
+            if let (Some(min), Some(max)) = (counts.clone().min(), counts.max())
+            {
+                writeln!(f, "{}", tr_fmt("Subsamples per Sample: {} to {}", &[&min, &max]))?;
+            }
+            let subsamples = self.samples.iter().flat_map(|sample| sample.subsamples.iter());
+            let clear: u64 = subsamples.clone().map(|subsample| subsample.clear_bytes as u64).sum();
+            let protected: u64 = subsamples.map(|subsample| subsample.protected_bytes as u64).sum();
+            writeln!(f, "{}", tr_fmt("Clear Bytes: {}, Protected Bytes: {}", &[&clear, &protected]))?;
+        }
+
+        for (index, sample) in self.samples.iter().take(MAX_DISPLAYED_SAMPLES).enumerate()
+        {
+            let iv = if sample.iv.is_empty() == true
+            {
+                "-".to_string()
+            }
+            else
+            {
+                hex(&sample.iv)
+            };
+            match sample.subsamples.is_empty()
+            {
+                | true => writeln!(f, "  {}", tr_fmt("Sample {}: IV {}", &[&(index + 1), &iv]))?,
+                | false => writeln!(f, "  {}", tr_fmt("Sample {}: IV {}, {} subsamples", &[&(index + 1), &iv, &sample.subsamples.len()]))?
+            }
+        }
+        if self.samples.len() > MAX_DISPLAYED_SAMPLES
+        {
+            writeln!(f, "  {}", tr_fmt("... {} more samples", &[&(self.samples.len() - MAX_DISPLAYED_SAMPLES)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Sample Auxiliary Information Sizes Box (saiz)
+#[derive(Debug, Clone)]
+pub struct SampleAuxiliaryInfoSizesBox
+{
+    pub version:                  u8,
+    /// Type of the auxiliary information (e.g. the protection scheme), if given
+    pub aux_info_type:            Option<String>,
+    /// Size of every sample's information (0: sizes listed per sample)
+    pub default_sample_info_size: u8,
+    pub sample_count:             u32,
+    pub sample_info_sizes:        Vec<u8>
+}
+
+impl SampleAuxiliaryInfoSizesBox
+{
+    /// Parse saiz (Sample Auxiliary Information Sizes) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let (version, aux_info_type, position) = aux_info_header(data, "saiz")?;
+        if data.len() < position + 5
+        {
+            return Err(tr("saiz box too short").to_string());
+        }
+        let default_sample_info_size = data[position];
+        let sample_count = read_u32_be(data, position + 1);
+        let sample_info_sizes = match default_sample_info_size
+        {
+            | 0 => data[position + 5..].iter().take(sample_count as usize).copied().collect(),
+            | _ => Vec::new()
+        };
+
+        Ok(SampleAuxiliaryInfoSizesBox { version, aux_info_type, default_sample_info_size, sample_count, sample_info_sizes })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "aux_info_type": self.aux_info_type,
+            "default_sample_info_size": self.default_sample_info_size,
+            "sample_count": self.sample_count,
+            "sample_info_sizes": self.sample_info_sizes
+        })
+    }
+}
+
+impl fmt::Display for SampleAuxiliaryInfoSizesBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        if let Some(ref aux_info_type) = self.aux_info_type
+        {
+            writeln!(f, "{}", tr_fmt("Auxiliary Information Type: '{}'", &[&aux_info_type]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Sample Count: {}", &[&self.sample_count]))?;
+        match self.default_sample_info_size
+        {
+            | 0 =>
+            {
+                let sizes = self.sample_info_sizes.iter();
+                if let (Some(min), Some(max)) = (sizes.clone().min(), sizes.max())
+                {
+                    writeln!(f, "{}", tr_fmt("Sample Info Sizes: {} to {} bytes", &[&min, &max]))?;
+                }
+            }
+            | size => writeln!(f, "{}", tr_fmt("Sample Info Size: {} bytes (constant)", &[&size]))?
+        }
+        Ok(())
+    }
+}
+
+/// Sample Auxiliary Information Offsets Box (saio)
+#[derive(Debug, Clone)]
+pub struct SampleAuxiliaryInfoOffsetsBox
+{
+    pub version:       u8,
+    pub aux_info_type: Option<String>,
+    /// Offsets of the information (relative to the moof in fragments, to the file otherwise)
+    pub offsets:       Vec<u64>
+}
+
+impl SampleAuxiliaryInfoOffsetsBox
+{
+    /// Parse saio (Sample Auxiliary Information Offsets) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let (version, aux_info_type, position) = aux_info_header(data, "saio")?;
+        if data.len() < position + 4
+        {
+            return Err(tr("saio box too short").to_string());
+        }
+        let entry_count = read_u32_be(data, position) as usize;
+        let offset_size = if version == 0
+        {
+            4
+        }
+        else
+        {
+            8
+        };
+        let offsets = data[position + 4..]
+            .chunks_exact(offset_size)
+            .take(entry_count)
+            .map(|entry| {
+                if version == 0
+                {
+                    read_u32_be(entry, 0) as u64
+                }
+                else
+                {
+                    read_u64_be(entry, 0)
+                }
+            })
+            .collect();
+
+        Ok(SampleAuxiliaryInfoOffsetsBox { version, aux_info_type, offsets })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "aux_info_type": self.aux_info_type, "offsets": self.offsets })
+    }
+}
+
+impl fmt::Display for SampleAuxiliaryInfoOffsetsBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        if let Some(ref aux_info_type) = self.aux_info_type
+        {
+            writeln!(f, "{}", tr_fmt("Auxiliary Information Type: '{}'", &[&aux_info_type]))?;
+        }
+        let offsets: Vec<String> = self.offsets.iter().take(MAX_DISPLAYED_SAMPLES).map(|offset| offset.to_string()).collect();
+        let more = if self.offsets.len() > MAX_DISPLAYED_SAMPLES
+        {
+            ", ..."
+        }
+        else
+        {
+            ""
+        };
+        writeln!(f, "{}", tr_fmt("Offsets ({}): {}{}", &[&self.offsets.len(), &offsets.join(", "), &more]))?;
+        Ok(())
+    }
+}
+
+/// Version, optional auxiliary information type (flags bit 0) and the position after them of a saiz/saio box
+fn aux_info_header(data: &[u8], box_type: &str) -> Result<(u8, Option<String>, usize), String>
+{
+    if data.len() < 4
+    {
+        return Err(tr_fmt("{} box too short", &[&box_type]));
+    }
+    let version = data[0];
+    if data[3] & 0x01 != 0
+    {
+        if data.len() < 12
+        {
+            return Err(tr_fmt("{} box too short for its information type", &[&box_type]));
+        }
+        // aux_info_type_parameter follows the type
+        return Ok((version, Some(String::from_utf8_lossy(&data[4..8]).to_string()), 12));
+    }
+    Ok((version, None, 4))
+}
+
+/// Scheme Type Box (schm)
+#[derive(Debug, Clone)]
+pub struct SchemeTypeBox
+{
+    pub scheme_type:    String,
+    pub scheme_version: u32,
+    pub scheme_uri:     Option<String>
+}
+
+impl SchemeTypeBox
+{
+    /// Parse schm (Scheme Type) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12
+        {
+            return Err(tr("schm box too short").to_string());
+        }
+        let scheme_uri = (data[3] & 0x01 != 0).then(|| String::from_utf8_lossy(&data[12..]).trim_end_matches('\0').to_string());
+        Ok(SchemeTypeBox { scheme_type: String::from_utf8_lossy(&data[4..8]).to_string(), scheme_version: read_u32_be(data, 8), scheme_uri })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "scheme_type": self.scheme_type, "scheme_version": self.scheme_version, "scheme_uri": self.scheme_uri })
+    }
+}
+
+impl fmt::Display for SchemeTypeBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Scheme: '{}' ({})", &[&self.scheme_type, &scheme_name(&self.scheme_type)]))?;
+        writeln!(f, "{}", tr_fmt("Scheme Version: {}", &[&format!("0x{:08X}", self.scheme_version)]))?;
+        if let Some(ref uri) = self.scheme_uri
+        {
+            writeln!(f, "{}", tr_fmt("Scheme URI: {}", &[&uri]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Original Format Box (frma): format code of the sample entry before encryption
+#[derive(Debug, Clone)]
+pub struct OriginalFormatBox
+{
+    pub data_format: String
+}
+
+impl OriginalFormatBox
+{
+    /// Parse frma (Original Format) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("frma box too short").to_string());
+        }
+        Ok(OriginalFormatBox { data_format: String::from_utf8_lossy(&data[0..4]).to_string() })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "data_format": self.data_format })
+    }
+}
+
+impl fmt::Display for OriginalFormatBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Original Format: '{}'", &[&self.data_format]))
+    }
+}
+
+/// Decoded Protection Scheme Information Box (sinf) of an encrypted sample entry
+#[derive(Debug, Clone, Default)]
+pub struct ProtectionSchemeInfo
+{
+    pub original_format:  Option<OriginalFormatBox>,
+    pub scheme_type:      Option<SchemeTypeBox>,
+    pub track_encryption: Option<TrackEncryptionBox>
+}
+
+impl ProtectionSchemeInfo
+{
+    /// Decode the frma, schm and schi/tenc children of a sinf payload
+    pub fn parse(data: &[u8]) -> Self
+    {
+        let mut info = Self::default();
+        for (box_type, payload) in child_boxes_at(data, 0).unwrap_or_default()
+        {
+            match box_type.as_str()
+            {
+                | "frma" => info.original_format = OriginalFormatBox::parse(payload).ok(),
+                | "schm" => info.scheme_type = SchemeTypeBox::parse(payload).ok(),
+                | "schi" =>
+                {
+                    let tenc = child_boxes_at(payload, 0).unwrap_or_default().into_iter().find(|(child_type, _)| child_type == "tenc");
+                    info.track_encryption = tenc.and_then(|(_, tenc)| TrackEncryptionBox::parse(tenc).ok());
+                }
+                | _ => ()
+            }
+        }
+        info
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "original_format": self.original_format.as_ref().map(OriginalFormatBox::to_json),
+            "scheme_type": self.scheme_type.as_ref().map(SchemeTypeBox::to_json),
+            "track_encryption": self.track_encryption.as_ref().map(TrackEncryptionBox::to_json)
+        })
+    }
+}
+
+impl fmt::Display for ProtectionSchemeInfo
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if let Some(ref original_format) = self.original_format
+        {
+            write!(f, "{}", original_format)?;
+        }
+        if let Some(ref scheme_type) = self.scheme_type
+        {
+            write!(f, "{}", scheme_type)?;
+        }
+        if let Some(ref track_encryption) = self.track_encryption
+        {
+            writeln!(f, "{}", tr("Track Encryption (tenc):"))?;
+            for line in track_encryption.to_string().lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
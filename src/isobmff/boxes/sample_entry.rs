@@ -0,0 +1,695 @@
+use std::fmt;
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::boxes::{
+        amr_config::AmrSpecificBox, av1_config::Av1ConfigurationBox, avc_config::AvcConfigurationBox, esds::ElementaryStreamDescriptorBox,
+        hevc_config::HevcConfigurationBox, protection::ProtectionSchemeInfo
+    }
+};
+
+/// Size of the VisualSampleEntry fields in front of the child boxes
+const VISUAL_SAMPLE_ENTRY_SIZE: usize = 78;
+/// Size of the AudioSampleEntry fields in front of the child boxes (QuickTime sound description version 0)
+const AUDIO_SAMPLE_ENTRY_SIZE: usize = 28;
+/// Size of an ALACSpecificConfig
+pub const ALAC_CONFIG_SIZE: usize = 24;
+
+/// Fields of a VisualSampleEntry
+#[derive(Debug, Clone)]
+pub struct VisualSampleEntry
+{
+    pub data_reference_index:  u16,
+    pub width:                 u16,
+    pub height:                u16,
+    /// Resolution in pixels per inch (usually 72)
+    pub horizontal_resolution: f64,
+    pub vertical_resolution:   f64,
+    pub frame_count:           u16,
+    /// Name of the encoder (up to 31 characters)
+    pub compressor_name:       String,
+    /// Color depth in bits (0x18 = color without alpha)
+    pub depth:                 u16
+}
+
+impl VisualSampleEntry
+{
+    /// Parse the fields in front of the child boxes
+    fn parse(data: &[u8]) -> Option<Self>
+    {
+        if data.len() < VISUAL_SAMPLE_ENTRY_SIZE
+        {
+            return None;
+        }
+        let fixed_16_16 = |position: usize| u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]) as f64 / 65536.0;
+
+        // Compressor name: a length byte followed by 31 bytes of padded text
+        let name_length = (data[42] as usize).min(31);
+        let compressor_name = String::from_utf8_lossy(&data[43..43 + name_length]).trim_end_matches('\0').to_string();
+
+        Some(Self {
+            data_reference_index: u16::from_be_bytes([data[6], data[7]]),
+            width: u16::from_be_bytes([data[24], data[25]]),
+            height: u16::from_be_bytes([data[26], data[27]]),
+            horizontal_resolution: fixed_16_16(28),
+            vertical_resolution: fixed_16_16(32),
+            frame_count: u16::from_be_bytes([data[40], data[41]]),
+            compressor_name,
+            depth: u16::from_be_bytes([data[74], data[75]])
+        })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "data_reference_index": self.data_reference_index,
+            "width": self.width,
+            "height": self.height,
+            "horizontal_resolution": self.horizontal_resolution,
+            "vertical_resolution": self.vertical_resolution,
+            "frame_count": self.frame_count,
+            "compressor_name": self.compressor_name,
+            "depth": self.depth
+        })
+    }
+}
+
+/// Fields of an AudioSampleEntry (including QuickTime sound description versions 1 and 2)
+#[derive(Debug, Clone)]
+pub struct AudioSampleEntry
+{
+    pub data_reference_index: u16,
+    /// QuickTime sound description version (0 for ISO audio sample entries)
+    pub version:              u16,
+    pub channel_count:        u32,
+    /// Bits per sample
+    pub sample_size:          u32,
+    pub sample_rate:          f64
+}
+
+impl AudioSampleEntry
+{
+    /// Parse the fields in front of the child boxes
+    fn parse(data: &[u8]) -> Option<Self>
+    {
+        if data.len() < AUDIO_SAMPLE_ENTRY_SIZE
+        {
+            return None;
+        }
+        let version = u16::from_be_bytes([data[8], data[9]]);
+        let data_reference_index = u16::from_be_bytes([data[6], data[7]]);
+
+        // Version 2 moves the format into 64-bit float sample rate and 32-bit channel/bit fields
+        if version == 2 && data.len() >= AUDIO_SAMPLE_ENTRY_SIZE + 36
+        {
+            let field = |position: usize| u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]);
+            let sample_rate = f64::from_be_bytes([data[32], data[33], data[34], data[35], data[36], data[37], data[38], data[39]]);
+            return Some(Self { data_reference_index, version, channel_count: field(40), sample_size: field(48), sample_rate });
+        }
+
+        Some(Self {
+            data_reference_index,
+            version,
+            channel_count: u16::from_be_bytes([data[16], data[17]]) as u32,
+            sample_size: u16::from_be_bytes([data[18], data[19]]) as u32,
+            // Sample rate is a 16.16 fixed-point value
+            sample_rate: u32::from_be_bytes([data[24], data[25], data[26], data[27]]) as f64 / 65536.0
+        })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "data_reference_index": self.data_reference_index,
+            "version": self.version,
+            "channel_count": self.channel_count,
+            "sample_size": self.sample_size,
+            "sample_rate": self.sample_rate
+        })
+    }
+}
+
+/// Bit Rate Box (btrt)
+#[derive(Debug, Clone)]
+pub struct BitRateBox
+{
+    /// Size of the decoding buffer in bytes
+    pub buffer_size: u32,
+    pub max_bitrate: u32,
+    pub avg_bitrate: u32
+}
+
+impl BitRateBox
+{
+    /// Parse btrt (Bit Rate) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12
+        {
+            return Err(tr("btrt box too short").to_string());
+        }
+        let field = |position: usize| u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]);
+
+        Ok(BitRateBox { buffer_size: field(0), max_bitrate: field(4), avg_bitrate: field(8) })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "buffer_size": self.buffer_size, "max_bitrate": self.max_bitrate, "avg_bitrate": self.avg_bitrate })
+    }
+}
+
+impl fmt::Display for BitRateBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Buffer Size: {} bytes", &[&self.buffer_size]))?;
+        writeln!(f, "{}", tr_fmt("Max Bitrate: {} bit/s", &[&self.max_bitrate]))?;
+        writeln!(f, "{}", tr_fmt("Average Bitrate: {} bit/s", &[&self.avg_bitrate]))?;
+        Ok(())
+    }
+}
+
+/// Pixel Aspect Ratio Box (pasp)
+#[derive(Debug, Clone)]
+pub struct PixelAspectRatioBox
+{
+    pub h_spacing: u32,
+    pub v_spacing: u32
+}
+
+impl PixelAspectRatioBox
+{
+    /// Parse pasp (Pixel Aspect Ratio) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("pasp box too short").to_string());
+        }
+
+        Ok(PixelAspectRatioBox {
+            h_spacing: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+            v_spacing: u32::from_be_bytes([data[4], data[5], data[6], data[7]])
+        })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "h_spacing": self.h_spacing, "v_spacing": self.v_spacing })
+    }
+}
+
+impl fmt::Display for PixelAspectRatioBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Pixel Aspect Ratio: {}:{}", &[&self.h_spacing, &self.v_spacing]))?;
+        Ok(())
+    }
+}
+
+/// Colour Information Box (colr)
+#[derive(Debug, Clone)]
+pub enum ColourInformationBox
+{
+    /// nclx (ISO) or nclc (QuickTime) color parameters; full range is only stored in nclx
+    Parameters
+    {
+        colour_type:              String,
+        colour_primaries:         u16,
+        transfer_characteristics: u16,
+        matrix_coefficients:      u16,
+        full_range:               Option<bool>
+    },
+    /// Restricted (rICC) or unrestricted (prof) ICC profile
+    IccProfile
+    {
+        colour_type: String, size: usize
+    },
+    Other
+    {
+        colour_type: String
+    }
+}
+
+impl ColourInformationBox
+{
+    /// Parse colr (Colour Information) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("colr box too short").to_string());
+        }
+        let colour_type = String::from_utf8_lossy(&data[0..4]).to_string();
+
+        match colour_type.as_str()
+        {
+            | "nclx" | "nclc" if data.len() >= 10 => Ok(ColourInformationBox::Parameters {
+                colour_primaries: u16::from_be_bytes([data[4], data[5]]),
+                transfer_characteristics: u16::from_be_bytes([data[6], data[7]]),
+                matrix_coefficients: u16::from_be_bytes([data[8], data[9]]),
+                full_range: data.get(10).filter(|_| colour_type == "nclx").map(|flags| flags & 0x80 != 0),
+                colour_type
+            }),
+            | "rICC" | "prof" => Ok(ColourInformationBox::IccProfile { colour_type, size: data.len() - 4 }),
+            | _ => Ok(ColourInformationBox::Other { colour_type })
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        match self
+        {
+            | ColourInformationBox::Parameters { colour_type, colour_primaries, transfer_characteristics, matrix_coefficients, full_range } => serde_json::json!({
+                "colour_type": colour_type,
+                "colour_primaries": colour_primaries,
+                "transfer_characteristics": transfer_characteristics,
+                "matrix_coefficients": matrix_coefficients,
+                "full_range": full_range
+            }),
+            | ColourInformationBox::IccProfile { colour_type, size } => serde_json::json!({ "colour_type": colour_type, "profile_size": size }),
+            | ColourInformationBox::Other { colour_type } => serde_json::json!({ "colour_type": colour_type })
+        }
+    }
+}
+
+/// Name of a colour primaries code point (ISO/IEC 23091-2)
+fn colour_primaries_name(value: u16) -> &'static str
+{
+    match value
+    {
+        | 1 => "BT.709",
+        | 5 => "BT.601 PAL",
+        | 6 => "BT.601 NTSC",
+        | 9 => "BT.2020",
+        | 12 => "Display P3",
+        | 2 => "unspecified",
+        | _ => "other"
+    }
+}
+
+/// Name of a transfer characteristics code point (ISO/IEC 23091-2)
+fn transfer_characteristics_name(value: u16) -> &'static str
+{
+    match value
+    {
+        | 1 | 6 | 14 | 15 => "BT.709",
+        | 13 => "sRGB",
+        | 16 => "PQ (SMPTE ST 2084)",
+        | 18 => "HLG",
+        | 2 => "unspecified",
+        | _ => "other"
+    }
+}
+
+/// Name of a matrix coefficients code point (ISO/IEC 23091-2)
+fn matrix_coefficients_name(value: u16) -> &'static str
+{
+    match value
+    {
+        | 0 => "Identity (RGB)",
+        | 1 => "BT.709",
+        | 5 | 6 => "BT.601",
+        | 9 => "BT.2020 non-constant",
+        | 10 => "BT.2020 constant",
+        | 2 => "unspecified",
+        | _ => "other"
+    }
+}
+
+impl fmt::Display for ColourInformationBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | ColourInformationBox::Parameters { colour_type, colour_primaries, transfer_characteristics, matrix_coefficients, full_range } =>
+            {
+                writeln!(f, "{}", tr_fmt("Colour Type: {}", &[&colour_type]))?;
+                writeln!(f, "{}", tr_fmt("Colour Primaries: {} ({})", &[&colour_primaries, &colour_primaries_name(*colour_primaries)]))?;
+                writeln!(
+                    f,
+                    "{}",
+                    tr_fmt("Transfer Characteristics: {} ({})", &[&transfer_characteristics, &transfer_characteristics_name(*transfer_characteristics)])
+                )?;
+                writeln!(f, "{}", tr_fmt("Matrix Coefficients: {} ({})", &[&matrix_coefficients, &matrix_coefficients_name(*matrix_coefficients)]))?;
+                if let Some(full_range) = full_range
+                {
+                    writeln!(f, "{}", tr_fmt("Full Range: {}", &[&full_range]))?;
+                }
+            }
+            | ColourInformationBox::IccProfile { colour_type, size } =>
+            {
+                writeln!(f, "{}", tr_fmt("Colour Type: {}", &[&colour_type]))?;
+                writeln!(f, "{}", tr_fmt("ICC Profile: {} bytes", &[&size]))?;
+            }
+            | ColourInformationBox::Other { colour_type } => writeln!(f, "{}", tr_fmt("Colour Type: {}", &[&colour_type]))?
+        }
+        Ok(())
+    }
+}
+
+/// Whether a format code denotes a visual sample entry
+fn is_visual_format(format: &str) -> bool
+{
+    matches!(
+        format,
+        "avc1" |
+            "avc2" |
+            "avc3" |
+            "avc4" |
+            "hvc1" |
+            "hev1" |
+            "dvh1" |
+            "dvhe" |
+            "av01" |
+            "vp08" |
+            "vp09" |
+            "mp4v" |
+            "s263" |
+            "jpeg" |
+            "mjpa" |
+            "mjpb" |
+            "apcn" |
+            "apch" |
+            "apcs" |
+            "apco" |
+            "ap4h" |
+            "encv" |
+            "hvt1" |
+            "vvc1" |
+            "vvi1"
+    )
+}
+
+/// Whether a format code denotes an audio sample entry
+fn is_audio_format(format: &str) -> bool
+{
+    matches!(
+        format,
+        "mp4a" |
+            ".mp3" |
+            "ac-3" |
+            "ec-3" |
+            "ac-4" |
+            "Opus" |
+            "fLaC" |
+            "alac" |
+            "samr" |
+            "sawb" |
+            "enca" |
+            "lpcm" |
+            "ipcm" |
+            "fpcm" |
+            "sowt" |
+            "twos" |
+            "raw " |
+            "in24" |
+            "in32" |
+            "fl32" |
+            "fl64" |
+            "ulaw" |
+            "alaw" |
+            "mha1" |
+            "mhm1" |
+            "dtsc" |
+            "dtsh" |
+            "dtsl"
+    )
+}
+
+/// Sample entry of a Sample Description Box: format code, decoded audio/visual fields and child boxes
+#[derive(Debug, Clone)]
+pub struct SampleEntry
+{
+    pub format: String,
+    /// Entry payload after the box header
+    pub data:   Vec<u8>,
+    /// Decoded fields if this is a visual sample entry
+    pub visual: Option<VisualSampleEntry>,
+    /// Decoded fields if this is an audio sample entry
+    pub audio:  Option<AudioSampleEntry>
+}
+
+impl SampleEntry
+{
+    /// Decode a sample entry; unknown format codes are classified by where their child boxes start
+    pub fn parse(format: String, data: Vec<u8>) -> Self
+    {
+        let mut entry = Self { format, data, visual: None, audio: None };
+
+        let visual = if is_visual_format(&entry.format) == true
+        {
+            true
+        }
+        else if is_audio_format(&entry.format) == true
+        {
+            false
+        }
+        else
+        {
+            child_boxes_at(&entry.data, entry.audio_children_start()).is_none() && child_boxes_at(&entry.data, VISUAL_SAMPLE_ENTRY_SIZE).is_some()
+        };
+
+        if visual == true
+        {
+            entry.visual = VisualSampleEntry::parse(&entry.data);
+        }
+        else if is_audio_format(&entry.format) == true || child_boxes_at(&entry.data, entry.audio_children_start()).is_some()
+        {
+            entry.audio = AudioSampleEntry::parse(&entry.data);
+        }
+        entry
+    }
+
+    /// Width and height if this is a VisualSampleEntry
+    pub fn dimensions(&self) -> Option<(u16, u16)>
+    {
+        self.visual.as_ref().map(|visual| (visual.width, visual.height))
+    }
+
+    /// Channel count and sample rate if this is an AudioSampleEntry
+    pub fn audio_format(&self) -> Option<(u16, u32)>
+    {
+        self.audio.as_ref().map(|audio| (audio.channel_count as u16, audio.sample_rate as u32))
+    }
+
+    /// Start of the child boxes of an audio entry (QuickTime sound description versions 1 and 2 append 16 and 36 bytes)
+    fn audio_children_start(&self) -> usize
+    {
+        match self.data.get(8..10)
+        {
+            | Some([0, 1]) => AUDIO_SAMPLE_ENTRY_SIZE + 16,
+            | Some([0, 2]) => AUDIO_SAMPLE_ENTRY_SIZE + 36,
+            | _ => AUDIO_SAMPLE_ENTRY_SIZE
+        }
+    }
+
+    /// Child boxes of the entry (e.g. esds, avcC, btrt), located after the audio or visual sample entry fields
+    pub fn child_boxes(&self) -> Vec<(String, &[u8])>
+    {
+        let start = if self.visual.is_some() == true
+        {
+            VISUAL_SAMPLE_ENTRY_SIZE
+        }
+        else
+        {
+            self.audio_children_start()
+        };
+
+        child_boxes_at(&self.data, start).unwrap_or_default()
+    }
+
+    /// Payload of the first child box of the given type
+    fn child_box(&self, box_type: &str) -> Option<Vec<u8>>
+    {
+        self.child_boxes().into_iter().find(|(child_type, _)| child_type == box_type).map(|(_, data)| data.to_vec())
+    }
+
+    /// Protection scheme (original format, scheme and default encryption parameters) of encv/enca entries
+    pub fn protection(&self) -> Option<ProtectionSchemeInfo>
+    {
+        self.child_box("sinf").map(|sinf| ProtectionSchemeInfo::parse(&sinf))
+    }
+
+    /// Elementary stream descriptor of MPEG-4 entries (mp4a, mp4v), also found inside a QuickTime `wave` box
+    pub fn esds(&self) -> Option<ElementaryStreamDescriptorBox>
+    {
+        let children = self.child_boxes();
+        let wave_children = children.iter().find(|(box_type, _)| box_type == "wave").and_then(|(_, wave)| child_boxes_at(wave, 0)).unwrap_or_default();
+        let (_, esds) = children.iter().chain(wave_children.iter()).find(|(box_type, _)| box_type == "esds")?;
+
+        ElementaryStreamDescriptorBox::parse(esds).ok()
+    }
+
+    /// ALACSpecificConfig (the magic cookie) of alac entries: the `alac` child box after its version and flags, also found inside a QuickTime `wave` box
+    pub fn alac_config(&self) -> Option<Vec<u8>>
+    {
+        let children = self.child_boxes();
+        let wave_children = children.iter().find(|(box_type, _)| box_type == "wave").and_then(|(_, wave)| child_boxes_at(wave, 0)).unwrap_or_default();
+        let (_, alac) = children.iter().chain(wave_children.iter()).find(|(box_type, _)| box_type == "alac")?;
+
+        alac.get(4..).filter(|config| config.len() >= ALAC_CONFIG_SIZE).map(<[u8]>::to_vec)
+    }
+
+    /// FLAC metadata blocks of fLaC entries: the `dfLa` child box after its version and flags
+    pub fn flac_metadata(&self) -> Option<Vec<u8>>
+    {
+        self.child_box("dfLa")?.get(4..).filter(|blocks| blocks.is_empty() == false).map(<[u8]>::to_vec)
+    }
+
+    /// AVC decoder configuration of avc1/avc3 entries
+    pub fn avc_config(&self) -> Option<AvcConfigurationBox>
+    {
+        AvcConfigurationBox::parse(&self.child_box("avcC")?).ok()
+    }
+
+    /// HEVC decoder configuration of hvc1/hev1 entries
+    pub fn hevc_config(&self) -> Option<HevcConfigurationBox>
+    {
+        HevcConfigurationBox::parse(&self.child_box("hvcC")?).ok()
+    }
+
+    /// AV1 codec configuration of av01 entries
+    pub fn av1_config(&self) -> Option<Av1ConfigurationBox>
+    {
+        Av1ConfigurationBox::parse(&self.child_box("av1C")?).ok()
+    }
+
+    /// AMR configuration of samr/sawb entries
+    pub fn amr_config(&self) -> Option<AmrSpecificBox>
+    {
+        AmrSpecificBox::parse(&self.child_box("damr")?, self.format == "sawb").ok()
+    }
+
+    /// Bit rate box of the entry
+    pub fn bit_rate(&self) -> Option<BitRateBox>
+    {
+        BitRateBox::parse(&self.child_box("btrt")?).ok()
+    }
+
+    /// Pixel aspect ratio of visual entries
+    pub fn pixel_aspect_ratio(&self) -> Option<PixelAspectRatioBox>
+    {
+        PixelAspectRatioBox::parse(&self.child_box("pasp")?).ok()
+    }
+
+    /// Colour information of visual entries
+    pub fn colour_information(&self) -> Option<ColourInformationBox>
+    {
+        ColourInformationBox::parse(&self.child_box("colr")?).ok()
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        // Child boxes, decoded where a parser exists
+        let children = self
+            .child_boxes()
+            .into_iter()
+            .map(|(box_type, data)| {
+                let fields = match box_type.as_str()
+                {
+                    | "esds" | "wave" => self.esds().map(|esds| esds.to_json()),
+                    | "avcC" => self.avc_config().map(|avcc| avcc.to_json()),
+                    | "hvcC" => self.hevc_config().map(|hvcc| hvcc.to_json()),
+                    | "av1C" => self.av1_config().map(|av1c| av1c.to_json()),
+                    | "damr" => self.amr_config().map(|damr| damr.to_json()),
+                    | "btrt" => self.bit_rate().map(|btrt| btrt.to_json()),
+                    | "pasp" => self.pixel_aspect_ratio().map(|pasp| pasp.to_json()),
+                    | "colr" => self.colour_information().map(|colr| colr.to_json()),
+                    | "sinf" => self.protection().map(|sinf| sinf.to_json()),
+                    | _ => None
+                };
+                serde_json::json!({ "type": box_type, "size": data.len(), "fields": fields })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "format": self.format,
+            "visual": self.visual.as_ref().map(VisualSampleEntry::to_json),
+            "audio": self.audio.as_ref().map(AudioSampleEntry::to_json),
+            "children": children
+        })
+    }
+}
+
+impl fmt::Display for SampleEntry
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if let Some(ref visual) = self.visual
+        {
+            writeln!(f, "{}", tr_fmt("Data Reference Index: {}", &[&visual.data_reference_index]))?;
+            writeln!(f, "{}", tr_fmt("Dimensions: {}x{}", &[&visual.width, &visual.height]))?;
+            writeln!(f, "{}", tr_fmt("Resolution: {} x {} dpi", &[&visual.horizontal_resolution, &visual.vertical_resolution]))?;
+            writeln!(f, "{}", tr_fmt("Frame Count: {}", &[&visual.frame_count]))?;
+            if visual.compressor_name.is_empty() == false
+            {
+                writeln!(f, "{}", tr_fmt("Compressor Name: {}", &[&visual.compressor_name]))?;
+            }
+            writeln!(f, "{}", tr_fmt("Depth: {}", &[&format!("0x{:04X}", visual.depth)]))?;
+        }
+        if let Some(ref audio) = self.audio
+        {
+            writeln!(f, "{}", tr_fmt("Data Reference Index: {}", &[&audio.data_reference_index]))?;
+            if audio.version > 0
+            {
+                writeln!(f, "{}", tr_fmt("Sound Description Version: {}", &[&audio.version]))?;
+            }
+            writeln!(f, "{}", tr_fmt("Channels: {}", &[&audio.channel_count]))?;
+            writeln!(f, "{}", tr_fmt("Sample Size: {} bits", &[&audio.sample_size]))?;
+            writeln!(f, "{}", tr_fmt("Sample Rate: {} Hz", &[&audio.sample_rate]))?;
+        }
+
+        // Child boxes, decoded where a parser exists
+        for (box_type, data) in self.child_boxes()
+        {
+            let decoded = match box_type.as_str()
+            {
+                | "esds" => self.esds().map(|esds| esds.to_string()),
+                | "wave" => self.esds().map(|esds| esds.to_string()),
+                | "avcC" => self.avc_config().map(|avcc| avcc.to_string()),
+                | "hvcC" => self.hevc_config().map(|hvcc| hvcc.to_string()),
+                | "av1C" => self.av1_config().map(|av1c| av1c.to_string()),
+                | "damr" => self.amr_config().map(|damr| damr.to_string()),
+                | "btrt" => self.bit_rate().map(|btrt| btrt.to_string()),
+                | "pasp" => self.pixel_aspect_ratio().map(|pasp| pasp.to_string()),
+                | "colr" => self.colour_information().map(|colr| colr.to_string()),
+                | "sinf" => self.protection().map(|sinf| sinf.to_string()),
+                | _ => None
+            };
+            writeln!(f, "{}", tr_fmt("'{}' ({} bytes)", &[&box_type, &data.len()]))?;
+            for line in decoded.unwrap_or_default().lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Boxes (type and payload) from `start` to the end of `data`, None unless they exactly fill it
+pub fn child_boxes_at(data: &[u8], start: usize) -> Option<Vec<(String, &[u8])>>
+{
+    let mut boxes = Vec::new();
+    let mut position = start;
+    while position + 8 <= data.len()
+    {
+        let size = u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]) as usize;
+        if size < 8 || position + size > data.len()
+        {
+            return None;
+        }
+        boxes.push((String::from_utf8_lossy(&data[position + 4..position + 8]).to_string(), &data[position + 8..position + size]));
+        position += size;
+    }
+    (boxes.is_empty() == false).then_some(boxes)
+}
@@ -0,0 +1,398 @@
+use std::fmt;
+
+use crate::{
+    bytes::read_u32_be,
+    hashes::to_hex,
+    i18n::{tr, tr_fmt}
+};
+
+/// Number of group entries and sample-to-group runs listed in the text output
+const MAX_DISPLAYED_ENTRIES: usize = 10;
+
+/// Grouping type as text, e.g. "roll"
+fn four_cc(bytes: &[u8]) -> String
+{
+    bytes
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' '
+            {
+                byte as char
+            }
+            else
+            {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// Size of one entry of a version 0 sgpd box, which does not store entry lengths
+fn fixed_entry_size(grouping_type: &str) -> Option<usize>
+{
+    match grouping_type
+    {
+        | "roll" | "prol" => Some(2),
+        | "rap " => Some(1),
+        | "seig" => Some(20),
+        | _ => None
+    }
+}
+
+/// Decoded entry of a Sample Group Description Box
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleGroupEntry
+{
+    /// Audio/visual roll recovery (`roll`) or pre-roll (`prol`): samples to decode before (negative) or after a sample
+    Roll(i16),
+    /// Random access point (`rap `): number of leading samples, if known
+    RandomAccessPoint(Option<u8>),
+    /// Common encryption parameters of a sample group (`seig`, ISO/IEC 23001-7)
+    Encryption
+    {
+        crypt_byte_block: u8,
+        skip_byte_block:  u8,
+        is_protected:     bool,
+        per_sample_iv:    u8,
+        key_id:           [u8; 16],
+        constant_iv:      Vec<u8>
+    },
+    /// Entry of a grouping type that is not decoded
+    Raw(Vec<u8>)
+}
+
+impl SampleGroupEntry
+{
+    fn parse(grouping_type: &str, data: &[u8]) -> Self
+    {
+        match grouping_type
+        {
+            | "roll" | "prol" if data.len() >= 2 => SampleGroupEntry::Roll(i16::from_be_bytes([data[0], data[1]])),
+            | "rap " if data.is_empty() == false =>
+            {
+                let known = data[0] & 0x80 != 0;
+                SampleGroupEntry::RandomAccessPoint(known.then_some(data[0] & 0x7F))
+            }
+            | "seig" if data.len() >= 20 =>
+            {
+                let mut key_id = [0u8; 16];
+                key_id.copy_from_slice(&data[4..20]);
+                let is_protected = data[2] != 0;
+                let per_sample_iv = data[3];
+                // Protected groups without per-sample IVs carry a constant IV
+                let constant_iv = match data.get(20)
+                {
+                    | Some(&size) if is_protected == true && per_sample_iv == 0 => data[21.min(data.len())..].iter().take(size as usize).copied().collect(),
+                    | _ => Vec::new()
+                };
+                SampleGroupEntry::Encryption { crypt_byte_block: data[1] >> 4, skip_byte_block: data[1] & 0x0F, is_protected, per_sample_iv, key_id, constant_iv }
+            }
+            | _ => SampleGroupEntry::Raw(data.to_vec())
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        match self
+        {
+            | SampleGroupEntry::Roll(roll_distance) => serde_json::json!({ "roll_distance": roll_distance }),
+            | SampleGroupEntry::RandomAccessPoint(leading_samples) => serde_json::json!({ "num_leading_samples": leading_samples }),
+            | SampleGroupEntry::Encryption { crypt_byte_block, skip_byte_block, is_protected, per_sample_iv, key_id, constant_iv } => serde_json::json!({
+                "crypt_byte_block": crypt_byte_block,
+                "skip_byte_block": skip_byte_block,
+                "is_protected": is_protected,
+                "per_sample_iv_size": per_sample_iv,
+                "key_id": to_hex(key_id),
+                "constant_iv": to_hex(constant_iv)
+            }),
+            | SampleGroupEntry::Raw(data) => serde_json::json!({ "data": to_hex(data) })
+        }
+    }
+}
+
+impl fmt::Display for SampleGroupEntry
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let hex = |bytes: &[u8]| bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<String>();
+        match self
+        {
+            | SampleGroupEntry::Roll(distance) => write!(f, "{}", tr_fmt("roll distance {} samples", &[&distance])),
+            | SampleGroupEntry::RandomAccessPoint(Some(leading)) => write!(f, "{}", tr_fmt("{} leading samples", &[&leading])),
+            | SampleGroupEntry::RandomAccessPoint(None) => write!(f, "{}", tr("leading samples unknown")),
+            | SampleGroupEntry::Encryption { crypt_byte_block, skip_byte_block, is_protected, per_sample_iv, key_id, constant_iv } =>
+            {
+                match is_protected
+                {
+                    | true => write!(f, "{}", tr_fmt("protected, KID {}", &[&hex(key_id)]))?,
+                    | false => write!(f, "{}", tr("not protected"))?
+                }
+                if *per_sample_iv > 0
+                {
+                    write!(f, "{}", tr_fmt(", per-sample IV size {}", &[&per_sample_iv]))?;
+                }
+                if constant_iv.is_empty() == false
+                {
+                    write!(f, "{}", tr_fmt(", constant IV {}", &[&hex(constant_iv)]))?;
+                }
+                if *crypt_byte_block > 0 || *skip_byte_block > 0
+                {
+                    write!(f, "{}", tr_fmt(", pattern {}:{}", &[&crypt_byte_block, &skip_byte_block]))?;
+                }
+                Ok(())
+            }
+            | SampleGroupEntry::Raw(data) => write!(f, "{}", tr_fmt("{} bytes: {}", &[&data.len(), &hex(&data[..data.len().min(32)])]))
+        }
+    }
+}
+
+/// Sample Group Description Box (sgpd)
+#[derive(Debug, Clone)]
+pub struct SampleGroupDescriptionBox
+{
+    pub version:                          u8,
+    pub grouping_type:                    String,
+    /// Length of every entry (version 1, 0 if entries store their own length)
+    pub default_length:                   Option<u32>,
+    /// Description index used by samples not mapped by an sbgp box (version 2 and later)
+    pub default_sample_description_index: Option<u32>,
+    pub entry_count:                      u32,
+    pub entries:                          Vec<SampleGroupEntry>
+}
+
+impl SampleGroupDescriptionBox
+{
+    /// Parse sgpd (Sample Group Description) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12
+        {
+            return Err(tr("sgpd box too short").to_string());
+        }
+
+        let version = data[0];
+        let grouping_type = four_cc(&data[4..8]);
+        let mut position = 8;
+        let mut default_length = None;
+        let mut default_sample_description_index = None;
+        if version == 1
+        {
+            default_length = Some(read_u32_be(data, position));
+            position += 4;
+        }
+        else if version >= 2
+        {
+            default_sample_description_index = Some(read_u32_be(data, position));
+            position += 4;
+        }
+        if data.len() < position + 4
+        {
+            return Err(tr("sgpd box too short for entry count").to_string());
+        }
+        let entry_count = read_u32_be(data, position);
+        position += 4;
+
+        let mut entries = Vec::new();
+        for _ in 0..entry_count
+        {
+            let length = match default_length
+            {
+                | Some(0) if data.len() >= position + 4 =>
+                {
+                    position += 4;
+                    read_u32_be(data, position - 4) as usize
+                }
+                | Some(0) => break,
+                | Some(length) => length as usize,
+                // Versions 0 and 2 only store the entries of grouping types with a known size
+                | None => match fixed_entry_size(&grouping_type)
+                {
+                    | Some(size) => size,
+                    | None => break
+                }
+            };
+            let Some(entry) = data.get(position..position.saturating_add(length))
+            else
+            {
+                break;
+            };
+            entries.push(SampleGroupEntry::parse(&grouping_type, entry));
+            position += length;
+        }
+
+        Ok(SampleGroupDescriptionBox { version, grouping_type, default_length, default_sample_description_index, entry_count, entries })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "grouping_type": self.grouping_type,
+            "default_length": self.default_length,
+            "default_sample_description_index": self.default_sample_description_index,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(SampleGroupEntry::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for SampleGroupDescriptionBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Grouping Type: '{}' ({})", &[&self.grouping_type, &grouping_type_name(&self.grouping_type)]))?;
+        if let Some(index) = self.default_sample_description_index
+        {
+            writeln!(f, "{}", tr_fmt("Default Group Description Index: {}", &[&index]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.entry_count]))?;
+        for (index, entry) in self.entries.iter().take(MAX_DISPLAYED_ENTRIES).enumerate()
+        {
+            writeln!(f, "  {}", tr_fmt("Group {}: {}", &[&(index + 1), &entry]))?;
+        }
+        if self.entries.len() > MAX_DISPLAYED_ENTRIES
+        {
+            writeln!(f, "  {}", tr_fmt("... {} more groups", &[&(self.entries.len() - MAX_DISPLAYED_ENTRIES)]))?;
+        }
+        if self.entries.len() < self.entry_count as usize
+        {
+            writeln!(f, "{}", tr_fmt("Parsed Entries: {} (entry size unknown or table truncated)", &[&self.entries.len()]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Run of an sbgp box: consecutive samples belonging to one group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleToGroupEntry
+{
+    pub sample_count:            u32,
+    /// 1-based index into the sgpd entries (0: no group; above 0x10000: entries of the sgpd in the same fragment)
+    pub group_description_index: u32
+}
+
+/// Sample-to-Group Box (sbgp)
+#[derive(Debug, Clone)]
+pub struct SampleToGroupBox
+{
+    pub version:                 u8,
+    pub grouping_type:           String,
+    /// Subtype of the grouping (version 1)
+    pub grouping_type_parameter: Option<u32>,
+    pub entry_count:             u32,
+    pub entries:                 Vec<SampleToGroupEntry>
+}
+
+impl SampleToGroupBox
+{
+    /// Parse sbgp (Sample-to-Group) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 12
+        {
+            return Err(tr("sbgp box too short").to_string());
+        }
+
+        let version = data[0];
+        let grouping_type = four_cc(&data[4..8]);
+        let mut position = 8;
+        let mut grouping_type_parameter = None;
+        if version == 1
+        {
+            grouping_type_parameter = Some(read_u32_be(data, position));
+            position += 4;
+        }
+        if data.len() < position + 4
+        {
+            return Err(tr("sbgp box too short for entry count").to_string());
+        }
+        let entry_count = read_u32_be(data, position);
+        position += 4;
+
+        let entries = data[position..]
+            .chunks_exact(8)
+            .take(entry_count as usize)
+            .map(|entry| SampleToGroupEntry { sample_count: read_u32_be(entry, 0), group_description_index: read_u32_be(entry, 4) })
+            .collect();
+
+        Ok(SampleToGroupBox { version, grouping_type, grouping_type_parameter, entry_count, entries })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "grouping_type": self.grouping_type,
+            "grouping_type_parameter": self.grouping_type_parameter,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "sample_count": entry.sample_count,
+                "group_description_index": entry.group_description_index
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for SampleToGroupBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Grouping Type: '{}' ({})", &[&self.grouping_type, &grouping_type_name(&self.grouping_type)]))?;
+        if let Some(parameter) = self.grouping_type_parameter
+        {
+            writeln!(f, "{}", tr_fmt("Grouping Type Parameter: {}", &[&format!("0x{:08X}", parameter)]))?;
+        }
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.entry_count]))?;
+
+        let sample_count: u64 = self.entries.iter().map(|entry| entry.sample_count as u64).sum();
+        let grouped: u64 = self.entries.iter().filter(|entry| entry.group_description_index != 0).map(|entry| entry.sample_count as u64).sum();
+        writeln!(f, "{}", tr_fmt("Samples: {} ({} in a group)", &[&sample_count, &grouped]))?;
+
+        let mut first_sample = 1u64;
+        for (index, entry) in self.entries.iter().enumerate()
+        {
+            if index < MAX_DISPLAYED_ENTRIES
+            {
+                let last_sample = first_sample + (entry.sample_count as u64).saturating_sub(1);
+                let group = match entry.group_description_index
+                {
+                    | 0 => "no group".to_string(),
+                    | group if group > 0x10000 => tr_fmt("group {} of this fragment", &[&(group - 0x10000)]),
+                    | group => tr_fmt("group {}", &[&group])
+                };
+                writeln!(f, "  {}", tr_fmt("Samples {}-{}: {}", &[&first_sample, &last_sample, &group]))?;
+            }
+            first_sample += entry.sample_count as u64;
+        }
+        if self.entries.len() > MAX_DISPLAYED_ENTRIES
+        {
+            writeln!(f, "  {}", tr_fmt("... {} more runs", &[&(self.entries.len() - MAX_DISPLAYED_ENTRIES)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Describe a sample grouping type
+fn grouping_type_name(grouping_type: &str) -> &'static str
+{
+    match grouping_type
+    {
+        | "roll" => "Roll Recovery",
+        | "prol" => "Pre-Roll",
+        | "rap " => "Random Access Point",
+        | "sync" => "Sync Sample",
+        | "seig" => "CENC Sample Encryption Information",
+        | "tele" => "Temporal Level",
+        | "sap " => "Stream Access Point",
+        | "alst" => "Alternative Startup Sequence",
+        | "tscl" => "Temporal Layer",
+        | "stsa" => "Step-wise Temporal Sub-layer Access",
+        | "rash" => "Rate Share",
+        | _ => "Unknown Grouping Type"
+    }
+}
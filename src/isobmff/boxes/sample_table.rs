@@ -1,12 +1,18 @@
 use std::fmt;
 
+use crate::{
+    bytes::read_u32_be,
+    i18n::{tr, tr_fmt},
+    isobmff::boxes::sample_entry::SampleEntry
+};
+
 /// Sample Description Box (stsd)
 #[derive(Debug, Clone)]
 pub struct SampleDescriptionBox
 {
     pub version:     u8,
     pub entry_count: u32,
-    pub entries:     Vec<String>
+    pub entries:     Vec<SampleEntry>
 }
 
 impl SampleDescriptionBox
@@ -16,7 +22,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("stsd box too short".to_string());
+            return Err(tr("stsd box too short").to_string());
         }
 
         let version = data[0];
@@ -35,7 +41,8 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
             let entry_size = u32::from_be_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
             let format = String::from_utf8_lossy(&data[offset + 4..offset + 8]).to_string();
-            entries.push(format);
+            let entry_end = (offset + entry_size as usize).min(data.len()).max(offset + 8);
+            entries.push(SampleEntry::parse(format, data[offset + 8..entry_end].to_vec()));
 
             offset += entry_size as usize;
             if offset >= data.len()
@@ -46,46 +53,121 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
 
         Ok(SampleDescriptionBox { version, entry_count, entries })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(SampleEntry::to_json).collect::<Vec<_>>()
+        })
+    }
 }
 
 impl fmt::Display for SampleDescriptionBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {}", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {}", &[&self.entry_count]))?;
         if self.entries.is_empty() == false
         {
-            write!(f, "Sample Entries: ")?;
-            let entry_list: Vec<String> = self.entries.iter().map(|e| format!("'{}'", e)).collect();
+            write!(f, "{}", tr("Sample Entries: "))?;
+            let entry_list: Vec<String> = self.entries.iter().map(|e| format!("'{}'", e.format)).collect();
             writeln!(f, "{}", entry_list.join(", "))?;
         }
+        for entry in &self.entries
+        {
+            writeln!(f, "{}", tr_fmt("Sample Entry '{}':", &[&entry.format]))?;
+            for line in entry.to_string().lines()
+            {
+                writeln!(f, "  {}", line)?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Default number of table entries parsed per stts/stsc/stsz/stco/co64 box
+pub const DEFAULT_MAX_TABLE_ENTRIES: usize = 1_000_000;
+
+/// Fixed-size entries following a table header, limited to the declared count, the available data and `max_entries`
+fn table_entries(data: &[u8], header_size: usize, entry_size: usize, entry_count: u32, max_entries: usize) -> impl Iterator<Item = &[u8]>
+{
+    data[header_size.min(data.len())..].chunks_exact(entry_size).take((entry_count as usize).min(max_entries))
+}
+
+/// Note for tables whose entries were not all parsed
+fn fmt_truncation(f: &mut fmt::Formatter<'_>, parsed: usize, declared: u32) -> fmt::Result
+{
+    if parsed < declared as usize
+    {
+        writeln!(f, "{}", tr_fmt("Parsed Entries: {} (table truncated or limited)", &[&parsed]))?;
+    }
+    Ok(())
+}
+
+/// Entry of a Time-to-Sample Box: a run of samples with the same duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeToSampleEntry
+{
+    pub sample_count: u32,
+    /// Sample duration in media timescale units
+    pub sample_delta: u32
+}
+
 /// Time-to-Sample Box (stts)
 #[derive(Debug, Clone)]
 pub struct TimeToSampleBox
 {
     pub version:     u8,
-    pub entry_count: u32
+    pub entry_count: u32,
+    pub entries:     Vec<TimeToSampleEntry>
 }
 
 impl TimeToSampleBox
 {
     /// Parse stts (Time-to-Sample) box
     pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse stts box, keeping at most `max_entries` entries
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("stts box too short".to_string());
+            return Err(tr("stts box too short").to_string());
         }
 
         let version = data[0];
-        let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let entry_count = read_u32_be(data, 4);
+        let entries = table_entries(data, 8, 8, entry_count, max_entries)
+            .map(|entry| TimeToSampleEntry { sample_count: read_u32_be(entry, 0), sample_delta: read_u32_be(entry, 4) })
+            .collect();
+
+        Ok(TimeToSampleBox { version, entry_count, entries })
+    }
 
-        Ok(TimeToSampleBox { version, entry_count })
+    /// Duration of every sample, in decoding order
+    pub fn sample_durations(&self) -> impl Iterator<Item = u32> + '_
+    {
+        self.entries.iter().flat_map(|entry| std::iter::repeat_n(entry.sample_delta, entry.sample_count as usize))
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "sample_count": entry.sample_count,
+                "sample_delta": entry.sample_delta
+            })).collect::<Vec<_>>()
+        })
     }
 }
 
@@ -93,34 +175,82 @@ impl fmt::Display for TimeToSampleBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {} time-to-sample entries", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} time-to-sample entries", &[&self.entry_count]))?;
+        fmt_truncation(f, self.entries.len(), self.entry_count)?;
+        let sample_count: u64 = self.entries.iter().map(|entry| entry.sample_count as u64).sum();
+        let total_duration: u64 = self.entries.iter().map(|entry| entry.sample_count as u64 * entry.sample_delta as u64).sum();
+        writeln!(f, "{}", tr_fmt("Samples: {}, total duration: {} units", &[&sample_count, &total_duration]))?;
         Ok(())
     }
 }
 
+/// Entry of a Sample-to-Chunk Box: chunks from `first_chunk` on hold `samples_per_chunk` samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleToChunkEntry
+{
+    /// 1-based index of the first chunk of this run
+    pub first_chunk:              u32,
+    pub samples_per_chunk:        u32,
+    pub sample_description_index: u32
+}
+
 /// Sample-to-Chunk Box (stsc)
 #[derive(Debug, Clone)]
 pub struct SampleToChunkBox
 {
     pub version:     u8,
-    pub entry_count: u32
+    pub entry_count: u32,
+    pub entries:     Vec<SampleToChunkEntry>
 }
 
 impl SampleToChunkBox
 {
     /// Parse stsc (Sample-to-Chunk) box
     pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse stsc box, keeping at most `max_entries` entries
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("stsc box too short".to_string());
+            return Err(tr("stsc box too short").to_string());
         }
 
         let version = data[0];
-        let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let entry_count = read_u32_be(data, 4);
+        let entries = table_entries(data, 8, 12, entry_count, max_entries)
+            .map(|entry| SampleToChunkEntry {
+                first_chunk:              read_u32_be(entry, 0),
+                samples_per_chunk:        read_u32_be(entry, 4),
+                sample_description_index: read_u32_be(entry, 8)
+            })
+            .collect();
+
+        Ok(SampleToChunkBox { version, entry_count, entries })
+    }
 
-        Ok(SampleToChunkBox { version, entry_count })
+    /// Number of samples in the chunk with the given 1-based index
+    pub fn samples_in_chunk(&self, chunk: u32) -> u32
+    {
+        self.entries.iter().rev().find(|entry| entry.first_chunk <= chunk).map_or(0, |entry| entry.samples_per_chunk)
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "first_chunk": entry.first_chunk,
+                "samples_per_chunk": entry.samples_per_chunk,
+                "sample_description_index": entry.sample_description_index
+            })).collect::<Vec<_>>()
+        })
     }
 }
 
@@ -128,8 +258,9 @@ impl fmt::Display for SampleToChunkBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {} sample-to-chunk entries", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} sample-to-chunk entries", &[&self.entry_count]))?;
+        fmt_truncation(f, self.entries.len(), self.entry_count)?;
         Ok(())
     }
 }
@@ -140,24 +271,64 @@ pub struct SampleSizeBox
 {
     pub version:      u8,
     pub sample_size:  u32,
-    pub sample_count: u32
+    pub sample_count: u32,
+    /// Individual sample sizes (empty if all samples have `sample_size`)
+    pub entry_sizes:  Vec<u32>
 }
 
 impl SampleSizeBox
 {
     /// Parse stsz (Sample Size) box
     pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse stsz box, keeping at most `max_entries` individual sizes
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
     {
         if data.len() < 12
         {
-            return Err("stsz box too short".to_string());
+            return Err(tr("stsz box too short").to_string());
         }
 
         let version = data[0];
-        let sample_size = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
-        let sample_count = u32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+        let sample_size = read_u32_be(data, 4);
+        let sample_count = read_u32_be(data, 8);
+        let entry_sizes = if sample_size == 0
+        {
+            table_entries(data, 12, 4, sample_count, max_entries).map(|entry| read_u32_be(entry, 0)).collect()
+        }
+        else
+        {
+            Vec::new()
+        };
+
+        Ok(SampleSizeBox { version, sample_size, sample_count, entry_sizes })
+    }
 
-        Ok(SampleSizeBox { version, sample_size, sample_count })
+    /// Size of every sample (as far as parsed for variable sizes)
+    pub fn sample_sizes(&self) -> Box<dyn Iterator<Item = u32> + '_>
+    {
+        if self.sample_size != 0
+        {
+            Box::new(std::iter::repeat_n(self.sample_size, self.sample_count as usize))
+        }
+        else
+        {
+            Box::new(self.entry_sizes.iter().copied())
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "sample_size": self.sample_size,
+            "sample_count": self.sample_count,
+            "entry_sizes": self.entry_sizes
+        })
     }
 }
 
@@ -165,16 +336,17 @@ impl fmt::Display for SampleSizeBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
         if self.sample_size == 0
         {
-            writeln!(f, "Sample Size: Variable")?;
-            writeln!(f, "Sample Count: {} (with individual sizes)", self.sample_count)?;
+            writeln!(f, "{}", tr("Sample Size: Variable"))?;
+            writeln!(f, "{}", tr_fmt("Sample Count: {} (with individual sizes)", &[&self.sample_count]))?;
+            fmt_truncation(f, self.entry_sizes.len(), self.sample_count)?;
         }
         else
         {
-            writeln!(f, "Sample Size: {} bytes (constant)", self.sample_size)?;
-            writeln!(f, "Sample Count: {}", self.sample_count)?;
+            writeln!(f, "{}", tr_fmt("Sample Size: {} bytes (constant)", &[&self.sample_size]))?;
+            writeln!(f, "{}", tr_fmt("Sample Count: {}", &[&self.sample_count]))?;
         }
         Ok(())
     }
@@ -184,24 +356,38 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
 #[derive(Debug, Clone)]
 pub struct ChunkOffsetBox
 {
-    pub version:     u8,
-    pub entry_count: u32
+    pub version:       u8,
+    pub entry_count:   u32,
+    pub chunk_offsets: Vec<u32>
 }
 
 impl ChunkOffsetBox
 {
     /// Parse stco (Chunk Offset) box
     pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse stco box, keeping at most `max_entries` offsets
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("stco box too short".to_string());
+            return Err(tr("stco box too short").to_string());
         }
 
         let version = data[0];
-        let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let entry_count = read_u32_be(data, 4);
+        let chunk_offsets = table_entries(data, 8, 4, entry_count, max_entries).map(|entry| read_u32_be(entry, 0)).collect();
 
-        Ok(ChunkOffsetBox { version, entry_count })
+        Ok(ChunkOffsetBox { version, entry_count, chunk_offsets })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "entry_count": self.entry_count, "chunk_offsets": self.chunk_offsets })
     }
 }
 
@@ -209,8 +395,9 @@ impl fmt::Display for ChunkOffsetBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {} chunk offsets (32-bit)", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} chunk offsets (32-bit)", &[&self.entry_count]))?;
+        fmt_truncation(f, self.chunk_offsets.len(), self.entry_count)?;
         Ok(())
     }
 }
@@ -219,24 +406,40 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
 #[derive(Debug, Clone)]
 pub struct ChunkOffset64Box
 {
-    pub version:     u8,
-    pub entry_count: u32
+    pub version:       u8,
+    pub entry_count:   u32,
+    pub chunk_offsets: Vec<u64>
 }
 
 impl ChunkOffset64Box
 {
     /// Parse co64 (64-bit Chunk Offset) box
     pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse co64 box, keeping at most `max_entries` offsets
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
     {
         if data.len() < 8
         {
-            return Err("co64 box too short".to_string());
+            return Err(tr("co64 box too short").to_string());
         }
 
         let version = data[0];
-        let entry_count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let entry_count = read_u32_be(data, 4);
+        let chunk_offsets = table_entries(data, 8, 8, entry_count, max_entries)
+            .map(|entry| u64::from_be_bytes([entry[0], entry[1], entry[2], entry[3], entry[4], entry[5], entry[6], entry[7]]))
+            .collect();
 
-        Ok(ChunkOffset64Box { version, entry_count })
+        Ok(ChunkOffset64Box { version, entry_count, chunk_offsets })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "entry_count": self.entry_count, "chunk_offsets": self.chunk_offsets })
     }
 }
 
@@ -244,8 +447,315 @@ impl fmt::Display for ChunkOffset64Box
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
-        writeln!(f, "Entry Count: {} chunk offsets (64-bit)", self.entry_count)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} chunk offsets (64-bit)", &[&self.entry_count]))?;
+        fmt_truncation(f, self.chunk_offsets.len(), self.entry_count)?;
+        Ok(())
+    }
+}
+
+/// Entry of a Composition Time-to-Sample Box: a run of samples with the same composition offset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompositionOffsetEntry
+{
+    pub sample_count:  u32,
+    /// Composition time minus decoding time in media timescale units (signed in version 1)
+    pub sample_offset: i64
+}
+
+/// Composition Time-to-Sample Box (ctts)
+#[derive(Debug, Clone)]
+pub struct CompositionOffsetBox
+{
+    pub version:     u8,
+    pub entry_count: u32,
+    pub entries:     Vec<CompositionOffsetEntry>
+}
+
+impl CompositionOffsetBox
+{
+    /// Parse ctts (Composition Time-to-Sample) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse ctts box, keeping at most `max_entries` entries
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("ctts box too short").to_string());
+        }
+
+        let version = data[0];
+        let entry_count = read_u32_be(data, 4);
+        let entries = table_entries(data, 8, 8, entry_count, max_entries)
+            .map(|entry| {
+                let raw = read_u32_be(entry, 4);
+                let sample_offset = if version == 1
+                {
+                    raw as i32 as i64
+                }
+                else
+                {
+                    raw as i64
+                };
+                CompositionOffsetEntry { sample_count: read_u32_be(entry, 0), sample_offset }
+            })
+            .collect();
+
+        Ok(CompositionOffsetBox { version, entry_count, entries })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "entry_count": self.entry_count,
+            "entries": self.entries.iter().map(|entry| serde_json::json!({
+                "sample_count": entry.sample_count,
+                "sample_offset": entry.sample_offset
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for CompositionOffsetBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} composition offset entries", &[&self.entry_count]))?;
+        fmt_truncation(f, self.entries.len(), self.entry_count)?;
+        let sample_count: u64 = self.entries.iter().map(|entry| entry.sample_count as u64).sum();
+        let offsets = self.entries.iter().map(|entry| entry.sample_offset);
+        if let (Some(min), Some(max)) = (offsets.clone().min(), offsets.max())
+        {
+            writeln!(f, "{}", tr_fmt("Samples: {}, composition offsets: {} to {} units", &[&sample_count, &min, &max]))?;
+        }
+        // A single offset for all samples only delays presentation, differing offsets mean reordered (B-)frames
+        let distinct = self.entries.iter().map(|entry| entry.sample_offset).collect::<std::collections::BTreeSet<_>>().len();
+        if distinct > 1
+        {
+            writeln!(f, "{}", tr_fmt("Frame Reordering: yes ({} distinct offsets)", &[&distinct]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Sync Sample Box (stss): the samples that are random access points (keyframes)
+#[derive(Debug, Clone)]
+pub struct SyncSampleBox
+{
+    pub version:        u8,
+    pub entry_count:    u32,
+    /// 1-based sample numbers in ascending order
+    pub sample_numbers: Vec<u32>
+}
+
+impl SyncSampleBox
+{
+    /// Parse stss (Sync Sample) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse stss box, keeping at most `max_entries` sample numbers
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
+    {
+        if data.len() < 8
+        {
+            return Err(tr("stss box too short").to_string());
+        }
+
+        let version = data[0];
+        let entry_count = read_u32_be(data, 4);
+        let sample_numbers = table_entries(data, 8, 4, entry_count, max_entries).map(|entry| read_u32_be(entry, 0)).collect();
+
+        Ok(SyncSampleBox { version, entry_count, sample_numbers })
+    }
+
+    /// Distances in samples between consecutive keyframes
+    pub fn keyframe_intervals(&self) -> impl Iterator<Item = u32> + '_
+    {
+        self.sample_numbers.windows(2).map(|pair| pair[1].saturating_sub(pair[0]))
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({ "version": self.version, "entry_count": self.entry_count, "sample_numbers": self.sample_numbers })
+    }
+}
+
+impl fmt::Display for SyncSampleBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        /// Number of keyframe sample numbers listed
+        const MAX_LISTED_KEYFRAMES: usize = 10;
+
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Entry Count: {} sync samples (keyframes)", &[&self.entry_count]))?;
+        fmt_truncation(f, self.sample_numbers.len(), self.entry_count)?;
+
+        if self.sample_numbers.is_empty() == false
+        {
+            let listed: Vec<String> = self.sample_numbers.iter().take(MAX_LISTED_KEYFRAMES).map(u32::to_string).collect();
+            let more = if self.sample_numbers.len() > MAX_LISTED_KEYFRAMES
+            {
+                ", ..."
+            }
+            else
+            {
+                ""
+            };
+            writeln!(f, "{}", tr_fmt("Keyframes: {}{}", &[&listed.join(", "), &more]))?;
+        }
+
+        let intervals: Vec<u32> = self.keyframe_intervals().collect();
+        if let (Some(min), Some(max)) = (intervals.iter().min(), intervals.iter().max())
+        {
+            let average = intervals.iter().map(|interval| *interval as f64).sum::<f64>() / intervals.len() as f64;
+            let kind = if min == max
+            {
+                "fixed GOP"
+            }
+            else
+            {
+                "variable GOP"
+            };
+            writeln!(f, "{}", tr_fmt("Keyframe Interval: {} to {} samples, average {} ({})", &[&min, &max, &format!("{:.1}", average), &kind]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Dependency flags of one sample from a Sample Dependency Type Box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleDependency
+{
+    /// 1: leading sample with a dependency before the preceding I-picture, 2: not a leading sample, 3: independent leading sample
+    pub is_leading:     u8,
+    /// 1: depends on other samples, 2: does not (I-picture)
+    pub depends_on:     u8,
+    /// 1: other samples depend on this one, 2: none does (disposable)
+    pub is_depended_on: u8,
+    /// 1: contains redundant coding, 2: does not
+    pub has_redundancy: u8
+}
+
+impl SampleDependency
+{
+    /// One-letter picture type for the dependency pattern: I (independent), P (referenced), b (disposable), ? (unknown)
+    pub fn symbol(&self) -> char
+    {
+        match (self.depends_on, self.is_depended_on)
+        {
+            | (2, _) => 'I',
+            | (_, 2) => 'b',
+            | (1, _) => 'P',
+            | _ => '?'
+        }
+    }
+}
+
+/// Sample Dependency Type Box (sdtp), one byte of flags per sample
+#[derive(Debug, Clone)]
+pub struct SampleDependencyBox
+{
+    pub version: u8,
+    pub samples: Vec<SampleDependency>
+}
+
+impl SampleDependencyBox
+{
+    /// Parse sdtp (Sample Dependency Type) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        Self::parse_with_limit(data, DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Parse sdtp box, keeping the flags of at most `max_entries` samples
+    pub fn parse_with_limit(data: &[u8], max_entries: usize) -> Result<Self, String>
+    {
+        if data.len() < 4
+        {
+            return Err(tr("sdtp box too short").to_string());
+        }
+
+        // The sample count is taken from stsz, the table simply fills the rest of the box
+        let version = data[0];
+        let samples = data[4..]
+            .iter()
+            .take(max_entries)
+            .map(|flags| SampleDependency {
+                is_leading:     flags >> 6,
+                depends_on:     (flags >> 4) & 0x03,
+                is_depended_on: (flags >> 2) & 0x03,
+                has_redundancy: flags & 0x03
+            })
+            .collect();
+
+        Ok(SampleDependencyBox { version, samples })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "samples": self.samples.iter().map(|sample| serde_json::json!({
+                "is_leading": sample.is_leading,
+                "depends_on": sample.depends_on,
+                "is_depended_on": sample.is_depended_on,
+                "has_redundancy": sample.has_redundancy
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for SampleDependencyBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        /// Number of samples shown in the dependency pattern
+        const MAX_PATTERN_SAMPLES: usize = 48;
+
+        let count = |predicate: fn(&SampleDependency) -> bool| self.samples.iter().filter(|sample| predicate(sample)).count();
+
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Samples: {}", &[&self.samples.len()]))?;
+        writeln!(f, "{}", tr_fmt("Independent (I): {}", &[&count(|sample| sample.depends_on == 2)]))?;
+        writeln!(f, "{}", tr_fmt("Dependent: {}", &[&count(|sample| sample.depends_on == 1)]))?;
+        writeln!(f, "{}", tr_fmt("Disposable (not referenced): {}", &[&count(|sample| sample.is_depended_on == 2)]))?;
+        let leading = count(|sample| sample.is_leading == 1 || sample.is_leading == 3);
+        if leading > 0
+        {
+            writeln!(f, "{}", tr_fmt("Leading Samples: {}", &[&leading]))?;
+        }
+        let redundant = count(|sample| sample.has_redundancy == 1);
+        if redundant > 0
+        {
+            writeln!(f, "{}", tr_fmt("Redundant Coding: {} samples", &[&redundant]))?;
+        }
+
+        if self.samples.is_empty() == false
+        {
+            let pattern: String = self.samples.iter().take(MAX_PATTERN_SAMPLES).map(SampleDependency::symbol).collect();
+            let more = if self.samples.len() > MAX_PATTERN_SAMPLES
+            {
+                "..."
+            }
+            else
+            {
+                ""
+            };
+            writeln!(f, "{}", tr_fmt("Pattern: {}{} (I independent, P referenced, b disposable)", &[&pattern, &more]))?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Number of sidx references listed in the text output
+const MAX_DISPLAYED_REFERENCES: usize = 10;
+
+/// Reference of a Segment Index Box to a subsegment or another sidx
+#[derive(Debug, Clone)]
+pub struct SegmentReference
+{
+    /// True if the reference points to another sidx box instead of media
+    pub reference_type:      bool,
+    /// Size of the referenced material in bytes
+    pub referenced_size:     u32,
+    /// Duration in sidx timescale units
+    pub subsegment_duration: u32,
+    pub starts_with_sap:     bool,
+    pub sap_type:            u8,
+    pub sap_delta_time:      u32
+}
+
+/// Segment Index Box (sidx)
+#[derive(Debug, Clone)]
+pub struct SegmentIndexBox
+{
+    pub version:                    u8,
+    pub reference_id:               u32,
+    pub timescale:                  u32,
+    pub earliest_presentation_time: u64,
+    /// Distance from the end of this box to the first referenced byte
+    pub first_offset:               u64,
+    pub reference_count:            u16,
+    pub references:                 Vec<SegmentReference>
+}
+
+impl SegmentIndexBox
+{
+    /// Parse sidx (Segment Index) box
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let field = |position: usize| u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]);
+
+        if data.len() < 12
+        {
+            return Err(tr("sidx box too short").to_string());
+        }
+
+        let version = data[0];
+        let reference_id = field(4);
+        let timescale = field(8);
+
+        // Version 1 uses 64-bit presentation time and offset
+        let (earliest_presentation_time, first_offset, position) = if version == 1
+        {
+            if data.len() < 32
+            {
+                return Err(tr("sidx version 1 box too short").to_string());
+            }
+            (((field(12) as u64) << 32) | field(16) as u64, ((field(20) as u64) << 32) | field(24) as u64, 28)
+        }
+        else
+        {
+            if data.len() < 24
+            {
+                return Err(tr("sidx version 0 box too short").to_string());
+            }
+            (field(12) as u64, field(16) as u64, 20)
+        };
+
+        // Two reserved bytes precede the reference count
+        let reference_count = u16::from_be_bytes([data[position + 2], data[position + 3]]);
+        let references = data[position + 4..]
+            .chunks_exact(12)
+            .take(reference_count as usize)
+            .map(|reference| {
+                let size_field = u32::from_be_bytes([reference[0], reference[1], reference[2], reference[3]]);
+                let sap_field = u32::from_be_bytes([reference[8], reference[9], reference[10], reference[11]]);
+                SegmentReference {
+                    reference_type:      size_field & 0x8000_0000 != 0,
+                    referenced_size:     size_field & 0x7FFF_FFFF,
+                    subsegment_duration: u32::from_be_bytes([reference[4], reference[5], reference[6], reference[7]]),
+                    starts_with_sap:     sap_field & 0x8000_0000 != 0,
+                    sap_type:            ((sap_field >> 28) & 0x07) as u8,
+                    sap_delta_time:      sap_field & 0x0FFF_FFFF
+                }
+            })
+            .collect();
+
+        Ok(SegmentIndexBox { version, reference_id, timescale, earliest_presentation_time, first_offset, reference_count, references })
+    }
+
+    /// Convert timescale units to seconds
+    fn seconds(&self, value: u64) -> Option<f64>
+    {
+        (self.timescale > 0).then(|| value as f64 / self.timescale as f64)
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "reference_id": self.reference_id,
+            "timescale": self.timescale,
+            "earliest_presentation_time": self.earliest_presentation_time,
+            "first_offset": self.first_offset,
+            "reference_count": self.reference_count,
+            "references": self.references.iter().map(|reference| serde_json::json!({
+                "reference_type": reference.reference_type,
+                "referenced_size": reference.referenced_size,
+                "subsegment_duration": reference.subsegment_duration,
+                "starts_with_sap": reference.starts_with_sap,
+                "sap_type": reference.sap_type,
+                "sap_delta_time": reference.sap_delta_time
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for SegmentIndexBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let with_seconds = |value: u64| match self.seconds(value)
+        {
+            | Some(seconds) => format!("{} ({:.3} s)", value, seconds),
+            | None => value.to_string()
+        };
+
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Reference ID: {}", &[&self.reference_id]))?;
+        writeln!(f, "{}", tr_fmt("Timescale: {} units/second", &[&self.timescale]))?;
+        writeln!(f, "{}", tr_fmt("Earliest Presentation Time: {}", &[&with_seconds(self.earliest_presentation_time)]))?;
+        writeln!(f, "{}", tr_fmt("First Offset: {}", &[&self.first_offset]))?;
+        writeln!(f, "{}", tr_fmt("Reference Count: {}", &[&self.reference_count]))?;
+
+        let total_size: u64 = self.references.iter().map(|reference| reference.referenced_size as u64).sum();
+        let total_duration: u64 = self.references.iter().map(|reference| reference.subsegment_duration as u64).sum();
+        writeln!(f, "{}", tr_fmt("Total Referenced Size: {} bytes", &[&total_size]))?;
+        writeln!(f, "{}", tr_fmt("Total Duration: {}", &[&with_seconds(total_duration)]))?;
+
+        for (index, reference) in self.references.iter().take(MAX_DISPLAYED_REFERENCES).enumerate()
+        {
+            let target = if reference.reference_type == true
+            {
+                "sidx"
+            }
+            else
+            {
+                "media"
+            };
+            let sap = if reference.starts_with_sap == true
+            {
+                tr_fmt(", starts with SAP type {}", &[&reference.sap_type])
+            }
+            else
+            {
+                String::new()
+            };
+            writeln!(
+                f,
+                "{}",
+                tr_fmt("Reference {}: {} {} bytes, duration {}{}", &[
+                    &(index + 1),
+                    &target,
+                    &reference.referenced_size,
+                    &with_seconds(reference.subsegment_duration as u64),
+                    &sap
+                ])
+            )?;
+        }
+        if self.references.len() > MAX_DISPLAYED_REFERENCES
+        {
+            writeln!(f, "{}", tr_fmt("... {} more references", &[&(self.references.len() - MAX_DISPLAYED_REFERENCES)]))?;
+        }
+        Ok(())
+    }
+}
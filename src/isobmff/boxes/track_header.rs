@@ -1,5 +1,10 @@
 use std::fmt;
 
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::timestamps::format_mac_time
+};
+
 /// Track Header Box (tkhd)
 #[derive(Debug, Clone)]
 pub struct TrackHeaderBox
@@ -13,10 +18,89 @@ pub struct TrackHeaderBox
     pub layer:             i16,
     pub alternate_group:   i16,
     pub volume:            f64,
+    pub matrix:            TransformationMatrix,
     pub width:             f64,
     pub height:            f64
 }
 
+/// Transformation matrix of a track header, applied to the decoded picture on display
+///
+/// Stored as `{a, b, u, c, d, v, x, y, w}`: a point (p, q) is displayed at
+/// (a·p + c·q + x, b·p + d·q + y). `a`, `b`, `c`, `d`, `x` and `y` are 16.16 fixed point,
+/// `u`, `v` and `w` are 2.30 fixed point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformationMatrix
+{
+    pub values: [f64; 9]
+}
+
+impl TransformationMatrix
+{
+    /// Parse the 36-byte matrix
+    pub fn parse(data: &[u8; 36]) -> Self
+    {
+        let mut values = [0.0; 9];
+        for (index, value) in values.iter_mut().enumerate()
+        {
+            let fixed = i32::from_be_bytes([data[index * 4], data[index * 4 + 1], data[index * 4 + 2], data[index * 4 + 3]]);
+            // The third column (u, v, w) uses 2.30 fixed point
+            let scale = if index % 3 == 2
+            {
+                1_073_741_824.0
+            }
+            else
+            {
+                65536.0
+            };
+            *value = fixed as f64 / scale;
+        }
+        Self { values }
+    }
+
+    /// Whether the matrix leaves the picture unchanged
+    pub fn is_identity(&self) -> bool
+    {
+        self.values == [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+    }
+
+    /// Clockwise rotation in degrees (0 to 359), rounded to whole degrees
+    pub fn rotation(&self) -> u16
+    {
+        let [a, b, ..] = self.values;
+        let degrees = b.atan2(a).to_degrees().round() as i32;
+        degrees.rem_euclid(360) as u16
+    }
+
+    /// Horizontal and vertical scale factors
+    pub fn scale(&self) -> (f64, f64)
+    {
+        let [a, b, _, c, d, ..] = self.values;
+        (a.hypot(b), c.hypot(d))
+    }
+
+    /// Translation in pixels
+    pub fn translation(&self) -> (f64, f64)
+    {
+        (self.values[6], self.values[7])
+    }
+
+    /// Whether the matrix mirrors the picture (negative determinant)
+    pub fn is_mirrored(&self) -> bool
+    {
+        let [a, b, _, c, d, ..] = self.values;
+        a * d - b * c < 0.0
+    }
+}
+
+impl fmt::Display for TransformationMatrix
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let [a, b, u, c, d, v, x, y, w] = self.values;
+        write!(f, "[{} {} {} | {} {} {} | {} {} {}]", a, b, u, c, d, v, x, y, w)
+    }
+}
+
 impl TrackHeaderBox
 {
     /// Parse tkhd (Track Header) box
@@ -24,7 +108,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
     {
         if data.len() < 4
         {
-            return Err("tkhd box too short".to_string());
+            return Err(tr("tkhd box too short").to_string());
         }
 
         let version = data[0];
@@ -35,7 +119,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 64-bit version
             if data.len() < 40
             {
-                return Err("tkhd version 1 box too short".to_string());
+                return Err(tr("tkhd version 1 box too short").to_string());
             }
 
             let creation = u64::from_be_bytes([data[4], data[5], data[6], data[7], data[8], data[9], data[10], data[11]]);
@@ -51,7 +135,7 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             // 32-bit version
             if data.len() < 28
             {
-                return Err("tkhd version 0 box too short".to_string());
+                return Err(tr("tkhd version 0 box too short").to_string());
             }
 
             let creation = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as u64;
@@ -73,9 +157,9 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
             24
         };
 
-        if data.len() < base_offset + 52
+        if data.len() < base_offset + 60
         {
-            return Err("tkhd box too short for additional fields".to_string());
+            return Err(tr("tkhd box too short for additional fields").to_string());
         }
 
         // 8 bytes reserved at base_offset
@@ -84,7 +168,9 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         let volume_fixed = i16::from_be_bytes([data[base_offset + 12], data[base_offset + 13]]);
         let volume = (volume_fixed as f64) / 256.0;
         // 2 bytes reserved at base_offset + 14
-        // 36 bytes transformation matrix at base_offset + 16
+        let mut matrix_bytes = [0u8; 36];
+        matrix_bytes.copy_from_slice(&data[base_offset + 16..base_offset + 52]);
+        let matrix = TransformationMatrix::parse(&matrix_bytes);
 
         let width_fixed = u32::from_be_bytes([data[base_offset + 52], data[base_offset + 53], data[base_offset + 54], data[base_offset + 55]]);
         let width = (width_fixed as f64) / 65536.0;
@@ -92,7 +178,26 @@ pub fn parse(data: &[u8]) -> Result<Self, String>
         let height_fixed = u32::from_be_bytes([data[base_offset + 56], data[base_offset + 57], data[base_offset + 58], data[base_offset + 59]]);
         let height = (height_fixed as f64) / 65536.0;
 
-        Ok(TrackHeaderBox { version, flags, creation_time, modification_time, track_id, duration, layer, alternate_group, volume, width, height })
+        Ok(TrackHeaderBox { version, flags, creation_time, modification_time, track_id, duration, layer, alternate_group, volume, matrix, width, height })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "flags": self.flags,
+            "creation_time": self.creation_time,
+            "modification_time": self.modification_time,
+            "track_id": self.track_id,
+            "duration": self.duration,
+            "layer": self.layer,
+            "alternate_group": self.alternate_group,
+            "volume": self.volume,
+            "matrix": self.matrix.values,
+            "width": self.width,
+            "height": self.height
+        })
     }
 }
 
@@ -100,24 +205,55 @@ impl fmt::Display for TrackHeaderBox
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "{}", tr_fmt("Version: {}", &[&self.version]))?;
         writeln!(
             f,
-            "Flags: 0x{:06X} (Track enabled: {}, In movie: {}, In preview: {})",
-            self.flags,
-            (self.flags & 0x01) != 0,
-            (self.flags & 0x02) != 0,
-            (self.flags & 0x04) != 0
+            "{}",
+            tr_fmt("Flags: {} (Track enabled: {}, In movie: {}, In preview: {})", &[
+                &format!("0x{:06X}", self.flags),
+                &((self.flags & 0x01) != 0),
+                &((self.flags & 0x02) != 0),
+                &((self.flags & 0x04) != 0)
+            ])
         )?;
-        writeln!(f, "Creation Time: {} (Mac epoch)", self.creation_time)?;
-        writeln!(f, "Modification Time: {} (Mac epoch)", self.modification_time)?;
-        writeln!(f, "Track ID: {}", self.track_id)?;
-        writeln!(f, "Duration: {} units", self.duration)?;
-        writeln!(f, "Layer: {}", self.layer)?;
-        writeln!(f, "Alternate Group: {}", self.alternate_group)?;
-        writeln!(f, "Volume: {:.2}", self.volume)?;
-        writeln!(f, "Width: {:.2} pixels", self.width)?;
-        writeln!(f, "Height: {:.2} pixels", self.height)?;
+        writeln!(f, "{}", tr_fmt("Creation Time: {}", &[&format_mac_time(self.creation_time)]))?;
+        writeln!(f, "{}", tr_fmt("Modification Time: {}", &[&format_mac_time(self.modification_time)]))?;
+        writeln!(f, "{}", tr_fmt("Track ID: {}", &[&self.track_id]))?;
+        writeln!(f, "{}", tr_fmt("Duration: {} units", &[&self.duration]))?;
+        writeln!(f, "{}", tr_fmt("Layer: {}", &[&self.layer]))?;
+        writeln!(f, "{}", tr_fmt("Alternate Group: {}", &[&self.alternate_group]))?;
+        writeln!(f, "{}", tr_fmt("Volume: {}", &[&format!("{:.2}", self.volume)]))?;
+        writeln!(f, "{}", tr_fmt("Width: {} pixels", &[&format!("{:.2}", self.width)]))?;
+        writeln!(f, "{}", tr_fmt("Height: {} pixels", &[&format!("{:.2}", self.height)]))?;
+
+        if self.matrix.is_identity() == true
+        {
+            writeln!(f, "{}", tr("Matrix: identity"))?;
+            return Ok(());
+        }
+        writeln!(f, "{}", tr_fmt("Matrix: {}", &[&self.matrix]))?;
+        let rotation = self.matrix.rotation();
+        match rotation
+        {
+            | 90 | 270 =>
+                writeln!(f, "{}", tr_fmt("Rotation: {}° clockwise (displayed as {}x{})", &[&rotation, &format!("{:.0}", self.height), &format!("{:.0}", self.width)]))?,
+            | 0 => (),
+            | _ => writeln!(f, "{}", tr_fmt("Rotation: {}° clockwise", &[&rotation]))?
+        }
+        if self.matrix.is_mirrored() == true
+        {
+            writeln!(f, "{}", tr("Mirrored: yes"))?;
+        }
+        let (scale_x, scale_y) = self.matrix.scale();
+        if scale_x != 1.0 || scale_y != 1.0
+        {
+            writeln!(f, "{}", tr_fmt("Scale: {} x {}", &[&format!("{:.4}", scale_x), &format!("{:.4}", scale_y)]))?;
+        }
+        let (translate_x, translate_y) = self.matrix.translation();
+        if translate_x != 0.0 || translate_y != 0.0
+        {
+            writeln!(f, "{}", tr_fmt("Translation: {}, {} pixels", &[&format!("{:.2}", translate_x), &format!("{:.2}", translate_y)]))?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,282 @@
+use std::fmt;
+
+use crate::{
+    bytes::{read_u32_be, read_u64_be},
+    hashes::to_hex,
+    hexdump::format_hexdump_limited,
+    i18n::{tr, tr_fmt}
+};
+
+/// Number of XML lines shown for XMP and spherical video payloads
+const MAX_DISPLAYED_XML_LINES: usize = 40;
+
+/// Number of payload bytes shown as hexdump for unknown UUIDs
+const MAX_DISPLAYED_PAYLOAD_BYTES: usize = 64;
+
+/// Adobe XMP metadata packet
+pub const XMP_UUID: [u8; 16] = [0xBE, 0x7A, 0xCF, 0xCB, 0x97, 0xA9, 0x42, 0xE8, 0x9C, 0x71, 0x99, 0x94, 0x91, 0xE3, 0xAF, 0xAC];
+/// Microsoft PIFF track fragment extended header (Smooth Streaming absolute time)
+pub const TFXD_UUID: [u8; 16] = [0x6D, 0x1D, 0x9B, 0x05, 0x42, 0xD5, 0x44, 0xE6, 0x80, 0xE2, 0x14, 0x1D, 0xAF, 0xF7, 0x57, 0xB2];
+/// Microsoft PIFF track fragment reference (Smooth Streaming look-ahead fragments)
+pub const TFRF_UUID: [u8; 16] = [0xD4, 0x80, 0x7E, 0xF2, 0xCA, 0x39, 0x46, 0x95, 0x8E, 0x54, 0x26, 0xCB, 0x9E, 0x46, 0xA7, 0x9F];
+/// Sony PSP user metadata (USMT)
+pub const PSP_USMT_UUID: [u8; 16] = [0x55, 0x53, 0x4D, 0x54, 0x21, 0xD2, 0x4F, 0xCE, 0xBB, 0x88, 0x69, 0x5C, 0xFA, 0xC9, 0xC7, 0x40];
+/// Sony PSP profile (PROF)
+pub const PSP_PROF_UUID: [u8; 16] = [0x50, 0x52, 0x4F, 0x46, 0x21, 0xD2, 0x4F, 0xCE, 0xBB, 0x88, 0x69, 0x5C, 0xFA, 0xC9, 0xC7, 0x40];
+
+/// Google spherical video V1 metadata (RDF/XML)
+pub const SPHERICAL_UUID: [u8; 16] = [0xFF, 0xCC, 0x82, 0x63, 0xF8, 0x55, 0x4A, 0x93, 0x88, 0x14, 0x58, 0x7A, 0x02, 0x52, 0x1F, 0xDD];
+
+/// Names of further well-known UUIDs that are only identified, not decoded
+const KNOWN_UUIDS: [([u8; 16], &str); 4] = [
+    ([0xA2, 0x39, 0x4F, 0x52, 0x5A, 0x9B, 0x4F, 0x14, 0xA2, 0x44, 0x6C, 0x42, 0x7C, 0x64, 0x8D, 0xF4], "PIFF Sample Encryption"),
+    ([0x89, 0x74, 0xDB, 0xCE, 0x7B, 0xE7, 0x4C, 0x51, 0x84, 0xF9, 0x71, 0x48, 0xF9, 0x88, 0x25, 0x54], "PIFF Track Encryption"),
+    ([0xD0, 0x8A, 0x4F, 0x18, 0x10, 0xF3, 0x4A, 0x82, 0xB6, 0xC8, 0x32, 0xD8, 0xAB, 0xA1, 0x83, 0x3D], "PIFF Protection System Specific Header"),
+    ([0x85, 0xC0, 0xB6, 0x87, 0x82, 0x0F, 0x11, 0xE0, 0x81, 0x11, 0xF4, 0xCE, 0x46, 0x2B, 0x6A, 0x48], "Canon CR3 Metadata")
+];
+
+/// Format a UUID in canonical 8-4-4-4-12 form
+pub fn format_uuid(uuid: &[u8; 16]) -> String
+{
+    let hex: String = uuid.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+/// Decoded payload of a uuid box with a known extended type
+#[derive(Debug, Clone)]
+pub enum UuidPayload
+{
+    /// XML document (XMP packet, spherical video metadata)
+    Xml(String),
+    /// PIFF tfxd: absolute time and duration of the fragment
+    FragmentTime
+    {
+        absolute_time: u64, duration: u64
+    },
+    /// PIFF tfrf: time and duration of the following fragments
+    FragmentReferences(Vec<(u64, u64)>),
+    /// Sony PSP metadata strings (type, text)
+    PspMetadata(Vec<(u16, String)>),
+    /// Unknown or undecoded payload
+    Raw(Vec<u8>)
+}
+
+/// User Extension Box (uuid)
+#[derive(Debug, Clone)]
+pub struct UuidBox
+{
+    /// Extended type following the box header
+    pub uuid:    [u8; 16],
+    pub payload: UuidPayload
+}
+
+impl UuidBox
+{
+    /// Parse uuid (User Extension) box; `data` starts with the 16-byte extended type
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 16
+        {
+            return Err(tr("uuid box too short").to_string());
+        }
+
+        let mut uuid = [0u8; 16];
+        uuid.copy_from_slice(&data[..16]);
+        let body = &data[16..];
+
+        let payload = match uuid
+        {
+            | XMP_UUID | SPHERICAL_UUID => UuidPayload::Xml(String::from_utf8_lossy(body).trim_end_matches('\0').to_string()),
+            | TFXD_UUID => Self::parse_fragment_time(body)?,
+            | TFRF_UUID => Self::parse_fragment_references(body)?,
+            | PSP_USMT_UUID => UuidPayload::PspMetadata(Self::parse_psp_metadata(body)),
+            | _ => UuidPayload::Raw(body.to_vec())
+        };
+
+        Ok(UuidBox { uuid, payload })
+    }
+
+    /// Parse PIFF tfxd (version 1: 64-bit times)
+    fn parse_fragment_time(body: &[u8]) -> Result<UuidPayload, String>
+    {
+        let (absolute_time, duration) = match body.first()
+        {
+            | Some(1) if body.len() >= 20 => (read_u64_be(body, 4), read_u64_be(body, 12)),
+            | Some(0) if body.len() >= 12 => (read_u32_be(body, 4) as u64, read_u32_be(body, 8) as u64),
+            | _ => return Err(tr("tfxd uuid box too short").to_string())
+        };
+
+        Ok(UuidPayload::FragmentTime { absolute_time, duration })
+    }
+
+    /// Parse PIFF tfrf (version 1: 64-bit times)
+    fn parse_fragment_references(body: &[u8]) -> Result<UuidPayload, String>
+    {
+        if body.len() < 5
+        {
+            return Err(tr("tfrf uuid box too short").to_string());
+        }
+
+        let entry_size = if body[0] == 1
+        {
+            16
+        }
+        else
+        {
+            8
+        };
+        let count = body[4] as usize;
+        let fragments = body[5..]
+            .chunks_exact(entry_size)
+            .take(count)
+            .map(|entry| {
+                if entry_size == 16
+                {
+                    (read_u64_be(entry, 0), read_u64_be(entry, 8))
+                }
+                else
+                {
+                    (read_u32_be(entry, 0) as u64, read_u32_be(entry, 4) as u64)
+                }
+            })
+            .collect();
+
+        Ok(UuidPayload::FragmentReferences(fragments))
+    }
+
+    /// Parse the MTDT strings of a Sony PSP USMT box (UTF-16 BE text entries)
+    fn parse_psp_metadata(body: &[u8]) -> Vec<(u16, String)>
+    {
+        let mut entries = Vec::new();
+
+        // USMT holds an MTDT box: size, 'MTDT', entry count, then entries of
+        // size (u16, including this header), type (u32), language (u16), encoding (u16) and text
+        if body.len() < 10 || &body[4..8] != b"MTDT"
+        {
+            return entries;
+        }
+        let count = u16::from_be_bytes([body[8], body[9]]);
+        let mut position = 10;
+        for _ in 0..count
+        {
+            if position + 10 > body.len()
+            {
+                break;
+            }
+            let size = u16::from_be_bytes([body[position], body[position + 1]]) as usize;
+            if size < 10 || position + size > body.len()
+            {
+                break;
+            }
+            let entry_type = read_u32_be(body, position + 2) as u16;
+            let units: Vec<u16> = body[position + 10..position + size].chunks_exact(2).map(|unit| u16::from_be_bytes([unit[0], unit[1]])).collect();
+            entries.push((entry_type, String::from_utf16_lossy(&units).trim_end_matches('\0').to_string()));
+            position += size;
+        }
+
+        entries
+    }
+
+    /// Name of the extended type if it is well known
+    pub fn uuid_name(&self) -> Option<&'static str>
+    {
+        match self.uuid
+        {
+            | XMP_UUID => Some("XMP Metadata"),
+            | TFXD_UUID => Some("PIFF Track Fragment Extended Header (tfxd)"),
+            | TFRF_UUID => Some("PIFF Track Fragment Reference (tfrf)"),
+            | PSP_USMT_UUID => Some("Sony PSP User Metadata (USMT)"),
+            | PSP_PROF_UUID => Some("Sony PSP Profile (PROF)"),
+            | SPHERICAL_UUID => Some("Spherical Video V1"),
+            | _ => KNOWN_UUIDS.iter().find(|(uuid, _)| *uuid == self.uuid).map(|(_, name)| *name)
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let payload = match self.payload
+        {
+            | UuidPayload::Xml(ref xml) => serde_json::json!({ "xml": xml }),
+            | UuidPayload::FragmentTime { absolute_time, duration } => serde_json::json!({ "absolute_time": absolute_time, "duration": duration }),
+            | UuidPayload::FragmentReferences(ref references) => serde_json::json!({
+                "fragment_references": references.iter().map(|&(absolute_time, duration)| serde_json::json!({ "absolute_time": absolute_time, "duration": duration })).collect::<Vec<_>>()
+            }),
+            | UuidPayload::PspMetadata(ref fields) => serde_json::json!({
+                "psp_metadata": fields.iter().map(|(field_type, value)| serde_json::json!({ "field_type": field_type, "value": value })).collect::<Vec<_>>()
+            }),
+            | UuidPayload::Raw(ref data) => serde_json::json!({ "data_size": data.len() })
+        };
+        serde_json::json!({ "uuid": to_hex(&self.uuid), "payload": payload })
+    }
+}
+
+/// Describe a Sony PSP metadata entry type
+fn psp_metadata_type_name(entry_type: u16) -> &'static str
+{
+    match entry_type
+    {
+        | 1 => "Title",
+        | 3 => "Creation Date",
+        | 4 => "Software",
+        | 10 => "Encoder",
+        | _ => "Entry"
+    }
+}
+
+impl fmt::Display for UuidBox
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self.uuid_name()
+        {
+            | Some(name) => writeln!(f, "{}", tr_fmt("UUID: {} ({})", &[&format_uuid(&self.uuid), &name]))?,
+            | None => writeln!(f, "{}", tr_fmt("UUID: {}", &[&format_uuid(&self.uuid)]))?
+        }
+
+        match &self.payload
+        {
+            | UuidPayload::Xml(xml) =>
+            {
+                let lines: Vec<&str> = xml.lines().filter(|line| line.trim().is_empty() == false).collect();
+                for line in lines.iter().take(MAX_DISPLAYED_XML_LINES)
+                {
+                    writeln!(f, "  {}", line.trim_end())?;
+                }
+                if lines.len() > MAX_DISPLAYED_XML_LINES
+                {
+                    writeln!(f, "  {}", tr_fmt("... {} more lines", &[&(lines.len() - MAX_DISPLAYED_XML_LINES)]))?;
+                }
+            }
+            | UuidPayload::FragmentTime { absolute_time, duration } =>
+            {
+                writeln!(f, "{}", tr_fmt("Fragment Absolute Time: {}", &[&absolute_time]))?;
+                writeln!(f, "{}", tr_fmt("Fragment Duration: {}", &[&duration]))?;
+            }
+            | UuidPayload::FragmentReferences(fragments) =>
+            {
+                writeln!(f, "{}", tr_fmt("Fragment Count: {}", &[&fragments.len()]))?;
+                for (index, (time, duration)) in fragments.iter().enumerate()
+                {
+                    writeln!(f, "{}", tr_fmt("Fragment {}: time {}, duration {}", &[&(index + 1), &time, &duration]))?;
+                }
+            }
+            | UuidPayload::PspMetadata(entries) =>
+            {
+                for (entry_type, text) in entries
+                {
+                    writeln!(f, "{}", tr_fmt("{} (type {}): \"{}\"", &[&psp_metadata_type_name(*entry_type), &entry_type, &text]))?;
+                }
+            }
+            | UuidPayload::Raw(data) =>
+            {
+                writeln!(f, "{}", tr_fmt("Payload: {} bytes", &[&data.len()]))?;
+                for line in format_hexdump_limited(data, 0, Some(MAX_DISPLAYED_PAYLOAD_BYTES)).lines()
+                {
+                    writeln!(f, "  {}", line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::{fmt, io::SeekFrom};
+
+use crate::{
+    chapters::{Chapter, format_time},
+    i18n::tr_fmt,
+    isobmff::{
+        r#box::IsobmffBox,
+        content::IsobmffContent,
+        samples::{sample_offsets, sample_sizes, track_id},
+        tree::IsobmffTree
+    },
+    media_source::MediaSource
+};
+
+/// Decode a text sample: 16-bit length followed by UTF-8 or BOM-prefixed UTF-16 text
+fn decode_text_sample(sample: &[u8]) -> String
+{
+    let length = sample.get(0..2).map_or(0, |bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as usize);
+    let text = sample.get(2..2 + length).unwrap_or(&sample[sample.len().min(2)..]);
+    match text
+    {
+        | [0xFE, 0xFF, rest @ ..] => String::from_utf16_lossy(&rest.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect::<Vec<_>>()),
+        | [0xFF, 0xFE, rest @ ..] => String::from_utf16_lossy(&rest.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect::<Vec<_>>()),
+        | _ => String::from_utf8_lossy(text).to_string()
+    }
+}
+
+/// Text track a `chap` track reference points to, with the chapters read from its samples
+#[derive(Debug, Clone)]
+pub struct ChapterTrack
+{
+    /// Track holding the `chap` reference
+    pub referenced_by: u32,
+    pub track_id:      u32,
+    pub chapters:      Vec<Chapter>
+}
+
+impl ChapterTrack
+{
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "track_id": self.track_id,
+            "referenced_by": self.referenced_by,
+            "chapters": self.chapters.iter().map(|chapter| serde_json::json!({
+                "start": chapter.start as f64 / 1000.0,
+                "end": chapter.end as f64 / 1000.0,
+                "title": chapter.title
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for ChapterTrack
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Track {} (referenced by track {}), {} chapters:", &[&self.track_id, &self.referenced_by, &self.chapters.len()]))?;
+        for (index, chapter) in self.chapters.iter().enumerate()
+        {
+            writeln!(f, "  {:>2}. {} - {}  {}", index + 1, format_time(chapter.start), format_time(chapter.end), chapter.title)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a `trak` box holds text samples (QuickTime `text` or MPEG-4 `sbtl` handler)
+fn is_text_track(trak: &IsobmffBox) -> bool
+{
+    match trak.find("mdia/hdlr").and_then(|hdlr| hdlr.content.as_ref())
+    {
+        | Some(IsobmffContent::Handler(handler)) => matches!(handler.handler_type.as_str(), "text" | "sbtl"),
+        | _ => false
+    }
+}
+
+/// Read the chapters of the text track referenced by the first `chap` track reference
+///
+/// A `chap` reference can also list a video track with chapter pictures, so the first referenced
+/// text track is used. Each of its samples is one chapter title; the decoding time and duration
+/// of the sample give the chapter start and end.
+pub fn read_chapter_track(tree: &IsobmffTree, file: &mut dyn MediaSource) -> Result<Option<ChapterTrack>, Box<dyn std::error::Error>>
+{
+    let Some(moov) = tree.boxes.iter().find(|isobmff_box| isobmff_box.box_type == "moov")
+    else
+    {
+        return Ok(None);
+    };
+    let tracks: Vec<&IsobmffBox> = moov.children.iter().filter(|child| child.box_type == "trak").collect();
+
+    let reference = tracks.iter().find_map(|trak| match trak.find("tref/chap").and_then(|chap| chap.content.as_ref())
+    {
+        | Some(IsobmffContent::Chapter(chapter_box)) => chapter_box
+            .track_ids
+            .iter()
+            .find_map(|&chapter_track_id| tracks.iter().find(|candidate| track_id(candidate) == chapter_track_id && is_text_track(candidate) == true))
+            .map(|chapter_track| (track_id(trak), *chapter_track)),
+        | _ => None
+    });
+    let Some((referenced_by, trak, stbl)) = reference.and_then(|(referenced_by, trak)| Some((referenced_by, trak, trak.find("mdia/minf/stbl")?)))
+    else
+    {
+        return Ok(None);
+    };
+    let timescale = match trak.find("mdia/mdhd").and_then(|mdhd| mdhd.content.as_ref())
+    {
+        | Some(IsobmffContent::MediaHeader(media_header)) if media_header.timescale > 0 => media_header.timescale as u64,
+        | _ => return Err("Chapter track has no valid media timescale".into())
+    };
+
+    let durations: Vec<u32> = match stbl.find("stts").and_then(|stts| stts.content.as_ref())
+    {
+        | Some(IsobmffContent::TimeToSample(stts)) => stts.sample_durations().collect(),
+        | _ => Vec::new()
+    };
+    let sizes = sample_sizes(stbl);
+    let offsets = sample_offsets(stbl, &sizes);
+
+    let mut chapters = Vec::new();
+    let mut time = 0u64;
+    for (index, (&offset, &size)) in offsets.iter().zip(&sizes).enumerate()
+    {
+        let duration = durations.get(index).copied().unwrap_or(0) as u64;
+
+        let mut sample = vec![0u8; size as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut sample)?;
+
+        let mut title = decode_text_sample(&sample);
+        if title.is_empty() == true
+        {
+            title = format!("Chapter {}", index + 1);
+        }
+        chapters.push(Chapter { start: time * 1000 / timescale, end: (time + duration) * 1000 / timescale, title, url: None, image: None });
+        time += duration;
+    }
+
+    Ok(Some(ChapterTrack { referenced_by, track_id: track_id(trak), chapters }))
+}
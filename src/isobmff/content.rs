@@ -3,16 +3,37 @@
 // Re-export box types from individual modules
 pub use crate::isobmff::boxes::chapter::ChapterBox;
 pub use crate::isobmff::boxes::{
+    av1_config::Av1ConfigurationBox,
+    avc_config::{AvcConfigurationBox, ParameterSet},
     data_reference::{DataReferenceBox, UrlEntryBox, UrnEntryBox},
-    edit_list::EditListBox,
+    edit_list::{EditListBox, EditListEntry},
+    esds::{AudioSpecificConfig, ElementaryStreamDescriptorBox},
+    event_message::{EventMessageBox, ProducerReferenceTimeBox},
     file_type::FileTypeBox,
     handler::HandlerBox,
+    hevc_config::{HevcConfigurationBox, HevcNalArray},
+    image_items::{
+        ImageSpatialExtentsBox, ItemExtent, ItemInfoEntryBox, ItemLocation, ItemLocationBox, ItemPropertyAssociationBox, ItemReference, ItemReferenceBox,
+        PixelInformationBox, PrimaryItemBox, PropertyAssociation
+    },
     media_header::MediaHeaderBox,
     media_info_header::{NullMediaHeaderBox, SoundMediaHeaderBox, VideoMediaHeaderBox},
-    metadata_keys::{MetadataMeanBox, MetadataNameBox},
+    metadata_keys::{MetadataKey, MetadataKeysBox, MetadataMeanBox, MetadataNameBox},
+    movie_fragment::{FragmentContext, MovieFragmentHeaderBox, TrackExtendsBox, TrackFragmentDecodeTimeBox, TrackFragmentHeaderBox, TrackRunBox, TrackRunSample},
     movie_header::MovieHeaderBox,
-    sample_table::{ChunkOffset64Box, ChunkOffsetBox, SampleDescriptionBox, SampleSizeBox, SampleToChunkBox, TimeToSampleBox},
-    track_header::TrackHeaderBox
+    protection::{
+        OriginalFormatBox, ProtectionSchemeInfo, ProtectionSystemHeaderBox, SampleAuxiliaryInfoOffsetsBox, SampleAuxiliaryInfoSizesBox, SampleEncryptionBox,
+        SampleEncryptionEntry, SchemeTypeBox, Subsample, TrackEncryptionBox
+    },
+    sample_entry::{AudioSampleEntry, BitRateBox, ColourInformationBox, PixelAspectRatioBox, SampleEntry, VisualSampleEntry},
+    sample_group::{SampleGroupDescriptionBox, SampleGroupEntry, SampleToGroupBox, SampleToGroupEntry},
+    sample_table::{
+        ChunkOffset64Box, ChunkOffsetBox, CompositionOffsetBox, SampleDependency, SampleDependencyBox, SampleDescriptionBox, SampleSizeBox, SampleToChunkBox,
+        SyncSampleBox, TimeToSampleBox
+    },
+    segment_index::{SegmentIndexBox, SegmentReference},
+    track_header::TrackHeaderBox,
+    user_extension::{UuidBox, UuidPayload}
 };
 
 /// Parsed ISOBMFF box content for various box types
@@ -34,12 +55,112 @@ pub enum IsobmffContent
     SampleSize(SampleSizeBox),
     ChunkOffset(ChunkOffsetBox),
     ChunkOffset64(ChunkOffset64Box),
+    CompositionOffset(CompositionOffsetBox),
+    SyncSample(SyncSampleBox),
+    SampleDependency(SampleDependencyBox),
+    SampleGroupDescription(SampleGroupDescriptionBox),
+    SampleToGroup(SampleToGroupBox),
+    ProtectionSystemHeader(ProtectionSystemHeaderBox),
+    TrackEncryption(TrackEncryptionBox),
+    SampleEncryption(SampleEncryptionBox),
+    SampleAuxiliaryInfoSizes(SampleAuxiliaryInfoSizesBox),
+    SampleAuxiliaryInfoOffsets(SampleAuxiliaryInfoOffsetsBox),
+    SchemeType(SchemeTypeBox),
+    OriginalFormat(OriginalFormatBox),
     EditList(EditListBox),
     UrlEntry(UrlEntryBox),
     UrnEntry(UrnEntryBox),
     Chapter(ChapterBox),
     MetadataMean(MetadataMeanBox),
-    MetadataName(MetadataNameBox)
+    MetadataName(MetadataNameBox),
+    MetadataKeys(MetadataKeysBox),
+    MovieFragmentHeader(MovieFragmentHeaderBox),
+    TrackExtends(TrackExtendsBox),
+    TrackFragmentHeader(TrackFragmentHeaderBox),
+    TrackFragmentDecodeTime(TrackFragmentDecodeTimeBox),
+    TrackRun(TrackRunBox),
+    SegmentIndex(SegmentIndexBox),
+    EventMessage(EventMessageBox),
+    ProducerReferenceTime(ProducerReferenceTimeBox),
+    ItemLocation(ItemLocationBox),
+    ItemInfoEntry(ItemInfoEntryBox),
+    PrimaryItem(PrimaryItemBox),
+    ItemReference(ItemReferenceBox),
+    ItemPropertyAssociation(ItemPropertyAssociationBox),
+    ImageSpatialExtents(ImageSpatialExtentsBox),
+    PixelInformation(PixelInformationBox),
+    AvcConfiguration(AvcConfigurationBox),
+    HevcConfiguration(HevcConfigurationBox),
+    Av1Configuration(Av1ConfigurationBox),
+    ColourInformation(ColourInformationBox),
+    PixelAspectRatio(PixelAspectRatioBox),
+    UserExtension(UuidBox)
+}
+
+impl IsobmffContent
+{
+    /// Structured representation of the parsed fields for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        match self
+        {
+            | IsobmffContent::FileType(box_data) => box_data.to_json(),
+            | IsobmffContent::MovieHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::MediaHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::Handler(box_data) => box_data.to_json(),
+            | IsobmffContent::VideoMediaHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::SoundMediaHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::NullMediaHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::DataReference(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleDescription(box_data) => box_data.to_json(),
+            | IsobmffContent::TimeToSample(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleToChunk(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleSize(box_data) => box_data.to_json(),
+            | IsobmffContent::ChunkOffset(box_data) => box_data.to_json(),
+            | IsobmffContent::ChunkOffset64(box_data) => box_data.to_json(),
+            | IsobmffContent::CompositionOffset(box_data) => box_data.to_json(),
+            | IsobmffContent::SyncSample(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleDependency(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleGroupDescription(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleToGroup(box_data) => box_data.to_json(),
+            | IsobmffContent::ProtectionSystemHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackEncryption(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleEncryption(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleAuxiliaryInfoSizes(box_data) => box_data.to_json(),
+            | IsobmffContent::SampleAuxiliaryInfoOffsets(box_data) => box_data.to_json(),
+            | IsobmffContent::SchemeType(box_data) => box_data.to_json(),
+            | IsobmffContent::OriginalFormat(box_data) => box_data.to_json(),
+            | IsobmffContent::EditList(box_data) => box_data.to_json(),
+            | IsobmffContent::UrlEntry(box_data) => box_data.to_json(),
+            | IsobmffContent::UrnEntry(box_data) => box_data.to_json(),
+            | IsobmffContent::Chapter(box_data) => box_data.to_json(),
+            | IsobmffContent::MetadataMean(box_data) => box_data.to_json(),
+            | IsobmffContent::MetadataName(box_data) => box_data.to_json(),
+            | IsobmffContent::MetadataKeys(box_data) => box_data.to_json(),
+            | IsobmffContent::MovieFragmentHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackExtends(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackFragmentHeader(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackFragmentDecodeTime(box_data) => box_data.to_json(),
+            | IsobmffContent::TrackRun(box_data) => box_data.to_json(),
+            | IsobmffContent::SegmentIndex(box_data) => box_data.to_json(),
+            | IsobmffContent::EventMessage(box_data) => box_data.to_json(),
+            | IsobmffContent::ProducerReferenceTime(box_data) => box_data.to_json(),
+            | IsobmffContent::ItemLocation(box_data) => box_data.to_json(),
+            | IsobmffContent::ItemInfoEntry(box_data) => box_data.to_json(),
+            | IsobmffContent::PrimaryItem(box_data) => box_data.to_json(),
+            | IsobmffContent::ItemReference(box_data) => box_data.to_json(),
+            | IsobmffContent::ItemPropertyAssociation(box_data) => box_data.to_json(),
+            | IsobmffContent::ImageSpatialExtents(box_data) => box_data.to_json(),
+            | IsobmffContent::PixelInformation(box_data) => box_data.to_json(),
+            | IsobmffContent::AvcConfiguration(box_data) => box_data.to_json(),
+            | IsobmffContent::HevcConfiguration(box_data) => box_data.to_json(),
+            | IsobmffContent::Av1Configuration(box_data) => box_data.to_json(),
+            | IsobmffContent::ColourInformation(box_data) => box_data.to_json(),
+            | IsobmffContent::PixelAspectRatio(box_data) => box_data.to_json(),
+            | IsobmffContent::UserExtension(box_data) => box_data.to_json()
+        }
+    }
 }
 
 impl fmt::Display for IsobmffContent
@@ -63,12 +184,46 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
             | IsobmffContent::SampleSize(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::ChunkOffset(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::ChunkOffset64(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::CompositionOffset(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SyncSample(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleDependency(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleGroupDescription(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleToGroup(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ProtectionSystemHeader(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::TrackEncryption(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleEncryption(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleAuxiliaryInfoSizes(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SampleAuxiliaryInfoOffsets(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SchemeType(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::OriginalFormat(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::EditList(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::UrlEntry(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::UrnEntry(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::Chapter(box_data) => write!(f, "{}", box_data),
             | IsobmffContent::MetadataMean(box_data) => write!(f, "{}", box_data),
-            | IsobmffContent::MetadataName(box_data) => write!(f, "{}", box_data)
+            | IsobmffContent::MetadataName(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::MetadataKeys(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::MovieFragmentHeader(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::TrackExtends(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::TrackFragmentHeader(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::TrackFragmentDecodeTime(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::TrackRun(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::SegmentIndex(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::EventMessage(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ProducerReferenceTime(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ItemLocation(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ItemInfoEntry(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::PrimaryItem(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ItemReference(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ItemPropertyAssociation(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ImageSpatialExtents(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::PixelInformation(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::AvcConfiguration(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::HevcConfiguration(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::Av1Configuration(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::ColourInformation(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::PixelAspectRatio(box_data) => write!(f, "{}", box_data),
+            | IsobmffContent::UserExtension(box_data) => write!(f, "{}", box_data)
         }
     }
 }
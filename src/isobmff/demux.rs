@@ -0,0 +1,237 @@
+// Audio elementary stream extraction
+//
+// Backs `extract audio`: demuxes an audio track into a file players and codec tools read without
+// an MP4 demuxer. AAC frames get an ADTS header synthesized from the AudioSpecificConfig of the
+// esds box, FLAC frames follow the "fLaC" marker and the metadata blocks of the dfLa box, and ALAC
+// packets go into a Core Audio Format file with the magic cookie and a packet table.
+
+use std::io::{SeekFrom, Write};
+
+use crate::{
+    i18n::tr_fmt,
+    isobmff::{
+        boxes::{esds::AudioSpecificConfig, sample_entry::ALAC_CONFIG_SIZE},
+        content::IsobmffContent,
+        samples::{SampleLocation, find_track, locate_samples},
+        tree::IsobmffTree
+    },
+    media_source::MediaSource
+};
+
+/// Codec of an extractable audio track with the configuration its output file needs
+#[derive(Debug, Clone)]
+pub enum AudioCodec
+{
+    Aac(AudioSpecificConfig),
+    /// FLAC metadata blocks (STREAMINFO first)
+    Flac(Vec<u8>),
+    /// ALACSpecificConfig, written as the magic cookie
+    Alac(Vec<u8>)
+}
+
+/// Audio track prepared for extraction: codec configuration and sample positions
+#[derive(Debug, Clone)]
+pub struct AudioStream
+{
+    pub track_id: u32,
+    pub codec:    AudioCodec,
+    pub samples:  Vec<SampleLocation>
+}
+
+impl AudioStream
+{
+    /// Prepare track `id` of `tree` for extraction, None if the tree has no such track
+    pub fn from_tree(tree: &IsobmffTree, id: u32) -> Result<Option<Self>, String>
+    {
+        let Some(trak) = find_track(tree, id)
+        else
+        {
+            return Ok(None);
+        };
+        let handler_type = match trak.find("mdia/hdlr").and_then(|hdlr| hdlr.content.as_ref())
+        {
+            | Some(IsobmffContent::Handler(handler)) => handler.handler_type.as_str(),
+            | _ => ""
+        };
+        if handler_type != "soun"
+        {
+            return Err(tr_fmt("Track {} is not an audio track (handler '{}')", &[&id, &handler_type]));
+        }
+        let Some(IsobmffContent::SampleDescription(stsd)) = trak.find("mdia/minf/stbl/stsd").and_then(|stsd| stsd.content.as_ref())
+        else
+        {
+            return Err(tr_fmt("Track {} has no sample description", &[&id]));
+        };
+        let Some(entry) = stsd.entries.first()
+        else
+        {
+            return Err(tr_fmt("Track {} has no sample description", &[&id]));
+        };
+
+        let missing_config = || tr_fmt("The '{}' sample entry of track {} has no decoder configuration", &[&entry.format, &id]);
+        let codec = match entry.format.as_str()
+        {
+            | "mp4a" => AudioCodec::Aac(entry.esds().and_then(|esds| esds.audio_config).ok_or_else(missing_config)?),
+            | "fLaC" => AudioCodec::Flac(entry.flac_metadata().ok_or_else(missing_config)?),
+            | "alac" => AudioCodec::Alac(entry.alac_config().ok_or_else(missing_config)?),
+            | format => return Err(tr_fmt("Track {} has codec '{}', only AAC (mp4a), ALAC and FLAC tracks can be extracted", &[&id, &format]))
+        };
+        if let AudioCodec::Aac(ref config) = codec &&
+            config.adts_header(0).is_none() == true
+        {
+            return Err(tr_fmt("{} at {} Hz cannot be written with ADTS headers", &[&config.profile_name(), &config.sample_rate]));
+        }
+
+        Ok(Some(Self { track_id: id, codec, samples: locate_samples(tree, id) }))
+    }
+
+    /// File extension of the output
+    pub fn extension(&self) -> &'static str
+    {
+        match self.codec
+        {
+            | AudioCodec::Aac(_) => "aac",
+            | AudioCodec::Flac(_) => "flac",
+            | AudioCodec::Alac(_) => "caf"
+        }
+    }
+
+    /// Codec and container shown in the text output
+    pub fn description(&self) -> &'static str
+    {
+        match self.codec
+        {
+            | AudioCodec::Aac(_) => "AAC, ADTS",
+            | AudioCodec::Flac(_) => "FLAC",
+            | AudioCodec::Alac(_) => "ALAC, Core Audio Format"
+        }
+    }
+
+    /// Write the stream to `output`, reading the samples from `file`; returns the number of bytes written
+    pub fn write(&self, file: &mut dyn MediaSource, output: &mut dyn Write) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut written = 0u64;
+        let mut emit = |output: &mut dyn Write, data: &[u8]| -> std::io::Result<()> {
+            written += data.len() as u64;
+            output.write_all(data)
+        };
+
+        match self.codec
+        {
+            | AudioCodec::Aac(_) =>
+            {}
+            | AudioCodec::Flac(ref blocks) =>
+            {
+                emit(output, b"fLaC")?;
+                emit(output, blocks)?;
+            }
+            | AudioCodec::Alac(ref cookie) => emit(output, &self.caf_header(cookie))?
+        }
+
+        let file_size = file.size()?;
+        let mut data = Vec::new();
+        for sample in &self.samples
+        {
+            if sample.offset.saturating_add(sample.size as u64) > file_size
+            {
+                return Err(tr_fmt("Sample {} at offset {} ({} bytes) lies beyond the end of the file", &[
+                    &sample.number,
+                    &format!("0x{:08X}", sample.offset),
+                    &sample.size
+                ])
+                .into());
+            }
+            data.resize(sample.size as usize, 0);
+            file.seek(SeekFrom::Start(sample.offset))?;
+            file.read_exact(&mut data)?;
+
+            if let AudioCodec::Aac(ref config) = self.codec
+            {
+                let header = config
+                    .adts_header(data.len())
+                    .ok_or_else(|| tr_fmt("Sample {} of track {} is too large for an ADTS frame ({} bytes)", &[&sample.number, &self.track_id, &data.len()]))?;
+                emit(output, &header)?;
+            }
+            emit(output, &data)?;
+        }
+
+        Ok(written)
+    }
+
+    /// Core Audio Format header, format description, magic cookie and packet table, followed by the header of the audio data chunk
+    fn caf_header(&self, cookie: &[u8]) -> Vec<u8>
+    {
+        let config = |offset: usize| u32::from_be_bytes([cookie[offset], cookie[offset + 1], cookie[offset + 2], cookie[offset + 3]]);
+        let frames_per_packet = config(0);
+        let (bit_depth, channels, sample_rate) = (cookie[5], cookie[9], config(20));
+        // ALAC format flags give the bit depth of the source data
+        let format_flags: u32 = match bit_depth
+        {
+            | 16 => 1,
+            | 20 => 2,
+            | 24 => 3,
+            | 32 => 4,
+            | _ => 0
+        };
+
+        // The durations of the sample table count the frames; the last packet is usually shorter
+        let packets = self.samples.len() as u64;
+        let valid_frames = match self.samples.iter().map(|sample| sample.duration as u64).sum::<u64>()
+        {
+            | 0 => packets * frames_per_packet as u64,
+            | frames => frames
+        };
+        let remainder_frames = (packets * frames_per_packet as u64).saturating_sub(valid_frames);
+        let mut packet_sizes = Vec::new();
+        for sample in &self.samples
+        {
+            write_variable_length(&mut packet_sizes, sample.size);
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(b"caff");
+        header.extend_from_slice(&1u16.to_be_bytes());
+        header.extend_from_slice(&0u16.to_be_bytes());
+
+        header.extend_from_slice(b"desc");
+        header.extend_from_slice(&32i64.to_be_bytes());
+        header.extend_from_slice(&(sample_rate as f64).to_be_bytes());
+        header.extend_from_slice(b"alac");
+        for value in [format_flags, 0, frames_per_packet, channels as u32, 0]
+        {
+            header.extend_from_slice(&value.to_be_bytes());
+        }
+
+        header.extend_from_slice(b"kuki");
+        header.extend_from_slice(&(ALAC_CONFIG_SIZE as i64).to_be_bytes());
+        header.extend_from_slice(&cookie[..ALAC_CONFIG_SIZE]);
+
+        header.extend_from_slice(b"pakt");
+        header.extend_from_slice(&(24 + packet_sizes.len() as i64).to_be_bytes());
+        header.extend_from_slice(&(packets as i64).to_be_bytes());
+        header.extend_from_slice(&(valid_frames as i64).to_be_bytes());
+        header.extend_from_slice(&0i32.to_be_bytes());
+        header.extend_from_slice(&(remainder_frames as i32).to_be_bytes());
+        header.extend_from_slice(&packet_sizes);
+
+        // The data chunk starts with a 32-bit edit count
+        let data_size: u64 = self.samples.iter().map(|sample| sample.size as u64).sum();
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&(4 + data_size as i64).to_be_bytes());
+        header.extend_from_slice(&0u32.to_be_bytes());
+        header
+    }
+}
+
+/// Append `value` as a CAF variable-length integer (7 bits per byte, most significant first, high bit set on all but the last byte)
+fn write_variable_length(output: &mut Vec<u8>, value: u32)
+{
+    let mut groups = vec![(value & 0x7F) as u8];
+    let mut rest = value >> 7;
+    while rest > 0
+    {
+        groups.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+    output.extend(groups.iter().rev());
+}
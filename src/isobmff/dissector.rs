@@ -1,30 +1,73 @@
-use std::{
-    fmt,
-    fs::File,
-    io::{Read, Seek, SeekFrom}
-};
-
-use owo_colors::OwoColorize;
+use std::{cell::RefCell, fmt, io::Write};
 
 use crate::{
-    cli::DissectOptions,
-    isobmff::{r#box::IsobmffBox, content::*, itunes_metadata::ItunesMetadata},
-    media_dissector::MediaDissector
+    artwork::Artwork,
+    chapters::Chapter,
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::ProbeFormat,
+    find::Occurrence,
+    i18n::{tr, tr_fmt},
+    isobmff::{
+        r#box::{IsobmffBox, get_box_description},
+        content::*,
+        demux::AudioStream,
+        fragments::{TrackFragment, segment_media, track_fragments},
+        itunes_metadata::ItunesMetadata,
+        sample_stats::TrackSampleStats,
+        samples::{SampleLocation, locate_samples},
+        tree::{InitSegment, IsobmffTree},
+        validation::{check_mandatory_boxes, check_segment_indexes}
+    },
+    layout::Layout,
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    metadata::MediaMetadata,
+    options::DissectOptions,
+    playlist::SegmentMedia,
+    summary::Summary,
+    visitor::DissectionVisitor
 };
 
+/// Largest leaf box whose data is kept in the tree; larger boxes (media data, huge tables) are skipped or mapped
+const MAX_KEPT_DATA_SIZE: u64 = 1024 * 1024;
+
+/// Box types a QuickTime file without ftyp box, or a media segment without styp box, starts with
+const HEADERLESS_START_TYPES: [&[u8]; 8] = [b"moov", b"mdat", b"wide", b"free", b"skip", b"pnot", b"moof", b"sidx"];
+
+/// Largest file type box accepted after leading junk; real ones list a few dozen brands at most
+const MAX_FILE_TYPE_SIZE: u32 = 4096;
+
+/// Bytes of leading junk searched for a file type box; files embedded further into other data are left to `carve`
+const MAX_LEADING_JUNK: usize = 4096;
+
+/// Source of hexdumps: set for `--dump`, boxes whose data is not kept are read from it while printing
+pub type DumpSource<'a> = RefCell<&'a mut dyn MediaSource>;
+
+/// Hexdump settings of `--dump`: where to read data that is not kept, how much of it and of which boxes
+#[derive(Clone, Copy)]
+pub struct DumpSettings<'a, 'b>
+{
+    pub source:  &'a DumpSource<'b>,
+    pub options: &'a DissectOptions
+}
+
 /// Wrapper for displaying box with verbose option
-pub struct VerboseBoxDisplay<'a>
+pub struct VerboseBoxDisplay<'a, 'b>
 {
     pub box_ref:   &'a IsobmffBox,
     pub verbose:   bool,
-    pub show_dump: bool
+    pub dump:      Option<DumpSettings<'a, 'b>>,
+    pub show_spec: bool
 }
 
-impl<'a> fmt::Display for VerboseBoxDisplay<'a>
+impl<'a, 'b> fmt::Display for VerboseBoxDisplay<'a, 'b>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        self.box_ref.fmt_with_indent_and_options(f, 0, self.verbose, self.show_dump)
+        self.box_ref.fmt_with_indent_and_options(f, 0, &[], self.verbose, self.dump, self.show_spec)
     }
 }
 
@@ -40,10 +83,13 @@ impl IsobmffBox
 {
     fn fmt_with_indent(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result
     {
-        self.fmt_with_indent_and_options(f, indent, false, false)
+        self.fmt_with_indent_and_options(f, indent, &[], false, None, false)
     }
 
-    fn fmt_with_indent_and_options(&self, f: &mut fmt::Formatter<'_>, indent: usize, verbose: bool, show_dump: bool) -> fmt::Result
+    /// Display the box and its children; `parent_path` holds the types of the enclosing boxes for `--dump-path`
+    fn fmt_with_indent_and_options(
+        &self, f: &mut fmt::Formatter<'_>, indent: usize, parent_path: &[&str], verbose: bool, dump: Option<DumpSettings>, show_spec: bool
+    ) -> fmt::Result
     {
         // Skip certain technical boxes unless verbose mode is enabled
         if verbose == false && matches!(self.box_type.as_str(), "mdat" | "free" | "stts" | "stsc" | "stsz" | "stco" | "co64" | "ctts")
@@ -54,20 +100,37 @@ fn fmt_with_indent_and_options(&self, f: &mut fmt::Formatter<'_>, indent: usize,
         let indent_str = "    ".repeat(indent);
 
         // Format box display string
-        let box_info = format!("'{}' ({})", self.box_type, self.get_description());
+        let box_info = match self.key_name
+        {
+            | Some(ref key_name) => format!("'{}' ({})", key_name, tr_fmt("Metadata Key {}", &[&self.box_type])),
+            | None => format!("'{}' ({})", self.box_type, tr(self.get_description()))
+        };
+        let offset_str = format!("0x{:08X}", self.offset);
 
         // Color code based on box type
         if self.is_container == true
         {
-            writeln!(f, "{}Box at offset 0x{:08X}: {} - Size: {} bytes", indent_str, self.offset, box_info.cyan(), self.size)?;
+            writeln!(f, "{}{}", indent_str, tr_fmt("Box at offset {}: {} - Size: {} bytes", &[&offset_str, &box_info.cyan(), &self.size]))?;
         }
         else if matches!(self.box_type.as_str(), "ftyp" | "mdat")
         {
-            writeln!(f, "{}Box at offset 0x{:08X}: {} - Size: {} bytes", indent_str, self.offset, box_info.yellow(), self.size)?;
+            writeln!(f, "{}{}", indent_str, tr_fmt("Box at offset {}: {} - Size: {} bytes", &[&offset_str, &box_info.yellow(), &self.size]))?;
         }
         else
         {
-            writeln!(f, "{}Box at offset 0x{:08X}: {} - Size: {} bytes", indent_str, self.offset, box_info, self.size)?;
+            writeln!(f, "{}{}", indent_str, tr_fmt("Box at offset {}: {} - Size: {} bytes", &[&offset_str, &box_info, &self.size]))?;
+        }
+
+        if self.missing_bytes > 0
+        {
+            let truncated = tr_fmt("Truncated: declared size {} bytes, {} bytes missing", &[&self.declared_size(), &self.missing_bytes]);
+            writeln!(f, "{}    {}", indent_str, truncated.red())?;
+        }
+
+        if show_spec == true &&
+            let Some(reference) = self.spec_reference()
+        {
+            writeln!(f, "{}    {}", indent_str, tr_fmt("Specification: {}", &[&reference]))?;
         }
 
         // Display parsed content for iTunes metadata boxes
@@ -91,32 +154,41 @@ fn fmt_with_indent_and_options(&self, f: &mut fmt::Formatter<'_>, indent: usize,
         }
 
         // Show hexdump if requested and box has data
-        if show_dump && !self.data.is_empty()
+        let path = [parent_path, &[self.box_type.as_str()]].concat();
+        let dump_limit = dump.and_then(|settings| settings.options.dump_limit.bytes());
+        let dumped = dump.filter(|settings| settings.options.dumps(&path) == true);
+        if dumped.is_some() && !self.data.is_empty()
         {
-            writeln!(f, "{}    Raw data:", indent_str)?;
-            // Limit hexdump for covr boxes (cover art) and large data boxes (likely images) to 128 bytes
-            // Data boxes > 1KB are likely image data inside covr/artwork containers
-            let hexdump = if self.box_type == "covr" || (self.box_type == "data" && self.data.len() > 1024)
-            {
-                crate::hexdump::format_hexdump_limited(&self.data, 0, Some(128))
-            }
-            else
-            {
-                crate::hexdump::format_hexdump(&self.data, 0)
-            };
-            for line in hexdump.lines()
+            writeln!(f, "{}    {}", indent_str, tr("Raw data:"))?;
+            for line in crate::hexdump::format_hexdump_annotated(&self.data, 0, dump_limit, &self.field_spans()).lines()
             {
                 writeln!(f, "{}    {}", indent_str, line)?;
             }
             writeln!(f)?;
         }
+        else if let Some(settings) = dumped &&
+            self.is_container == false &&
+            self.data_size() > 0
+        {
+            // Large boxes (e.g. mdat) are not kept in the tree, only their start is read for the dump
+            let length = self.data_size().min(dump_limit.map_or(u64::MAX, |limit| limit as u64));
+            if let Ok(window) = settings.source.borrow_mut().map(self.offset + self.header_size, length)
+            {
+                writeln!(f, "{}    {}", indent_str, tr_fmt("Raw data (first {} of {} bytes):", &[&length, &self.data_size()]))?;
+                for line in crate::hexdump::format_hexdump(&window, 0).lines()
+                {
+                    writeln!(f, "{}    {}", indent_str, line)?;
+                }
+                writeln!(f)?;
+            }
+        }
 
         // Display children for container boxes
         if self.is_container == true && self.children.is_empty() == false
         {
             for child in &self.children
             {
-                child.fmt_with_indent_and_options(f, indent + 1, verbose, show_dump)?;
+                child.fmt_with_indent_and_options(f, indent + 1, &path, verbose, dump, show_spec)?;
             }
         }
 
@@ -125,6 +197,7 @@ fn fmt_with_indent_and_options(&self, f: &mut fmt::Formatter<'_>, indent: usize,
 }
 
 /// ISOBMFF (ISO Base Media File Format) dissector - unit struct
+#[derive(Default)]
 pub struct IsobmffDissector;
 
 impl IsobmffDissector
@@ -201,8 +274,42 @@ fn is_itunes_metadata_box(box_type: &str) -> bool
             )
     }
 
-    /// Parse boxes from file
-    fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize) -> Result<Vec<IsobmffBox>, String>
+    /// Set the key names of QuickTime metadata items from the keys box next to their ilst
+    fn resolve_metadata_keys(meta_children: &mut [IsobmffBox])
+    {
+        let keys = meta_children.iter().find_map(|child| match child.content
+        {
+            | Some(IsobmffContent::MetadataKeys(ref keys)) => Some(keys.clone()),
+            | _ => None
+        });
+
+        if let Some(keys) = keys &&
+            let Some(ilst) = meta_children.iter_mut().find(|child| child.box_type == "ilst")
+        {
+            for item in ilst.children.iter_mut()
+            {
+                item.key_name = item.key_index().and_then(|index| keys.key_name(index)).map(str::to_string);
+            }
+        }
+    }
+
+    /// Parse boxes from file (sequentially, so seeks only happen when skipping unread box data)
+    ///
+    /// Sample tables (stts, stsc, stsz, stco, co64) keep at most `max_table_entries` entries.
+    pub fn parse_boxes(reader: &mut MediaReader, start_offset: u64, end_offset: u64, depth: usize, max_table_entries: usize) -> Result<Vec<IsobmffBox>, String>
+    {
+        Self::parse_boxes_recovering(reader, start_offset, end_offset, depth, max_table_entries, false, &mut Vec::new())
+    }
+
+    /// Parse boxes like `parse_boxes`, adding problems that did not abort parsing to `findings` with their offset
+    ///
+    /// Boxes running past their parent or the end of the file are kept with the bytes that are
+    /// present (see `IsobmffBox::missing_bytes`). With `recover` set, a corrupt box header does
+    /// not abort parsing either: parsing resumes at the next plausible box header (a known box
+    /// type whose size fits into the parent).
+    pub fn parse_boxes_recovering(
+        reader: &mut MediaReader, start_offset: u64, end_offset: u64, depth: usize, max_table_entries: usize, recover: bool, findings: &mut Vec<(u64, String)>
+    ) -> Result<Vec<IsobmffBox>, String>
     {
         let mut boxes = Vec::new();
         let mut current_offset = start_offset;
@@ -210,26 +317,60 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
         // Prevent excessive recursion
         if depth > 20
         {
-            return Err("Maximum box nesting depth exceeded".to_string());
+            return Err(tr("Maximum box nesting depth exceeded").to_string());
         }
 
         while current_offset < end_offset
         {
-            file.seek(SeekFrom::Start(current_offset)).map_err(|e| format!("Seek error at offset 0x{:08X}: {}", current_offset, e))?;
+            reader.seek_to(current_offset).map_err(|e| tr_fmt("Seek error at offset {}: {}", &[&format!("0x{:08X}", current_offset), &e]))?;
 
             // Read box header (minimum 8 bytes: 4 for size, 4 for type)
             let mut header = [0u8; 8];
-            file.read_exact(&mut header).map_err(|e| format!("Failed to read box header at 0x{:08X}: {}", current_offset, e))?;
+            if current_offset + 8 > reader.size()
+            {
+                findings.push((
+                    current_offset,
+                    tr_fmt("Incomplete box header at {}: the file ends after {} bytes", &[&format!("0x{:08X}", current_offset), &(reader.size() - current_offset)])
+                ));
+                break;
+            }
+            if let Err(e) = reader.read_exact(&mut header)
+            {
+                let error = tr_fmt("Failed to read box header at {}: {}", &[&format!("0x{:08X}", current_offset), &e]);
+                if recover == false
+                {
+                    return Err(error);
+                }
+                findings.push((current_offset, tr_fmt("{}; {} trailing bytes ignored", &[&error, &(end_offset - current_offset)])));
+                break;
+            }
 
             // Parse size and type
             let size_32 = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
-            let box_type = Self::box_type_to_string(&header[4..8]);
+            // QuickTime metadata items are typed by their 1-based index into the keys box
+            let box_type = if header[4] == 0
+            {
+                u32::from_be_bytes([header[4], header[5], header[6], header[7]]).to_string()
+            }
+            else
+            {
+                Self::box_type_to_string(&header[4..8])
+            };
 
-            let (box_size, header_size) = if size_32 == 1
+            let (mut box_size, header_size) = if size_32 == 1
             {
                 // Extended size (64-bit)
                 let mut extended_size = [0u8; 8];
-                file.read_exact(&mut extended_size).map_err(|e| format!("Failed to read extended size: {}", e))?;
+                if let Err(e) = reader.read_exact(&mut extended_size)
+                {
+                    let error = tr_fmt("Failed to read extended size: {}", &[&e]);
+                    if recover == false
+                    {
+                        return Err(error);
+                    }
+                    findings.push((current_offset, tr_fmt("{}; {} trailing bytes ignored", &[&error, &(end_offset - current_offset)])));
+                    break;
+                }
                 let size_64 = u64::from_be_bytes(extended_size);
                 (size_64, 16u64)
             }
@@ -243,18 +384,76 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
                 (size_32 as u64, 8u64)
             };
 
+            // Data appended after the last top-level box is not a box: a top-level box needs a printable type,
+            // and a size that fits into the file unless it is a known box that was cut off
+            if depth == 0 && Self::is_top_level_box(&header[4..8], box_size, end_offset - current_offset) == false
+            {
+                findings.push((current_offset, tr_fmt("{} bytes of unrecognized data after the last box", &[&(end_offset - current_offset)])));
+                break;
+            }
+
             // Validate box size
             if box_size < header_size
             {
-                return Err(format!("Invalid box size {} at offset 0x{:08X} (smaller than header)", box_size, current_offset));
+                let error = tr_fmt("Invalid box size {} at offset {} (smaller than header)", &[&box_size, &format!("0x{:08X}", current_offset)]);
+                if recover == false
+                {
+                    return Err(error);
+                }
+
+                // Resynchronize on the next plausible box header
+                match Self::find_next_box(reader, current_offset + 1, end_offset).map_err(|e| tr_fmt("Read error while resynchronizing: {}", &[&e]))?
+                {
+                    | Some(next_offset) =>
+                    {
+                        findings.push((current_offset, tr_fmt("{}; skipped {} bytes to the next box", &[&error, &(next_offset - current_offset)])));
+                        current_offset = next_offset;
+                        continue;
+                    }
+                    | None =>
+                    {
+                        findings.push((current_offset, tr_fmt("{}; no further box in the remaining {} bytes", &[&error, &(end_offset - current_offset)])));
+                        break;
+                    }
+                }
             }
 
+            // A box whose header does not fit into its parent has no content range to keep
+            if current_offset + header_size > end_offset
+            {
+                findings.push((
+                    current_offset,
+                    tr_fmt("Incomplete box header at {}: the parent box ends after {} bytes", &[&format!("0x{:08X}", current_offset), &(end_offset - current_offset)])
+                ));
+                break;
+            }
+
+            // A box running past its parent keeps the bytes that are present; with --recover a box
+            // that does not reach the end of the file is more likely corrupt and skipped instead
+            let mut missing_bytes = 0;
             if current_offset + box_size > end_offset
             {
-                return Err(format!("Box at offset 0x{:08X} extends beyond parent (size: {}, available: {})", current_offset, box_size, end_offset - current_offset));
+                if recover == true && current_offset + box_size <= reader.size()
+                {
+                    let error = tr_fmt("Box at offset {} extends beyond parent (size: {}, available: {})", &[
+                        &format!("0x{:08X}", current_offset),
+                        &box_size,
+                        &(end_offset - current_offset)
+                    ]);
+                    if let Some(next_offset) =
+                        Self::find_next_box(reader, current_offset + 1, end_offset).map_err(|e| tr_fmt("Read error while resynchronizing: {}", &[&e]))?
+                    {
+                        findings.push((current_offset, tr_fmt("{}; skipped {} bytes to the next box", &[&error, &(next_offset - current_offset)])));
+                        current_offset = next_offset;
+                        continue;
+                    }
+                }
+                missing_bytes = current_offset + box_size - end_offset;
+                box_size = end_offset - current_offset;
             }
 
             let mut isobmff_box = IsobmffBox::new(current_offset, box_type.clone(), box_size, header_size);
+            isobmff_box.missing_bytes = missing_bytes;
 
             // Parse container contents or read data
             if isobmff_box.is_container == true
@@ -265,16 +464,38 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
                 // Special handling for FullBox containers - they have version/flags (4 bytes) before children
                 // meta: just version/flags
                 // dref: version/flags + entry_count (8 bytes total)
-                if isobmff_box.box_type == "meta" && content_end - content_start >= 4
+                // iinf: version/flags + entry_count (16-bit in version 0, 32-bit otherwise)
+                if isobmff_box.box_type == "meta" && content_end.saturating_sub(content_start) >= 4
                 {
-                    content_start += 4; // Skip version (1 byte) + flags (3 bytes)
+                    // QuickTime meta boxes have no version/flags and start directly with their hdlr box
+                    let mut peek = [0u8; 8];
+                    reader.seek_to(content_start).map_err(|e| tr_fmt("Seek error: {}", &[&e]))?;
+                    let is_quicktime = content_end.saturating_sub(content_start) >= 8 && reader.read_exact(&mut peek).is_ok() && &peek[4..8] == b"hdlr";
+                    if is_quicktime == false
+                    {
+                        content_start += 4; // Skip version (1 byte) + flags (3 bytes)
+                    }
                 }
-                else if isobmff_box.box_type == "dref" && content_end - content_start >= 8
+                else if isobmff_box.box_type == "dref" && content_end.saturating_sub(content_start) >= 8
                 {
                     content_start += 8; // Skip version/flags (4 bytes) + entry_count (4 bytes)
                 }
+                else if isobmff_box.box_type == "iinf" && content_end.saturating_sub(content_start) >= 6
+                {
+                    reader.seek_to(content_start).map_err(|e| tr_fmt("Seek error: {}", &[&e]))?;
+                    let mut version = [0u8; 1];
+                    reader.read_exact(&mut version).map_err(|e| tr_fmt("Failed to read iinf version: {}", &[&e]))?;
+                    content_start += if version[0] == 0
+                    {
+                        6
+                    }
+                    else
+                    {
+                        8
+                    };
+                }
 
-                isobmff_box.children = Self::parse_boxes(file, content_start, content_end, depth + 1)?;
+                isobmff_box.children = Self::parse_boxes_recovering(reader, content_start, content_end, depth + 1, max_table_entries, recover, findings)?;
 
                 // Parse iTunes metadata if this is a metadata box with a 'data' child
                 if Self::is_itunes_metadata_box(&box_type)
@@ -291,6 +512,35 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
                         }
                     }
                 }
+
+                // QuickTime metadata items hold a 'data' box like iTunes items but are not known containers
+                if box_type == "ilst"
+                {
+                    for item in isobmff_box.children.iter_mut().filter(|child| child.key_index().is_some())
+                    {
+                        item.is_container = true;
+                        item.data.clear();
+                        item.children = Self::parse_boxes_recovering(
+                            reader,
+                            item.offset + item.header_size,
+                            item.offset + item.size,
+                            depth + 2,
+                            max_table_entries,
+                            recover,
+                            findings
+                        )?;
+                        if let Some(data_box) = item.children.iter().find(|child| child.box_type == "data")
+                        {
+                            item.itunes_content = ItunesMetadata::parse(&item.box_type, &data_box.data).ok();
+                        }
+                    }
+                }
+
+                // Resolve QuickTime metadata item indices to the key names of the keys box
+                if box_type == "meta"
+                {
+                    Self::resolve_metadata_keys(&mut isobmff_box.children);
+                }
             }
             else
             {
@@ -298,41 +548,23 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
                 let data_size = isobmff_box.data_size();
 
                 // Only read data for smaller boxes (skip large media data)
-                if data_size > 0 && data_size <= 1024 * 1024
+                if data_size > 0 && data_size <= MAX_KEPT_DATA_SIZE
                 {
-                    file.seek(SeekFrom::Start(current_offset + header_size)).map_err(|e| format!("Seek error: {}", e))?;
+                    reader.seek_to(current_offset + header_size).map_err(|e| tr_fmt("Seek error: {}", &[&e]))?;
 
                     let mut data = vec![0u8; data_size as usize];
-                    file.read_exact(&mut data).map_err(|e| format!("Failed to read box data: {}", e))?;
+                    reader.read_exact(&mut data).map_err(|e| tr_fmt("Failed to read box data: {}", &[&e]))?;
 
                     isobmff_box.data = data;
 
                     // Parse content for standard ISOBMFF boxes
-                    isobmff_box.content = match box_type.as_str()
-                    {
-                        | "ftyp" => FileTypeBox::parse(&isobmff_box.data).ok().map(IsobmffContent::FileType),
-                        | "mvhd" => MovieHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::MovieHeader),
-                        | "tkhd" => TrackHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::TrackHeader),
-                        | "mdhd" => MediaHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::MediaHeader),
-                        | "hdlr" => HandlerBox::parse(&isobmff_box.data).ok().map(IsobmffContent::Handler),
-                        | "vmhd" => VideoMediaHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::VideoMediaHeader),
-                        | "smhd" => SoundMediaHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::SoundMediaHeader),
-                        | "nmhd" => NullMediaHeaderBox::parse(&isobmff_box.data).ok().map(IsobmffContent::NullMediaHeader),
-                        | "dref" => DataReferenceBox::parse(&isobmff_box.data).ok().map(IsobmffContent::DataReference),
-                        | "stsd" => SampleDescriptionBox::parse(&isobmff_box.data).ok().map(IsobmffContent::SampleDescription),
-                        | "stts" => TimeToSampleBox::parse(&isobmff_box.data).ok().map(IsobmffContent::TimeToSample),
-                        | "stsc" => SampleToChunkBox::parse(&isobmff_box.data).ok().map(IsobmffContent::SampleToChunk),
-                        | "stsz" => SampleSizeBox::parse(&isobmff_box.data).ok().map(IsobmffContent::SampleSize),
-                        | "stco" => ChunkOffsetBox::parse(&isobmff_box.data).ok().map(IsobmffContent::ChunkOffset),
-                        | "co64" => ChunkOffset64Box::parse(&isobmff_box.data).ok().map(IsobmffContent::ChunkOffset64),
-                        | "elst" => EditListBox::parse(&isobmff_box.data).ok().map(IsobmffContent::EditList),
-                        | "url " => UrlEntryBox::parse(&isobmff_box.data).ok().map(IsobmffContent::UrlEntry),
-                        | "urn " => UrnEntryBox::parse(&isobmff_box.data).ok().map(IsobmffContent::UrnEntry),
-                        | "chap" => ChapterBox::parse(&isobmff_box.data).ok().map(IsobmffContent::Chapter),
-                        | "mean" => MetadataMeanBox::parse(&isobmff_box.data).ok().map(IsobmffContent::MetadataMean),
-                        | "name" => MetadataNameBox::parse(&isobmff_box.data).ok().map(IsobmffContent::MetadataName),
-                        | _ => None
-                    };
+                    isobmff_box.content = Self::parse_content(&box_type, &isobmff_box.data, max_table_entries);
+                }
+                else if data_size > MAX_KEPT_DATA_SIZE && matches!(box_type.as_str(), "stts" | "stsc" | "stsz" | "stco" | "co64")
+                {
+                    // Sample tables of long recordings are parsed from a mapped window without keeping their data
+                    let window = reader.map(current_offset + header_size, data_size).map_err(|e| tr_fmt("Failed to map box data: {}", &[&e]))?;
+                    isobmff_box.content = Self::parse_content(&box_type, &window, max_table_entries);
                 }
             }
 
@@ -342,6 +574,127 @@ fn parse_boxes(file: &mut File, start_offset: u64, end_offset: u64, depth: usize
 
         Ok(boxes)
     }
+
+    /// Parse the content of a leaf box from its data, None for unknown box types or malformed data
+    fn parse_content(box_type: &str, data: &[u8], max_table_entries: usize) -> Option<IsobmffContent>
+    {
+        match box_type
+        {
+            | "ftyp" => FileTypeBox::parse(data).ok().map(IsobmffContent::FileType),
+            | "mvhd" => MovieHeaderBox::parse(data).ok().map(IsobmffContent::MovieHeader),
+            | "tkhd" => TrackHeaderBox::parse(data).ok().map(IsobmffContent::TrackHeader),
+            | "mdhd" => MediaHeaderBox::parse(data).ok().map(IsobmffContent::MediaHeader),
+            | "hdlr" => HandlerBox::parse(data).ok().map(IsobmffContent::Handler),
+            | "vmhd" => VideoMediaHeaderBox::parse(data).ok().map(IsobmffContent::VideoMediaHeader),
+            | "smhd" => SoundMediaHeaderBox::parse(data).ok().map(IsobmffContent::SoundMediaHeader),
+            | "nmhd" => NullMediaHeaderBox::parse(data).ok().map(IsobmffContent::NullMediaHeader),
+            | "dref" => DataReferenceBox::parse(data).ok().map(IsobmffContent::DataReference),
+            | "stsd" => SampleDescriptionBox::parse(data).ok().map(IsobmffContent::SampleDescription),
+            | "stts" => TimeToSampleBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::TimeToSample),
+            | "stsc" => SampleToChunkBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::SampleToChunk),
+            | "stsz" => SampleSizeBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::SampleSize),
+            | "stco" => ChunkOffsetBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::ChunkOffset),
+            | "co64" => ChunkOffset64Box::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::ChunkOffset64),
+            | "ctts" => CompositionOffsetBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::CompositionOffset),
+            | "stss" => SyncSampleBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::SyncSample),
+            | "sdtp" => SampleDependencyBox::parse_with_limit(data, max_table_entries).ok().map(IsobmffContent::SampleDependency),
+            | "sgpd" => SampleGroupDescriptionBox::parse(data).ok().map(IsobmffContent::SampleGroupDescription),
+            | "sbgp" => SampleToGroupBox::parse(data).ok().map(IsobmffContent::SampleToGroup),
+            | "pssh" => ProtectionSystemHeaderBox::parse(data).ok().map(IsobmffContent::ProtectionSystemHeader),
+            | "tenc" => TrackEncryptionBox::parse(data).ok().map(IsobmffContent::TrackEncryption),
+            | "senc" => SampleEncryptionBox::parse(data).ok().map(IsobmffContent::SampleEncryption),
+            | "saiz" => SampleAuxiliaryInfoSizesBox::parse(data).ok().map(IsobmffContent::SampleAuxiliaryInfoSizes),
+            | "saio" => SampleAuxiliaryInfoOffsetsBox::parse(data).ok().map(IsobmffContent::SampleAuxiliaryInfoOffsets),
+            | "schm" => SchemeTypeBox::parse(data).ok().map(IsobmffContent::SchemeType),
+            | "frma" => OriginalFormatBox::parse(data).ok().map(IsobmffContent::OriginalFormat),
+            | "elst" => EditListBox::parse(data).ok().map(IsobmffContent::EditList),
+            | "url " => UrlEntryBox::parse(data).ok().map(IsobmffContent::UrlEntry),
+            | "urn " => UrnEntryBox::parse(data).ok().map(IsobmffContent::UrnEntry),
+            | "chap" => ChapterBox::parse(data).ok().map(IsobmffContent::Chapter),
+            | "mean" => MetadataMeanBox::parse(data).ok().map(IsobmffContent::MetadataMean),
+            | "name" => MetadataNameBox::parse(data).ok().map(IsobmffContent::MetadataName),
+            | "keys" => MetadataKeysBox::parse(data).ok().map(IsobmffContent::MetadataKeys),
+            | "mfhd" => MovieFragmentHeaderBox::parse(data).ok().map(IsobmffContent::MovieFragmentHeader),
+            | "trex" => TrackExtendsBox::parse(data).ok().map(IsobmffContent::TrackExtends),
+            | "tfhd" => TrackFragmentHeaderBox::parse(data).ok().map(IsobmffContent::TrackFragmentHeader),
+            | "tfdt" => TrackFragmentDecodeTimeBox::parse(data).ok().map(IsobmffContent::TrackFragmentDecodeTime),
+            | "trun" => TrackRunBox::parse(data).ok().map(IsobmffContent::TrackRun),
+            | "sidx" => SegmentIndexBox::parse(data).ok().map(IsobmffContent::SegmentIndex),
+            | "emsg" => EventMessageBox::parse(data).ok().map(IsobmffContent::EventMessage),
+            | "prft" => ProducerReferenceTimeBox::parse(data).ok().map(IsobmffContent::ProducerReferenceTime),
+            | "iloc" => ItemLocationBox::parse(data).ok().map(IsobmffContent::ItemLocation),
+            | "infe" => ItemInfoEntryBox::parse(data).ok().map(IsobmffContent::ItemInfoEntry),
+            | "pitm" => PrimaryItemBox::parse(data).ok().map(IsobmffContent::PrimaryItem),
+            | "iref" => ItemReferenceBox::parse(data).ok().map(IsobmffContent::ItemReference),
+            | "ipma" => ItemPropertyAssociationBox::parse(data).ok().map(IsobmffContent::ItemPropertyAssociation),
+            | "ispe" => ImageSpatialExtentsBox::parse(data).ok().map(IsobmffContent::ImageSpatialExtents),
+            | "pixi" => PixelInformationBox::parse(data).ok().map(IsobmffContent::PixelInformation),
+            // Codec and colour properties of image items (inside ipco)
+            | "avcC" => AvcConfigurationBox::parse(data).ok().map(IsobmffContent::AvcConfiguration),
+            | "hvcC" => HevcConfigurationBox::parse(data).ok().map(IsobmffContent::HevcConfiguration),
+            | "av1C" => Av1ConfigurationBox::parse(data).ok().map(IsobmffContent::Av1Configuration),
+            | "colr" => ColourInformationBox::parse(data).ok().map(IsobmffContent::ColourInformation),
+            | "pasp" => PixelAspectRatioBox::parse(data).ok().map(IsobmffContent::PixelAspectRatio),
+            | "uuid" => UuidBox::parse(data).ok().map(IsobmffContent::UserExtension),
+            | _ => None
+        }
+    }
+
+    /// Whether a header with type `type_bytes` and size `box_size` starts a top-level box, with `available` bytes left in the file
+    fn is_top_level_box(type_bytes: &[u8], box_size: u64, available: u64) -> bool
+    {
+        let printable = type_bytes.iter().all(|&b| b.is_ascii_graphic() == true || b == b' ');
+        let known = get_box_description(&Self::box_type_to_string(type_bytes)) != "Unknown Box Type";
+
+        printable == true && (box_size <= available || known == true)
+    }
+
+    /// Scan forward from `start_offset` for the next plausible box header: a known box type whose size fits before `end_offset`
+    fn find_next_box(reader: &mut MediaReader, start_offset: u64, end_offset: u64) -> std::io::Result<Option<u64>>
+    {
+        const WINDOW: u64 = 64 * 1024;
+
+        let mut window_start = start_offset;
+        while window_start + 8 <= end_offset
+        {
+            // Windows overlap by a header so boxes starting at the end of a window are still found
+            let mut data = vec![0u8; (end_offset - window_start).min(WINDOW + 7) as usize];
+            reader.seek_to(window_start)?;
+            reader.read_exact(&mut data)?;
+
+            for (index, header) in data.windows(8).enumerate()
+            {
+                let offset = window_start + index as u64;
+                let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+                if size >= 8 && offset + size <= end_offset && get_box_description(&Self::box_type_to_string(&header[4..8])) != "Unknown Box Type"
+                {
+                    return Ok(Some(offset));
+                }
+            }
+            window_start += WINDOW;
+        }
+
+        Ok(None)
+    }
+}
+
+/// Mark boxes as covered; the payload of free space boxes only counts as far as it is zero-filled
+fn add_box_coverage(boxes: &[IsobmffBox], coverage: &mut Coverage)
+{
+    for isobmff_box in boxes
+    {
+        let is_free_space = matches!(isobmff_box.box_type.as_str(), "free" | "skip" | "wide");
+        if is_free_space == true && isobmff_box.data.len() as u64 == isobmff_box.data_size()
+        {
+            let data_offset = isobmff_box.offset + isobmff_box.header_size;
+            coverage.add(isobmff_box.offset, data_offset);
+            coverage.add_padding(data_offset, &isobmff_box.data);
+        }
+        else
+        {
+            coverage.add(isobmff_box.offset, isobmff_box.offset + isobmff_box.size);
+        }
+    }
 }
 
 impl MediaDissector for IsobmffDissector
@@ -356,67 +709,291 @@ fn name(&self) -> &'static str
         "ISO Base Media File Format Dissector"
     }
 
-    fn dissect_with_options(&self, file: &mut File, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
     {
-        let file_size = file.metadata()?.len();
-
-        // Parse all boxes
-        let boxes = Self::parse_boxes(file, 0, file_size, 0).map_err(|e| format!("Failed to parse ISOBMFF boxes: {}", e))?;
+        let tree = IsobmffTree::read_with_options(file, start_offset, options, diagnostics)?;
+        let boxes = &tree.boxes;
+        let filtered = options.filter.as_ref().map(|filter| tree.filtered(filter));
+        // Cover art is not kept in the tree, the pictures are read from the file
+        let artwork = options.inspect_art.then(|| tree.artwork(file)).transpose()?;
+        // Chapter titles are samples in mdat, the `chap` box itself only lists track IDs
+        let chapter_track = (options.show_data == true && filtered.is_none() == true).then(|| tree.chapter_track(file)).transpose()?.flatten();
 
         // Header information
         if options.show_header == true
         {
-            println!("\n{}", "ISO Base Media File Format Header:".bright_cyan().bold());
+            writeln!(output, "\n{}", tr("ISO Base Media File Format Header:").bright_cyan().bold())?;
 
             // Show ftyp box if present
             if let Some(ftyp) = boxes.first() &&
                 ftyp.box_type == "ftyp"
             {
-                println!("{}", ftyp);
+                writeln!(output, "{}", ftyp)?;
             }
 
-            println!();
+            writeln!(output)?;
         }
 
         // Boxes/structure information
         if options.show_data == true
         {
-            println!("{}\n", "Box Structure:".bright_cyan().bold());
+            writeln!(output, "{}\n", tr("Box Structure:").bright_cyan().bold())?;
 
-            for isobmff_box in &boxes
+            let source = RefCell::new(&mut *file);
+            for isobmff_box in &filtered.as_ref().unwrap_or(&tree).boxes
             {
-                print!("{}", VerboseBoxDisplay { box_ref: isobmff_box, verbose: options.show_verbose, show_dump: options.show_dump });
+                write!(output, "{}", VerboseBoxDisplay {
+                    box_ref:   isobmff_box,
+                    verbose:   options.show_verbose,
+                    dump:      options.show_dump.then_some(DumpSettings { source: &source, options }),
+                    show_spec: options.show_spec
+                })?;
             }
         }
 
-        Ok(())
+        if let Some(chapter_track) = chapter_track
+        {
+            writeln!(output, "\n{}", tr("Chapter Track:").bright_cyan().bold())?;
+            write!(output, "{}", chapter_track)?;
+        }
+
+        if let Some(artwork) = artwork
+        {
+            writeln!(output, "\n{}", tr("Cover Art:").bright_cyan().bold())?;
+            for artwork in artwork
+            {
+                writeln!(output, "  {}", tr_fmt("{} ({} bytes)", &[&artwork.location, &artwork.data.len()]).cyan())?;
+                for line in crate::image::inspect(&artwork.data)
+                {
+                    writeln!(output, "    {}", line)?;
+                }
+            }
+        }
+
+        if options.show_stats == true
+        {
+            writeln!(output, "\n{}", tr("Sample Table Statistics:").bright_cyan().bold())?;
+            for stats in tree.sample_stats()
+            {
+                write!(output, "{}", stats)?;
+            }
+        }
+
+        Ok(tree.end_offset)
     }
 
-    fn can_handle(&self, header: &[u8]) -> bool
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
     {
-        // Need at least 12 bytes to check for ftyp box
-        if header.len() < 12
+        let tree = IsobmffTree::read_with_options(file, start_offset, options, diagnostics)?;
+
+        let mut content = match options.filter
         {
-            return false;
+            | Some(ref filter) => tree.filtered(filter).to_json(options.show_details),
+            | None => tree.to_json(options.show_details)
+        };
+        if options.show_stats == true
+        {
+            content["sample_stats"] = tree.sample_stats().iter().map(TrackSampleStats::to_json).collect();
         }
+        if options.filter.is_none() == true &&
+            let Some(chapter_track) = tree.chapter_track(file)?
+        {
+            content["chapter_track"] = chapter_track.to_json();
+        }
+        Ok((content, tree.end_offset))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, diagnostics)?;
 
-        // Check for 'ftyp' box at start of file
-        let box_type = String::from_utf8_lossy(&header[4..8]);
+        Ok((Some(tree.probe()), tree.end_offset))
+    }
 
-        if box_type == "ftyp"
-        {
-            // Additional validation: check major brand
-            let major_brand = String::from_utf8_lossy(&header[8..12]);
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        add_box_coverage(&tree.boxes, coverage);
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_artwork(&self, file: &mut dyn MediaSource, start_offset: u64, artwork: &mut Vec<Artwork>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        artwork.extend(tree.artwork(file)?);
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_chapters(&self, file: &mut dyn MediaSource, start_offset: u64, chapters: &mut Vec<Chapter>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        chapters.extend(tree.chapters(file)?);
+
+        Ok(tree.end_offset)
+    }
 
-            // Common ISOBMFF brands
-            let valid_brands = [
-                "isom", "iso2", "iso3", "iso4", "iso5", "iso6", "mp41", "mp42", "mp71", "M4A ", "M4V ", "M4P ", "M4B ", "qt  ", "mqt ", "3gp4", "3gp5", "3gp6",
-                "3gp7", "3gp8", "3gp9", "3g2a", "3g2b", "3g2c", "mmp4", "avc1", "iso5", "MSNV", "dash", "msdh", "msix"
-            ];
+    fn collect_samples(
+        &self, file: &mut dyn MediaSource, start_offset: u64, track_id: u32, samples: &mut Vec<SampleLocation>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        samples.extend(locate_samples(&tree, track_id));
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_audio_streams(
+        &self, file: &mut dyn MediaSource, start_offset: u64, track_id: u32, streams: &mut Vec<AudioStream>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        streams.extend(AudioStream::from_tree(&tree, track_id)?);
+
+        Ok(tree.end_offset)
+    }
 
-            return valid_brands.iter().any(|&b| major_brand == b);
+    fn collect_fragments(&self, file: &mut dyn MediaSource, start_offset: u64, fragments: &mut Vec<TrackFragment>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        fragments.extend(track_fragments(&tree));
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_segment_media(
+        &self, file: &mut dyn MediaSource, start_offset: u64, init_segment: Option<&InitSegment>, media: &mut Vec<SegmentMedia>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let options = DissectOptions { init_segment: init_segment.cloned(), ..DissectOptions::default() };
+        let tree = IsobmffTree::read_with_options(file, start_offset, &options, &mut Diagnostics::new())?;
+        media.extend(segment_media(&tree, init_segment));
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_metadata(&self, file: &mut dyn MediaSource, start_offset: u64, metadata: &mut MediaMetadata) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        metadata.merge(tree.metadata(file)?);
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_occurrences(
+        &self, file: &mut dyn MediaSource, start_offset: u64, code: &str, occurrences: &mut Vec<Occurrence>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        occurrences.extend(tree.occurrences(code));
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_summary(&self, file: &mut dyn MediaSource, start_offset: u64, summary: &mut Summary) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        summary.tracks.extend(tree.track_summaries());
+
+        Ok(tree.end_offset)
+    }
+
+    fn collect_layout(&self, file: &mut dyn MediaSource, start_offset: u64, layout: &mut Layout) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, &mut Diagnostics::new())?;
+        tree.add_layout(layout);
+
+        Ok(tree.end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, diagnostics)?;
+        check_mandatory_boxes(&tree.boxes, diagnostics);
+        check_segment_indexes(&tree, diagnostics);
+
+        Ok(tree.end_offset)
+    }
+
+    fn visit(
+        &self, file: &mut dyn MediaSource, start_offset: u64, visitor: &mut dyn DissectionVisitor, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let tree = IsobmffTree::read(file, start_offset, diagnostics)?;
+        tree.visit(visitor);
+
+        Ok(tree.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], remaining: u64) -> Confidence
+    {
+        match start_confidence(header, remaining)
+        {
+            // A file type box after leading junk, which the tree skips with a warning
+            | Confidence::None if leading_junk(header, remaining).is_some() == true => Confidence::Medium,
+            | confidence => confidence
         }
+    }
+
+    fn sniff_chained(&self, header: &[u8], remaining: u64) -> Confidence
+    {
+        start_confidence(header, remaining)
+    }
+}
+
+/// Whether `data` starts with an ftyp or styp box with a printable major brand
+fn is_file_type_box(data: &[u8]) -> bool
+{
+    // Any printable major brand: unusual brands are reported by the validation, not rejected
+    // (media segments carry their brands in a styp box of the same layout)
+    data.len() >= 12 && (&data[4..8] == b"ftyp" || &data[4..8] == b"styp") && data[8..12].iter().all(|&byte| byte.is_ascii_graphic() || byte == b' ')
+}
 
-        false
+/// Rate the box at the start of `header`: a file type box followed by another known box is certain
+fn start_confidence(header: &[u8], remaining: u64) -> Confidence
+{
+    // Need at least 12 bytes to check for ftyp box
+    if header.len() < 12
+    {
+        return Confidence::None;
+    }
+
+    let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    if is_file_type_box(header) == true
+    {
+        let next = header.get(size as usize..size as usize + 8);
+        let next_is_box = next.is_some_and(|next| get_box_description(&IsobmffDissector::box_type_to_string(&next[4..8])) != "Unknown Box Type");
+        return match size >= 16 && (size as u64 == remaining || next_is_box == true)
+        {
+            | true => Confidence::Certain,
+            | false => Confidence::High
+        };
+    }
+
+    // Old QuickTime files and some media segments have no ftyp or styp box and start directly with one of these boxes
+    match HEADERLESS_START_TYPES.contains(&&header[4..8]) == true && (size == 0 || size == 1 || (size >= 8 && size as u64 <= remaining))
+    {
+        | true => Confidence::Medium,
+        | false => Confidence::None
+    }
+}
+
+/// Number of bytes of unrecognized data before a file type box, for regions that do not start with a box
+/// but have a plausible ftyp or styp box in `header`
+pub fn leading_junk(header: &[u8], remaining: u64) -> Option<usize>
+{
+    if start_confidence(header, remaining) != Confidence::None
+    {
+        return None;
     }
+    (1..header.len().saturating_sub(11).min(MAX_LEADING_JUNK + 1)).find(|&position| {
+        let size = u32::from_be_bytes([header[position], header[position + 1], header[position + 2], header[position + 3]]);
+        (16..=MAX_FILE_TYPE_SIZE).contains(&size) == true && is_file_type_box(&header[position..]) == true
+    })
 }
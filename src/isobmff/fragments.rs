@@ -0,0 +1,327 @@
+// Fragment timing
+//
+// Backs the `fragments` command: one row per track fragment (traf) of each movie fragment with the
+// sequence number (mfhd), base media decode time (tfdt), the duration and sample count of its track
+// runs and the bytes of the moof box and its sample data. Each fragment of a track should start
+// where the previous one ended; gaps and overlaps between them are what DASH and HLS packagers
+// typically get wrong, so they are listed separately (see `schema/fragments.schema.json`). The
+// `playlist` command takes the start and duration of each HLS segment from the same fragments.
+
+use std::{fmt, path::Path};
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::{
+        r#box::IsobmffBox,
+        content::IsobmffContent,
+        samples::{find_track, find_track_extends, fragment_header, fragment_samples},
+        tree::{InitSegment, IsobmffTree}
+    },
+    playlist::SegmentMedia,
+    report::SCHEMA_VERSION,
+    summary::write_table
+};
+
+/// Track fragment (traf) of a movie fragment
+#[derive(Debug, Clone)]
+pub struct TrackFragment
+{
+    /// Sequence number of the movie fragment (mfhd)
+    pub sequence_number:  Option<u32>,
+    pub track_id:         u32,
+    /// Media timescale of the track (mdhd), None if the file has no movie box for it (e.g. a media segment)
+    pub timescale:        Option<u32>,
+    /// Decode time of the first sample in timescale units (tfdt)
+    pub base_decode_time: Option<u64>,
+    /// Sum of the sample durations in timescale units
+    pub duration:         u64,
+    pub sample_count:     usize,
+    /// Offset of the moof box
+    pub offset:           u64,
+    /// End of the moof box or of the sample data of the fragment, whichever is later
+    pub end_offset:       u64
+}
+
+impl TrackFragment
+{
+    /// Time in timescale units with seconds if the timescale is known, e.g. "90000 (1.000 s)"
+    fn format_ticks(&self, ticks: u64) -> String
+    {
+        match self.timescale
+        {
+            | Some(timescale) if timescale > 0 => format!("{} ({:.3} s)", ticks, ticks as f64 / timescale as f64),
+            | _ => ticks.to_string()
+        }
+    }
+
+    /// Structured representation for the JSON document
+    fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "sequence_number": self.sequence_number,
+            "track_id": self.track_id,
+            "timescale": self.timescale,
+            "base_decode_time": self.base_decode_time,
+            "duration": self.duration,
+            "sample_count": self.sample_count,
+            "offset": self.offset,
+            "size": self.end_offset - self.offset
+        })
+    }
+}
+
+/// Kind of timing discontinuity between consecutive fragments of a track
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscontinuityKind
+{
+    /// The fragment starts after the previous one ended
+    Gap,
+    /// The fragment starts before the previous one ended
+    Overlap
+}
+
+/// Fragment whose decode time does not continue the previous fragment of the same track
+#[derive(Debug, Clone)]
+pub struct Discontinuity
+{
+    pub kind:     DiscontinuityKind,
+    /// Index of the fragment into `FragmentTiming::fragments`
+    pub fragment: usize,
+    /// Index of the previous fragment of the same track
+    pub previous: usize,
+    /// Decode time the fragment was expected to start at (previous decode time plus duration)
+    pub expected: u64,
+    /// Decode time from the tfdt box of the fragment
+    pub actual:   u64
+}
+
+impl Discontinuity
+{
+    /// Size of the gap or overlap in timescale units
+    pub fn ticks(&self) -> u64
+    {
+        self.actual.abs_diff(self.expected)
+    }
+}
+
+/// Track fragments of a file in file order with the timing discontinuities between them
+#[derive(Debug, Clone, Default)]
+pub struct FragmentTiming
+{
+    pub fragments:       Vec<TrackFragment>,
+    pub discontinuities: Vec<Discontinuity>
+}
+
+impl FragmentTiming
+{
+    /// Compare each fragment with the previous fragment of the same track
+    ///
+    /// Fragments without a tfdt box, or following one without, are not checked.
+    pub fn new(fragments: Vec<TrackFragment>) -> Self
+    {
+        let mut discontinuities = Vec::new();
+        for (index, fragment) in fragments.iter().enumerate()
+        {
+            let Some(previous) = fragments[..index].iter().rposition(|candidate| candidate.track_id == fragment.track_id)
+            else
+            {
+                continue;
+            };
+            if let (Some(previous_time), Some(actual)) = (fragments[previous].base_decode_time, fragment.base_decode_time)
+            {
+                let expected = previous_time + fragments[previous].duration;
+                let kind = match actual.cmp(&expected)
+                {
+                    | std::cmp::Ordering::Equal => continue,
+                    | std::cmp::Ordering::Greater => DiscontinuityKind::Gap,
+                    | std::cmp::Ordering::Less => DiscontinuityKind::Overlap
+                };
+                discontinuities.push(Discontinuity { kind, fragment: index, previous, expected, actual });
+            }
+        }
+        Self { fragments, discontinuities }
+    }
+
+    /// Label of a fragment in messages: its sequence number, or its position if it has no mfhd box
+    fn fragment_label(&self, index: usize) -> String
+    {
+        match self.fragments[index].sequence_number
+        {
+            | Some(sequence_number) => sequence_number.to_string(),
+            | None => format!("#{}", index + 1)
+        }
+    }
+
+    /// Description of a discontinuity, e.g. "Track 1: gap of 1024 (0.023 s) between fragments 2 and 3"
+    pub fn describe(&self, discontinuity: &Discontinuity) -> String
+    {
+        let fragment = &self.fragments[discontinuity.fragment];
+        let template = match discontinuity.kind
+        {
+            | DiscontinuityKind::Gap => "Track {}: gap of {} between fragments {} and {} (expected decode time {}, found {})",
+            | DiscontinuityKind::Overlap => "Track {}: overlap of {} between fragments {} and {} (expected decode time {}, found {})"
+        };
+        tr_fmt(template, &[
+            &fragment.track_id,
+            &fragment.format_ticks(discontinuity.ticks()),
+            &self.fragment_label(discontinuity.previous),
+            &self.fragment_label(discontinuity.fragment),
+            &discontinuity.expected,
+            &discontinuity.actual
+        ])
+    }
+
+    /// JSON document of the fragments command (see `schema/fragments.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "fragments": self.fragments.iter().map(TrackFragment::to_json).collect::<Vec<_>>(),
+            "discontinuities": self.discontinuities.iter().map(|discontinuity| serde_json::json!({
+                "kind": match discontinuity.kind
+                {
+                    | DiscontinuityKind::Gap => "gap",
+                    | DiscontinuityKind::Overlap => "overlap"
+                },
+                "track_id": self.fragments[discontinuity.fragment].track_id,
+                "fragment": discontinuity.fragment,
+                "previous_fragment": discontinuity.previous,
+                "expected_decode_time": discontinuity.expected,
+                "decode_time": discontinuity.actual,
+                "ticks": discontinuity.ticks()
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Gather the track fragments of the top-level moof boxes of `tree`
+pub fn track_fragments(tree: &IsobmffTree) -> Vec<TrackFragment>
+{
+    let mut fragments = Vec::new();
+    for moof in tree.boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "moof")
+    {
+        let sequence_number = match moof.find("mfhd").and_then(|mfhd| mfhd.content.as_ref())
+        {
+            | Some(IsobmffContent::MovieFragmentHeader(mfhd)) => Some(mfhd.sequence_number),
+            | _ => None
+        };
+        for traf in moof.children.iter().filter(|child| child.box_type == "traf")
+        {
+            let Some(tfhd) = fragment_header(traf)
+            else
+            {
+                continue;
+            };
+            let tfdt = match traf.find("tfdt").and_then(|tfdt| tfdt.content.as_ref())
+            {
+                | Some(IsobmffContent::TrackFragmentDecodeTime(tfdt)) => Some(tfdt),
+                | _ => None
+            };
+            // Media segments take the timescale attached from their initialization segment
+            let timescale = match find_track(tree, tfhd.track_id).and_then(|trak| trak.find("mdia/mdhd")).and_then(|mdhd| mdhd.content.as_ref())
+            {
+                | Some(IsobmffContent::MediaHeader(mdhd)) => Some(mdhd.timescale),
+                | _ => tfdt.and_then(|tfdt| tfdt.timescale)
+            };
+            let samples = fragment_samples(moof, traf, find_track_extends(tree, tfhd.track_id));
+            let data_end = samples.iter().map(|sample| sample.offset + sample.size as u64).max().unwrap_or(0);
+
+            fragments.push(TrackFragment {
+                sequence_number,
+                track_id: tfhd.track_id,
+                timescale,
+                base_decode_time: tfdt.map(|tfdt| tfdt.base_media_decode_time),
+                duration: samples.iter().map(|sample| sample.duration as u64).sum(),
+                sample_count: samples.len(),
+                offset: moof.offset,
+                end_offset: (moof.offset + moof.size).max(data_end)
+            });
+        }
+    }
+    fragments
+}
+
+/// Timing and protection scheme of a media segment for the playlist check, None if `tree` has no movie fragments
+///
+/// The start is the earliest decode time of a track, the duration that of the longest track. The
+/// protection scheme comes from the sample entries of the movie box, or of `init_segment` without one.
+pub fn segment_media(tree: &IsobmffTree, init_segment: Option<&InitSegment>) -> Option<SegmentMedia>
+{
+    let fragments = track_fragments(tree);
+    if fragments.is_empty() == true
+    {
+        return None;
+    }
+
+    let mut tracks: Vec<(u32, Option<f64>, f64)> = Vec::new();
+    for fragment in &fragments
+    {
+        let Some(timescale) = fragment.timescale.filter(|&timescale| timescale > 0)
+        else
+        {
+            continue;
+        };
+        let start = fragment.base_decode_time.map(|time| time as f64 / timescale as f64);
+        let duration = fragment.duration as f64 / timescale as f64;
+        match tracks.iter_mut().find(|(track_id, ..)| *track_id == fragment.track_id)
+        {
+            | Some(track) => track.2 += duration,
+            | None => tracks.push((fragment.track_id, start, duration))
+        }
+    }
+
+    let moov = tree.boxes.iter().find(|isobmff_box| isobmff_box.box_type == "moov").or(init_segment.map(|init_segment| &init_segment.moov));
+    Some(SegmentMedia {
+        start:      tracks.iter().filter_map(|&(_, start, _)| start).reduce(f64::min),
+        duration:   tracks.iter().map(|&(_, _, duration)| duration).reduce(f64::max),
+        encryption: moov.and_then(protection_scheme)
+    })
+}
+
+/// Protection scheme (schm) of the first encrypted sample entry of the movie box
+fn protection_scheme(moov: &IsobmffBox) -> Option<String>
+{
+    moov.children.iter().filter(|child| child.box_type == "trak").find_map(|trak| match trak.find("mdia/minf/stbl/stsd").and_then(|stsd| stsd.content.as_ref())
+    {
+        | Some(IsobmffContent::SampleDescription(stsd)) =>
+            stsd.entries.iter().find_map(|entry| entry.protection().and_then(|protection| protection.scheme_type).map(|scheme_type| scheme_type.scheme_type)),
+        | _ => None
+    })
+}
+
+impl fmt::Display for FragmentTiming
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut rows = vec![[tr("Seq"), tr("Track"), tr("Decode Time"), tr("Duration"), tr("Samples"), tr("Byte Range")].map(str::to_string)];
+        for fragment in &self.fragments
+        {
+            rows.push([
+                fragment.sequence_number.map_or_else(|| "-".to_string(), |sequence_number| sequence_number.to_string()),
+                fragment.track_id.to_string(),
+                fragment.base_decode_time.map_or_else(|| "-".to_string(), |time| fragment.format_ticks(time)),
+                fragment.format_ticks(fragment.duration),
+                fragment.sample_count.to_string(),
+                tr_fmt("0x{}-0x{} ({} bytes)", &[
+                    &format!("{:08X}", fragment.offset),
+                    &format!("{:08X}", fragment.end_offset - 1),
+                    &(fragment.end_offset - fragment.offset)
+                ])
+            ]);
+        }
+        writeln!(f, "{}", tr("Fragments:"))?;
+        write_table(f, &rows)?;
+
+        writeln!(f, "\n{}", tr("Timing:"))?;
+        if self.discontinuities.is_empty() == true
+        {
+            writeln!(f, "  {}", tr("No gaps or overlaps between consecutive fragments of a track"))?;
+        }
+        for discontinuity in &self.discontinuities
+        {
+            writeln!(f, "  {}", self.describe(discontinuity))?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use crate::{
+    hashes::to_hex,
+    i18n::{tr, tr_fmt}
+};
+
 /// iTunes metadata data type
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ItunesDataType
@@ -33,6 +38,22 @@ pub fn from_flags(flags: u32) -> Self
             | other => ItunesDataType::Binary(other)
         }
     }
+
+    /// Well-known type code stored in the flags of the data box
+    pub fn code(&self) -> u8
+    {
+        match self
+        {
+            | ItunesDataType::Implicit => 0x00,
+            | ItunesDataType::Utf8 => 0x01,
+            | ItunesDataType::Utf16Be => 0x02,
+            | ItunesDataType::Jpeg => 0x0D,
+            | ItunesDataType::Png => 0x0E,
+            | ItunesDataType::SignedInt => 0x15,
+            | ItunesDataType::UnsignedInt => 0x16,
+            | ItunesDataType::Binary(type_byte) => *type_byte
+        }
+    }
 }
 
 impl fmt::Display for ItunesDataType
@@ -41,14 +62,14 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
         match self
         {
-            | ItunesDataType::Implicit => write!(f, "Implicit"),
-            | ItunesDataType::Utf8 => write!(f, "UTF-8"),
-            | ItunesDataType::Utf16Be => write!(f, "UTF-16 BE"),
-            | ItunesDataType::Jpeg => write!(f, "JPEG Image"),
-            | ItunesDataType::Png => write!(f, "PNG Image"),
-            | ItunesDataType::SignedInt => write!(f, "Signed Integer"),
-            | ItunesDataType::UnsignedInt => write!(f, "Unsigned Integer"),
-            | ItunesDataType::Binary(type_byte) => write!(f, "Binary (0x{:02X})", type_byte)
+            | ItunesDataType::Implicit => write!(f, "{}", tr("Implicit")),
+            | ItunesDataType::Utf8 => write!(f, "{}", tr("UTF-8")),
+            | ItunesDataType::Utf16Be => write!(f, "{}", tr("UTF-16 BE")),
+            | ItunesDataType::Jpeg => write!(f, "{}", tr("JPEG Image")),
+            | ItunesDataType::Png => write!(f, "{}", tr("PNG Image")),
+            | ItunesDataType::SignedInt => write!(f, "{}", tr("Signed Integer")),
+            | ItunesDataType::UnsignedInt => write!(f, "{}", tr("Unsigned Integer")),
+            | ItunesDataType::Binary(type_byte) => write!(f, "{}", tr_fmt("Binary ({})", &[&format!("0x{:02X}", type_byte)]))
         }
     }
 }
@@ -99,7 +120,7 @@ pub fn parse(box_type: &str, data: &[u8]) -> Result<Self, String>
 
         if data.len() < 8
         {
-            return Err("iTunes data box too short".to_string());
+            return Err(tr("iTunes data box too short").to_string());
         }
 
         let _version = data[0];
@@ -155,7 +176,7 @@ pub fn parse(box_type: &str, data: &[u8]) -> Result<Self, String>
                     | 2 => i16::from_be_bytes([payload[0], payload[1]]) as i64,
                     | 4 => i32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]) as i64,
                     | 8 => i64::from_be_bytes([payload[0], payload[1], payload[2], payload[3], payload[4], payload[5], payload[6], payload[7]]),
-                    | _ => return Err(format!("Invalid signed integer size: {} bytes", payload.len()))
+                    | _ => return Err(tr_fmt("Invalid signed integer size: {} bytes", &[&payload.len()]))
                 };
                 ItunesContent::Integer(value)
             }
@@ -183,7 +204,7 @@ pub fn parse(box_type: &str, data: &[u8]) -> Result<Self, String>
                     | 2 => u16::from_be_bytes([payload[0], payload[1]]) as u64,
                     | 4 => u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]) as u64,
                     | 8 => u64::from_be_bytes([payload[0], payload[1], payload[2], payload[3], payload[4], payload[5], payload[6], payload[7]]),
-                    | _ => return Err(format!("Invalid unsigned integer size: {} bytes", payload.len()))
+                    | _ => return Err(tr_fmt("Invalid unsigned integer size: {} bytes", &[&payload.len()]))
                 };
                 ItunesContent::UnsignedInteger(value)
             }
@@ -194,41 +215,57 @@ pub fn parse(box_type: &str, data: &[u8]) -> Result<Self, String>
 
         Ok(ItunesMetadata { data_type, content })
     }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let value = match self.content
+        {
+            | ItunesContent::Text(ref text) => serde_json::json!(text),
+            | ItunesContent::Integer(value) => serde_json::json!(value),
+            | ItunesContent::UnsignedInteger(value) => serde_json::json!(value),
+            | ItunesContent::Image { ref format, data_size } => serde_json::json!({ "format": format, "data_size": data_size }),
+            | ItunesContent::Binary(ref data) => serde_json::json!(to_hex(data)),
+            | ItunesContent::TrackNumber { track, total_tracks } => serde_json::json!({ "track": track, "total_tracks": total_tracks }),
+            | ItunesContent::DiskNumber { disk, total_disks } => serde_json::json!({ "disk": disk, "total_disks": total_disks })
+        };
+        serde_json::json!({ "data_type": self.data_type.code(), "value": value })
+    }
 }
 
 impl fmt::Display for ItunesMetadata
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        writeln!(f, "Data Type: {}", self.data_type)?;
+        writeln!(f, "{}", tr_fmt("Data Type: {}", &[&self.data_type]))?;
 
         match &self.content
         {
-            | ItunesContent::Text(text) => writeln!(f, "Value: \"{}\"", text)?,
-            | ItunesContent::Integer(value) => writeln!(f, "Value: {}", value)?,
-            | ItunesContent::UnsignedInteger(value) => writeln!(f, "Value: {}", value)?,
-            | ItunesContent::Image { format, data_size } => writeln!(f, "Value: {} image, {} bytes", format, data_size)?,
-            | ItunesContent::Binary(data) => writeln!(f, "Value: Binary data, {} bytes", data.len())?,
+            | ItunesContent::Text(text) => writeln!(f, "{}", tr_fmt("Value: \"{}\"", &[&text]))?,
+            | ItunesContent::Integer(value) => writeln!(f, "{}", tr_fmt("Value: {}", &[&value]))?,
+            | ItunesContent::UnsignedInteger(value) => writeln!(f, "{}", tr_fmt("Value: {}", &[&value]))?,
+            | ItunesContent::Image { format, data_size } => writeln!(f, "{}", tr_fmt("Value: {} image, {} bytes", &[&format, &data_size]))?,
+            | ItunesContent::Binary(data) => writeln!(f, "{}", tr_fmt("Value: Binary data, {} bytes", &[&data.len()]))?,
             | ItunesContent::TrackNumber { track, total_tracks } =>
             {
                 if *total_tracks > 0
                 {
-                    writeln!(f, "Value: Track {} of {}", track, total_tracks)?
+                    writeln!(f, "{}", tr_fmt("Value: Track {} of {}", &[&track, &total_tracks]))?
                 }
                 else
                 {
-                    writeln!(f, "Value: Track {}", track)?
+                    writeln!(f, "{}", tr_fmt("Value: Track {}", &[&track]))?
                 }
             }
             | ItunesContent::DiskNumber { disk, total_disks } =>
             {
                 if *total_disks > 0
                 {
-                    writeln!(f, "Value: Disk {} of {}", disk, total_disks)?
+                    writeln!(f, "{}", tr_fmt("Value: Disk {} of {}", &[&disk, &total_disks]))?
                 }
                 else
                 {
-                    writeln!(f, "Value: Disk {}", disk)?
+                    writeln!(f, "{}", tr_fmt("Value: Disk {}", &[&disk]))?
                 }
             }
         }
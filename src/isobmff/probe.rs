@@ -0,0 +1,185 @@
+use crate::{
+    ffprobe::{ProbeFormat, ProbeStream},
+    isobmff::{r#box::IsobmffBox, content::IsobmffContent},
+    summary::TrackSummary
+};
+
+/// Find the first direct child box of the given type
+fn child<'a>(parent: &'a IsobmffBox, box_type: &str) -> Option<&'a IsobmffBox>
+{
+    parent.children.iter().find(|child| child.box_type == box_type)
+}
+
+/// Follow a path of box types from `parent` down the tree
+fn descend<'a>(parent: &'a IsobmffBox, path: &[&str]) -> Option<&'a IsobmffBox>
+{
+    path.iter().try_fold(parent, |current, box_type| child(current, box_type))
+}
+
+/// ffmpeg codec name and long name for a sample entry format code
+fn codec_names(format: &str) -> Option<(&'static str, &'static str)>
+{
+    match format
+    {
+        | "avc1" | "avc3" => Some(("h264", "H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10")),
+        | "hvc1" | "hev1" => Some(("hevc", "H.265 / HEVC (High Efficiency Video Coding)")),
+        | "av01" => Some(("av1", "Alliance for Open Media AV1")),
+        | "vp08" => Some(("vp8", "On2 VP8")),
+        | "vp09" => Some(("vp9", "Google VP9")),
+        | "mp4v" => Some(("mpeg4", "MPEG-4 part 2")),
+        | "jpeg" | "mjpa" => Some(("mjpeg", "Motion JPEG")),
+        | "apcn" | "apch" | "apcs" | "apco" | "ap4h" => Some(("prores", "Apple ProRes (iCodec Pro)")),
+        | "mp4a" => Some(("aac", "AAC (Advanced Audio Coding)")),
+        | ".mp3" => Some(("mp3", "MP3 (MPEG audio layer 3)")),
+        | "ac-3" => Some(("ac3", "ATSC A/52A (AC-3)")),
+        | "ec-3" => Some(("eac3", "ATSC A/52B (AC-3, E-AC-3)")),
+        | "Opus" => Some(("opus", "Opus (Opus Interactive Audio Codec)")),
+        | "fLaC" => Some(("flac", "FLAC (Free Lossless Audio Codec)")),
+        | "alac" => Some(("alac", "ALAC (Apple Lossless Audio Codec)")),
+        | "samr" => Some(("amr_nb", "AMR-NB (Adaptive Multi-Rate NarrowBand)")),
+        | "sawb" => Some(("amr_wb", "AMR-WB (Adaptive Multi-Rate WideBand)")),
+        | "tx3g" | "text" => Some(("mov_text", "MOV text")),
+        | "wvtt" => Some(("webvtt", "WebVTT subtitle")),
+        | "stpp" => Some(("ttml", "Timed Text Markup Language")),
+        | _ => None
+    }
+}
+
+/// ffprobe codec type for a handler type
+fn codec_type(handler_type: &str) -> &'static str
+{
+    match handler_type
+    {
+        | "vide" => "video",
+        | "soun" => "audio",
+        | "text" | "sbtl" | "subt" | "clcp" => "subtitle",
+        | _ => "data"
+    }
+}
+
+/// Describe one trak box as an ffprobe stream
+fn probe_track(trak: &IsobmffBox) -> ProbeStream
+{
+    let mut stream = ProbeStream { codec_type: "data", ..Default::default() };
+
+    if let Some(IsobmffContent::Handler(hdlr)) = descend(trak, &["mdia", "hdlr"]).and_then(|hdlr| hdlr.content.as_ref())
+    {
+        stream.codec_type = codec_type(&hdlr.handler_type);
+    }
+
+    if let Some(IsobmffContent::MediaHeader(mdhd)) = descend(trak, &["mdia", "mdhd"]).and_then(|mdhd| mdhd.content.as_ref()) &&
+        mdhd.timescale > 0 &&
+        mdhd.duration > 0
+    {
+        stream.duration = Some(mdhd.duration as f64 / mdhd.timescale as f64);
+    }
+
+    if let Some(IsobmffContent::SampleDescription(stsd)) = descend(trak, &["mdia", "minf", "stbl", "stsd"]).and_then(|stsd| stsd.content.as_ref()) &&
+        let Some(entry) = stsd.entries.first()
+    {
+        if let Some((codec_name, codec_long_name)) = codec_names(&entry.format)
+        {
+            stream.codec_name = Some(codec_name);
+            stream.codec_long_name = Some(codec_long_name);
+        }
+        stream.codec_tag_string = Some(entry.format.clone());
+
+        match stream.codec_type
+        {
+            | "video" =>
+            {
+                if let Some((width, height)) = entry.dimensions()
+                {
+                    stream.width = Some(width as u32);
+                    stream.height = Some(height as u32);
+                }
+            }
+            | "audio" =>
+            {
+                if let Some((channels, sample_rate)) = entry.audio_format()
+                {
+                    stream.channels = Some(channels);
+                    stream.sample_rate = Some(sample_rate);
+                }
+
+                // AMR sample entries carry fixed placeholder values (2 channels), the codecs are mono at 8 or 16 kHz
+                match entry.format.as_str()
+                {
+                    | "samr" => (stream.channels, stream.sample_rate) = (Some(1), Some(8000)),
+                    | "sawb" => (stream.channels, stream.sample_rate) = (Some(1), Some(16000)),
+                    | _ =>
+                    {}
+                }
+
+                // The AudioSpecificConfig is authoritative for AAC (the sample entry may hold placeholders)
+                if let Some(esds) = entry.esds()
+                {
+                    if esds.avg_bitrate > 0
+                    {
+                        stream.bit_rate = Some(esds.avg_bitrate as u64);
+                    }
+                    if let Some(config) = esds.audio_config
+                    {
+                        stream.sample_rate = Some(config.extension_sample_rate.unwrap_or(config.sample_rate));
+                        if let Some(channels) = config.channels()
+                        {
+                            stream.channels = Some(channels);
+                        }
+                    }
+                }
+            }
+            | _ =>
+            {}
+        }
+    }
+
+    // Fall back to the presentation size of the track header
+    if stream.codec_type == "video" &&
+        stream.width.is_none() == true &&
+        let Some(IsobmffContent::TrackHeader(tkhd)) = child(trak, "tkhd").and_then(|tkhd| tkhd.content.as_ref())
+    {
+        stream.width = Some(tkhd.width as u32);
+        stream.height = Some(tkhd.height as u32);
+    }
+
+    stream
+}
+
+/// Describe a parsed box tree in ffprobe terms
+pub fn probe_boxes(boxes: &[IsobmffBox]) -> ProbeFormat
+{
+    let moov = boxes.iter().find(|isobmff_box| isobmff_box.box_type == "moov");
+
+    let streams: Vec<ProbeStream> = moov.map(|moov| moov.children.iter().filter(|child| child.box_type == "trak").map(probe_track).collect()).unwrap_or_default();
+
+    let movie_duration = moov.and_then(|moov| child(moov, "mvhd")).and_then(|mvhd| match &mvhd.content
+    {
+        | Some(IsobmffContent::MovieHeader(mvhd)) if mvhd.timescale > 0 && mvhd.duration > 0 => Some(mvhd.duration as f64 / mvhd.timescale as f64),
+        | _ => None
+    });
+    let duration = movie_duration.or_else(|| streams.iter().filter_map(|stream| stream.duration).reduce(f64::max));
+
+    ProbeFormat { format_name: "mov,mp4,m4a,3gp,3g2,mj2", format_long_name: "QuickTime / MOV", duration, streams }
+}
+
+/// One summary line per track: the ffprobe stream plus track ID (tkhd) and language (mdhd)
+pub fn summarize_tracks(boxes: &[IsobmffBox]) -> Vec<TrackSummary>
+{
+    let tracks = boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "moov").flat_map(|moov| moov.children.iter().filter(|child| child.box_type == "trak"));
+
+    tracks
+        .map(|trak| {
+            let track_id = match child(trak, "tkhd").and_then(|tkhd| tkhd.content.as_ref())
+            {
+                | Some(IsobmffContent::TrackHeader(tkhd)) => tkhd.track_id,
+                | _ => 0
+            };
+            let language = match descend(trak, &["mdia", "mdhd"]).and_then(|mdhd| mdhd.content.as_ref())
+            {
+                | Some(IsobmffContent::MediaHeader(mdhd)) => Some(mdhd.language.clone()),
+                | _ => None
+            };
+            TrackSummary { track_id, language, stream: probe_track(trak) }
+        })
+        .collect()
+}
@@ -0,0 +1,240 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    isobmff::{r#box::IsobmffBox, content::IsobmffContent}
+};
+
+/// Number of distinct sample durations listed in the text output
+const MAX_LISTED_DURATIONS: usize = 8;
+
+/// Sample table statistics of one track (`--stats`)
+#[derive(Debug, Clone)]
+pub struct TrackSampleStats
+{
+    pub track_id:          u32,
+    pub handler_type:      String,
+    pub timescale:         u32,
+    pub sample_count:      u64,
+    /// Total duration in timescale units
+    pub total_duration:    u64,
+    /// Number of samples per sample duration (timescale units)
+    pub durations:         BTreeMap<u32, u64>,
+    /// Number of sample sizes known from stsz (less than `sample_count` if the table was limited)
+    pub sized_samples:     u64,
+    pub min_size:          u32,
+    pub max_size:          u32,
+    pub total_size:        u64,
+    pub chunk_count:       u64,
+    pub min_chunk_samples: u32,
+    pub max_chunk_samples: u32,
+    /// Lowest and highest chunk offset
+    pub chunk_span:        Option<(u64, u64)>,
+    /// Whether the chunks are stored in ascending file order
+    pub chunks_ascending:  bool
+}
+
+impl TrackSampleStats
+{
+    /// Compute the statistics of a `trak` box, None if it has no sample table
+    pub fn from_track(trak: &IsobmffBox) -> Option<Self>
+    {
+        let stbl = trak.find("mdia/minf/stbl")?;
+        let content = |path: &str| trak.find(path).and_then(|isobmff_box| isobmff_box.content.as_ref());
+
+        let track_id = match content("tkhd")
+        {
+            | Some(IsobmffContent::TrackHeader(track_header)) => track_header.track_id,
+            | _ => 0
+        };
+        let handler_type = match content("mdia/hdlr")
+        {
+            | Some(IsobmffContent::Handler(handler)) => handler.handler_type.clone(),
+            | _ => String::new()
+        };
+        let timescale = match content("mdia/mdhd")
+        {
+            | Some(IsobmffContent::MediaHeader(media_header)) => media_header.timescale,
+            | _ => 0
+        };
+
+        let mut stats = Self {
+            track_id,
+            handler_type,
+            timescale,
+            sample_count: 0,
+            total_duration: 0,
+            durations: BTreeMap::new(),
+            sized_samples: 0,
+            min_size: 0,
+            max_size: 0,
+            total_size: 0,
+            chunk_count: 0,
+            min_chunk_samples: 0,
+            max_chunk_samples: 0,
+            chunk_span: None,
+            chunks_ascending: true
+        };
+
+        if let Some(IsobmffContent::TimeToSample(stts)) = stbl.find("stts").and_then(|stts| stts.content.as_ref())
+        {
+            for entry in &stts.entries
+            {
+                stats.sample_count += entry.sample_count as u64;
+                stats.total_duration += entry.sample_count as u64 * entry.sample_delta as u64;
+                *stats.durations.entry(entry.sample_delta).or_default() += entry.sample_count as u64;
+            }
+        }
+
+        if let Some(IsobmffContent::SampleSize(stsz)) = stbl.find("stsz").and_then(|stsz| stsz.content.as_ref())
+        {
+            if stsz.sample_size != 0
+            {
+                // Constant size needs no iteration over the samples
+                stats.sized_samples = stsz.sample_count as u64;
+                stats.min_size = stsz.sample_size;
+                stats.max_size = stsz.sample_size;
+                stats.total_size = stsz.sample_count as u64 * stsz.sample_size as u64;
+            }
+            else if stsz.entry_sizes.is_empty() == false
+            {
+                stats.sized_samples = stsz.entry_sizes.len() as u64;
+                stats.min_size = stsz.entry_sizes.iter().copied().min().unwrap_or(0);
+                stats.max_size = stsz.entry_sizes.iter().copied().max().unwrap_or(0);
+                stats.total_size = stsz.entry_sizes.iter().map(|&size| size as u64).sum();
+            }
+        }
+
+        let chunk_offsets: Vec<u64> = match stbl.find("stco").or_else(|| stbl.find("co64")).and_then(|offsets| offsets.content.as_ref())
+        {
+            | Some(IsobmffContent::ChunkOffset(stco)) => stco.chunk_offsets.iter().map(|&offset| offset as u64).collect(),
+            | Some(IsobmffContent::ChunkOffset64(co64)) => co64.chunk_offsets.clone(),
+            | _ => Vec::new()
+        };
+        stats.chunk_count = chunk_offsets.len() as u64;
+        stats.chunk_span = chunk_offsets.iter().copied().min().zip(chunk_offsets.iter().copied().max());
+        stats.chunks_ascending = chunk_offsets.windows(2).all(|pair| pair[0] < pair[1]);
+
+        if let Some(IsobmffContent::SampleToChunk(stsc)) = stbl.find("stsc").and_then(|stsc| stsc.content.as_ref())
+        {
+            // Each entry covers the chunks up to the first chunk of the next entry (or the last chunk)
+            let mut chunk_samples = Vec::new();
+            for (index, entry) in stsc.entries.iter().enumerate()
+            {
+                let next_first_chunk = stsc.entries.get(index + 1).map_or(stats.chunk_count + 1, |next| next.first_chunk as u64);
+                if next_first_chunk > entry.first_chunk as u64
+                {
+                    chunk_samples.push(entry.samples_per_chunk);
+                }
+            }
+            stats.min_chunk_samples = chunk_samples.iter().copied().min().unwrap_or(0);
+            stats.max_chunk_samples = chunk_samples.iter().copied().max().unwrap_or(0);
+        }
+
+        Some(stats)
+    }
+
+    /// Average sample size in bytes
+    pub fn average_size(&self) -> f64
+    {
+        if self.sized_samples == 0
+        {
+            return 0.0;
+        }
+        self.total_size as f64 / self.sized_samples as f64
+    }
+
+    /// Average number of samples per chunk
+    pub fn average_chunk_samples(&self) -> f64
+    {
+        if self.chunk_count == 0
+        {
+            return 0.0;
+        }
+        self.sample_count as f64 / self.chunk_count as f64
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "track_id": self.track_id,
+            "handler_type": self.handler_type,
+            "timescale": self.timescale,
+            "sample_count": self.sample_count,
+            "total_duration": self.total_duration,
+            "durations": self.durations.iter().map(|(duration, count)| serde_json::json!({ "duration": duration, "count": count })).collect::<Vec<_>>(),
+            "sizes": {
+                "samples": self.sized_samples,
+                "min": self.min_size,
+                "max": self.max_size,
+                "average": self.average_size(),
+                "total": self.total_size
+            },
+            "chunks": {
+                "count": self.chunk_count,
+                "min_samples": self.min_chunk_samples,
+                "max_samples": self.max_chunk_samples,
+                "average_samples": self.average_chunk_samples(),
+                "first_offset": self.chunk_span.map(|(first, _)| first),
+                "last_offset": self.chunk_span.map(|(_, last)| last),
+                "ascending": self.chunks_ascending
+            }
+        })
+    }
+}
+
+impl fmt::Display for TrackSampleStats
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Track {} ('{}'), timescale {}:", &[&self.track_id, &self.handler_type, &self.timescale]))?;
+
+        let seconds = if self.timescale > 0
+        {
+            format!("{:.3}", self.total_duration as f64 / self.timescale as f64)
+        }
+        else
+        {
+            "?".to_string()
+        };
+        writeln!(f, "  {}", tr_fmt("Samples: {}, duration: {} s", &[&self.sample_count, &seconds]))?;
+
+        // Most frequent durations first
+        let mut durations: Vec<(&u32, &u64)> = self.durations.iter().collect();
+        durations.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let mut listed: Vec<String> = durations.iter().take(MAX_LISTED_DURATIONS).map(|(duration, count)| format!("{} x {}", duration, count)).collect();
+        if durations.len() > MAX_LISTED_DURATIONS
+        {
+            listed.push(tr_fmt("{} more", &[&(durations.len() - MAX_LISTED_DURATIONS)]));
+        }
+        writeln!(f, "  {}", tr_fmt("Sample durations (units x samples): {}", &[&listed.join(", ")]))?;
+
+        writeln!(
+            f,
+            "  {}",
+            tr_fmt("Sample sizes: min {}, max {}, average {} bytes ({} bytes total)", &[
+                &self.min_size,
+                &self.max_size,
+                &format!("{:.1}", self.average_size()),
+                &self.total_size
+            ])
+        )?;
+
+        let mut chunks = tr_fmt("Chunks: {}, {}-{} samples per chunk (average {})", &[
+            &self.chunk_count,
+            &self.min_chunk_samples,
+            &self.max_chunk_samples,
+            &format!("{:.1}", self.average_chunk_samples())
+        ]);
+        if let Some((first, last)) = self.chunk_span
+        {
+            chunks.push_str(&format!(", 0x{:08X} - 0x{:08X}", first, last));
+        }
+        if self.chunks_ascending == false
+        {
+            chunks.push_str(&format!(" ({})", tr("not in file order")));
+        }
+        writeln!(f, "  {}", chunks)
+    }
+}
@@ -0,0 +1,279 @@
+// Sample location
+//
+// Resolves where the samples of a track are stored. The sample table of the `trak` box maps
+// samples to chunks (stsc) with their sizes (stsz) and chunk offsets (stco/co64); fragmented
+// files continue in the track runs (trun) of each `moof`, whose sizes fall back to the tfhd and
+// trex defaults. Backs `extract samples` and the chapter track reader.
+
+use std::{
+    fs,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    str::FromStr
+};
+
+use crate::{
+    i18n::tr_fmt,
+    isobmff::{
+        r#box::IsobmffBox,
+        content::{IsobmffContent, TrackExtendsBox, TrackFragmentHeaderBox},
+        tree::IsobmffTree
+    },
+    media_source::MediaSource
+};
+
+/// Position of one sample in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleLocation
+{
+    /// Sample number, counted from 1 over the sample table and all fragments
+    pub number:   u64,
+    pub offset:   u64,
+    pub size:     u32,
+    /// Duration in units of the media timescale (mdhd)
+    pub duration: u32
+}
+
+/// Inclusive range of sample numbers, e.g. "1..10", "5.." or "7"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleRange
+{
+    pub first: u64,
+    /// Last sample of the range, None for all following samples
+    pub last:  Option<u64>
+}
+
+impl SampleRange
+{
+    /// Whether sample `number` lies in the range
+    pub fn contains(&self, number: u64) -> bool
+    {
+        number >= self.first && self.last.is_none_or(|last| number <= last)
+    }
+}
+
+impl FromStr for SampleRange
+{
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err>
+    {
+        let number = |part: &str| part.trim().parse::<u64>().ok().filter(|&number| number > 0);
+        let range = match text.split_once("..")
+        {
+            | Some((first, last)) =>
+            {
+                let first = match first.trim().is_empty()
+                {
+                    | true => Some(1),
+                    | false => number(first)
+                };
+                let last = match last.trim().is_empty()
+                {
+                    | true => Some(None),
+                    | false => number(last).map(Some)
+                };
+                first.zip(last).map(|(first, last)| SampleRange { first, last })
+            }
+            | None => number(text).map(|sample| SampleRange { first: sample, last: Some(sample) })
+        };
+        range
+            .filter(|range| range.last.is_none_or(|last| last >= range.first))
+            .ok_or_else(|| tr_fmt("Expected a sample range such as 1..10, 5.. or 7 (samples are counted from 1), got '{}'", &[&text]))
+    }
+}
+
+/// File offsets of all samples, resolved through the sample-to-chunk table
+pub fn sample_offsets(stbl: &IsobmffBox, sizes: &[u32]) -> Vec<u64>
+{
+    let content = |box_type: &str| stbl.find(box_type).and_then(|isobmff_box| isobmff_box.content.as_ref());
+    let Some(IsobmffContent::SampleToChunk(stsc)) = content("stsc")
+    else
+    {
+        return Vec::new();
+    };
+    let chunk_offsets: Vec<u64> = match content("stco").or_else(|| content("co64"))
+    {
+        | Some(IsobmffContent::ChunkOffset(stco)) => stco.chunk_offsets.iter().map(|&offset| offset as u64).collect(),
+        | Some(IsobmffContent::ChunkOffset64(co64)) => co64.chunk_offsets.clone(),
+        | _ => Vec::new()
+    };
+
+    let mut offsets = Vec::new();
+    for (chunk_index, chunk_offset) in chunk_offsets.into_iter().enumerate()
+    {
+        let mut offset = chunk_offset;
+        for _ in 0..stsc.samples_in_chunk(chunk_index as u32 + 1)
+        {
+            let Some(&size) = sizes.get(offsets.len())
+            else
+            {
+                return offsets;
+            };
+            offsets.push(offset);
+            offset += size as u64;
+        }
+    }
+    offsets
+}
+
+/// Sample sizes of a sample table (stsz)
+pub fn sample_sizes(stbl: &IsobmffBox) -> Vec<u32>
+{
+    match stbl.find("stsz").and_then(|stsz| stsz.content.as_ref())
+    {
+        | Some(IsobmffContent::SampleSize(stsz)) => stsz.sample_sizes().collect(),
+        | _ => Vec::new()
+    }
+}
+
+/// Track ID of a `trak` box, 0 without a readable `tkhd`
+pub fn track_id(trak: &IsobmffBox) -> u32
+{
+    match trak.find("tkhd").and_then(|tkhd| tkhd.content.as_ref())
+    {
+        | Some(IsobmffContent::TrackHeader(track_header)) => track_header.track_id,
+        | _ => 0
+    }
+}
+
+/// The `trak` box of track `id`
+pub fn find_track(tree: &IsobmffTree, id: u32) -> Option<&IsobmffBox>
+{
+    tree.boxes
+        .iter()
+        .filter(|isobmff_box| isobmff_box.box_type == "moov")
+        .flat_map(|moov| moov.children.iter().filter(|child| child.box_type == "trak"))
+        .find(|trak| track_id(trak) == id)
+}
+
+/// Fragment defaults (trex) of track `id`
+pub fn find_track_extends(tree: &IsobmffTree, id: u32) -> Option<&TrackExtendsBox>
+{
+    let mvex = tree.boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "moov").find_map(|moov| moov.find("mvex"))?;
+    mvex.children.iter().find_map(|trex| match trex.content
+    {
+        | Some(IsobmffContent::TrackExtends(ref track_extends)) if track_extends.track_id == id => Some(track_extends),
+        | _ => None
+    })
+}
+
+/// Track fragment header (tfhd) of a `traf` box
+pub fn fragment_header(traf: &IsobmffBox) -> Option<&TrackFragmentHeaderBox>
+{
+    match traf.find("tfhd").and_then(|tfhd| tfhd.content.as_ref())
+    {
+        | Some(IsobmffContent::TrackFragmentHeader(tfhd)) => Some(tfhd),
+        | _ => None
+    }
+}
+
+/// Locate the samples of the track runs of `traf`, a track fragment of `moof`, numbered from 1
+///
+/// Track fragments without an explicit base data offset are taken relative to their `moof` box, as
+/// required with `default-base-is-moof` and written by all common packagers for single-track fragments.
+/// Sizes and durations missing from the runs fall back to the tfhd and then the trex defaults, or to those
+/// attached from the initialization segment of a media segment.
+pub fn fragment_samples(moof: &IsobmffBox, traf: &IsobmffBox, track_extends: Option<&TrackExtendsBox>) -> Vec<SampleLocation>
+{
+    let Some(tfhd) = fragment_header(traf)
+    else
+    {
+        return Vec::new();
+    };
+
+    let mut locations = Vec::new();
+    let base = tfhd.base_data_offset.unwrap_or(moof.offset);
+    let mut offset = base;
+    for trun in traf.children.iter().filter(|child| child.box_type == "trun")
+    {
+        let Some(IsobmffContent::TrackRun(ref run)) = trun.content
+        else
+        {
+            continue;
+        };
+        // Runs without a data offset continue where the previous run ended
+        if let Some(data_offset) = run.data_offset
+        {
+            offset = base.saturating_add_signed(data_offset as i64);
+        }
+        for sample in &run.samples
+        {
+            // Media segments without a movie box rely on the defaults attached from their initialization segment
+            let context = run.context.unwrap_or_default();
+            let size = sample.size.or(tfhd.default_sample_size).or(track_extends.map(|trex| trex.default_sample_size)).or(context.default_sample_size).unwrap_or(0);
+            let duration = sample
+                .duration
+                .or(tfhd.default_sample_duration)
+                .or(track_extends.map(|trex| trex.default_sample_duration))
+                .or(context.default_sample_duration)
+                .unwrap_or(0);
+            locations.push(SampleLocation { number: locations.len() as u64 + 1, offset, size, duration });
+            offset += size as u64;
+        }
+    }
+    locations
+}
+
+/// Locate the samples of track `id`: first those of its sample table, then those of the track runs of all movie fragments
+pub fn locate_samples(tree: &IsobmffTree, id: u32) -> Vec<SampleLocation>
+{
+    let mut locations = Vec::new();
+    if let Some(stbl) = find_track(tree, id).and_then(|trak| trak.find("mdia/minf/stbl"))
+    {
+        let sizes = sample_sizes(stbl);
+        let durations: Vec<u32> = match stbl.find("stts").and_then(|stts| stts.content.as_ref())
+        {
+            | Some(IsobmffContent::TimeToSample(stts)) => stts.sample_durations().collect(),
+            | _ => Vec::new()
+        };
+        for (index, (&offset, &size)) in sample_offsets(stbl, &sizes).iter().zip(&sizes).enumerate()
+        {
+            let duration = durations.get(index).copied().unwrap_or(0);
+            locations.push(SampleLocation { number: locations.len() as u64 + 1, offset, size, duration });
+        }
+    }
+
+    let track_extends = find_track_extends(tree, id);
+    for moof in tree.boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "moof")
+    {
+        for traf in moof.children.iter().filter(|child| child.box_type == "traf" && fragment_header(child).is_some_and(|tfhd| tfhd.track_id == id))
+        {
+            for sample in fragment_samples(moof, traf, track_extends)
+            {
+                locations.push(SampleLocation { number: locations.len() as u64 + 1, ..sample });
+            }
+        }
+    }
+    locations
+}
+
+/// Write the payload of each sample in `samples` to `directory` as "track<ID>_sample<NNNNNN>.bin"
+pub fn extract(file: &mut dyn MediaSource, samples: &[SampleLocation], track_id: u32, directory: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>>
+{
+    fs::create_dir_all(directory)?;
+    let file_size = file.size()?;
+
+    let mut paths = Vec::new();
+    for sample in samples
+    {
+        if sample.offset.saturating_add(sample.size as u64) > file_size
+        {
+            return Err(tr_fmt("Sample {} at offset {} ({} bytes) lies beyond the end of the file", &[
+                &sample.number,
+                &format!("0x{:08X}", sample.offset),
+                &sample.size
+            ])
+            .into());
+        }
+        let mut data = vec![0u8; sample.size as usize];
+        file.seek(SeekFrom::Start(sample.offset))?;
+        file.read_exact(&mut data)?;
+
+        let path = directory.join(format!("track{}_sample{:06}.bin", track_id, sample.number));
+        fs::write(&path, &data)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
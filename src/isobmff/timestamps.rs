@@ -0,0 +1,116 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    isobmff::{r#box::IsobmffBox, content::IsobmffContent},
+    mkv::element::format_unix_time
+};
+
+/// Seconds between the Mac/ISOBMFF epoch (1904-01-01) and the Unix epoch (1970-01-01)
+pub const MAC_EPOCH_OFFSET: u64 = 2_082_844_800;
+
+/// Maximum spread between header timestamps before they are considered inconsistent (one year)
+const MAX_TIMESTAMP_SPREAD: u64 = 365 * 24 * 60 * 60;
+
+/// Creation/modification time pair taken from a mvhd, tkhd or mdhd box
+struct HeaderTimes
+{
+    box_type:          &'static str,
+    offset:            u64,
+    creation_time:     u64,
+    modification_time: u64
+}
+
+/// Mac-epoch timestamp as raw seconds and ISO-8601 UTC date, e.g. "3786912000 (2024-01-01T00:00:00Z)"
+///
+/// Zero means the time was never set and is shown as such instead of as 1904-01-01.
+pub fn format_mac_time(value: u64) -> String
+{
+    match value
+    {
+        | 0 => tr("0 (not set)").to_string(),
+        | value => format!("{} ({})", value, format_unix_time(value as i64 - MAC_EPOCH_OFFSET as i64))
+    }
+}
+
+/// Check mvhd/tkhd/mdhd timestamps for implausible values and report findings
+///
+/// Zero timestamps, dates before 1970 or in the future, modification before creation
+/// and creation times that differ wildly between headers often reveal re-muxed or doctored files.
+pub fn check_timestamps(boxes: &[IsobmffBox], diagnostics: &mut Diagnostics)
+{
+    let mut times = Vec::new();
+    collect_header_times(boxes, &mut times);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) + MAC_EPOCH_OFFSET;
+
+    for entry in &times
+    {
+        check_single_timestamp(entry, "creation time", entry.creation_time, now, diagnostics);
+        check_single_timestamp(entry, "modification time", entry.modification_time, now, diagnostics);
+
+        if entry.creation_time != 0 && entry.modification_time != 0 && entry.modification_time < entry.creation_time
+        {
+            diagnostics.warning(
+                Some(entry.offset),
+                tr_fmt("{} modification time ({}) is earlier than its creation time ({})", &[&entry.box_type, &entry.modification_time, &entry.creation_time])
+            );
+        }
+    }
+
+    // Compare creation times across headers (ignoring unset values)
+    let set_times: Vec<&HeaderTimes> = times.iter().filter(|entry| entry.creation_time != 0).collect();
+    if let (Some(earliest), Some(latest)) = (set_times.iter().min_by_key(|entry| entry.creation_time), set_times.iter().max_by_key(|entry| entry.creation_time)) &&
+        latest.creation_time - earliest.creation_time > MAX_TIMESTAMP_SPREAD
+    {
+        diagnostics.warning(
+            Some(latest.offset),
+            tr_fmt("{} creation time differs from {} at {} by {} days (possible re-mux or edited file)", &[
+                &latest.box_type,
+                &earliest.box_type,
+                &format!("0x{:08X}", earliest.offset),
+                &((latest.creation_time - earliest.creation_time) / 86400)
+            ])
+        );
+    }
+}
+
+/// Check a single Mac-epoch timestamp value
+fn check_single_timestamp(entry: &HeaderTimes, kind: &'static str, value: u64, now: u64, diagnostics: &mut Diagnostics)
+{
+    if value == 0
+    {
+        diagnostics.info(Some(entry.offset), tr_fmt("{} {} is not set (0)", &[&entry.box_type, &tr(kind)]));
+    }
+    else if value < MAC_EPOCH_OFFSET
+    {
+        diagnostics.warning(Some(entry.offset), tr_fmt("{} {} {} lies before 1970", &[&entry.box_type, &tr(kind), &format_mac_time(value)]));
+    }
+    else if value > now
+    {
+        diagnostics.warning(Some(entry.offset), tr_fmt("{} {} {} lies in the future", &[&entry.box_type, &tr(kind), &format_mac_time(value)]));
+    }
+}
+
+/// Recursively collect header timestamps from the box tree
+fn collect_header_times(boxes: &[IsobmffBox], times: &mut Vec<HeaderTimes>)
+{
+    for isobmff_box in boxes
+    {
+        let entry = match &isobmff_box.content
+        {
+            | Some(IsobmffContent::MovieHeader(mvhd)) => Some(("mvhd", mvhd.creation_time, mvhd.modification_time)),
+            | Some(IsobmffContent::TrackHeader(tkhd)) => Some(("tkhd", tkhd.creation_time, tkhd.modification_time)),
+            | Some(IsobmffContent::MediaHeader(mdhd)) => Some(("mdhd", mdhd.creation_time, mdhd.modification_time)),
+            | _ => None
+        };
+
+        if let Some((box_type, creation_time, modification_time)) = entry
+        {
+            times.push(HeaderTimes { box_type, offset: isobmff_box.offset, creation_time, modification_time });
+        }
+
+        collect_header_times(&isobmff_box.children, times);
+    }
+}
@@ -0,0 +1,524 @@
+use std::{
+    io::{Read, SeekFrom},
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    artwork::{Artwork, itunes_mime_type},
+    chapters::Chapter,
+    diagnostics::Diagnostics,
+    dissector_registry::SNIFF_SIZE,
+    ffprobe::ProbeFormat,
+    find::{Occurrence, StructureKind},
+    i18n::{tr, tr_fmt},
+    id3v1::genre_by_index,
+    isobmff::{
+        r#box::IsobmffBox,
+        chapter_track::{ChapterTrack, read_chapter_track},
+        content::{FragmentContext, IsobmffContent},
+        dissector::{IsobmffDissector, leading_junk},
+        itunes_metadata::ItunesContent,
+        probe::{probe_boxes, summarize_tracks},
+        sample_stats::TrackSampleStats,
+        samples::{fragment_header, track_id},
+        timestamps::check_timestamps,
+        truncation::check_truncation
+    },
+    layout::Layout,
+    loudness::Loudness,
+    media_reader::MediaReader,
+    media_source::{self, MediaSource},
+    metadata::{MediaMetadata, SetPosition},
+    options::DissectOptions,
+    path_filter::{PathFilter, PathMatch},
+    summary::TrackSummary,
+    visitor::DissectionVisitor
+};
+
+/// Parsed box hierarchy of an ISOBMFF stream
+#[derive(Debug, Clone)]
+pub struct IsobmffTree
+{
+    /// Top-level boxes in file order
+    pub boxes:      Vec<IsobmffBox>,
+    /// Offset just past the box stream
+    pub end_offset: u64
+}
+
+impl IsobmffTree
+{
+    /// Parse all boxes from `start_offset` to the end of the file and run plausibility checks
+    pub fn read(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        Self::read_with_options(file, start_offset, &DissectOptions::default(), diagnostics)
+    }
+
+    /// Parse all boxes like `read`, keeping at most `options.max_table_entries` entries per sample table
+    ///
+    /// Truncated boxes are reported with the number of missing bytes. With `options.recover` set,
+    /// corrupt box headers are reported as warnings and parsing resumes at the next plausible box
+    /// header instead of failing.
+    pub fn read_with_options(
+        file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+
+        // Junk in front of the file type box (e.g. of a damaged download) is skipped
+        let mut header = Vec::new();
+        file.seek(SeekFrom::Start(start_offset))?;
+        (&mut *file).take(SNIFF_SIZE as u64).read_to_end(&mut header)?;
+        let start_offset = match leading_junk(&header, file_size - start_offset)
+        {
+            | Some(junk) =>
+            {
+                let box_offset = start_offset + junk as u64;
+                diagnostics.warning(
+                    Some(start_offset),
+                    tr_fmt("{} bytes of unrecognized data before the file type box at {} skipped", &[&junk, &format!("0x{:08X}", box_offset)])
+                );
+                box_offset
+            }
+            | None => start_offset
+        };
+
+        // Parse all boxes (the box stream runs to the end of the file)
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut findings = Vec::new();
+        let mut boxes = IsobmffDissector::parse_boxes_recovering(&mut reader, start_offset, file_size, 0, options.max_table_entries, options.recover, &mut findings)
+            .map_err(|e| tr_fmt("Failed to parse ISOBMFF boxes: {}", &[&e]))?;
+        for (offset, message) in findings
+        {
+            diagnostics.warning(Some(offset), message);
+        }
+        check_truncation(&boxes, file_size, diagnostics);
+
+        // Edit list durations are in movie timescale units
+        for moov in boxes.iter_mut().filter(|isobmff_box| isobmff_box.box_type == "moov")
+        {
+            let movie_timescale = match moov.children.iter().find(|child| child.box_type == "mvhd").and_then(|mvhd| mvhd.content.as_ref())
+            {
+                | Some(IsobmffContent::MovieHeader(movie_header)) => Some(movie_header.timescale),
+                | _ => None
+            };
+            set_movie_timescale(&mut moov.children, movie_timescale);
+        }
+
+        // Track fragments take their timescale and sample defaults from the movie box, or from the initialization segment of a media segment
+        let tracks = boxes
+            .iter()
+            .find(|isobmff_box| isobmff_box.box_type == "moov")
+            .or(options.init_segment.as_ref().map(|init_segment| &init_segment.moov))
+            .map(track_contexts);
+        match tracks
+        {
+            | Some(tracks) => set_fragment_context(&mut boxes, &tracks, diagnostics),
+            | None if boxes.iter().any(|isobmff_box| isobmff_box.box_type == "moof") == true =>
+            {
+                diagnostics
+                    .info(None, tr("Media segment without movie box: timescales and trex sample defaults are unknown (pass the initialization segment with --init)"));
+            }
+            | None => ()
+        }
+
+        // Plausibility checks on header timestamps
+        check_timestamps(&boxes, diagnostics);
+
+        Ok(Self { boxes, end_offset: file_size })
+    }
+
+    /// Find a box by its path of box types (e.g. "moov/trak/mdia/hdlr"), following the first match on each level
+    pub fn find(&self, path: &str) -> Option<&IsobmffBox>
+    {
+        let mut types = path.split('/');
+        let first = types.next()?;
+        let mut current = self.boxes.iter().find(|isobmff_box| isobmff_box.box_type == first)?;
+        for box_type in types
+        {
+            current = current.children.iter().find(|child| child.box_type == box_type)?;
+        }
+        Some(current)
+    }
+
+    /// Pictures of all iTunes cover art (`covr`) boxes, read from the file since large images are not kept in the tree
+    pub fn artwork(&self, file: &mut dyn MediaSource) -> Result<Vec<Artwork>, Box<dyn std::error::Error>>
+    {
+        let mut artwork = Vec::new();
+        collect_cover_art(&self.boxes, "", file, &mut artwork)?;
+        Ok(artwork)
+    }
+
+    /// Text track referenced by a `chap` track reference, with its chapters
+    pub fn chapter_track(&self, file: &mut dyn MediaSource) -> Result<Option<ChapterTrack>, Box<dyn std::error::Error>>
+    {
+        read_chapter_track(self, file)
+    }
+
+    /// Chapters of the text track referenced by a `chap` track reference
+    pub fn chapters(&self, file: &mut dyn MediaSource) -> Result<Vec<Chapter>, Box<dyn std::error::Error>>
+    {
+        Ok(self.chapter_track(file)?.map(|track| track.chapters).unwrap_or_default())
+    }
+
+    /// Normalized metadata from the iTunes and QuickTime metadata items, cover art and chapter track
+    pub fn metadata(&self, file: &mut dyn MediaSource) -> Result<MediaMetadata, Box<dyn std::error::Error>>
+    {
+        let mut metadata = MediaMetadata { artwork: self.artwork(file)?, chapters: self.chapters(file)?, ..Default::default() };
+        add_item_metadata(&self.boxes, &mut metadata);
+        Ok(metadata)
+    }
+
+    /// Sample table statistics of all tracks
+    pub fn sample_stats(&self) -> Vec<TrackSampleStats>
+    {
+        self.boxes
+            .iter()
+            .filter(|isobmff_box| isobmff_box.box_type == "moov")
+            .flat_map(|moov| moov.children.iter().filter(|child| child.box_type == "trak"))
+            .filter_map(TrackSampleStats::from_track)
+            .collect()
+    }
+
+    /// One summary line per track (codec, duration, language, picture size or audio format)
+    pub fn track_summaries(&self) -> Vec<TrackSummary>
+    {
+        summarize_tracks(&self.boxes)
+    }
+
+    /// Describe the streams in ffprobe terms
+    pub fn probe(&self) -> ProbeFormat
+    {
+        probe_boxes(&self.boxes)
+    }
+
+    /// Boxes of type `code` anywhere in the tree, with their paths
+    pub fn occurrences(&self, code: &str) -> Vec<Occurrence>
+    {
+        let mut occurrences = Vec::new();
+        find_boxes(&self.boxes, code, "", &mut occurrences);
+        occurrences
+    }
+
+    /// Call `visitor` for all boxes in file order, with the data of the leaf boxes that was read
+    pub fn visit(&self, visitor: &mut dyn DissectionVisitor)
+    {
+        visit_boxes(&self.boxes, 0, visitor);
+    }
+
+    /// Add all boxes to `layout`, nested by their depth in the tree
+    pub fn add_layout(&self, layout: &mut Layout)
+    {
+        add_box_layout(&self.boxes, 0, layout);
+    }
+
+    /// Copy of the tree with only the boxes selected by a `--filter` and their ancestors
+    pub fn filtered(&self, filter: &PathFilter) -> IsobmffTree
+    {
+        IsobmffTree { boxes: select_boxes(&self.boxes, filter, &mut Vec::new()), end_offset: self.end_offset }
+    }
+
+    /// Structured representation for JSON reports, with the text output lines of parsed box contents if `details` is set
+    pub fn to_json(&self, details: bool) -> serde_json::Value
+    {
+        serde_json::json!({ "boxes": self.boxes.iter().map(|isobmff_box| isobmff_box.to_json(details)).collect::<Vec<_>>() })
+    }
+}
+
+/// Movie box of an initialization segment, giving a media segment dissected on its own the timescales and sample defaults of its tracks
+#[derive(Debug, Clone)]
+pub struct InitSegment
+{
+    pub path: PathBuf,
+    pub moov: IsobmffBox
+}
+
+impl InitSegment
+{
+    /// Read the movie box of the initialization segment at `path`
+    pub fn read(path: &Path) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let mut file = media_source::open(path)?;
+        let tree = IsobmffTree::read(file.as_mut(), 0, &mut Diagnostics::new())?;
+        let moov = tree
+            .boxes
+            .into_iter()
+            .find(|isobmff_box| isobmff_box.box_type == "moov")
+            .ok_or_else(|| tr_fmt("No moov box found in the initialization segment {}", &[&path.display()]))?;
+        Ok(Self { path: path.to_path_buf(), moov })
+    }
+}
+
+/// Add the boxes of type `code` below `boxes` to `occurrences`
+fn find_boxes(boxes: &[IsobmffBox], code: &str, parent_path: &str, occurrences: &mut Vec<Occurrence>)
+{
+    for isobmff_box in boxes
+    {
+        let path = if parent_path.is_empty() == true
+        {
+            isobmff_box.box_type.clone()
+        }
+        else
+        {
+            format!("{}/{}", parent_path, isobmff_box.box_type)
+        };
+        if isobmff_box.box_type == code
+        {
+            occurrences.push(Occurrence { offset: isobmff_box.offset, size: Some(isobmff_box.size), kind: StructureKind::Box, path: Some(path.clone()) });
+        }
+        find_boxes(&isobmff_box.children, code, &path, occurrences);
+    }
+}
+
+/// Call `visitor` for `boxes` at nesting level `depth` and everything below them
+fn visit_boxes(boxes: &[IsobmffBox], depth: usize, visitor: &mut dyn DissectionVisitor)
+{
+    for isobmff_box in boxes
+    {
+        visitor.on_box_start(isobmff_box, depth);
+        if isobmff_box.data.is_empty() == false
+        {
+            visitor.on_data(isobmff_box.offset + isobmff_box.header_size, &isobmff_box.data);
+        }
+        visit_boxes(&isobmff_box.children, depth + 1, visitor);
+        visitor.on_box_end(isobmff_box, depth);
+    }
+}
+
+/// Add `boxes` at nesting level `depth` and everything below them to `layout`
+fn add_box_layout(boxes: &[IsobmffBox], depth: usize, layout: &mut Layout)
+{
+    for isobmff_box in boxes
+    {
+        layout.add(isobmff_box.offset, isobmff_box.size, depth, isobmff_box.box_type.clone());
+        add_box_layout(&isobmff_box.children, depth + 1, layout);
+    }
+}
+
+/// Copy the boxes selected by `filter` with everything below them, and the ancestors of selected boxes
+fn select_boxes(boxes: &[IsobmffBox], filter: &PathFilter, path: &mut Vec<String>) -> Vec<IsobmffBox>
+{
+    let mut selected = Vec::new();
+    for isobmff_box in boxes
+    {
+        path.push(isobmff_box.box_type.clone());
+        match filter.check(path)
+        {
+            | PathMatch::Selected => selected.push(isobmff_box.clone()),
+            | PathMatch::Ancestor =>
+            {
+                let children = select_boxes(&isobmff_box.children, filter, path);
+                if children.is_empty() == false
+                {
+                    let mut ancestor = isobmff_box.clone();
+                    ancestor.children = children;
+                    selected.push(ancestor);
+                }
+            }
+            | PathMatch::None => ()
+        }
+        path.pop();
+    }
+    selected
+}
+
+/// Attach the movie timescale to all edit lists below `boxes`
+fn set_movie_timescale(boxes: &mut [IsobmffBox], movie_timescale: Option<u32>)
+{
+    for isobmff_box in boxes
+    {
+        if let Some(IsobmffContent::EditList(edit_list)) = isobmff_box.content.as_mut()
+        {
+            edit_list.movie_timescale = movie_timescale;
+        }
+        set_movie_timescale(&mut isobmff_box.children, movie_timescale);
+    }
+}
+
+/// Timescale and trex sample defaults of each track of `moov`, by track ID
+fn track_contexts(moov: &IsobmffBox) -> Vec<(u32, FragmentContext)>
+{
+    let mvex = moov.find("mvex");
+    moov.children
+        .iter()
+        .filter(|child| child.box_type == "trak")
+        .map(|trak| {
+            let id = track_id(trak);
+            let timescale = match trak.find("mdia/mdhd").and_then(|mdhd| mdhd.content.as_ref())
+            {
+                | Some(IsobmffContent::MediaHeader(media_header)) => Some(media_header.timescale),
+                | _ => None
+            };
+            let track_extends = mvex.and_then(|mvex| {
+                mvex.children.iter().find_map(|trex| match trex.content
+                {
+                    | Some(IsobmffContent::TrackExtends(ref track_extends)) if track_extends.track_id == id => Some(track_extends),
+                    | _ => None
+                })
+            });
+            let context = FragmentContext {
+                timescale,
+                default_sample_duration: track_extends.map(|trex| trex.default_sample_duration),
+                default_sample_size: track_extends.map(|trex| trex.default_sample_size)
+            };
+            (id, context)
+        })
+        .collect()
+}
+
+/// Attach the timescale and sample defaults of their track to the tfdt and trun boxes of all movie fragments in `boxes`
+fn set_fragment_context(boxes: &mut [IsobmffBox], tracks: &[(u32, FragmentContext)], diagnostics: &mut Diagnostics)
+{
+    for moof in boxes.iter_mut().filter(|isobmff_box| isobmff_box.box_type == "moof")
+    {
+        for traf in moof.children.iter_mut().filter(|child| child.box_type == "traf")
+        {
+            let Some((id, default_sample_duration, default_sample_size)) =
+                fragment_header(traf).map(|tfhd| (tfhd.track_id, tfhd.default_sample_duration, tfhd.default_sample_size))
+            else
+            {
+                continue;
+            };
+            let Some(&(_, track)) = tracks.iter().find(|(track_id, _)| *track_id == id)
+            else
+            {
+                diagnostics.warning(Some(traf.offset), tr_fmt("Track fragment of track {} has no matching track in the movie box", &[&id]));
+                continue;
+            };
+
+            // Defaults of the fragment header take precedence over those of the track
+            let context = FragmentContext {
+                timescale:               track.timescale,
+                default_sample_duration: default_sample_duration.or(track.default_sample_duration),
+                default_sample_size:     default_sample_size.or(track.default_sample_size)
+            };
+            for child in &mut traf.children
+            {
+                match child.content.as_mut()
+                {
+                    | Some(IsobmffContent::TrackFragmentDecodeTime(tfdt)) => tfdt.timescale = context.timescale,
+                    | Some(IsobmffContent::TrackRun(run)) => run.context = Some(context),
+                    | _ => ()
+                }
+            }
+        }
+    }
+}
+
+/// Add the images of the `data` boxes inside `covr` boxes to `artwork` (a `covr` box may hold several)
+fn collect_cover_art(boxes: &[IsobmffBox], prefix: &str, file: &mut dyn MediaSource, artwork: &mut Vec<Artwork>) -> Result<(), Box<dyn std::error::Error>>
+{
+    for isobmff_box in boxes
+    {
+        let path = format!("{}{}", prefix, isobmff_box.box_type);
+        if isobmff_box.box_type != "covr"
+        {
+            collect_cover_art(&isobmff_box.children, &format!("{}/", path), file, artwork)?;
+            continue;
+        }
+
+        for data_box in isobmff_box.children.iter().filter(|child| child.box_type == "data" && child.data_size() >= 8)
+        {
+            // Version/flags (type indicator in the last flag byte) and reserved locale, then the image
+            let mut header = [0u8; 8];
+            file.seek(SeekFrom::Start(data_box.offset + data_box.header_size))?;
+            file.read_exact(&mut header)?;
+            let mut data = vec![0u8; (data_box.data_size() - 8) as usize];
+            file.read_exact(&mut data)?;
+
+            artwork.push(Artwork { location: path.clone(), mime_type: itunes_mime_type(header[3]).to_string(), description: "Cover Art".to_string(), data });
+        }
+    }
+
+    Ok(())
+}
+
+/// Map the items of all `ilst` boxes below `boxes` to the normalized metadata
+fn add_item_metadata(boxes: &[IsobmffBox], metadata: &mut MediaMetadata)
+{
+    for isobmff_box in boxes
+    {
+        if isobmff_box.box_type != "ilst"
+        {
+            add_item_metadata(&isobmff_box.children, metadata);
+            continue;
+        }
+
+        for item in &isobmff_box.children
+        {
+            let value = match item.itunes_content.as_ref().map(|itunes| &itunes.content)
+            {
+                | Some(ItunesContent::Text(text)) => Some(text.clone()),
+                | Some(ItunesContent::Integer(value)) => Some(value.to_string()),
+                | Some(ItunesContent::UnsignedInteger(value)) => Some(value.to_string()),
+                | _ => None
+            };
+            let position = match item.itunes_content.as_ref().map(|itunes| &itunes.content)
+            {
+                | Some(ItunesContent::TrackNumber { track, total_tracks }) if *track > 0 =>
+                    Some(SetPosition { number: *track as u32, total: Some(*total_tracks as u32).filter(|&total| total > 0) }),
+                | Some(ItunesContent::DiskNumber { disk, total_disks }) if *disk > 0 =>
+                    Some(SetPosition { number: *disk as u32, total: Some(*total_disks as u32).filter(|&total| total > 0) }),
+                | _ => None
+            };
+
+            // QuickTime metadata items are named by their key, iTunes items by their box type
+            let key = item.key_name.as_deref().unwrap_or(item.box_type.as_str());
+            let field = match key
+            {
+                | "©nam" | "com.apple.quicktime.title" => Some(&mut metadata.title),
+                | "©ART" | "com.apple.quicktime.artist" => Some(&mut metadata.artist),
+                | "©alb" | "com.apple.quicktime.album" => Some(&mut metadata.album),
+                | "aART" => Some(&mut metadata.album_artist),
+                | "©day" | "com.apple.quicktime.creationdate" => Some(&mut metadata.date),
+                | "©gen" | "com.apple.quicktime.genre" => Some(&mut metadata.genre),
+                | "©cmt" | "com.apple.quicktime.comment" => Some(&mut metadata.comment),
+                | _ => None
+            };
+
+            match (key, field)
+            {
+                | (_, Some(field)) => *field = field.take().or(value),
+                | ("trkn", None) => metadata.track = metadata.track.or(position),
+                | ("disk", None) => metadata.disc = metadata.disc.or(position),
+                // Predefined genres are stored as ID3v1 genre index + 1
+                | ("gnre", None) =>
+                {
+                    let genre = value.and_then(|index| index.parse::<usize>().ok()).and_then(|index| index.checked_sub(1)).and_then(genre_by_index);
+                    metadata.genre = metadata.genre.take().or(genre.map(str::to_string));
+                }
+                | ("----", None) =>
+                {
+                    if let Some(value) = value
+                    {
+                        let key = freeform_key(item);
+                        metadata.loudness.extend(Loudness::from_field(&key, &value));
+                        metadata.add_custom(key, value);
+                    }
+                }
+                | ("covr", None) => (),
+                | (key, None) =>
+                {
+                    if let Some(value) = value
+                    {
+                        metadata.add_custom(key, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Key of a freeform (`----`) item: "mean:name", e.g. "com.apple.iTunes:iTunSMPB"
+fn freeform_key(item: &IsobmffBox) -> String
+{
+    // mean and name boxes hold version/flags followed by the string
+    let string = |box_type: &str| {
+        item.children
+            .iter()
+            .find(|child| child.box_type == box_type)
+            .and_then(|child| child.data.get(4..))
+            .map(|data| String::from_utf8_lossy(data).to_string())
+            .unwrap_or_default()
+    };
+    format!("{}:{}", string("mean"), string("name"))
+}
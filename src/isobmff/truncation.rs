@@ -0,0 +1,108 @@
+use crate::{
+    diagnostics::Diagnostics,
+    i18n::tr_fmt,
+    isobmff::{r#box::IsobmffBox, content::IsobmffContent}
+};
+
+/// Report truncated boxes and the structures an interrupted file no longer reaches
+///
+/// Each outermost truncated box is reported with its declared and present size, naming the
+/// innermost box the cut runs through. If the file itself ends early, the movie and media data
+/// boxes it never reaches and chunks of sample data beyond its end are reported as well.
+pub fn check_truncation(boxes: &[IsobmffBox], file_size: u64, diagnostics: &mut Diagnostics)
+{
+    report_truncated_boxes(boxes, "", diagnostics);
+
+    // Only a file that ends inside a box or box header was cut short, other truncations are corrupt sizes
+    let ends_early = boxes.last().is_some_and(|last| last.missing_bytes > 0 || last.offset + last.size < file_size);
+    if ends_early == false
+    {
+        return;
+    }
+
+    // Fragmented files carry their sample tables and data in moof/mdat pairs instead
+    let has_type = |box_type: &str| boxes.iter().any(|isobmff_box| isobmff_box.box_type == box_type);
+    let unreached: Vec<&str> = ["moov", "mdat"].into_iter().filter(|&box_type| has_type(box_type) == false && has_type("moof") == false).collect();
+    if unreached.is_empty() == false
+    {
+        diagnostics.error(Some(file_size), tr_fmt("Top-level boxes not reached before the end of the file: {}", &[&unreached.join(", ")]));
+    }
+
+    for trak in boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "moov").flat_map(|moov| moov.children.iter()).filter(|child| child.box_type == "trak")
+    {
+        check_chunk_offsets(trak, file_size, diagnostics);
+    }
+}
+
+/// Report the outermost truncated boxes, not their truncated descendants
+fn report_truncated_boxes(boxes: &[IsobmffBox], parent_path: &str, diagnostics: &mut Diagnostics)
+{
+    for isobmff_box in boxes
+    {
+        let path = match parent_path
+        {
+            | "" => isobmff_box.box_type.clone(),
+            | _ => format!("{}/{}", parent_path, isobmff_box.box_type)
+        };
+
+        if isobmff_box.missing_bytes == 0
+        {
+            report_truncated_boxes(&isobmff_box.children, &path, diagnostics);
+            continue;
+        }
+
+        let mut message = tr_fmt("Box '{}' is truncated: declared size {} bytes, {} bytes present, {} bytes missing", &[
+            &path,
+            &isobmff_box.declared_size(),
+            &isobmff_box.size,
+            &isobmff_box.missing_bytes
+        ]);
+        let innermost = innermost_truncated_path(isobmff_box, &path);
+        if innermost != path
+        {
+            message = tr_fmt("{} (cut off inside {})", &[&message, &innermost]);
+        }
+        diagnostics.error(Some(isobmff_box.offset), message);
+    }
+}
+
+/// Path of the deepest truncated box below (and including) `isobmff_box`
+fn innermost_truncated_path(isobmff_box: &IsobmffBox, path: &str) -> String
+{
+    match isobmff_box.children.iter().find(|child| child.missing_bytes > 0)
+    {
+        | Some(child) => innermost_truncated_path(child, &format!("{}/{}", path, child.box_type)),
+        | None => path.to_string()
+    }
+}
+
+/// Report the chunks of a track whose sample data starts beyond the end of the file
+fn check_chunk_offsets(trak: &IsobmffBox, file_size: u64, diagnostics: &mut Diagnostics)
+{
+    let Some(stbl) = trak.find("mdia/minf/stbl")
+    else
+    {
+        return;
+    };
+    let chunk_offsets: Vec<u64> = stbl
+        .children
+        .iter()
+        .find_map(|child| match child.content.as_ref()
+        {
+            | Some(IsobmffContent::ChunkOffset(chunk_offset)) => Some(chunk_offset.chunk_offsets.iter().map(|&offset| offset as u64).collect()),
+            | Some(IsobmffContent::ChunkOffset64(chunk_offset)) => Some(chunk_offset.chunk_offsets.clone()),
+            | _ => None
+        })
+        .unwrap_or_default();
+
+    let unreachable = chunk_offsets.iter().filter(|&&offset| offset >= file_size).count();
+    if unreachable > 0
+    {
+        let track_id = match trak.find("tkhd").and_then(|tkhd| tkhd.content.as_ref())
+        {
+            | Some(IsobmffContent::TrackHeader(track_header)) => track_header.track_id.to_string(),
+            | _ => "?".to_string()
+        };
+        diagnostics.error(Some(trak.offset), tr_fmt("Track {}: {} of {} chunks start beyond the end of the file", &[&track_id, &unreachable, &chunk_offsets.len()]));
+    }
+}
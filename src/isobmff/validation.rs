@@ -0,0 +1,242 @@
+use crate::{
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    isobmff::{
+        r#box::IsobmffBox,
+        boxes::{file_type::is_known_brand, segment_index::SegmentIndexBox},
+        content::IsobmffContent,
+        fragments::{TrackFragment, track_fragments},
+        tree::IsobmffTree
+    }
+};
+
+/// Mandatory child boxes by parent box type (alternatives separated by '|')
+const MANDATORY_CHILDREN: [(&str, &[&str]); 6] = [
+    ("moov", &["mvhd"]),
+    ("trak", &["tkhd", "mdia"]),
+    ("mdia", &["mdhd", "hdlr", "minf"]),
+    ("minf", &["dinf", "stbl"]),
+    ("stbl", &["stsd", "stts", "stsc", "stsz|stz2", "stco|co64"]),
+    ("moof", &["mfhd"])
+];
+
+/// Check the presence and count of the boxes ISO/IEC 14496-12 requires and report findings
+pub fn check_mandatory_boxes(boxes: &[IsobmffBox], diagnostics: &mut Diagnostics)
+{
+    let count = |box_type: &str| boxes.iter().filter(|isobmff_box| isobmff_box.box_type == box_type).count();
+
+    match boxes.iter().position(|isobmff_box| isobmff_box.box_type == "ftyp")
+    {
+        | None => diagnostics.warning(boxes.first().map(|isobmff_box| isobmff_box.offset), tr("No ftyp box (file type compatibility is unknown)")),
+        | Some(index) if boxes[..index].iter().any(|isobmff_box| isobmff_box.box_type != "free" && isobmff_box.box_type != "skip") =>
+        {
+            diagnostics.warning(Some(boxes[index].offset), tr("ftyp box is not the first significant box"));
+        }
+        | Some(_) => ()
+    }
+    if let Some(ftyp) = boxes.iter().find(|isobmff_box| isobmff_box.box_type == "ftyp") &&
+        let Some(IsobmffContent::FileType(file_type)) = &ftyp.content &&
+        is_known_brand(&file_type.major_brand) == false
+    {
+        diagnostics.info(Some(ftyp.offset), tr_fmt("Unknown major brand '{}' (dissected as generic ISOBMFF)", &[&file_type.major_brand]));
+    }
+    if count("ftyp") > 1
+    {
+        diagnostics.error(None, tr_fmt("{} {} boxes at top level, at most one is allowed", &[&count("ftyp"), &"ftyp"]));
+    }
+
+    // Fragmented segments carry their track setup in a separate initialization segment, image files in meta
+    let moov_count = count("moov");
+    if moov_count > 1
+    {
+        diagnostics.error(None, tr_fmt("{} {} boxes at top level, at most one is allowed", &[&moov_count, &"moov"]));
+    }
+    else if moov_count == 0 && count("moof") == 0 && count("meta") == 0
+    {
+        diagnostics.error(None, tr("No moov box (movie header and track setup are missing)"));
+    }
+    if count("meta") > 0 && count("moov") == 0
+    {
+        for meta in boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "meta")
+        {
+            check_children(meta, &["hdlr"], diagnostics);
+        }
+    }
+
+    check_box_tree(boxes, diagnostics);
+}
+
+/// Check the mandatory children of all boxes below `boxes`
+fn check_box_tree(boxes: &[IsobmffBox], diagnostics: &mut Diagnostics)
+{
+    for isobmff_box in boxes
+    {
+        if let Some((_, required)) = MANDATORY_CHILDREN.iter().find(|(parent, _)| *parent == isobmff_box.box_type)
+        {
+            check_children(isobmff_box, required, diagnostics);
+        }
+
+        match isobmff_box.box_type.as_str()
+        {
+            | "moov" if isobmff_box.children.iter().any(|child| child.box_type == "trak") == false =>
+            {
+                diagnostics.warning(Some(isobmff_box.offset), tr("moov box has no tracks"));
+            }
+            | "mdia" => check_media_header(isobmff_box, diagnostics),
+            | "traf" => check_children(isobmff_box, &["tfhd"], diagnostics),
+            | _ => ()
+        }
+
+        check_box_tree(&isobmff_box.children, diagnostics);
+    }
+}
+
+/// Report missing children of `parent`, each entry may list alternatives separated by '|'
+fn check_children(parent: &IsobmffBox, required: &[&str], diagnostics: &mut Diagnostics)
+{
+    for alternatives in required
+    {
+        if alternatives.split('|').any(|box_type| parent.children.iter().any(|child| child.box_type == box_type)) == false
+        {
+            diagnostics.error(Some(parent.offset), tr_fmt("{} box is missing its mandatory {} box", &[&parent.box_type, &alternatives.replace('|', "/")]));
+        }
+    }
+}
+
+/// Check that the media information of a track has the media header box matching its handler
+fn check_media_header(mdia: &IsobmffBox, diagnostics: &mut Diagnostics)
+{
+    let handler_type = match mdia.find("hdlr").and_then(|hdlr| hdlr.content.as_ref())
+    {
+        | Some(IsobmffContent::Handler(handler)) => handler.handler_type.clone(),
+        | _ => return
+    };
+    let Some(minf) = mdia.find("minf")
+    else
+    {
+        return;
+    };
+
+    let expected = match handler_type.as_str()
+    {
+        | "vide" => "vmhd",
+        | "soun" => "smhd",
+        | "hint" => "hmhd",
+        | "subt" => "sthd",
+        | _ => return
+    };
+    if minf.children.iter().any(|child| child.box_type == expected) == false
+    {
+        diagnostics.warning(Some(minf.offset), tr_fmt("Track with handler '{}' has no {} media header box", &[&handler_type, &expected]));
+    }
+}
+
+/// Check the references of each top-level sidx box against the boxes that follow it and the durations of their track runs
+///
+/// Players seek by adding up the referenced sizes, so a size that does not end on the box boundary of a
+/// subsegment breaks seeking into everything after it, while wrong durations make them land at the wrong time.
+pub fn check_segment_indexes(tree: &IsobmffTree, diagnostics: &mut Diagnostics)
+{
+    let fragments = track_fragments(tree);
+    for sidx in tree.boxes.iter().filter(|isobmff_box| isobmff_box.box_type == "sidx")
+    {
+        if let Some(IsobmffContent::SegmentIndex(ref segment_index)) = sidx.content
+        {
+            check_segment_index(&tree.boxes, sidx, segment_index, &fragments, diagnostics);
+        }
+    }
+}
+
+/// Walk the references of `segment_index` through the top-level `boxes`
+fn check_segment_index(boxes: &[IsobmffBox], sidx: &IsobmffBox, segment_index: &SegmentIndexBox, fragments: &[TrackFragment], diagnostics: &mut Diagnostics)
+{
+    let mut position = sidx.offset + sidx.size + segment_index.first_offset;
+    for (index, reference) in segment_index.references.iter().enumerate()
+    {
+        let number = index + 1;
+        let Some(first) = boxes.iter().position(|isobmff_box| isobmff_box.offset == position)
+        else
+        {
+            // Without a box to start from the following references cannot be located either
+            diagnostics
+                .error(Some(sidx.offset), tr_fmt("sidx reference {} points to offset {}, where no top-level box starts", &[&number, &format!("0x{:08X}", position)]));
+            return;
+        };
+
+        let declared_end = position + reference.referenced_size as u64;
+        let mut end = position;
+        for isobmff_box in boxes[first..].iter().take_while(|isobmff_box| isobmff_box.offset < declared_end)
+        {
+            end = isobmff_box.offset + isobmff_box.size;
+        }
+        if end != declared_end
+        {
+            // Continue after the first fragment so that each wrong size is reported on its own
+            let actual_end = first_fragment_end(&boxes[first..]);
+            diagnostics.error(
+                Some(sidx.offset),
+                tr_fmt("sidx reference {} declares {} bytes, but the subsegment at offset {} spans {} bytes (up to the end of its first mdat box)", &[
+                    &number,
+                    &reference.referenced_size,
+                    &format!("0x{:08X}", position),
+                    &(actual_end - position)
+                ])
+            );
+            position = actual_end;
+            continue;
+        }
+
+        if reference.reference_type == true
+        {
+            if boxes[first].box_type != "sidx"
+            {
+                diagnostics.error(Some(sidx.offset), tr_fmt("sidx reference {} points to a {} box instead of a sidx box", &[&number, &boxes[first].box_type]));
+            }
+        }
+        else
+        {
+            check_subsegment_duration(sidx.offset, segment_index, number, reference.subsegment_duration, fragments, position..declared_end, diagnostics);
+        }
+        position = declared_end;
+    }
+}
+
+/// Compare the declared duration of a subsegment with the durations of the fragments of the referenced track in `range`
+fn check_subsegment_duration(
+    sidx_offset: u64, segment_index: &SegmentIndexBox, number: usize, declared: u32, fragments: &[TrackFragment], range: std::ops::Range<u64>,
+    diagnostics: &mut Diagnostics
+)
+{
+    let subsegment: Vec<&TrackFragment> =
+        fragments.iter().filter(|fragment| fragment.track_id == segment_index.reference_id && range.contains(&fragment.offset) == true).collect();
+    let Some(first) = subsegment.first()
+    else
+    {
+        return;
+    };
+    if segment_index.timescale == 0
+    {
+        return;
+    }
+
+    // Media segments without a movie box use the sidx timescale for their track runs as well
+    let timescale = first.timescale.filter(|&timescale| timescale > 0).unwrap_or(segment_index.timescale) as u128;
+    let duration: u64 = subsegment.iter().map(|fragment| fragment.duration).sum();
+    let scaled = ((duration as u128 * segment_index.timescale as u128 + timescale / 2) / timescale) as u64;
+    if scaled != declared as u64
+    {
+        diagnostics.warning(
+            Some(sidx_offset),
+            tr_fmt("sidx reference {} declares a duration of {}, but the track runs of track {} add up to {} (sidx timescale {})", &[
+                &number, &declared, &segment_index.reference_id, &scaled, &segment_index.timescale
+            ])
+        );
+    }
+}
+
+/// End of the first fragment of `boxes`: the end of the mdat box following its first moof box, or of the last box without one
+fn first_fragment_end(boxes: &[IsobmffBox]) -> u64
+{
+    let moof = boxes.iter().position(|isobmff_box| isobmff_box.box_type == "moof").unwrap_or(0);
+    boxes[moof..].iter().find(|isobmff_box| isobmff_box.box_type == "mdat").or(boxes.last()).map_or(0, |isobmff_box| isobmff_box.offset + isobmff_box.size)
+}
@@ -0,0 +1,744 @@
+use std::{fmt, io::SeekFrom, ops::Range, path::Path, str::FromStr};
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    id3v2::writer::EditResult,
+    media_source::{self, MediaSource},
+    rewrite
+};
+
+/// Boxes on the way to the iTunes metadata and the chunk offset tables, parsed into children when rewriting `moov`
+const CONTAINER_TYPES: [&[u8; 4]; 8] = [b"moov", b"trak", b"mdia", b"minf", b"stbl", b"udta", b"meta", b"ilst"];
+
+/// iTunes metadata item selected by `tag set`/`tag remove`: a four-character atom (e.g. ©nam, covr) or `----:<mean>:<name>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItunesKey
+{
+    /// Atom type as stored (© is the byte 0xA9)
+    Atom([u8; 4]),
+    /// Free-form item identified by its mean and name boxes, e.g. com.apple.iTunes and MOOD
+    FreeForm
+    {
+        mean: String, name: String
+    }
+}
+
+impl ItunesKey
+{
+    fn matches(&self, item: &RawBox) -> bool
+    {
+        match self
+        {
+            | ItunesKey::Atom(atom) => &item.box_type == atom,
+            | ItunesKey::FreeForm { mean, name } =>
+                &item.box_type == b"----" &&
+                    free_form_string(item, b"mean").is_some_and(|item_mean| item_mean == *mean) &&
+                    free_form_string(item, b"name").is_some_and(|item_name| item_name.eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+impl FromStr for ItunesKey
+{
+    type Err = String;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err>
+    {
+        if let Some(free_form) = key.strip_prefix("----:")
+        {
+            return match free_form.split_once(':')
+            {
+                | Some((mean, name)) if mean.is_empty() == false && name.is_empty() == false =>
+                    Ok(ItunesKey::FreeForm { mean: mean.to_string(), name: name.to_string() }),
+                | _ => Err(tr_fmt("Invalid free-form key '{}': expected ----:<mean>:<name>", &[&key]))
+            };
+        }
+
+        // Atom types are MacRoman, the © of the iTunes atoms is 0xA9 as in ISO-8859-1
+        let bytes: Vec<u8> = key.chars().filter_map(|character| u8::try_from(character as u32).ok()).collect();
+        match <[u8; 4]>::try_from(bytes.as_slice())
+        {
+            | Ok(atom) if key.chars().count() == 4 => Ok(ItunesKey::Atom(atom)),
+            | _ => Err(tr_fmt("Invalid iTunes item key '{}': expected four characters (e.g. ©nam) or ----:<mean>:<name>", &[&key]))
+        }
+    }
+}
+
+impl fmt::Display for ItunesKey
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | ItunesKey::Atom(atom) => write!(f, "{}", atom.iter().map(|&byte| byte as char).collect::<String>()),
+            | ItunesKey::FreeForm { mean, name } => write!(f, "----:{}:{}", mean, name)
+        }
+    }
+}
+
+/// One change to the iTunes metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItunesEdit
+{
+    /// Add the item or replace its value (and drop duplicates); the value of covr is the path of a JPEG or PNG image
+    Set(ItunesKey, String),
+    /// Remove every item the key matches
+    Remove(ItunesKey)
+}
+
+/// How the rewritten movie box was fitted into the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoovPlacement
+{
+    /// The size did not change, or nothing follows the movie box that it refers to
+    InPlace,
+    /// A `free` box inside or right after the movie box absorbed the size change
+    FreeSpace,
+    /// The data after the movie box moved by this many bytes and the chunk offsets were updated
+    Shifted
+    {
+        delta: i64, chunk_offsets: usize
+    }
+}
+
+/// Result of editing the iTunes metadata of a file
+#[derive(Debug, Clone)]
+pub struct ItunesEditReport
+{
+    /// Result of each edit, in the order given
+    pub results:   Vec<EditResult>,
+    /// Size of the written movie box, None if nothing changed and the file was left alone
+    pub moov_size: Option<u64>,
+    pub placement: MoovPlacement
+}
+
+/// Result of moving the movie box in front of the media data
+#[derive(Debug, Clone)]
+pub struct FaststartReport
+{
+    /// Whether the file was rewritten (false if the movie box already came first)
+    pub moved:         bool,
+    pub moov_size:     u64,
+    /// Number of updated chunk offsets
+    pub chunk_offsets: usize
+}
+
+/// Box of the movie box tree kept in memory for rewriting
+#[derive(Debug, Clone)]
+pub struct RawBox
+{
+    pub box_type: [u8; 4],
+    /// Version and flags in front of the children of full box containers (`meta`)
+    pub prefix:   Vec<u8>,
+    pub payload:  RawPayload
+}
+
+/// Content of a `RawBox`
+#[derive(Debug, Clone)]
+pub enum RawPayload
+{
+    /// Box data after the header, kept byte for byte
+    Data(Vec<u8>),
+    Children(Vec<RawBox>)
+}
+
+impl RawBox
+{
+    pub fn new(box_type: &[u8; 4], data: Vec<u8>) -> Self
+    {
+        Self { box_type: *box_type, prefix: Vec::new(), payload: RawPayload::Data(data) }
+    }
+
+    fn container(box_type: &[u8; 4], prefix: Vec<u8>) -> Self
+    {
+        Self { box_type: *box_type, prefix, payload: RawPayload::Children(Vec::new()) }
+    }
+
+    /// Parse the boxes in `data`, descending into the containers on the way to `ilst`, `stco` and `co64`
+    pub fn parse_all(data: &[u8]) -> Result<Vec<RawBox>, String>
+    {
+        let mut boxes = Vec::new();
+        let mut pos = 0;
+        while pos + 8 <= data.len()
+        {
+            let size32 = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as u64;
+            let box_type: [u8; 4] = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
+            let (size, header_size) = match size32
+            {
+                | 0 => ((data.len() - pos) as u64, 8),
+                | 1 if pos + 16 <= data.len() => (u64::from_be_bytes(data[pos + 8..pos + 16].try_into().unwrap_or_default()), 16),
+                | size => (size, 8)
+            };
+            if size < header_size || size > (data.len() - pos) as u64
+            {
+                return Err(tr_fmt("Box '{}' at position {} has an invalid size of {} bytes", &[&type_name(&box_type), &pos, &size]));
+            }
+
+            let body = &data[pos + header_size as usize..pos + size as usize];
+            boxes.push(Self::parse_body(box_type, body)?);
+            pos += size as usize;
+        }
+        if data[pos..].iter().any(|&byte| byte != 0) == true
+        {
+            return Err(tr_fmt("Unexpected data at position {} after the last box", &[&pos]));
+        }
+        Ok(boxes)
+    }
+
+    fn parse_body(box_type: [u8; 4], body: &[u8]) -> Result<Self, String>
+    {
+        if CONTAINER_TYPES.contains(&&box_type) == false
+        {
+            return Ok(Self::new(&box_type, body.to_vec()));
+        }
+
+        // ISO meta boxes are full boxes, QuickTime meta boxes start with their handler right away
+        let prefix_size = match &box_type == b"meta" && body.get(4..8) != Some(b"hdlr")
+        {
+            | true => 4.min(body.len()),
+            | false => 0
+        };
+        Ok(Self { box_type, prefix: body[..prefix_size].to_vec(), payload: RawPayload::Children(Self::parse_all(&body[prefix_size..])?) })
+    }
+
+    pub fn children(&self) -> &[RawBox]
+    {
+        match self.payload
+        {
+            | RawPayload::Children(ref children) => children,
+            | RawPayload::Data(_) => &[]
+        }
+    }
+
+    pub fn children_mut(&mut self) -> Option<&mut Vec<RawBox>>
+    {
+        match self.payload
+        {
+            | RawPayload::Children(ref mut children) => Some(children),
+            | RawPayload::Data(_) => None
+        }
+    }
+
+    /// First child of the given type
+    pub fn child_mut(&mut self, box_type: &[u8; 4]) -> Option<&mut RawBox>
+    {
+        self.children_mut()?.iter_mut().find(|child| &child.box_type == box_type)
+    }
+
+    /// Size of the serialized box
+    pub fn size(&self) -> u64
+    {
+        let body_size = self.prefix.len() as u64 +
+            match self.payload
+            {
+                | RawPayload::Data(ref data) => data.len() as u64,
+                | RawPayload::Children(ref children) => children.iter().map(RawBox::size).sum()
+            };
+        match body_size + 8 > u32::MAX as u64
+        {
+            | true => body_size + 16,
+            | false => body_size + 8
+        }
+    }
+
+    /// Append the serialized box to `output`, with a 64-bit size only where 32 bits do not suffice
+    pub fn write_to(&self, output: &mut Vec<u8>)
+    {
+        let size = self.size();
+        match size > u32::MAX as u64
+        {
+            | true =>
+            {
+                output.extend(1u32.to_be_bytes());
+                output.extend(self.box_type);
+                output.extend(size.to_be_bytes());
+            }
+            | false =>
+            {
+                output.extend((size as u32).to_be_bytes());
+                output.extend(self.box_type);
+            }
+        }
+        output.extend(&self.prefix);
+        match self.payload
+        {
+            | RawPayload::Data(ref data) => output.extend(data),
+            | RawPayload::Children(ref children) => children.iter().for_each(|child| child.write_to(output))
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8>
+    {
+        let mut output = Vec::with_capacity(self.size() as usize);
+        self.write_to(&mut output);
+        output
+    }
+
+    /// Add `delta` to every chunk offset in the `stco`/`co64` boxes below this box that lies within `range`
+    ///
+    /// Returns the number of changed offsets, or an error if an `stco` offset no longer fits into 32 bits.
+    pub fn shift_chunk_offsets(&mut self, range: Range<u64>, delta: i64) -> Result<usize, String>
+    {
+        let box_type = self.box_type;
+        match self.payload
+        {
+            | RawPayload::Children(ref mut children) =>
+            {
+                let mut count = 0;
+                for child in children
+                {
+                    count += child.shift_chunk_offsets(range.clone(), delta)?;
+                }
+                Ok(count)
+            }
+            | RawPayload::Data(ref mut data) if &box_type == b"stco" || &box_type == b"co64" =>
+            {
+                let entry_size = if &box_type == b"co64"
+                {
+                    8
+                }
+                else
+                {
+                    4
+                };
+                let mut count = 0;
+                for entry in data.get_mut(8..).unwrap_or_default().chunks_exact_mut(entry_size)
+                {
+                    let offset = match entry_size
+                    {
+                        | 8 => u64::from_be_bytes(entry.try_into().unwrap_or_default()),
+                        | _ => u32::from_be_bytes(entry.try_into().unwrap_or_default()) as u64
+                    };
+                    if range.contains(&offset) == false
+                    {
+                        continue;
+                    }
+
+                    let shifted = offset.checked_add_signed(delta).ok_or_else(|| tr("Chunk offset out of range after moving the media data").to_string())?;
+                    match entry_size
+                    {
+                        | 8 => entry.copy_from_slice(&shifted.to_be_bytes()),
+                        | _ => entry.copy_from_slice(
+                            &u32::try_from(shifted)
+                                .map_err(|_| tr("Chunk offsets would exceed 32 bits, the 'stco' table would have to become 'co64'").to_string())?
+                                .to_be_bytes()
+                        )
+                    }
+                    count += 1;
+                }
+                Ok(count)
+            }
+            | RawPayload::Data(_) => Ok(0)
+        }
+    }
+
+    /// Smallest chunk offset in the `stco`/`co64` boxes below this box, if any
+    pub fn first_chunk_offset(&self) -> Option<u64>
+    {
+        match self.payload
+        {
+            | RawPayload::Children(ref children) => children.iter().filter_map(RawBox::first_chunk_offset).min(),
+            | RawPayload::Data(ref data) if &self.box_type == b"stco" =>
+                data.get(8..)?.chunks_exact(4).map(|entry| u32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]) as u64).min(),
+            | RawPayload::Data(ref data) if &self.box_type == b"co64" =>
+                data.get(8..)?.chunks_exact(8).map(|entry| u64::from_be_bytes(entry.try_into().unwrap_or_default())).min(),
+            | RawPayload::Data(_) => None
+        }
+    }
+}
+
+/// Top-level box of a file
+#[derive(Debug, Clone)]
+pub struct TopLevelBox
+{
+    pub box_type:    [u8; 4],
+    pub offset:      u64,
+    pub size:        u64,
+    pub header_size: u64
+}
+
+impl TopLevelBox
+{
+    pub fn end(&self) -> u64
+    {
+        self.offset + self.size
+    }
+}
+
+/// Read the headers of the top-level boxes of `file`
+pub fn top_level_boxes(file: &mut dyn MediaSource) -> Result<Vec<TopLevelBox>, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+    let mut boxes = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= file_size
+    {
+        let mut header = [0u8; 16];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut header[..8])?;
+        let box_type = [header[4], header[5], header[6], header[7]];
+        let (size, header_size) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]])
+        {
+            | 0 => (file_size - offset, 8),
+            | 1 =>
+            {
+                file.read_exact(&mut header[8..])?;
+                (u64::from_be_bytes(header[8..16].try_into().unwrap_or_default()), 16)
+            }
+            | size => (size as u64, 8)
+        };
+        if size < header_size || offset + size > file_size
+        {
+            return Err(tr_fmt("Box '{}' at offset {} has an invalid size of {} bytes, the file is not rewritten", &[&type_name(&box_type), &offset, &size]).into());
+        }
+        boxes.push(TopLevelBox { box_type, offset, size, header_size });
+        offset += size;
+    }
+    Ok(boxes)
+}
+
+/// Whether the file at `path` starts like an ISOBMFF file (MP4, M4A, MOV, ...)
+pub fn is_isobmff_file(path: &Path) -> Result<bool, Box<dyn std::error::Error>>
+{
+    let mut file = media_source::open(path)?;
+    let mut header = [0u8; 12];
+    file.seek(SeekFrom::Start(0))?;
+    Ok(file.read_exact(&mut header).is_ok() == true && &header[4..8] == b"ftyp")
+}
+
+/// Apply `edits` to the iTunes metadata (`moov/udta/meta/ilst`) of the file at `path`
+///
+/// The movie box is rebuilt in memory with the missing `udta`/`meta`/`ilst` boxes added. A size
+/// change is absorbed by `free` boxes inside or right after the movie box if possible; otherwise
+/// the data after the movie box moves and the chunk offsets pointing there are updated. The
+/// file is rewritten through a temporary file (see `rewrite`).
+pub fn edit_file(path: &Path, edits: &[ItunesEdit]) -> Result<ItunesEditReport, Box<dyn std::error::Error>>
+{
+    let mut file = media_source::open(path)?;
+    let top_level = top_level_boxes(file.as_mut())?;
+    let moov_index = top_level.iter().position(|top| &top.box_type == b"moov").ok_or_else(|| tr("The file has no movie box ('moov')"))?;
+    let moov_box = &top_level[moov_index];
+    let mut moov = RawBox::parse_body(*b"moov", file.map(moov_box.offset, moov_box.size)?.get(moov_box.header_size as usize..).unwrap_or_default())?;
+
+    let results = edits.iter().map(|edit| apply(&mut moov, edit)).collect::<Result<Vec<_>, _>>()?;
+    if results.iter().all(|&result| result == EditResult::NotFound) == true
+    {
+        return Ok(ItunesEditReport { results, moov_size: None, placement: MoovPlacement::InPlace });
+    }
+
+    let mut delta = moov.size() as i64 - moov_box.size as i64;
+    let mut placement = MoovPlacement::InPlace;
+    let mut replaced_end = moov_box.end();
+    let mut trailing_free = None;
+    if delta != 0 && absorb_in_free_box(&mut moov, delta) == true
+    {
+        placement = MoovPlacement::FreeSpace;
+        delta = 0;
+    }
+    if delta != 0 &&
+        let Some(next) = top_level.get(moov_index + 1) &&
+        (&next.box_type == b"free" || &next.box_type == b"skip") &&
+        (next.size as i64 - delta >= 8 || next.size as i64 == delta)
+    {
+        // The free box after the movie box shrinks or grows, or disappears if the movie box needs all of it
+        replaced_end = next.end();
+        trailing_free = (next.size as i64 != delta).then(|| RawBox::new(&next.box_type, vec![0; (next.size as i64 - delta - 8) as usize]));
+        placement = MoovPlacement::FreeSpace;
+        delta = 0;
+    }
+    // Fragments carry absolute offsets (tfhd, sidx, mfra) this rewrite does not update
+    if delta != 0 && top_level[moov_index + 1..].iter().any(|top| &top.box_type == b"moof" || &top.box_type == b"sidx") == true
+    {
+        return Err(tr("The movie box of a fragmented file cannot change its size without free space next to it").into());
+    }
+    if delta != 0 && moov.first_chunk_offset().is_some_and(|offset| offset >= moov_box.end()) == true
+    {
+        let chunk_offsets = moov.shift_chunk_offsets(moov_box.end()..u64::MAX, delta)?;
+        placement = MoovPlacement::Shifted { delta, chunk_offsets };
+    }
+
+    let mut replacement = moov.to_bytes();
+    if let Some(free) = trailing_free
+    {
+        free.write_to(&mut replacement);
+    }
+    drop(file);
+    rewrite::replace_range(path, moov_box.offset, replaced_end, &replacement)?;
+
+    Ok(ItunesEditReport { results, moov_size: Some(moov.size()), placement })
+}
+
+/// Move the movie box of the file at `path` in front of the first `mdat` box ("fast start")
+///
+/// Players reading the file progressively need the movie box before the media data. The boxes
+/// between the first `mdat` and the movie box move back by the size of the movie box and the
+/// chunk offsets pointing into them are updated; nothing else changes. The file is rewritten
+/// through a temporary file (see `rewrite`).
+pub fn faststart_file(path: &Path) -> Result<FaststartReport, Box<dyn std::error::Error>>
+{
+    let mut file = media_source::open(path)?;
+    let top_level = top_level_boxes(file.as_mut())?;
+    let moov_index = top_level.iter().position(|top| &top.box_type == b"moov").ok_or_else(|| tr("The file has no movie box ('moov')"))?;
+    let moov_box = &top_level[moov_index];
+    let Some(mdat_index) = top_level[..moov_index].iter().position(|top| &top.box_type == b"mdat")
+    else
+    {
+        return Ok(FaststartReport { moved: false, moov_size: moov_box.size, chunk_offsets: 0 });
+    };
+    // Fragments carry absolute offsets (tfhd, sidx, mfra) this rewrite does not update
+    if top_level.iter().any(|top| &top.box_type == b"moof" || &top.box_type == b"sidx" || &top.box_type == b"mfra") == true
+    {
+        return Err(tr("The movie box of a fragmented file cannot be moved").into());
+    }
+
+    let mut moov = RawBox::parse_body(*b"moov", file.map(moov_box.offset, moov_box.size)?.get(moov_box.header_size as usize..).unwrap_or_default())?;
+    let mdat_offset = top_level[mdat_index].offset;
+    let moov_size = moov.size();
+
+    // The rebuilt movie box can be smaller than the original (zero bytes behind its last child are
+    // dropped), then the boxes after it move as well; they are shifted first, their new offsets
+    // lie behind the range of the boxes moving back by the size of the movie box
+    let mut chunk_offsets = 0;
+    if moov_size != moov_box.size
+    {
+        chunk_offsets += moov.shift_chunk_offsets(moov_box.end()..u64::MAX, moov_size as i64 - moov_box.size as i64)?;
+    }
+    chunk_offsets += moov.shift_chunk_offsets(mdat_offset..moov_box.offset, moov_size as i64)?;
+    let moov_bytes = moov.to_bytes();
+    drop(file);
+
+    let moov_box = moov_box.clone();
+    rewrite::rewrite(path, |original, output| {
+        rewrite::copy_range(original, output, 0, mdat_offset)?;
+        output.write_all(&moov_bytes)?;
+        rewrite::copy_range(original, output, mdat_offset, moov_box.offset - mdat_offset)?;
+        let length = original.metadata()?.len();
+        rewrite::copy_range(original, output, moov_box.end(), length - moov_box.end())
+    })?;
+
+    Ok(FaststartReport { moved: true, moov_size, chunk_offsets })
+}
+
+/// Apply one edit to the `ilst` box of the movie box, creating `udta`, `meta` and `ilst` when setting an item
+fn apply(moov: &mut RawBox, edit: &ItunesEdit) -> Result<EditResult, String>
+{
+    match edit
+    {
+        | ItunesEdit::Set(key, value) =>
+        {
+            let item = encode_item(key, value)?;
+            let ilst = ilst_mut(moov, true).ok_or_else(|| tr("The movie box has no room for an 'ilst' box").to_string())?;
+            let Some(items) = ilst.children_mut()
+            else
+            {
+                return Err(tr("The 'ilst' box cannot be parsed").to_string());
+            };
+            match items.iter().position(|existing| key.matches(existing))
+            {
+                | Some(index) =>
+                {
+                    items[index] = item;
+                    let mut position = 0;
+                    items.retain(|existing| {
+                        position += 1;
+                        position - 1 == index || key.matches(existing) == false
+                    });
+                    Ok(EditResult::Replaced)
+                }
+                | None =>
+                {
+                    items.push(item);
+                    Ok(EditResult::Added)
+                }
+            }
+        }
+        | ItunesEdit::Remove(key) =>
+        {
+            let Some(items) = ilst_mut(moov, false).and_then(RawBox::children_mut)
+            else
+            {
+                return Ok(EditResult::NotFound);
+            };
+            let count = items.len();
+            items.retain(|existing| key.matches(existing) == false);
+            match count - items.len()
+            {
+                | 0 => Ok(EditResult::NotFound),
+                | removed => Ok(EditResult::Removed(removed))
+            }
+        }
+    }
+}
+
+/// The `moov/udta/meta/ilst` box, created with its parents (and an `mdir` handler) if `create` is set
+fn ilst_mut(moov: &mut RawBox, create: bool) -> Option<&mut RawBox>
+{
+    if create == true
+    {
+        if moov.child_mut(b"udta").is_none() == true
+        {
+            moov.children_mut()?.push(RawBox::container(b"udta", Vec::new()));
+        }
+        let udta = moov.child_mut(b"udta")?;
+        if udta.child_mut(b"meta").is_none() == true
+        {
+            let mut meta = RawBox::container(b"meta", vec![0; 4]);
+            let mut handler = vec![0; 8];
+            handler.extend(b"mdirappl");
+            handler.extend([0; 9]);
+            meta.children_mut()?.push(RawBox::new(b"hdlr", handler));
+            udta.children_mut()?.push(meta);
+        }
+        let meta = udta.child_mut(b"meta")?;
+        if meta.child_mut(b"ilst").is_none() == true
+        {
+            // Items go before any free space iTunes leaves behind the item list
+            let children = meta.children_mut()?;
+            let position = children.iter().position(|child| &child.box_type == b"free").unwrap_or(children.len());
+            children.insert(position, RawBox::container(b"ilst", Vec::new()));
+        }
+    }
+    moov.child_mut(b"udta")?.child_mut(b"meta")?.child_mut(b"ilst")
+}
+
+/// Let a `free` box in `meta`, `udta` or `moov` absorb a size change of `delta` bytes
+///
+/// A growing movie box takes the space from a free box, a shrinking one grows the first free box
+/// or leaves a new one behind the item list. Returns false if no free box can absorb the change.
+fn absorb_in_free_box(moov: &mut RawBox, delta: i64) -> bool
+{
+    let fits = |free: &RawBox| free.size() as i64 - delta >= 8 || (free.size() as i64 == delta);
+    let containers: [&[&[u8; 4]]; 3] = [&[b"udta", b"meta"], &[b"udta"], &[]];
+    for path in containers
+    {
+        let Some(container) = path.iter().try_fold(&mut *moov, |parent, box_type| parent.child_mut(box_type))
+        else
+        {
+            continue;
+        };
+        let Some(children) = container.children_mut()
+        else
+        {
+            continue;
+        };
+        if let Some(index) = children.iter().position(|child| &child.box_type == b"free" && fits(child) == true)
+        {
+            match children[index].size() as i64 == delta
+            {
+                | true =>
+                {
+                    children.remove(index);
+                }
+                | false => children[index] = RawBox::new(b"free", vec![0; (children[index].size() as i64 - delta - 8) as usize])
+            }
+            return true;
+        }
+    }
+
+    // Leave the bytes the movie box no longer needs as free space
+    if delta <= -8 &&
+        let Some(meta) = moov.child_mut(b"udta").and_then(|udta| udta.child_mut(b"meta")) &&
+        let Some(children) = meta.children_mut()
+    {
+        children.push(RawBox::new(b"free", vec![0; (-delta - 8) as usize]));
+        return true;
+    }
+    false
+}
+
+/// Item box holding `value` in a `data` box of the type iTunes uses for the key
+fn encode_item(key: &ItunesKey, value: &str) -> Result<RawBox, String>
+{
+    let number = |value: &str| value.trim().parse::<u16>().map_err(|_| tr_fmt("'{}' is not a number for item {}", &[&value, key]));
+
+    // Data type (well-known types of the iTunes metadata) and payload
+    let (data_type, payload): (u32, Vec<u8>) = match key
+    {
+        | ItunesKey::Atom(atom) => match atom
+        {
+            | b"trkn" | b"disk" =>
+            {
+                let (index, total) = match value.split_once('/')
+                {
+                    | Some((index, total)) => (number(index)?, number(total)?),
+                    | None => (number(value)?, 0)
+                };
+                let mut payload = vec![0, 0];
+                payload.extend(index.to_be_bytes());
+                payload.extend(total.to_be_bytes());
+                if atom == b"trkn"
+                {
+                    payload.extend([0, 0]);
+                }
+                (0x00, payload)
+            }
+            | b"tmpo" => (0x15, number(value)?.to_be_bytes().to_vec()),
+            | b"gnre" => (0x00, number(value)?.to_be_bytes().to_vec()),
+            | b"cpil" | b"pgap" | b"pcst" | b"hdvd" | b"stik" | b"rtng" | b"shwm" =>
+            {
+                let flag = u8::try_from(number(value)?).map_err(|_| tr_fmt("'{}' is not a number for item {}", &[&value, key]))?;
+                (0x15, vec![flag])
+            }
+            | b"covr" =>
+            {
+                let image = std::fs::read(value).map_err(|e| tr_fmt("Cannot read cover image '{}': {}", &[&value, &e]))?;
+                match image.as_slice()
+                {
+                    | [0xFF, 0xD8, 0xFF, ..] => (0x0D, image),
+                    | [0x89, b'P', b'N', b'G', ..] => (0x0E, image),
+                    | _ => return Err(tr_fmt("Cover image '{}' is neither JPEG nor PNG", &[&value]))
+                }
+            }
+            | _ => (0x01, value.as_bytes().to_vec())
+        },
+        | ItunesKey::FreeForm { .. } => (0x01, value.as_bytes().to_vec())
+    };
+
+    let mut data = data_type.to_be_bytes().to_vec();
+    data.extend([0; 4]);
+    data.extend(payload);
+
+    match key
+    {
+        | ItunesKey::Atom(atom) =>
+        {
+            let mut item = RawBox::container(atom, Vec::new());
+            item.children_mut().into_iter().for_each(|children| children.push(RawBox::new(b"data", data.clone())));
+            Ok(item)
+        }
+        | ItunesKey::FreeForm { mean, name } =>
+        {
+            let mut item = RawBox::container(b"----", Vec::new());
+            if let Some(children) = item.children_mut()
+            {
+                children.push(RawBox::new(b"mean", [&[0u8; 4][..], mean.as_bytes()].concat()));
+                children.push(RawBox::new(b"name", [&[0u8; 4][..], name.as_bytes()].concat()));
+                children.push(RawBox::new(b"data", data));
+            }
+            Ok(item)
+        }
+    }
+}
+
+/// String of the `mean` or `name` box of a free-form item
+fn free_form_string(item: &RawBox, box_type: &[u8; 4]) -> Option<String>
+{
+    // Items read from the file keep their data, items added by an edit have children
+    let children = match item.payload
+    {
+        | RawPayload::Data(ref data) => RawBox::parse_all(data).ok()?,
+        | RawPayload::Children(ref children) => children.clone()
+    };
+    let child = children.into_iter().find(|child| &child.box_type == box_type)?;
+    match child.payload
+    {
+        | RawPayload::Data(ref data) => Some(String::from_utf8_lossy(data.get(4..)?).to_string()),
+        | RawPayload::Children(_) => None
+    }
+}
+
+/// Box type for messages, © for the 0xA9 of iTunes atoms
+fn type_name(box_type: &[u8; 4]) -> String
+{
+    box_type.iter().map(|&byte| byte as char).collect()
+}
@@ -0,0 +1,202 @@
+// File layout
+//
+// Backs the `layout` command: a byte map of the whole file with the regions of all layers (tags,
+// audio data, top-level boxes and what they contain), their sizes and shares of the file, so it
+// is visible at a glance where the space goes (e.g. oversized artwork). Bytes no dissector claims
+// are listed as unrecognized data.
+
+use std::{fmt, path::Path};
+
+use crate::{
+    ape_tag::ApeTag,
+    i18n::tr,
+    id3v1::{ID3V1_SIZE, Id3v1Tag},
+    lyrics3::Lyrics3Tag,
+    media_source::MediaSource,
+    report::SCHEMA_VERSION,
+    summary::write_table
+};
+
+/// Name of the regions no dissector claims
+pub const UNRECOGNIZED: &str = "Unrecognized data";
+
+/// Generic region names translated in the text output (format names and structure IDs stay as they are)
+const GENERIC_NAMES: [&str; 3] = ["MPEG audio", "Padding", UNRECOGNIZED];
+
+/// Width of the share bar in the text output
+const BAR_WIDTH: usize = 20;
+
+/// Byte range of a structure
+#[derive(Debug, Clone)]
+pub struct LayoutRegion
+{
+    pub offset: u64,
+    pub size:   u64,
+    /// Nesting level: 0 for layers and top-level structures, 1 for their frames or child boxes, ...
+    pub depth:  usize,
+    /// Format name (e.g. "ID3v2.3", "MPEG audio"), box type or frame ID
+    pub name:   String
+}
+
+impl LayoutRegion
+{
+    pub fn end_offset(&self) -> u64
+    {
+        self.offset + self.size
+    }
+}
+
+/// Regions of a whole file in file order
+#[derive(Debug, Clone, Default)]
+pub struct Layout
+{
+    pub file_size: u64,
+    pub regions:   Vec<LayoutRegion>
+}
+
+impl Layout
+{
+    /// Create an empty layout of a file of `file_size` bytes
+    pub fn new(file_size: u64) -> Self
+    {
+        Self { file_size, regions: Vec::new() }
+    }
+
+    /// Add the region `offset..offset + size` (empty regions are skipped)
+    pub fn add(&mut self, offset: u64, size: u64, depth: usize, name: impl Into<String>)
+    {
+        if size > 0
+        {
+            self.regions.push(LayoutRegion { offset, size, depth, name: name.into() });
+        }
+    }
+
+    /// Add the APE, Lyrics3 and ID3v1 tags at the end of the file that lie within `start..end`
+    ///
+    /// Returns the start of the first of these tags (`end` if there are none), where the data in front of them ends.
+    pub fn add_trailing_tags(&mut self, file: &mut dyn MediaSource, start: u64, end: u64) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let mut tags = Vec::new();
+        if let Some(ape) = ApeTag::read(file)?
+        {
+            tags.push((ape.offset, ape.size, ape.version_name()));
+        }
+        if let Some(lyrics3) = Lyrics3Tag::read(file)?
+        {
+            tags.push((lyrics3.offset, lyrics3.size, lyrics3.version_name()));
+        }
+        if let Some(id3v1) = Id3v1Tag::read(file)?
+        {
+            tags.push((id3v1.offset, ID3V1_SIZE, id3v1.version()));
+        }
+
+        let mut data_end = end;
+        for (offset, size, name) in tags.into_iter().filter(|&(offset, size, _)| offset >= start && offset + size <= end)
+        {
+            self.add(offset, size, 0, name);
+            data_end = data_end.min(offset);
+        }
+        Ok(data_end)
+    }
+
+    /// Sort the regions into file order and add the top-level ranges no dissector claimed as unrecognized data
+    pub fn fill_gaps(&mut self)
+    {
+        self.regions.sort_by_key(|region| (region.offset, region.depth));
+
+        let mut gaps = Vec::new();
+        let mut position = 0;
+        for region in self.regions.iter().filter(|region| region.depth == 0)
+        {
+            if region.offset > position
+            {
+                gaps.push((position, region.offset - position));
+            }
+            position = position.max(region.end_offset());
+        }
+        if position < self.file_size
+        {
+            gaps.push((position, self.file_size - position));
+        }
+
+        for (offset, size) in gaps
+        {
+            self.add(offset, size, 0, UNRECOGNIZED);
+        }
+        self.regions.sort_by_key(|region| (region.offset, region.depth));
+    }
+
+    /// Drop the regions nested deeper than `depth`
+    pub fn limit_depth(&mut self, depth: usize)
+    {
+        self.regions.retain(|region| region.depth <= depth);
+    }
+
+    /// Share of the file in percent
+    fn share(&self, size: u64) -> f64
+    {
+        match self.file_size
+        {
+            | 0 => 0.0,
+            | file_size => size as f64 * 100.0 / file_size as f64
+        }
+    }
+
+    /// JSON document of the layout command (see `schema/layout.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        let regions: Vec<serde_json::Value> = self
+            .regions
+            .iter()
+            .map(|region| {
+                serde_json::json!({
+                    "offset": region.offset,
+                    "end_offset": region.end_offset(),
+                    "size": region.size,
+                    "share": self.share(region.size),
+                    "depth": region.depth,
+                    "name": region.name
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "file_size": self.file_size,
+            "regions": regions
+        })
+    }
+}
+
+/// Region name for the text output: generic names are translated, format names and structure IDs stay as they are
+pub fn translated_name(name: &str) -> &str
+{
+    match GENERIC_NAMES.iter().find(|&&generic| generic == name)
+    {
+        | Some(&generic) => tr(generic),
+        | None => name
+    }
+}
+
+impl fmt::Display for Layout
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut rows = vec![[tr("Range"), tr("Size"), tr("Share"), "", tr("Structure")].map(str::to_string)];
+        for region in &self.regions
+        {
+            let share = self.share(region.size);
+            let name = translated_name(&region.name);
+            rows.push([
+                format!("0x{:08X}-0x{:08X}", region.offset, region.end_offset() - 1),
+                region.size.to_string(),
+                format!("{:.1}%", share),
+                // At least one mark for every region that is not empty, so small structures stay visible
+                "#".repeat(((share * BAR_WIDTH as f64 / 100.0).round() as usize).max(1)),
+                format!("{}{}", "  ".repeat(region.depth), name)
+            ]);
+        }
+        write_table(f, &rows)
+    }
+}
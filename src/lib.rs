@@ -0,0 +1,77 @@
+// the-drill media analysis library
+//
+// Parsers behind the `the-drill` command line tool: ID3v1/ID3v2, APE and Lyrics3 tags, MPEG audio, ISOBMFF
+// (MP4, MOV, M4A, ...), Ogg, Matroska/WebM, MPEG transport and program streams, DSF and DSDIFF
+// files, WavPack, Musepack and True Audio streams, AMR speech and JPEG/PNG/GIF/BMP images.
+// `Dissector` runs the same layer-by-layer dissection as the command line tool with the dissectors of a
+// `DissectorRegistry`, which applications extend by their own formats, and reports the structures
+// it finds to a `DissectionVisitor`; the format modules expose the parsed structures (`Id3v2Tag`,
+// `IsobmffTree`, frame and box types) for direct use.
+
+// Dissection entry points
+pub mod dissector;
+pub mod dissector_registry;
+pub mod media_dissector;
+pub mod options;
+pub mod visitor;
+
+// Format support
+pub mod ape_tag;
+pub mod audio;
+pub mod dsd;
+pub mod id3v1;
+pub mod id3v2;
+pub mod image;
+pub mod isobmff;
+pub mod lyrics3;
+pub mod mkv;
+pub mod mpeg_audio;
+pub mod mpegps;
+pub mod mpegts;
+pub mod ogg;
+pub mod unknown_dissector;
+
+// Analysis and output support
+pub mod artwork;
+pub mod batch;
+pub mod bytes;
+pub mod carve;
+pub mod chapters;
+pub mod color;
+pub mod coverage;
+pub mod diagnostics;
+pub mod diff;
+pub mod dj_metadata;
+pub mod ffprobe;
+pub mod find;
+pub mod gapless;
+pub mod hashes;
+pub mod hexdump;
+pub mod i18n;
+pub mod inflate;
+pub mod layout;
+pub mod loudness;
+pub mod media_reader;
+pub mod media_source;
+pub mod metadata;
+pub mod music_ids;
+pub mod objects;
+pub mod optimize;
+pub mod path_filter;
+pub mod payload;
+pub mod playlist;
+pub mod podcast;
+pub mod report;
+pub mod rewrite;
+pub mod summary;
+pub mod timed_id3;
+
+// Re-export the public API for convenience
+pub use diagnostics::Diagnostics;
+pub use dissector::Dissector;
+pub use dissector_registry::DissectorRegistry;
+pub use id3v2::{Id3v2Frame, Id3v2Tag};
+pub use isobmff::{IsobmffBox, IsobmffTree};
+pub use media_dissector::MediaDissector;
+pub use options::{DissectOptions, TagSizeThresholds};
+pub use visitor::DissectionVisitor;
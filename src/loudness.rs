@@ -0,0 +1,159 @@
+// Loudness normalization data
+//
+// Backs `--loudness`: the volume adjustments players apply, gathered from all metadata layers.
+// ReplayGain values are stored as text (ID3v2 TXXX frames, iTunes freeform items), ID3v2.4 RVA2
+// frames carry the same information in binary per channel, and iTunes Sound Check stores an
+// `iTunNORM` string of hexadecimal energy and peak values (COMM frame or freeform item) that is
+// decoded to dB here.
+
+use crate::id3v2::frames::relative_volume::RelativeVolumeFrame;
+
+/// Kind of loudness value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoudnessKind
+{
+    TrackGain,
+    TrackPeak,
+    AlbumGain,
+    AlbumPeak,
+    /// Loudness the ReplayGain values are relative to (89 dB SPL originally, -18 LUFS for ReplayGain 2.0)
+    ReferenceLoudness,
+    /// RVA2 adjustment of one channel
+    RelativeVolume,
+    /// iTunes Sound Check (`iTunNORM`)
+    SoundCheck
+}
+
+impl LoudnessKind
+{
+    /// Kind of the ReplayGain field `name` (TXXX description or iTunes freeform name), compared ignoring case
+    fn from_field_name(name: &str) -> Option<Self>
+    {
+        let kinds = [
+            ("REPLAYGAIN_TRACK_GAIN", LoudnessKind::TrackGain),
+            ("REPLAYGAIN_TRACK_PEAK", LoudnessKind::TrackPeak),
+            ("REPLAYGAIN_ALBUM_GAIN", LoudnessKind::AlbumGain),
+            ("REPLAYGAIN_ALBUM_PEAK", LoudnessKind::AlbumPeak),
+            ("REPLAYGAIN_REFERENCE_LOUDNESS", LoudnessKind::ReferenceLoudness),
+            ("iTunNORM", LoudnessKind::SoundCheck)
+        ];
+        kinds.iter().find(|(field_name, _)| field_name.eq_ignore_ascii_case(name.trim())).map(|&(_, kind)| kind)
+    }
+
+    /// Identifier used in JSON reports
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | LoudnessKind::TrackGain => "replaygain_track_gain",
+            | LoudnessKind::TrackPeak => "replaygain_track_peak",
+            | LoudnessKind::AlbumGain => "replaygain_album_gain",
+            | LoudnessKind::AlbumPeak => "replaygain_album_peak",
+            | LoudnessKind::ReferenceLoudness => "replaygain_reference_loudness",
+            | LoudnessKind::RelativeVolume => "relative_volume",
+            | LoudnessKind::SoundCheck => "sound_check"
+        }
+    }
+
+    /// Name shown in the text output
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            | LoudnessKind::TrackGain => "ReplayGain track gain",
+            | LoudnessKind::TrackPeak => "ReplayGain track peak",
+            | LoudnessKind::AlbumGain => "ReplayGain album gain",
+            | LoudnessKind::AlbumPeak => "ReplayGain album peak",
+            | LoudnessKind::ReferenceLoudness => "ReplayGain reference loudness",
+            | LoudnessKind::RelativeVolume => "Relative volume adjustment",
+            | LoudnessKind::SoundCheck => "Sound Check"
+        }
+    }
+}
+
+/// Loudness value found in the metadata of a file
+#[derive(Debug, Clone)]
+pub struct Loudness
+{
+    pub kind:    LoudnessKind,
+    /// Metadata field holding the value, e.g. "TXXX:REPLAYGAIN_TRACK_GAIN", "RVA2:track" or "com.apple.iTunes:iTunNORM"
+    pub field:   String,
+    /// RVA2 channel the adjustment applies to
+    pub channel: Option<&'static str>,
+    /// Gain to apply in dB, or the reference loudness in dB for `ReferenceLoudness`
+    pub gain:    Option<f64>,
+    /// Peak sample value relative to full scale (1.0 = 0 dBFS)
+    pub peak:    Option<f64>
+}
+
+impl Loudness
+{
+    /// Value of a ReplayGain text field or Sound Check string, None for other fields
+    ///
+    /// `field` is the key of the field in the custom metadata: "TXXX:<description>", "COMM:<description>" or "<mean>:<name>".
+    pub fn from_field(field: &str, value: &str) -> Option<Self>
+    {
+        let kind = LoudnessKind::from_field_name(field.rsplit_once(':').map_or(field, |(_, name)| name))?;
+        let (gain, peak) = match kind
+        {
+            | LoudnessKind::TrackPeak | LoudnessKind::AlbumPeak => (None, parse_number(value)),
+            | LoudnessKind::SoundCheck => decode_sound_check(value)?,
+            | _ => (parse_number(value), None)
+        };
+        Some(Self { kind, field: field.to_string(), channel: None, gain, peak })
+    }
+
+    /// Values of an RVA2 frame, one per channel
+    pub fn from_relative_volume(frame: &RelativeVolumeFrame) -> Vec<Self>
+    {
+        frame
+            .channels
+            .iter()
+            .map(|channel| Self {
+                kind:    LoudnessKind::RelativeVolume,
+                field:   format!("RVA2:{}", frame.identification),
+                channel: Some(channel.channel_name()),
+                gain:    Some(channel.adjustment),
+                peak:    channel.peak_ratio()
+            })
+            .collect()
+    }
+
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "kind": self.kind.id(),
+            "field": self.field,
+            "channel": self.channel,
+            "gain": self.gain,
+            "peak": self.peak
+        })
+    }
+}
+
+/// Parse a ReplayGain value such as "-6.54 dB" or "0.988547"
+fn parse_number(value: &str) -> Option<f64>
+{
+    let value = value.trim();
+    let number = value.strip_suffix("dB").or_else(|| value.strip_suffix("db")).unwrap_or(value);
+    number.trim().parse().ok().filter(|number: &f64| number.is_finite())
+}
+
+/// Decode an `iTunNORM` string of ten hexadecimal fields into gain in dB and peak
+///
+/// Fields 1 and 2 hold the energy of the left and right channel in thousandths of the reference
+/// level, fields 7 and 8 the peak sample values of a 16-bit scale.
+fn decode_sound_check(value: &str) -> Option<(Option<f64>, Option<f64>)>
+{
+    let fields: Vec<u32> = value.split_whitespace().map(|field| u32::from_str_radix(field, 16).ok()).collect::<Option<_>>()?;
+    if fields.len() < 8
+    {
+        return None;
+    }
+
+    let energy = fields[0].max(fields[1]);
+    let gain = (energy > 0).then(|| -10.0 * (energy as f64 / 1000.0).log10());
+    let peak = Some(fields[6].max(fields[7]) as f64 / 32768.0);
+    Some((gain, peak))
+}
@@ -0,0 +1,327 @@
+// Lyrics3 v1 and v2 tags
+//
+// A Lyrics3 block sits between the audio data (or an APE tag) and the ID3v1 tag at the end of the
+// file. Lyrics3 v1 holds up to 5100 bytes of lyrics between "LYRICSBEGIN" and "LYRICSEND".
+// Lyrics3 v2 stores fields with a three-letter ID and a five-digit size (LYR lyrics, INF
+// information, AUT author, IMG image links, ...), followed by the six-digit tag size and
+// "LYRICS200". All text is ISO-8859-1. Tools unaware of the block count it as audio data.
+
+use std::io::{SeekFrom, Write};
+
+use crate::{
+    ape_tag::ApeTag,
+    color::Colorize,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    id3v1::Id3v1Tag,
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Marker at the start of both versions
+const LYRICS_BEGIN: &[u8] = b"LYRICSBEGIN";
+/// Marker at the end of a Lyrics3 v1 tag
+const LYRICS_END: &[u8] = b"LYRICSEND";
+/// Marker at the end of a Lyrics3 v2 tag
+const LYRICS_200: &[u8] = b"LYRICS200";
+
+/// Largest lyrics text of a Lyrics3 v1 tag
+const MAX_V1_LYRICS_SIZE: u64 = 5100;
+
+/// Size of the field size digits and the end marker of a Lyrics3 v2 tag
+const V2_FOOTER_SIZE: u64 = 15;
+
+/// Size of the field ID and the field size digits of a Lyrics3 v2 field
+const V2_FIELD_HEADER_SIZE: usize = 8;
+
+/// Longest single-line value shown in the text output
+const MAX_DISPLAY_LENGTH: usize = 80;
+
+/// Name of a Lyrics3 v2 field
+pub fn field_name(id: &str) -> &'static str
+{
+    match id
+    {
+        | "IND" => "Indications",
+        | "LYR" => "Lyrics",
+        | "INF" => "Additional Information",
+        | "AUT" => "Lyrics Author",
+        | "EAL" => "Extended Album",
+        | "EAR" => "Extended Artist",
+        | "ETT" => "Extended Title",
+        | "IMG" => "Image Links",
+        | _ => "Unknown field"
+    }
+}
+
+/// Decode ISO-8859-1 text
+fn decode_text(data: &[u8]) -> String
+{
+    data.iter().map(|&byte| byte as char).collect()
+}
+
+/// Parse a decimal number written with ASCII digits only
+fn parse_digits(data: &[u8]) -> Option<u64>
+{
+    if data.is_empty() == true || data.iter().all(u8::is_ascii_digit) == false
+    {
+        return None;
+    }
+    std::str::from_utf8(data).ok()?.parse().ok()
+}
+
+/// Field of a Lyrics3 tag (the lyrics of a v1 tag are reported as a LYR field)
+#[derive(Debug, Clone)]
+pub struct Lyrics3Field
+{
+    /// File offset of the field
+    pub offset: u64,
+    pub id:     String,
+    pub value:  String
+}
+
+impl Lyrics3Field
+{
+    /// Lines of the value (lyrics and image links are separated by CR LF)
+    pub fn lines(&self) -> Vec<&str>
+    {
+        self.value.lines().collect()
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "offset": self.offset,
+            "id": self.id,
+            "name": field_name(&self.id),
+            "value": self.value
+        })
+    }
+}
+
+/// Parsed Lyrics3 tag
+#[derive(Debug, Clone)]
+pub struct Lyrics3Tag
+{
+    /// File offset of "LYRICSBEGIN"
+    pub offset:        u64,
+    /// Total size including the markers
+    pub size:          u64,
+    /// 1 or 2
+    pub version:       u8,
+    pub fields:        Vec<Lyrics3Field>,
+    /// Bytes behind the last field that could not be parsed (Lyrics3 v2 only)
+    pub unparsed_size: u64
+}
+
+impl Lyrics3Tag
+{
+    /// Read the Lyrics3 tag in front of the ID3v1 tag, or in front of an APE tag directly before it
+    ///
+    /// Lyrics3 tags require an ID3v1 tag, files without one have none.
+    pub fn read(file: &mut dyn MediaSource) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        let Some(id3v1) = Id3v1Tag::read(file)?
+        else
+        {
+            return Ok(None);
+        };
+        if let Some(tag) = Self::read_before(file, id3v1.offset)?
+        {
+            return Ok(Some(tag));
+        }
+
+        match ApeTag::read(file)?
+        {
+            | Some(ape) if ape.offset + ape.size == id3v1.offset => Self::read_before(file, ape.offset),
+            | _ => Ok(None)
+        }
+    }
+
+    /// Read a Lyrics3 tag that ends at `end`
+    pub fn read_before(file: &mut dyn MediaSource, end: u64) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        if end < V2_FOOTER_SIZE
+        {
+            return Ok(None);
+        }
+        let mut footer = [0u8; V2_FOOTER_SIZE as usize];
+        file.seek(SeekFrom::Start(end - V2_FOOTER_SIZE))?;
+        file.read_exact(&mut footer)?;
+
+        if &footer[6..] == LYRICS_200
+        {
+            return Self::read_v2(file, end, &footer);
+        }
+        if &footer[6..] == LYRICS_END
+        {
+            return Self::read_v1(file, end);
+        }
+        Ok(None)
+    }
+
+    /// Read a Lyrics3 v2 tag whose footer (size digits and "LYRICS200") ends at `end`
+    fn read_v2(file: &mut dyn MediaSource, end: u64, footer: &[u8]) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        // The size covers "LYRICSBEGIN" and the fields, not the footer
+        let Some(size) = parse_digits(&footer[0..6])
+        else
+        {
+            return Ok(None);
+        };
+        if size < LYRICS_BEGIN.len() as u64 || size + V2_FOOTER_SIZE > end
+        {
+            return Ok(None);
+        }
+        let offset = end - V2_FOOTER_SIZE - size;
+        let mut data = vec![0u8; size as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut data)?;
+        if data.starts_with(LYRICS_BEGIN) == false
+        {
+            return Ok(None);
+        }
+
+        // Fields: three-letter ID, five-digit size, value; parsing stops at the first malformed field
+        let mut fields = Vec::new();
+        let mut position = LYRICS_BEGIN.len();
+        while position + V2_FIELD_HEADER_SIZE <= data.len()
+        {
+            let id = &data[position..position + 3];
+            let Some(value_size) = parse_digits(&data[position + 3..position + V2_FIELD_HEADER_SIZE])
+            else
+            {
+                break;
+            };
+            let value_start = position + V2_FIELD_HEADER_SIZE;
+            if id.iter().all(u8::is_ascii_uppercase) == false || value_start + value_size as usize > data.len()
+            {
+                break;
+            }
+            fields.push(Lyrics3Field {
+                offset: offset + position as u64,
+                id:     decode_text(id),
+                value:  decode_text(&data[value_start..value_start + value_size as usize])
+            });
+            position = value_start + value_size as usize;
+        }
+
+        Ok(Some(Self { offset, size: size + V2_FOOTER_SIZE, version: 2, fields, unparsed_size: (data.len() - position) as u64 }))
+    }
+
+    /// Read a Lyrics3 v1 tag whose "LYRICSEND" marker ends at `end`
+    fn read_v1(file: &mut dyn MediaSource, end: u64) -> Result<Option<Self>, Box<dyn std::error::Error>>
+    {
+        // v1 has no size field, search the last "LYRICSBEGIN" within the largest possible tag
+        let lyrics_end = end - LYRICS_END.len() as u64;
+        let search_start = lyrics_end.saturating_sub(MAX_V1_LYRICS_SIZE + LYRICS_BEGIN.len() as u64);
+        let mut data = vec![0u8; (lyrics_end - search_start) as usize];
+        file.seek(SeekFrom::Start(search_start))?;
+        file.read_exact(&mut data)?;
+
+        let Some(position) = data.windows(LYRICS_BEGIN.len()).rposition(|window| window == LYRICS_BEGIN)
+        else
+        {
+            return Ok(None);
+        };
+        let offset = search_start + position as u64;
+        let lyrics =
+            Lyrics3Field { offset: offset + LYRICS_BEGIN.len() as u64, id: "LYR".to_string(), value: decode_text(&data[position + LYRICS_BEGIN.len()..]) };
+
+        Ok(Some(Self { offset, size: end - offset, version: 1, fields: vec![lyrics], unparsed_size: 0 }))
+    }
+
+    /// Version label (Lyrics3v1 or Lyrics3v2)
+    pub fn version_name(&self) -> &'static str
+    {
+        match self.version
+        {
+            | 1 => "Lyrics3v1",
+            | _ => "Lyrics3v2"
+        }
+    }
+
+    /// Report fields that could not be parsed and the block's effect on audio size calculations
+    pub fn check_fields(&self, diagnostics: &mut Diagnostics)
+    {
+        if self.unparsed_size > 0
+        {
+            diagnostics.warning(
+                Some(self.offset + self.size - V2_FOOTER_SIZE - self.unparsed_size),
+                tr_fmt("Lyrics3v2 field is malformed, {} bytes of the tag could not be parsed", &[&self.unparsed_size])
+            );
+        }
+        diagnostics.info(
+            Some(self.offset),
+            tr_fmt("{} tag of {} bytes between the audio data and the ID3v1 tag (tools unaware of it count it as audio)", &[&self.version_name(), &self.size])
+        );
+    }
+
+    /// Structured representation of this tag for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version_name(),
+            "offset": self.offset,
+            "size": self.size,
+            "fields": self.fields.iter().map(Lyrics3Field::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Shorten long single-line values for display
+fn truncate_value(value: &str) -> String
+{
+    if value.chars().count() > MAX_DISPLAY_LENGTH
+    {
+        format!("{}...", value.chars().take(MAX_DISPLAY_LENGTH).collect::<String>())
+    }
+    else
+    {
+        value.to_string()
+    }
+}
+
+/// Display the Lyrics3 tag of the file, if present
+pub fn dissect_lyrics3_tag(
+    file: &mut dyn MediaSource, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    let Some(tag) = Lyrics3Tag::read(file)?
+    else
+    {
+        return Ok(());
+    };
+
+    tag.check_fields(diagnostics);
+
+    if options.show_header == false && options.show_data == false
+    {
+        return Ok(());
+    }
+
+    writeln!(output, "\n{}", tr_fmt("{} Tag Found at offset {}:", &[&tag.version_name(), &format!("0x{:08X}", tag.offset)]).bright_cyan().bold())?;
+    writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&tag.size]))?;
+    writeln!(output, "  {}", tr_fmt("Fields: {}", &[&tag.fields.len()]))?;
+
+    // Multi-line values (lyrics, image links) are summarized, their lines follow with --data
+    for field in &tag.fields
+    {
+        let lines = field.lines();
+        match lines.len()
+        {
+            | 0 | 1 => writeln!(output, "    {} ({}): {}", field.id, tr(field_name(&field.id)), truncate_value(&field.value))?,
+            | count => writeln!(output, "    {} ({}): {}", field.id, tr(field_name(&field.id)), tr_fmt("{} lines", &[&count]))?
+        }
+        if options.show_data == true && lines.len() > 1
+        {
+            for line in lines
+            {
+                writeln!(output, "      {}", line)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,50 +1,1393 @@
-use std::{fs::File, path::PathBuf};
+use std::{
+    fs,
+    io::{self, BufWriter, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process::ExitCode
+};
 
 use clap::Parser;
+use the_drill::{
+    Diagnostics, DissectOptions, Dissector, artwork,
+    batch::{self, BatchFilter, BatchSummary},
+    carve, chapters,
+    color::{self, Colorize},
+    coverage::SMALL_GAP,
+    diagnostics::Severity,
+    diff::{ChangeKind, Comparison},
+    gapless::Gapless,
+    hashes::RegionHash,
+    hexdump,
+    i18n::{self, Language, tr, tr_fmt},
+    id3v2::writer::{self, EditResult, FrameConversion, FrameSelector, TagEdit},
+    isobmff::{
+        samples,
+        tree::InitSegment,
+        writer::{self as isobmff_writer, FaststartReport, ItunesEdit, ItunesKey, MoovPlacement}
+    },
+    layout,
+    loudness::{Loudness, LoudnessKind},
+    media_source::{self, MediaSource},
+    music_ids::{MusicId, MusicIdKind},
+    objects,
+    playlist::{self, Playlist, PlaylistCheck, SegmentStatus},
+    podcast::Podcast,
+    visitor::OutlinePrinter
+};
 
-use crate::cli::{Cli, Commands, DissectOptions};
+use crate::{
+    cli::{
+        CarveArgs, ChaptersArgs, Cli, Commands, DiffArgs, DissectArgs, DissectFormat, ExtractArgs, ExtractAudioArgs, ExtractCommands, ExtractObjectsArgs,
+        ExtractSamplesArgs, FindArgs, FragmentsArgs, LayoutArgs, OptimizeArgs, OutputFormat, PlaylistArgs, SummaryArgs, TagArgs, TagCommands, ValidateArgs
+    },
+    exit_status::ExitStatus
+};
 
 mod cli;
-mod dissector_builder;
-mod hexdump;
-mod id3v2;
-mod isobmff;
-mod media_dissector;
-mod unknown_dissector;
+mod exit_status;
 
-use dissector_builder::DissectorBuilder;
+fn main() -> ExitCode
+{
+    // Usage errors get their own exit code instead of clap's 2, which stands for an unknown file format
+    let cli = match Cli::try_parse()
+    {
+        | Ok(cli) => cli,
+        | Err(error) if error.use_stderr() == false => error.exit(),
+        | Err(error) =>
+        {
+            let _ = error.print();
+            return ExitStatus::UsageError.into();
+        }
+    };
+    i18n::set_language(cli.lang.unwrap_or_else(Language::from_environment));
+    color::set_color(cli.color);
+
+    let result = match cli.command
+    {
+        | Commands::Dissect(args) => dissect_options(&args).and_then(|options| {
+            if args.is_batch() == true && options.init_segment.is_some() == true
+            {
+                Err(tr("--init is only available for single files").into())
+            }
+            else if args.is_batch() == true && (args.extract_art.is_some() == true || args.extract_chapter_art.is_some() == true)
+            {
+                Err(tr("Artwork extraction is only available for single files").into())
+            }
+            else if args.is_batch() == true && args.region().is_some() == true
+            {
+                Err(tr("--offset and --length are only available for single files").into())
+            }
+            else if args.normalized == true && args.format != DissectFormat::Json
+            {
+                Err(tr("Normalized metadata is only available with --format json").into())
+            }
+            else if args.normalized == true && args.is_batch() == true
+            {
+                Err(tr("Normalized metadata is only available for single files").into())
+            }
+            else if args.normalized == true
+            {
+                print_normalized_metadata(args.path(), args.region())
+            }
+            else if args.outline == true && args.format != DissectFormat::Text
+            {
+                Err(tr("The outline is only available with --format text").into())
+            }
+            else if args.outline == true && args.is_batch() == true
+            {
+                Err(tr("The outline is only available for single files").into())
+            }
+            else if args.outline == true
+            {
+                print_outline(args.path(), args.region())
+            }
+            else if args.is_batch() == true
+            {
+                dissect_batch(&args.inputs(), &args.filter(), &options, args.format, args.jobs, args.quiet)
+            }
+            else if args.quiet == true
+            {
+                check_file(args.path(), args.region(), &options)
+            }
+            else
+            {
+                dissect_file(args.path(), args.region(), &options, args.format, args.extract_art.as_deref(), args.extract_chapter_art.as_deref())
+            }
+        }),
+        | Commands::Carve(args) => carve_file(&args),
+        | Commands::Chapters(args) => export_chapters(&args),
+        | Commands::Validate(args) => validate_file(&args),
+        | Commands::Summary(args) => summarize_file(&args),
+        | Commands::Layout(args) => print_layout(&args),
+        | Commands::Fragments(args) => list_fragments(&args),
+        | Commands::Playlist(args) => check_playlist(&args),
+        | Commands::Find(args) => find_in_file(&args),
+        | Commands::Optimize(args) => optimize_file(&args),
+        | Commands::Diff(args) => diff_files(&args),
+        | Commands::Tag(args) => edit_tag(&args),
+        | Commands::Extract(args) => extract_from_file(&args)
+    };
+
+    let status = match result
+    {
+        | Ok(status) => status,
+        | Err(e) =>
+        {
+            eprintln!("{} {}", tr("Error:").bright_red().bold(), e);
+            ExitStatus::from_error(e.as_ref())
+        }
+    };
+
+    status.into()
+}
+
+/// Dissection options selected by the arguments, with the movie box of the initialization segment given with --init
+fn dissect_options(args: &DissectArgs) -> Result<DissectOptions, Box<dyn std::error::Error>>
+{
+    let init_segment = args.init.as_deref().map(InitSegment::read).transpose()?;
+    Ok(DissectOptions { init_segment, ..args.options() })
+}
 
-fn main() -> Result<(), Box<dyn std::error::Error>>
+fn dissect_file(
+    file_path: &Path, region: Option<(u64, Option<u64>)>, options: &DissectOptions, format: DissectFormat, extract_art: Option<&Path>,
+    extract_chapter_art: Option<&Path>
+) -> Result<ExitStatus, Box<dyn std::error::Error>>
 {
-    let cli = Cli::parse();
+    // Build appropriate dissector based on file content
+    let mut dissector = open_dissector(file_path, region)?;
 
-    match cli.command
+    let mut diagnostics = Diagnostics::new();
+
+    match format
     {
-        | Commands::Dissect { file, header, data, all, verbose, dump } =>
+        | DissectFormat::Text =>
+        {
+            // Print file info
+            println!("{}", tr_fmt("Analyzing file: {}", &[&file_path.display()]));
+            if let Some(ref init_segment) = options.init_segment
+            {
+                println!("{}", tr_fmt("Initialization segment: {}", &[&init_segment.path.display()]));
+            }
+            if let Some((offset, length)) = dissector.region()
+            {
+                println!(
+                    "{}",
+                    tr_fmt("Region: {} - {} ({} bytes, offsets below are relative to the region)", &[
+                        &format!("0x{:08X}", offset),
+                        &format!("0x{:08X}", offset + length),
+                        &length
+                    ])
+                );
+            }
+            println!("{}", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+
+            // Perform dissection with options, chaining into following formats
+            dissector.print(options, &mut io::stdout(), &mut diagnostics)?;
+
+            if options.show_gaps == true
+            {
+                let gaps = dissector.gaps()?;
+                print_gaps(dissector.source(), &gaps)?;
+            }
+
+            if options.show_hashes == true
+            {
+                print_hashes(&dissector.hashes()?);
+            }
+
+            if options.show_music_ids == true
+            {
+                print_music_ids(&dissector.music_ids()?);
+            }
+
+            if options.show_loudness == true
+            {
+                print_loudness(&dissector.loudness()?);
+            }
+
+            if options.show_gapless == true
+            {
+                print_gapless(&dissector.gapless()?);
+            }
+
+            if options.show_podcast == true
+            {
+                print_podcast(&dissector.podcast()?);
+            }
+
+            if options.show_payloads == true
+            {
+                println!("\n{}", tr("Embedded Payloads:").bright_cyan().bold());
+                if dissector.print_payloads(options, &mut io::stdout(), &mut diagnostics)? == 0
+                {
+                    println!("  {}", tr("(none)"));
+                }
+            }
+
+            // Report findings collected during dissection
+            if diagnostics.is_empty() == false
+            {
+                println!("\n{}", tr("Diagnostics:").bright_cyan().bold());
+                print!("{}", diagnostics);
+            }
+        }
+        | DissectFormat::Json =>
         {
-            let options = DissectOptions::from_flags(header, data, all, verbose, dump);
-            dissect_file(&file, &options)?;
+            println!("{}", serde_json::to_string_pretty(&dissector.report(options, &mut diagnostics)?)?);
+        }
+        | DissectFormat::Ffprobe =>
+        {
+            println!("{}", serde_json::to_string_pretty(&dissector.probe_document(&mut diagnostics)?)?);
+        }
+    }
+
+    if let Some(directory) = extract_art
+    {
+        extract_artwork(&mut dissector, directory, format)?;
+    }
+
+    if let Some(directory) = extract_chapter_art
+    {
+        extract_chapter_artwork(&mut dissector, directory, format)?;
+    }
+
+    Ok(dissection_status(&dissector, &diagnostics))
+}
+
+/// Dissect a file without printing anything, for --quiet
+fn check_file(file_path: &Path, region: Option<(u64, Option<u64>)>, options: &DissectOptions) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = open_dissector(file_path, region)?;
+    let mut diagnostics = Diagnostics::new();
+    dissector.report(options, &mut diagnostics)?;
+
+    Ok(dissection_status(&dissector, &diagnostics))
+}
+
+/// Derive the exit status of a dissection from what was found
+fn dissection_status(dissector: &Dissector, diagnostics: &Diagnostics) -> ExitStatus
+{
+    if dissector.media_type() == "Unknown"
+    {
+        ExitStatus::UnknownFormat
+    }
+    else if diagnostics.has_warnings() == true
+    {
+        ExitStatus::Warnings
+    }
+    else
+    {
+        ExitStatus::Success
+    }
+}
+
+/// Open a file for dissection, restricted to a byte range if --offset or --length was given
+fn open_dissector(file_path: &Path, region: Option<(u64, Option<u64>)>) -> Result<Dissector, Box<dyn std::error::Error>>
+{
+    match region
+    {
+        | Some((offset, length)) => Dissector::open_region(file_path, offset, length),
+        | None => Dissector::open(file_path)
+    }
+}
+
+/// Print the metadata of all layers, normalized across formats, as JSON
+fn print_normalized_metadata(file_path: &Path, region: Option<(u64, Option<u64>)>) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = open_dissector(file_path, region)?;
+    let metadata = dissector.metadata()?;
+    println!("{}", serde_json::to_string_pretty(&metadata.to_json(file_path))?);
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn print_outline(file_path: &Path, region: Option<(u64, Option<u64>)>) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = open_dissector(file_path, region)?;
+    let mut printer = OutlinePrinter::new(io::stdout());
+    dissector.visit(&mut printer)?;
+    printer.finish()?;
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// Dissect every selected file of a directory or several inputs in parallel, printing one summary line per file and an aggregate report
+fn dissect_batch(
+    inputs: &[PathBuf], filter: &BatchFilter, options: &DissectOptions, format: DissectFormat, jobs: usize, quiet: bool
+) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    if format == DissectFormat::Ffprobe
+    {
+        return Err(tr("ffprobe output is only available for single files").into());
+    }
+
+    // A single directory is reported with paths relative to it, several inputs with the paths as given
+    let directory = inputs[0].as_path();
+    let single_directory = inputs.len() == 1;
+    let files = batch::collect_inputs(inputs, filter)?;
+    if format == DissectFormat::Text && quiet == false
+    {
+        match single_directory
+        {
+            | true => println!("{}", tr_fmt("Analyzing directory: {} ({} files)", &[&directory.display(), &files.len()])),
+            | false => println!("{}", tr_fmt("Analyzing {} inputs ({} files)", &[&inputs.len(), &files.len()]))
+        }
+    }
+
+    let mut summary = BatchSummary::default();
+    let mut reports = Vec::new();
+    let mut status = ExitStatus::Success;
+
+    // Workers only build reports; errors are turned into text there since they cannot cross threads
+    let dissect = |index: usize| {
+        let mut diagnostics = Diagnostics::new();
+        let report = Dissector::open(&files[index]).and_then(|mut dissector| dissector.report(options, &mut diagnostics));
+        (report.map_err(|e| (ExitStatus::from_error(e.as_ref()), e.to_string())), diagnostics)
+    };
+
+    batch::run_ordered(files.len(), batch::worker_count(jobs), dissect, |index, (report, diagnostics)| {
+        let path = &files[index];
+        let display_path = match single_directory
+        {
+            | true => path.strip_prefix(directory).unwrap_or(path).display().to_string(),
+            | false => path.display().to_string()
+        };
+
+        match report
+        {
+            | Ok(report) =>
+            {
+                let formats: Vec<&str> = report["layers"].as_array().into_iter().flatten().filter_map(|layer| layer["format"].as_str()).collect();
+                summary.add_file(&formats, diagnostics.has_warnings());
+
+                let file_status = if formats.first() == Some(&"Unknown")
+                {
+                    ExitStatus::UnknownFormat
+                }
+                else if diagnostics.has_warnings() == true
+                {
+                    ExitStatus::Warnings
+                }
+                else
+                {
+                    ExitStatus::Success
+                };
+                status = status.max(file_status);
+
+                if format == DissectFormat::Text && quiet == false
+                {
+                    let mut line = format!("  {}: {}", display_path, formats.join(" + "));
+                    if diagnostics.has_warnings() == true
+                    {
+                        line.push_str(&format!(" ({})", tr_fmt("{} warnings", &[&diagnostics.warning_count()]).yellow()));
+                    }
+                    println!("{}", line);
+                }
+                reports.push(report);
+            }
+            | Err((error_status, error)) =>
+            {
+                status = status.max(error_status);
+                if format == DissectFormat::Text && quiet == false
+                {
+                    println!("  {}: {} {}", display_path, tr("Error:").bright_red().bold(), error);
+                }
+                summary.add_error(path, error);
+            }
+        }
+    });
+
+    match format
+    {
+        | _ if quiet == true => (),
+        | DissectFormat::Json => println!("{}", serde_json::to_string_pretty(&summary.to_json(inputs, reports))?),
+        | _ =>
+        {
+            println!("\n{}", tr("Summary:").bright_cyan().bold());
+            println!("  {}", tr_fmt("Files dissected: {}", &[&summary.files]));
+            if summary.formats.is_empty() == false
+            {
+                println!("  {}", tr("Formats:"));
+                for (name, count) in &summary.formats
+                {
+                    println!("    {}: {}", name, count);
+                }
+            }
+            println!("  {}", tr_fmt("Files with warnings: {}", &[&summary.with_warnings]));
+            println!("  {}", tr_fmt("Errors: {}", &[&summary.errors.len()]));
+            for (path, error) in &summary.errors
+            {
+                let path = match single_directory
+                {
+                    | true => path.strip_prefix(directory).unwrap_or(path),
+                    | false => path
+                };
+                println!("    {}: {}", path.display(), error);
+            }
+        }
+    }
+
+    Ok(status)
+}
+
+/// Write the embedded pictures of a dissected file to `directory`, listing them in text output
+fn extract_artwork(dissector: &mut Dissector, directory: &Path, format: DissectFormat) -> Result<(), Box<dyn std::error::Error>>
+{
+    let artwork = dissector.artwork()?;
+
+    // Pictures are named after the input file
+    let paths = artwork::extract(&artwork, directory, &input_stem(dissector))?;
+
+    // JSON output must stay a single document
+    if format == DissectFormat::Text
+    {
+        println!("\n{}", tr("Extracted Artwork:").bright_cyan().bold());
+        if paths.is_empty() == true
+        {
+            println!("  {}", tr("(none)"));
+        }
+        for (picture, path) in artwork.iter().zip(&paths)
+        {
+            println!("  {}", tr_fmt("{} ({}, {}, {} bytes)", &[&path.display(), &picture.location, &picture.description, &picture.data.len()]));
         }
     }
 
     Ok(())
 }
 
-fn dissect_file(file_path: &PathBuf, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+/// File name of the input without extension, "stdin" when reading standard input
+fn input_stem(dissector: &Dissector) -> String
 {
-    // Open file
-    let mut file = File::open(file_path)?;
+    match dissector.path().file_stem()
+    {
+        | Some(stem) if dissector.path().as_os_str() != media_source::STDIN_PATH => stem.to_string_lossy().to_string(),
+        | _ => "stdin".to_string()
+    }
+}
 
-    // Build appropriate dissector based on file content
-    let builder = DissectorBuilder::new();
-    let dissector = builder.build_for_file(&mut file)?;
+/// Write the chapter pictures of a dissected file to `directory`, listing them in text output
+fn extract_chapter_artwork(dissector: &mut Dissector, directory: &Path, format: DissectFormat) -> Result<(), Box<dyn std::error::Error>>
+{
+    let chapters = dissector.chapters()?;
+    let paths = chapters::extract_art(&chapters, directory)?;
+
+    // JSON output must stay a single document
+    if format == DissectFormat::Text
+    {
+        println!("\n{}", tr("Extracted Chapter Artwork:").bright_cyan().bold());
+        if paths.is_empty() == true
+        {
+            println!("  {}", tr("(none)"));
+        }
+        for (index, path) in paths
+        {
+            if let Some(ref picture) = chapters[index].image
+            {
+                println!("  {}", tr_fmt("{} ({}, {}, {} bytes)", &[&path.display(), &picture.location, &picture.description, &picture.data.len()]));
+            }
+        }
+    }
+
+    Ok(())
+}
 
-    // Print file info
-    println!("Analyzing file: {}", file_path.display());
-    println!("Detected format: {} ({})", dissector.media_type(), dissector.name());
+/// Print uncovered byte ranges, with a hexdump for small ones
+fn print_gaps(file: &mut dyn MediaSource, gaps: &[(u64, u64)]) -> Result<(), Box<dyn std::error::Error>>
+{
+    println!("\n{}", tr("Uncovered Byte Ranges:").bright_cyan().bold());
+    if gaps.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+        return Ok(());
+    }
 
-    // Perform dissection with options
-    dissector.dissect_with_options(&mut file, options)?;
+    for &(start, end) in gaps
+    {
+        println!("  {}", tr_fmt("{} - {} ({} bytes)", &[&format!("0x{:08X}", start), &format!("0x{:08X}", end), &(end - start)]));
+        if end - start <= SMALL_GAP
+        {
+            let mut data = vec![0u8; (end - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut data)?;
+            for line in hexdump::format_hexdump(&data, start as usize).lines()
+            {
+                println!("    {}", line);
+            }
+        }
+    }
 
+    let total: u64 = gaps.iter().map(|(start, end)| end - start).sum();
+    println!("  {}", tr_fmt("Total: {} bytes in {} ranges", &[&total, &gaps.len()]));
     Ok(())
 }
+
+/// Print the digests of the top-level regions
+fn print_hashes(hashes: &[RegionHash])
+{
+    println!("\n{}", tr("Region Hashes:").bright_cyan().bold());
+    if hashes.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+    }
+    for hash in hashes
+    {
+        let name = match hash.name.as_str()
+        {
+            | "mdat" => tr("mdat payload"),
+            | name => layout::translated_name(name)
+        };
+        println!("  {}", tr_fmt("{} at offset {} ({} bytes)", &[&name, &format!("0x{:08X}", hash.offset), &hash.size]));
+        println!("    MD5:     {}", hash.md5);
+        println!("    SHA-256: {}", hash.sha256);
+    }
+}
+
+/// Print the MusicBrainz and AcoustID identifiers with the fields they were found in
+fn print_music_ids(ids: &[MusicId])
+{
+    println!("\n{}", tr("Music Identifiers:").bright_cyan().bold());
+    if ids.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+    }
+    for id in ids
+    {
+        match (id.kind, id.fingerprint)
+        {
+            // Fingerprints run to hundreds of characters, the decoded header says more
+            | (MusicIdKind::AcoustIdFingerprint, Some(fingerprint)) => println!(
+                "  {}: {}",
+                tr(id.kind.name()),
+                tr_fmt("Chromaprint algorithm {}, {} values ({} s of audio)", &[
+                    &fingerprint.algorithm,
+                    &fingerprint.values,
+                    &format!("{:.1}", fingerprint.duration())
+                ])
+            ),
+            | _ => println!("  {}: {}", tr(id.kind.name()), id.value)
+        }
+        println!("    {}", tr_fmt("from {}", &[&id.source]));
+    }
+}
+
+/// Print the loudness normalization values with the fields they were found in
+fn print_loudness(values: &[Loudness])
+{
+    println!("\n{}", tr("Loudness:").bright_cyan().bold());
+    if values.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+    }
+    for value in values
+    {
+        let mut parts = Vec::new();
+        if let Some(channel) = value.channel
+        {
+            parts.push(tr(channel).to_string());
+        }
+        match (value.kind, value.gain)
+        {
+            // The reference is a level, not an adjustment
+            | (LoudnessKind::ReferenceLoudness, Some(level)) => parts.push(format!("{:.2} dB", level)),
+            | (_, Some(gain)) => parts.push(format!("{:+.2} dB", gain)),
+            | (_, None) => ()
+        }
+        // Peaks are only labeled next to a gain, ReplayGain peak fields hold nothing else
+        match (value.peak, parts.is_empty())
+        {
+            | (Some(peak), true) => parts.push(format!("{:.6}", peak)),
+            | (Some(peak), false) => parts.push(tr_fmt("peak {}", &[&format!("{:.6}", peak)])),
+            | (None, _) => ()
+        }
+        if parts.is_empty() == true
+        {
+            parts.push(tr("invalid value").to_string());
+        }
+        println!("  {}: {}", tr(value.kind.name()), parts.join(", "));
+        println!("    {}", tr_fmt("from {}", &[&value.field]));
+    }
+}
+
+/// Print the encoder delay and padding of each source with the samples players trim
+fn print_gapless(values: &[Gapless])
+{
+    println!("\n{}", tr("Gapless Playback:").bright_cyan().bold());
+    if values.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+    }
+    for gapless in values
+    {
+        match gapless.offset
+        {
+            | Some(offset) => println!("  {}", tr_fmt("{} header at offset {}", &[&gapless.location, &format!("0x{:08X}", offset)])),
+            | None => println!("  {}", gapless.location)
+        }
+        println!("    {}", tr_fmt("Encoder delay: {} samples, padding: {} samples", &[&gapless.encoder_delay, &gapless.padding]));
+        if gapless.decoder_delay > 0
+        {
+            println!("    {}", tr_fmt("Decoder delay: {} samples", &[&gapless.decoder_delay]));
+        }
+        println!("    {}", tr_fmt("Trim: {} samples at the start, {} samples at the end", &[&gapless.trim_start(), &gapless.trim_end()]));
+        match (gapless.samples, gapless.duration())
+        {
+            | (Some(samples), Some(duration)) => println!("    {}", tr_fmt("Playable samples: {} ({} s)", &[&samples, &format!("{:.3}", duration)])),
+            | (Some(samples), None) => println!("    {}", tr_fmt("Playable samples: {}", &[&samples])),
+            | (None, _) => ()
+        }
+    }
+}
+
+/// Print the podcast fields and the chapters with their links and pictures
+fn print_podcast(podcast: &Podcast)
+{
+    println!("\n{}", tr("Podcast:").bright_cyan().bold());
+    if podcast.fields.is_empty() == true && podcast.chapters.is_empty() == true
+    {
+        println!("  {}", tr("(none)"));
+    }
+    for field in &podcast.fields
+    {
+        println!("  {}: {}", tr(field.kind.name()), field.value);
+        println!("    {}", tr_fmt("from {}", &[&field.source]));
+    }
+    if podcast.chapters.is_empty() == false
+    {
+        println!("  {}", tr_fmt("Chapters ({}):", &[&podcast.chapters.len()]));
+    }
+    for (index, chapter) in podcast.chapters.iter().enumerate()
+    {
+        println!("    {:>2}. {} - {}  {}", index + 1, chapters::format_time(chapter.start), chapters::format_time(chapter.end), chapter.title);
+        if let Some(ref url) = chapter.url
+        {
+            println!("        {}", tr_fmt("URL: {}", &[url]));
+        }
+        if let Some(ref image) = chapter.image
+        {
+            println!("        {}", tr_fmt("Image: {}, {} bytes", &[&image.mime_type, &image.data.len()]));
+        }
+    }
+}
+
+fn carve_file(args: &CarveArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut file = media_source::open(&args.file)?;
+
+    println!("{}", tr_fmt("Carving file: {}", &[&args.file.display()]));
+    let candidates = carve::scan(file.as_mut(), args.min_frames)?;
+
+    if candidates.is_empty() == true
+    {
+        println!("{}", tr("No embedded media structures found"));
+        return Ok(ExitStatus::UnknownFormat);
+    }
+
+    println!("\n{}", tr_fmt("Candidates ({}):", &[&candidates.len()]).bright_cyan().bold());
+    for (index, candidate) in candidates.iter().enumerate()
+    {
+        // Estimated extents are marked with '~'
+        let extent = if candidate.estimated == true
+        {
+            format!("~{}", candidate.length)
+        }
+        else
+        {
+            candidate.length.to_string()
+        };
+        println!(
+            "  {:>3}  0x{:08X}  {}  {:<7}  {}",
+            index + 1,
+            candidate.offset,
+            tr_fmt("{} bytes", &[&format!("{:>12}", extent)]),
+            candidate.format,
+            candidate.details
+        );
+    }
+
+    if let Some(ref directory) = args.extract
+    {
+        println!("\n{}", tr("Extracted files:").bright_cyan().bold());
+        for path in carve::extract(file.as_mut(), &candidates, directory)?
+        {
+            println!("  {}", path.display());
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn export_chapters(args: &ChaptersArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let chapters = dissector.chapters()?;
+    if chapters.is_empty() == true
+    {
+        return Err(tr_fmt("No chapters found in {}", &[&args.file.display()]).into());
+    }
+
+    let export = chapters::export(&chapters, args.format, &args.file);
+    match args.output
+    {
+        | Some(ref path) => fs::write(path, export)?,
+        | None => print!("{}", export)
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn validate_file(args: &ValidateArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let mut diagnostics = Diagnostics::new();
+    dissector.validate(&mut diagnostics)?;
+
+    let counts = [Severity::Error, Severity::Warning, Severity::Info].map(|severity| diagnostics.count(severity));
+    match args.format
+    {
+        | _ if args.quiet == true => (),
+        | OutputFormat::Json =>
+        {
+            let document = serde_json::json!({
+                "file": args.file.display().to_string(),
+                "format": dissector.media_type(),
+                "valid": diagnostics.has_errors() == false,
+                "errors": counts[0],
+                "warnings": counts[1],
+                "infos": counts[2],
+                "findings": diagnostics.to_json()
+            });
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+        | _ =>
+        {
+            println!("{}", tr_fmt("Validating file: {}", &[&args.file.display()]));
+            println!("{}", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+
+            println!("\n{}", tr("Findings:").bright_cyan().bold());
+            if diagnostics.is_empty() == true
+            {
+                println!("  {}", tr("(none)"));
+            }
+            else
+            {
+                print!("{}", diagnostics);
+            }
+            println!("\n{}", tr_fmt("{} errors, {} warnings, {} notes", &[&counts[0], &counts[1], &counts[2]]));
+        }
+    }
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else if diagnostics.has_errors() == true
+    {
+        Ok(ExitStatus::Violations)
+    }
+    else if diagnostics.has_warnings() == true
+    {
+        Ok(ExitStatus::Warnings)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn summarize_file(args: &SummaryArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let summary = dissector.summary()?;
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary.to_json(&args.file))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Summary of file: {}", &[&args.file.display()]));
+            println!("{}\n", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+            if summary.is_empty() == true
+            {
+                println!("{}", tr("Nothing to summarize"));
+            }
+            else
+            {
+                print!("{}", summary);
+            }
+        }
+    }
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn print_layout(args: &LayoutArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let mut layout = dissector.layout()?;
+    layout.limit_depth(args.depth);
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&layout.to_json(&args.file))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Layout of file: {} ({} bytes)", &[&args.file.display(), &layout.file_size]));
+            println!("{}\n", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+            print!("{}", layout);
+        }
+    }
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn list_fragments(args: &FragmentsArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let timing = dissector.fragments()?;
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&timing.to_json(&args.file))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Fragments of file: {}", &[&args.file.display()]));
+            println!("{}\n", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+            if timing.fragments.is_empty() == true
+            {
+                println!("{}", tr("No movie fragments found"));
+            }
+            else
+            {
+                print!("{}", timing);
+            }
+        }
+    }
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else if timing.discontinuities.is_empty() == false
+    {
+        Ok(ExitStatus::Warnings)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn check_playlist(args: &PlaylistArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let playlist = Playlist::parse(&String::from_utf8_lossy(&fs::read(&args.file)?))?;
+
+    // Initialization segments are read once per EXT-X-MAP URI
+    let mut init_segments: Vec<(String, Result<InitSegment, String>)> = Vec::new();
+    let mut statuses = Vec::new();
+    for segment in &playlist.segments
+    {
+        let Some(path) = playlist::resolve(&args.file, &segment.uri)
+        else
+        {
+            statuses.push(SegmentStatus::Remote);
+            continue;
+        };
+        let init_segment = match segment.map
+        {
+            | Some(ref uri) =>
+            {
+                if init_segments.iter().any(|(candidate, _)| candidate == uri) == false
+                {
+                    let init_segment = match playlist::resolve(&args.file, uri)
+                    {
+                        | Some(init_path) => InitSegment::read(&init_path).map_err(|e| tr_fmt("Initialization segment {}: {}", &[&uri, &e])),
+                        | None => Err(tr_fmt("Initialization segment {}: remote, not fetched", &[&uri]))
+                    };
+                    init_segments.push((uri.clone(), init_segment));
+                }
+                match init_segments.iter().find(|(candidate, _)| candidate == uri).map(|(_, init_segment)| init_segment)
+                {
+                    | Some(Ok(init_segment)) => Some(init_segment),
+                    | Some(Err(e)) =>
+                    {
+                        statuses.push(SegmentStatus::Unreadable(e.clone()));
+                        continue;
+                    }
+                    | None => None
+                }
+            }
+            | None => None
+        };
+
+        let opened = match segment.byte_range
+        {
+            | Some((offset, length)) => Dissector::open_region(&path, offset, Some(length)),
+            | None => Dissector::open(&path)
+        };
+        let status = opened.and_then(|mut dissector| {
+            let media = dissector.segment_media(init_segment)?;
+            Ok(SegmentStatus::Dissected { media_type: dissector.media_type(), media })
+        });
+        statuses.push(status.unwrap_or_else(|e| SegmentStatus::Unreadable(e.to_string())));
+    }
+    let check = PlaylistCheck::new(playlist, statuses);
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&check.to_json(&args.file))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Playlist: {}", &[&args.file.display()]));
+            let target_duration = check.playlist.target_duration.map_or_else(|| "-".to_string(), |target_duration| target_duration.to_string());
+            println!("{}", tr_fmt("Target duration: {} s, media sequence {}", &[&target_duration, &check.playlist.media_sequence]));
+            match check.playlist.end_list
+            {
+                | true => println!("{}\n", tr_fmt("{} segments, complete (EXT-X-ENDLIST)", &[&check.playlist.segments.len()])),
+                | false => println!("{}\n", tr_fmt("{} segments, open for more (no EXT-X-ENDLIST)", &[&check.playlist.segments.len()]))
+            }
+            if check.playlist.segments.is_empty() == true
+            {
+                println!("{}", tr("No media segments found"));
+            }
+            else
+            {
+                print!("{}", check);
+            }
+        }
+    }
+
+    if check.diagnostics.has_errors() == true
+    {
+        Ok(ExitStatus::Violations)
+    }
+    else if check.diagnostics.has_warnings() == true
+    {
+        Ok(ExitStatus::Warnings)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn optimize_file(args: &OptimizeArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    if args.faststart == true
+    {
+        return match args.format
+        {
+            | OutputFormat::Text => faststart_file(&args.file),
+            | _ => Err(tr("--faststart only supports text output").into())
+        };
+    }
+    let mut dissector = Dissector::open(&args.file)?;
+    let report = dissector.optimization_report()?;
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report.to_json(&args.file))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Optimization report of file: {} ({} bytes)", &[&args.file.display(), &report.file_size]));
+            println!("{}\n", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+            if report.is_empty() == true
+            {
+                println!("{}", tr("No wasted space found"));
+            }
+            else
+            {
+                print!("{}", report);
+                let reclaimable = report.reclaimable();
+                println!("\n{}", tr_fmt("Reclaimable: {} bytes ({}% of the file)", &[&reclaimable, &format!("{:.1}", report.share(reclaimable))]));
+            }
+        }
+    }
+
+    if dissector.media_type() == "Unknown"
+    {
+        Ok(ExitStatus::UnknownFormat)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn faststart_file(file_path: &Path) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    if isobmff_writer::is_isobmff_file(file_path)? == false
+    {
+        return Err(tr_fmt("{} is not an ISOBMFF file (MP4, M4A, MOV, ...)", &[&file_path.display()]).into());
+    }
+
+    let FaststartReport { moved, moov_size, chunk_offsets } = isobmff_writer::faststart_file(file_path)?;
+    println!("{}", tr_fmt("Moving the movie box to the front of file: {}", &[&file_path.display()]));
+    match moved
+    {
+        | true => println!("  {}", tr_fmt("Movie box of {} bytes moved in front of the media data, {} chunk offsets updated", &[&moov_size, &chunk_offsets])),
+        | false => println!("{}", tr("The movie box already precedes the media data, the file was left untouched"))
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn find_in_file(args: &FindArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let search = dissector.find(&args.code)?;
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&search.to_json(&args.file, &args.code))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Searching '{}' in file: {}", &[&args.code, &args.file.display()]));
+            println!("{}", tr_fmt("Detected format: {} ({})", &[&dissector.media_type(), &tr(dissector.name())]));
+            if let Some(ref error) = search.walk_error
+            {
+                println!("{} {}", tr("Structure walk stopped:").yellow(), error);
+            }
+
+            println!();
+            if search.occurrences.is_empty() == true
+            {
+                println!("{}", tr("No occurrences found"));
+            }
+            else
+            {
+                print!("{}", search);
+                println!("\n{}", tr_fmt("{} occurrences, {} found only by the byte scan", &[&search.occurrences.len(), &search.scan_only()]));
+            }
+        }
+    }
+
+    if search.walk_error.is_some() == true
+    {
+        Ok(ExitStatus::Warnings)
+    }
+    else
+    {
+        Ok(ExitStatus::Success)
+    }
+}
+
+fn diff_files(args: &DiffArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let options = DissectOptions::default();
+    let mut reports = Vec::new();
+    for path in [&args.old, &args.new]
+    {
+        let mut diagnostics = Diagnostics::new();
+        reports.push(Dissector::open(path)?.report(&options, &mut diagnostics)?);
+    }
+    let comparison = Comparison::new(&reports[0], &reports[1]);
+
+    match args.format
+    {
+        | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&comparison.to_json(&args.old, &args.new))?),
+        | _ =>
+        {
+            println!("{}", tr_fmt("Comparing {} with {}", &[&args.old.display(), &args.new.display()]));
+            println!();
+            if comparison.is_identical() == true
+            {
+                println!("{}", tr("No structural differences"));
+            }
+            else
+            {
+                print!("{}", comparison);
+                let counts = [ChangeKind::Added, ChangeKind::Removed, ChangeKind::Changed, ChangeKind::Moved].map(|kind| comparison.count(kind));
+                println!("\n{}", tr_fmt("{} added, {} removed, {} changed, {} moved", &[&counts[0], &counts[1], &counts[2], &counts[3]]));
+            }
+        }
+    }
+
+    match comparison.is_identical()
+    {
+        | true => Ok(ExitStatus::Success),
+        | false => Ok(ExitStatus::Warnings)
+    }
+}
+
+fn edit_tag(args: &TagArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    // Frame or item keys with the value to set, None to remove them
+    let (file_path, changes): (&Path, Vec<(&str, Option<&str>)>) = match args.command
+    {
+        | TagCommands::Set(ref set) => (&set.file, set.frames.iter().map(|(frame, value)| (frame.as_str(), Some(value.as_str()))).collect()),
+        | TagCommands::Remove(ref remove) => (&remove.file, remove.frames.iter().map(|frame| (frame.as_str(), None)).collect()),
+        | TagCommands::Strip(ref strip) => return strip_tags(&strip.file, strip.id3v1),
+        | TagCommands::Convert(ref convert) => return convert_tag(&convert.file),
+        | TagCommands::Repad(ref repad) => return repad_tag(&repad.file, repad.padding)
+    };
+    if isobmff_writer::is_isobmff_file(file_path)? == true
+    {
+        return edit_itunes_metadata(file_path, &changes);
+    }
+
+    let edits = changes
+        .iter()
+        .map(|&(frame, value)| {
+            let frame: FrameSelector = frame.parse()?;
+            Ok(match value
+            {
+                | Some(value) => TagEdit::Set(frame, value.to_string()),
+                | None => TagEdit::Remove(frame)
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let report = writer::edit_file(file_path, &edits)?;
+    println!("{}", tr_fmt("Editing ID3v2.{} tag of file: {}", &[&report.major_version, &file_path.display()]));
+    print_edit_results(&changes, &report.results, false);
+
+    match report.tag_size
+    {
+        | Some(size) => print_tag_written(size, report.padding, report.dropped_extended_header),
+        | None => println!("{}", tr("Nothing changed, the file was left untouched"))
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn extract_from_file(args: &ExtractArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    match args.command
+    {
+        | ExtractCommands::Samples(ref samples) => extract_samples(samples),
+        | ExtractCommands::Audio(ref audio) => extract_audio(audio),
+        | ExtractCommands::Objects(ref objects) => extract_objects(objects)
+    }
+}
+
+fn extract_samples(args: &ExtractSamplesArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let mut locations = dissector.samples(args.track)?;
+    if locations.is_empty() == true
+    {
+        return Err(tr_fmt("No samples found for track {} in {}", &[&args.track, &args.file.display()]).into());
+    }
+    let total = locations.len();
+    if let Some(range) = args.range
+    {
+        locations.retain(|sample| range.contains(sample.number));
+        if locations.is_empty() == true
+        {
+            return Err(tr_fmt("Track {} has {} samples, none in the requested range", &[&args.track, &total]).into());
+        }
+    }
+
+    let paths = samples::extract(dissector.source(), &locations, args.track, &args.output)?;
+    println!("{}", tr_fmt("Extracting {} of {} samples of track {} from file: {}", &[&locations.len(), &total, &args.track, &args.file.display()]));
+    for (sample, path) in locations.iter().zip(&paths)
+    {
+        println!("  {}", tr_fmt("Sample {}: offset {}, {} bytes -> {}", &[&sample.number, &format!("0x{:08X}", sample.offset), &sample.size, &path.display()]));
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn extract_audio(args: &ExtractAudioArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let Some(stream) = dissector.audio_stream(args.track)?
+    else
+    {
+        return Err(tr_fmt("No track {} found in {}", &[&args.track, &args.file.display()]).into());
+    };
+
+    let path = match args.output
+    {
+        | Some(ref path) => path.clone(),
+        | None => PathBuf::from(format!("{}_track{}.{}", input_stem(&dissector), args.track, stream.extension()))
+    };
+    let mut output = BufWriter::new(fs::File::create(&path)?);
+    let size = stream.write(dissector.source(), &mut output)?;
+    output.flush()?;
+
+    println!(
+        "{}",
+        tr_fmt("Extracted {} samples of track {} ({}) to {} ({} bytes)", &[&stream.samples.len(), &args.track, &stream.description(), &path.display(), &size])
+    );
+
+    Ok(ExitStatus::Success)
+}
+
+fn extract_objects(args: &ExtractObjectsArgs) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let mut dissector = Dissector::open(&args.file)?;
+    let embedded = dissector.objects()?;
+    if embedded.is_empty() == true
+    {
+        return Err(tr_fmt("No embedded objects found in {}", &[&args.file.display()]).into());
+    }
+
+    let paths = objects::extract(&embedded, &args.output, &args.file)?;
+    println!("{}", tr_fmt("Extracting {} objects from file: {}", &[&embedded.len(), &args.file.display()]));
+    for (object, path) in embedded.iter().zip(&paths)
+    {
+        println!("  {}", tr_fmt("{} ({}, {} bytes)", &[&path.display(), &object.location, &object.data.len()]));
+    }
+    if let Some(manifest) = paths.last()
+    {
+        println!("{}", tr_fmt("Manifest: {}", &[&manifest.display()]));
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn edit_itunes_metadata(file_path: &Path, changes: &[(&str, Option<&str>)]) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let edits = changes
+        .iter()
+        .map(|&(key, value)| {
+            let key: ItunesKey = key.parse()?;
+            Ok(match value
+            {
+                | Some(value) => ItunesEdit::Set(key, value.to_string()),
+                | None => ItunesEdit::Remove(key)
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let report = isobmff_writer::edit_file(file_path, &edits)?;
+    println!("{}", tr_fmt("Editing iTunes metadata of file: {}", &[&file_path.display()]));
+    print_edit_results(changes, &report.results, true);
+
+    let Some(size) = report.moov_size
+    else
+    {
+        println!("{}", tr("Nothing changed, the file was left untouched"));
+        return Ok(ExitStatus::Success);
+    };
+    println!("{}", tr_fmt("Movie box written: {} bytes", &[&size]));
+    match report.placement
+    {
+        | MoovPlacement::InPlace => (),
+        | MoovPlacement::FreeSpace => println!("{}", tr("Free space next to the metadata absorbed the size change, the media data did not move")),
+        | MoovPlacement::Shifted { delta, chunk_offsets } =>
+            println!("{}", tr_fmt("The media data after the movie box moved by {} bytes, {} chunk offsets updated", &[&delta, &chunk_offsets]).yellow()),
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Print one line per edit of a tag command; `boxes` names the edited structures iTunes metadata boxes instead of ID3v2 frames
+fn print_edit_results(changes: &[(&str, Option<&str>)], results: &[EditResult], boxes: bool)
+{
+    for (&(frame, value), result) in changes.iter().zip(results)
+    {
+        let line = match (value, result)
+        {
+            | (Some(value), EditResult::Added) => tr_fmt("Added {}: \"{}\"", &[&frame, &value]),
+            | (Some(value), _) => tr_fmt("Replaced {}: \"{}\"", &[&frame, &value]),
+            | (None, EditResult::Removed(count)) => match (boxes, count)
+            {
+                | (false, 1) => tr_fmt("Removed {}: 1 frame", &[&frame]),
+                | (false, _) => tr_fmt("Removed {}: {} frames", &[&frame, count]),
+                | (true, 1) => tr_fmt("Removed {}: 1 box", &[&frame]),
+                | (true, _) => tr_fmt("Removed {}: {} boxes", &[&frame, count])
+            },
+            | (None, _) if boxes == true => tr_fmt("No {} box to remove", &[&frame]).yellow().to_string(),
+            | (None, _) => tr_fmt("No {} frame to remove", &[&frame]).yellow().to_string()
+        };
+        println!("  {}", line);
+    }
+}
+
+fn strip_tags(file_path: &Path, id3v1: bool) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let report = writer::strip_file(file_path, id3v1)?;
+    println!("{}", tr_fmt("Stripping tags of file: {}", &[&file_path.display()]));
+    match report.id3v2_length
+    {
+        | 0 => println!("  {}", tr("No ID3v2 tag found").yellow()),
+        | length => println!("  {}", tr_fmt("Removed ID3v2 tag: {} bytes", &[&length]))
+    }
+    match (id3v1, report.id3v1_length)
+    {
+        | (false, _) => (),
+        | (true, 0) => println!("  {}", tr("No ID3v1 tag found").yellow()),
+        | (true, length) => println!("  {}", tr_fmt("Removed ID3v1 tag: {} bytes", &[&length]))
+    }
+    if report.id3v2_length == 0 && report.id3v1_length == 0
+    {
+        println!("{}", tr("Nothing changed, the file was left untouched"));
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn convert_tag(file_path: &Path) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let report = writer::convert_file(file_path)?;
+    println!("{}", tr_fmt("Converting ID3v2.3 tag to ID3v2.4 of file: {}", &[&file_path.display()]));
+    for conversion in &report.conversions
+    {
+        let line = match conversion
+        {
+            | FrameConversion::Renamed(from, to) => tr_fmt("Renamed {} to {}", &[from, to]),
+            | FrameConversion::Merged(from, timestamp) => tr_fmt("Merged {} into TDRC: \"{}\"", &[&from.join(", "), timestamp]),
+            | FrameConversion::Dropped(id) => tr_fmt("Dropped {}: not defined in ID3v2.4", &[id]).yellow().to_string()
+        };
+        println!("  {}", line);
+    }
+    print_tag_written(report.tag_size, report.padding, report.dropped_extended_header);
+
+    Ok(ExitStatus::Success)
+}
+
+fn repad_tag(file_path: &Path, padding: u32) -> Result<ExitStatus, Box<dyn std::error::Error>>
+{
+    let report = writer::repad_file(file_path, padding)?;
+    println!("{}", tr_fmt("Repadding ID3v2.{} tag of file: {}", &[&report.major_version, &file_path.display()]));
+    println!("  {}", tr_fmt("Padding: {} bytes before, {} bytes requested", &[&report.original_padding, &report.padding]));
+
+    let Some(size) = report.tag_size
+    else
+    {
+        println!("{}", tr("Nothing changed, the file was left untouched"));
+        return Ok(ExitStatus::Success);
+    };
+    print_tag_written(size, report.padding, report.dropped_extended_header);
+    println!("{}", tr_fmt("The audio data moved by {} bytes", &[&report.shift]));
+
+    Ok(ExitStatus::Success)
+}
+
+fn print_tag_written(size: u32, padding: u32, dropped_extended_header: bool)
+{
+    println!("{}", tr_fmt("Tag written: {} bytes of frames and padding ({} bytes padding)", &[&size, &padding]));
+    if dropped_extended_header == true
+    {
+        println!("{}", tr("The extended header was dropped, its CRC would no longer match the frames").yellow());
+    }
+}
@@ -1,6 +1,38 @@
-use std::fs::File;
+use std::io::{self, Cursor, Write};
 
-use crate::cli::DissectOptions;
+use crate::{
+    artwork::Artwork,
+    chapters::Chapter,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::ProbeFormat,
+    find::Occurrence,
+    isobmff::{demux::AudioStream, fragments::TrackFragment, samples::SampleLocation, tree::InitSegment},
+    layout::Layout,
+    media_source::MediaSource,
+    metadata::MediaMetadata,
+    objects::EmbeddedObject,
+    options::DissectOptions,
+    playlist::SegmentMedia,
+    summary::Summary,
+    visitor::DissectionVisitor
+};
+
+/// How strongly the first bytes of a region indicate the format of a dissector, ordered from no to full match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence
+{
+    /// The region is not in this format
+    None,
+    /// A short signature that also occurs by chance, e.g. a single MPEG audio sync word
+    Low,
+    /// A plausible structure without a magic number, or a magic number found after leading junk
+    Medium,
+    /// A magic number at the start of the region
+    High,
+    /// A magic number confirmed by the structures following it, e.g. consecutive packets or frames
+    Certain
+}
 
 /// Common trait for all media file dissectors
 pub trait MediaDissector
@@ -8,11 +40,179 @@ pub trait MediaDissector
     /// The type of media this dissector handles
     fn media_type(&self) -> &'static str;
 
-    /// Dissect the media file with specific output options
-    fn dissect_with_options(&self, file: &mut File, options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>;
+    /// Dissect the media region starting at `start_offset` with specific output options, writing the text output to `output`
+    /// and reporting findings to `diagnostics`
+    ///
+    /// Returns the offset just past the region consumed by this dissector, so that the
+    /// remaining bytes can be re-probed for a following format.
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>;
+
+    /// Dissect the media region starting at `start_offset` into a structured report value (see `schema/report.schema.json`)
+    ///
+    /// Returns the report value and the offset just past the consumed region.
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>;
+
+    /// Dissect a payload held in memory (a tag in a chunk, an embedded picture or object) with specific output options
+    ///
+    /// Offsets in the output and in `diagnostics` are relative to the start of `data`. Returns the number of bytes consumed.
+    fn dissect_payload(&self, data: &[u8], options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics)
+    -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.dissect_with_options(&mut Cursor::new(data.to_vec()), 0, options, output, diagnostics)
+    }
+
+    /// Dissect a payload held in memory into a structured report value like `dissect_to_json`
+    ///
+    /// Returns the report value and the number of bytes consumed.
+    fn payload_to_json(&self, data: &[u8], options: &DissectOptions, diagnostics: &mut Diagnostics) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        self.dissect_to_json(&mut Cursor::new(data.to_vec()), 0, options, diagnostics)
+    }
+
+    /// Describe the media region starting at `start_offset` in ffprobe terms (container name, duration, streams)
+    ///
+    /// Returns None if the region carries no stream information, and the offset just past the consumed region.
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>;
+
+    /// Mark the byte ranges of all recognized structures in the region starting at `start_offset` as covered
+    ///
+    /// Returns the offset just past the consumed region.
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>;
+
+    /// Add the pictures embedded in the region starting at `start_offset` (cover art, chapter images) to `artwork`
+    ///
+    /// Returns the offset just past the consumed region. Formats without embedded pictures only skip the region.
+    fn collect_artwork(&self, file: &mut dyn MediaSource, start_offset: u64, _artwork: &mut Vec<Artwork>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the binary objects other than pictures (GEOB, PRIV and UFID payloads) of the region starting at `start_offset` to `objects`
+    ///
+    /// Returns the offset just past the consumed region. Formats without such objects only skip the region.
+    fn collect_objects(&self, file: &mut dyn MediaSource, start_offset: u64, _objects: &mut Vec<EmbeddedObject>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the chapters of the region starting at `start_offset` to `chapters`
+    ///
+    /// Returns the offset just past the consumed region. Formats without chapters only skip the region.
+    fn collect_chapters(&self, file: &mut dyn MediaSource, start_offset: u64, _chapters: &mut Vec<Chapter>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the positions of the samples of track `track_id` in the region starting at `start_offset` to `samples`
+    ///
+    /// Returns the offset just past the consumed region. Formats without numbered tracks only skip the region.
+    fn collect_samples(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _track_id: u32, _samples: &mut Vec<SampleLocation>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add track `track_id` of the region starting at `start_offset`, prepared for audio extraction, to `streams`
+    ///
+    /// Returns the offset just past the consumed region. Formats without numbered tracks only skip the region.
+    fn collect_audio_streams(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _track_id: u32, _streams: &mut Vec<AudioStream>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the track fragments of the movie fragments in the region starting at `start_offset` to `fragments`
+    ///
+    /// Returns the offset just past the consumed region. Formats without movie fragments only skip the region.
+    fn collect_fragments(&self, file: &mut dyn MediaSource, start_offset: u64, _fragments: &mut Vec<TrackFragment>) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the timing and encryption of the media segment in the region starting at `start_offset` to `media`
+    ///
+    /// Fragmented MP4 segments without a movie box take their track context from `init_segment`.
+    /// Returns the offset just past the consumed region. Formats other than HLS segments only skip the region.
+    fn collect_segment_media(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _init_segment: Option<&InitSegment>, _media: &mut Vec<SegmentMedia>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the normalized metadata of the region starting at `start_offset` to `metadata`
+    ///
+    /// Returns the offset just past the consumed region. Formats without descriptive metadata only skip the region.
+    fn collect_metadata(&self, file: &mut dyn MediaSource, start_offset: u64, _metadata: &mut MediaMetadata) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the summary lines of the region starting at `start_offset` (tracks) to `summary`
+    ///
+    /// Returns the offset just past the consumed region. Formats without a summary only skip the region.
+    fn collect_summary(&self, file: &mut dyn MediaSource, start_offset: u64, _summary: &mut Summary) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the boxes or frames of type `code` in the region starting at `start_offset` to `occurrences`
+    ///
+    /// Returns the offset just past the consumed region. Formats without boxes or frames only skip the region.
+    fn collect_occurrences(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _code: &str, _occurrences: &mut Vec<Occurrence>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Add the byte ranges of the region starting at `start_offset` and of its structures to `layout`
+    ///
+    /// Returns the offset just past the consumed region. Formats without a detailed layout add the
+    /// whole region under their media type, split from the APE, Lyrics3 and ID3v1 tags at its end.
+    fn collect_layout(&self, file: &mut dyn MediaSource, start_offset: u64, layout: &mut Layout) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let end_offset = self.add_coverage(file, start_offset, &mut Coverage::new())?;
+        let data_end = layout.add_trailing_tags(file, start_offset, end_offset)?;
+        layout.add(start_offset, data_end.saturating_sub(start_offset), 0, self.media_type());
+
+        Ok(end_offset)
+    }
+
+    /// Run specification conformance checks on the region starting at `start_offset`, reporting findings to `diagnostics`
+    ///
+    /// Returns the offset just past the consumed region. Formats without conformance checks only skip the region.
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, _diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.add_coverage(file, start_offset, &mut Coverage::new())
+    }
+
+    /// Walk the structures of the region starting at `start_offset` in file order, calling `visitor` for each of them and
+    /// reporting findings to `diagnostics`
+    ///
+    /// Returns the offset just past the consumed region. Formats without a structure walk only report the findings of their dissection.
+    fn visit(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _visitor: &mut dyn DissectionVisitor, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        self.dissect_with_options(file, start_offset, &DissectOptions::default(), &mut io::sink(), diagnostics)
+    }
+
+    /// Rate how likely a region starting with `header` is in this format; `remaining` is the number of bytes from the start
+    /// of the region to the end of the file, of which `header` holds up to `dissector_registry::SNIFF_SIZE`
+    fn sniff(&self, header: &[u8], remaining: u64) -> Confidence;
 
-    /// Check if this dissector can handle the given file header
-    fn can_handle(&self, header: &[u8]) -> bool;
+    /// Rate a region that follows a previously dissected one like `sniff`
+    fn sniff_chained(&self, header: &[u8], remaining: u64) -> Confidence
+    {
+        self.sniff(header, remaining)
+    }
 
     /// Get a descriptive name for this dissector
     fn name(&self) -> &'static str;
@@ -0,0 +1,64 @@
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+use crate::media_source::{ByteWindow, MediaSource};
+
+/// Read buffer size, large enough to cover typical box/frame headers plus their payloads in one read
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Buffered reader over a media source that tracks its position to avoid redundant seeks
+///
+/// Sequential reads are served from the buffer; forward skips within the buffer do not
+/// touch the underlying file, which keeps syscalls low on slow (network) filesystems.
+pub struct MediaReader<'a>
+{
+    inner:    BufReader<&'a mut dyn MediaSource>,
+    position: u64,
+    size:     u64
+}
+
+impl<'a> MediaReader<'a>
+{
+    /// Create a reader positioned at `offset`
+    pub fn new(file: &'a mut dyn MediaSource, offset: u64) -> std::io::Result<Self>
+    {
+        let size = file.size()?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self { inner: BufReader::with_capacity(BUFFER_SIZE, file), position: offset, size })
+    }
+
+    /// Total size of the underlying source in bytes
+    pub fn size(&self) -> u64
+    {
+        self.size
+    }
+
+    /// Move to an absolute offset, skipping the seek when already there
+    pub fn seek_to(&mut self, offset: u64) -> std::io::Result<()>
+    {
+        if offset != self.position
+        {
+            // seek_relative keeps the buffer when the target lies inside it
+            self.inner.seek_relative(offset as i64 - self.position as i64)?;
+            self.position = offset;
+        }
+        Ok(())
+    }
+
+    /// Map `length` bytes at `offset` without copying them through the buffer, positioned at the end of the range afterwards
+    pub fn map(&mut self, offset: u64, length: u64) -> std::io::Result<ByteWindow>
+    {
+        let window = self.inner.get_mut().map(offset, length)?;
+        // Resynchronize the buffer with the underlying source, whose position the mapping moved
+        self.inner.seek(SeekFrom::Start(offset + length))?;
+        self.position = offset + length;
+        Ok(window)
+    }
+
+    /// Read exactly `buffer.len()` bytes at the current position
+    pub fn read_exact(&mut self, buffer: &mut [u8]) -> std::io::Result<()>
+    {
+        self.inner.read_exact(buffer)?;
+        self.position += buffer.len() as u64;
+        Ok(())
+    }
+}
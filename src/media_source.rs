@@ -0,0 +1,218 @@
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    ops::Deref,
+    path::Path
+};
+
+use memmap2::{Mmap, MmapOptions};
+
+use crate::i18n::tr_fmt;
+
+/// Path that stands for standard input
+pub const STDIN_PATH: &str = "-";
+
+/// Bytes of a range of an input, memory-mapped where possible so large tags and boxes are not copied to the heap
+pub enum ByteWindow
+{
+    /// Mapped range of a file, pages are only loaded when touched
+    Mapped(Mmap),
+    /// Range read into memory
+    Owned(Vec<u8>)
+}
+
+impl Deref for ByteWindow
+{
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8]
+    {
+        match self
+        {
+            | ByteWindow::Mapped(map) => map,
+            | ByteWindow::Owned(data) => data
+        }
+    }
+}
+
+/// Seekable input of a dissection: a file, or data buffered in memory (e.g. read from stdin)
+pub trait MediaSource: Read + Seek
+{
+    /// Total size of the input in bytes
+    fn size(&mut self) -> io::Result<u64>;
+
+    /// Bytes from `offset` to `offset + length`, leaving the position at the end of the range like `read_exact`
+    ///
+    /// The default reads the range into memory.
+    fn map(&mut self, offset: u64, length: u64) -> io::Result<ByteWindow>
+    {
+        self.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0u8; length as usize];
+        self.read_exact(&mut data)?;
+        Ok(ByteWindow::Owned(data))
+    }
+}
+
+impl MediaSource for File
+{
+    fn size(&mut self) -> io::Result<u64>
+    {
+        Ok(self.metadata()?.len())
+    }
+
+    fn map(&mut self, offset: u64, length: u64) -> io::Result<ByteWindow>
+    {
+        if offset.checked_add(length).is_none_or(|end| end > self.size().unwrap_or(0)) == true
+        {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "range extends beyond the end of the file"));
+        }
+        if length == 0
+        {
+            self.seek(SeekFrom::Start(offset))?;
+            return Ok(ByteWindow::Owned(Vec::new()));
+        }
+
+        // SAFETY: the mapping is read-only; like the buffered readers it assumes the file is not
+        // truncated by another process while it is dissected
+        let map = unsafe { MmapOptions::new().offset(offset).len(length as usize).map(&*self)? };
+        self.seek(SeekFrom::Start(offset + length))?;
+        Ok(ByteWindow::Mapped(map))
+    }
+}
+
+impl MediaSource for Cursor<Vec<u8>>
+{
+    fn size(&mut self) -> io::Result<u64>
+    {
+        Ok(self.get_ref().len() as u64)
+    }
+}
+
+/// Buffer all data from `reader` (e.g. stdin or a pipe) in memory so it can be dissected with seeks
+pub fn buffer_reader(mut reader: impl Read) -> io::Result<Cursor<Vec<u8>>>
+{
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    Ok(Cursor::new(data))
+}
+
+/// Open a file for dissection; "-" buffers standard input, which may be a non-seekable pipe
+pub fn open(path: &Path) -> io::Result<Box<dyn MediaSource>>
+{
+    if path.as_os_str() == STDIN_PATH
+    {
+        Ok(Box::new(buffer_reader(io::stdin().lock())?))
+    }
+    else
+    {
+        Ok(Box::new(File::open(path)?))
+    }
+}
+
+/// Byte range of an input that is dissected as if it were a file of its own (`--offset`, `--length`)
+pub struct Region
+{
+    source:   Box<dyn MediaSource>,
+    offset:   u64,
+    length:   u64,
+    /// Read position relative to `offset`
+    position: u64
+}
+
+impl Region
+{
+    /// Restrict `source` to `length` bytes from `offset` (to the end of the input if `length` is None)
+    pub fn new(mut source: Box<dyn MediaSource>, offset: u64, length: Option<u64>) -> Result<Self, Box<dyn std::error::Error>>
+    {
+        let size = source.size()?;
+        if offset > size
+        {
+            return Err(tr_fmt("Offset {} is beyond the end of the input ({} bytes)", &[&offset, &size]).into());
+        }
+
+        let available = size - offset;
+        let length = match length
+        {
+            | Some(length) if length > available =>
+                return Err(tr_fmt("Region of {} bytes at offset {} exceeds the input ({} bytes)", &[&length, &offset, &size]).into()),
+            | Some(length) => length,
+            | None => available
+        };
+
+        Ok(Self { source, offset, length, position: 0 })
+    }
+
+    /// Offset of the region within the input
+    pub fn offset(&self) -> u64
+    {
+        self.offset
+    }
+
+    /// Length of the region in bytes
+    pub fn length(&self) -> u64
+    {
+        self.length
+    }
+}
+
+impl Read for Region
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+    {
+        let remaining = self.length.saturating_sub(self.position);
+        let count = (buf.len() as u64).min(remaining) as usize;
+        if count == 0
+        {
+            return Ok(0);
+        }
+
+        self.source.seek(SeekFrom::Start(self.offset + self.position))?;
+        let read = self.source.read(&mut buf[..count])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for Region
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64>
+    {
+        let position = match pos
+        {
+            | SeekFrom::Start(position) => Some(position),
+            | SeekFrom::End(delta) => self.length.checked_add_signed(delta),
+            | SeekFrom::Current(delta) => self.position.checked_add_signed(delta)
+        };
+
+        match position
+        {
+            | Some(position) =>
+            {
+                self.position = position;
+                Ok(position)
+            }
+            | None => Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position"))
+        }
+    }
+}
+
+impl MediaSource for Region
+{
+    fn size(&mut self) -> io::Result<u64>
+    {
+        Ok(self.length)
+    }
+
+    fn map(&mut self, offset: u64, length: u64) -> io::Result<ByteWindow>
+    {
+        if offset.checked_add(length).is_none_or(|end| end > self.length) == true
+        {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "range extends beyond the end of the region"));
+        }
+
+        let window = self.source.map(self.offset + offset, length)?;
+        self.position = offset + length;
+        Ok(window)
+    }
+}
@@ -0,0 +1,161 @@
+// Normalized metadata
+//
+// Format-independent view of the descriptive metadata of a file: ID3v2 frames and iTunes
+// metadata items are mapped to the same fields, so consumers of `--normalized` JSON output
+// (see `schema/metadata.schema.json`) do not need per-format mapping.
+
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{artwork::Artwork, chapters::Chapter, loudness::Loudness, report::SCHEMA_VERSION};
+
+/// Position within a set, e.g. track 3 of 12
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetPosition
+{
+    pub number: u32,
+    pub total:  Option<u32>
+}
+
+impl SetPosition
+{
+    /// Parse an ID3v2 TRCK/TPOS value ("3" or "3/12"), None if the number is missing or zero
+    pub fn parse(text: &str) -> Option<Self>
+    {
+        let (number, total) = match text.split_once('/')
+        {
+            | Some((number, total)) => (number, total.trim().parse().ok().filter(|&total| total > 0)),
+            | None => (text, None)
+        };
+        let number = number.trim().parse().ok().filter(|&number| number > 0)?;
+        Some(Self { number, total })
+    }
+
+    fn to_json(self) -> serde_json::Value
+    {
+        serde_json::json!({ "number": self.number, "total": self.total })
+    }
+}
+
+/// Descriptive metadata mapped from the format-specific tags of a file
+#[derive(Debug, Clone, Default)]
+pub struct MediaMetadata
+{
+    pub title:        Option<String>,
+    pub artist:       Option<String>,
+    pub album:        Option<String>,
+    pub album_artist: Option<String>,
+    /// Release or recording date as stored (e.g. "2024" or "2024-05-01")
+    pub date:         Option<String>,
+    /// Genre name, numeric ID3v1 genre references resolved
+    pub genre:        Option<String>,
+    pub comment:      Option<String>,
+    pub track:        Option<SetPosition>,
+    pub disc:         Option<SetPosition>,
+    pub artwork:      Vec<Artwork>,
+    pub chapters:     Vec<Chapter>,
+    /// Fields without a normalized equivalent, keyed by frame ID, item type or custom key
+    pub custom:       BTreeMap<String, String>,
+    /// ReplayGain, RVA2 and Sound Check values (shown by `--loudness`, not part of the normalized JSON)
+    pub loudness:     Vec<Loudness>
+}
+
+impl MediaMetadata
+{
+    /// Add the metadata of another layer: fields already set are kept, lists are appended
+    pub fn merge(&mut self, other: MediaMetadata)
+    {
+        let fields = [
+            (&mut self.title, other.title),
+            (&mut self.artist, other.artist),
+            (&mut self.album, other.album),
+            (&mut self.album_artist, other.album_artist),
+            (&mut self.date, other.date),
+            (&mut self.genre, other.genre),
+            (&mut self.comment, other.comment)
+        ];
+        for (field, value) in fields
+        {
+            if field.is_none() == true
+            {
+                *field = value;
+            }
+        }
+        self.track = self.track.or(other.track);
+        self.disc = self.disc.or(other.disc);
+        self.artwork.extend(other.artwork);
+        self.chapters.extend(other.chapters);
+        self.loudness.extend(other.loudness);
+        for (key, value) in other.custom
+        {
+            self.custom.entry(key).or_insert(value);
+        }
+    }
+
+    /// Set a custom field unless the key is already present
+    pub fn add_custom(&mut self, key: impl Into<String>, value: impl Into<String>)
+    {
+        self.custom.entry(key.into()).or_insert_with(|| value.into());
+    }
+
+    /// JSON document of `--format json --normalized` (see `schema/metadata.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "metadata": {
+                "title": self.title,
+                "artist": self.artist,
+                "album": self.album,
+                "album_artist": self.album_artist,
+                "date": self.date,
+                "genre": self.genre,
+                "comment": self.comment,
+                "track": self.track.map(SetPosition::to_json),
+                "disc": self.disc.map(SetPosition::to_json),
+                "artwork": self.artwork.iter().map(|picture| serde_json::json!({
+                    "location": picture.location,
+                    "mime_type": picture.mime_type,
+                    "description": picture.description,
+                    "size": picture.data.len()
+                })).collect::<Vec<_>>(),
+                "chapters": self.chapters.iter().map(|chapter| serde_json::json!({
+                    "start": chapter.start as f64 / 1000.0,
+                    "end": chapter.end as f64 / 1000.0,
+                    "title": chapter.title
+                })).collect::<Vec<_>>(),
+                "custom": self.custom
+            }
+        })
+    }
+}
+
+/// Decode base64 as stored in text fields: standard or URL-safe alphabet, padding optional, line breaks ignored
+///
+/// Returns None on other characters. Trailing bits that do not fill a byte are dropped.
+pub fn decode_base64(text: &str) -> Option<Vec<u8>>
+{
+    let mut data = Vec::with_capacity(text.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    for byte in text.trim_end_matches(['=', '\0']).bytes().filter(|byte| byte.is_ascii_whitespace() == false)
+    {
+        let value = match byte
+        {
+            | b'A'..=b'Z' => byte - b'A',
+            | b'a'..=b'z' => byte - b'a' + 26,
+            | b'0'..=b'9' => byte - b'0' + 52,
+            | b'+' | b'-' => 62,
+            | b'/' | b'_' => 63,
+            | _ => return None
+        };
+        bits = (bits << 6) | value as u32;
+        bit_count += 6;
+        if bit_count >= 8
+        {
+            bit_count -= 8;
+            data.push((bits >> bit_count) as u8);
+        }
+    }
+    Some(data)
+}
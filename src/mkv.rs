@@ -0,0 +1,23 @@
+// Matroska/WebM (EBML) dissection
+//
+// This module provides support for Matroska and WebM files based on the Extensible
+// Binary Meta Language (RFC 8794, RFC 9559). Supports hierarchical element parsing
+// including unknown-sized Segments and Clusters, typed element values, and stream
+// summaries of the Tracks element.
+
+// Core types and dissector
+pub mod dissector;
+pub mod element;
+pub mod element_ids;
+pub mod probe;
+
+// Re-export commonly used types for convenience
+pub use dissector::MatroskaDissector;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the Matroska dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<MatroskaDissector>(1000);
+}
@@ -0,0 +1,366 @@
+use std::{fmt, io::Write};
+
+use crate::{
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::ProbeFormat,
+    i18n::{tr, tr_fmt},
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    mkv::{
+        element::{EbmlElement, read_element_id, read_vint},
+        element_ids::{CLUSTER, EBML, ElementKind, SEGMENT, VOID, element_info, is_top_level},
+        probe::probe_elements
+    },
+    options::{DissectOptions, DumpLimit}
+};
+
+/// Maximum element nesting depth
+const MAX_DEPTH: usize = 20;
+
+/// Largest leaf payload read into memory (larger payloads such as attachments are skipped)
+const MAX_LEAF_DATA: u64 = 1024 * 1024;
+
+/// Bytes read from SimpleBlock/Block payloads (enough for track number, timestamp and flags)
+const BLOCK_HEADER_BYTES: u64 = 12;
+
+/// Wrapper for displaying an element tree with verbose option
+pub struct VerboseElementDisplay<'a>
+{
+    pub element: &'a EbmlElement,
+    pub verbose: bool,
+    /// Hexdump limit of `--dump`, None without hexdumps
+    pub dump:    Option<DumpLimit>
+}
+
+impl<'a> fmt::Display for VerboseElementDisplay<'a>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        fmt_element(f, self.element, 0, self.verbose, self.dump)
+    }
+}
+
+/// Format an element and its children
+fn fmt_element(f: &mut fmt::Formatter<'_>, element: &EbmlElement, indent: usize, verbose: bool, dump: Option<DumpLimit>) -> fmt::Result
+{
+    // Skip media data, cue index and padding unless verbose mode is enabled
+    if verbose == false && matches!(element.name(), "Cluster" | "Cues" | "Void" | "CRC-32")
+    {
+        return Ok(());
+    }
+
+    let indent_str = "    ".repeat(indent);
+    let element_info = format!("'{}' (0x{:X})", element.name(), element.id);
+    let offset_str = format!("0x{:08X}", element.offset);
+    let mut line = tr_fmt("Element at offset {}: {} - Size: {} bytes", &[
+        &offset_str,
+        &if element.is_master() == true
+        {
+            element_info.cyan().to_string()
+        }
+        else
+        {
+            element_info
+        },
+        &element.size
+    ]);
+    if element.unknown_size == true
+    {
+        line.push_str(&format!(" ({})", tr("unknown size")));
+    }
+    if let Some(value) = element.value()
+    {
+        line.push_str(&format!(" = {}", value));
+    }
+    writeln!(f, "{}{}", indent_str, line)?;
+
+    if let Some(limit) = dump &&
+        element.data.is_empty() == false
+    {
+        writeln!(f, "{}    {}", indent_str, tr("Raw data:"))?;
+        let hexdump = crate::hexdump::format_hexdump_limited(&element.data, 0, limit.bytes());
+        for line in hexdump.lines()
+        {
+            writeln!(f, "{}    {}", indent_str, line)?;
+        }
+        writeln!(f)?;
+    }
+
+    for child in &element.children
+    {
+        fmt_element(f, child, indent + 1, verbose, dump)?;
+    }
+
+    Ok(())
+}
+
+/// Matroska/WebM (EBML) dissector - unit struct
+#[derive(Default)]
+pub struct MatroskaDissector;
+
+impl MatroskaDissector
+{
+    /// Parse all top-level elements from `start_offset` and check the document type
+    fn read_elements(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Vec<EbmlElement>, u64), Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let (elements, end_offset) =
+            Self::parse_elements(&mut reader, start_offset, file_size, 0, None, diagnostics).map_err(|e| tr_fmt("Failed to parse EBML elements: {}", &[&e]))?;
+
+        if let Some(header) = elements.iter().find(|element| element.id == EBML)
+        {
+            match header.child_string("DocType")
+            {
+                | Some(doc_type) if doc_type == "matroska" || doc_type == "webm" =>
+                {}
+                | Some(doc_type) => diagnostics.info(Some(header.offset), tr_fmt("EBML document type '{}' is neither Matroska nor WebM", &[&doc_type])),
+                | None => diagnostics.warning(Some(header.offset), tr("EBML header has no DocType element"))
+            }
+        }
+        if elements.iter().any(|element| element.id == SEGMENT) == false
+        {
+            diagnostics.warning(Some(start_offset), tr("No Segment element found after the EBML header"));
+        }
+
+        Ok((elements, end_offset))
+    }
+
+    /// Parse elements between `start_offset` and `end_offset`
+    ///
+    /// `unknown_parent` is the ID of an enclosing element of unknown size, whose end is
+    /// implied by the first element that cannot be its child. Returns the elements and
+    /// the offset just past the last one.
+    fn parse_elements(
+        reader: &mut MediaReader, start_offset: u64, end_offset: u64, depth: usize, unknown_parent: Option<u32>, diagnostics: &mut Diagnostics
+    ) -> Result<(Vec<EbmlElement>, u64), String>
+    {
+        let mut elements = Vec::new();
+        let mut current_offset = start_offset;
+
+        if depth > MAX_DEPTH
+        {
+            return Err(tr("Maximum element nesting depth exceeded").to_string());
+        }
+
+        while current_offset < end_offset
+        {
+            reader.seek_to(current_offset).map_err(|e| tr_fmt("Seek error at offset {}: {}", &[&format!("0x{:08X}", current_offset), &e]))?;
+
+            // ID (up to 4 bytes) and size (up to 8 bytes)
+            let mut header = vec![0u8; (end_offset - current_offset).min(12) as usize];
+            reader.read_exact(&mut header).map_err(|e| tr_fmt("Failed to read element header at {}: {}", &[&format!("0x{:08X}", current_offset), &e]))?;
+
+            let parsed = read_element_id(&header).and_then(|(id, id_length)| read_vint(&header[id_length..]).map(|size| (id, id_length, size)));
+            let Some((id, id_length, (data_size, size_length, unknown_size))) = parsed
+            else
+            {
+                // Top-level garbage ends the EBML region; inside an element the rest of the parent is skipped
+                if depth > 0
+                {
+                    diagnostics.warning(Some(current_offset), tr("Invalid EBML element header, skipping the rest of the parent element"));
+                }
+                break;
+            };
+
+            // Data following the last top-level element belongs to another format
+            if depth == 0 && matches!(id, EBML | SEGMENT | VOID) == false
+            {
+                break;
+            }
+
+            // An unknown-sized Cluster ends at the next top-level element, an unknown-sized Segment at the next EBML header
+            match unknown_parent
+            {
+                | Some(CLUSTER) if is_top_level(id) == true => break,
+                | Some(SEGMENT) if matches!(id, EBML | SEGMENT) == true => break,
+                | _ =>
+                {}
+            }
+
+            let header_size = (id_length + size_length) as u64;
+            let data_offset = current_offset + header_size;
+            let is_master = element_info(id).is_some_and(|(_, kind)| kind == ElementKind::Master);
+
+            let mut element = EbmlElement { id, offset: current_offset, size: 0, header_size, unknown_size, children: Vec::new(), data: Vec::new() };
+
+            let data_end = if unknown_size == true
+            {
+                if is_master == false
+                {
+                    return Err(tr_fmt("Element '{}' at offset {} has unknown size but is not a master element", &[
+                        &element.name(),
+                        &format!("0x{:08X}", current_offset)
+                    ]));
+                }
+                end_offset
+            }
+            else if data_offset + data_size > end_offset
+            {
+                diagnostics.warning(
+                    Some(current_offset),
+                    tr_fmt("Element '{}' extends beyond its parent or the end of the file (size: {}, available: {})", &[
+                        &element.name(),
+                        &data_size,
+                        &end_offset.saturating_sub(data_offset)
+                    ])
+                );
+                end_offset
+            }
+            else
+            {
+                data_offset + data_size
+            };
+
+            if is_master == true
+            {
+                let parent = if unknown_size == true
+                {
+                    Some(id)
+                }
+                else
+                {
+                    None
+                };
+                let (children, children_end) = Self::parse_elements(reader, data_offset, data_end, depth + 1, parent, diagnostics)?;
+                element.children = children;
+                element.size = if unknown_size == true
+                {
+                    children_end - current_offset
+                }
+                else
+                {
+                    data_end - current_offset
+                };
+            }
+            else
+            {
+                element.size = data_end - current_offset;
+                let read_size = match element.name()
+                {
+                    | "SimpleBlock" | "Block" => element.data_size().min(BLOCK_HEADER_BYTES),
+                    | _ if element.data_size() <= MAX_LEAF_DATA => element.data_size(),
+                    | _ => 0
+                };
+                if read_size > 0
+                {
+                    reader.seek_to(data_offset).map_err(|e| tr_fmt("Seek error: {}", &[&e]))?;
+                    element.data = vec![0u8; read_size as usize];
+                    reader.read_exact(&mut element.data).map_err(|e| tr_fmt("Failed to read element data: {}", &[&e]))?;
+                }
+            }
+
+            current_offset += element.size;
+            elements.push(element);
+        }
+
+        Ok((elements, current_offset))
+    }
+}
+
+/// Mark elements as covered; the payload of Void elements only counts as far as it is zero-filled
+fn add_element_coverage(elements: &[EbmlElement], coverage: &mut Coverage)
+{
+    for element in elements
+    {
+        let data_offset = element.offset + element.header_size;
+        if element.is_master() == true
+        {
+            coverage.add(element.offset, data_offset);
+            add_element_coverage(&element.children, coverage);
+        }
+        else if element.is_padding() == true && element.data.len() as u64 == element.data_size()
+        {
+            coverage.add(element.offset, data_offset);
+            coverage.add_padding(data_offset, &element.data);
+        }
+        else
+        {
+            coverage.add(element.offset, element.offset + element.size);
+        }
+    }
+}
+
+impl MediaDissector for MatroskaDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "Matroska"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "Matroska/WebM Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let (elements, end_offset) = Self::read_elements(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("EBML Header:").bright_cyan().bold())?;
+
+            if let Some(header) = elements.first() &&
+                header.id == EBML
+            {
+                write!(output, "{}", VerboseElementDisplay { element: header, verbose: false, dump: None })?;
+            }
+
+            writeln!(output)?;
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "{}\n", tr("Element Structure:").bright_cyan().bold())?;
+
+            for element in &elements
+            {
+                write!(output, "{}", VerboseElementDisplay { element, verbose: options.show_verbose, dump: options.show_dump.then_some(options.dump_limit) })?;
+            }
+        }
+
+        Ok(end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let (elements, end_offset) = Self::read_elements(file, start_offset, diagnostics)?;
+
+        Ok((serde_json::json!({ "elements": elements.iter().map(EbmlElement::to_json).collect::<Vec<_>>() }), end_offset))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let (elements, end_offset) = Self::read_elements(file, start_offset, diagnostics)?;
+
+        Ok((Some(probe_elements(&elements)), end_offset))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let (elements, end_offset) = Self::read_elements(file, start_offset, &mut Diagnostics::new())?;
+        add_element_coverage(&elements, coverage);
+
+        Ok(end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // EBML header element ID
+        match header.starts_with(&[0x1A, 0x45, 0xDF, 0xA3])
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
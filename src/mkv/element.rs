@@ -0,0 +1,279 @@
+use crate::{
+    i18n::tr_fmt,
+    id3v2::frames::chapter::format_timestamp,
+    mkv::element_ids::{ElementKind, VOID, element_info}
+};
+
+/// Seconds between the Unix epoch and the Matroska date epoch (2001-01-01T00:00:00 UTC)
+const MATROSKA_EPOCH_OFFSET: i64 = 978_307_200;
+
+/// Represents an EBML element
+#[derive(Debug, Clone)]
+pub struct EbmlElement
+{
+    pub id:           u32,
+    pub offset:       u64,
+    /// Size including the ID and size fields (resolved for unknown-sized elements)
+    pub size:         u64,
+    /// Length of the ID and size fields
+    pub header_size:  u64,
+    /// Whether the size field holds the reserved "unknown size" value
+    pub unknown_size: bool,
+    pub children:     Vec<EbmlElement>,
+    /// Payload of leaf elements (only the block header for SimpleBlock/Block, empty for large payloads)
+    pub data:         Vec<u8>
+}
+
+/// Read an EBML variable-length integer, returning (value without marker, length, all value bits set)
+pub fn read_vint(bytes: &[u8]) -> Option<(u64, usize, bool)>
+{
+    let first = *bytes.first()?;
+    if first == 0
+    {
+        return None;
+    }
+
+    let length = first.leading_zeros() as usize + 1;
+    let data = bytes.get(..length)?;
+    let mut value = (first as u64) & (0xFF >> length);
+    for &byte in &data[1..]
+    {
+        value = (value << 8) | byte as u64;
+    }
+
+    let all_ones = value == (1u64 << (7 * length)) - 1;
+    Some((value, length, all_ones))
+}
+
+/// Read an EBML element ID (marker bits are part of the ID), returning (ID, length)
+pub fn read_element_id(bytes: &[u8]) -> Option<(u32, usize)>
+{
+    let first = *bytes.first()?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > 4
+    {
+        return None;
+    }
+
+    let data = bytes.get(..length)?;
+    Some((data.iter().fold(0u32, |id, &byte| (id << 8) | byte as u32), length))
+}
+
+/// Read a big-endian unsigned integer of 0-8 bytes
+pub fn read_unsigned(data: &[u8]) -> u64
+{
+    data.iter().take(8).fold(0u64, |value, &byte| (value << 8) | byte as u64)
+}
+
+/// Read a big-endian float of 4 or 8 bytes (0 bytes means 0.0)
+pub fn read_float(data: &[u8]) -> Option<f64>
+{
+    match data.len()
+    {
+        | 0 => Some(0.0),
+        | 4 => Some(f32::from_be_bytes([data[0], data[1], data[2], data[3]]) as f64),
+        | 8 => Some(f64::from_be_bytes([data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7]])),
+        | _ => None
+    }
+}
+
+/// Format Unix seconds as an ISO 8601 UTC date
+pub fn format_unix_time(seconds: i64) -> String
+{
+    let days = seconds.div_euclid(86_400);
+    let time = seconds.rem_euclid(86_400);
+
+    // Civil date from day count (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10
+    {
+        month_index + 3
+    }
+    else
+    {
+        month_index - 9
+    };
+    let year = year_of_era +
+        era * 400 +
+        if month <= 2
+        {
+            1
+        }
+        else
+        {
+            0
+        };
+
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, (time % 3600) / 60, time % 60)
+}
+
+/// Describe a TrackType value
+fn track_type_name(value: u64) -> &'static str
+{
+    match value
+    {
+        | 1 => "video",
+        | 2 => "audio",
+        | 3 => "complex",
+        | 0x10 => "logo",
+        | 0x11 => "subtitle",
+        | 0x12 => "buttons",
+        | 0x20 => "control",
+        | 0x21 => "metadata",
+        | _ => "unknown"
+    }
+}
+
+impl EbmlElement
+{
+    /// Element name from the Matroska specification, "Unknown" for unlisted IDs
+    pub fn name(&self) -> &'static str
+    {
+        element_info(self.id).map(|(name, _)| name).unwrap_or("Unknown")
+    }
+
+    /// Value type, unknown elements are treated as binary
+    pub fn kind(&self) -> ElementKind
+    {
+        element_info(self.id).map(|(_, kind)| kind).unwrap_or(ElementKind::Binary)
+    }
+
+    pub fn is_master(&self) -> bool
+    {
+        self.kind() == ElementKind::Master
+    }
+
+    /// Size of the element payload
+    pub fn data_size(&self) -> u64
+    {
+        self.size - self.header_size
+    }
+
+    /// Find the first direct child with the given element name
+    pub fn child(&self, name: &str) -> Option<&EbmlElement>
+    {
+        self.children.iter().find(|child| child.name() == name)
+    }
+
+    /// Value of an unsigned integer child
+    pub fn child_unsigned(&self, name: &str) -> Option<u64>
+    {
+        self.child(name).map(|child| read_unsigned(&child.data))
+    }
+
+    /// Value of a float child
+    pub fn child_float(&self, name: &str) -> Option<f64>
+    {
+        self.child(name).and_then(|child| read_float(&child.data))
+    }
+
+    /// Value of a string child
+    pub fn child_string(&self, name: &str) -> Option<String>
+    {
+        self.child(name).map(|child| String::from_utf8_lossy(&child.data).trim_end_matches('\0').to_string())
+    }
+
+    /// Human-readable value of a leaf element, None for master elements and unread payloads
+    pub fn value(&self) -> Option<String>
+    {
+        let data = &self.data;
+        let value = match self.kind()
+        {
+            | ElementKind::Master => return None,
+            | ElementKind::UnsignedInt =>
+            {
+                let value = read_unsigned(data);
+                match self.name()
+                {
+                    | "TrackType" => format!("{} ({})", value, track_type_name(value)),
+                    | "ChapterTimeStart" | "ChapterTimeEnd" => format!("{} ns ({})", value, format_timestamp((value / 1_000_000).min(u32::MAX as u64) as u32)),
+                    | _ => value.to_string()
+                }
+            }
+            | ElementKind::SignedInt =>
+            {
+                // Sign-extend from the stored width
+                let shift = 64 - 8 * data.len().clamp(1, 8) as u32;
+                (((read_unsigned(data) << shift) as i64) >> shift).to_string()
+            }
+            | ElementKind::Float => match read_float(data)
+            {
+                | Some(value) => value.to_string(),
+                | None => tr_fmt("invalid float of {} bytes", &[&data.len()])
+            },
+            | ElementKind::String | ElementKind::Utf8 => format!("\"{}\"", String::from_utf8_lossy(data).trim_end_matches('\0')),
+            | ElementKind::Date =>
+            {
+                if data.len() != 8
+                {
+                    return Some(tr_fmt("invalid date of {} bytes", &[&data.len()]));
+                }
+                let nanoseconds = read_unsigned(data) as i64;
+                format_unix_time(MATROSKA_EPOCH_OFFSET + nanoseconds.div_euclid(1_000_000_000))
+            }
+            | ElementKind::Binary => match self.name()
+            {
+                | "SimpleBlock" | "Block" => self.block_header()?,
+                | "SeekID" =>
+                {
+                    let id = read_unsigned(data) as u32;
+                    format!("0x{:X} ({})", id, element_info(id).map(|(name, _)| name).unwrap_or("Unknown"))
+                }
+                | _ if data.is_empty() == false && data.len() <= 16 => data.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(" "),
+                | _ => tr_fmt("{} bytes", &[&self.data_size()])
+            }
+        };
+
+        Some(value)
+    }
+
+    /// Describe the header of a SimpleBlock/Block (track number, relative timestamp, flags)
+    fn block_header(&self) -> Option<String>
+    {
+        let (track, length, _) = read_vint(&self.data)?;
+        let timestamp = i16::from_be_bytes([*self.data.get(length)?, *self.data.get(length + 1)?]);
+        let flags = *self.data.get(length + 2)?;
+
+        let mut description = tr_fmt("track {}, timestamp {}", &[&track, &format!("{:+}", timestamp)]);
+        if self.name() == "SimpleBlock" && flags & 0x80 != 0
+        {
+            description.push_str(", keyframe");
+        }
+        match (flags >> 1) & 0x03
+        {
+            | 1 => description.push_str(", Xiph lacing"),
+            | 2 => description.push_str(", fixed-size lacing"),
+            | 3 => description.push_str(", EBML lacing"),
+            | _ =>
+            {}
+        }
+        Some(description)
+    }
+
+    /// Check if the payload of this element is padding
+    pub fn is_padding(&self) -> bool
+    {
+        self.id == VOID
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "id": format!("0x{:X}", self.id),
+            "name": self.name(),
+            "offset": self.offset,
+            "size": self.size,
+            "header_size": self.header_size,
+            "unknown_size": self.unknown_size,
+            "value": self.value(),
+            "children": self.children.iter().map(EbmlElement::to_json).collect::<Vec<_>>()
+        })
+    }
+}
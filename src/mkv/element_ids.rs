@@ -0,0 +1,244 @@
+/// Value type of an EBML element (Matroska specification, RFC 9559)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind
+{
+    Master,
+    UnsignedInt,
+    SignedInt,
+    Float,
+    /// Printable ASCII string
+    String,
+    Utf8,
+    /// Nanoseconds since 2001-01-01T00:00:00 UTC
+    Date,
+    Binary
+}
+
+/// Element ID of the EBML header
+pub const EBML: u32 = 0x1A45_DFA3;
+/// Element ID of the Segment
+pub const SEGMENT: u32 = 0x1853_8067;
+/// Element ID of a Cluster
+pub const CLUSTER: u32 = 0x1F43_B675;
+/// Element ID of a Void (padding) element
+pub const VOID: u32 = 0xEC;
+
+/// Get name and value type of a known element ID
+pub fn element_info(id: u32) -> Option<(&'static str, ElementKind)>
+{
+    use ElementKind::*;
+
+    let info = match id
+    {
+        // EBML header and global elements
+        | EBML => ("EBML", Master),
+        | 0x4286 => ("EBMLVersion", UnsignedInt),
+        | 0x42F7 => ("EBMLReadVersion", UnsignedInt),
+        | 0x42F2 => ("EBMLMaxIDLength", UnsignedInt),
+        | 0x42F3 => ("EBMLMaxSizeLength", UnsignedInt),
+        | 0x4282 => ("DocType", String),
+        | 0x4287 => ("DocTypeVersion", UnsignedInt),
+        | 0x4285 => ("DocTypeReadVersion", UnsignedInt),
+        | VOID => ("Void", Binary),
+        | 0xBF => ("CRC-32", Binary),
+
+        // Segment and meta seek
+        | SEGMENT => ("Segment", Master),
+        | 0x114D_9B74 => ("SeekHead", Master),
+        | 0x4DBB => ("Seek", Master),
+        | 0x53AB => ("SeekID", Binary),
+        | 0x53AC => ("SeekPosition", UnsignedInt),
+
+        // Segment information
+        | 0x1549_A966 => ("Info", Master),
+        | 0x73A4 => ("SegmentUUID", Binary),
+        | 0x7384 => ("SegmentFilename", Utf8),
+        | 0x3CB923 => ("PrevUUID", Binary),
+        | 0x3EB923 => ("NextUUID", Binary),
+        | 0x2AD7B1 => ("TimestampScale", UnsignedInt),
+        | 0x4489 => ("Duration", Float),
+        | 0x4461 => ("DateUTC", Date),
+        | 0x7BA9 => ("Title", Utf8),
+        | 0x4D80 => ("MuxingApp", Utf8),
+        | 0x5741 => ("WritingApp", Utf8),
+
+        // Clusters and blocks
+        | CLUSTER => ("Cluster", Master),
+        | 0xE7 => ("Timestamp", UnsignedInt),
+        | 0xA7 => ("Position", UnsignedInt),
+        | 0xAB => ("PrevSize", UnsignedInt),
+        | 0xA3 => ("SimpleBlock", Binary),
+        | 0xA0 => ("BlockGroup", Master),
+        | 0xA1 => ("Block", Binary),
+        | 0x75A1 => ("BlockAdditions", Master),
+        | 0xA6 => ("BlockMore", Master),
+        | 0xA5 => ("BlockAdditional", Binary),
+        | 0xEE => ("BlockAddID", UnsignedInt),
+        | 0x9B => ("BlockDuration", UnsignedInt),
+        | 0xFA => ("ReferencePriority", UnsignedInt),
+        | 0xFB => ("ReferenceBlock", SignedInt),
+        | 0xA4 => ("CodecState", Binary),
+        | 0x75A2 => ("DiscardPadding", SignedInt),
+
+        // Tracks
+        | 0x1654_AE6B => ("Tracks", Master),
+        | 0xAE => ("TrackEntry", Master),
+        | 0xD7 => ("TrackNumber", UnsignedInt),
+        | 0x73C5 => ("TrackUID", UnsignedInt),
+        | 0x83 => ("TrackType", UnsignedInt),
+        | 0xB9 => ("FlagEnabled", UnsignedInt),
+        | 0x88 => ("FlagDefault", UnsignedInt),
+        | 0x55AA => ("FlagForced", UnsignedInt),
+        | 0x55AB => ("FlagHearingImpaired", UnsignedInt),
+        | 0x55AC => ("FlagVisualImpaired", UnsignedInt),
+        | 0x55AD => ("FlagTextDescriptions", UnsignedInt),
+        | 0x55AE => ("FlagOriginal", UnsignedInt),
+        | 0x55AF => ("FlagCommentary", UnsignedInt),
+        | 0x9C => ("FlagLacing", UnsignedInt),
+        | 0x23E383 => ("DefaultDuration", UnsignedInt),
+        | 0x234E7A => ("DefaultDecodedFieldDuration", UnsignedInt),
+        | 0x55EE => ("MaxBlockAdditionID", UnsignedInt),
+        | 0x41E4 => ("BlockAdditionMapping", Master),
+        | 0x536E => ("Name", Utf8),
+        | 0x22B59C => ("Language", String),
+        | 0x22B59D => ("LanguageBCP47", String),
+        | 0x86 => ("CodecID", String),
+        | 0x63A2 => ("CodecPrivate", Binary),
+        | 0x258688 => ("CodecName", Utf8),
+        | 0x56AA => ("CodecDelay", UnsignedInt),
+        | 0x56BB => ("SeekPreRoll", UnsignedInt),
+        | 0x7446 => ("AttachmentLink", UnsignedInt),
+        | 0xAA => ("CodecDecodeAll", UnsignedInt),
+        | 0x6FAB => ("TrackOverlay", UnsignedInt),
+        | 0x6624 => ("TrackTranslate", Master),
+
+        // Video settings
+        | 0xE0 => ("Video", Master),
+        | 0x9A => ("FlagInterlaced", UnsignedInt),
+        | 0x9D => ("FieldOrder", UnsignedInt),
+        | 0x53B8 => ("StereoMode", UnsignedInt),
+        | 0x53C0 => ("AlphaMode", UnsignedInt),
+        | 0xB0 => ("PixelWidth", UnsignedInt),
+        | 0xBA => ("PixelHeight", UnsignedInt),
+        | 0x54AA => ("PixelCropBottom", UnsignedInt),
+        | 0x54BB => ("PixelCropTop", UnsignedInt),
+        | 0x54CC => ("PixelCropLeft", UnsignedInt),
+        | 0x54DD => ("PixelCropRight", UnsignedInt),
+        | 0x54B0 => ("DisplayWidth", UnsignedInt),
+        | 0x54BA => ("DisplayHeight", UnsignedInt),
+        | 0x54B2 => ("DisplayUnit", UnsignedInt),
+        | 0x2EB524 => ("UncompressedFourCC", Binary),
+        | 0x55B0 => ("Colour", Master),
+        | 0x55B1 => ("MatrixCoefficients", UnsignedInt),
+        | 0x55B2 => ("BitsPerChannel", UnsignedInt),
+        | 0x55B9 => ("Range", UnsignedInt),
+        | 0x55BA => ("TransferCharacteristics", UnsignedInt),
+        | 0x55BB => ("Primaries", UnsignedInt),
+        | 0x55BC => ("MaxCLL", UnsignedInt),
+        | 0x55BD => ("MaxFALL", UnsignedInt),
+        | 0x55D0 => ("MasteringMetadata", Master),
+        | 0x7670 => ("Projection", Master),
+
+        // Audio settings
+        | 0xE1 => ("Audio", Master),
+        | 0xB5 => ("SamplingFrequency", Float),
+        | 0x78B5 => ("OutputSamplingFrequency", Float),
+        | 0x9F => ("Channels", UnsignedInt),
+        | 0x6264 => ("BitDepth", UnsignedInt),
+        | 0x52F1 => ("Emphasis", UnsignedInt),
+
+        // Content encoding (compression/encryption)
+        | 0x6D80 => ("ContentEncodings", Master),
+        | 0x6240 => ("ContentEncoding", Master),
+        | 0x5031 => ("ContentEncodingOrder", UnsignedInt),
+        | 0x5032 => ("ContentEncodingScope", UnsignedInt),
+        | 0x5033 => ("ContentEncodingType", UnsignedInt),
+        | 0x5034 => ("ContentCompression", Master),
+        | 0x4254 => ("ContentCompAlgo", UnsignedInt),
+        | 0x4255 => ("ContentCompSettings", Binary),
+        | 0x5035 => ("ContentEncryption", Master),
+        | 0x47E1 => ("ContentEncAlgo", UnsignedInt),
+        | 0x47E2 => ("ContentEncKeyID", Binary),
+
+        // Cueing data
+        | 0x1C53_BB6B => ("Cues", Master),
+        | 0xBB => ("CuePoint", Master),
+        | 0xB3 => ("CueTime", UnsignedInt),
+        | 0xB7 => ("CueTrackPositions", Master),
+        | 0xF7 => ("CueTrack", UnsignedInt),
+        | 0xF1 => ("CueClusterPosition", UnsignedInt),
+        | 0xF0 => ("CueRelativePosition", UnsignedInt),
+        | 0xB2 => ("CueDuration", UnsignedInt),
+        | 0x5378 => ("CueBlockNumber", UnsignedInt),
+        | 0xEA => ("CueCodecState", UnsignedInt),
+        | 0xDB => ("CueReference", Master),
+        | 0x96 => ("CueRefTime", UnsignedInt),
+
+        // Attachments
+        | 0x1941_A469 => ("Attachments", Master),
+        | 0x61A7 => ("AttachedFile", Master),
+        | 0x467E => ("FileDescription", Utf8),
+        | 0x466E => ("FileName", Utf8),
+        | 0x4660 => ("FileMediaType", String),
+        | 0x465C => ("FileData", Binary),
+        | 0x46AE => ("FileUID", UnsignedInt),
+
+        // Chapters
+        | 0x1043_A770 => ("Chapters", Master),
+        | 0x45B9 => ("EditionEntry", Master),
+        | 0x45BC => ("EditionUID", UnsignedInt),
+        | 0x45BD => ("EditionFlagHidden", UnsignedInt),
+        | 0x45DB => ("EditionFlagDefault", UnsignedInt),
+        | 0x45DD => ("EditionFlagOrdered", UnsignedInt),
+        | 0x4520 => ("EditionDisplay", Master),
+        | 0x4521 => ("EditionString", Utf8),
+        | 0x45E4 => ("EditionLanguageIETF", String),
+        | 0xB6 => ("ChapterAtom", Master),
+        | 0x73C4 => ("ChapterUID", UnsignedInt),
+        | 0x5654 => ("ChapterStringUID", Utf8),
+        | 0x91 => ("ChapterTimeStart", UnsignedInt),
+        | 0x92 => ("ChapterTimeEnd", UnsignedInt),
+        | 0x98 => ("ChapterFlagHidden", UnsignedInt),
+        | 0x4598 => ("ChapterFlagEnabled", UnsignedInt),
+        | 0x6E67 => ("ChapterSegmentUUID", Binary),
+        | 0x4588 => ("ChapterSkipType", UnsignedInt),
+        | 0x6EBC => ("ChapterSegmentEditionUID", UnsignedInt),
+        | 0x63C3 => ("ChapterPhysicalEquiv", UnsignedInt),
+        | 0x8F => ("ChapterTrack", Master),
+        | 0x89 => ("ChapterTrackUID", UnsignedInt),
+        | 0x80 => ("ChapterDisplay", Master),
+        | 0x85 => ("ChapString", Utf8),
+        | 0x437C => ("ChapLanguage", String),
+        | 0x437D => ("ChapLanguageBCP47", String),
+        | 0x437E => ("ChapCountry", String),
+        | 0x6944 => ("ChapProcess", Master),
+
+        // Tags
+        | 0x1254_C367 => ("Tags", Master),
+        | 0x7373 => ("Tag", Master),
+        | 0x63C0 => ("Targets", Master),
+        | 0x68CA => ("TargetTypeValue", UnsignedInt),
+        | 0x63CA => ("TargetType", String),
+        | 0x63C5 => ("TagTrackUID", UnsignedInt),
+        | 0x63C9 => ("TagEditionUID", UnsignedInt),
+        | 0x63C4 => ("TagChapterUID", UnsignedInt),
+        | 0x63C6 => ("TagAttachmentUID", UnsignedInt),
+        | 0x67C8 => ("SimpleTag", Master),
+        | 0x45A3 => ("TagName", Utf8),
+        | 0x447A => ("TagLanguage", String),
+        | 0x447B => ("TagLanguageBCP47", String),
+        | 0x4484 => ("TagDefault", UnsignedInt),
+        | 0x4487 => ("TagString", Utf8),
+        | 0x4485 => ("TagBinary", Binary),
+
+        | _ => return None
+    };
+
+    Some(info)
+}
+
+/// Check if an element is a direct child of the Segment (ends an unknown-sized Cluster)
+pub fn is_top_level(id: u32) -> bool
+{
+    matches!(id, 0x114D_9B74 | 0x1549_A966 | 0x1654_AE6B | CLUSTER | 0x1C53_BB6B | 0x1941_A469 | 0x1043_A770 | 0x1254_C367 | EBML | SEGMENT)
+}
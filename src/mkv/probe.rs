@@ -0,0 +1,106 @@
+use crate::{
+    ffprobe::{ProbeFormat, ProbeStream},
+    mkv::element::EbmlElement
+};
+
+/// Default TimestampScale (1 ms) when the Info element does not set one
+const DEFAULT_TIMESTAMP_SCALE: u64 = 1_000_000;
+
+/// ffmpeg codec name and long name for a Matroska CodecID
+fn codec_names(codec_id: &str) -> Option<(&'static str, &'static str)>
+{
+    // AAC profiles are encoded in the CodecID suffix (A_AAC/MPEG4/LC etc.)
+    if codec_id.starts_with("A_AAC") == true
+    {
+        return Some(("aac", "AAC (Advanced Audio Coding)"));
+    }
+
+    match codec_id
+    {
+        | "V_MPEG4/ISO/AVC" => Some(("h264", "H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10")),
+        | "V_MPEGH/ISO/HEVC" => Some(("hevc", "H.265 / HEVC (High Efficiency Video Coding)")),
+        | "V_AV1" => Some(("av1", "Alliance for Open Media AV1")),
+        | "V_VP8" => Some(("vp8", "On2 VP8")),
+        | "V_VP9" => Some(("vp9", "Google VP9")),
+        | "V_MPEG4/ISO/ASP" | "V_MPEG4/ISO/SP" | "V_MPEG4/ISO/AP" => Some(("mpeg4", "MPEG-4 part 2")),
+        | "V_MPEG2" => Some(("mpeg2video", "MPEG-2 video")),
+        | "V_MJPEG" => Some(("mjpeg", "Motion JPEG")),
+        | "V_THEORA" => Some(("theora", "Theora")),
+        | "V_PRORES" => Some(("prores", "Apple ProRes (iCodec Pro)")),
+        | "A_OPUS" => Some(("opus", "Opus (Opus Interactive Audio Codec)")),
+        | "A_VORBIS" => Some(("vorbis", "Vorbis")),
+        | "A_FLAC" => Some(("flac", "FLAC (Free Lossless Audio Codec)")),
+        | "A_MPEG/L3" => Some(("mp3", "MP3 (MPEG audio layer 3)")),
+        | "A_MPEG/L2" => Some(("mp2", "MP2 (MPEG audio layer 2)")),
+        | "A_AC3" => Some(("ac3", "ATSC A/52A (AC-3)")),
+        | "A_EAC3" => Some(("eac3", "ATSC A/52B (AC-3, E-AC-3)")),
+        | "A_DTS" => Some(("dts", "DCA (DTS Coherent Acoustics)")),
+        | "A_TRUEHD" => Some(("truehd", "TrueHD")),
+        | "A_ALAC" => Some(("alac", "ALAC (Apple Lossless Audio Codec)")),
+        | "S_TEXT/UTF8" => Some(("subrip", "SubRip subtitle")),
+        | "S_TEXT/ASS" | "S_TEXT/SSA" => Some(("ass", "ASS (Advanced SSA) subtitle")),
+        | "S_TEXT/WEBVTT" => Some(("webvtt", "WebVTT subtitle")),
+        | "S_HDMV/PGS" => Some(("hdmv_pgs_subtitle", "HDMV Presentation Graphic Stream subtitles")),
+        | "S_VOBSUB" => Some(("dvd_subtitle", "DVD subtitles")),
+        | _ => None
+    }
+}
+
+/// ffprobe codec type for a TrackType value
+fn codec_type(track_type: Option<u64>) -> &'static str
+{
+    match track_type
+    {
+        | Some(1) => "video",
+        | Some(2) => "audio",
+        | Some(0x11) => "subtitle",
+        | _ => "data"
+    }
+}
+
+/// Describe one TrackEntry element as an ffprobe stream
+fn probe_track(track: &EbmlElement) -> ProbeStream
+{
+    let mut stream = ProbeStream { codec_type: codec_type(track.child_unsigned("TrackType")), ..Default::default() };
+
+    if let Some(codec_id) = track.child_string("CodecID") &&
+        let Some((codec_name, codec_long_name)) = codec_names(&codec_id)
+    {
+        stream.codec_name = Some(codec_name);
+        stream.codec_long_name = Some(codec_long_name);
+    }
+
+    if let Some(video) = track.child("Video")
+    {
+        stream.width = video.child_unsigned("PixelWidth").map(|width| width as u32);
+        stream.height = video.child_unsigned("PixelHeight").map(|height| height as u32);
+    }
+
+    if let Some(audio) = track.child("Audio")
+    {
+        // SamplingFrequency defaults to 8 kHz, Channels to 1
+        stream.sample_rate = Some(audio.child_float("SamplingFrequency").unwrap_or(8000.0) as u32);
+        stream.channels = Some(audio.child_unsigned("Channels").unwrap_or(1) as u16);
+    }
+
+    stream
+}
+
+/// Describe a parsed element tree in ffprobe terms
+pub fn probe_elements(elements: &[EbmlElement]) -> ProbeFormat
+{
+    let segment = elements.iter().find(|element| element.name() == "Segment");
+
+    let streams: Vec<ProbeStream> = segment
+        .and_then(|segment| segment.child("Tracks"))
+        .map(|tracks| tracks.children.iter().filter(|child| child.name() == "TrackEntry").map(probe_track).collect())
+        .unwrap_or_default();
+
+    // Duration is stored in TimestampScale units (nanoseconds per tick)
+    let duration = segment.and_then(|segment| segment.child("Info")).and_then(|info| {
+        let scale = info.child_unsigned("TimestampScale").unwrap_or(DEFAULT_TIMESTAMP_SCALE);
+        info.child_float("Duration").filter(|&duration| duration > 0.0).map(|duration| duration * scale as f64 / 1_000_000_000.0)
+    });
+
+    ProbeFormat { format_name: "matroska,webm", format_long_name: "Matroska / WebM", duration, streams }
+}
@@ -0,0 +1,708 @@
+// MPEG-1/2/2.5 audio (Layer I-III) frame headers
+//
+// Used to describe the audio stream that follows an ID3v2 tag in MP3 files: first
+// frame header, Xing/Info/VBRI headers and the resulting duration estimate. Raw streams
+// without any tag are walked frame by frame (`frame_scan`), including the ICY metadata
+// blocks of Shoutcast/Icecast stream dumps (`icy`).
+
+pub mod frame_scan;
+pub mod icy;
+
+use std::io::{Read, SeekFrom, Write};
+
+use crate::{
+    ape_tag::ApeTag,
+    bytes::read_u32_be,
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    id3v1::Id3v1Tag,
+    id3v2::frames::chapter::format_timestamp,
+    lyrics3::Lyrics3Tag,
+    media_dissector::Confidence,
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Number of bytes searched for the first frame header
+const SYNC_SEARCH_WINDOW: u64 = 64 * 1024;
+
+/// Consecutive frames a raw stream must start with to be rated `Confidence::High`
+const SNIFF_FRAMES: usize = 4;
+
+/// Bit rates in kbit/s indexed by the header bitrate index (MPEG-1 layers I-III, MPEG-2/2.5 layer I, MPEG-2/2.5 layers II/III)
+const BITRATES: [[u32; 16]; 5] = [
+    [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448, 0],
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384, 0],
+    [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 0],
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256, 0],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160, 0]
+];
+
+/// MPEG audio version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpegVersion
+{
+    Mpeg1,
+    Mpeg2,
+    Mpeg25
+}
+
+/// Decoded 4-byte MPEG audio frame header
+#[derive(Debug, Clone, Copy)]
+pub struct MpegFrameHeader
+{
+    pub version:     MpegVersion,
+    /// Layer number (1-3)
+    pub layer:       u8,
+    /// Bit rate in kbit/s
+    pub bitrate:     u32,
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    pub padding:     bool,
+    pub channels:    u16,
+    /// Channel mode bits (0 = stereo, 1 = joint stereo, 2 = dual channel, 3 = mono)
+    pub mode:        u8,
+    /// Whether a 16-bit CRC follows the header
+    pub protected:   bool,
+    pub copyright:   bool,
+    pub original:    bool,
+    /// Emphasis bits (0 = none, 1 = 50/15 ms, 3 = CCITT J.17)
+    pub emphasis:    u8
+}
+
+impl MpegFrameHeader
+{
+    /// Decode a frame header, returning None for invalid or free-format headers
+    pub fn parse(header: &[u8]) -> Option<Self>
+    {
+        if header.len() < 4 || header[0] != 0xFF || header[1] & 0xE0 != 0xE0
+        {
+            return None;
+        }
+
+        let version = match (header[1] >> 3) & 0x03
+        {
+            | 0 => MpegVersion::Mpeg25,
+            | 2 => MpegVersion::Mpeg2,
+            | 3 => MpegVersion::Mpeg1,
+            | _ => return None
+        };
+        let layer = match (header[1] >> 1) & 0x03
+        {
+            | 1 => 3,
+            | 2 => 2,
+            | 3 => 1,
+            | _ => return None
+        };
+
+        let table = match (version, layer)
+        {
+            | (MpegVersion::Mpeg1, layer) => layer as usize - 1,
+            | (_, 1) => 3,
+            | _ => 4
+        };
+        let bitrate = BITRATES[table][(header[2] >> 4) as usize];
+        if bitrate == 0
+        {
+            return None;
+        }
+
+        let base_rate = match (header[2] >> 2) & 0x03
+        {
+            | 0 => 44100,
+            | 1 => 48000,
+            | 2 => 32000,
+            | _ => return None
+        };
+        let sample_rate = match version
+        {
+            | MpegVersion::Mpeg1 => base_rate,
+            | MpegVersion::Mpeg2 => base_rate / 2,
+            | MpegVersion::Mpeg25 => base_rate / 4
+        };
+
+        let padding = (header[2] >> 1) & 0x01 != 0;
+        let mode = header[3] >> 6;
+        let channels = if mode == 3
+        {
+            1
+        }
+        else
+        {
+            2
+        };
+
+        Some(Self {
+            version,
+            layer,
+            bitrate,
+            sample_rate,
+            padding,
+            channels,
+            mode,
+            protected: header[1] & 0x01 == 0,
+            copyright: header[3] & 0x08 != 0,
+            original: header[3] & 0x04 != 0,
+            emphasis: header[3] & 0x03
+        })
+    }
+
+    /// Number of PCM samples per frame
+    pub fn samples_per_frame(&self) -> u32
+    {
+        match (self.layer, self.version)
+        {
+            | (1, _) => 384,
+            | (3, MpegVersion::Mpeg2 | MpegVersion::Mpeg25) => 576,
+            | _ => 1152
+        }
+    }
+
+    /// Frame length in bytes including the header
+    pub fn frame_length(&self) -> usize
+    {
+        let padding = self.padding as u32;
+        let length = if self.layer == 1
+        {
+            (12 * self.bitrate * 1000 / self.sample_rate + padding) * 4
+        }
+        else
+        {
+            self.samples_per_frame() / 8 * self.bitrate * 1000 / self.sample_rate + padding
+        };
+        length as usize
+    }
+
+    /// Size of the Layer III side information that precedes a Xing/Info header
+    fn side_info_size(&self) -> usize
+    {
+        match (self.version, self.channels)
+        {
+            | (MpegVersion::Mpeg1, 1) => 17,
+            | (MpegVersion::Mpeg1, _) => 32,
+            | (_, 1) => 9,
+            | _ => 17
+        }
+    }
+
+    /// Version name as used in the specifications
+    pub fn version_name(&self) -> &'static str
+    {
+        match self.version
+        {
+            | MpegVersion::Mpeg1 => "MPEG-1",
+            | MpegVersion::Mpeg2 => "MPEG-2",
+            | MpegVersion::Mpeg25 => "MPEG-2.5"
+        }
+    }
+
+    /// Channel mode description
+    pub fn mode_name(&self) -> &'static str
+    {
+        match self.mode
+        {
+            | 0 => "Stereo",
+            | 1 => "Joint stereo",
+            | 2 => "Dual channel",
+            | _ => "Mono"
+        }
+    }
+
+    /// Emphasis description
+    pub fn emphasis_name(&self) -> &'static str
+    {
+        match self.emphasis
+        {
+            | 0 => "none",
+            | 1 => "50/15 ms",
+            | 3 => "CCITT J.17",
+            | _ => "reserved"
+        }
+    }
+
+    /// ffmpeg codec name and long name for this layer
+    fn codec_names(&self) -> (&'static str, &'static str)
+    {
+        match self.layer
+        {
+            | 1 => ("mp1", "MP1 (MPEG audio layer 1)"),
+            | 2 => ("mp2", "MP2 (MPEG audio layer 2)"),
+            | _ => ("mp3", "MP3 (MPEG audio layer 3)")
+        }
+    }
+}
+
+/// Find the first frame header in `buffer`, requiring a second header right behind it when it fits the buffer
+fn find_first_frame(buffer: &[u8]) -> Option<(usize, MpegFrameHeader)>
+{
+    (0..buffer.len().saturating_sub(3)).find_map(|position| {
+        let header = MpegFrameHeader::parse(&buffer[position..])?;
+        let next = position + header.frame_length();
+        if next + 4 <= buffer.len() && MpegFrameHeader::parse(&buffer[next..]).is_none()
+        {
+            return None;
+        }
+        Some((position, header))
+    })
+}
+
+/// Kind of VBR header stored in the first frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbrHeaderKind
+{
+    /// Xing header (VBR stream)
+    Xing,
+    /// Xing header written by LAME for CBR streams
+    Info,
+    /// Fraunhofer VBRI header
+    Vbri
+}
+
+/// VBR header (Xing/Info or VBRI) found in the first frame
+#[derive(Debug, Clone)]
+pub struct VbrHeader
+{
+    pub kind:    VbrHeaderKind,
+    /// File offset of the header tag
+    pub offset:  u64,
+    /// Number of frames, excluding the frame holding the header
+    pub frames:  Option<u32>,
+    /// Stream size in bytes
+    pub bytes:   Option<u32>,
+    /// Encoder quality indicator
+    pub quality: Option<u32>,
+    /// Encoder version string of a LAME extension (e.g. "LAME3.100")
+    pub encoder: Option<String>,
+    /// Samples the encoder added in front of the audio and behind it (LAME extension)
+    pub gapless: Option<(u16, u16)>
+}
+
+impl VbrHeader
+{
+    /// Parse a Xing/Info header at `position` in `buffer`
+    fn parse_xing(buffer: &[u8], position: usize, offset: u64) -> Option<Self>
+    {
+        let kind = match buffer.get(position..position + 4)?
+        {
+            | b"Xing" => VbrHeaderKind::Xing,
+            | b"Info" => VbrHeaderKind::Info,
+            | _ => return None
+        };
+        if buffer.len() < position + 8
+        {
+            return None;
+        }
+        let flags = read_u32_be(buffer, position + 4);
+
+        // Optional fields follow in flag order: frames, bytes, 100-byte TOC, quality
+        let mut field = position + 8;
+        let mut next_field = |present: bool, length: usize| {
+            let start = field;
+            if present == true
+            {
+                field += length;
+            }
+            (present == true && buffer.len() >= start + length).then_some(start)
+        };
+        let frames = next_field(flags & 0x01 != 0, 4).map(|start| read_u32_be(buffer, start));
+        let bytes = next_field(flags & 0x02 != 0, 4).map(|start| read_u32_be(buffer, start));
+        next_field(flags & 0x04 != 0, 100);
+        let quality = next_field(flags & 0x08 != 0, 4).map(|start| read_u32_be(buffer, start));
+
+        // LAME and compatible encoders append a 9-byte version string
+        let encoder = buffer
+            .get(field..field + 9)
+            .filter(|version| version.iter().all(|&byte| byte.is_ascii_graphic() || byte == b' ') && version.starts_with(b"L") == true)
+            .map(|version| String::from_utf8_lossy(version).trim_end().to_string());
+
+        // The LAME extension stores encoder delay and padding as two 12-bit values 21 bytes behind the version string
+        let gapless = buffer
+            .get(field + 21..field + 24)
+            .filter(|_| encoder.is_some())
+            .map(|bytes| (((bytes[0] as u16) << 4) | (bytes[1] as u16 >> 4), ((bytes[1] as u16 & 0x0F) << 8) | bytes[2] as u16));
+
+        Some(Self { kind, offset: offset + position as u64, frames, bytes, quality, encoder, gapless })
+    }
+
+    /// Parse a VBRI header at `position` in `buffer`
+    fn parse_vbri(buffer: &[u8], position: usize, offset: u64) -> Option<Self>
+    {
+        if buffer.get(position..position + 4)? != b"VBRI"
+        {
+            return None;
+        }
+
+        Some(Self {
+            kind:    VbrHeaderKind::Vbri,
+            offset:  offset + position as u64,
+            frames:  (buffer.len() >= position + 18).then(|| read_u32_be(buffer, position + 14)),
+            bytes:   (buffer.len() >= position + 14).then(|| read_u32_be(buffer, position + 10)),
+            quality: buffer.get(position + 8..position + 10).map(|quality| u16::from_be_bytes([quality[0], quality[1]]) as u32),
+            encoder: None,
+            gapless: None
+        })
+    }
+
+    /// Header name as written in the file
+    pub fn name(&self) -> &'static str
+    {
+        match self.kind
+        {
+            | VbrHeaderKind::Xing => "Xing",
+            | VbrHeaderKind::Info => "Info",
+            | VbrHeaderKind::Vbri => "VBRI"
+        }
+    }
+}
+
+/// Summary of an MPEG audio stream derived from its first frame
+#[derive(Debug, Clone)]
+pub struct MpegAudioInfo
+{
+    /// File offset of the first frame
+    pub offset:     u64,
+    pub header:     MpegFrameHeader,
+    pub vbr_header: Option<VbrHeader>,
+    /// Size of the audio data (up to the end of the file or a trailing ID3v1 tag)
+    pub audio_size: u64,
+    /// Duration in seconds
+    pub duration:   f64,
+    /// Average bit rate in bits per second
+    pub bit_rate:   u64,
+    /// Whether duration and bit rate assume a constant bit rate (no frame count available)
+    pub estimated:  bool
+}
+
+impl MpegAudioInfo
+{
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "offset": self.offset,
+            "version": self.header.version_name(),
+            "layer": self.header.layer,
+            "bitrate": self.header.bitrate,
+            "sample_rate": self.header.sample_rate,
+            "channel_mode": self.header.mode_name(),
+            "crc_protected": self.header.protected,
+            "copyright": self.header.copyright,
+            "original": self.header.original,
+            "emphasis": self.header.emphasis_name(),
+            "vbr_header": self.vbr_header.as_ref().map(|vbr| serde_json::json!({
+                "type": vbr.name(),
+                "offset": vbr.offset,
+                "frames": vbr.frames,
+                "bytes": vbr.bytes,
+                "quality": vbr.quality,
+                "encoder": vbr.encoder,
+                "encoder_delay": vbr.gapless.map(|(delay, _)| delay),
+                "encoder_padding": vbr.gapless.map(|(_, padding)| padding)
+            })),
+            "audio_size": self.audio_size,
+            "duration": self.duration,
+            "average_bit_rate": self.bit_rate,
+            "estimated": self.estimated
+        })
+    }
+}
+
+/// Rate how likely `data` starts a raw MPEG audio stream: the more frames of the same version, layer and sample rate
+/// follow each other, the less likely the sync word occurs by chance
+pub fn sniff(data: &[u8]) -> Confidence
+{
+    let Some(first) = MpegFrameHeader::parse(data)
+    else
+    {
+        // A bare sync word, e.g. of a free-format stream (layer 0 is reserved and marks ADTS frames instead)
+        return match data
+        {
+            | [0xFF, second, ..] if second & 0xE0 == 0xE0 && second & 0x06 != 0 => Confidence::Low,
+            | _ => Confidence::None
+        };
+    };
+
+    let mut frames = 1;
+    let mut offset = first.frame_length();
+    while frames < SNIFF_FRAMES &&
+        let Some(next) = data.get(offset..).and_then(MpegFrameHeader::parse) &&
+        (next.version, next.layer, next.sample_rate) == (first.version, first.layer, first.sample_rate)
+    {
+        frames += 1;
+        offset += next.frame_length();
+    }
+
+    match frames
+    {
+        | 1 => Confidence::Low,
+        | 2 | 3 => Confidence::Medium,
+        | _ => Confidence::High
+    }
+}
+
+/// Analyze the MPEG audio stream starting at (or shortly after) `offset`, if there is one
+pub fn analyze(file: &mut dyn MediaSource, offset: u64) -> Result<Option<MpegAudioInfo>, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+
+    let mut buffer = Vec::new();
+    file.seek(SeekFrom::Start(offset))?;
+    (&mut *file).take(SYNC_SEARCH_WINDOW).read_to_end(&mut buffer)?;
+
+    let Some((position, header)) = find_first_frame(&buffer)
+    else
+    {
+        return Ok(None);
+    };
+
+    // Audio runs to the end of the file, minus trailing APE, Lyrics3 and ID3v1 tags
+    let first_frame = offset + position as u64;
+    let audio_end = match (ApeTag::read(file)?, Lyrics3Tag::read(file)?)
+    {
+        | (Some(ape), Some(lyrics3)) if ape.offset > first_frame => ape.offset.min(lyrics3.offset),
+        | (Some(ape), None) if ape.offset > first_frame => ape.offset,
+        | (_, Some(lyrics3)) if lyrics3.offset > first_frame => lyrics3.offset,
+        | _ => Id3v1Tag::read(file)?.map_or(file_size, |id3v1| id3v1.offset)
+    };
+    let audio_size = audio_end.saturating_sub(first_frame);
+
+    // Xing/Info follows the Layer III side information, VBRI always sits 32 bytes behind the header
+    let vbr_header = if header.layer == 3
+    {
+        VbrHeader::parse_xing(&buffer, position + 4 + header.side_info_size(), offset).or_else(|| VbrHeader::parse_vbri(&buffer, position + 36, offset))
+    }
+    else
+    {
+        None
+    };
+
+    let frame_count = vbr_header.as_ref().and_then(|vbr| vbr.frames).filter(|&frames| frames > 0);
+    let (duration, bit_rate, estimated) = match frame_count
+    {
+        | Some(frames) =>
+        {
+            let duration = frames as f64 * header.samples_per_frame() as f64 / header.sample_rate as f64;
+            (duration, (audio_size as f64 * 8.0 / duration) as u64, false)
+        }
+        | None => (audio_size as f64 * 8.0 / (header.bitrate as f64 * 1000.0), header.bitrate as u64 * 1000, true)
+    };
+
+    Ok(Some(MpegAudioInfo { offset: offset + position as u64, header, vbr_header, audio_size, duration, bit_rate, estimated }))
+}
+
+/// Describe the MPEG audio stream starting at (or shortly after) `offset`, if there is one
+pub fn probe(file: &mut dyn MediaSource, offset: u64) -> Result<Option<ProbeFormat>, Box<dyn std::error::Error>>
+{
+    let Some(info) = analyze(file, offset)?
+    else
+    {
+        return Ok(None);
+    };
+
+    let (codec_name, codec_long_name) = info.header.codec_names();
+    let stream = ProbeStream {
+        codec_name: Some(codec_name),
+        codec_long_name: Some(codec_long_name),
+        codec_type: "audio",
+        sample_rate: Some(info.header.sample_rate),
+        channels: Some(info.header.channels),
+        duration: Some(info.duration),
+        bit_rate: Some(info.bit_rate),
+        ..Default::default()
+    };
+
+    Ok(Some(ProbeFormat {
+        format_name:      "mp3",
+        format_long_name: "MP2/3 (MPEG audio layer 2/3)",
+        duration:         Some(info.duration),
+        streams:          vec![stream]
+    }))
+}
+
+/// Localized yes/no for flag output
+fn yes_no(value: bool) -> &'static str
+{
+    if value == true
+    {
+        tr("yes")
+    }
+    else
+    {
+        tr("no")
+    }
+}
+
+/// Print the MPEG audio stream behind a tag and report anomalies of its first frame
+pub fn dissect_mpeg_audio(
+    file: &mut dyn MediaSource, offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    let Some(info) = analyze(file, offset)?
+    else
+    {
+        if offset < file.size()?
+        {
+            diagnostics.info(Some(offset), tr("No MPEG audio frames found after the tag"));
+        }
+        return Ok(());
+    };
+
+    if info.offset > offset
+    {
+        diagnostics.info(Some(offset), tr_fmt("{} bytes of unrecognized data before the first MPEG audio frame", &[&(info.offset - offset)]));
+    }
+    if let Some(ref vbr) = info.vbr_header &&
+        let Some(bytes) = vbr.bytes &&
+        (bytes as u64).abs_diff(info.audio_size) > info.audio_size / 10
+    {
+        diagnostics.warning(
+            Some(vbr.offset),
+            tr_fmt("{} header stream size ({} bytes) differs from the audio data size ({} bytes)", &[&vbr.name(), &bytes, &info.audio_size])
+        );
+    }
+
+    if options.show_header == false && options.show_data == false
+    {
+        return Ok(());
+    }
+
+    let header = &info.header;
+    writeln!(output, "\n{}", tr("MPEG Audio Stream:").bright_cyan().bold())?;
+    writeln!(output, "  {}", tr_fmt("First frame at offset {}", &[&format!("0x{:08X}", info.offset)]))?;
+    writeln!(output, "  {}", tr_fmt("Version: {}", &[&format!("{} Layer {}", header.version_name(), "I".repeat(header.layer as usize))]))?;
+    writeln!(output, "  {}", tr_fmt("Bitrate: {} kbps", &[&header.bitrate]))?;
+    writeln!(output, "  {}", tr_fmt("Sample Rate: {} Hz", &[&header.sample_rate]))?;
+    writeln!(output, "  {}", tr_fmt("Channel Mode: {}", &[&tr(header.mode_name())]))?;
+    writeln!(output, "  {}", tr_fmt("CRC Protection: {}", &[&yes_no(header.protected)]))?;
+    writeln!(output, "  {}", tr_fmt("Copyright: {}, Original: {}", &[&yes_no(header.copyright), &yes_no(header.original)]))?;
+    writeln!(output, "  {}", tr_fmt("Emphasis: {}", &[&tr(header.emphasis_name())]))?;
+
+    match info.vbr_header
+    {
+        | Some(ref vbr) =>
+        {
+            writeln!(output, "  {}", tr_fmt("{} header at offset {}", &[&vbr.name(), &format!("0x{:08X}", vbr.offset)]))?;
+            if let Some(frames) = vbr.frames
+            {
+                writeln!(output, "    {}", tr_fmt("Frames: {}", &[&frames]))?;
+            }
+            if let Some(bytes) = vbr.bytes
+            {
+                writeln!(output, "    {}", tr_fmt("Stream size: {} bytes", &[&bytes]))?;
+            }
+            if let Some(quality) = vbr.quality
+            {
+                writeln!(output, "    {}", tr_fmt("Quality: {}", &[&quality]))?;
+            }
+            if let Some(ref encoder) = vbr.encoder
+            {
+                writeln!(output, "    {}", tr_fmt("Encoder: {}", &[encoder]))?;
+            }
+            if let Some((delay, padding)) = vbr.gapless
+            {
+                writeln!(output, "    {}", tr_fmt("Encoder delay: {} samples, padding: {} samples", &[&delay, &padding]))?;
+            }
+        }
+        | None => writeln!(output, "  {}", tr("No VBR header (Xing/Info/VBRI)"))?
+    }
+
+    let duration = format_timestamp((info.duration * 1000.0).round().min(u32::MAX as f64) as u32);
+    if info.estimated == true
+    {
+        writeln!(output, "  {}", tr_fmt("Duration: {} (estimated from file size, assuming constant bit rate)", &[&duration]))?;
+    }
+    else
+    {
+        writeln!(output, "  {}", tr_fmt("Duration: {}", &[&duration]))?;
+        writeln!(output, "  {}", tr_fmt("Average Bitrate: {} kbps", &[&(info.bit_rate / 1000)]))?;
+    }
+
+    Ok(())
+}
+
+/// Read a frame header at `offset` if it lies within the file
+fn read_header(reader: &mut MediaReader, offset: u64, file_size: u64) -> std::io::Result<Option<MpegFrameHeader>>
+{
+    if offset + 4 > file_size
+    {
+        return Ok(None);
+    }
+
+    let mut header = [0u8; 4];
+    reader.seek_to(offset)?;
+    reader.read_exact(&mut header)?;
+    Ok(MpegFrameHeader::parse(&header))
+}
+
+/// Mark the MPEG audio frames from `offset` on as covered, resynchronizing over junk between frames
+///
+/// A trailing ID3v1 tag is covered as well. Returns the end of the audio data, or `offset` if
+/// there are no frames.
+pub fn add_coverage(file: &mut dyn MediaSource, offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+{
+    let file_size = file.size()?;
+    let mut reader = MediaReader::new(file, offset)?;
+
+    let mut position = offset;
+    let mut run_start = None;
+    let mut audio_end = offset;
+
+    while position + 4 <= file_size
+    {
+        // A frame counts if it is the continuation of a run or is followed by another frame
+        let header = match read_header(&mut reader, position, file_size)?
+        {
+            | Some(header) if run_start.is_some() == true => Some(header),
+            | Some(header) if read_header(&mut reader, position + header.frame_length() as u64, file_size)?.is_some() == true => Some(header),
+            | _ => None
+        };
+
+        match header
+        {
+            | Some(header) =>
+            {
+                run_start.get_or_insert(position);
+                position = (position + header.frame_length() as u64).min(file_size);
+                audio_end = position;
+            }
+            | None =>
+            {
+                if let Some(start) = run_start.take()
+                {
+                    coverage.add(start, position);
+                }
+                position += 1;
+            }
+        }
+    }
+    if let Some(start) = run_start
+    {
+        coverage.add(start, position);
+    }
+
+    // APE, Lyrics3 and ID3v1 tags at the end of the file
+    if let Some(ape) = ApeTag::read(file)? &&
+        ape.offset >= offset
+    {
+        coverage.add(ape.offset, ape.offset + ape.size);
+        audio_end = audio_end.max(ape.offset + ape.size);
+    }
+    if let Some(lyrics3) = Lyrics3Tag::read(file)? &&
+        lyrics3.offset >= offset
+    {
+        coverage.add(lyrics3.offset, lyrics3.offset + lyrics3.size);
+        audio_end = audio_end.max(lyrics3.offset + lyrics3.size);
+    }
+    if let Some(id3v1) = Id3v1Tag::read(file)? &&
+        id3v1.offset >= offset
+    {
+        coverage.add(id3v1.offset, file_size);
+        audio_end = file_size;
+    }
+
+    Ok(audio_end)
+}
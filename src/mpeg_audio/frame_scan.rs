@@ -0,0 +1,296 @@
+// Frame-by-frame scan of raw MPEG audio streams
+//
+// Files without any tag (e.g. saved radio streams) are walked frame by frame instead of being
+// described by their first frame only: frame count, bit rate distribution, padded frames and the
+// places where the frame sync is lost. Stream dumps of Shoutcast/Icecast servers interrupt the
+// frames with ICY metadata blocks, which are reported with their stream titles.
+
+use std::{collections::BTreeMap, io::Write};
+
+use super::{
+    MpegFrameHeader, analyze,
+    icy::{IcyMetadata, find_icy_metadata},
+    read_header
+};
+use crate::{
+    color::Colorize,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    id3v2::frames::chapter::format_timestamp,
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Findings of one kind reported before only the total is reported
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Largest region searched for an ICY metadata block when the frame sync is lost
+const MAX_GAP_SEARCH: u64 = 64 * 1024;
+
+/// Result of walking all frames of an MPEG audio stream
+#[derive(Debug, Clone)]
+pub struct FrameScan
+{
+    /// File offset of the first frame
+    pub first_frame:   u64,
+    pub frames:        u64,
+    /// Number of frames per bit rate in kbit/s
+    pub bit_rates:     BTreeMap<u32, u64>,
+    /// Frames with the padding bit set
+    pub padded_frames: u64,
+    /// Samples per channel of all frames
+    pub samples:       u64,
+    pub sample_rate:   u32,
+    /// End of the last complete frame
+    pub end_offset:    u64,
+    /// Number of places where the frame sync was lost for other reasons than ICY metadata
+    pub sync_losses:   u64,
+    /// Bytes skipped at these places
+    pub skipped_bytes: u64,
+    /// ICY metadata blocks between the frames
+    pub icy_metadata:  Vec<IcyMetadata>,
+    /// Empty (zero length) ICY metadata markers between the frames
+    pub icy_markers:   u64
+}
+
+impl FrameScan
+{
+    /// "CBR" if all frames share one bit rate, "VBR" otherwise
+    pub fn bit_rate_mode(&self) -> &'static str
+    {
+        match self.bit_rates.len()
+        {
+            | 0 | 1 => "CBR",
+            | _ => "VBR"
+        }
+    }
+
+    /// Duration in seconds
+    pub fn duration(&self) -> f64
+    {
+        self.samples as f64 / self.sample_rate as f64
+    }
+
+    /// Average bit rate of the frames in bits per second
+    pub fn average_bit_rate(&self) -> u64
+    {
+        let icy_bytes = self.icy_metadata.iter().map(|block| block.size).sum::<u64>() + self.icy_markers;
+        let audio_bytes = (self.end_offset - self.first_frame).saturating_sub(self.skipped_bytes + icy_bytes);
+        match self.duration()
+        {
+            | duration if duration > 0.0 => (audio_bytes as f64 * 8.0 / duration) as u64,
+            | _ => 0
+        }
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let bit_rates: Vec<serde_json::Value> = self.bit_rates.iter().map(|(bit_rate, frames)| serde_json::json!({ "bitrate": bit_rate, "frames": frames })).collect();
+        serde_json::json!({
+            "first_frame": self.first_frame,
+            "frames": self.frames,
+            "mode": self.bit_rate_mode(),
+            "bit_rates": bit_rates,
+            "padded_frames": self.padded_frames,
+            "duration": self.duration(),
+            "average_bit_rate": self.average_bit_rate(),
+            "end_offset": self.end_offset,
+            "sync_losses": self.sync_losses,
+            "skipped_bytes": self.skipped_bytes,
+            "icy_metadata": self.icy_metadata.iter().map(IcyMetadata::to_json).collect::<Vec<_>>(),
+            "icy_markers": self.icy_markers
+        })
+    }
+}
+
+/// Whether a header continues the stream of the first frame (a different version, layer or sample rate is a false sync)
+fn same_stream(first: &MpegFrameHeader, header: &MpegFrameHeader) -> bool
+{
+    header.version == first.version && header.layer == first.layer && header.sample_rate == first.sample_rate
+}
+
+/// Read a frame header at `offset` that continues the stream of `first`
+fn read_stream_header(reader: &mut MediaReader, offset: u64, end: u64, first: &MpegFrameHeader) -> std::io::Result<Option<MpegFrameHeader>>
+{
+    Ok(read_header(reader, offset, end)?.filter(|header| same_stream(first, header) == true))
+}
+
+/// Walk all frames of the MPEG audio stream starting at (or shortly after) `offset`, if there is one
+///
+/// Lost frame syncs are resolved by searching for the next pair of frames. ICY metadata blocks are
+/// searched from the start of the last frame, as servers insert them at fixed byte intervals
+/// regardless of the frame boundaries.
+pub fn scan_frames(file: &mut dyn MediaSource, offset: u64, diagnostics: &mut Diagnostics) -> Result<Option<FrameScan>, Box<dyn std::error::Error>>
+{
+    let Some(info) = analyze(file, offset)?
+    else
+    {
+        return Ok(None);
+    };
+    let first = info.header;
+    let end = info.offset + info.audio_size;
+    let mut reader = MediaReader::new(file, info.offset)?;
+
+    let mut scan = FrameScan {
+        first_frame:   info.offset,
+        frames:        0,
+        bit_rates:     BTreeMap::new(),
+        padded_frames: 0,
+        samples:       0,
+        sample_rate:   first.sample_rate,
+        end_offset:    info.offset,
+        sync_losses:   0,
+        skipped_bytes: 0,
+        icy_metadata:  Vec::new(),
+        icy_markers:   0
+    };
+    // Regions without frames: start, size and whether an ICY metadata block explains them
+    let mut gaps: Vec<(u64, u64, bool)> = Vec::new();
+    let mut gap_start: Option<u64> = None;
+    let mut last_frame = info.offset;
+    let mut position = info.offset;
+
+    while position + 4 <= end
+    {
+        // Within a run every header counts, after a lost sync only one followed by another frame
+        let header = match read_stream_header(&mut reader, position, end, &first)?
+        {
+            | Some(header) if gap_start.is_none() == true => Some(header),
+            | Some(header) if read_stream_header(&mut reader, position + header.frame_length() as u64, end, &first)?.is_some() == true => Some(header),
+            | _ => None
+        };
+        let Some(header) = header
+        else
+        {
+            gap_start.get_or_insert(position);
+            position += 1;
+            continue;
+        };
+
+        if let Some(start) = gap_start.take()
+        {
+            let search_start = last_frame.max(position.saturating_sub(MAX_GAP_SEARCH));
+            let mut buffer = vec![0u8; (position - search_start) as usize];
+            reader.seek_to(search_start)?;
+            reader.read_exact(&mut buffer)?;
+            let blocks = find_icy_metadata(&buffer, search_start);
+            gaps.push((start, position - start, blocks.is_empty() == false));
+            scan.icy_metadata.extend(blocks);
+        }
+
+        let length = header.frame_length() as u64;
+        if position + length > end
+        {
+            diagnostics.warning(Some(position), tr_fmt("Last MPEG audio frame truncated: {} bytes declared, {} available", &[&length, &(end - position)]));
+            break;
+        }
+        scan.frames += 1;
+        *scan.bit_rates.entry(header.bitrate).or_default() += 1;
+        scan.padded_frames += header.padding as u64;
+        scan.samples += header.samples_per_frame() as u64;
+        last_frame = position;
+        position += length;
+        scan.end_offset = position;
+    }
+
+    // Servers send a single zero length byte when the metadata did not change
+    let icy_stream = scan.icy_metadata.is_empty() == false;
+    for &(start, size, icy) in &gaps
+    {
+        if icy == true
+        {
+            continue;
+        }
+        if icy_stream == true && size == 1
+        {
+            scan.icy_markers += 1;
+            continue;
+        }
+        scan.sync_losses += 1;
+        scan.skipped_bytes += size;
+        if scan.sync_losses as usize <= MAX_REPORTED_ERRORS
+        {
+            diagnostics.warning(Some(start), tr_fmt("MPEG audio frame sync lost, {} bytes skipped before the next frame", &[&size]));
+        }
+    }
+    if scan.sync_losses as usize > MAX_REPORTED_ERRORS
+    {
+        diagnostics.warning(Some(scan.first_frame), tr_fmt("Frame sync lost {} times in total ({} bytes skipped)", &[&scan.sync_losses, &scan.skipped_bytes]));
+    }
+    if let Some(start) = gap_start
+    {
+        diagnostics.warning(Some(start), tr_fmt("{} bytes of other data behind the last MPEG audio frame", &[&(end - start)]));
+    }
+    if icy_stream == true
+    {
+        diagnostics.info(Some(scan.first_frame), tr_fmt("{} ICY metadata blocks between the frames (Shoutcast/Icecast stream dump)", &[&scan.icy_metadata.len()]));
+    }
+
+    Ok(Some(scan))
+}
+
+/// Print the frame scan of the raw MPEG audio stream starting at (or shortly after) `offset`
+pub fn dissect_frame_scan(
+    file: &mut dyn MediaSource, offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    let Some(scan) = scan_frames(file, offset, diagnostics)?
+    else
+    {
+        return Ok(());
+    };
+
+    if options.show_header == true
+    {
+        writeln!(output, "\n{}", tr("MPEG Frame Scan:").bright_cyan().bold())?;
+        writeln!(output, "  {}", tr_fmt("Frames: {}", &[&scan.frames]))?;
+        let (lowest, highest) = (scan.bit_rates.keys().next().copied().unwrap_or(0), scan.bit_rates.keys().last().copied().unwrap_or(0));
+        match lowest == highest
+        {
+            | true => writeln!(output, "  {}", tr_fmt("Bit rate mode: {} ({} kbps)", &[&scan.bit_rate_mode(), &lowest]))?,
+            | false => writeln!(output, "  {}", tr_fmt("Bit rate mode: {} ({} kbps)", &[&scan.bit_rate_mode(), &format!("{}-{}", lowest, highest)]))?
+        }
+        if scan.frames > 0
+        {
+            writeln!(
+                output,
+                "  {}",
+                tr_fmt("Padded frames: {} ({}%)", &[&scan.padded_frames, &format!("{:.1}", scan.padded_frames as f64 * 100.0 / scan.frames as f64)])
+            )?;
+        }
+        writeln!(output, "  {}", tr_fmt("Duration: {}", &[&format_timestamp((scan.duration() * 1000.0).round().min(u32::MAX as f64) as u32)]))?;
+        writeln!(output, "  {}", tr_fmt("Average Bitrate: {} kbps", &[&(scan.average_bit_rate() / 1000)]))?;
+        if scan.sync_losses > 0
+        {
+            writeln!(output, "  {}", tr_fmt("Frame sync lost: {} times, {} bytes skipped", &[&scan.sync_losses, &scan.skipped_bytes]))?;
+        }
+        if scan.icy_metadata.is_empty() == false
+        {
+            writeln!(output, "  {}", tr_fmt("ICY metadata blocks: {}", &[&scan.icy_metadata.len()]))?;
+        }
+    }
+
+    if options.show_data == true
+    {
+        if scan.bit_rates.len() > 1
+        {
+            writeln!(output, "\n{}", tr("Bit Rates:").bright_cyan().bold())?;
+            for (bit_rate, frames) in &scan.bit_rates
+            {
+                writeln!(output, "  {}", tr_fmt("{} kbps: {} frames", &[bit_rate, frames]))?;
+            }
+        }
+        if scan.icy_metadata.is_empty() == false
+        {
+            writeln!(output, "\n{}", tr("ICY Metadata:").bright_cyan().bold())?;
+            for block in &scan.icy_metadata
+            {
+                writeln!(output, "  0x{:08X}: {}", block.offset, block.text())?;
+            }
+        }
+    }
+
+    Ok(())
+}
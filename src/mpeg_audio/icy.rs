@@ -0,0 +1,85 @@
+// Shoutcast/Icecast (ICY) metadata blocks
+//
+// Streams requested with "Icy-MetaData: 1" carry a metadata block every icy-metaint bytes of
+// audio: one length byte (in units of 16 bytes) followed by "StreamTitle='...';" fields padded
+// with NUL bytes. Saved stream dumps keep these blocks between (and inside) the MPEG frames.
+
+/// Field that starts every non-empty metadata block
+const STREAM_TITLE: &[u8] = b"StreamTitle='";
+
+/// ICY metadata block found in a stream dump
+#[derive(Debug, Clone)]
+pub struct IcyMetadata
+{
+    /// File offset of the length byte
+    pub offset: u64,
+    /// Block size including the length byte
+    pub size:   u64,
+    /// Fields in stream order (e.g. StreamTitle, StreamUrl)
+    pub fields: Vec<(String, String)>
+}
+
+impl IcyMetadata
+{
+    /// Parse the block whose StreamTitle field starts at `position` in `buffer`, which starts at file offset `offset`
+    fn parse(buffer: &[u8], position: usize, offset: u64) -> Option<Self>
+    {
+        let length = *buffer.get(position.checked_sub(1)?)? as usize * 16;
+        let text = buffer.get(position..position + length)?;
+        let text = String::from_utf8_lossy(text.split(|&byte| byte == 0).next().unwrap_or_default()).to_string();
+
+        // Fields are written as key='value'; values may contain quotes, so split at the "';" terminator
+        let fields: Vec<(String, String)> =
+            text.split("';").filter_map(|field| field.split_once("='")).map(|(key, value)| (key.trim().to_string(), value.to_string())).collect();
+        if fields.is_empty() == true
+        {
+            return None;
+        }
+
+        Some(Self { offset: offset + position as u64 - 1, size: length as u64 + 1, fields })
+    }
+
+    /// Value of the StreamTitle field
+    pub fn title(&self) -> Option<&str>
+    {
+        self.fields.iter().find(|(key, _)| key == "StreamTitle").map(|(_, value)| value.as_str())
+    }
+
+    /// Fields as written in the stream ("StreamTitle='...'; StreamUrl='...'")
+    pub fn text(&self) -> String
+    {
+        self.fields.iter().map(|(key, value)| format!("{}='{}'", key, value)).collect::<Vec<_>>().join("; ")
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        let fields: serde_json::Map<String, serde_json::Value> = self.fields.iter().map(|(key, value)| (key.clone(), serde_json::json!(value))).collect();
+        serde_json::json!({
+            "offset": self.offset,
+            "size": self.size,
+            "fields": fields
+        })
+    }
+}
+
+/// Find all metadata blocks in `buffer`, which starts at file offset `offset`
+pub fn find_icy_metadata(buffer: &[u8], offset: u64) -> Vec<IcyMetadata>
+{
+    let mut blocks: Vec<IcyMetadata> = Vec::new();
+    let mut position = 0;
+    while let Some(found) = buffer[position..].windows(STREAM_TITLE.len()).position(|window| window == STREAM_TITLE)
+    {
+        let start = position + found;
+        match IcyMetadata::parse(buffer, start, offset)
+        {
+            | Some(block) =>
+            {
+                position = start + block.size as usize - 1;
+                blocks.push(block);
+            }
+            | None => position = start + 1
+        }
+    }
+    blocks
+}
@@ -0,0 +1,21 @@
+// MPEG program stream dissection
+//
+// This module provides support for MPEG-1 and MPEG-2 program streams (ISO/IEC 11172-1,
+// ISO/IEC 13818-1) as found in DVD VOB files and legacy .mpg files: pack headers with the
+// system clock reference, system headers, and PES packets grouped per stream ID, including
+// the DVD sub-streams of private stream 1 and the navigation packets of private stream 2.
+
+// Core types and dissector
+pub mod dissector;
+pub mod pack;
+
+// Re-export commonly used types for convenience
+pub use dissector::MpegPsDissector;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the MPEG program stream dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<MpegPsDissector>(800);
+}
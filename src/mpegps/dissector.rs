@@ -0,0 +1,514 @@
+use std::{collections::BTreeMap, io::Write};
+
+use crate::{
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    mpegps::pack::{END_CODE, PACK_START_CODE, PackHeader, PesHeader, SYSTEM_HEADER_START_CODE, SystemHeader, VideoSequenceHeader, stream_id_name, substream_name},
+    options::DissectOptions
+};
+
+/// Findings of one kind reported before only the total is reported
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Bytes searched for the next pack header after the start code sequence was lost
+const MAX_RESYNC_DISTANCE: u64 = 1024 * 1024;
+
+/// System clock frequency the SCR counts in
+const SCR_FREQUENCY: f64 = 27_000_000.0;
+
+/// The PTS wraps around after 2^33 ticks of 90 kHz
+const PTS_WRAP: u64 = 1 << 33;
+
+/// Packets of one elementary stream (stream ID, and sub-stream ID for private stream 1)
+#[derive(Debug, Clone, Default)]
+struct StreamStats
+{
+    packets:   u64,
+    bytes:     u64,
+    first_pts: Option<u64>,
+    /// Largest PTS distance from the first PTS, in 90 kHz ticks
+    pts_span:  u64,
+    video:     Option<VideoSequenceHeader>
+}
+
+impl StreamStats
+{
+    /// Duration covered by the presentation time stamps in seconds
+    fn pts_duration(&self) -> Option<f64>
+    {
+        self.first_pts.map(|_| self.pts_span as f64 / 90000.0)
+    }
+}
+
+/// Result of walking the packs of a program stream region
+struct ProgramStream
+{
+    mpeg_version:       Option<u8>,
+    packs:              u64,
+    /// Largest multiplex rate of the pack headers in bits per second
+    bit_rate:           u64,
+    /// Sum of the regular SCR intervals in 27 MHz ticks
+    scr_elapsed:        u64,
+    system_header:      Option<SystemHeader>,
+    streams:            BTreeMap<(u8, Option<u8>), StreamStats>,
+    padding_bytes:      u64,
+    navigation_packets: u64,
+    has_end_code:       bool,
+    /// Byte ranges of consecutive packs (broken by resynchronization)
+    ranges:             Vec<(u64, u64)>,
+    end_offset:         u64
+}
+
+impl ProgramStream
+{
+    /// Duration from the SCR, or from the presentation time stamps
+    fn duration(&self) -> Option<f64>
+    {
+        match self.scr_elapsed
+        {
+            | 0 => self.streams.values().filter_map(StreamStats::pts_duration).reduce(f64::max),
+            | elapsed => Some(elapsed as f64 / SCR_FREQUENCY)
+        }
+    }
+
+    /// Description of an elementary stream
+    fn stream_description(stream_id: u8, substream_id: Option<u8>) -> &'static str
+    {
+        match substream_id
+        {
+            | Some(substream_id) => substream_name(substream_id),
+            | None => stream_id_name(stream_id)
+        }
+    }
+
+    /// ffmpeg codec name and codec type of an elementary stream
+    fn codec(&self, stream_id: u8, substream_id: Option<u8>) -> (Option<&'static str>, &'static str)
+    {
+        match (stream_id, substream_id)
+        {
+            | (0xE0..=0xEF, _) if self.mpeg_version == Some(1) => (Some("mpeg1video"), "video"),
+            | (0xE0..=0xEF, _) => (Some("mpeg2video"), "video"),
+            | (0xC0..=0xDF, _) => (Some("mp2"), "audio"),
+            | (_, Some(0x20..=0x3F)) => (Some("dvd_subtitle"), "subtitle"),
+            | (_, Some(0x80..=0x87)) => (Some("ac3"), "audio"),
+            | (_, Some(0x88..=0x8F)) => (Some("dts"), "audio"),
+            | (_, Some(0xA0..=0xA7)) => (Some("pcm_dvd"), "audio"),
+            | _ => (None, "data")
+        }
+    }
+}
+
+/// MPEG program stream dissector (DVD VOB, MPEG-1/MPEG-2 .mpg files) - unit struct
+#[derive(Default)]
+pub struct MpegPsDissector;
+
+impl MpegPsDissector
+{
+    /// Find the next pack start code from `offset`
+    fn resync(reader: &mut MediaReader, offset: u64, file_size: u64) -> Result<Option<u64>, Box<dyn std::error::Error>>
+    {
+        let length = MAX_RESYNC_DISTANCE.min(file_size.saturating_sub(offset));
+        let mut window = vec![0u8; length as usize];
+        reader.seek_to(offset)?;
+        reader.read_exact(&mut window)?;
+
+        Ok(window.windows(4).position(|bytes| bytes == [0x00, 0x00, 0x01, PACK_START_CODE]).map(|position| offset + position as u64))
+    }
+
+    /// Walk all packs and packets from `start_offset`, collecting stream statistics and SCR timing
+    fn read_packs(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<ProgramStream, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut stream = ProgramStream {
+            mpeg_version:       None,
+            packs:              0,
+            bit_rate:           0,
+            scr_elapsed:        0,
+            system_header:      None,
+            streams:            BTreeMap::new(),
+            padding_bytes:      0,
+            navigation_packets: 0,
+            has_end_code:       false,
+            ranges:             Vec::new(),
+            end_offset:         start_offset
+        };
+        let mut offset = start_offset;
+        let mut range_start = start_offset;
+        let mut last_scr: Option<u64> = None;
+        let mut scr_errors = 0;
+        let mut packet = Vec::new();
+        let mut truncated = false;
+
+        while offset + 4 <= file_size
+        {
+            let mut start_code = [0u8; 4];
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut start_code)?;
+
+            if start_code[0..3] != [0x00, 0x00, 0x01] || start_code[3] < END_CODE
+            {
+                stream.ranges.push((range_start, offset));
+                match Self::resync(&mut reader, offset + 1, file_size)?
+                {
+                    | Some(next) =>
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("Program stream start code missing, {} bytes skipped to the next pack", &[&(next - offset)]));
+                        offset = next;
+                        range_start = next;
+                        continue;
+                    }
+                    | None =>
+                    {
+                        diagnostics.info(Some(offset), tr_fmt("Program stream packs end, {} bytes of other data follow", &[&(file_size - offset)]));
+                        stream.end_offset = offset;
+                        return Ok(stream);
+                    }
+                }
+            }
+
+            match start_code[3]
+            {
+                | END_CODE =>
+                {
+                    stream.has_end_code = true;
+                    offset += 4;
+                    break;
+                }
+                | PACK_START_CODE =>
+                {
+                    let mut header = start_code.to_vec();
+                    header.resize((14u64.min(file_size - offset)) as usize, 0);
+                    reader.read_exact(&mut header[4..])?;
+                    let pack = match PackHeader::parse(&header)
+                    {
+                        | Ok(pack) => pack,
+                        | Err(e) =>
+                        {
+                            diagnostics.warning(Some(offset), e);
+                            truncated = header.len() < 14;
+                            break;
+                        }
+                    };
+                    if stream.mpeg_version.is_some_and(|version| version != pack.mpeg_version) == true
+                    {
+                        diagnostics.warning(
+                            Some(offset),
+                            tr_fmt("MPEG-{} pack header in an MPEG-{} program stream", &[&pack.mpeg_version, &stream.mpeg_version.unwrap_or(0)])
+                        );
+                    }
+                    stream.mpeg_version.get_or_insert(pack.mpeg_version);
+                    stream.packs += 1;
+                    stream.bit_rate = stream.bit_rate.max(pack.bit_rate());
+
+                    // SCR intervals of up to 0.7 s are allowed (2.7.1); larger jumps are left out of the duration
+                    if let Some(last) = last_scr
+                    {
+                        match pack.scr.checked_sub(last)
+                        {
+                            | Some(interval) if interval < SCR_FREQUENCY as u64 => stream.scr_elapsed += interval,
+                            | _ =>
+                            {
+                                scr_errors += 1;
+                                if scr_errors <= MAX_REPORTED_ERRORS
+                                {
+                                    diagnostics.warning(
+                                        Some(offset),
+                                        tr_fmt("SCR jumps from {} s to {} s", &[
+                                            &format!("{:.3}", last as f64 / SCR_FREQUENCY),
+                                            &format!("{:.3}", pack.scr as f64 / SCR_FREQUENCY)
+                                        ])
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    last_scr = Some(pack.scr);
+                    offset += pack.size as u64;
+                }
+                | stream_id =>
+                {
+                    let mut length = [0u8; 2];
+                    if offset + 6 > file_size
+                    {
+                        diagnostics.warning(Some(offset), tr("Program stream packet header truncated at end of file"));
+                        truncated = true;
+                        break;
+                    }
+                    reader.read_exact(&mut length)?;
+                    let size = 6 + u16::from_be_bytes(length) as u64;
+                    if offset + size > file_size
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("Program stream packet truncated: {} bytes declared, {} available", &[&size, &(file_size - offset)]));
+                        truncated = true;
+                        break;
+                    }
+                    packet.clear();
+                    packet.extend_from_slice(&start_code);
+                    packet.extend_from_slice(&length);
+                    packet.resize(size as usize, 0);
+                    reader.read_exact(&mut packet[6..])?;
+
+                    match stream_id
+                    {
+                        | SYSTEM_HEADER_START_CODE => match SystemHeader::parse(&packet[6..])
+                        {
+                            | Ok(system_header) =>
+                            {
+                                stream.system_header.get_or_insert(system_header);
+                            }
+                            | Err(e) => diagnostics.warning(Some(offset), e)
+                        },
+                        | 0xBE => stream.padding_bytes += size,
+                        | 0xBF => stream.navigation_packets += 1,
+                        | _ => Self::add_packet(&mut stream, stream_id, &packet, offset, diagnostics)
+                    }
+                    offset += size;
+                }
+            }
+        }
+
+        if stream.has_end_code == false && truncated == false && offset + 4 > file_size
+        {
+            diagnostics.info(Some(offset), tr("Program stream has no end code (truncated or cut file)"));
+        }
+        if scr_errors > MAX_REPORTED_ERRORS
+        {
+            diagnostics.warning(Some(start_offset), tr_fmt("{} SCR discontinuities in total", &[&scr_errors]));
+        }
+        if stream.packs == 0
+        {
+            diagnostics.warning(Some(start_offset), tr("Program stream contains no pack header"));
+        }
+        stream.ranges.push((range_start, offset));
+        // The truncated packet at the end still belongs to the stream
+        stream.end_offset = match truncated
+        {
+            | true => file_size,
+            | false => offset
+        };
+
+        Ok(stream)
+    }
+
+    /// Count a PES packet of an elementary stream
+    fn add_packet(stream: &mut ProgramStream, stream_id: u8, packet: &[u8], offset: u64, diagnostics: &mut Diagnostics)
+    {
+        let Some(header) = PesHeader::parse(packet)
+        else
+        {
+            diagnostics.warning(Some(offset), tr_fmt("PES packet of stream {} has a truncated header", &[&format!("0x{:02X}", stream_id)]));
+            return;
+        };
+        let payload = packet.get(header.payload_offset..).unwrap_or_default();
+        // DVD audio and subpicture streams are multiplexed into private stream 1, tagged by the first payload byte
+        let substream_id = match stream_id
+        {
+            | 0xBD => payload.first().copied(),
+            | _ => None
+        };
+
+        let stats = stream.streams.entry((stream_id, substream_id)).or_default();
+        stats.packets += 1;
+        stats.bytes += payload.len() as u64;
+        if matches!(stream_id, 0xE0..=0xEF) == true && stats.video.is_none() == true
+        {
+            stats.video = VideoSequenceHeader::find(payload);
+        }
+        if let Some(pts) = header.pts
+        {
+            match stats.first_pts
+            {
+                | Some(first) =>
+                {
+                    let span = (pts + PTS_WRAP - first) % PTS_WRAP;
+                    // Time stamps before the first one (reordered frames) wrap around to huge distances
+                    if span < PTS_WRAP / 2
+                    {
+                        stats.pts_span = stats.pts_span.max(span);
+                    }
+                }
+                | None => stats.first_pts = Some(pts)
+            }
+        }
+    }
+}
+
+impl MediaDissector for MpegPsDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "MPEG-PS"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "MPEG Program Stream Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packs(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("MPEG Program Stream:").bright_cyan().bold())?;
+            if let Some(version) = stream.mpeg_version
+            {
+                writeln!(output, "  {}", tr_fmt("Pack headers: MPEG-{}", &[&version]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Packs: {}", &[&stream.packs]))?;
+            writeln!(output, "  {}", tr_fmt("Mux rate: {} kbit/s", &[&(stream.bit_rate / 1000)]))?;
+            if let Some(duration) = stream.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.end_offset - start_offset)]))?;
+        }
+
+        if options.show_data == true
+        {
+            if let Some(ref system_header) = stream.system_header
+            {
+                writeln!(output, "\n{}", tr("System Header:").bright_cyan().bold())?;
+                for line in system_header.to_string().lines()
+                {
+                    writeln!(output, "  {}", line)?;
+                }
+            }
+
+            writeln!(output, "\n{}", tr("Elementary Streams:").bright_cyan().bold())?;
+            for (&(stream_id, substream_id), stats) in &stream.streams
+            {
+                let id = match substream_id
+                {
+                    | Some(substream_id) => format!("0x{:02X}/0x{:02X}", stream_id, substream_id),
+                    | None => format!("0x{:02X}", stream_id)
+                };
+                let mut line = format!(
+                    "{:<11} {:<20} {}, {}",
+                    id,
+                    ProgramStream::stream_description(stream_id, substream_id),
+                    tr_fmt("{} packets", &[&stats.packets]),
+                    tr_fmt("{} bytes", &[&stats.bytes])
+                );
+                if let Some(duration) = stats.pts_duration()
+                {
+                    line.push_str(&format!(", {}", tr_fmt("PTS span {} s", &[&format!("{:.3}", duration)])));
+                }
+                if let Some(video) = stats.video
+                {
+                    line.push_str(&format!(", {}", video));
+                }
+                writeln!(output, "  {}", line)?;
+            }
+            if stream.navigation_packets > 0
+            {
+                writeln!(output, "  {}", tr_fmt("DVD navigation packets: {}", &[&stream.navigation_packets]))?;
+            }
+            if stream.padding_bytes > 0
+            {
+                writeln!(output, "  {}", tr_fmt("Padding: {} bytes", &[&stream.padding_bytes]))?;
+            }
+        }
+
+        Ok(stream.end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packs(file, start_offset, diagnostics)?;
+
+        Ok((
+            serde_json::json!({
+                "mpeg_version": stream.mpeg_version,
+                "packs": stream.packs,
+                "mux_rate": stream.bit_rate,
+                "duration": stream.duration(),
+                "end_code": stream.has_end_code,
+                "system_header": stream.system_header.as_ref().map(|system_header| serde_json::json!({
+                    "rate_bound": system_header.rate_bound as u64 * 400,
+                    "audio_bound": system_header.audio_bound,
+                    "video_bound": system_header.video_bound
+                })),
+                "streams": stream.streams.iter().map(|(&(stream_id, substream_id), stats)| serde_json::json!({
+                    "stream_id": stream_id,
+                    "substream_id": substream_id,
+                    "description": ProgramStream::stream_description(stream_id, substream_id),
+                    "packets": stats.packets,
+                    "bytes": stats.bytes,
+                    "pts_duration": stats.pts_duration(),
+                    "width": stats.video.map(|video| video.width),
+                    "height": stats.video.map(|video| video.height),
+                    "frame_rate": stats.video.and_then(|video| video.frame_rate)
+                })).collect::<Vec<_>>(),
+                "navigation_packets": stream.navigation_packets,
+                "padding_bytes": stream.padding_bytes
+            }),
+            stream.end_offset
+        ))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packs(file, start_offset, diagnostics)?;
+
+        let streams = stream
+            .streams
+            .iter()
+            .map(|(&(stream_id, substream_id), stats)| {
+                let (codec_name, codec_type) = stream.codec(stream_id, substream_id);
+                ProbeStream {
+                    codec_name,
+                    codec_long_name: Some(ProgramStream::stream_description(stream_id, substream_id)),
+                    codec_type,
+                    width: stats.video.map(|video| video.width as u32),
+                    height: stats.video.map(|video| video.height as u32),
+                    duration: stats.pts_duration(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok((Some(ProbeFormat { format_name: "mpeg", format_long_name: "MPEG-PS (MPEG-2 Program Stream)", duration: stream.duration(), streams }), stream.end_offset))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_packs(file, start_offset, &mut Diagnostics::new())?;
+        for &(start, end) in &stream.ranges
+        {
+            coverage.add(start, end);
+        }
+
+        Ok(stream.end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packs(file, start_offset, diagnostics)?;
+
+        Ok(stream.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // Pack start code followed by an MPEG-1 or MPEG-2 pack header
+        match header.len() >= 5 && header[0..4] == [0x00, 0x00, 0x01, PACK_START_CODE] && (header[4] >> 6 == 0b01 || header[4] >> 4 == 0b0010)
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Start code of a pack header
+pub const PACK_START_CODE: u8 = 0xBA;
+
+/// Start code of a system header
+pub const SYSTEM_HEADER_START_CODE: u8 = 0xBB;
+
+/// Start code ending the program stream
+pub const END_CODE: u8 = 0xB9;
+
+/// Describe a stream ID (ISO/IEC 13818-1 table 2-22)
+pub fn stream_id_name(stream_id: u8) -> &'static str
+{
+    match stream_id
+    {
+        | 0xBC => "Program stream map",
+        | 0xBD => "Private stream 1",
+        | 0xBE => "Padding stream",
+        | 0xBF => "Private stream 2",
+        | 0xC0..=0xDF => "MPEG audio",
+        | 0xE0..=0xEF => "MPEG video",
+        | 0xF0 => "ECM stream",
+        | 0xF1 => "EMM stream",
+        | 0xF2 => "DSM-CC stream",
+        | 0xFF => "Program stream directory",
+        | _ => "Reserved stream"
+    }
+}
+
+/// Describe a DVD sub-stream of private stream 1, identified by the first payload byte
+pub fn substream_name(substream_id: u8) -> &'static str
+{
+    match substream_id
+    {
+        | 0x20..=0x3F => "DVD subpicture",
+        | 0x80..=0x87 => "AC-3 audio",
+        | 0x88..=0x8F => "DTS audio",
+        | 0xA0..=0xA7 => "LPCM audio",
+        | _ => "Private data"
+    }
+}
+
+/// Pack header carrying the system clock reference (ISO/IEC 13818-1, 2.5.3.3; ISO/IEC 11172-1 for MPEG-1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackHeader
+{
+    /// 1 for ISO/IEC 11172-1 packs, 2 for ISO/IEC 13818-1 packs
+    pub mpeg_version: u8,
+    /// System clock reference in 27 MHz ticks
+    pub scr:          u64,
+    /// Multiplex rate in units of 50 bytes per second
+    pub mux_rate:     u32,
+    /// Pack header size including start code and stuffing
+    pub size:         usize
+}
+
+impl PackHeader
+{
+    /// Parse the pack header at the start of `data` (including its start code)
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let bytes = data.get(4..).unwrap_or_default();
+        match bytes.first()
+        {
+            | Some(&first) if first >> 6 == 0b01 =>
+            {
+                if bytes.len() < 10
+                {
+                    return Err(tr("MPEG-2 pack header truncated").to_string());
+                }
+                let b = |index: usize| bytes[index] as u64;
+                let base = ((b(0) >> 3) & 0x07) << 30 | (b(0) & 0x03) << 28 | b(1) << 20 | (b(2) >> 3) << 15 | (b(2) & 0x03) << 13 | b(3) << 5 | b(4) >> 3;
+                let extension = (b(4) & 0x03) << 7 | b(5) >> 1;
+                Ok(PackHeader {
+                    mpeg_version: 2,
+                    scr:          base * 300 + extension,
+                    mux_rate:     (b(6) << 14 | b(7) << 6 | b(8) >> 2) as u32,
+                    size:         14 + (bytes[9] & 0x07) as usize
+                })
+            }
+            | Some(&first) if first >> 4 == 0b0010 =>
+            {
+                if bytes.len() < 8
+                {
+                    return Err(tr("MPEG-1 pack header truncated").to_string());
+                }
+                let b = |index: usize| bytes[index] as u64;
+                let base = ((b(0) >> 1) & 0x07) << 30 | b(1) << 22 | (b(2) >> 1) << 15 | b(3) << 7 | b(4) >> 1;
+                Ok(PackHeader { mpeg_version: 1, scr: base * 300, mux_rate: ((b(5) & 0x7F) << 15 | b(6) << 7 | b(7) >> 1) as u32, size: 12 })
+            }
+            | _ => Err(tr("Unknown pack header layout").to_string())
+        }
+    }
+
+    /// Multiplex rate in bits per second
+    pub fn bit_rate(&self) -> u64
+    {
+        self.mux_rate as u64 * 50 * 8
+    }
+}
+
+/// System header with the stream bounds of the program stream (2.5.3.5)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemHeader
+{
+    /// Upper bound of the multiplex rate in units of 50 bytes per second
+    pub rate_bound:  u32,
+    pub audio_bound: u8,
+    pub video_bound: u8,
+    /// Stream ID and P-STD buffer size in bytes of every stream entry
+    pub streams:     Vec<(u8, u32)>
+}
+
+impl SystemHeader
+{
+    /// Parse the body of a system header (following its start code and length field)
+    pub fn parse(body: &[u8]) -> Result<Self, String>
+    {
+        if body.len() < 6
+        {
+            return Err(tr("System header too short").to_string());
+        }
+
+        // Stream entries: stream ID, '11', buffer bound scale and 13-bit buffer size bound
+        let streams = body[6..]
+            .chunks_exact(3)
+            .take_while(|entry| entry[0] & 0x80 != 0)
+            .map(|entry| {
+                let scale = match entry[1] & 0x20
+                {
+                    | 0 => 128,
+                    | _ => 1024
+                };
+                (entry[0], (u16::from_be_bytes([entry[1] & 0x1F, entry[2]]) as u32) * scale)
+            })
+            .collect();
+
+        Ok(SystemHeader {
+            rate_bound: ((body[0] as u32 & 0x7F) << 15) | (body[1] as u32) << 7 | body[2] as u32 >> 1,
+            audio_bound: body[3] >> 2,
+            video_bound: body[4] & 0x1F,
+            streams
+        })
+    }
+}
+
+impl fmt::Display for SystemHeader
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Rate bound: {} kbit/s", &[&(self.rate_bound as u64 * 400 / 1000)]))?;
+        writeln!(f, "{}", tr_fmt("Audio bound: {}, video bound: {}", &[&self.audio_bound, &self.video_bound]))?;
+        for &(stream_id, buffer) in &self.streams
+        {
+            writeln!(f, "  {}", tr_fmt("Stream {} ({}): buffer {} bytes", &[&format!("0x{:02X}", stream_id), &stream_id_name(stream_id), &buffer]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a 33-bit time stamp from its 5-byte PES encoding
+fn read_timestamp(bytes: &[u8]) -> u64
+{
+    ((bytes[0] as u64 >> 1) & 0x07) << 30 | (bytes[1] as u64) << 22 | (bytes[2] as u64 >> 1) << 15 | (bytes[3] as u64) << 7 | bytes[4] as u64 >> 1
+}
+
+/// Header of a PES packet in a program stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PesHeader
+{
+    /// Presentation time stamp in 90 kHz units
+    pub pts:            Option<u64>,
+    /// Offset of the packet data within the packet (after start code, length and optional header)
+    pub payload_offset: usize
+}
+
+impl PesHeader
+{
+    /// Parse the header of the PES packet `packet` (including start code and length), MPEG-1 or MPEG-2 syntax
+    pub fn parse(packet: &[u8]) -> Option<Self>
+    {
+        let stream_id = *packet.get(3)?;
+        // Padding, private stream 2 and the system streams have no optional header
+        if matches!(stream_id, 0xBC | 0xBE | 0xBF | 0xF0 | 0xF1 | 0xF2 | 0xF8 | 0xFF) == true
+        {
+            return Some(PesHeader { pts: None, payload_offset: 6 });
+        }
+
+        if *packet.get(6)? >> 6 == 0b10
+        {
+            let header_length = *packet.get(8)? as usize;
+            let pts = match packet[7] & 0x80
+            {
+                | 0 => None,
+                | _ => packet.get(9..14).map(read_timestamp)
+            };
+            return Some(PesHeader { pts, payload_offset: 9 + header_length });
+        }
+
+        // MPEG-1: stuffing bytes, optional STD buffer field, then the time stamp fields
+        let mut position = 6;
+        while *packet.get(position)? == 0xFF
+        {
+            position += 1;
+        }
+        if packet[position] >> 6 == 0b01
+        {
+            position += 2;
+        }
+        let marker = *packet.get(position)? >> 4;
+        let (pts, length) = match marker
+        {
+            | 0b0010 => (packet.get(position..position + 5).map(read_timestamp), 5),
+            | 0b0011 => (packet.get(position..position + 5).map(read_timestamp), 10),
+            | _ => (None, 1)
+        };
+        Some(PesHeader { pts, payload_offset: position + length })
+    }
+}
+
+/// Sequence header of an MPEG-1/MPEG-2 video stream (ISO/IEC 13818-2, 6.2.2.1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoSequenceHeader
+{
+    pub width:      u16,
+    pub height:     u16,
+    pub frame_rate: Option<f64>
+}
+
+impl VideoSequenceHeader
+{
+    /// Find and parse a sequence header within the video data `data`
+    pub fn find(data: &[u8]) -> Option<Self>
+    {
+        let position = data.windows(4).position(|window| window == [0x00, 0x00, 0x01, 0xB3])?;
+        let bytes = data.get(position + 4..position + 8)?;
+        let frame_rate = match bytes[3] & 0x0F
+        {
+            | 1 => Some(24000.0 / 1001.0),
+            | 2 => Some(24.0),
+            | 3 => Some(25.0),
+            | 4 => Some(30000.0 / 1001.0),
+            | 5 => Some(30.0),
+            | 6 => Some(50.0),
+            | 7 => Some(60000.0 / 1001.0),
+            | 8 => Some(60.0),
+            | _ => None
+        };
+
+        Some(VideoSequenceHeader { width: (bytes[0] as u16) << 4 | (bytes[1] as u16) >> 4, height: ((bytes[1] & 0x0F) as u16) << 8 | bytes[2] as u16, frame_rate })
+    }
+}
+
+impl fmt::Display for VideoSequenceHeader
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if let Some(frame_rate) = self.frame_rate
+        {
+            write!(f, "{}", tr_fmt(", {} fps", &[&format!("{:.3}", frame_rate)]))?;
+        }
+        Ok(())
+    }
+}
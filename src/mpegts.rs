@@ -0,0 +1,22 @@
+// MPEG transport stream dissection
+//
+// This module provides support for MPEG-2 transport streams (ISO/IEC 13818-1) as used by
+// broadcast captures, Blu-ray M2TS files and HLS segments: packet layout detection, program
+// association and program map tables with CRC verification, continuity counter checks per
+// PID, PCR timing and the presentation time stamps of the elementary streams.
+
+// Core types and dissector
+pub mod dissector;
+pub mod packet;
+pub mod psi;
+
+// Re-export commonly used types for convenience
+pub use dissector::MpegTsDissector;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the MPEG transport stream dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<MpegTsDissector>(900);
+}
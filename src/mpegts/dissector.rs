@@ -0,0 +1,794 @@
+use std::{collections::BTreeMap, io::Write};
+
+use crate::{
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    isobmff::tree::InitSegment,
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    mpegts::{
+        packet::{NULL_PID, PACKET_SIZE, PCR_FREQUENCY, PacketFormat, SYNC_BYTE, TsPacket, pes_payload, pes_pts},
+        psi::{ProgramAssociation, ProgramMap, SectionAssembler}
+    },
+    options::DissectOptions,
+    playlist::SegmentMedia,
+    timed_id3::TimedId3
+};
+
+/// Findings of one kind reported per PID before only the total is reported
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Bytes searched for the next packet after the sync byte was lost
+const MAX_RESYNC_DISTANCE: u64 = 1024 * 1024;
+
+/// Packets a sniff buffer must hold, all in sync, for a stream to be rated `Confidence::Certain`
+const SNIFF_PACKETS: usize = 8;
+
+/// Largest PCR interval allowed by ISO/IEC 13818-1 (2.7.2), in 27 MHz ticks
+const MAX_PCR_INTERVAL: u64 = 2_700_000;
+
+/// The PCR wraps around after 2^33 ticks of its 90 kHz base
+const PCR_WRAP: u64 = (1 << 33) * 300;
+
+/// The PTS wraps around after 2^33 ticks of 90 kHz
+const PTS_WRAP: u64 = 1 << 33;
+
+/// Program clock reference values of one PID
+#[derive(Debug, Clone, Default)]
+struct PcrTiming
+{
+    count:        u64,
+    first:        u64,
+    last:         u64,
+    /// Sum of the regular intervals in ticks (intervals across discontinuities are left out)
+    elapsed:      u64,
+    max_interval: u64,
+    first_offset: u64,
+    last_offset:  u64
+}
+
+impl PcrTiming
+{
+    fn duration(&self) -> f64
+    {
+        self.elapsed as f64 / PCR_FREQUENCY
+    }
+
+    /// Transport rate between the first and last PCR in bits per second
+    fn bit_rate(&self) -> Option<u64>
+    {
+        (self.elapsed > 0).then(|| ((self.last_offset - self.first_offset) as f64 * 8.0 / self.duration()) as u64)
+    }
+
+    fn to_json(&self, pid: u16) -> serde_json::Value
+    {
+        serde_json::json!({
+            "pid": pid,
+            "count": self.count,
+            "first": self.first as f64 / PCR_FREQUENCY,
+            "last": self.last as f64 / PCR_FREQUENCY,
+            "duration": self.duration(),
+            "max_interval": self.max_interval as f64 / PCR_FREQUENCY,
+            "bit_rate": self.bit_rate()
+        })
+    }
+}
+
+/// Packet statistics of one PID
+#[derive(Debug, Clone, Default)]
+struct PidStats
+{
+    packets:           u64,
+    continuity_errors: usize,
+    transport_errors:  usize,
+    scrambled:         u64,
+    last_counter:      Option<u8>,
+    /// Packet with payload repeated once with the same counter (allowed by 2.4.3.3)
+    duplicate:         bool,
+    pcr:               Option<PcrTiming>,
+    first_pts:         Option<u64>,
+    /// Largest PTS distance from the first PTS, in 90 kHz ticks
+    pts_span:          u64
+}
+
+impl PidStats
+{
+    /// Duration covered by the presentation time stamps in seconds
+    fn pts_duration(&self) -> Option<f64>
+    {
+        self.first_pts.map(|_| self.pts_span as f64 / 90000.0)
+    }
+}
+
+/// Result of walking the packets of a transport stream region
+struct TransportStream
+{
+    format:         PacketFormat,
+    packets:        u64,
+    pat:            Option<ProgramAssociation>,
+    programs:       Vec<ProgramMap>,
+    pids:           BTreeMap<u16, PidStats>,
+    /// Byte ranges of consecutive packets (broken by resynchronization)
+    ranges:         Vec<(u64, u64)>,
+    /// ID3v2 tags of the timed metadata PIDs with their presentation time stamps
+    timed_metadata: Vec<(u16, TimedId3)>,
+    end_offset:     u64
+}
+
+impl TransportStream
+{
+    /// Role of a PID in the stream, e.g. "PAT" or the stream type of an elementary stream
+    fn pid_description(&self, pid: u16) -> &'static str
+    {
+        if let Some(stream) = self.programs.iter().flat_map(|program| program.streams.iter()).find(|stream| stream.pid == pid)
+        {
+            return stream.description();
+        }
+        if self.pat.as_ref().is_some_and(|pat| pat.programs.iter().any(|&(number, pmt_pid)| number != 0 && pmt_pid == pid)) == true
+        {
+            return "PMT";
+        }
+        match pid
+        {
+            | 0x0000 => "PAT",
+            | 0x0001 => "CAT",
+            | 0x0010 => "NIT",
+            | 0x0011 => "SDT/BAT",
+            | 0x0012 => "EIT",
+            | 0x0014 => "TDT/TOT",
+            | NULL_PID => "Null packets",
+            | _ => "Unreferenced"
+        }
+    }
+
+    /// Whether `pid` is an elementary stream carrying timed ID3 metadata
+    fn is_timed_id3(&self, pid: u16) -> bool
+    {
+        self.programs.iter().flat_map(|program| program.streams.iter()).any(|stream| stream.pid == pid && stream.is_timed_id3() == true)
+    }
+
+    /// PCR timing of the first program, falling back to any PID carrying PCRs
+    fn pcr(&self) -> Option<&PcrTiming>
+    {
+        self.programs
+            .first()
+            .and_then(|program| self.pids.get(&program.pcr_pid))
+            .and_then(|stats| stats.pcr.as_ref())
+            .or_else(|| self.pids.values().find_map(|stats| stats.pcr.as_ref()))
+    }
+
+    /// Duration from the PCR, or from the presentation time stamps without PCR
+    fn duration(&self) -> Option<f64>
+    {
+        self.pcr().filter(|pcr| pcr.elapsed > 0).map(PcrTiming::duration).or_else(|| self.pids.values().filter_map(PidStats::pts_duration).reduce(f64::max))
+    }
+}
+
+/// MPEG transport stream dissector - unit struct
+#[derive(Default)]
+pub struct MpegTsDissector;
+
+impl MpegTsDissector
+{
+    /// Find the next position from `offset` where three packets in a row start with the sync byte
+    fn resync(reader: &mut MediaReader, offset: u64, format: PacketFormat, file_size: u64) -> Result<Option<u64>, Box<dyn std::error::Error>>
+    {
+        let packet_size = format.packet_size() as u64;
+        let limit = (offset + MAX_RESYNC_DISTANCE).min(file_size.saturating_sub(packet_size * 2 + 1));
+        let mut byte = [0u8; 1];
+        for candidate in offset..limit
+        {
+            let mut synced = true;
+            for index in 0..3
+            {
+                reader.seek_to(candidate + format.prefix_size() as u64 + index * packet_size)?;
+                reader.read_exact(&mut byte)?;
+                if byte[0] != SYNC_BYTE
+                {
+                    synced = false;
+                    break;
+                }
+            }
+            if synced == true
+            {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Walk all packets from `start_offset`, collecting PSI tables, continuity, PCR and PTS statistics
+    fn read_packets(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<TransportStream, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+
+        let mut header = vec![0u8; (3 * PacketFormat::ReedSolomon.packet_size()).min((file_size - start_offset) as usize)];
+        reader.read_exact(&mut header)?;
+        let format = PacketFormat::detect(&header).ok_or(tr("No MPEG transport stream packets found"))?;
+        let packet_size = format.packet_size() as u64;
+
+        let mut stream = TransportStream {
+            format,
+            packets: 0,
+            pat: None,
+            programs: Vec::new(),
+            pids: BTreeMap::new(),
+            ranges: Vec::new(),
+            timed_metadata: Vec::new(),
+            end_offset: start_offset
+        };
+        let mut assemblers: BTreeMap<u16, SectionAssembler> = BTreeMap::new();
+        // PES packets of the timed ID3 PIDs being assembled, with the offset of their first packet
+        let mut pes_packets: BTreeMap<u16, (u64, Vec<u8>)> = BTreeMap::new();
+        let mut packet = vec![0u8; format.packet_size()];
+        let mut offset = start_offset;
+        let mut range_start = start_offset;
+
+        while offset + packet_size <= file_size
+        {
+            reader.seek_to(offset)?;
+            reader.read_exact(&mut packet)?;
+            let data = &packet[format.prefix_size()..format.prefix_size() + PACKET_SIZE];
+
+            if data[0] != SYNC_BYTE
+            {
+                stream.ranges.push((range_start, offset));
+                match Self::resync(&mut reader, offset + 1, format, file_size)?
+                {
+                    | Some(next) =>
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("Transport packet sync lost, {} bytes skipped", &[&(next - offset)]));
+                        offset = next;
+                        range_start = next;
+                        continue;
+                    }
+                    | None =>
+                    {
+                        diagnostics.info(Some(offset), tr_fmt("Transport packets end, {} bytes of other data follow", &[&(file_size - offset)]));
+                        stream.end_offset = offset;
+                        Self::add_timed_metadata(&mut stream, pes_packets, diagnostics);
+                        return Self::finish(stream, diagnostics);
+                    }
+                }
+            }
+
+            let ts_packet = match TsPacket::parse(data)
+            {
+                | Ok(ts_packet) => ts_packet,
+                | Err(e) =>
+                {
+                    diagnostics.warning(Some(offset), e);
+                    stream.packets += 1;
+                    offset += packet_size;
+                    continue;
+                }
+            };
+            stream.packets += 1;
+            let sync_offset = offset + format.prefix_size() as u64;
+            let stats = stream.pids.entry(ts_packet.pid).or_default();
+            stats.packets += 1;
+
+            if ts_packet.transport_error == true
+            {
+                stats.transport_errors += 1;
+                if stats.transport_errors <= MAX_REPORTED_ERRORS
+                {
+                    diagnostics.warning(Some(sync_offset), tr_fmt("PID {}: transport error indicator set", &[&format!("0x{:04X}", ts_packet.pid)]));
+                }
+                offset += packet_size;
+                continue;
+            }
+            if ts_packet.scrambling != 0
+            {
+                stats.scrambled += 1;
+            }
+
+            // The counter advances with every packet carrying payload, null packets are exempt
+            let discontinuity = ts_packet.adaptation.is_some_and(|adaptation| adaptation.discontinuity);
+            if ts_packet.pid != NULL_PID && ts_packet.has_payload() == true
+            {
+                if let Some(last) = stats.last_counter &&
+                    discontinuity == false
+                {
+                    let expected = (last + 1) & 0x0F;
+                    if ts_packet.continuity_counter == last && stats.duplicate == false
+                    {
+                        stats.duplicate = true;
+                    }
+                    else if ts_packet.continuity_counter != expected
+                    {
+                        stats.continuity_errors += 1;
+                        if stats.continuity_errors <= MAX_REPORTED_ERRORS
+                        {
+                            diagnostics.warning(
+                                Some(sync_offset),
+                                tr_fmt("PID {}: continuity counter {} follows {} ({} packets lost)", &[
+                                    &format!("0x{:04X}", ts_packet.pid),
+                                    &ts_packet.continuity_counter,
+                                    &last,
+                                    &(ts_packet.continuity_counter as i32 - expected as i32).rem_euclid(16)
+                                ])
+                            );
+                        }
+                    }
+                    if ts_packet.continuity_counter != last
+                    {
+                        stats.duplicate = false;
+                    }
+                }
+                stats.last_counter = Some(ts_packet.continuity_counter);
+            }
+
+            if let Some(pcr) = ts_packet.adaptation.and_then(|adaptation| adaptation.pcr)
+            {
+                match stats.pcr
+                {
+                    | Some(ref mut timing) =>
+                    {
+                        let interval = (pcr + PCR_WRAP - timing.last) % PCR_WRAP;
+                        // Jumps of a second or more are discontinuities: signaled ones are expected, others are errors
+                        if interval >= PCR_FREQUENCY as u64
+                        {
+                            if discontinuity == false
+                            {
+                                diagnostics.warning(
+                                    Some(sync_offset),
+                                    tr_fmt("PID {}: PCR jumps by {} s without discontinuity indicator", &[
+                                        &format!("0x{:04X}", ts_packet.pid),
+                                        &format!("{:.3}", interval as f64 / PCR_FREQUENCY)
+                                    ])
+                                );
+                            }
+                        }
+                        else
+                        {
+                            timing.elapsed += interval;
+                            timing.max_interval = timing.max_interval.max(interval);
+                        }
+                        timing.count += 1;
+                        timing.last = pcr;
+                        timing.last_offset = sync_offset;
+                    }
+                    | None =>
+                    {
+                        stats.pcr = Some(PcrTiming { count: 1, first: pcr, last: pcr, first_offset: sync_offset, last_offset: sync_offset, ..Default::default() });
+                    }
+                }
+            }
+
+            if let Some(payload_start) = ts_packet.payload_start
+            {
+                let payload = &data[payload_start..];
+                let is_pmt = stream.pat.as_ref().is_some_and(|pat| pat.programs.iter().any(|&(number, pid)| number != 0 && pid == ts_packet.pid));
+                if ts_packet.pid == 0x0000 || is_pmt == true
+                {
+                    let sections = assemblers.entry(ts_packet.pid).or_default().push(payload, ts_packet.payload_unit_start);
+                    for section in sections
+                    {
+                        Self::add_section(&mut stream, ts_packet.pid, &section, sync_offset, diagnostics);
+                    }
+                }
+                else if ts_packet.payload_unit_start == true &&
+                    let Some(pts) = pes_pts(payload)
+                {
+                    let stats = stream.pids.entry(ts_packet.pid).or_default();
+                    match stats.first_pts
+                    {
+                        | Some(first) =>
+                        {
+                            let span = (pts + PTS_WRAP - first) % PTS_WRAP;
+                            // Time stamps before the first one (reordered frames) wrap around to huge distances
+                            if span < PTS_WRAP / 2
+                            {
+                                stats.pts_span = stats.pts_span.max(span);
+                            }
+                        }
+                        | None => stats.first_pts = Some(pts)
+                    }
+                }
+
+                if stream.is_timed_id3(ts_packet.pid) == true
+                {
+                    if ts_packet.payload_unit_start == true &&
+                        let Some(previous) = pes_packets.insert(ts_packet.pid, (sync_offset, payload.to_vec()))
+                    {
+                        Self::add_timed_metadata(&mut stream, BTreeMap::from([(ts_packet.pid, previous)]), diagnostics);
+                    }
+                    else if ts_packet.payload_unit_start == false &&
+                        let Some((_, pes)) = pes_packets.get_mut(&ts_packet.pid)
+                    {
+                        pes.extend_from_slice(payload);
+                    }
+                }
+            }
+
+            offset += packet_size;
+        }
+
+        stream.ranges.push((range_start, offset));
+        stream.end_offset = offset;
+        Self::add_timed_metadata(&mut stream, pes_packets, diagnostics);
+        if offset < file_size
+        {
+            diagnostics.warning(Some(offset), tr_fmt("Transport packet truncated: {} of {} bytes", &[&(file_size - offset), &packet_size]));
+            // The truncated packet at the end still belongs to the stream
+            stream.end_offset = file_size;
+        }
+        Self::finish(stream, diagnostics)
+    }
+
+    /// Decode the ID3v2 tags of complete PES packets of the timed metadata PIDs
+    fn add_timed_metadata(stream: &mut TransportStream, pes_packets: BTreeMap<u16, (u64, Vec<u8>)>, diagnostics: &mut Diagnostics)
+    {
+        for (pid, (offset, pes)) in pes_packets
+        {
+            let Some(payload) = pes_payload(&pes)
+            else
+            {
+                diagnostics.warning(Some(offset), tr_fmt("PID {}: timed ID3 data without PES header", &[&format!("0x{:04X}", pid)]));
+                continue;
+            };
+            match TimedId3::parse(offset, pes_pts(&pes), payload)
+            {
+                | Ok(Some(timed_id3)) => stream.timed_metadata.push((pid, timed_id3)),
+                | Ok(None) => diagnostics.warning(Some(offset), tr_fmt("PID {}: timed ID3 PES packet without ID3v2 tag", &[&format!("0x{:04X}", pid)])),
+                | Err(e) => diagnostics.warning(Some(offset), tr_fmt("PID {}: timed ID3 tag: {}", &[&format!("0x{:04X}", pid), &e]))
+            }
+        }
+    }
+
+    /// Decode a complete PAT or PMT section
+    fn add_section(stream: &mut TransportStream, pid: u16, section: &[u8], offset: u64, diagnostics: &mut Diagnostics)
+    {
+        if pid == 0x0000
+        {
+            match ProgramAssociation::parse(section)
+            {
+                | Ok(pat) =>
+                {
+                    if let Some(ref previous) = stream.pat &&
+                        previous.version != pat.version
+                    {
+                        diagnostics.info(Some(offset), tr_fmt("PAT version changes from {} to {}", &[&previous.version, &pat.version]));
+                    }
+                    stream.pat = Some(pat);
+                }
+                | Err(e) => diagnostics.warning(Some(offset), tr_fmt("PAT: {}", &[&e]))
+            }
+            return;
+        }
+
+        match ProgramMap::parse(section)
+        {
+            | Ok(pmt) => match stream.programs.iter_mut().find(|program| program.program_number == pmt.program_number)
+            {
+                | Some(program) =>
+                {
+                    if program.version != pmt.version
+                    {
+                        diagnostics
+                            .info(Some(offset), tr_fmt("Program {}: PMT version changes from {} to {}", &[&pmt.program_number, &program.version, &pmt.version]));
+                    }
+                    *program = pmt;
+                }
+                | None => stream.programs.push(pmt)
+            },
+            | Err(e) => diagnostics.warning(Some(offset), tr_fmt("PMT on PID {}: {}", &[&format!("0x{:04X}", pid), &e]))
+        }
+    }
+
+    /// Report the findings that need the whole stream: missing tables, error totals and PCR intervals
+    fn finish(stream: TransportStream, diagnostics: &mut Diagnostics) -> Result<TransportStream, Box<dyn std::error::Error>>
+    {
+        let start = stream.ranges.first().map(|&(start, _)| start);
+        match stream.pat
+        {
+            | Some(ref pat) =>
+            {
+                for &(number, pid) in pat.programs.iter().filter(|&&(number, _)| number != 0)
+                {
+                    if stream.programs.iter().any(|program| program.program_number == number) == false
+                    {
+                        diagnostics.warning(start, tr_fmt("Program {}: no program map table found on PID {}", &[&number, &format!("0x{:04X}", pid)]));
+                    }
+                }
+            }
+            | None => diagnostics.warning(start, tr("No program association table (PID 0x0000) found"))
+        }
+
+        for (&pid, stats) in &stream.pids
+        {
+            if stats.continuity_errors > MAX_REPORTED_ERRORS
+            {
+                diagnostics.warning(start, tr_fmt("PID {}: {} continuity counter errors in total", &[&format!("0x{:04X}", pid), &stats.continuity_errors]));
+            }
+            if stats.transport_errors > MAX_REPORTED_ERRORS
+            {
+                diagnostics
+                    .warning(start, tr_fmt("PID {}: {} packets with transport error indicator in total", &[&format!("0x{:04X}", pid), &stats.transport_errors]));
+            }
+            if stats.scrambled > 0
+            {
+                diagnostics.info(start, tr_fmt("PID {}: {} scrambled packets", &[&format!("0x{:04X}", pid), &stats.scrambled]));
+            }
+            if let Some(ref pcr) = stats.pcr &&
+                pcr.max_interval > MAX_PCR_INTERVAL
+            {
+                diagnostics.warning(
+                    start,
+                    tr_fmt("PID {}: PCR interval of up to {} ms exceeds the allowed 100 ms", &[
+                        &format!("0x{:04X}", pid),
+                        &format!("{:.1}", pcr.max_interval as f64 / PCR_FREQUENCY * 1000.0)
+                    ])
+                );
+            }
+            if stream.pat.is_some() == true && stream.pid_description(pid) == "Unreferenced"
+            {
+                diagnostics.info(start, tr_fmt("PID {}: {} packets not referenced by any program", &[&format!("0x{:04X}", pid), &stats.packets]));
+            }
+        }
+
+        Ok(stream)
+    }
+}
+
+impl MediaDissector for MpegTsDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "MPEG-TS"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "MPEG Transport Stream Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packets(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("MPEG Transport Stream:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Packet format: {}", &[&stream.format]))?;
+            writeln!(output, "  {}", tr_fmt("Packets: {}", &[&stream.packets]))?;
+            if let Some(ref pat) = stream.pat
+            {
+                writeln!(output, "  {}", tr_fmt("Transport stream ID: {}", &[&pat.transport_stream_id]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Programs: {}", &[&stream.programs.len()]))?;
+            if let Some(duration) = stream.duration()
+            {
+                writeln!(output, "  {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+            }
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(stream.end_offset - start_offset)]))?;
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "\n{}", tr("Programs:").bright_cyan().bold())?;
+            for program in &stream.programs
+            {
+                let pmt_pid = stream.pat.as_ref().and_then(|pat| pat.programs.iter().find(|&&(number, _)| number == program.program_number)).map(|&(_, pid)| pid);
+                writeln!(
+                    output,
+                    "\n  {}",
+                    tr_fmt("Program {}: PMT PID {}, PCR PID {}", &[
+                        &program.program_number,
+                        &pmt_pid.map(|pid| format!("0x{:04X}", pid)).unwrap_or_else(|| "?".to_string()),
+                        &format!("0x{:04X}", program.pcr_pid)
+                    ])
+                    .cyan()
+                )?;
+                for elementary_stream in &program.streams
+                {
+                    writeln!(output, "    {}", elementary_stream)?;
+                }
+            }
+
+            writeln!(output, "\n{}", tr("PIDs:").bright_cyan().bold())?;
+            for (&pid, stats) in &stream.pids
+            {
+                let mut line = format!("PID 0x{:04X}  {:<28} {}", pid, stream.pid_description(pid), tr_fmt("{} packets", &[&stats.packets]));
+                if stats.continuity_errors > 0
+                {
+                    line.push_str(&format!(", {}", tr_fmt("{} continuity errors", &[&stats.continuity_errors])));
+                }
+                if let Some(duration) = stats.pts_duration()
+                {
+                    line.push_str(&format!(", {}", tr_fmt("PTS span {} s", &[&format!("{:.3}", duration)])));
+                }
+                writeln!(output, "  {}", line)?;
+            }
+
+            let timings: Vec<(u16, &PcrTiming)> = stream.pids.iter().filter_map(|(&pid, stats)| stats.pcr.as_ref().map(|pcr| (pid, pcr))).collect();
+            if timings.is_empty() == false
+            {
+                writeln!(output, "\n{}", tr("PCR Timing:").bright_cyan().bold())?;
+                for (pid, pcr) in timings
+                {
+                    let mut line = tr_fmt("PID 0x{}: {} values, {} s, maximum interval {} ms", &[
+                        &format!("{:04X}", pid),
+                        &pcr.count,
+                        &format!("{:.3}", pcr.duration()),
+                        &format!("{:.1}", pcr.max_interval as f64 / PCR_FREQUENCY * 1000.0)
+                    ]);
+                    if let Some(bit_rate) = pcr.bit_rate()
+                    {
+                        line.push_str(&format!(", {}", tr_fmt("{} kbit/s", &[&(bit_rate / 1000)])));
+                    }
+                    writeln!(output, "  {}", line)?;
+                }
+            }
+
+            if stream.timed_metadata.is_empty() == false
+            {
+                writeln!(output, "\n{}", tr("Timed ID3 Metadata:").bright_cyan().bold())?;
+                for (pid, timed_id3) in &stream.timed_metadata
+                {
+                    write!(output, "  {}", tr_fmt("PID {}  ", &[&format!("0x{:04X}", pid)]))?;
+                    for (index, line) in timed_id3.to_string().lines().enumerate()
+                    {
+                        match index
+                        {
+                            | 0 => writeln!(output, "{}", line)?,
+                            | _ => writeln!(output, "    {}", line)?
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(stream.end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packets(file, start_offset, diagnostics)?;
+
+        let programs: Vec<serde_json::Value> = stream
+            .programs
+            .iter()
+            .map(|program| {
+                serde_json::json!({
+                    "program_number": program.program_number,
+                    "pmt_pid": stream.pat.as_ref().and_then(|pat| pat.programs.iter().find(|&&(number, _)| number == program.program_number)).map(|&(_, pid)| pid),
+                    "pcr_pid": program.pcr_pid,
+                    "streams": program.streams.iter().map(|elementary_stream| serde_json::json!({
+                        "pid": elementary_stream.pid,
+                        "stream_type": elementary_stream.stream_type,
+                        "description": elementary_stream.description(),
+                        "registration": elementary_stream.registration(),
+                        "language": elementary_stream.language()
+                    })).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        Ok((
+            serde_json::json!({
+                "packet_format": stream.format.key(),
+                "packets": stream.packets,
+                "transport_stream_id": stream.pat.as_ref().map(|pat| pat.transport_stream_id),
+                "duration": stream.duration(),
+                "programs": programs,
+                "pids": stream.pids.iter().map(|(&pid, stats)| serde_json::json!({
+                    "pid": pid,
+                    "description": stream.pid_description(pid),
+                    "packets": stats.packets,
+                    "continuity_errors": stats.continuity_errors,
+                    "transport_errors": stats.transport_errors,
+                    "scrambled_packets": stats.scrambled,
+                    "pts_duration": stats.pts_duration()
+                })).collect::<Vec<_>>(),
+                "pcr": stream.pids.iter().filter_map(|(&pid, stats)| stats.pcr.as_ref().map(|pcr| pcr.to_json(pid))).collect::<Vec<_>>(),
+                "timed_id3": stream.timed_metadata.iter().map(|(pid, timed_id3)| {
+                    let mut json = timed_id3.to_json();
+                    json["pid"] = serde_json::json!(pid);
+                    json
+                }).collect::<Vec<_>>()
+            }),
+            stream.end_offset
+        ))
+    }
+
+    fn collect_segment_media(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _init_segment: Option<&InitSegment>, media: &mut Vec<SegmentMedia>
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packets(file, start_offset, &mut Diagnostics::new())?;
+
+        // HLS signals sample encryption through the stream types, other systems through the scrambling bits
+        let encryption = if stream.programs.iter().flat_map(|program| program.streams.iter()).any(|stream| stream.is_sample_encrypted()) == true
+        {
+            Some("SAMPLE-AES".to_string())
+        }
+        else if stream.pids.values().any(|stats| stats.scrambled > 0) == true
+        {
+            Some("scrambled".to_string())
+        }
+        else
+        {
+            None
+        };
+        media.push(SegmentMedia {
+            start: stream.pids.values().filter_map(|stats| stats.first_pts).min().map(|pts| pts as f64 / 90000.0),
+            duration: stream.duration(),
+            encryption
+        });
+
+        Ok(stream.end_offset)
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packets(file, start_offset, diagnostics)?;
+
+        let streams = stream
+            .programs
+            .iter()
+            .flat_map(|program| program.streams.iter())
+            .map(|elementary_stream| {
+                let (codec_name, codec_type) = elementary_stream.codec();
+                ProbeStream {
+                    codec_name,
+                    codec_long_name: Some(elementary_stream.description()),
+                    codec_type,
+                    duration: stream.pids.get(&elementary_stream.pid).and_then(PidStats::pts_duration),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok((
+            Some(ProbeFormat { format_name: "mpegts", format_long_name: "MPEG-TS (MPEG-2 Transport Stream)", duration: stream.duration(), streams }),
+            stream.end_offset
+        ))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let stream = Self::read_packets(file, start_offset, &mut Diagnostics::new())?;
+        for &(start, end) in &stream.ranges
+        {
+            coverage.add(start, end);
+        }
+
+        Ok(stream.end_offset)
+    }
+
+    fn validate(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let stream = Self::read_packets(file, start_offset, diagnostics)?;
+
+        Ok(stream.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        let Some(format) = PacketFormat::detect(header)
+        else
+        {
+            return Confidence::None;
+        };
+
+        // Sync bytes at every packet of a full sniff buffer rule out a chance match of the first three
+        let packets = header.len().saturating_sub(format.prefix_size()) / format.packet_size();
+        match packets >= SNIFF_PACKETS && (0..packets).all(|index| header[format.prefix_size() + index * format.packet_size()] == SYNC_BYTE)
+        {
+            | true => Confidence::Certain,
+            | false => Confidence::High
+        }
+    }
+}
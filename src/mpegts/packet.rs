@@ -0,0 +1,216 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Sync byte at the start of every transport packet
+pub const SYNC_BYTE: u8 = 0x47;
+
+/// Size of a transport packet without prefix or parity bytes
+pub const PACKET_SIZE: usize = 188;
+
+/// PID of null (stuffing) packets
+pub const NULL_PID: u16 = 0x1FFF;
+
+/// System clock frequency the program clock reference counts in
+pub const PCR_FREQUENCY: f64 = 27_000_000.0;
+
+/// Layout of the packets of a transport stream file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketFormat
+{
+    /// Plain 188-byte packets (broadcast captures, HLS segments)
+    Standard,
+    /// 192-byte packets with a 4-byte arrival timestamp prefix (M2TS/BDAV)
+    Timestamped,
+    /// 204-byte packets with 16 Reed-Solomon parity bytes
+    ReedSolomon
+}
+
+impl PacketFormat
+{
+    /// Detect the packet layout from three consecutive sync bytes at the start of `header`
+    pub fn detect(header: &[u8]) -> Option<Self>
+    {
+        [PacketFormat::Standard, PacketFormat::Timestamped, PacketFormat::ReedSolomon]
+            .into_iter()
+            .find(|format| (0..3).all(|index| header.get(format.prefix_size() + index * format.packet_size()) == Some(&SYNC_BYTE)))
+    }
+
+    /// Bytes per packet including prefix and parity bytes
+    pub fn packet_size(self) -> usize
+    {
+        match self
+        {
+            | PacketFormat::Standard => PACKET_SIZE,
+            | PacketFormat::Timestamped => PACKET_SIZE + 4,
+            | PacketFormat::ReedSolomon => PACKET_SIZE + 16
+        }
+    }
+
+    /// Bytes preceding the sync byte of each packet
+    pub fn prefix_size(self) -> usize
+    {
+        match self
+        {
+            | PacketFormat::Timestamped => 4,
+            | _ => 0
+        }
+    }
+
+    /// Stable name used in structured output
+    pub fn key(self) -> &'static str
+    {
+        match self
+        {
+            | PacketFormat::Standard => "ts",
+            | PacketFormat::Timestamped => "m2ts",
+            | PacketFormat::ReedSolomon => "ts204"
+        }
+    }
+}
+
+impl fmt::Display for PacketFormat
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | PacketFormat::Standard => write!(f, "{}", tr("188-byte packets")),
+            | PacketFormat::Timestamped => write!(f, "{}", tr("192-byte packets (M2TS, 4-byte timestamp prefix)")),
+            | PacketFormat::ReedSolomon => write!(f, "{}", tr("204-byte packets (16 bytes Reed-Solomon parity)"))
+        }
+    }
+}
+
+/// Adaptation field of a transport packet (ISO/IEC 13818-1, 2.4.3.4)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptationField
+{
+    pub discontinuity: bool,
+    pub random_access: bool,
+    /// Program clock reference in 27 MHz ticks
+    pub pcr:           Option<u64>
+}
+
+impl AdaptationField
+{
+    fn parse(data: &[u8]) -> Self
+    {
+        let flags = data.first().copied().unwrap_or(0);
+        // 33-bit base in 90 kHz units, 6 reserved bits and a 9-bit extension
+        let pcr = match data.get(1..7)
+        {
+            | Some(bytes) if flags & 0x10 != 0 =>
+            {
+                let base = ((bytes[0] as u64) << 25) | ((bytes[1] as u64) << 17) | ((bytes[2] as u64) << 9) | ((bytes[3] as u64) << 1) | ((bytes[4] as u64) >> 7);
+                let extension = (((bytes[4] & 0x01) as u64) << 8) | bytes[5] as u64;
+                Some(base * 300 + extension)
+            }
+            | _ => None
+        };
+
+        AdaptationField { discontinuity: flags & 0x80 != 0, random_access: flags & 0x40 != 0, pcr }
+    }
+}
+
+/// Transport packet header (ISO/IEC 13818-1, 2.4.3.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TsPacket
+{
+    pub transport_error:    bool,
+    pub payload_unit_start: bool,
+    pub pid:                u16,
+    /// Transport scrambling control (0: not scrambled)
+    pub scrambling:         u8,
+    pub continuity_counter: u8,
+    pub adaptation:         Option<AdaptationField>,
+    /// Offset of the payload within the packet, None if the packet carries no payload
+    pub payload_start:      Option<usize>
+}
+
+impl TsPacket
+{
+    /// Parse the header of the 188-byte packet `data`, which must start with the sync byte
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < PACKET_SIZE || data[0] != SYNC_BYTE
+        {
+            return Err(tr("Missing transport packet sync byte").to_string());
+        }
+
+        let control = (data[3] >> 4) & 0x03;
+        let mut payload_start = 4;
+        let adaptation = match control
+        {
+            | 2 | 3 =>
+            {
+                let length = data[4] as usize;
+                if 5 + length > PACKET_SIZE
+                {
+                    return Err(tr_fmt("Adaptation field length {} exceeds the packet", &[&length]));
+                }
+                payload_start = 5 + length;
+                Some(AdaptationField::parse(&data[5..5 + length]))
+            }
+            | _ => None
+        };
+        let payload_start = match control
+        {
+            | 1 | 3 if payload_start < PACKET_SIZE => Some(payload_start),
+            | _ => None
+        };
+
+        Ok(TsPacket {
+            transport_error: data[1] & 0x80 != 0,
+            payload_unit_start: data[1] & 0x40 != 0,
+            pid: u16::from_be_bytes([data[1] & 0x1F, data[2]]),
+            scrambling: data[3] >> 6,
+            continuity_counter: data[3] & 0x0F,
+            adaptation,
+            payload_start
+        })
+    }
+
+    /// Check whether the packet carries payload (only those advance the continuity counter)
+    pub fn has_payload(&self) -> bool
+    {
+        self.payload_start.is_some()
+    }
+}
+
+/// Data of a complete PES packet after its header, limited to the packet length if the header gives one
+pub fn pes_payload(pes: &[u8]) -> Option<&[u8]>
+{
+    if pes.len() < 6 || pes[0..3] != [0x00, 0x00, 0x01]
+    {
+        return None;
+    }
+    let end = match u16::from_be_bytes([pes[4], pes[5]]) as usize
+    {
+        | 0 => pes.len(),
+        | length => (6 + length).min(pes.len())
+    };
+    let start = match pes[3]
+    {
+        | 0xBC | 0xBE | 0xBF | 0xF0 | 0xF1 | 0xF2 | 0xF8 | 0xFF => 6,
+        | _ => 9 + *pes.get(8)? as usize
+    };
+    pes.get(start..end)
+}
+
+/// Presentation time stamp of a PES packet header in 90 kHz units
+pub fn pes_pts(payload: &[u8]) -> Option<u64>
+{
+    // Packet start code prefix, stream id, length, then the optional header with PTS_DTS_flags
+    if payload.len() < 14 || payload[0..3] != [0x00, 0x00, 0x01] || payload[7] & 0x80 == 0
+    {
+        return None;
+    }
+    // Padding, private stream 2 and the system streams have no optional header
+    if matches!(payload[3], 0xBC | 0xBE | 0xBF | 0xF0 | 0xF1 | 0xF2 | 0xF8 | 0xFF) == true
+    {
+        return None;
+    }
+    let bytes = &payload[9..14];
+    Some(((bytes[0] as u64 >> 1) & 0x07) << 30 | (bytes[1] as u64) << 22 | (bytes[2] as u64 >> 1) << 15 | (bytes[3] as u64) << 7 | bytes[4] as u64 >> 1)
+}
@@ -0,0 +1,363 @@
+use std::fmt;
+
+use crate::{
+    i18n::{tr, tr_fmt},
+    ogg::page::CRC_TABLE
+};
+
+/// Upper bound for a section (private sections may use the full 12-bit length)
+const MAX_SECTION_SIZE: usize = 3 + 4095;
+
+/// CRC-32 of an MPEG-2 section (polynomial 0x04C11DB7, initial value 0xFFFFFFFF); zero over a section including its CRC
+pub fn section_crc(data: &[u8]) -> u32
+{
+    data.iter().fold(0xFFFF_FFFF, |crc, &byte| (crc << 8) ^ CRC_TABLE[((crc >> 24) as u8 ^ byte) as usize])
+}
+
+/// Describe a PMT stream type (ISO/IEC 13818-1 table 2-34 and common registered values)
+pub fn stream_type_name(stream_type: u8) -> &'static str
+{
+    match stream_type
+    {
+        | 0x01 => "MPEG-1 Video",
+        | 0x02 => "MPEG-2 Video",
+        | 0x03 => "MPEG-1 Audio",
+        | 0x04 => "MPEG-2 Audio",
+        | 0x05 => "Private sections",
+        | 0x06 => "PES private data",
+        | 0x0F => "AAC Audio (ADTS)",
+        | 0x10 => "MPEG-4 Visual",
+        | 0x11 => "AAC Audio (LATM)",
+        | 0x15 => "Metadata in PES (timed ID3)",
+        | 0x1B => "H.264/AVC Video",
+        | 0x24 => "H.265/HEVC Video",
+        | 0x33 => "H.266/VVC Video",
+        | 0x81 => "AC-3 Audio",
+        | 0x86 => "SCTE-35 Splice Information",
+        | 0x87 => "E-AC-3 Audio",
+        // HLS sample encryption (Apple MPEG-2 Stream Encryption Format)
+        | 0xC1 => "AC-3 Audio (SAMPLE-AES)",
+        | 0xC2 => "E-AC-3 Audio (SAMPLE-AES)",
+        | 0xCF => "AAC Audio (ADTS, SAMPLE-AES)",
+        | 0xDB => "H.264/AVC Video (SAMPLE-AES)",
+        | _ => "Unknown stream type"
+    }
+}
+
+/// Descriptor of a PMT program or elementary stream loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum Descriptor
+{
+    /// Format identifier of the registration descriptor, e.g. "AC-3" or "ID3 "
+    Registration(String),
+    /// ISO 639 language codes
+    Language(Vec<String>),
+    Other(u8)
+}
+
+impl Descriptor
+{
+    /// Parse a descriptor loop
+    fn parse_loop(data: &[u8]) -> Vec<Self>
+    {
+        let mut descriptors = Vec::new();
+        let mut position = 0;
+        while position + 2 <= data.len()
+        {
+            let tag = data[position];
+            let end = (position + 2 + data[position + 1] as usize).min(data.len());
+            let body = &data[position + 2..end];
+            descriptors.push(match tag
+            {
+                | 0x05 if body.len() >= 4 => Descriptor::Registration(String::from_utf8_lossy(&body[0..4]).to_string()),
+                // Each entry: three-letter code and an audio type byte
+                | 0x0A => Descriptor::Language(body.chunks_exact(4).map(|entry| String::from_utf8_lossy(&entry[0..3]).to_string()).collect()),
+                | _ => Descriptor::Other(tag)
+            });
+            position = end;
+        }
+        descriptors
+    }
+}
+
+impl fmt::Display for Descriptor
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | Descriptor::Registration(format) => write!(f, "{}", tr_fmt("registration \"{}\"", &[&format])),
+            | Descriptor::Language(languages) => write!(f, "{}", tr_fmt("language {}", &[&languages.join(", ")])),
+            | Descriptor::Other(tag) => write!(f, "{}", tr_fmt("descriptor {}", &[&format!("0x{:02X}", tag)]))
+        }
+    }
+}
+
+/// Common header of a long-form section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionHeader
+{
+    pub table_id:     u8,
+    /// Transport stream ID (PAT) or program number (PMT)
+    pub table_id_ext: u16,
+    pub version:      u8
+}
+
+/// Validate the syntax and CRC of a long-form section, returning its header and the body between header and CRC
+pub fn parse_section(section: &[u8]) -> Result<(SectionHeader, &[u8]), String>
+{
+    if section.len() < 12
+    {
+        return Err(tr_fmt("Section of {} bytes is too short", &[&section.len()]));
+    }
+    if section[1] & 0x80 == 0
+    {
+        return Err(tr_fmt("Table {} lacks the section syntax indicator", &[&format!("0x{:02X}", section[0])]));
+    }
+    let stored = u32::from_be_bytes([section[section.len() - 4], section[section.len() - 3], section[section.len() - 2], section[section.len() - 1]]);
+    if section_crc(section) != 0
+    {
+        return Err(tr_fmt("Table {} section CRC mismatch (stored {})", &[&format!("0x{:02X}", section[0]), &format!("0x{:08X}", stored)]));
+    }
+
+    let header = SectionHeader { table_id: section[0], table_id_ext: u16::from_be_bytes([section[3], section[4]]), version: (section[5] >> 1) & 0x1F };
+    Ok((header, &section[8..section.len() - 4]))
+}
+
+/// Program association table: program number to PMT PID
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramAssociation
+{
+    pub transport_stream_id: u16,
+    pub version:             u8,
+    /// (program number, PMT PID); program 0 points to the network information table
+    pub programs:            Vec<(u16, u16)>
+}
+
+impl ProgramAssociation
+{
+    pub fn parse(section: &[u8]) -> Result<Self, String>
+    {
+        let (header, body) = parse_section(section)?;
+        if header.table_id != 0x00
+        {
+            return Err(tr_fmt("Unexpected table {} on the PAT PID", &[&format!("0x{:02X}", header.table_id)]));
+        }
+
+        let programs = body.chunks_exact(4).map(|entry| (u16::from_be_bytes([entry[0], entry[1]]), u16::from_be_bytes([entry[2] & 0x1F, entry[3]]))).collect();
+        Ok(ProgramAssociation { transport_stream_id: header.table_id_ext, version: header.version, programs })
+    }
+}
+
+/// Elementary stream of a program map table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementaryStream
+{
+    pub stream_type: u8,
+    pub pid:         u16,
+    pub descriptors: Vec<Descriptor>
+}
+
+impl ElementaryStream
+{
+    /// Format identifier of the registration descriptor
+    pub fn registration(&self) -> Option<&str>
+    {
+        self.descriptors.iter().find_map(|descriptor| match descriptor
+        {
+            | Descriptor::Registration(format) => Some(format.as_str()),
+            | _ => None
+        })
+    }
+
+    /// First language of the ISO 639 language descriptor
+    pub fn language(&self) -> Option<&str>
+    {
+        self.descriptors.iter().find_map(|descriptor| match descriptor
+        {
+            | Descriptor::Language(languages) => languages.first().map(String::as_str),
+            | _ => None
+        })
+    }
+
+    /// Whether the stream carries timed ID3 metadata in PES packets
+    pub fn is_timed_id3(&self) -> bool
+    {
+        matches!(self.description(), "Metadata in PES (timed ID3)" | "Metadata (timed ID3)")
+    }
+
+    /// Whether the stream type signals HLS sample encryption (SAMPLE-AES)
+    pub fn is_sample_encrypted(&self) -> bool
+    {
+        matches!(self.stream_type, 0xC1 | 0xC2 | 0xCF | 0xDB)
+    }
+
+    /// Stream type with private data resolved through registration and DVB descriptors
+    pub fn description(&self) -> &'static str
+    {
+        match (self.stream_type, self.registration())
+        {
+            | (0x06, Some("AC-3")) => "AC-3 Audio",
+            | (0x06, Some("EAC3")) => "E-AC-3 Audio",
+            | (0x06, Some("Opus")) => "Opus Audio",
+            | (0x06, Some("ID3 ")) => "Metadata (timed ID3)",
+            | (0x06, _) if self.descriptors.contains(&Descriptor::Other(0x6A)) == true => "AC-3 Audio",
+            | (0x06, _) if self.descriptors.contains(&Descriptor::Other(0x7A)) == true => "E-AC-3 Audio",
+            | (0x06, _) if self.descriptors.contains(&Descriptor::Other(0x56)) == true => "Teletext",
+            | (0x06, _) if self.descriptors.contains(&Descriptor::Other(0x59)) == true => "DVB Subtitles",
+            | (stream_type, _) => stream_type_name(stream_type)
+        }
+    }
+
+    /// ffmpeg codec name, long name and codec type of the stream
+    pub fn codec(&self) -> (Option<&'static str>, &'static str)
+    {
+        match self.description()
+        {
+            | "MPEG-1 Video" => (Some("mpeg1video"), "video"),
+            | "MPEG-2 Video" => (Some("mpeg2video"), "video"),
+            | "MPEG-4 Visual" => (Some("mpeg4"), "video"),
+            | "H.264/AVC Video" | "H.264/AVC Video (SAMPLE-AES)" => (Some("h264"), "video"),
+            | "H.265/HEVC Video" => (Some("hevc"), "video"),
+            | "H.266/VVC Video" => (Some("vvc"), "video"),
+            | "MPEG-1 Audio" => (Some("mp2"), "audio"),
+            | "MPEG-2 Audio" => (Some("mp3"), "audio"),
+            | "AAC Audio (ADTS)" | "AAC Audio (LATM)" | "AAC Audio (ADTS, SAMPLE-AES)" => (Some("aac"), "audio"),
+            | "AC-3 Audio" | "AC-3 Audio (SAMPLE-AES)" => (Some("ac3"), "audio"),
+            | "E-AC-3 Audio" | "E-AC-3 Audio (SAMPLE-AES)" => (Some("eac3"), "audio"),
+            | "Opus Audio" => (Some("opus"), "audio"),
+            | "Metadata in PES (timed ID3)" | "Metadata (timed ID3)" => (Some("timed_id3"), "data"),
+            | "Teletext" => (Some("dvb_teletext"), "subtitle"),
+            | "DVB Subtitles" => (Some("dvb_subtitle"), "subtitle"),
+            | "SCTE-35 Splice Information" => (Some("scte_35"), "data"),
+            | _ => (None, "data")
+        }
+    }
+}
+
+impl fmt::Display for ElementaryStream
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", tr_fmt("PID {}  type {}  {}", &[&format!("0x{:04X}", self.pid), &format!("0x{:02X}", self.stream_type), &self.description()]))?;
+        let descriptors: Vec<String> = self.descriptors.iter().map(Descriptor::to_string).collect();
+        if descriptors.is_empty() == false
+        {
+            write!(f, " ({})", descriptors.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Program map table of one program
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramMap
+{
+    pub program_number: u16,
+    pub version:        u8,
+    pub pcr_pid:        u16,
+    pub descriptors:    Vec<Descriptor>,
+    pub streams:        Vec<ElementaryStream>
+}
+
+impl ProgramMap
+{
+    pub fn parse(section: &[u8]) -> Result<Self, String>
+    {
+        let (header, body) = parse_section(section)?;
+        if header.table_id != 0x02
+        {
+            return Err(tr_fmt("Unexpected table {} on a PMT PID", &[&format!("0x{:02X}", header.table_id)]));
+        }
+        if body.len() < 4
+        {
+            return Err(tr("Program map table too short").to_string());
+        }
+
+        let pcr_pid = u16::from_be_bytes([body[0] & 0x1F, body[1]]);
+        let info_length = (u16::from_be_bytes([body[2] & 0x0F, body[3]]) as usize).min(body.len() - 4);
+        let descriptors = Descriptor::parse_loop(&body[4..4 + info_length]);
+
+        let mut streams = Vec::new();
+        let mut position = 4 + info_length;
+        while position + 5 <= body.len()
+        {
+            let es_info_length = u16::from_be_bytes([body[position + 3] & 0x0F, body[position + 4]]) as usize;
+            let end = (position + 5 + es_info_length).min(body.len());
+            streams.push(ElementaryStream {
+                stream_type: body[position],
+                pid:         u16::from_be_bytes([body[position + 1] & 0x1F, body[position + 2]]),
+                descriptors: Descriptor::parse_loop(&body[position + 5..end])
+            });
+            position = end;
+        }
+
+        Ok(ProgramMap { program_number: header.table_id_ext, version: header.version, pcr_pid, descriptors, streams })
+    }
+}
+
+/// Reassembles the sections carried on one PID from packet payloads
+#[derive(Debug, Clone, Default)]
+pub struct SectionAssembler
+{
+    buffer:  Vec<u8>,
+    started: bool
+}
+
+impl SectionAssembler
+{
+    /// Add a packet payload, returning the sections it completes
+    pub fn push(&mut self, payload: &[u8], payload_unit_start: bool) -> Vec<Vec<u8>>
+    {
+        let mut data = payload;
+        if payload_unit_start == true
+        {
+            // The pointer field gives the bytes finishing the previous section
+            let Some((&pointer, rest)) = data.split_first()
+            else
+            {
+                return Vec::new();
+            };
+            let pointer = (pointer as usize).min(rest.len());
+            if self.started == true
+            {
+                self.buffer.extend_from_slice(&rest[..pointer]);
+            }
+            let mut sections = self.take_sections();
+            self.buffer.clear();
+            self.buffer.extend_from_slice(&rest[pointer..]);
+            self.started = true;
+            sections.extend(self.take_sections());
+            return sections;
+        }
+        if self.started == false
+        {
+            return Vec::new();
+        }
+        data = &data[..data.len().min(MAX_SECTION_SIZE.saturating_sub(self.buffer.len()))];
+        self.buffer.extend_from_slice(data);
+        self.take_sections()
+    }
+
+    /// Remove the complete sections at the start of the buffer
+    fn take_sections(&mut self) -> Vec<Vec<u8>>
+    {
+        let mut sections = Vec::new();
+        // A table ID of 0xFF starts the stuffing that fills the rest of the packet
+        while self.buffer.len() >= 3 && self.buffer[0] != 0xFF
+        {
+            let length = 3 + (u16::from_be_bytes([self.buffer[1] & 0x0F, self.buffer[2]]) as usize);
+            if self.buffer.len() < length
+            {
+                return sections;
+            }
+            sections.push(self.buffer.drain(..length).collect());
+        }
+        if self.buffer.first() == Some(&0xFF)
+        {
+            self.buffer.clear();
+            self.started = false;
+        }
+        sections
+    }
+}
@@ -0,0 +1,187 @@
+// Music identifiers
+//
+// Backs `--music-ids`: the MusicBrainz, AcoustID and MusicIP identifiers a tagger such as Picard
+// stores, gathered from the normalized metadata of all layers whatever frame type or item carries
+// them (ID3v2 TXXX and UFID frames, iTunes freeform items). AcoustID fingerprints are compressed
+// Chromaprint fingerprints; their header is decoded to show the algorithm and the covered duration.
+
+use crate::metadata::{MediaMetadata, decode_base64};
+
+/// Owner identifier of the UFID frame holding the MusicBrainz recording ID
+pub const MUSICBRAINZ_UFID_OWNER: &str = "http://musicbrainz.org";
+
+/// Duration of audio covered by one Chromaprint fingerprint value (a step of 4096 / 3 samples at 11025 Hz)
+const CHROMAPRINT_VALUE_DURATION: f64 = 4096.0 / 3.0 / 11025.0;
+
+/// Kind of identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MusicIdKind
+{
+    Recording,
+    Track,
+    Release,
+    ReleaseGroup,
+    Artist,
+    ReleaseArtist,
+    Work,
+    Disc,
+    OriginalRelease,
+    OriginalArtist,
+    Trm,
+    Puid,
+    AcoustId,
+    AcoustIdFingerprint
+}
+
+/// Field names used by taggers for each kind (TXXX description, iTunes freeform name), compared ignoring case
+const FIELD_NAMES: [(&str, MusicIdKind); 14] = [
+    // iTunes has no UFID equivalent, the recording ID is stored as "Track Id" there
+    ("MusicBrainz Track Id", MusicIdKind::Recording),
+    ("MusicBrainz Release Track Id", MusicIdKind::Track),
+    ("MusicBrainz Album Id", MusicIdKind::Release),
+    ("MusicBrainz Release Group Id", MusicIdKind::ReleaseGroup),
+    ("MusicBrainz Artist Id", MusicIdKind::Artist),
+    ("MusicBrainz Album Artist Id", MusicIdKind::ReleaseArtist),
+    ("MusicBrainz Work Id", MusicIdKind::Work),
+    ("MusicBrainz Disc Id", MusicIdKind::Disc),
+    ("MusicBrainz Original Album Id", MusicIdKind::OriginalRelease),
+    ("MusicBrainz Original Artist Id", MusicIdKind::OriginalArtist),
+    ("MusicBrainz TRM Id", MusicIdKind::Trm),
+    ("MusicIP PUID", MusicIdKind::Puid),
+    ("Acoustid Id", MusicIdKind::AcoustId),
+    ("Acoustid Fingerprint", MusicIdKind::AcoustIdFingerprint)
+];
+
+impl MusicIdKind
+{
+    /// Kind of the field `name` (TXXX description or iTunes freeform name), None for other fields
+    pub fn from_field_name(name: &str) -> Option<Self>
+    {
+        FIELD_NAMES.iter().find(|(field_name, _)| field_name.eq_ignore_ascii_case(name.trim())).map(|&(_, kind)| kind)
+    }
+
+    /// Identifier used in JSON reports
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | MusicIdKind::Recording => "musicbrainz_recording",
+            | MusicIdKind::Track => "musicbrainz_track",
+            | MusicIdKind::Release => "musicbrainz_release",
+            | MusicIdKind::ReleaseGroup => "musicbrainz_release_group",
+            | MusicIdKind::Artist => "musicbrainz_artist",
+            | MusicIdKind::ReleaseArtist => "musicbrainz_release_artist",
+            | MusicIdKind::Work => "musicbrainz_work",
+            | MusicIdKind::Disc => "musicbrainz_disc",
+            | MusicIdKind::OriginalRelease => "musicbrainz_original_release",
+            | MusicIdKind::OriginalArtist => "musicbrainz_original_artist",
+            | MusicIdKind::Trm => "musicbrainz_trm",
+            | MusicIdKind::Puid => "musicip_puid",
+            | MusicIdKind::AcoustId => "acoustid",
+            | MusicIdKind::AcoustIdFingerprint => "acoustid_fingerprint"
+        }
+    }
+
+    /// Name shown in the text output
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            | MusicIdKind::Recording => "MusicBrainz recording ID",
+            | MusicIdKind::Track => "MusicBrainz track ID",
+            | MusicIdKind::Release => "MusicBrainz release ID",
+            | MusicIdKind::ReleaseGroup => "MusicBrainz release group ID",
+            | MusicIdKind::Artist => "MusicBrainz artist ID",
+            | MusicIdKind::ReleaseArtist => "MusicBrainz release artist ID",
+            | MusicIdKind::Work => "MusicBrainz work ID",
+            | MusicIdKind::Disc => "MusicBrainz disc ID",
+            | MusicIdKind::OriginalRelease => "MusicBrainz original release ID",
+            | MusicIdKind::OriginalArtist => "MusicBrainz original artist ID",
+            | MusicIdKind::Trm => "MusicBrainz TRM ID",
+            | MusicIdKind::Puid => "MusicIP PUID",
+            | MusicIdKind::AcoustId => "AcoustID",
+            | MusicIdKind::AcoustIdFingerprint => "AcoustID fingerprint"
+        }
+    }
+}
+
+/// Header of a compressed Chromaprint fingerprint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint
+{
+    /// Chromaprint algorithm (1 is the default of fpcalc)
+    pub algorithm: u8,
+    /// Number of 32-bit fingerprint values
+    pub values:    u32
+}
+
+impl Fingerprint
+{
+    /// Decode the header of a fingerprint as stored by fpcalc and Picard (URL-safe base64 without padding)
+    pub fn parse(text: &str) -> Option<Self>
+    {
+        let data = decode_base64(text.get(..8)?)?;
+        (data.len() >= 4).then(|| Self { algorithm: data[0], values: u32::from_be_bytes([0, data[1], data[2], data[3]]) })
+    }
+
+    /// Duration of audio covered by the fingerprint in seconds
+    pub fn duration(&self) -> f64
+    {
+        self.values as f64 * CHROMAPRINT_VALUE_DURATION
+    }
+}
+
+/// Identifier found in the metadata of a file
+#[derive(Debug, Clone)]
+pub struct MusicId
+{
+    pub kind:        MusicIdKind,
+    /// Metadata field holding the identifier, e.g. "UFID:http://musicbrainz.org" or "com.apple.iTunes:MusicBrainz Album Id"
+    pub source:      String,
+    pub value:       String,
+    /// Decoded header of an AcoustID fingerprint
+    pub fingerprint: Option<Fingerprint>
+}
+
+impl MusicId
+{
+    /// Identifier held by the custom metadata field `key`, None for other fields
+    pub fn from_custom(key: &str, value: &str) -> Option<Self>
+    {
+        let kind = match key.strip_prefix("UFID:")
+        {
+            | Some(owner) => (owner == MUSICBRAINZ_UFID_OWNER).then_some(MusicIdKind::Recording)?,
+            // TXXX frames are keyed "TXXX:<description>", iTunes freeform items "<mean>:<name>"
+            | None => MusicIdKind::from_field_name(key.rsplit_once(':').map_or(key, |(_, name)| name))?
+        };
+        let fingerprint = match kind
+        {
+            | MusicIdKind::AcoustIdFingerprint => Fingerprint::parse(value),
+            | _ => None
+        };
+        Some(Self { kind, source: key.to_string(), value: value.to_string(), fingerprint })
+    }
+
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "kind": self.kind.id(),
+            "source": self.source,
+            "value": self.value,
+            "fingerprint": self.fingerprint.map(|fingerprint| serde_json::json!({
+                "algorithm": fingerprint.algorithm,
+                "values": fingerprint.values,
+                "duration": fingerprint.duration()
+            }))
+        })
+    }
+}
+
+/// Gather the identifiers of the custom fields of `metadata`, ordered by kind
+pub fn collect(metadata: &MediaMetadata) -> Vec<MusicId>
+{
+    let mut ids: Vec<MusicId> = metadata.custom.iter().filter_map(|(key, value)| MusicId::from_custom(key, value)).collect();
+    ids.sort_by_key(|id| id.kind);
+    ids
+}
@@ -0,0 +1,164 @@
+// Embedded binary objects
+//
+// Backs `extract objects`: every binary payload a tag carries, pictures (APIC frames, iTunes `covr`
+// items) as well as general encapsulated objects (GEOB), private frames (PRIV) and unique file
+// identifiers (UFID), collected layer by layer and written with file names derived from their
+// metadata, next to a JSON manifest describing each written file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    artwork::{Artwork, picture_extension},
+    chapters::file_name_from_text,
+    hashes::{Sha256, to_hex}
+};
+
+/// File name of the manifest written next to the objects
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Kind of embedded object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind
+{
+    Picture,
+    EncapsulatedObject,
+    Private,
+    UniqueFileId
+}
+
+impl ObjectKind
+{
+    /// Identifier used in the manifest
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | ObjectKind::Picture => "picture",
+            | ObjectKind::EncapsulatedObject => "encapsulated_object",
+            | ObjectKind::Private => "private",
+            | ObjectKind::UniqueFileId => "unique_file_id"
+        }
+    }
+}
+
+/// Binary object embedded in a tag
+#[derive(Debug, Clone)]
+pub struct EmbeddedObject
+{
+    pub kind:      ObjectKind,
+    /// Where the object was found (e.g. "GEOB", "CHAP ch1/APIC", "moov/udta/meta/ilst/covr")
+    pub location:  String,
+    /// MIME type of pictures and encapsulated objects, empty for PRIV and UFID frames
+    pub mime_type: String,
+    /// Picture description, GEOB file name (or description if it has none), or PRIV/UFID owner identifier
+    pub name:      String,
+    pub data:      Vec<u8>
+}
+
+impl EmbeddedObject
+{
+    /// Object for an embedded picture
+    pub fn from_artwork(picture: &Artwork) -> Self
+    {
+        Self {
+            kind:      ObjectKind::Picture,
+            location:  picture.location.clone(),
+            mime_type: picture.mime_type.clone(),
+            name:      picture.description.clone(),
+            data:      picture.data.clone()
+        }
+    }
+
+    /// File extension: sniffed for pictures, taken from the stored file name or MIME type for encapsulated objects, "bin" otherwise
+    pub fn extension(&self) -> String
+    {
+        match self.kind
+        {
+            | ObjectKind::Picture => picture_extension(&self.mime_type, &self.data).to_string(),
+            | ObjectKind::EncapsulatedObject =>
+            {
+                let from_file_name = Path::new(&self.name)
+                    .extension()
+                    .map(|extension| file_name_from_text(&extension.to_string_lossy()))
+                    .filter(|extension| extension.is_empty() == false);
+                from_file_name.unwrap_or_else(|| extension_for_mime_type(&self.mime_type).to_string())
+            }
+            | ObjectKind::Private | ObjectKind::UniqueFileId => "bin".to_string()
+        }
+    }
+
+    /// File name as `<nn>_<location>_<name>.<extension>`, `number` counting from 1
+    fn file_name(&self, number: usize) -> String
+    {
+        // Encapsulated objects keep their own extension instead of doubling it
+        let name = match self.kind
+        {
+            | ObjectKind::EncapsulatedObject => Path::new(&self.name).file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().to_string()),
+            | _ => self.name.clone()
+        };
+        let name = file_name_from_text(&name);
+        match name.is_empty()
+        {
+            | true => format!("{:02}_{}.{}", number, file_name_from_text(&self.location), self.extension()),
+            | false => format!("{:02}_{}_{}.{}", number, file_name_from_text(&self.location), name, self.extension())
+        }
+    }
+}
+
+/// File extension of common GEOB MIME types, "bin" for others
+fn extension_for_mime_type(mime_type: &str) -> &'static str
+{
+    match mime_type.to_ascii_lowercase().as_str()
+    {
+        | "text/plain" => "txt",
+        | "text/xml" | "application/xml" => "xml",
+        | "application/json" => "json",
+        | "text/html" => "html",
+        | "application/pdf" => "pdf",
+        | "application/zip" => "zip",
+        | "image/jpeg" => "jpg",
+        | "image/png" => "png",
+        | _ => "bin"
+    }
+}
+
+/// Write every object to `directory` and a manifest describing them, returning the created paths (objects first, manifest last)
+pub fn extract(objects: &[EmbeddedObject], directory: &Path, source: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>>
+{
+    fs::create_dir_all(directory)?;
+
+    let mut paths = Vec::new();
+    let mut entries = Vec::new();
+    for (index, object) in objects.iter().enumerate()
+    {
+        let file_name = object.file_name(index + 1);
+        let path = directory.join(&file_name);
+        fs::write(&path, &object.data)?;
+        paths.push(path);
+
+        let mut sha256 = Sha256::default();
+        sha256.update(&object.data);
+        entries.push(serde_json::json!({
+            "file": file_name,
+            "kind": object.kind.id(),
+            "location": object.location,
+            "mime_type": (object.mime_type.is_empty() == false).then_some(&object.mime_type),
+            "name": object.name,
+            "size": object.data.len(),
+            "sha256": to_hex(&sha256.finalize())
+        }));
+    }
+
+    let manifest = serde_json::json!({
+        "source": source.display().to_string(),
+        "objects": entries
+    });
+    let path = directory.join(MANIFEST_FILE_NAME);
+    fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+    paths.push(path);
+
+    Ok(paths)
+}
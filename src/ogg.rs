@@ -0,0 +1,30 @@
+// Ogg container dissection
+//
+// This module provides support for Ogg containers (RFC 3533): page header parsing
+// with checksum verification, reassembly of the header packets of each logical
+// bitstream, and decoding of Vorbis, Opus and FLAC-in-Ogg identification headers
+// together with their Vorbis comment blocks.
+
+// Core types and dissector
+pub mod dissector;
+pub mod page;
+pub mod vorbis_comment;
+
+// Codec header implementations
+pub mod codecs
+{
+    pub mod flac;
+    pub mod opus;
+    pub mod vorbis;
+}
+
+// Re-export commonly used types for convenience
+pub use dissector::OggDissector;
+
+use crate::dissector_registry::DissectorRegistry;
+
+/// Register the Ogg dissector with `registry`
+pub fn register(registry: &mut DissectorRegistry)
+{
+    registry.register_dissector::<OggDissector>(1100);
+}
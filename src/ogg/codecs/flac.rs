@@ -0,0 +1,103 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// FLAC STREAMINFO metadata block
+#[derive(Debug, Clone)]
+pub struct FlacStreamInfo
+{
+    pub min_block_size:  u16,
+    pub max_block_size:  u16,
+    pub min_frame_size:  u32,
+    pub max_frame_size:  u32,
+    pub sample_rate:     u32,
+    pub channels:        u8,
+    pub bits_per_sample: u8,
+    /// Total number of samples per channel (0 if unknown)
+    pub total_samples:   u64,
+    pub md5:             [u8; 16]
+}
+
+impl FlacStreamInfo
+{
+    /// Parse the 34-byte STREAMINFO block body
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        if data.len() < 34
+        {
+            return Err(tr("STREAMINFO block too short").to_string());
+        }
+
+        let packed = u64::from_be_bytes([data[10], data[11], data[12], data[13], data[14], data[15], data[16], data[17]]);
+        let mut md5 = [0u8; 16];
+        md5.copy_from_slice(&data[18..34]);
+
+        Ok(Self {
+            min_block_size: u16::from_be_bytes([data[0], data[1]]),
+            max_block_size: u16::from_be_bytes([data[2], data[3]]),
+            min_frame_size: u32::from_be_bytes([0, data[4], data[5], data[6]]),
+            max_frame_size: u32::from_be_bytes([0, data[7], data[8], data[9]]),
+            // 20 bits sample rate, 3 bits channels - 1, 5 bits bits per sample - 1, 36 bits total samples
+            sample_rate: (packed >> 44) as u32,
+            channels: ((packed >> 41) & 0x07) as u8 + 1,
+            bits_per_sample: ((packed >> 36) & 0x1F) as u8 + 1,
+            total_samples: packed & 0x0F_FFFF_FFFF,
+            md5
+        })
+    }
+}
+
+impl fmt::Display for FlacStreamInfo
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Block Size: {} - {} samples", &[&self.min_block_size, &self.max_block_size]))?;
+        writeln!(f, "{}", tr_fmt("Frame Size: {} - {} bytes", &[&self.min_frame_size, &self.max_frame_size]))?;
+        writeln!(f, "{}", tr_fmt("Sample Rate: {} Hz", &[&self.sample_rate]))?;
+        writeln!(f, "{}", tr_fmt("Channels: {}", &[&self.channels]))?;
+        writeln!(f, "{}", tr_fmt("Bits per Sample: {}", &[&self.bits_per_sample]))?;
+        writeln!(f, "{}", tr_fmt("Total Samples: {}", &[&self.total_samples]))?;
+        writeln!(f, "{}", tr_fmt("MD5: {}", &[&(self.md5.iter().map(|byte| format!("{:02x}", byte)).collect::<String>())]))
+    }
+}
+
+/// First packet of a FLAC-in-Ogg stream ("\x7FFLAC" mapping header with STREAMINFO)
+#[derive(Debug, Clone)]
+pub struct OggFlacHeader
+{
+    pub mapping_major: u8,
+    pub mapping_minor: u8,
+    /// Number of header packets following this one (0 = unknown)
+    pub header_count:  u16,
+    pub stream_info:   FlacStreamInfo
+}
+
+impl OggFlacHeader
+{
+    /// Parse the mapping header packet including its signature
+    pub fn parse(packet: &[u8]) -> Result<Self, String>
+    {
+        if packet.len() < 13 + 4 + 34 || &packet[0..5] != b"\x7FFLAC" || &packet[9..13] != b"fLaC"
+        {
+            return Err(tr("FLAC mapping header too short").to_string());
+        }
+
+        Ok(Self {
+            mapping_major: packet[5],
+            mapping_minor: packet[6],
+            header_count:  u16::from_be_bytes([packet[7], packet[8]]),
+            // Metadata block header (4 bytes) precedes the STREAMINFO body
+            stream_info:   FlacStreamInfo::parse(&packet[17..])?
+        })
+    }
+}
+
+impl fmt::Display for OggFlacHeader
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Mapping Version: {}.{}", &[&self.mapping_major, &self.mapping_minor]))?;
+        writeln!(f, "{}", tr_fmt("Header Packets: {}", &[&self.header_count]))?;
+        write!(f, "{}", self.stream_info)
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Opus identification header (OpusHead, RFC 7845)
+#[derive(Debug, Clone)]
+pub struct OpusHead
+{
+    pub version:           u8,
+    pub channels:          u8,
+    /// Samples (at 48 kHz) to discard from the decoder output
+    pub pre_skip:          u16,
+    /// Sample rate of the original input (informational only, Opus always decodes at 48 kHz)
+    pub input_sample_rate: u32,
+    /// Output gain in Q7.8 dB
+    pub output_gain:       i16,
+    pub mapping_family:    u8
+}
+
+impl OpusHead
+{
+    /// Parse the OpusHead packet including its signature
+    pub fn parse(packet: &[u8]) -> Result<Self, String>
+    {
+        if packet.len() < 19 || &packet[0..8] != b"OpusHead"
+        {
+            return Err(tr("OpusHead packet too short").to_string());
+        }
+
+        Ok(Self {
+            version:           packet[8],
+            channels:          packet[9],
+            pre_skip:          u16::from_le_bytes([packet[10], packet[11]]),
+            input_sample_rate: u32::from_le_bytes([packet[12], packet[13], packet[14], packet[15]]),
+            output_gain:       i16::from_le_bytes([packet[16], packet[17]]),
+            mapping_family:    packet[18]
+        })
+    }
+
+    /// Get a description of the channel mapping family
+    fn mapping_family_description(&self) -> &'static str
+    {
+        match self.mapping_family
+        {
+            | 0 => "mono/stereo",
+            | 1 => "Vorbis channel order",
+            | 2 => "ambisonics",
+            | 3 => "ambisonics with projection",
+            | 255 => "unidentified",
+            | _ => "reserved"
+        }
+    }
+}
+
+impl fmt::Display for OpusHead
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Opus Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Channels: {}", &[&self.channels]))?;
+        writeln!(f, "{}", tr_fmt("Pre-skip: {} samples", &[&self.pre_skip]))?;
+        writeln!(f, "{}", tr_fmt("Input Sample Rate: {} Hz", &[&self.input_sample_rate]))?;
+        writeln!(f, "{}", tr_fmt("Output Gain: {} dB", &[&format!("{:.2}", self.output_gain as f64 / 256.0)]))?;
+        writeln!(f, "{}", tr_fmt("Channel Mapping Family: {} ({})", &[&self.mapping_family, &self.mapping_family_description()]))
+    }
+}
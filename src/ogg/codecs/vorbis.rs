@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Vorbis identification header (packet type 1)
+#[derive(Debug, Clone)]
+pub struct VorbisIdentification
+{
+    pub version:         u32,
+    pub channels:        u8,
+    pub sample_rate:     u32,
+    pub bitrate_maximum: i32,
+    pub bitrate_nominal: i32,
+    pub bitrate_minimum: i32,
+    pub blocksize_short: u16,
+    pub blocksize_long:  u16
+}
+
+impl VorbisIdentification
+{
+    /// Parse the identification packet including its "\x01vorbis" prefix
+    pub fn parse(packet: &[u8]) -> Result<Self, String>
+    {
+        if packet.len() < 30 || &packet[0..7] != b"\x01vorbis"
+        {
+            return Err(tr("Vorbis identification header too short").to_string());
+        }
+
+        let read_i32 = |pos: usize| i32::from_le_bytes([packet[pos], packet[pos + 1], packet[pos + 2], packet[pos + 3]]);
+
+        Ok(Self {
+            version:         read_i32(7) as u32,
+            channels:        packet[11],
+            sample_rate:     read_i32(12) as u32,
+            bitrate_maximum: read_i32(16),
+            bitrate_nominal: read_i32(20),
+            bitrate_minimum: read_i32(24),
+            blocksize_short: 1 << (packet[28] & 0x0F),
+            blocksize_long:  1 << (packet[28] >> 4)
+        })
+    }
+}
+
+impl fmt::Display for VorbisIdentification
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        // Zero or negative bit rates are unset
+        let bitrate = |value: i32| {
+            if value > 0
+            {
+                tr_fmt("{} bps", &[&value])
+            }
+            else
+            {
+                "-".to_string()
+            }
+        };
+
+        writeln!(f, "{}", tr_fmt("Vorbis Version: {}", &[&self.version]))?;
+        writeln!(f, "{}", tr_fmt("Channels: {}", &[&self.channels]))?;
+        writeln!(f, "{}", tr_fmt("Sample Rate: {} Hz", &[&self.sample_rate]))?;
+        writeln!(
+            f,
+            "{}",
+            tr_fmt("Bitrate (max/nominal/min): {} / {} / {}", &[&bitrate(self.bitrate_maximum), &bitrate(self.bitrate_nominal), &bitrate(self.bitrate_minimum)])
+        )?;
+        writeln!(f, "{}", tr_fmt("Block Sizes: {} / {}", &[&self.blocksize_short, &self.blocksize_long]))
+    }
+}
@@ -0,0 +1,572 @@
+use std::io::Write;
+
+use crate::{
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::{ProbeFormat, ProbeStream},
+    i18n::{tr, tr_fmt},
+    media_dissector::{Confidence, MediaDissector},
+    media_reader::MediaReader,
+    media_source::MediaSource,
+    ogg::{
+        codecs::{flac::OggFlacHeader, opus::OpusHead, vorbis::VorbisIdentification},
+        page::{OggPage, PAGE_HEADER_SIZE, page_checksum},
+        vorbis_comment::VorbisComment
+    },
+    options::DissectOptions
+};
+
+/// Upper bound for a reassembled header packet (comment packets may carry embedded pictures)
+const MAX_HEADER_PACKET_SIZE: usize = 16 * 1024 * 1024;
+
+/// Codec of a logical bitstream, identified by the signature of its first packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OggCodec
+{
+    Vorbis,
+    Opus,
+    Flac,
+    Speex,
+    Theora,
+    Unknown
+}
+
+impl OggCodec
+{
+    /// Identify the codec from the first packet of a logical bitstream
+    fn identify(packet: &[u8]) -> Self
+    {
+        if packet.starts_with(b"\x01vorbis") == true
+        {
+            OggCodec::Vorbis
+        }
+        else if packet.starts_with(b"OpusHead") == true
+        {
+            OggCodec::Opus
+        }
+        else if packet.starts_with(b"\x7FFLAC") == true
+        {
+            OggCodec::Flac
+        }
+        else if packet.starts_with(b"Speex   ") == true
+        {
+            OggCodec::Speex
+        }
+        else if packet.starts_with(b"\x80theora") == true
+        {
+            OggCodec::Theora
+        }
+        else
+        {
+            OggCodec::Unknown
+        }
+    }
+
+    /// Stable codec name used in structured output
+    fn key(self) -> &'static str
+    {
+        match self
+        {
+            | OggCodec::Vorbis => "vorbis",
+            | OggCodec::Opus => "opus",
+            | OggCodec::Flac => "flac",
+            | OggCodec::Speex => "speex",
+            | OggCodec::Theora => "theora",
+            | OggCodec::Unknown => "unknown"
+        }
+    }
+
+    /// Number of header packets at the start of the stream (FLAC announces its count in the first packet)
+    fn header_packets(self, first_packet: &[u8]) -> usize
+    {
+        match self
+        {
+            | OggCodec::Vorbis | OggCodec::Theora => 3,
+            | OggCodec::Opus | OggCodec::Speex => 2,
+            | OggCodec::Flac => match first_packet.get(7..9)
+            {
+                | Some(count) if count != [0, 0] => 1 + u16::from_be_bytes([count[0], count[1]]) as usize,
+                | _ => 2
+            },
+            | OggCodec::Unknown => 1
+        }
+    }
+}
+
+/// Parsed codec identification header
+#[derive(Debug, Clone)]
+enum CodecHeader
+{
+    Vorbis(VorbisIdentification),
+    Opus(OpusHead),
+    Flac(OggFlacHeader)
+}
+
+/// One logical bitstream (identified by its serial number)
+#[derive(Debug, Clone)]
+struct LogicalStream
+{
+    serial:            u32,
+    first_page_offset: u64,
+    pages:             usize,
+    last_sequence:     u32,
+    /// Granule position of the last page that completed a packet
+    last_granule:      Option<u64>,
+    has_eos:           bool,
+    codec:             OggCodec,
+    /// Header packets still to be collected (unknown until the first packet is complete)
+    headers_needed:    Option<usize>,
+    header_packets:    Vec<Vec<u8>>,
+    /// Packet data carried over to the next page
+    partial:           Vec<u8>,
+    header:            Option<CodecHeader>,
+    comment:           Option<VorbisComment>
+}
+
+impl LogicalStream
+{
+    fn new(page: &OggPage) -> Self
+    {
+        Self {
+            serial:            page.serial,
+            first_page_offset: page.offset,
+            pages:             0,
+            last_sequence:     page.sequence,
+            last_granule:      None,
+            has_eos:           false,
+            codec:             OggCodec::Unknown,
+            headers_needed:    None,
+            header_packets:    Vec::new(),
+            partial:           Vec::new(),
+            header:            None,
+            comment:           None
+        }
+    }
+
+    /// Check whether header packets are still being collected
+    fn collecting(&self) -> bool
+    {
+        self.headers_needed.is_none_or(|needed| self.header_packets.len() < needed)
+    }
+
+    /// Add the segments of a page, collecting completed header packets
+    fn add_payload(&mut self, page: &OggPage, payload: &[u8])
+    {
+        let mut position = 0;
+        for &lacing in &page.lacing
+        {
+            let segment = &payload[position..position + lacing as usize];
+            position += lacing as usize;
+
+            if self.collecting() == false
+            {
+                continue;
+            }
+
+            if self.partial.len() + segment.len() <= MAX_HEADER_PACKET_SIZE
+            {
+                self.partial.extend_from_slice(segment);
+            }
+
+            // A lacing value below 255 terminates the packet
+            if lacing < 255
+            {
+                let packet = std::mem::take(&mut self.partial);
+                if self.headers_needed.is_none()
+                {
+                    self.codec = OggCodec::identify(&packet);
+                    self.headers_needed = Some(self.codec.header_packets(&packet));
+                }
+                self.header_packets.push(packet);
+            }
+        }
+    }
+
+    /// Parse the collected header packets into codec header and comments
+    fn parse_headers(&mut self, diagnostics: &mut Diagnostics)
+    {
+        let Some(first) = self.header_packets.first()
+        else
+        {
+            return;
+        };
+
+        let header = match self.codec
+        {
+            | OggCodec::Vorbis => VorbisIdentification::parse(first).map(CodecHeader::Vorbis),
+            | OggCodec::Opus => OpusHead::parse(first).map(CodecHeader::Opus),
+            | OggCodec::Flac => OggFlacHeader::parse(first).map(CodecHeader::Flac),
+            | _ => return
+        };
+        match header
+        {
+            | Ok(header) => self.header = Some(header),
+            | Err(e) => diagnostics.warning(Some(self.first_page_offset), tr_fmt("Ogg stream {}: {}", &[&format!("0x{:08X}", self.serial), &e]))
+        }
+
+        // Vorbis and Opus carry the comment header as second packet, FLAC as a VORBIS_COMMENT metadata block anywhere in the header packets
+        let comment = match self.codec
+        {
+            | OggCodec::Vorbis => self.header_packets.get(1).filter(|packet| packet.starts_with(b"\x03vorbis") == true).map(|packet| &packet[7..]),
+            | OggCodec::Opus => self.header_packets.get(1).filter(|packet| packet.starts_with(b"OpusTags") == true).map(|packet| &packet[8..]),
+            | _ => self.header_packets.iter().skip(1).find(|packet| packet.len() >= 4 && packet[0] & 0x7F == 4).map(|packet| &packet[4..])
+        };
+        if let Some(comment) = comment
+        {
+            match VorbisComment::parse(comment)
+            {
+                | Ok(comment) => self.comment = Some(comment),
+                | Err(e) => diagnostics.warning(Some(self.first_page_offset), tr_fmt("Ogg stream {}: {}", &[&format!("0x{:08X}", self.serial), &e]))
+            }
+        }
+    }
+
+    /// Sample rate the granule position counts in
+    fn sample_rate(&self) -> Option<u32>
+    {
+        match self.header
+        {
+            | Some(CodecHeader::Vorbis(ref identification)) => Some(identification.sample_rate),
+            // Opus granule positions always count 48 kHz samples
+            | Some(CodecHeader::Opus(_)) => Some(48000),
+            | Some(CodecHeader::Flac(ref flac)) => Some(flac.stream_info.sample_rate),
+            | None => None
+        }
+    }
+
+    fn channels(&self) -> Option<u16>
+    {
+        match self.header
+        {
+            | Some(CodecHeader::Vorbis(ref identification)) => Some(identification.channels as u16),
+            | Some(CodecHeader::Opus(ref head)) => Some(head.channels as u16),
+            | Some(CodecHeader::Flac(ref flac)) => Some(flac.stream_info.channels as u16),
+            | None => None
+        }
+    }
+
+    /// Duration in seconds derived from the last granule position
+    fn duration(&self) -> Option<f64>
+    {
+        let granule = self.last_granule?;
+        let sample_rate = self.sample_rate().filter(|&rate| rate > 0)?;
+        let pre_skip = match self.header
+        {
+            | Some(CodecHeader::Opus(ref head)) => head.pre_skip as u64,
+            | _ => 0
+        };
+
+        Some(granule.saturating_sub(pre_skip) as f64 / sample_rate as f64)
+    }
+
+    /// Structured representation for JSON reports
+    fn to_json(&self) -> serde_json::Value
+    {
+        let details = match self.header
+        {
+            | Some(CodecHeader::Vorbis(ref identification)) => identification.to_string(),
+            | Some(CodecHeader::Opus(ref head)) => head.to_string(),
+            | Some(CodecHeader::Flac(ref flac)) => flac.to_string(),
+            | None => String::new()
+        };
+
+        serde_json::json!({
+            "serial": self.serial,
+            "codec": self.codec.key(),
+            "first_page_offset": self.first_page_offset,
+            "pages": self.pages,
+            "last_granule": self.last_granule,
+            "duration": self.duration(),
+            "details": details.lines().collect::<Vec<_>>(),
+            "comments": self.comment.as_ref().map(|comment| serde_json::json!({
+                "vendor": comment.vendor,
+                "entries": comment.comments.iter().map(|(name, value)| serde_json::json!({ "name": name, "value": value })).collect::<Vec<_>>()
+            }))
+        })
+    }
+}
+
+/// Result of walking the pages of an Ogg region
+struct OggContainer
+{
+    pages:      Vec<OggPage>,
+    streams:    Vec<LogicalStream>,
+    /// Offset just past the last complete page
+    end_offset: u64
+}
+
+/// Ogg container dissector - unit struct
+#[derive(Default)]
+pub struct OggDissector;
+
+impl OggDissector
+{
+    /// Walk all pages from `start_offset`, verifying checksums and sequence numbers and collecting stream headers
+    fn read_pages(file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<OggContainer, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+        let mut reader = MediaReader::new(file, start_offset)?;
+        let mut pages = Vec::new();
+        let mut streams: Vec<LogicalStream> = Vec::new();
+        let mut offset = start_offset;
+
+        while offset + PAGE_HEADER_SIZE as u64 <= file_size
+        {
+            reader.seek_to(offset)?;
+            let mut header = [0u8; PAGE_HEADER_SIZE];
+            reader.read_exact(&mut header)?;
+
+            // Anything that is not a page ends the Ogg region
+            let Some(mut page) = OggPage::parse_header(&header, offset)
+            else
+            {
+                diagnostics.info(Some(offset), tr_fmt("Ogg pages end, {} bytes of other data follow", &[&(file_size - offset)]));
+                break;
+            };
+
+            let segment_count = header[26] as u64;
+            if offset + PAGE_HEADER_SIZE as u64 + segment_count > file_size
+            {
+                diagnostics.warning(Some(offset), tr("Ogg page header truncated at end of file"));
+                break;
+            }
+            page.lacing.resize(segment_count as usize, 0);
+            reader.read_exact(&mut page.lacing)?;
+
+            if offset + page.size() > file_size
+            {
+                diagnostics.warning(Some(offset), tr_fmt("Ogg page truncated: {} bytes declared, {} available", &[&page.size(), &(file_size - offset)]));
+                break;
+            }
+            let mut payload = vec![0u8; page.payload_size() as usize];
+            reader.read_exact(&mut payload)?;
+
+            let mut raw = Vec::with_capacity(page.size() as usize);
+            raw.extend_from_slice(&header);
+            raw.extend_from_slice(&page.lacing);
+            raw.extend_from_slice(&payload);
+            let checksum = page_checksum(&raw);
+            if checksum != page.checksum
+            {
+                diagnostics.warning(
+                    Some(offset),
+                    tr_fmt("Ogg page checksum mismatch: stored {}, computed {}", &[&format!("0x{:08X}", page.checksum), &format!("0x{:08X}", checksum)])
+                );
+            }
+            if page.version != 0
+            {
+                diagnostics.warning(Some(offset), tr_fmt("Unsupported Ogg page version {}", &[&page.version]));
+            }
+
+            let index = match streams.iter().position(|stream| stream.serial == page.serial)
+            {
+                | Some(index) =>
+                {
+                    let stream = &streams[index];
+                    if page.sequence != stream.last_sequence.wrapping_add(1)
+                    {
+                        diagnostics.warning(
+                            Some(offset),
+                            tr_fmt("Ogg stream {}: page sequence {} follows {} (pages lost or reordered)", &[
+                                &format!("0x{:08X}", page.serial),
+                                &page.sequence,
+                                &stream.last_sequence
+                            ])
+                        );
+                    }
+                    if stream.has_eos == true
+                    {
+                        diagnostics.warning(Some(offset), tr_fmt("Ogg stream {}: page after end of stream", &[&format!("0x{:08X}", page.serial)]));
+                    }
+                    index
+                }
+                | None =>
+                {
+                    if page.is_bos() == false
+                    {
+                        diagnostics
+                            .warning(Some(offset), tr_fmt("Ogg stream {}: first page lacks the beginning-of-stream flag", &[&format!("0x{:08X}", page.serial)]));
+                    }
+                    streams.push(LogicalStream::new(&page));
+                    streams.len() - 1
+                }
+            };
+
+            let stream = &mut streams[index];
+            stream.pages += 1;
+            stream.last_sequence = page.sequence;
+            stream.has_eos |= page.is_eos();
+            if page.granule_position != u64::MAX
+            {
+                stream.last_granule = Some(page.granule_position);
+            }
+            stream.add_payload(&page, &payload);
+
+            offset += page.size();
+            pages.push(page);
+        }
+
+        for stream in &mut streams
+        {
+            stream.parse_headers(diagnostics);
+            if stream.has_eos == false
+            {
+                diagnostics.info(
+                    Some(stream.first_page_offset),
+                    tr_fmt("Ogg stream {} has no end-of-stream page (truncated or live capture)", &[&format!("0x{:08X}", stream.serial)])
+                );
+            }
+        }
+
+        Ok(OggContainer { pages, streams, end_offset: offset })
+    }
+}
+
+impl MediaDissector for OggDissector
+{
+    fn media_type(&self) -> &'static str
+    {
+        "Ogg"
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "Ogg Container Dissector"
+    }
+
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let container = Self::read_pages(file, start_offset, diagnostics)?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("Ogg Container:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Pages: {}", &[&container.pages.len()]))?;
+            writeln!(output, "  {}", tr_fmt("Logical streams: {}", &[&container.streams.len()]))?;
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&(container.end_offset - start_offset)]))?;
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "\n{}", tr("Logical Streams:").bright_cyan().bold())?;
+            for stream in &container.streams
+            {
+                writeln!(output, "\n  {}", tr_fmt("Stream 0x{}: {}", &[&format!("{:08X}", stream.serial), &stream.codec.key()]).cyan())?;
+                writeln!(output, "    {}", tr_fmt("First page at offset {}", &[&format!("0x{:08X}", stream.first_page_offset)]))?;
+                writeln!(output, "    {}", tr_fmt("Pages: {}", &[&stream.pages]))?;
+                if let Some(granule) = stream.last_granule
+                {
+                    writeln!(output, "    {}", tr_fmt("Last granule position: {}", &[&granule]))?;
+                }
+                if let Some(duration) = stream.duration()
+                {
+                    writeln!(output, "    {}", tr_fmt("Duration: {} s", &[&format!("{:.3}", duration)]))?;
+                }
+
+                let details = match stream.header
+                {
+                    | Some(CodecHeader::Vorbis(ref identification)) => identification.to_string(),
+                    | Some(CodecHeader::Opus(ref head)) => head.to_string(),
+                    | Some(CodecHeader::Flac(ref flac)) => flac.to_string(),
+                    | None => String::new()
+                };
+                for line in details.lines()
+                {
+                    writeln!(output, "    {}", line)?;
+                }
+
+                if let Some(ref comment) = stream.comment
+                {
+                    for line in comment.to_string().lines()
+                    {
+                        writeln!(output, "    {}", line)?;
+                    }
+                }
+            }
+
+            if options.show_verbose == true
+            {
+                writeln!(output, "\n{}", tr("Pages:").bright_cyan().bold())?;
+                for page in &container.pages
+                {
+                    writeln!(output, "  {}", page)?;
+                }
+            }
+        }
+
+        Ok(container.end_offset)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let container = Self::read_pages(file, start_offset, diagnostics)?;
+
+        Ok((
+            serde_json::json!({
+                "pages": container.pages.len(),
+                "streams": container.streams.iter().map(LogicalStream::to_json).collect::<Vec<_>>()
+            }),
+            container.end_offset
+        ))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        let container = Self::read_pages(file, start_offset, diagnostics)?;
+
+        let streams: Vec<ProbeStream> = container
+            .streams
+            .iter()
+            .map(|stream| {
+                let (codec_name, codec_long_name, codec_type) = match stream.codec
+                {
+                    | OggCodec::Vorbis => (Some("vorbis"), Some("Vorbis"), "audio"),
+                    | OggCodec::Opus => (Some("opus"), Some("Opus (Opus Interactive Audio Codec)"), "audio"),
+                    | OggCodec::Flac => (Some("flac"), Some("FLAC (Free Lossless Audio Codec)"), "audio"),
+                    | OggCodec::Speex => (Some("speex"), Some("Speex"), "audio"),
+                    | OggCodec::Theora => (Some("theora"), Some("Theora"), "video"),
+                    | OggCodec::Unknown => (None, None, "data")
+                };
+                ProbeStream {
+                    codec_name,
+                    codec_long_name,
+                    codec_type,
+                    sample_rate: stream.sample_rate(),
+                    channels: stream.channels(),
+                    duration: stream.duration(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let duration = streams.iter().filter_map(|stream| stream.duration).reduce(f64::max);
+
+        Ok((Some(ProbeFormat { format_name: "ogg", format_long_name: "Ogg", duration, streams }), container.end_offset))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        // Findings were already reported by the dissection pass
+        let container = Self::read_pages(file, start_offset, &mut Diagnostics::new())?;
+        for page in &container.pages
+        {
+            coverage.add(page.offset, page.offset + page.size());
+        }
+
+        Ok(container.end_offset)
+    }
+
+    fn sniff(&self, header: &[u8], _remaining: u64) -> Confidence
+    {
+        // Capture pattern followed by stream structure version 0
+        match header.len() >= 5 && &header[0..4] == b"OggS" && header[4] == 0
+        {
+            | true => Confidence::High,
+            | false => Confidence::None
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Size of the fixed part of an Ogg page header (before the lacing table)
+pub const PAGE_HEADER_SIZE: usize = 27;
+
+/// Header type flag: page continues a packet from the previous page
+const FLAG_CONTINUATION: u8 = 0x01;
+/// Header type flag: first page of a logical bitstream
+const FLAG_BOS: u8 = 0x02;
+/// Header type flag: last page of a logical bitstream
+const FLAG_EOS: u8 = 0x04;
+
+/// Ogg page header (RFC 3533)
+#[derive(Debug, Clone)]
+pub struct OggPage
+{
+    /// File offset of the "OggS" capture pattern
+    pub offset:           u64,
+    pub version:          u8,
+    pub header_type:      u8,
+    /// Codec-specific position (e.g. PCM sample count), u64::MAX if no packet ends on this page
+    pub granule_position: u64,
+    pub serial:           u32,
+    pub sequence:         u32,
+    pub checksum:         u32,
+    /// Segment lacing values
+    pub lacing:           Vec<u8>
+}
+
+impl OggPage
+{
+    /// Parse the fixed page header, returning None if the capture pattern is missing
+    pub fn parse_header(data: &[u8], offset: u64) -> Option<Self>
+    {
+        if data.len() < PAGE_HEADER_SIZE || &data[0..4] != b"OggS"
+        {
+            return None;
+        }
+
+        Some(Self {
+            offset,
+            version: data[4],
+            header_type: data[5],
+            granule_position: u64::from_le_bytes([data[6], data[7], data[8], data[9], data[10], data[11], data[12], data[13]]),
+            serial: u32::from_le_bytes([data[14], data[15], data[16], data[17]]),
+            sequence: u32::from_le_bytes([data[18], data[19], data[20], data[21]]),
+            checksum: u32::from_le_bytes([data[22], data[23], data[24], data[25]]),
+            lacing: Vec::with_capacity(data[26] as usize)
+        })
+    }
+
+    /// Size of the page header including the lacing table
+    pub fn header_size(&self) -> u64
+    {
+        (PAGE_HEADER_SIZE + self.lacing.len()) as u64
+    }
+
+    /// Size of the page payload
+    pub fn payload_size(&self) -> u64
+    {
+        self.lacing.iter().map(|&value| value as u64).sum()
+    }
+
+    /// Total page size
+    pub fn size(&self) -> u64
+    {
+        self.header_size() + self.payload_size()
+    }
+
+    pub fn is_continuation(&self) -> bool
+    {
+        self.header_type & FLAG_CONTINUATION != 0
+    }
+
+    pub fn is_bos(&self) -> bool
+    {
+        self.header_type & FLAG_BOS != 0
+    }
+
+    pub fn is_eos(&self) -> bool
+    {
+        self.header_type & FLAG_EOS != 0
+    }
+}
+
+impl fmt::Display for OggPage
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut flags = Vec::new();
+        if self.is_continuation() == true
+        {
+            flags.push("continued");
+        }
+        if self.is_bos() == true
+        {
+            flags.push("BOS");
+        }
+        if self.is_eos() == true
+        {
+            flags.push("EOS");
+        }
+
+        write!(
+            f,
+            "{}",
+            tr_fmt("Page at offset {}: serial {}, sequence {}, ", &[&format!("0x{:08X}", self.offset), &format!("0x{:08X}", self.serial), &self.sequence])
+        )?;
+        if self.granule_position == u64::MAX
+        {
+            write!(f, "{}", tr("granule -"))?;
+        }
+        else
+        {
+            write!(f, "{}", tr_fmt("granule {}", &[&self.granule_position]))?;
+        }
+        write!(f, "{}", tr_fmt(", {} segments, {} bytes", &[&self.lacing.len(), &self.size()]))?;
+        if flags.is_empty() == false
+        {
+            write!(f, " [{}]", flags.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lookup table for the Ogg page checksum (CRC-32 polynomial 0x04C11DB7, also used by MPEG-2 sections)
+pub const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256
+    {
+        let mut crc = (index as u32) << 24;
+        let mut bit = 0;
+        while bit < 8
+        {
+            crc = if crc & 0x8000_0000 != 0
+            {
+                (crc << 1) ^ 0x04C1_1DB7
+            }
+            else
+            {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// Ogg page checksum (CRC-32 with polynomial 0x04C11DB7, no reflection, zero initial value)
+pub fn page_checksum(page: &[u8]) -> u32
+{
+    page.iter().enumerate().fold(0u32, |crc, (index, &byte)| {
+        // The checksum field itself is computed as zero
+        let byte = if (22..26).contains(&index) == true
+        {
+            0
+        }
+        else
+        {
+            byte
+        };
+        (crc << 8) ^ CRC_TABLE[((crc >> 24) as u8 ^ byte) as usize]
+    })
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use crate::i18n::{tr, tr_fmt};
+
+/// Longest comment value shown in full (METADATA_BLOCK_PICTURE values carry base64 images)
+const MAX_DISPLAY_LENGTH: usize = 80;
+
+/// Vorbis comment block (used by Vorbis, Opus and FLAC)
+#[derive(Debug, Clone)]
+pub struct VorbisComment
+{
+    pub vendor:   String,
+    /// Comments as (field name, value) in stored order
+    pub comments: Vec<(String, String)>
+}
+
+/// Read a little-endian u32 length at `pos`
+fn read_length(data: &[u8], pos: usize) -> Result<usize, String>
+{
+    data.get(pos..pos + 4).map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize).ok_or_else(|| "Vorbis comment truncated".to_string())
+}
+
+impl VorbisComment
+{
+    /// Parse a Vorbis comment block (without packet type/signature prefix)
+    pub fn parse(data: &[u8]) -> Result<Self, String>
+    {
+        let vendor_length = read_length(data, 0)?;
+        let vendor = data.get(4..4 + vendor_length).ok_or(tr("Vorbis comment vendor string truncated"))?;
+        let vendor = String::from_utf8_lossy(vendor).to_string();
+
+        let mut pos = 4 + vendor_length;
+        let count = read_length(data, pos)?;
+        pos += 4;
+
+        let mut comments = Vec::new();
+        for _ in 0..count
+        {
+            let length = read_length(data, pos)?;
+            let comment = data.get(pos + 4..pos + 4 + length).ok_or(tr("Vorbis comment entry truncated"))?;
+            pos += 4 + length;
+
+            let comment = String::from_utf8_lossy(comment);
+            match comment.split_once('=')
+            {
+                | Some((name, value)) => comments.push((name.to_string(), value.to_string())),
+                | None => comments.push((comment.to_string(), String::new()))
+            }
+        }
+
+        Ok(Self { vendor, comments })
+    }
+}
+
+impl fmt::Display for VorbisComment
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{}", tr_fmt("Vendor: {}", &[&self.vendor]))?;
+        writeln!(f, "{}", tr_fmt("Comments: {}", &[&self.comments.len()]))?;
+        for (name, value) in &self.comments
+        {
+            if value.chars().count() > MAX_DISPLAY_LENGTH
+            {
+                let shortened: String = value.chars().take(MAX_DISPLAY_LENGTH).collect();
+                writeln!(f, "  {}", tr_fmt("{}={}... ({} chars)", &[&name, &shortened, &value.chars().count()]))?;
+            }
+            else
+            {
+                writeln!(f, "  {}={}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+// Optimization report
+//
+// Backs `optimize --report`: quantifies the space a file wastes and what could be done about it.
+// The findings are derived from the byte map of the file (ID3v2 padding, `free` and `skip` boxes,
+// the order of `moov` and `mdat`) and from the embedded pictures (identical copies, pictures
+// larger than any player displays them).
+
+use std::{fmt, path::Path};
+
+use crate::{
+    artwork::Artwork,
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    image::Image,
+    layout::Layout,
+    report::SCHEMA_VERSION,
+    summary::write_table
+};
+
+/// Pictures wider or higher than this are reported as oversized (the largest size podcast directories ask for)
+pub const MAX_ARTWORK_DIMENSION: u32 = 3000;
+
+/// Pictures larger than this are reported as oversized
+pub const MAX_ARTWORK_SIZE: u64 = 1024 * 1024;
+
+/// Kind of wasted space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FindingKind
+{
+    /// Padding behind the frames of an ID3v2 tag
+    Padding,
+    /// `free` or `skip` box
+    FreeBox,
+    /// Picture stored more than once
+    DuplicateArtwork,
+    /// Picture with more pixels or bytes than needed
+    OversizedArtwork,
+    /// `moov` stored after `mdat`, players have to read the whole file before they can start
+    MoovAfterMdat
+}
+
+impl FindingKind
+{
+    /// Identifier used in JSON reports
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | FindingKind::Padding => "padding",
+            | FindingKind::FreeBox => "free_box",
+            | FindingKind::DuplicateArtwork => "duplicate_artwork",
+            | FindingKind::OversizedArtwork => "oversized_artwork",
+            | FindingKind::MoovAfterMdat => "moov_after_mdat"
+        }
+    }
+
+    /// Name shown in the text output
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            | FindingKind::Padding => "Tag padding",
+            | FindingKind::FreeBox => "Free space box",
+            | FindingKind::DuplicateArtwork => "Duplicate artwork",
+            | FindingKind::OversizedArtwork => "Oversized artwork",
+            | FindingKind::MoovAfterMdat => "Movie box after media data"
+        }
+    }
+
+    /// Suggested action
+    pub fn suggestion(self) -> &'static str
+    {
+        match self
+        {
+            | FindingKind::Padding => "Rewrite the tag with less padding with tag repad (keep some if the tag is edited often, it avoids rewriting the file)",
+            | FindingKind::FreeBox => "Drop the box when the file is rewritten (keep it if the metadata is edited often, it absorbs size changes)",
+            | FindingKind::DuplicateArtwork => "Store the picture once, e.g. leave it out of the chapters that repeat the cover",
+            | FindingKind::OversizedArtwork => "Scale the picture down to at most 3000x3000 pixels and store it as JPEG",
+            | FindingKind::MoovAfterMdat =>
+                "Move the movie box in front of the media data with optimize --faststart, so playback can start before the file is fully downloaded",
+        }
+    }
+}
+
+/// Wasted space found in a file
+#[derive(Debug, Clone)]
+pub struct Finding
+{
+    pub kind:         FindingKind,
+    /// File offset of the structure (pictures are located by `location` only)
+    pub offset:       Option<u64>,
+    /// Where the structure is, e.g. "ID3v2.3/Padding", "moov/udta/free" or "CHAP ch2/APIC"
+    pub location:     String,
+    /// Size of the structure
+    pub size:         u64,
+    /// Bytes saved by following the suggestion (None if it depends on the encoder, e.g. for scaled pictures)
+    pub reclaimable:  Option<u64>,
+    /// Location of the first copy of a duplicate picture
+    pub duplicate_of: Option<String>,
+    /// Width and height of a picture
+    pub dimensions:   Option<(u32, u32)>
+}
+
+impl Finding
+{
+    /// Details column of the text output
+    fn details(&self) -> String
+    {
+        match (&self.duplicate_of, self.dimensions)
+        {
+            | (Some(first), _) => tr_fmt("same picture as {}", &[first]),
+            | (None, Some((width, height))) => format!("{}x{}", width, height),
+            | (None, None) => String::new()
+        }
+    }
+
+    /// Structured representation for the JSON report
+    fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "kind": self.kind.id(),
+            "offset": self.offset,
+            "location": self.location,
+            "size": self.size,
+            "reclaimable": self.reclaimable,
+            "duplicate_of": self.duplicate_of,
+            "width": self.dimensions.map(|(width, _)| width),
+            "height": self.dimensions.map(|(_, height)| height),
+            "suggestion": self.kind.suggestion()
+        })
+    }
+}
+
+/// Wasted space of a whole file
+#[derive(Debug, Clone, Default)]
+pub struct OptimizationReport
+{
+    pub file_size: u64,
+    pub findings:  Vec<Finding>
+}
+
+impl OptimizationReport
+{
+    /// Derive the findings from the byte map and the embedded pictures of a file
+    pub fn new(layout: &Layout, artwork: &[Artwork]) -> Self
+    {
+        let mut report = Self { file_size: layout.file_size, findings: Vec::new() };
+        report.check_layout(layout);
+        report.check_artwork(artwork);
+        report.findings.sort_by_key(|finding| finding.kind);
+
+        report
+    }
+
+    /// Padding, free space boxes and the order of the top-level boxes
+    fn check_layout(&mut self, layout: &Layout)
+    {
+        // Regions follow the region containing them, the path is rebuilt from the names of the enclosing regions
+        let mut path: Vec<&str> = Vec::new();
+        let mut moov = None;
+        let mut mdat_offset = None;
+        for region in &layout.regions
+        {
+            path.truncate(region.depth);
+            path.push(&region.name);
+
+            let kind = match (region.name.as_str(), region.depth)
+            {
+                | ("Padding", _) => FindingKind::Padding,
+                | ("free" | "skip", _) => FindingKind::FreeBox,
+                | ("moov", 0) =>
+                {
+                    moov = Some((region.offset, region.size));
+                    continue;
+                }
+                | ("mdat", 0) =>
+                {
+                    mdat_offset = mdat_offset.or(Some(region.offset));
+                    continue;
+                }
+                | _ => continue
+            };
+            self.findings.push(Finding {
+                kind,
+                offset: Some(region.offset),
+                location: path.join("/"),
+                size: region.size,
+                reclaimable: Some(region.size),
+                duplicate_of: None,
+                dimensions: None
+            });
+        }
+
+        if let (Some((moov_offset, moov_size)), Some(mdat_offset)) = (moov, mdat_offset) &&
+            moov_offset > mdat_offset
+        {
+            self.findings.push(Finding {
+                kind:         FindingKind::MoovAfterMdat,
+                offset:       Some(moov_offset),
+                location:     "moov".to_string(),
+                size:         moov_size,
+                reclaimable:  None,
+                duplicate_of: None,
+                dimensions:   None
+            });
+        }
+    }
+
+    /// Identical and oversized pictures
+    fn check_artwork(&mut self, artwork: &[Artwork])
+    {
+        for (index, picture) in artwork.iter().enumerate()
+        {
+            let size = picture.data.len() as u64;
+            if let Some(first) = artwork[..index].iter().find(|other| other.data == picture.data)
+            {
+                // Copies are only reported once, as duplicates
+                self.findings.push(Finding {
+                    kind: FindingKind::DuplicateArtwork,
+                    offset: None,
+                    location: picture.location.clone(),
+                    size,
+                    reclaimable: Some(size),
+                    duplicate_of: Some(first.location.clone()),
+                    dimensions: None
+                });
+                continue;
+            }
+
+            let dimensions = Image::parse(&picture.data, 0, &mut Diagnostics::new()).ok().and_then(|image| image.dimensions());
+            let too_large = matches!(dimensions, Some((width, height)) if width.max(height) > MAX_ARTWORK_DIMENSION);
+            if too_large == true || size > MAX_ARTWORK_SIZE
+            {
+                self.findings.push(Finding {
+                    kind: FindingKind::OversizedArtwork,
+                    offset: None,
+                    location: picture.location.clone(),
+                    size,
+                    reclaimable: None,
+                    duplicate_of: None,
+                    dimensions
+                });
+            }
+        }
+    }
+
+    /// Sum of the bytes that can be reclaimed
+    pub fn reclaimable(&self) -> u64
+    {
+        self.findings.iter().filter_map(|finding| finding.reclaimable).sum()
+    }
+
+    /// Share of the file in percent
+    pub fn share(&self, size: u64) -> f64
+    {
+        match self.file_size
+        {
+            | 0 => 0.0,
+            | file_size => size as f64 * 100.0 / file_size as f64
+        }
+    }
+
+    /// Whether nothing was found
+    pub fn is_empty(&self) -> bool
+    {
+        self.findings.is_empty()
+    }
+
+    /// JSON document of `optimize --report` (see `schema/optimize.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "file_size": self.file_size,
+            "reclaimable": self.reclaimable(),
+            "findings": self.findings.iter().map(Finding::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for OptimizationReport
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut rows = vec![[tr("Finding"), tr("Location"), tr("Offset"), tr("Size"), tr("Reclaimable"), tr("Details")].map(str::to_string)];
+        for finding in &self.findings
+        {
+            rows.push([
+                tr(finding.kind.name()).to_string(),
+                finding.location.clone(),
+                finding.offset.map(|offset| format!("0x{:08X}", offset)).unwrap_or_else(|| "-".to_string()),
+                finding.size.to_string(),
+                finding.reclaimable.map(|size| size.to_string()).unwrap_or_else(|| "-".to_string()),
+                finding.details()
+            ]);
+        }
+        write_table(f, &rows)?;
+
+        // One suggestion per kind of finding, in the order of the table
+        writeln!(f, "\n{}", tr("Suggestions:"))?;
+        let mut kinds: Vec<FindingKind> = self.findings.iter().map(|finding| finding.kind).collect();
+        kinds.dedup();
+        for kind in kinds
+        {
+            writeln!(f, "  {}: {}", tr(kind.name()), tr(kind.suggestion()))?;
+        }
+        Ok(())
+    }
+}
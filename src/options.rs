@@ -0,0 +1,201 @@
+// Dissection options
+//
+// Output selection and plausibility thresholds shared by all dissectors. The command line
+// tool builds them from its arguments; library users start from `DissectOptions::default()`.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    i18n::tr_fmt,
+    isobmff::{boxes::sample_table::DEFAULT_MAX_TABLE_ENTRIES, tree::InitSegment},
+    path_filter::{PathFilter, PathMatch}
+};
+
+/// Bytes of frame or box data shown per hexdump unless `--dump-limit` says otherwise
+pub const DEFAULT_DUMP_LIMIT: usize = 256;
+
+/// How much of each frame or box `--dump` shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpLimit
+{
+    /// At most this many bytes, followed by a note on the bytes left out
+    Bytes(usize),
+    /// All data, however large
+    Full
+}
+
+impl DumpLimit
+{
+    /// Byte limit for `hexdump::format_hexdump_limited` (None for everything)
+    pub fn bytes(self) -> Option<usize>
+    {
+        match self
+        {
+            | DumpLimit::Bytes(bytes) => Some(bytes),
+            | DumpLimit::Full => None
+        }
+    }
+}
+
+impl Default for DumpLimit
+{
+    fn default() -> Self
+    {
+        DumpLimit::Bytes(DEFAULT_DUMP_LIMIT)
+    }
+}
+
+impl FromStr for DumpLimit
+{
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err>
+    {
+        match value
+        {
+            | "full" => Ok(DumpLimit::Full),
+            | bytes => bytes.parse().map(DumpLimit::Bytes).map_err(|_| tr_fmt("Expected a byte count or 'full', got '{}'", &[&value]))
+        }
+    }
+}
+
+impl fmt::Display for DumpLimit
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            | DumpLimit::Bytes(bytes) => write!(f, "{}", bytes),
+            | DumpLimit::Full => write!(f, "full")
+        }
+    }
+}
+
+/// Tag size thresholds in bytes (None disables the respective level)
+#[derive(Debug, Clone, Copy)]
+pub struct TagSizeThresholds
+{
+    pub info:     Option<u64>,
+    pub warning:  Option<u64>,
+    pub critical: Option<u64>
+}
+
+impl TagSizeThresholds
+{
+    /// Build thresholds from megabyte values where 0 disables a level
+    pub fn from_megabytes(info: u64, warning: u64, critical: u64) -> Self
+    {
+        let to_bytes = |megabytes: u64| {
+            if megabytes == 0
+            {
+                None
+            }
+            else
+            {
+                Some(megabytes * 1_000_000)
+            }
+        };
+        Self { info: to_bytes(info), warning: to_bytes(warning), critical: to_bytes(critical) }
+    }
+}
+
+/// Options for controlling dissect output
+#[derive(Debug, Clone)]
+pub struct DissectOptions
+{
+    pub show_header:         bool,
+    pub show_data:           bool,
+    pub show_verbose:        bool,
+    pub show_dump:           bool,
+    /// Bytes of data shown per frame or box by the hexdumps of `show_dump`
+    pub dump_limit:          DumpLimit,
+    /// Only dump the ID3v2 frames and ISOBMFF boxes selected by these path expressions (all of them if None)
+    pub dump_filter:         Option<PathFilter>,
+    pub show_gaps:           bool,
+    /// Show MD5 and SHA-256 digests of the top-level regions (tags, audio data, mdat payloads)
+    pub show_hashes:         bool,
+    /// Show the MusicBrainz and AcoustID identifiers gathered from all metadata layers
+    pub show_music_ids:      bool,
+    /// Show the ReplayGain, RVA2 and Sound Check values gathered from all metadata layers
+    pub show_loudness:       bool,
+    /// Show the encoder delay and padding of MP3 LAME headers and iTunSMPB strings with the resulting trim values
+    pub show_gapless:        bool,
+    /// Show the podcast fields (iTunes frames and items) and the chapters with their links and pictures
+    pub show_podcast:        bool,
+    /// Show sample table statistics (durations, sizes, chunk layout) per track
+    pub show_stats:          bool,
+    /// Annotate frames and boxes with the specification section that defines them
+    pub show_spec:           bool,
+    /// Analyze embedded pictures (format, dimensions, JFIF and EXIF metadata)
+    pub inspect_art:         bool,
+    /// Dissect embedded pictures and objects with the dissector of their format
+    pub show_payloads:       bool,
+    /// Add the text output lines of parsed ISOBMFF box and ID3v2 frame contents to JSON reports
+    pub show_details:        bool,
+    /// Only show the ID3v2 frames and ISOBMFF boxes selected by these path expressions (and their ancestors)
+    pub filter:              Option<PathFilter>,
+    pub tag_size_thresholds: TagSizeThresholds,
+    /// Maximum number of entries parsed per sample table (stts, stsc, stsz, stco, co64)
+    pub max_table_entries:   usize,
+    /// Report corrupt boxes and frames as warnings and resynchronize instead of aborting
+    pub recover:             bool,
+    /// Initialization segment whose movie box gives the track fragments of a media segment their timescales and sample defaults
+    pub init_segment:        Option<InitSegment>
+}
+
+impl Default for TagSizeThresholds
+{
+    /// Thresholds of 10 MB (info), 50 MB (warning) and 100 MB (critical)
+    fn default() -> Self
+    {
+        Self::from_megabytes(10, 50, 100)
+    }
+}
+
+impl Default for DissectOptions
+{
+    /// Show header and all data, no verbose output, hexdumps, gaps, hashes, statistics, specification references, artwork analysis, payloads or JSON detail
+    /// lines, no recovery or initialization segment
+    fn default() -> Self
+    {
+        DissectOptions {
+            show_header:         true,
+            show_data:           true,
+            show_verbose:        false,
+            show_dump:           false,
+            dump_limit:          DumpLimit::default(),
+            dump_filter:         None,
+            show_gaps:           false,
+            show_hashes:         false,
+            show_music_ids:      false,
+            show_loudness:       false,
+            show_gapless:        false,
+            show_podcast:        false,
+            show_stats:          false,
+            show_spec:           false,
+            inspect_art:         false,
+            show_payloads:       false,
+            show_details:        false,
+            filter:              None,
+            tag_size_thresholds: TagSizeThresholds::default(),
+            max_table_entries:   DEFAULT_MAX_TABLE_ENTRIES,
+            recover:             false,
+            init_segment:        None
+        }
+    }
+}
+
+impl DissectOptions
+{
+    /// Whether the hexdumps show the data of the frame or box at `path` (type names, outermost first)
+    pub fn dumps<S: AsRef<str>>(&self, path: &[S]) -> bool
+    {
+        self.show_dump == true && self.dump_filter.as_ref().is_none_or(|filter| filter.check(path) == PathMatch::Selected)
+    }
+
+    /// Whether the frame at `path` is shown in dump layout: it is dumped or contains dumped sub-frames
+    pub fn dump_layout<S: AsRef<str>>(&self, path: &[S]) -> bool
+    {
+        self.show_dump == true && self.dump_filter.as_ref().is_none_or(|filter| filter.check(path) != PathMatch::None)
+    }
+}
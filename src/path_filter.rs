@@ -0,0 +1,111 @@
+// Path filter
+//
+// Selection of `--filter` expressions such as `moov/trak/mdia/minf/stbl/stsd` or `CHAP/*`:
+// a path names nested boxes or frames by type, `*` stands for any one element and `**` for
+// any number of elements. Output keeps the selected elements with everything below them,
+// plus the ancestors needed to reach them.
+
+use std::{fmt, str::FromStr};
+
+use crate::i18n::tr_fmt;
+
+/// How a box or frame path relates to a filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathMatch
+{
+    /// Neither the element nor anything below it is selected
+    None,
+    /// Elements below may be selected, the element itself is only shown to reach them
+    Ancestor,
+    /// The element is selected, including everything below it
+    Selected
+}
+
+/// One path expression, split into its elements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern
+{
+    segments: Vec<String>
+}
+
+impl PathPattern
+{
+    /// Match a path of box or frame types against this pattern
+    pub fn check<S: AsRef<str>>(&self, path: &[S]) -> PathMatch
+    {
+        check_segments(&self.segments, path)
+    }
+}
+
+impl FromStr for PathPattern
+{
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err>
+    {
+        let segments: Vec<String> = expression.trim_matches('/').split('/').map(str::to_string).collect();
+        if segments.iter().any(String::is_empty) == true
+        {
+            return Err(tr_fmt("Invalid path expression '{}': empty path element", &[&expression]));
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl fmt::Display for PathPattern
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.segments.join("/"))
+    }
+}
+
+/// Any number of path expressions; an element is selected if one of them selects it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathFilter
+{
+    pub patterns: Vec<PathPattern>
+}
+
+impl PathFilter
+{
+    pub fn new(patterns: Vec<PathPattern>) -> Self
+    {
+        Self { patterns }
+    }
+
+    /// Match a path of box or frame types (outermost first) against all expressions
+    pub fn check<S: AsRef<str>>(&self, path: &[S]) -> PathMatch
+    {
+        self.patterns.iter().map(|pattern| pattern.check(path)).max().unwrap_or(PathMatch::None)
+    }
+}
+
+fn check_segments<S: AsRef<str>>(segments: &[String], path: &[S]) -> PathMatch
+{
+    match (segments.split_first(), path.split_first())
+    {
+        | (None, None) => PathMatch::Selected,
+        // The path ends before the pattern does: elements below may still match
+        | (Some(_), None) if segments.iter().all(|segment| segment == "**") == true => PathMatch::Selected,
+        | (Some(_), None) => PathMatch::Ancestor,
+        | (None, Some(_)) => PathMatch::None,
+        | (Some((segment, rest)), Some((_, path_rest))) if segment == "**" =>
+        {
+            // "**" either ends here or swallows one more path element
+            check_segments(rest, path).max(check_segments(segments, path_rest))
+        }
+        | (Some((segment, rest)), Some((element, path_rest))) =>
+        {
+            if segment == "*" || segment == element.as_ref()
+            {
+                check_segments(rest, path_rest)
+            }
+            else
+            {
+                PathMatch::None
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Embedded payloads
+//
+// Pictures and tag objects can hold complete media files: a JPEG in an APIC frame or `covr` item,
+// an MP4 file or another ID3v2 tag in a GEOB frame. `--payloads` hands each of them to the
+// dissector the registry selects for its first bytes, as if it were a file of its own. Offsets
+// in the output of a payload are relative to its start, so its findings name its location.
+
+use std::io::Write;
+
+use crate::{
+    color::Colorize,
+    diagnostics::Diagnostics,
+    dissector_registry::DissectorRegistry,
+    i18n::{tr, tr_fmt},
+    objects::EmbeddedObject,
+    options::DissectOptions,
+    report
+};
+
+/// Write the dissection of `object` to `output` under a heading with its location and format; objects no dissector recognizes are skipped
+///
+/// Returns whether the object was dissected. A payload that fails to parse is reported as a warning.
+pub fn print_payload(
+    registry: &DissectorRegistry, object: &EmbeddedObject, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+) -> Result<bool, Box<dyn std::error::Error>>
+{
+    let Some(dissector) = registry.build_for_payload(&object.data)
+    else
+    {
+        return Ok(false);
+    };
+
+    writeln!(output, "\n{}", tr_fmt("{} ({} bytes): {} ({})", &[&object.location, &object.data.len(), &dissector.media_type(), &tr(dissector.name())]).cyan())?;
+    let mut payload_diagnostics = Diagnostics::new();
+    if let Err(e) = dissector.dissect_payload(&object.data, options, output, &mut payload_diagnostics)
+    {
+        payload_diagnostics.warning(None, tr_fmt("Payload not dissected: {}", &[&e]));
+    }
+    diagnostics.extend_located(payload_diagnostics, &object.location);
+    Ok(true)
+}
+
+/// Structured report of `object` with its dissected layer, None if no dissector recognizes it
+pub fn payload_to_json(registry: &DissectorRegistry, object: &EmbeddedObject, options: &DissectOptions, diagnostics: &mut Diagnostics) -> Option<serde_json::Value>
+{
+    let dissector = registry.build_for_payload(&object.data)?;
+
+    let mut payload_diagnostics = Diagnostics::new();
+    let layer = match dissector.payload_to_json(&object.data, options, &mut payload_diagnostics)
+    {
+        | Ok((content, end_offset)) => Some(report::layer(dissector.as_ref(), 0, end_offset, content)),
+        | Err(e) =>
+        {
+            payload_diagnostics.warning(None, tr_fmt("Payload not dissected: {}", &[&e]));
+            None
+        }
+    };
+    diagnostics.extend_located(payload_diagnostics, &object.location);
+
+    Some(serde_json::json!({
+        "location": object.location,
+        "kind": object.kind.id(),
+        "size": object.data.len(),
+        "format": dissector.media_type(),
+        "layer": layer
+    }))
+}
@@ -0,0 +1,483 @@
+// HLS playlists
+//
+// Backs the `playlist` command: parses a media playlist (.m3u8) and compares what its local
+// segments carry, dissected as MPEG transport streams or fragmented MP4 after the initialization
+// segment of EXT-X-MAP, with what the playlist declares: EXTINF durations against the measured
+// ones, timestamp jumps against EXT-X-DISCONTINUITY and the encryption of each segment against the
+// EXT-X-KEY method in effect (see `schema/playlist.schema.json`).
+
+use std::{
+    fmt,
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    diagnostics::Diagnostics,
+    i18n::{tr, tr_fmt},
+    report::SCHEMA_VERSION,
+    summary::write_table
+};
+
+/// Seconds the measured timing of a segment may deviate from the playlist before it is reported
+///
+/// Transport stream durations end at the last time stamp and miss the duration of the last frame.
+pub const TIMING_TOLERANCE: f64 = 0.5;
+
+/// Encryption declared by an EXT-X-KEY tag
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistKey
+{
+    /// AES-128, SAMPLE-AES or SAMPLE-AES-CTR
+    pub method:     String,
+    pub uri:        Option<String>,
+    pub iv:         Option<String>,
+    pub key_format: Option<String>
+}
+
+/// Media segment of a playlist
+#[derive(Debug, Clone)]
+pub struct PlaylistSegment
+{
+    /// Media sequence number (EXT-X-MEDIA-SEQUENCE plus the position in the playlist)
+    pub sequence:      u64,
+    pub uri:           String,
+    /// Duration declared by EXTINF in seconds
+    pub duration:      f64,
+    pub title:         String,
+    /// Offset and length of the segment within its resource (EXT-X-BYTERANGE)
+    pub byte_range:    Option<(u64, u64)>,
+    /// Preceded by EXT-X-DISCONTINUITY
+    pub discontinuity: bool,
+    /// Key in effect, None for unencrypted segments (no key or METHOD=NONE)
+    pub key:           Option<PlaylistKey>,
+    /// URI of the initialization segment in effect (EXT-X-MAP)
+    pub map:           Option<String>
+}
+
+impl PlaylistSegment
+{
+    /// Label of the segment in messages, e.g. "12 (segment12.ts)"
+    fn label(&self) -> String
+    {
+        format!("{} ({})", self.sequence, self.uri)
+    }
+}
+
+/// Media playlist
+#[derive(Debug, Clone, Default)]
+pub struct Playlist
+{
+    /// EXT-X-VERSION
+    pub version:         Option<u32>,
+    /// EXT-X-TARGETDURATION in seconds
+    pub target_duration: Option<u64>,
+    /// EXT-X-MEDIA-SEQUENCE, the sequence number of the first segment
+    pub media_sequence:  u64,
+    /// EXT-X-PLAYLIST-TYPE (EVENT or VOD)
+    pub playlist_type:   Option<String>,
+    /// EXT-X-ENDLIST was found
+    pub end_list:        bool,
+    pub segments:        Vec<PlaylistSegment>
+}
+
+impl Playlist
+{
+    /// Parse a media playlist; master playlists are rejected with the list of their variant streams
+    pub fn parse(text: &str) -> Result<Self, String>
+    {
+        let mut lines = text.lines().map(str::trim).filter(|line| line.is_empty() == false);
+        if lines.next().map(|line| line.trim_start_matches('\u{FEFF}')) != Some("#EXTM3U")
+        {
+            return Err(tr("Not an HLS playlist: the first line is not #EXTM3U").to_string());
+        }
+
+        let mut playlist = Playlist::default();
+        let mut variants = Vec::new();
+        let mut variant_pending = false;
+        // Tags applying to the next segment only
+        let mut extinf: Option<(f64, String)> = None;
+        let mut byte_range: Option<(u64, Option<u64>)> = None;
+        let mut discontinuity = false;
+        // Tags applying to all following segments
+        let mut key: Option<PlaylistKey> = None;
+        let mut map: Option<String> = None;
+        // Where the last byte range of each resource ended, for ranges without an offset
+        let mut range_ends: Vec<(String, u64)> = Vec::new();
+
+        for line in lines
+        {
+            let Some(tag) = line.strip_prefix('#')
+            else
+            {
+                if variant_pending == true
+                {
+                    variants.push(line.to_string());
+                    variant_pending = false;
+                    continue;
+                }
+                let Some((duration, title)) = extinf.take()
+                else
+                {
+                    return Err(tr_fmt("Segment {} has no EXTINF tag", &[&line]));
+                };
+                let byte_range = byte_range.take().map(|(length, offset)| {
+                    let offset = offset.unwrap_or_else(|| range_ends.iter().rev().find(|(uri, _)| uri == line).map_or(0, |&(_, end)| end));
+                    range_ends.push((line.to_string(), offset + length));
+                    (offset, length)
+                });
+                playlist.segments.push(PlaylistSegment {
+                    sequence: playlist.media_sequence + playlist.segments.len() as u64,
+                    uri: line.to_string(),
+                    duration,
+                    title,
+                    byte_range,
+                    discontinuity,
+                    key: key.clone(),
+                    map: map.clone()
+                });
+                discontinuity = false;
+                continue;
+            };
+
+            let (name, value) = tag.split_once(':').unwrap_or((tag, ""));
+            match name
+            {
+                | "EXT-X-VERSION" => playlist.version = value.parse().ok(),
+                | "EXT-X-TARGETDURATION" => playlist.target_duration = value.parse().ok(),
+                | "EXT-X-MEDIA-SEQUENCE" => playlist.media_sequence = value.parse().unwrap_or(0),
+                | "EXT-X-PLAYLIST-TYPE" => playlist.playlist_type = Some(value.to_string()),
+                | "EXT-X-ENDLIST" => playlist.end_list = true,
+                | "EXT-X-DISCONTINUITY" => discontinuity = true,
+                | "EXT-X-STREAM-INF" => variant_pending = true,
+                | "EXTINF" =>
+                {
+                    let (duration, title) = value.split_once(',').unwrap_or((value, ""));
+                    let duration = duration.trim().parse().map_err(|_| tr_fmt("Invalid EXTINF duration '{}'", &[&duration]))?;
+                    extinf = Some((duration, title.trim().to_string()));
+                }
+                | "EXT-X-BYTERANGE" =>
+                {
+                    let (length, offset) = value.split_once('@').map_or((value, None), |(length, offset)| (length, Some(offset)));
+                    let invalid = || tr_fmt("Invalid EXT-X-BYTERANGE '{}'", &[&value]);
+                    let length = length.trim().parse().map_err(|_| invalid())?;
+                    let offset = offset.map(|offset| offset.trim().parse().map_err(|_| invalid())).transpose()?;
+                    byte_range = Some((length, offset));
+                }
+                | "EXT-X-KEY" =>
+                {
+                    let attributes = parse_attributes(value);
+                    let attribute = |attribute_name: &str| attributes.iter().find(|(candidate, _)| candidate == attribute_name).map(|(_, value)| value.clone());
+                    key = attribute("METHOD").filter(|method| method != "NONE").map(|method| PlaylistKey {
+                        method,
+                        uri: attribute("URI"),
+                        iv: attribute("IV"),
+                        key_format: attribute("KEYFORMAT")
+                    });
+                }
+                | "EXT-X-MAP" =>
+                {
+                    map = parse_attributes(value).into_iter().find(|(attribute_name, _)| attribute_name == "URI").map(|(_, uri)| uri);
+                }
+                | _ => ()
+            }
+        }
+
+        if variants.is_empty() == false
+        {
+            return Err(tr_fmt("Master playlist with {} variant streams ({}); pass one of the media playlists", &[&variants.len(), &variants.join(", ")]));
+        }
+        Ok(playlist)
+    }
+}
+
+/// Attribute list of a tag (NAME=value,NAME="quoted, value"), with the quotes removed
+fn parse_attributes(text: &str) -> Vec<(String, String)>
+{
+    let mut attributes = Vec::new();
+    let mut rest = text.trim();
+    while let Some((name, after)) = rest.split_once('=')
+    {
+        let (value, next) = match after.strip_prefix('"')
+        {
+            | Some(quoted) => match quoted.split_once('"')
+            {
+                | Some((value, next)) => (value, next),
+                | None => (quoted, "")
+            },
+            | None => after.split_once(',').map_or((after, ""), |(value, next)| (value, next))
+        };
+        attributes.push((name.trim().to_string(), value.to_string()));
+        rest = next.trim_start_matches(',').trim();
+    }
+    attributes
+}
+
+/// Timing and encryption found in a media segment
+#[derive(Debug, Clone, Default)]
+pub struct SegmentMedia
+{
+    /// Earliest decode time (tfdt) or presentation time stamp in seconds
+    pub start:      Option<f64>,
+    /// Duration of the longest track or stream in seconds
+    pub duration:   Option<f64>,
+    /// Protection scheme of fragmented MP4 (e.g. "cbcs"), "SAMPLE-AES" for transport streams with
+    /// sample-encrypted stream types, or "scrambled" for transport packets with scrambling bits
+    pub encryption: Option<String>
+}
+
+/// Outcome of inspecting the resource of one segment
+#[derive(Debug, Clone)]
+pub enum SegmentStatus
+{
+    /// Fetched over the network, which the command does not do
+    Remote,
+    /// The segment or its initialization segment could not be read
+    Unreadable(String),
+    /// Dissected as `media_type`; `media` is None for formats without segment timing
+    Dissected
+    {
+        media_type: &'static str, media: Option<SegmentMedia>
+    }
+}
+
+impl SegmentStatus
+{
+    fn media(&self) -> Option<&SegmentMedia>
+    {
+        match self
+        {
+            | SegmentStatus::Dissected { media: Some(media), .. } => Some(media),
+            | _ => None
+        }
+    }
+}
+
+/// Playlist with the inspection of each segment and the mismatches found between them
+#[derive(Debug, Clone)]
+pub struct PlaylistCheck
+{
+    pub playlist:    Playlist,
+    /// Status of each segment of `playlist.segments`
+    pub statuses:    Vec<SegmentStatus>,
+    pub diagnostics: Diagnostics
+}
+
+impl PlaylistCheck
+{
+    /// Compare the inspected segments with the playlist
+    pub fn new(playlist: Playlist, statuses: Vec<SegmentStatus>) -> Self
+    {
+        let mut diagnostics = Diagnostics::new();
+        let mut previous: Option<(&PlaylistSegment, &SegmentMedia)> = None;
+        for (segment, status) in playlist.segments.iter().zip(&statuses)
+        {
+            let label = segment.label();
+            if let Some(target_duration) = playlist.target_duration &&
+                segment.duration.round() > target_duration as f64
+            {
+                diagnostics.error(None, tr_fmt("Segment {}: EXTINF of {} s exceeds EXT-X-TARGETDURATION of {} s", &[&label, &segment.duration, &target_duration]));
+            }
+
+            let aes_128 = segment.key.as_ref().is_some_and(|key| key.method == "AES-128");
+            let media = match status
+            {
+                | SegmentStatus::Remote =>
+                {
+                    diagnostics.info(None, tr_fmt("Segment {}: remote segment, not fetched", &[&label]));
+                    None
+                }
+                | SegmentStatus::Unreadable(error) =>
+                {
+                    diagnostics.error(None, tr_fmt("Segment {}: {}", &[&label, error]));
+                    None
+                }
+                | SegmentStatus::Dissected { media_type: "Unknown", .. } if aes_128 == true =>
+                {
+                    diagnostics.info(None, tr_fmt("Segment {}: encrypted as a whole with AES-128, not inspected", &[&label]));
+                    None
+                }
+                | SegmentStatus::Dissected { media_type: "Unknown", .. } =>
+                {
+                    diagnostics.error(None, tr_fmt("Segment {}: neither an MPEG transport stream nor a fragmented MP4 segment", &[&label]));
+                    None
+                }
+                | SegmentStatus::Dissected { media_type, media } =>
+                {
+                    if aes_128 == true
+                    {
+                        diagnostics.error(None, tr_fmt("Segment {}: EXT-X-KEY declares AES-128, but the segment is readable in the clear", &[&label]));
+                    }
+                    if media.is_none() == true
+                    {
+                        diagnostics.warning(None, tr_fmt("Segment {}: {} segments carry no timing the playlist can be checked against", &[&label, media_type]));
+                    }
+                    media.as_ref()
+                }
+            };
+            let Some(media) = media
+            else
+            {
+                previous = None;
+                continue;
+            };
+
+            if let Some(duration) = media.duration &&
+                (duration - segment.duration).abs() > TIMING_TOLERANCE
+            {
+                diagnostics.warning(None, tr_fmt("Segment {}: EXTINF declares {} s, the media lasts {} s", &[&label, &segment.duration, &format!("{:.3}", duration)]));
+            }
+            check_encryption(segment, media, &label, &mut diagnostics);
+
+            if let Some((previous_segment, previous_media)) = previous &&
+                let (Some(previous_start), Some(start)) = (previous_media.start, media.start)
+            {
+                let expected = previous_start + previous_media.duration.unwrap_or(previous_segment.duration);
+                let jump = start - expected;
+                if jump.abs() > TIMING_TOLERANCE && segment.discontinuity == false
+                {
+                    diagnostics.warning(
+                        None,
+                        tr_fmt("Segment {}: timestamps jump by {} s from the previous segment without EXT-X-DISCONTINUITY", &[&label, &format!("{:+.3}", jump)])
+                    );
+                }
+                else if jump.abs() <= TIMING_TOLERANCE && segment.discontinuity == true
+                {
+                    diagnostics.info(None, tr_fmt("Segment {}: EXT-X-DISCONTINUITY, but the timestamps continue the previous segment", &[&label]));
+                }
+            }
+            previous = Some((segment, media));
+        }
+
+        Self { playlist, statuses, diagnostics }
+    }
+
+    /// JSON document of the playlist command (see `schema/playlist.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "version": self.playlist.version,
+            "target_duration": self.playlist.target_duration,
+            "media_sequence": self.playlist.media_sequence,
+            "playlist_type": self.playlist.playlist_type,
+            "end_list": self.playlist.end_list,
+            "segments": self.playlist.segments.iter().zip(&self.statuses).map(|(segment, status)| serde_json::json!({
+                "sequence": segment.sequence,
+                "uri": segment.uri,
+                "extinf": segment.duration,
+                "title": segment.title,
+                "byte_range": segment.byte_range.map(|(offset, length)| serde_json::json!({ "offset": offset, "length": length })),
+                "discontinuity": segment.discontinuity,
+                "key": segment.key.as_ref().map(|key| serde_json::json!({
+                    "method": key.method,
+                    "uri": key.uri,
+                    "iv": key.iv,
+                    "key_format": key.key_format
+                })),
+                "map": segment.map,
+                "status": match status
+                {
+                    | SegmentStatus::Remote => "remote",
+                    | SegmentStatus::Unreadable(_) => "unreadable",
+                    | SegmentStatus::Dissected { .. } => "dissected"
+                },
+                "format": match status
+                {
+                    | SegmentStatus::Dissected { media_type, .. } => Some(*media_type),
+                    | _ => None
+                },
+                "start": status.media().and_then(|media| media.start),
+                "duration": status.media().and_then(|media| media.duration),
+                "encryption": status.media().and_then(|media| media.encryption.as_deref())
+            })).collect::<Vec<_>>(),
+            "findings": self.diagnostics.to_json()
+        })
+    }
+}
+
+/// Report the encryption of `media` that does not match the key of `segment`
+fn check_encryption(segment: &PlaylistSegment, media: &SegmentMedia, label: &str, diagnostics: &mut Diagnostics)
+{
+    let method = segment.key.as_ref().map(|key| key.method.as_str());
+    match (method, media.encryption.as_deref())
+    {
+        | (None, Some(encryption)) => diagnostics.error(None, tr_fmt("Segment {}: encrypted ({}), but no EXT-X-KEY is in effect", &[&label, &encryption])),
+        | (Some(method @ ("SAMPLE-AES" | "SAMPLE-AES-CTR")), None) =>
+        {
+            diagnostics.error(None, tr_fmt("Segment {}: EXT-X-KEY declares {}, but the segment carries no encryption signaling", &[&label, &method]));
+        }
+        // Transport streams signal sample encryption through their stream types, fragmented MP4 through the protection scheme
+        | (Some(method @ "SAMPLE-AES"), Some(encryption)) if encryption != "SAMPLE-AES" && encryption != "cbcs" =>
+        {
+            diagnostics.warning(
+                None,
+                tr_fmt("Segment {}: EXT-X-KEY declares {}, but the segment is encrypted with '{}' (expected '{}')", &[&label, &method, &encryption, &"cbcs"])
+            );
+        }
+        | (Some(method @ "SAMPLE-AES-CTR"), Some(encryption)) if encryption != "cenc" =>
+        {
+            diagnostics.warning(
+                None,
+                tr_fmt("Segment {}: EXT-X-KEY declares {}, but the segment is encrypted with '{}' (expected '{}')", &[&label, &method, &encryption, &"cenc"])
+            );
+        }
+        | _ => ()
+    }
+}
+
+/// Path of the local resource `uri` relative to the playlist at `playlist_path`, None for remote resources
+pub fn resolve(playlist_path: &Path, uri: &str) -> Option<PathBuf>
+{
+    match uri.contains("://")
+    {
+        | true => None,
+        | false => Some(playlist_path.parent().unwrap_or(Path::new("")).join(uri))
+    }
+}
+
+impl fmt::Display for PlaylistCheck
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let seconds = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |value| format!("{:.3}", value));
+        let mut rows = vec![[tr("Seq"), tr("Segment"), tr("Format"), tr("EXTINF"), tr("Duration"), tr("Start"), tr("Key"), tr("Encryption")].map(str::to_string)];
+        for (segment, status) in self.playlist.segments.iter().zip(&self.statuses)
+        {
+            let media = status.media();
+            let mut uri = segment.uri.clone();
+            if let Some((offset, length)) = segment.byte_range
+            {
+                uri = format!("{} @{}+{}", uri, offset, length);
+            }
+            if segment.discontinuity == true
+            {
+                uri = format!("{} {}", uri, tr("(discontinuity)"));
+            }
+            rows.push([
+                segment.sequence.to_string(),
+                uri,
+                match status
+                {
+                    | SegmentStatus::Remote => tr("remote").to_string(),
+                    | SegmentStatus::Unreadable(_) => tr("unreadable").to_string(),
+                    | SegmentStatus::Dissected { media_type, .. } => media_type.to_string()
+                },
+                format!("{:.3}", segment.duration),
+                seconds(media.and_then(|media| media.duration)),
+                seconds(media.and_then(|media| media.start)),
+                segment.key.as_ref().map_or_else(|| "-".to_string(), |key| key.method.clone()),
+                media.and_then(|media| media.encryption.clone()).unwrap_or_else(|| "-".to_string())
+            ]);
+        }
+        writeln!(f, "{}", tr("Segments:"))?;
+        write_table(f, &rows)?;
+
+        writeln!(f, "\n{}", tr("Findings:"))?;
+        if self.diagnostics.is_empty() == true
+        {
+            writeln!(f, "  {}", tr("The segments match the playlist"))?;
+        }
+        write!(f, "{}", self.diagnostics)
+    }
+}
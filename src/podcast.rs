@@ -0,0 +1,142 @@
+// Podcast report
+//
+// Backs `--podcast`: the episode data podcast clients read, gathered from the normalized metadata
+// of all layers. iTunes writes the same information as ID3v2 frames outside the specification
+// (PCST, WFED, TGID, TDES, TCAT, TKWD) in MP3 files and as `ilst` items (pcst, purl, egid, desc,
+// ldes, catg, keyw) in MP4 files; the chapters come with the link and picture of each chapter.
+
+use crate::{chapters::Chapter, metadata::MediaMetadata};
+
+/// Kind of podcast field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PodcastFieldKind
+{
+    Flag,
+    FeedUrl,
+    EpisodeId,
+    Description,
+    LongDescription,
+    Category,
+    Keywords
+}
+
+/// ID3v2 frame IDs and iTunes item types of each kind
+const FIELD_KEYS: [(&str, PodcastFieldKind); 13] = [
+    ("PCST", PodcastFieldKind::Flag),
+    ("pcst", PodcastFieldKind::Flag),
+    ("WFED", PodcastFieldKind::FeedUrl),
+    ("purl", PodcastFieldKind::FeedUrl),
+    ("TGID", PodcastFieldKind::EpisodeId),
+    ("egid", PodcastFieldKind::EpisodeId),
+    ("TDES", PodcastFieldKind::Description),
+    ("desc", PodcastFieldKind::Description),
+    ("ldes", PodcastFieldKind::LongDescription),
+    ("TCAT", PodcastFieldKind::Category),
+    ("catg", PodcastFieldKind::Category),
+    ("TKWD", PodcastFieldKind::Keywords),
+    ("keyw", PodcastFieldKind::Keywords)
+];
+
+impl PodcastFieldKind
+{
+    /// Kind of the custom metadata field `key`, None for other fields
+    pub fn from_key(key: &str) -> Option<Self>
+    {
+        FIELD_KEYS.iter().find(|(field_key, _)| *field_key == key).map(|&(_, kind)| kind)
+    }
+
+    /// Identifier used in JSON reports
+    pub fn id(self) -> &'static str
+    {
+        match self
+        {
+            | PodcastFieldKind::Flag => "podcast",
+            | PodcastFieldKind::FeedUrl => "feed_url",
+            | PodcastFieldKind::EpisodeId => "episode_id",
+            | PodcastFieldKind::Description => "description",
+            | PodcastFieldKind::LongDescription => "long_description",
+            | PodcastFieldKind::Category => "category",
+            | PodcastFieldKind::Keywords => "keywords"
+        }
+    }
+
+    /// Name shown in the text output
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            | PodcastFieldKind::Flag => "Podcast flag",
+            | PodcastFieldKind::FeedUrl => "Feed URL",
+            | PodcastFieldKind::EpisodeId => "Episode ID",
+            | PodcastFieldKind::Description => "Description",
+            | PodcastFieldKind::LongDescription => "Long description",
+            | PodcastFieldKind::Category => "Category",
+            | PodcastFieldKind::Keywords => "Keywords"
+        }
+    }
+}
+
+/// Podcast field found in the metadata of a file
+#[derive(Debug, Clone)]
+pub struct PodcastField
+{
+    pub kind:   PodcastFieldKind,
+    /// Frame ID or item type holding the value, e.g. "WFED" or "purl"
+    pub source: String,
+    pub value:  String
+}
+
+impl PodcastField
+{
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "kind": self.kind.id(),
+            "source": self.source,
+            "value": self.value
+        })
+    }
+}
+
+/// Podcast fields and chapters of a file
+#[derive(Debug, Clone, Default)]
+pub struct Podcast
+{
+    pub fields:   Vec<PodcastField>,
+    pub chapters: Vec<Chapter>
+}
+
+impl Podcast
+{
+    /// Gather the podcast fields of `metadata`, ordered by kind, and its chapters
+    pub fn collect(metadata: &MediaMetadata) -> Self
+    {
+        let mut fields: Vec<PodcastField> = metadata
+            .custom
+            .iter()
+            .filter_map(|(key, value)| PodcastFieldKind::from_key(key).map(|kind| PodcastField { kind, source: key.clone(), value: value.clone() }))
+            .collect();
+        fields.sort_by_key(|field| field.kind);
+        Self { fields, chapters: metadata.chapters.clone() }
+    }
+
+    /// Structured representation for the JSON report
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "fields": self.fields.iter().map(PodcastField::to_json).collect::<Vec<_>>(),
+            "chapters": self.chapters.iter().map(|chapter| serde_json::json!({
+                "start": chapter.start as f64 / 1000.0,
+                "end": chapter.end as f64 / 1000.0,
+                "title": chapter.title,
+                "url": chapter.url,
+                "image": chapter.image.as_ref().map(|image| serde_json::json!({
+                    "location": image.location,
+                    "mime_type": image.mime_type,
+                    "size": image.data.len()
+                }))
+            })).collect::<Vec<_>>()
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use crate::{diagnostics::Diagnostics, hashes::RegionHash, media_dissector::MediaDissector};
+
+/// Version of the JSON report format described by `schema/report.schema.json`
+///
+/// Within a major version changes are additive only (new optional fields); renaming,
+/// removing or retyping a field requires a new major version.
+pub const SCHEMA_VERSION: &str = "2.0.0";
+
+/// Build the report entry for one dissected region
+pub fn layer(dissector: &dyn MediaDissector, offset: u64, end_offset: u64, content: serde_json::Value) -> serde_json::Value
+{
+    serde_json::json!({
+        "format": dissector.media_type(),
+        "dissector": dissector.name(),
+        "offset": offset,
+        "end_offset": end_offset,
+        "content": content
+    })
+}
+
+/// Build the top-level report document (`region`, `gaps` and `hashes` are only reported when requested)
+pub fn document(
+    file_path: &Path, file_size: u64, region: Option<(u64, u64)>, layers: Vec<serde_json::Value>, gaps: Option<&[(u64, u64)]>, hashes: Option<&[RegionHash]>,
+    diagnostics: &Diagnostics
+) -> serde_json::Value
+{
+    let mut document = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "file": file_path.display().to_string(),
+        "file_size": file_size,
+        "layers": layers,
+        "diagnostics": diagnostics.to_json()
+    });
+
+    if let Some((offset, length)) = region
+    {
+        document["region"] = serde_json::json!({ "offset": offset, "length": length });
+    }
+
+    if let Some(gaps) = gaps
+    {
+        document["gaps"] = gaps.iter().map(|&(start, end)| serde_json::json!({ "offset": start, "size": end - start })).collect();
+    }
+
+    if let Some(hashes) = hashes
+    {
+        document["hashes"] = hashes.iter().map(RegionHash::to_json).collect();
+    }
+
+    document
+}
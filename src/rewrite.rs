@@ -0,0 +1,72 @@
+// Safe rewrite of media files
+//
+// Editing commands never write into the original file: the new content is assembled in a
+// temporary file in the same directory, flushed to disk and renamed over the original, so an
+// interrupted run leaves the original untouched. The temporary file takes over the original's
+// permissions before the rename.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf}
+};
+
+use crate::i18n::tr;
+
+/// Rewrite the file at `path`: `write` receives the original file and the writer of the new content
+pub fn rewrite(path: &Path, write: impl FnOnce(&mut File, &mut dyn Write) -> io::Result<()>) -> io::Result<()>
+{
+    if path == Path::new("-")
+    {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, tr("Standard input cannot be edited")));
+    }
+
+    let mut original = File::open(path)?;
+    let permissions = original.metadata()?.permissions();
+    let temporary = temporary_path(path);
+
+    let result = (|| {
+        let mut output = BufWriter::new(File::create(&temporary)?);
+        write(&mut original, &mut output)?;
+        let output = output.into_inner().map_err(io::IntoInnerError::into_error)?;
+        output.sync_all()?;
+        fs::set_permissions(&temporary, permissions)?;
+        fs::rename(&temporary, path)
+    })();
+
+    if result.is_err() == true
+    {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Replace the bytes `start..end` of the file at `path` with `replacement`, keeping everything else
+pub fn replace_range(path: &Path, start: u64, end: u64, replacement: &[u8]) -> io::Result<()>
+{
+    rewrite(path, |original, output| {
+        copy_range(original, output, 0, start)?;
+        output.write_all(replacement)?;
+        let length = original.metadata()?.len();
+        copy_range(original, output, end, length.saturating_sub(end))
+    })
+}
+
+/// Copy `length` bytes starting at `offset` of `input` to `output`
+pub fn copy_range(input: &mut File, output: &mut dyn Write, offset: u64, length: u64) -> io::Result<()>
+{
+    input.seek(SeekFrom::Start(offset))?;
+    let copied = io::copy(&mut input.take(length), output)?;
+    if copied < length
+    {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, tr("The file ended while it was being copied")));
+    }
+    Ok(())
+}
+
+/// Hidden file next to `path` that receives the new content
+fn temporary_path(path: &Path) -> PathBuf
+{
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!(".{}.the-drill-tmp", name))
+}
@@ -0,0 +1,218 @@
+// Summary report
+//
+// Compact overview of a file for the `summary` command: one line per ISOBMFF track with the
+// facts otherwise spread over tkhd, mdhd, hdlr and stsd, and the key fields of ID3v2 tags
+// aggregated from their frames (see `schema/summary.schema.json`).
+
+use std::{fmt, path::Path};
+
+use crate::{
+    chapters::format_time,
+    ffprobe::ProbeStream,
+    i18n::{tr, tr_fmt},
+    report::SCHEMA_VERSION
+};
+
+/// One track of a media file
+#[derive(Debug, Clone)]
+pub struct TrackSummary
+{
+    pub track_id: u32,
+    /// Three-letter ISO 639-2/T language code
+    pub language: Option<String>,
+    /// Codec, picture size, audio format and duration in ffprobe terms
+    pub stream:   ProbeStream
+}
+
+impl TrackSummary
+{
+    /// Codec column: four-character code and ffmpeg codec name (e.g. "avc1 (h264)")
+    fn codec(&self) -> String
+    {
+        match (&self.stream.codec_tag_string, self.stream.codec_name)
+        {
+            | (Some(tag), Some(name)) => format!("{} ({})", tag, name),
+            | (Some(tag), None) => tag.clone(),
+            | (None, _) => "-".to_string()
+        }
+    }
+
+    /// Format column: picture size of video tracks, sample rate and channels of audio tracks
+    fn media_format(&self) -> String
+    {
+        match (self.stream.width, self.stream.height, self.stream.sample_rate, self.stream.channels)
+        {
+            | (Some(width), Some(height), ..) => format!("{}x{}", width, height),
+            | (_, _, Some(sample_rate), Some(channels)) => tr_fmt("{} Hz, {} channels", &[&sample_rate, &channels]),
+            | (_, _, Some(sample_rate), None) => format!("{} Hz", sample_rate),
+            | _ => "-".to_string()
+        }
+    }
+
+    /// Structured representation for the JSON summary
+    fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "track_id": self.track_id,
+            "type": self.stream.codec_type,
+            "codec": self.stream.codec_tag_string,
+            "codec_name": self.stream.codec_name,
+            "duration": self.stream.duration,
+            "language": self.language,
+            "width": self.stream.width,
+            "height": self.stream.height,
+            "sample_rate": self.stream.sample_rate,
+            "channels": self.stream.channels
+        })
+    }
+}
+
+/// Key fields of a tag, aggregated from its frames
+#[derive(Debug, Clone, Default)]
+pub struct TagSummary
+{
+    /// Tag format and version (e.g. "ID3v2.4")
+    pub version:         String,
+    pub title:           Option<String>,
+    pub artist:          Option<String>,
+    pub album:           Option<String>,
+    pub year:            Option<String>,
+    /// Genre with numeric ID3v1 references resolved to their names
+    pub genre:           Option<String>,
+    pub track:           Option<String>,
+    /// Duration in seconds
+    pub duration:        Option<f64>,
+    /// Where the duration comes from ("TLEN" or "MPEG audio")
+    pub duration_source: Option<&'static str>,
+    /// Number of embedded pictures, including chapter images
+    pub pictures:        usize,
+    /// Total size of the embedded pictures in bytes
+    pub picture_bytes:   u64,
+    pub chapters:        usize
+}
+
+impl TagSummary
+{
+    /// Structured representation for the JSON summary
+    fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "version": self.version,
+            "title": self.title,
+            "artist": self.artist,
+            "album": self.album,
+            "year": self.year,
+            "genre": self.genre,
+            "track": self.track,
+            "duration": self.duration,
+            "duration_source": self.duration_source,
+            "pictures": self.pictures,
+            "picture_bytes": self.picture_bytes,
+            "chapters": self.chapters
+        })
+    }
+
+    /// Label and value rows of the text output
+    fn rows(&self) -> Vec<[String; 2]>
+    {
+        let text = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        let duration = match (self.duration, self.duration_source)
+        {
+            | (Some(seconds), Some(source)) => format!("{} ({})", format_time((seconds * 1000.0).round() as u64), source),
+            | (Some(seconds), None) => format_time((seconds * 1000.0).round() as u64),
+            | _ => "-".to_string()
+        };
+
+        vec![
+            [tr("Title").to_string(), text(&self.title)],
+            [tr("Artist").to_string(), text(&self.artist)],
+            [tr("Album").to_string(), text(&self.album)],
+            [tr("Year").to_string(), text(&self.year)],
+            [tr("Genre").to_string(), text(&self.genre)],
+            [tr("Track").to_string(), text(&self.track)],
+            [tr("Duration").to_string(), duration],
+            [tr("Pictures").to_string(), tr_fmt("{} ({} bytes)", &[&self.pictures, &self.picture_bytes])],
+            [tr("Chapters").to_string(), self.chapters.to_string()],
+        ]
+    }
+}
+
+/// Everything the summary command reports for a file
+#[derive(Debug, Clone, Default)]
+pub struct Summary
+{
+    pub tracks: Vec<TrackSummary>,
+    pub tags:   Vec<TagSummary>
+}
+
+impl Summary
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.tracks.is_empty() == true && self.tags.is_empty() == true
+    }
+
+    /// JSON document of the summary command (see `schema/summary.schema.json`)
+    pub fn to_json(&self, file_path: &Path) -> serde_json::Value
+    {
+        serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "file": file_path.display().to_string(),
+            "tracks": self.tracks.iter().map(TrackSummary::to_json).collect::<Vec<_>>(),
+            "tags": self.tags.iter().map(TagSummary::to_json).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for Summary
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.tracks.is_empty() == false
+        {
+            let mut rows = vec![[tr("ID"), tr("Type"), tr("Codec"), tr("Duration"), tr("Language"), tr("Format")].map(str::to_string)];
+            for track in &self.tracks
+            {
+                let duration = track.stream.duration.map(|seconds| format_time((seconds * 1000.0).round() as u64)).unwrap_or_else(|| "-".to_string());
+                rows.push([
+                    track.track_id.to_string(),
+                    tr(track.stream.codec_type).to_string(),
+                    track.codec(),
+                    duration,
+                    track.language.clone().unwrap_or_else(|| "-".to_string()),
+                    track.media_format()
+                ]);
+            }
+
+            writeln!(f, "{}", tr("Tracks:"))?;
+            write_table(f, &rows)?;
+        }
+
+        for tag in &self.tags
+        {
+            writeln!(f, "{}", tr_fmt("{} tag:", &[&tag.version]))?;
+            write_table(f, &tag.rows())?;
+        }
+        Ok(())
+    }
+}
+
+/// Write rows as left-aligned columns, each as wide as its widest cell
+pub fn write_table<const N: usize>(f: &mut fmt::Formatter<'_>, rows: &[[String; N]]) -> fmt::Result
+{
+    let mut widths = [0usize; N];
+    for row in rows
+    {
+        for (width, cell) in widths.iter_mut().zip(row)
+        {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in rows
+    {
+        let cells: Vec<String> = row.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        writeln!(f, "  {}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+// Timed ID3 metadata
+//
+// HLS carries timed metadata as ID3v2 tags: in the PES packets of a transport stream PID with
+// stream type 0x15 or the "ID3 " registration, and in emsg boxes of fragmented MP4 with the
+// `https://aomedia.org/emsg/ID3` scheme. Packed audio segments start with a tag whose PRIV frame
+// `com.apple.streaming.transportStreamTimestamp` holds the 33-bit MPEG-TS timestamp of the first
+// sample; ad markers and other cues travel in TXXX and text frames.
+
+use std::{fmt, io::Cursor};
+
+use crate::{
+    i18n::tr_fmt,
+    id3v2::{
+        frame::{Id3v2Frame, Id3v2FrameContent},
+        tag::Id3v2Tag
+    }
+};
+
+/// Owner identifier of the PRIV frame holding the MPEG-TS timestamp of an HLS segment
+pub const TRANSPORT_STREAM_TIMESTAMP_OWNER: &str = "com.apple.streaming.transportStreamTimestamp";
+
+/// Scheme ID URI of emsg boxes carrying an ID3v2 tag
+pub const EMSG_ID3_SCHEME: &str = "https://aomedia.org/emsg/ID3";
+
+/// Ticks per second of MPEG-TS timestamps
+const TIMESTAMP_FREQUENCY: f64 = 90000.0;
+
+/// 33-bit MPEG-TS timestamp of a transportStreamTimestamp PRIV payload (eight bytes, big endian)
+pub fn transport_stream_timestamp(data: &[u8]) -> Option<u64>
+{
+    let bytes: [u8; 8] = data.try_into().ok()?;
+    Some(u64::from_be_bytes(bytes) & 0x1_FFFF_FFFF)
+}
+
+/// MPEG-TS timestamp with seconds, e.g. "900000 (10.000 s)"
+pub fn format_timestamp(ticks: u64) -> String
+{
+    format!("{} ({:.3} s)", ticks, ticks as f64 / TIMESTAMP_FREQUENCY)
+}
+
+/// One-line summary of a frame of a timed ID3 tag, e.g. "TXXX: CUE = ad-start" or "PRIV: com.apple...: 900000 (10.000 s)"
+pub fn frame_summary(frame: &Id3v2Frame) -> String
+{
+    let value = match frame.content
+    {
+        | Some(Id3v2FrameContent::Private(ref private)) => match transport_stream_timestamp(&private.data)
+        {
+            | Some(ticks) if private.owner_identifier == TRANSPORT_STREAM_TIMESTAMP_OWNER => format!("{}: {}", private.owner_identifier, format_timestamp(ticks)),
+            | _ => tr_fmt("{}: {} bytes", &[&private.owner_identifier, &private.data.len()])
+        },
+        | Some(Id3v2FrameContent::UserText(ref user_text)) => format!("{} = {}", user_text.description, user_text.value),
+        | _ => match frame.get_text()
+        {
+            | Some(text) => text.to_string(),
+            | None => tr_fmt("{} bytes", &[&frame.size])
+        }
+    };
+    format!("{}: {}", frame.id, value)
+}
+
+/// ID3v2 tag of timed metadata with the time it applies to
+#[derive(Debug, Clone)]
+pub struct TimedId3
+{
+    /// File offset of the transport packet or box the tag was found in
+    pub offset: u64,
+    /// Presentation time stamp of the PES packet in 90 kHz units
+    pub pts:    Option<u64>,
+    pub tag:    Id3v2Tag
+}
+
+impl TimedId3
+{
+    /// Parse the ID3v2 tag at the start of `data`, None if `data` does not start with a tag header
+    pub fn parse(offset: u64, pts: Option<u64>, data: &[u8]) -> Result<Option<Self>, String>
+    {
+        let tag = Id3v2Tag::read(&mut Cursor::new(data.to_vec()), 0).map_err(|e| e.to_string())?;
+        Ok(tag.map(|tag| Self { offset, pts, tag }))
+    }
+
+    /// MPEG-TS timestamp of the transportStreamTimestamp PRIV frame
+    pub fn transport_stream_timestamp(&self) -> Option<u64>
+    {
+        self.tag.frames.iter().find_map(|frame| match frame.content
+        {
+            | Some(Id3v2FrameContent::Private(ref private)) if private.owner_identifier == TRANSPORT_STREAM_TIMESTAMP_OWNER =>
+                transport_stream_timestamp(&private.data),
+            | _ => None
+        })
+    }
+
+    /// Structured representation for JSON reports
+    pub fn to_json(&self) -> serde_json::Value
+    {
+        serde_json::json!({
+            "offset": self.offset,
+            "pts": self.pts,
+            "version": format!("2.{}.{}", self.tag.major_version, self.tag.minor_version),
+            "transport_stream_timestamp": self.transport_stream_timestamp(),
+            "frames": self.tag.frames.iter().map(|frame| serde_json::json!({
+                "id": frame.id,
+                "summary": frame_summary(frame)
+            })).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl fmt::Display for TimedId3
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let frames = tr_fmt("ID3v2.{} tag with {} frames", &[&self.tag.major_version, &self.tag.frames.len()]);
+        match self.pts
+        {
+            | Some(pts) => writeln!(f, "{}", tr_fmt("At 0x{}, PTS {}: {}", &[&format!("{:08X}", self.offset), &format_timestamp(pts), &frames]))?,
+            | None => writeln!(f, "{}", tr_fmt("At 0x{}: {}", &[&format!("{:08X}", self.offset), &frames]))?
+        }
+        for frame in &self.tag.frames
+        {
+            writeln!(f, "  {}", frame_summary(frame))?;
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,207 @@
-use std::fs::File;
+use std::io::{Read, SeekFrom, Write};
 
-use crate::{cli::DissectOptions, media_dissector::MediaDissector};
+use crate::{
+    ape_tag::{ApeTag, dissect_ape_tag},
+    color::Colorize,
+    coverage::Coverage,
+    diagnostics::Diagnostics,
+    ffprobe::ProbeFormat,
+    i18n::{tr, tr_fmt},
+    id3v1::{Id3v1Tag, dissect_id3v1_trailer},
+    lyrics3::{Lyrics3Tag, dissect_lyrics3_tag},
+    media_dissector::{Confidence, MediaDissector},
+    media_source::MediaSource,
+    options::DissectOptions
+};
+
+/// Number of leading bytes searched for magic signatures and printable strings
+const HEADER_WINDOW: usize = 64 * 1024;
+
+/// Number of bytes sampled for the entropy estimate
+const ENTROPY_SAMPLE: u64 = 1024 * 1024;
+
+/// Number of leading bytes shown in the hexdump
+const HEXDUMP_BYTES: usize = 256;
+
+/// Minimum length of a printable ASCII run to be reported as a string
+const MIN_STRING_LENGTH: usize = 6;
+
+/// Maximum number of strings listed
+const MAX_STRINGS: usize = 32;
+
+/// Known file signature (magic bytes at a fixed offset)
+struct MagicSignature
+{
+    offset:      usize,
+    bytes:       &'static [u8],
+    description: &'static str
+}
+
+/// Catalog of signatures for formats without a dedicated dissector (or with an unrecognized variant)
+const MAGIC_CATALOG: &[MagicSignature] = &[
+    MagicSignature { offset: 0, bytes: b"ID3", description: "ID3v2 tag (unsupported version)" },
+    MagicSignature { offset: 4, bytes: b"ftyp", description: "ISOBMFF file type box (unrecognized brand)" },
+    MagicSignature { offset: 4, bytes: b"moov", description: "QuickTime movie box without ftyp" },
+    MagicSignature { offset: 4, bytes: b"mdat", description: "QuickTime media data box without ftyp" },
+    MagicSignature { offset: 4, bytes: b"wide", description: "QuickTime wide placeholder box" },
+    MagicSignature { offset: 0, bytes: b"fLaC", description: "FLAC audio" },
+    MagicSignature { offset: 0, bytes: b"OggS", description: "Ogg container page" },
+    MagicSignature { offset: 0, bytes: &[0x1A, 0x45, 0xDF, 0xA3], description: "EBML header (Matroska/WebM)" },
+    MagicSignature { offset: 0, bytes: b"RIFF", description: "RIFF container" },
+    MagicSignature { offset: 8, bytes: b"WAVE", description: "RIFF WAVE audio" },
+    MagicSignature { offset: 8, bytes: b"AVI ", description: "RIFF AVI video" },
+    MagicSignature { offset: 8, bytes: b"WEBP", description: "RIFF WebP image" },
+    MagicSignature { offset: 0, bytes: b"FORM", description: "IFF container (AIFF/AIFC)" },
+    MagicSignature { offset: 0, bytes: b"MAC ", description: "Monkey's Audio" },
+    MagicSignature { offset: 0, bytes: b"wvpk", description: "WavPack audio block" },
+    MagicSignature { offset: 0, bytes: b"MPCK", description: "Musepack SV8 audio" },
+    MagicSignature { offset: 0, bytes: b"MP+", description: "Musepack SV7 audio" },
+    MagicSignature { offset: 0, bytes: b"TTA1", description: "True Audio (TTA)" },
+    MagicSignature { offset: 0, bytes: b"#!AMR", description: "AMR audio" },
+    MagicSignature { offset: 0, bytes: b"DSD ", description: "DSF (DSD stream file)" },
+    MagicSignature { offset: 0, bytes: b"FRM8", description: "DSDIFF audio" },
+    MagicSignature { offset: 0, bytes: &[0x00, 0x00, 0x01, 0xBA], description: "MPEG program stream pack header" },
+    MagicSignature { offset: 0, bytes: b"APETAGEX", description: "APE tag" },
+    MagicSignature { offset: 0, bytes: b"LYRICSBEGIN", description: "Lyrics3 tag" },
+    MagicSignature { offset: 0, bytes: &[0xFF, 0xD8, 0xFF], description: "JPEG image" },
+    MagicSignature { offset: 0, bytes: &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A], description: "PNG image" },
+    MagicSignature { offset: 0, bytes: b"GIF8", description: "GIF image" },
+    MagicSignature { offset: 0, bytes: b"%PDF", description: "PDF document" },
+    MagicSignature { offset: 0, bytes: &[b'P', b'K', 0x03, 0x04], description: "ZIP archive" },
+    MagicSignature { offset: 0, bytes: &[0x1F, 0x8B], description: "gzip compressed data" },
+    MagicSignature { offset: 0, bytes: &[0x7F, b'E', b'L', b'F'], description: "ELF executable" }
+];
 
 /// Fallback dissector for unknown file formats
 pub struct UnknownDissector;
 
+/// Findings gathered from a region no dissector recognized
+struct UnknownAnalysis
+{
+    size:        u64,
+    entropy:     f64,
+    sample_size: usize,
+    window:      Vec<u8>,
+    signatures:  Vec<(u64, &'static str)>,
+    strings:     Vec<(u64, String)>
+}
+
+impl UnknownDissector
+{
+    /// Read the region starting at `start_offset` and gather size, entropy, signatures and strings
+    fn analyze(file: &mut dyn MediaSource, start_offset: u64) -> Result<UnknownAnalysis, Box<dyn std::error::Error>>
+    {
+        let file_size = file.size()?;
+
+        // Read a sample for entropy estimation (the header window is its prefix)
+        let mut sample = Vec::new();
+        file.seek(SeekFrom::Start(start_offset))?;
+        (&mut *file).take(ENTROPY_SAMPLE).read_to_end(&mut sample)?;
+        let window = sample[..sample.len().min(HEADER_WINDOW)].to_vec();
+
+        let signatures = Self::find_signatures(&window).into_iter().map(|(position, description)| (start_offset + position as u64, description)).collect();
+        let strings = Self::extract_strings(&window).into_iter().map(|(position, string)| (start_offset + position as u64, string)).collect();
+
+        Ok(UnknownAnalysis { size: file_size - start_offset, entropy: Self::entropy(&sample), sample_size: sample.len(), window, signatures, strings })
+    }
+
+    /// Estimate Shannon entropy in bits per byte
+    fn entropy(data: &[u8]) -> f64
+    {
+        if data.is_empty() == true
+        {
+            return 0.0;
+        }
+
+        let mut counts = [0u64; 256];
+        for &byte in data
+        {
+            counts[byte as usize] += 1;
+        }
+
+        let total = data.len() as f64;
+        counts.iter().filter(|&&count| count > 0).map(|&count| count as f64 / total).map(|p| -p * p.log2()).sum()
+    }
+
+    /// Describe what an entropy value typically indicates
+    fn describe_entropy(entropy: f64) -> &'static str
+    {
+        if entropy > 7.9
+        {
+            "compressed or encrypted data"
+        }
+        else if entropy > 6.5
+        {
+            "mixed binary data"
+        }
+        else if entropy > 4.5
+        {
+            "structured binary data"
+        }
+        else
+        {
+            "text or sparse data"
+        }
+    }
+
+    /// Find catalog signatures at their fixed offset and anywhere else in the window
+    fn find_signatures(window: &[u8]) -> Vec<(usize, &'static str)>
+    {
+        let mut matches = Vec::new();
+
+        for signature in MAGIC_CATALOG
+        {
+            if window.len() >= signature.offset + signature.bytes.len() && &window[signature.offset..signature.offset + signature.bytes.len()] == signature.bytes
+            {
+                matches.push((signature.offset, signature.description));
+            }
+            else if signature.bytes.len() >= 4 &&
+                let Some(position) = window.windows(signature.bytes.len()).position(|candidate| candidate == signature.bytes)
+            {
+                // Embedded signature (e.g. a container behind unknown leading data)
+                matches.push((position, signature.description));
+            }
+        }
+
+        matches.sort_by_key(|&(position, _)| position);
+        matches
+    }
+
+    /// Extract printable ASCII runs with their offsets
+    fn extract_strings(data: &[u8]) -> Vec<(usize, String)>
+    {
+        let mut strings = Vec::new();
+        let mut start = None;
+
+        for (i, &byte) in data.iter().enumerate()
+        {
+            let printable = (0x20..=0x7E).contains(&byte);
+            match (printable, start)
+            {
+                | (true, None) => start = Some(i),
+                | (false, Some(run_start)) =>
+                {
+                    if i - run_start >= MIN_STRING_LENGTH
+                    {
+                        strings.push((run_start, String::from_utf8_lossy(&data[run_start..i]).to_string()));
+                    }
+                    start = None;
+                }
+                | _ =>
+                {}
+            }
+        }
+
+        if let Some(run_start) = start &&
+            data.len() - run_start >= MIN_STRING_LENGTH
+        {
+            strings.push((run_start, String::from_utf8_lossy(&data[run_start..]).to_string()));
+        }
+
+        strings
+    }
+}
+
 impl MediaDissector for UnknownDissector
 {
     fn media_type(&self) -> &'static str
@@ -12,15 +209,118 @@ fn media_type(&self) -> &'static str
         "Unknown"
     }
 
-    fn dissect_with_options(&self, _file: &mut File, _options: &DissectOptions) -> Result<(), Box<dyn std::error::Error>>
+    fn dissect_with_options(
+        &self, file: &mut dyn MediaSource, start_offset: u64, options: &DissectOptions, output: &mut dyn Write, diagnostics: &mut Diagnostics
+    ) -> Result<u64, Box<dyn std::error::Error>>
+    {
+        let analysis = Self::analyze(file, start_offset)?;
+
+        writeln!(output, "{}", tr("Unknown format - no suitable dissector available"))?;
+
+        if options.show_header == true
+        {
+            writeln!(output, "\n{}", tr("File Summary:").bright_cyan().bold())?;
+            writeln!(output, "  {}", tr_fmt("Size: {} bytes", &[&analysis.size]))?;
+            writeln!(
+                output,
+                "  {}",
+                tr_fmt("Entropy: {} bits/byte over first {} bytes ({})", &[
+                    &format!("{:.3}", analysis.entropy),
+                    &analysis.sample_size,
+                    &tr(Self::describe_entropy(analysis.entropy))
+                ])
+            )?;
+
+            if analysis.signatures.is_empty() == true
+            {
+                writeln!(output, "  {}", tr_fmt("Magic bytes: no known signature in first {} bytes", &[&analysis.window.len()]))?;
+            }
+            else
+            {
+                writeln!(output, "  {}", tr("Magic bytes:"))?;
+                for (offset, description) in &analysis.signatures
+                {
+                    writeln!(output, "    0x{:08X}: {}", offset, tr(description))?;
+                }
+            }
+        }
+
+        if options.show_data == true
+        {
+            writeln!(output, "\n{}", tr_fmt("Printable Strings (>= {} chars, first {} bytes):", &[&MIN_STRING_LENGTH, &analysis.window.len()]).bright_cyan().bold())?;
+            if analysis.strings.is_empty() == true
+            {
+                writeln!(output, "  {}", tr("(none)"))?;
+            }
+            for (offset, string) in analysis.strings.iter().take(MAX_STRINGS)
+            {
+                writeln!(output, "  0x{:08X}: \"{}\"", offset, string)?;
+            }
+            if analysis.strings.len() > MAX_STRINGS
+            {
+                writeln!(output, "  {}", tr_fmt("... {} more", &[&(analysis.strings.len() - MAX_STRINGS)]))?;
+            }
+
+            writeln!(output, "\n{}", tr("Leading Bytes:").bright_cyan().bold())?;
+            let hexdump = crate::hexdump::format_hexdump_limited(&analysis.window, start_offset as usize, Some(HEXDUMP_BYTES));
+            for line in hexdump.lines()
+            {
+                writeln!(output, "  {}", line)?;
+            }
+        }
+
+        dissect_ape_tag(file, options, output, diagnostics)?;
+        dissect_lyrics3_tag(file, options, output, diagnostics)?;
+        dissect_id3v1_trailer(file, options, output)?;
+
+        Ok(start_offset + analysis.size)
+    }
+
+    fn dissect_to_json(
+        &self, file: &mut dyn MediaSource, start_offset: u64, _options: &DissectOptions, diagnostics: &mut Diagnostics
+    ) -> Result<(serde_json::Value, u64), Box<dyn std::error::Error>>
+    {
+        let analysis = Self::analyze(file, start_offset)?;
+
+        let mut value = serde_json::json!({
+            "size": analysis.size,
+            "entropy": analysis.entropy,
+            "entropy_sample_size": analysis.sample_size,
+            "signatures": analysis.signatures.iter().map(|(offset, description)| serde_json::json!({ "offset": offset, "description": description })).collect::<Vec<_>>(),
+            "strings": analysis.strings.iter().take(MAX_STRINGS).map(|(offset, string)| serde_json::json!({ "offset": offset, "value": string })).collect::<Vec<_>>()
+        });
+        if let Some(ape) = ApeTag::read(file)?
+        {
+            ape.check_item_count(diagnostics);
+            value["ape"] = ape.to_json();
+        }
+        if let Some(lyrics3) = Lyrics3Tag::read(file)?
+        {
+            lyrics3.check_fields(diagnostics);
+            value["lyrics3"] = lyrics3.to_json();
+        }
+        if let Some(id3v1) = Id3v1Tag::read(file)?
+        {
+            value["id3v1"] = id3v1.to_json();
+        }
+
+        Ok((value, start_offset + analysis.size))
+    }
+
+    fn probe(&self, file: &mut dyn MediaSource, start_offset: u64, _diagnostics: &mut Diagnostics) -> Result<(Option<ProbeFormat>, u64), Box<dyn std::error::Error>>
+    {
+        Ok((None, file.size()?.max(start_offset)))
+    }
+
+    fn add_coverage(&self, file: &mut dyn MediaSource, start_offset: u64, _coverage: &mut Coverage) -> Result<u64, Box<dyn std::error::Error>>
     {
-        println!("Unknown format - no suitable dissector available");
-        Ok(())
+        // Nothing in an unknown region is recognized
+        Ok(file.size()?.max(start_offset))
     }
 
-    fn can_handle(&self, _header: &[u8]) -> bool
+    fn sniff(&self, _header: &[u8], _remaining: u64) -> Confidence
     {
-        true // Always can handle as fallback
+        Confidence::Low // Always can handle as fallback
     }
 
     fn name(&self) -> &'static str
@@ -0,0 +1,216 @@
+// Dissection events
+//
+// Applications building indexes, editors or GUIs on top of the parsers implement
+// `DissectionVisitor` and pass it to `Dissector::visit` instead of scraping the text output. Each
+// layer is walked in file order: the boxes of ISOBMFF files with their nesting, the frames of
+// ID3v2 tags with the sub-frames of chapters, the data held by them, and the findings of the
+// parsers once the layer is read. Layers in formats without a structure walk (APE, Matroska,
+// MPEG audio, ...) report their start, end and findings. `Dissector::visit_text` sends the text
+// output of each layer as `on_text` events between the layer events instead; `TextPrinter` writes
+// them as the regular text output, `OutlinePrinter` writes the structure events as an indented
+// outline of the file.
+
+use std::io::{self, Write};
+
+use crate::{
+    diagnostics::Diagnostic,
+    i18n::{tr, tr_fmt},
+    id3v2::frame::Id3v2Frame,
+    isobmff::r#box::IsobmffBox,
+    media_dissector::MediaDissector
+};
+
+/// Receiver of the structures found while dissecting a file; all methods default to doing nothing
+pub trait DissectionVisitor
+{
+    /// A layer handled by `dissector` starts at `offset`
+    fn on_layer_start(&mut self, _dissector: &dyn MediaDissector, _offset: u64)
+    {
+    }
+
+    /// The layer handled by `dissector` ends just before `end_offset`
+    fn on_layer_end(&mut self, _dissector: &dyn MediaDissector, _end_offset: u64)
+    {
+    }
+
+    /// An ISOBMFF box at nesting level `depth` starts; its children follow before `on_box_end`
+    fn on_box_start(&mut self, _isobmff_box: &IsobmffBox, _depth: usize)
+    {
+    }
+
+    /// All children of an ISOBMFF box were visited
+    fn on_box_end(&mut self, _isobmff_box: &IsobmffBox, _depth: usize)
+    {
+    }
+
+    /// An ID3v2 frame at file offset `offset` (None where tag unsynchronisation or frame formats break the mapping);
+    /// sub-frames of chapters have depth 1
+    fn on_frame(&mut self, _frame: &Id3v2Frame, _offset: Option<u64>, _depth: usize)
+    {
+    }
+
+    /// Data of the box or frame just started, located at file offset `offset` (large media data is not read)
+    fn on_data(&mut self, _offset: u64, _data: &[u8])
+    {
+    }
+
+    /// A note or warning of the parser for the current layer
+    fn on_warning(&mut self, _diagnostic: &Diagnostic)
+    {
+    }
+
+    /// An error of the parser for the current layer; defaults to `on_warning`
+    fn on_error(&mut self, diagnostic: &Diagnostic)
+    {
+        self.on_warning(diagnostic);
+    }
+
+    /// A piece of the text output of the current layer (only sent by `Dissector::visit_text`)
+    fn on_text(&mut self, _text: &[u8])
+    {
+    }
+}
+
+/// Writer sending everything written to it to a visitor as `on_text` events
+pub struct TextEvents<'a>
+{
+    visitor: &'a mut dyn DissectionVisitor
+}
+
+impl<'a> TextEvents<'a>
+{
+    pub fn new(visitor: &'a mut dyn DissectionVisitor) -> Self
+    {
+        Self { visitor }
+    }
+}
+
+impl Write for TextEvents<'_>
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize>
+    {
+        self.visitor.on_text(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+/// Visitor writing the text output of the layers to an output, with a header in front of each layer after the first
+pub struct TextPrinter<W: Write>
+{
+    output: W,
+    /// First write error; nothing is written after it
+    result: io::Result<()>
+}
+
+impl<W: Write> TextPrinter<W>
+{
+    /// Create a printer writing to `output`
+    pub fn new(output: W) -> Self
+    {
+        Self { output, result: Ok(()) }
+    }
+
+    /// Finish the output, returning the first write error
+    pub fn finish(self) -> io::Result<()>
+    {
+        self.result
+    }
+
+    /// Write `text` unless an earlier write failed
+    fn write(&mut self, text: &[u8])
+    {
+        if self.result.is_ok() == true
+        {
+            self.result = self.output.write_all(text);
+        }
+    }
+}
+
+impl<W: Write> DissectionVisitor for TextPrinter<W>
+{
+    fn on_layer_start(&mut self, dissector: &dyn MediaDissector, offset: u64)
+    {
+        // The header of the first layer is printed by the caller, together with the file name
+        if offset > 0
+        {
+            let header = tr_fmt("Detected format at offset {}: {} ({})", &[&format!("0x{:08X}", offset), &dissector.media_type(), &tr(dissector.name())]);
+            self.write(format!("\n{}\n", header).as_bytes());
+        }
+    }
+
+    fn on_text(&mut self, text: &[u8])
+    {
+        self.write(text);
+    }
+}
+
+/// Visitor writing layers, boxes, frames and findings to an output as an indented outline
+pub struct OutlinePrinter<W: Write>
+{
+    output: W,
+    /// First write error; nothing is written after it
+    result: io::Result<()>
+}
+
+impl<W: Write> OutlinePrinter<W>
+{
+    /// Create a printer writing to `output`
+    pub fn new(output: W) -> Self
+    {
+        Self { output, result: Ok(()) }
+    }
+
+    /// Finish the outline, returning the first write error
+    pub fn finish(self) -> io::Result<()>
+    {
+        self.result
+    }
+
+    /// Write a line unless an earlier write failed
+    fn write_line(&mut self, line: &str)
+    {
+        if self.result.is_ok() == true
+        {
+            self.result = writeln!(self.output, "{}", line);
+        }
+    }
+
+    /// Write a structure of `size` bytes at nesting level `depth` below its layer
+    fn write_structure(&mut self, name: &str, offset: Option<u64>, size: u64, depth: usize)
+    {
+        let line = match offset
+        {
+            | Some(offset) => tr_fmt("{} at {} ({} bytes)", &[&name, &format!("0x{:08X}", offset), &size]),
+            | None => tr_fmt("{} ({} bytes)", &[&name, &size])
+        };
+        self.write_line(&format!("{}{}", "  ".repeat(depth + 1), line));
+    }
+}
+
+impl<W: Write> DissectionVisitor for OutlinePrinter<W>
+{
+    fn on_layer_start(&mut self, dissector: &dyn MediaDissector, offset: u64)
+    {
+        self.write_line(&tr_fmt("{} ({}) at {}", &[&dissector.media_type(), &tr(dissector.name()), &format!("0x{:08X}", offset)]));
+    }
+
+    fn on_box_start(&mut self, isobmff_box: &IsobmffBox, depth: usize)
+    {
+        self.write_structure(&isobmff_box.box_type, Some(isobmff_box.offset), isobmff_box.size, depth);
+    }
+
+    fn on_frame(&mut self, frame: &Id3v2Frame, offset: Option<u64>, depth: usize)
+    {
+        self.write_structure(&frame.id, offset, 10 + frame.size as u64, depth);
+    }
+
+    fn on_warning(&mut self, diagnostic: &Diagnostic)
+    {
+        self.write_line(&format!("  {}", diagnostic));
+    }
+}
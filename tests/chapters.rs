@@ -0,0 +1,33 @@
+// Chapter export formats
+
+use std::path::Path;
+
+use the_drill::chapters::{Chapter, ChapterFormat, export};
+
+/// Chapter from `start` to `end` milliseconds without link or picture
+fn chapter(start: u64, end: u64, title: &str) -> Chapter
+{
+    Chapter { start, end, title: title.to_string(), url: None, image: None }
+}
+
+#[test]
+fn cue_sheet_titles_stay_on_one_line()
+{
+    let chapters = [chapter(0, 1000, "Intro\r\nwith \"quotes\""), chapter(1000, 2000, "Second\nline")];
+    let cue = export(&chapters, ChapterFormat::Cue, Path::new("episode \"1\".mp3"));
+
+    assert_eq!(
+        cue,
+        "FILE \"episode '1'.mp3\" MP3\n  TRACK 01 AUDIO\n    TITLE \"Intro  with 'quotes'\"\n    INDEX 01 00:00:00\n  TRACK 02 AUDIO\n    TITLE \"Second line\"\n    \
+         INDEX 01 00:01:00\n"
+    );
+}
+
+#[test]
+fn webvtt_titles_stay_in_their_cue()
+{
+    let chapters = [chapter(0, 1000, "Intro\r\n\r\nwith --> arrow"), chapter(1000, 2000, "\nSecond\n\nline\n")];
+    let webvtt = export(&chapters, ChapterFormat::Webvtt, Path::new("episode.mp3"));
+
+    assert_eq!(webvtt, "WEBVTT\n\n1\n00:00:00.000 --> 00:00:01.000\nIntro with -> arrow\n\n2\n00:00:01.000 --> 00:00:02.000\nSecond line\n");
+}
@@ -0,0 +1,47 @@
+// Fixture builders shared by the integration tests
+
+#![allow(dead_code)]
+
+/// ID3v2.3 frame with a plain 32-bit size
+pub fn frame_v3(id: &str, data: &[u8]) -> Vec<u8>
+{
+    let mut bytes = id.as_bytes().to_vec();
+    bytes.extend((data.len() as u32).to_be_bytes());
+    bytes.extend([0, 0]);
+    bytes.extend(data);
+    bytes
+}
+
+/// ID3v2.3 tag holding `frames` without padding
+pub fn tag_v3(frames: &[u8]) -> Vec<u8>
+{
+    let size = frames.len() as u32;
+    let mut bytes = b"ID3\x03\x00\x00".to_vec();
+    bytes.extend([(size >> 21) as u8 & 0x7F, (size >> 14) as u8 & 0x7F, (size >> 7) as u8 & 0x7F, size as u8 & 0x7F]);
+    bytes.extend(frames);
+    bytes
+}
+
+/// ISOBMFF box of type `box_type` holding `content`
+pub fn isobmff_box(box_type: &[u8; 4], content: &[u8]) -> Vec<u8>
+{
+    let mut bytes = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+    bytes.extend(box_type);
+    bytes.extend(content);
+    bytes
+}
+
+/// ADTS frame (MPEG-4 AAC LC, 44.1 kHz, stereo, no CRC) with `payload_size` bytes of silence
+pub fn adts_frame(payload_size: usize) -> Vec<u8>
+{
+    let length = payload_size + 7;
+    let mut bytes = vec![0xFF, 0xF1, 0x50, 0x80 | (length >> 11) as u8, (length >> 3) as u8, ((length & 0x07) << 5) as u8 | 0x1F, 0xFC];
+    bytes.resize(length, 0);
+    bytes
+}
+
+/// Raw ADTS stream of `frames` frames
+pub fn adts_stream(frames: usize) -> Vec<u8>
+{
+    (0..frames).flat_map(|_| adts_frame(200)).collect()
+}
@@ -0,0 +1,87 @@
+// Process exit codes of the command line tool
+
+use std::{fs, path::PathBuf, process::Command};
+
+/// Write `data` to a file in the temporary directory, named after the test
+fn temp_file(name: &str, data: &[u8]) -> PathBuf
+{
+    let path = std::env::temp_dir().join(format!("the-drill-{}-{}", std::process::id(), name));
+    fs::write(&path, data).expect("temporary file");
+    path
+}
+
+/// Exit code of `the-drill` run with `args`
+fn exit_code(args: &[&str]) -> Option<i32>
+{
+    Command::new(env!("CARGO_BIN_EXE_the-drill")).args(args).output().expect("the-drill runs").status.code()
+}
+
+/// ID3v2.3 tag declaring 1000 bytes, with the file ending after the first frame
+fn truncated_tag() -> Vec<u8>
+{
+    let mut data = b"ID3\x03\x00\x00\x00\x00\x07\x68".to_vec();
+    data.extend(b"TIT2\x00\x00\x00\x06\x00\x00\x00Title");
+    data
+}
+
+#[test]
+fn truncated_tag_is_a_parse_failure()
+{
+    let path = temp_file("truncated.mp3", &truncated_tag());
+
+    let code = exit_code(&["dissect", "--quiet", path.to_str().expect("UTF-8 path")]);
+    let _ = fs::remove_file(&path);
+    assert_eq!(code, Some(3));
+}
+
+#[test]
+fn truncated_tag_is_recovered_with_a_warning()
+{
+    let path = temp_file("truncated-recover.mp3", &truncated_tag());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_the-drill")).args(["dissect", "--recover", path.to_str().expect("UTF-8 path")]).output().expect("the-drill runs");
+    let _ = fs::remove_file(&path);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Value: \"Title\""), "frame that fits is dissected:\n{}", text);
+    assert!(text.contains("Tag size (1000 bytes) exceeds the file, only 16 bytes are present"), "truncation is reported:\n{}", text);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn missing_file_is_an_io_error()
+{
+    let path = std::env::temp_dir().join(format!("the-drill-{}-missing.mp3", std::process::id()));
+    assert_eq!(exit_code(&["dissect", path.to_str().expect("UTF-8 path")]), Some(4));
+}
+
+#[test]
+fn unknown_format_and_usage_error_are_told_apart()
+{
+    let path = temp_file("unknown.bin", &[0x5A; 64]);
+
+    let unknown = exit_code(&["dissect", "--quiet", path.to_str().expect("UTF-8 path")]);
+    let usage = exit_code(&["dissect", "--bogus", path.to_str().expect("UTF-8 path")]);
+    let _ = fs::remove_file(&path);
+    assert_eq!(unknown, Some(2));
+    assert_eq!(usage, Some(6));
+}
+
+#[test]
+fn ffprobe_format_is_rejected_outside_dissect()
+{
+    let path = temp_file("ffprobe.mp3", &truncated_tag());
+
+    for command in ["validate", "summary", "layout", "fragments", "optimize", "find"]
+    {
+        let mut args = vec![command, "--format", "ffprobe", path.to_str().expect("UTF-8 path")];
+        if command == "find"
+        {
+            args.push("TIT2");
+        }
+        let output = Command::new(env!("CARGO_BIN_EXE_the-drill")).args(&args).output().expect("the-drill runs");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("invalid value 'ffprobe'"), "{} rejects ffprobe while parsing the arguments:\n{}", command, stderr);
+        assert_eq!(output.status.code(), Some(6), "{} reports a usage error", command);
+    }
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,52 @@
+// Conversion of ID3v2.3 tags to ID3v2.4 (`tag convert --to 2.4`)
+
+mod common;
+
+use std::io::Cursor;
+
+use common::{frame_v3, tag_v3};
+use the_drill::{
+    Id3v2Tag,
+    id3v2::{frame::Id3v2FrameContent, writer::TagWriter}
+};
+
+#[test]
+fn chapter_sub_frames_get_synchsafe_sizes()
+{
+    // A title of 200 characters makes the TIT2 sub-frame larger than 127 bytes, where plain and synchsafe sizes differ
+    let title = "x".repeat(200);
+    let mut tit2 = vec![0];
+    tit2.extend(title.as_bytes());
+
+    let mut chap = b"ch0\0".to_vec();
+    chap.extend([0, 0, 0, 0, 0, 0, 0x03, 0xE8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    chap.extend(frame_v3("TIT2", &tit2));
+
+    let mut ctoc = b"toc\0".to_vec();
+    ctoc.extend([0x03, 1]);
+    ctoc.extend(b"ch0\0");
+    ctoc.extend(frame_v3("TIT2", &tit2));
+
+    let mut frames = frame_v3("CHAP", &chap);
+    frames.extend(frame_v3("CTOC", &ctoc));
+
+    let mut writer = TagWriter::read(&mut Cursor::new(tag_v3(&frames))).expect("ID3v2.3 tag");
+    writer.convert_to_v2_4().expect("conversion");
+    let converted = writer.render().expect("rendered tag");
+
+    let tag = Id3v2Tag::read(&mut Cursor::new(converted), 0).expect("readable tag").expect("ID3v2 tag");
+    assert_eq!(tag.major_version, 4);
+    for frame in &tag.frames
+    {
+        let sub_frames = match frame.content
+        {
+            | Some(Id3v2FrameContent::Chapter(ref chapter)) => &chapter.sub_frames,
+            | Some(Id3v2FrameContent::TableOfContents(ref toc)) => &toc.sub_frames,
+            | _ => panic!("unexpected frame {}", frame.id)
+        };
+        assert_eq!(sub_frames.len(), 1, "sub-frames of {}", frame.id);
+        assert_eq!(sub_frames[0].size, 201, "TIT2 size in {}", frame.id);
+        assert_eq!(sub_frames[0].get_text(), Some(title.as_str()), "TIT2 text in {}", frame.id);
+    }
+    assert_eq!(tag.frames.len(), 2);
+}
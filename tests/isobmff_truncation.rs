@@ -0,0 +1,81 @@
+// Truncated and malformed ISOBMFF boxes end in a diagnostic, not a panic
+
+mod common;
+
+use std::io::Cursor;
+
+use common::isobmff_box;
+use the_drill::{Diagnostics, DissectOptions, Dissector};
+
+/// File type box of an MP4 file
+fn ftyp() -> Vec<u8>
+{
+    isobmff_box(b"ftyp", b"isom\0\0\x02\0isommp41")
+}
+
+/// Run the text output, the JSON report, the validation and the summary of `bytes`, returning the findings
+fn dissect_all(bytes: Vec<u8>) -> Diagnostics
+{
+    let mut diagnostics = Diagnostics::new();
+    let mut dissector = Dissector::from_source("truncated.mp4", Box::new(Cursor::new(bytes.clone()))).expect("dissector");
+    dissector.print(&DissectOptions::default(), &mut Vec::new(), &mut diagnostics).expect("text output");
+
+    let mut dissector = Dissector::from_source("truncated.mp4", Box::new(Cursor::new(bytes.clone()))).expect("dissector");
+    dissector.report(&DissectOptions::default(), &mut Diagnostics::new()).expect("JSON report");
+
+    let mut dissector = Dissector::from_source("truncated.mp4", Box::new(Cursor::new(bytes.clone()))).expect("dissector");
+    dissector.validate(&mut Diagnostics::new()).expect("validation");
+
+    let mut dissector = Dissector::from_source("truncated.mp4", Box::new(Cursor::new(bytes))).expect("dissector");
+    dissector.summary().expect("summary");
+
+    diagnostics
+}
+
+#[test]
+fn file_cut_inside_the_track_header_is_diagnosed()
+{
+    // Version 0 track header: 84 bytes of content, the file ends 6 bytes early (inside the width)
+    let mut tkhd = isobmff_box(b"tkhd", &[0; 84]);
+    tkhd[11] = 0x03;
+    let moov = isobmff_box(b"moov", &isobmff_box(b"trak", &tkhd));
+    let mut bytes = ftyp();
+    bytes.extend(moov);
+    bytes.truncate(bytes.len() - 6);
+
+    let diagnostics = dissect_all(bytes);
+
+    let messages: Vec<&str> = diagnostics.entries().iter().map(|diagnostic| diagnostic.message.as_str()).collect();
+    assert!(messages.iter().any(|message| message.contains("cut off inside moov/trak/tkhd")), "{:?}", messages);
+}
+
+#[test]
+fn meta_header_running_past_a_short_user_data_box_is_diagnosed()
+{
+    // A udta declaring 14 bytes leaves 6 bytes for the 8-byte header of its meta box
+    let mut udta = isobmff_box(b"udta", b"\0\0\0\x20meta");
+    udta[3] = 14;
+    let mut bytes = ftyp();
+    bytes.extend(isobmff_box(b"moov", &udta));
+
+    let diagnostics = dissect_all(bytes);
+
+    let messages: Vec<&str> = diagnostics.entries().iter().map(|diagnostic| diagnostic.message.as_str()).collect();
+    assert!(messages.iter().any(|message| message.contains("Incomplete box header")), "{:?}", messages);
+}
+
+#[test]
+fn data_appended_after_the_last_box_stays_uncovered()
+{
+    let mut bytes = ftyp();
+    bytes.extend(isobmff_box(b"free", &[0; 8]));
+    let end = bytes.len() as u64;
+    bytes.extend(b"EXTRA DATA AT THE END");
+
+    let mut dissector = Dissector::from_source("appended.mp4", Box::new(Cursor::new(bytes.clone()))).expect("dissector");
+    assert_eq!(dissector.gaps().expect("gaps"), vec![(end, end + 21)]);
+
+    let diagnostics = dissect_all(bytes);
+    let messages: Vec<&str> = diagnostics.entries().iter().map(|diagnostic| diagnostic.message.as_str()).collect();
+    assert!(messages.contains(&"21 bytes of unrecognized data after the last box"), "{:?}", messages);
+}
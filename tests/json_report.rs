@@ -0,0 +1,70 @@
+// JSON report fields of ISOBMFF boxes and ID3v2 frames
+
+mod common;
+
+use std::io::Cursor;
+
+use common::{frame_v3, isobmff_box, tag_v3};
+use the_drill::{Diagnostics, DissectOptions, Dissector};
+
+/// JSON report of `bytes` dissected as `name`
+fn report(name: &str, bytes: Vec<u8>, options: &DissectOptions) -> serde_json::Value
+{
+    let mut dissector = Dissector::from_source(name, Box::new(Cursor::new(bytes))).expect("dissector");
+    dissector.report(options, &mut Diagnostics::new()).expect("JSON report")
+}
+
+/// MP4 file with a movie header of timescale 600 and duration 1200
+fn movie() -> Vec<u8>
+{
+    let mut mvhd = vec![0; 100];
+    mvhd[12..16].copy_from_slice(&600u32.to_be_bytes());
+    mvhd[16..20].copy_from_slice(&1200u32.to_be_bytes());
+    mvhd[20..24].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+    mvhd[24..26].copy_from_slice(&0x0100u16.to_be_bytes());
+    let mut bytes = isobmff_box(b"ftyp", b"isom\0\0\x02\0isommp41");
+    bytes.extend(isobmff_box(b"moov", &isobmff_box(b"mvhd", &mvhd)));
+    bytes
+}
+
+#[test]
+fn boxes_report_their_content_as_fields()
+{
+    let report = report("movie.mp4", movie(), &DissectOptions::default());
+    let boxes = &report["layers"][0]["content"]["boxes"];
+
+    assert_eq!(boxes[0]["fields"]["major_brand"], "isom");
+    assert_eq!(boxes[0]["fields"]["compatible_brands"], serde_json::json!(["isom", "mp41"]));
+    let mvhd = &boxes[1]["children"][0];
+    assert_eq!(mvhd["fields"]["timescale"], 600);
+    assert_eq!(mvhd["fields"]["duration"], 1200);
+    assert_eq!(mvhd["fields"]["rate"], 1.0);
+    assert!(boxes[1]["fields"].is_null());
+    assert!(mvhd.get("details").is_none());
+}
+
+#[test]
+fn frames_report_their_content_as_fields()
+{
+    let mut comment = b"\0eng\0".to_vec();
+    comment.extend(b"Liner notes");
+    let tag = tag_v3(&[frame_v3("TIT2", b"\0Title"), frame_v3("COMM", &comment)].concat());
+    let report = report("song.mp3", tag, &DissectOptions::default());
+    let frames = &report["layers"][0]["content"]["frames"];
+
+    assert_eq!(frames[0]["fields"], serde_json::json!({ "encoding": 0, "text": "Title", "strings": ["Title"] }));
+    assert_eq!(frames[1]["fields"]["language"], "eng");
+    assert_eq!(frames[1]["fields"]["text"], "Liner notes");
+    assert!(frames[0].get("details").is_none());
+}
+
+#[test]
+fn details_add_the_text_output_lines()
+{
+    let options = DissectOptions { show_details: true, ..DissectOptions::default() };
+    let report = report("movie.mp4", movie(), &options);
+    let mvhd = &report["layers"][0]["content"]["boxes"][1]["children"][0];
+
+    assert_eq!(mvhd["fields"]["timescale"], 600);
+    assert!(mvhd["details"].as_array().is_some_and(|lines| lines.iter().any(|line| line == "Timescale: 600 units/second")));
+}
@@ -0,0 +1,29 @@
+// Format detection by scored sniffing
+
+mod common;
+
+use std::io::Cursor;
+
+use common::adts_stream;
+use the_drill::{Diagnostics, DissectOptions, Dissector, DissectorRegistry};
+
+#[test]
+fn raw_adts_stream_is_aac()
+{
+    let dissector = DissectorRegistry::new().build_for_payload(&adts_stream(8)).expect("a dissector recognizes ADTS");
+    assert_eq!(dissector.media_type(), "AAC");
+}
+
+#[test]
+fn adts_stream_behind_an_id3v2_tag_is_aac()
+{
+    let mut data = b"ID3\x03\x00\x00\x00\x00\x00\x10TIT2\x00\x00\x00\x06\x00\x00\x00Title".to_vec();
+    data.extend(adts_stream(8));
+
+    let mut dissector = Dissector::from_source("tagged.aac", Box::new(Cursor::new(data))).expect("source opens");
+    let report = dissector.report(&DissectOptions::default(), &mut Diagnostics::new()).expect("file is dissected");
+    let formats: Vec<&str> = report["layers"].as_array().expect("layers").iter().map(|layer| layer["format"].as_str().unwrap_or_default()).collect();
+    assert_eq!(formats, ["ID3v2.3", "AAC"]);
+    assert_eq!(report["layers"][1]["offset"], 26);
+    assert_eq!(report["layers"][1]["content"]["frames"], 8);
+}
@@ -0,0 +1,27 @@
+// Editing tags through the tag command
+
+use std::{fs, process::Command};
+
+/// Text output of `the-drill tag remove` on an ID3v2.3 tag holding one TIT2 frame, run twice
+fn remove_title_twice() -> (String, String)
+{
+    let path = std::env::temp_dir().join(format!("the-drill-{}-remove.mp3", std::process::id()));
+    fs::write(&path, b"ID3\x03\x00\x00\x00\x00\x00\x10TIT2\x00\x00\x00\x06\x00\x00\x00Title").expect("temporary file");
+
+    let run = || {
+        let output =
+            Command::new(env!("CARGO_BIN_EXE_the-drill")).args(["tag", "remove", path.to_str().expect("UTF-8 path"), "TIT2"]).output().expect("the-drill runs");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let outputs = (run(), run());
+    let _ = fs::remove_file(&path);
+    outputs
+}
+
+#[test]
+fn removed_frames_are_counted_in_the_singular()
+{
+    let (removed, not_found) = remove_title_twice();
+    assert!(removed.contains("Removed TIT2: 1 frame\n"), "{}", removed);
+    assert!(not_found.contains("No TIT2 frame to remove"), "{}", not_found);
+}
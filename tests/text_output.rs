@@ -0,0 +1,30 @@
+// Text output written to the writer passed to `Dissector::print`
+
+mod common;
+
+use std::io::Cursor;
+
+use common::{frame_v3, tag_v3};
+use the_drill::{Diagnostics, DissectOptions, Dissector};
+
+#[test]
+fn chapter_frames_are_printed_to_the_writer()
+{
+    let mut chap = b"ch0\0".to_vec();
+    chap.extend([0, 0, 0, 0, 0, 0, 0x03, 0xE8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    chap.extend(frame_v3("TIT2", b"\0Chapter One"));
+
+    let mut frames = frame_v3("TIT2", b"\0Episode");
+    frames.extend(frame_v3("CHAP", &chap));
+
+    let source = Box::new(Cursor::new(tag_v3(&frames)));
+    let mut dissector = Dissector::from_source("episode.mp3", source).expect("dissector");
+    let mut output = Vec::new();
+    dissector.print(&DissectOptions::default(), &mut output, &mut Diagnostics::new()).expect("dissection");
+
+    let text = String::from_utf8(output).expect("UTF-8 output");
+    let headers: Vec<&str> = text.lines().filter(|line| line.contains("Frame offset")).collect();
+    assert!(headers.iter().any(|line| line.contains("\"CHAP\"")), "CHAP header missing from:\n{}", text);
+    assert_eq!(headers.iter().filter(|line| line.contains("\"TIT2\"")).count(), 2, "TIT2 frame and sub-frame headers missing from:\n{}", text);
+    assert!(text.contains("Chapter One"), "sub-frame content missing from:\n{}", text);
+}
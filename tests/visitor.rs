@@ -0,0 +1,70 @@
+// Dissection events sent to a `DissectionVisitor`
+
+mod common;
+
+use std::io::Cursor;
+
+use common::adts_stream;
+use the_drill::{Diagnostics, DissectOptions, DissectionVisitor, Dissector, diagnostics::Diagnostic};
+
+/// Visitor recording the findings and the text output it receives
+#[derive(Default)]
+struct Recorder
+{
+    warnings: Vec<String>,
+    errors:   Vec<String>,
+    text:     Vec<u8>
+}
+
+impl DissectionVisitor for Recorder
+{
+    fn on_warning(&mut self, diagnostic: &Diagnostic)
+    {
+        self.warnings.push(diagnostic.message.clone());
+    }
+
+    fn on_error(&mut self, diagnostic: &Diagnostic)
+    {
+        self.errors.push(diagnostic.message.clone());
+    }
+
+    fn on_text(&mut self, text: &[u8])
+    {
+        self.text.extend(text);
+    }
+}
+
+/// ADTS stream whose last frame is cut off
+fn truncated_adts() -> Vec<u8>
+{
+    let mut bytes = adts_stream(4);
+    bytes.truncate(bytes.len() - 100);
+    bytes
+}
+
+#[test]
+fn findings_of_formats_without_a_structure_walk_are_visited()
+{
+    let mut dissector = Dissector::from_source("cut.aac", Box::new(Cursor::new(truncated_adts()))).expect("dissector");
+    let mut recorder = Recorder::default();
+    dissector.visit(&mut recorder).expect("visit");
+
+    assert!(recorder.warnings.iter().any(|message| message.contains("ADTS frame truncated")), "{:?}", recorder.warnings);
+    assert!(recorder.text.is_empty());
+}
+
+#[test]
+fn text_output_is_sent_as_events()
+{
+    let mut dissector = Dissector::from_source("cut.aac", Box::new(Cursor::new(truncated_adts()))).expect("dissector");
+    let mut output = Vec::new();
+    dissector.print(&DissectOptions::default(), &mut output, &mut Diagnostics::new()).expect("text output");
+
+    let mut dissector = Dissector::from_source("cut.aac", Box::new(Cursor::new(truncated_adts()))).expect("dissector");
+    let mut recorder = Recorder::default();
+    let mut diagnostics = Diagnostics::new();
+    dissector.visit_text(&DissectOptions::default(), &mut recorder, &mut diagnostics).expect("visit");
+
+    assert_eq!(String::from_utf8(recorder.text).expect("UTF-8 output"), String::from_utf8(output).expect("UTF-8 output"));
+    assert_eq!(recorder.warnings.len() + recorder.errors.len(), diagnostics.entries().len());
+}